@@ -0,0 +1,240 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline Bundles
+//!
+//! A bundle carries the events after a cut, the blobs they need and the
+//! exporter's heads from one node to another without a network: it is
+//! written to a file, carried across, and imported on the other side.
+//!
+//! The file is the canonical CBOR encoding of a [`Bundle`]. Every event is
+//! re-validated when it is decoded, and one or more agents attest to the
+//! bundle by signing its [`Bundle::statement`], which commits to the full
+//! events (their authors and signatures too, which event ids leave out),
+//! the blob hashes, the events the importer must already hold, and the
+//! heads. Import refuses a bundle unless every attestation verifies, then
+//! appends the events to a copy of the store, so a bundle is imported
+//! completely or not at all.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use jitos_core::blob::{blob_hash, BlobStore};
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::events::{AgentId, EventEnvelope, EventError, EventId, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_net::{PeerSigner, PeerVerifier};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Format tag of bundle files, and domain separator of their statements.
+pub const BUNDLE_V1: &str = "loom-bundle-v1";
+
+/// Errors exporting, loading or importing a bundle.
+#[derive(Debug, Error)]
+pub enum BundleError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("cut {cut} exceeds event sequence length {len}")]
+    CutOutOfBounds { cut: usize, len: usize },
+    #[error("unsupported bundle format {0}")]
+    Version(String),
+    #[error("bundle carries no head attestation")]
+    Unattested,
+    #[error("attestation by {} does not verify", .0.as_str())]
+    BadAttestation(AgentId),
+    #[error("bundle requires event {0}, which the store does not hold")]
+    MissingBase(EventId),
+    #[error("bundled event {index} rejected: {source}")]
+    InvalidEvent { index: usize, source: EventError },
+    #[error("attested head {0} is neither bundled nor in the store")]
+    UnknownHead(EventId),
+}
+
+/// An agent's signature over a bundle's [`Bundle::statement`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeadAttestation {
+    pub agent: AgentId,
+    pub signature: Signature,
+}
+
+/// Events, blobs and heads to carry to another node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bundle {
+    /// Always [`BUNDLE_V1`].
+    pub version: String,
+    /// Events the importer must already hold: parents of bundled events, and
+    /// heads, that are not bundled themselves, sorted.
+    pub requires: Vec<EventId>,
+    /// Events after the cut, in worldline order.
+    pub events: Vec<EventEnvelope>,
+    /// Blobs, sorted by [`blob_hash`]. Their names are recomputed on import.
+    pub blobs: Vec<Vec<u8>>,
+    /// The exporter's heads when the bundle was made.
+    pub heads: Vec<EventId>,
+    pub attestations: Vec<HeadAttestation>,
+}
+
+/// What an import added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Events appended to the store.
+    pub added: usize,
+    /// Bundled events the store already held.
+    pub skipped: usize,
+    /// Blobs put into the blob store.
+    pub blobs: usize,
+    /// Agents whose attestations verified.
+    pub attested_by: Vec<AgentId>,
+}
+
+impl Bundle {
+    /// What attesting agents sign: a hash over the format tag, the required
+    /// events, the bundled event envelopes, the blob hashes and the heads.
+    ///
+    /// Envelopes rather than ids, so an attestation also covers each event's
+    /// `agent_id` and `signature`.
+    pub fn statement(&self) -> Result<Hash, CanonicalError> {
+        let blobs: Vec<Hash> = self.blobs.iter().map(|b| blob_hash(b)).collect();
+        canonical::hash_canonical(&(BUNDLE_V1, &self.requires, &self.events, blobs, &self.heads))
+    }
+
+    /// Add `signer`'s attestation.
+    pub fn attest(&mut self, signer: &dyn PeerSigner) -> Result<(), CanonicalError> {
+        let signature = signer.sign(&self.statement()?);
+        self.attestations.push(HeadAttestation {
+            agent: signer.agent().clone(),
+            signature,
+        });
+        Ok(())
+    }
+}
+
+/// Bundle `store`'s events after the first `since`, with `blobs`, attested by
+/// `signer`.
+///
+/// # Errors
+///
+/// Fails if `since` exceeds the store's length.
+pub fn export(
+    store: &MemoryEventStore,
+    since: usize,
+    blobs: impl IntoIterator<Item = Vec<u8>>,
+    signer: &dyn PeerSigner,
+) -> Result<Bundle, BundleError> {
+    let all = store.events();
+    let events = all.get(since..).ok_or(BundleError::CutOutOfBounds {
+        cut: since,
+        len: all.len(),
+    })?;
+    let heads = store.heads();
+    let bundled: BTreeSet<EventId> = events.iter().map(EventEnvelope::event_id).collect();
+    let requires: BTreeSet<EventId> = events
+        .iter()
+        .flat_map(|e| e.parents().iter().copied())
+        .chain(heads.iter().copied())
+        .filter(|id| !bundled.contains(id))
+        .collect();
+    let mut blobs: Vec<Vec<u8>> = blobs.into_iter().collect();
+    blobs.sort_by_key(|b| blob_hash(b));
+    blobs.dedup();
+
+    let mut bundle = Bundle {
+        version: BUNDLE_V1.to_string(),
+        requires: requires.into_iter().collect(),
+        events: events.to_vec(),
+        blobs,
+        heads,
+        attestations: Vec::new(),
+    };
+    bundle.attest(signer)?;
+    Ok(bundle)
+}
+
+/// Verify `bundle` and add its events to `store` and its blobs to `blobs`.
+///
+/// Every attestation must verify under `verifier`. The events are appended
+/// with [`MemoryEventStore::append`] to a copy of `store`, which replaces
+/// `store` only if all of them are accepted and every head is known.
+///
+/// # Errors
+///
+/// Fails, leaving `store` and `blobs` untouched, on any check above.
+pub fn import(
+    store: &mut MemoryEventStore,
+    blobs: &mut impl BlobStore,
+    bundle: Bundle,
+    verifier: &dyn PeerVerifier,
+) -> Result<ImportReport, BundleError> {
+    if bundle.version != BUNDLE_V1 {
+        return Err(BundleError::Version(bundle.version));
+    }
+    if bundle.attestations.is_empty() {
+        return Err(BundleError::Unattested);
+    }
+    let statement = bundle.statement()?;
+    for attestation in &bundle.attestations {
+        if !verifier.verify(&attestation.agent, &statement, &attestation.signature) {
+            return Err(BundleError::BadAttestation(attestation.agent.clone()));
+        }
+    }
+    if let Some(missing) = bundle
+        .requires
+        .iter()
+        .find(|id| store.position(id).is_none())
+    {
+        return Err(BundleError::MissingBase(*missing));
+    }
+
+    let mut updated = store.clone();
+    let mut skipped = 0;
+    for (index, event) in bundle.events.into_iter().enumerate() {
+        if updated.position(&event.event_id()).is_some() {
+            skipped += 1;
+            continue;
+        }
+        updated
+            .append(event)
+            .map_err(|source| BundleError::InvalidEvent { index, source })?;
+    }
+    if let Some(head) = bundle.heads.iter().find(|h| updated.position(h).is_none()) {
+        return Err(BundleError::UnknownHead(*head));
+    }
+
+    let added = updated.len() - store.len();
+    *store = updated;
+    let blob_count = bundle.blobs.len();
+    for blob in bundle.blobs {
+        blobs.put(blob);
+    }
+    Ok(ImportReport {
+        added,
+        skipped,
+        blobs: blob_count,
+        attested_by: bundle.attestations.into_iter().map(|a| a.agent).collect(),
+    })
+}
+
+/// Encode a bundle as a bundle file body.
+pub fn encode(bundle: &Bundle) -> Result<Vec<u8>, BundleError> {
+    Ok(canonical::encode(bundle)?)
+}
+
+/// Decode a bundle file body, validating every event.
+pub fn decode(bytes: &[u8]) -> Result<Bundle, BundleError> {
+    Ok(canonical::decode(bytes)?)
+}
+
+/// Read a bundle file.
+pub fn load(path: &Path) -> Result<Bundle, BundleError> {
+    decode(&std::fs::read(path)?)
+}
+
+/// Write a bundle file.
+pub fn save(path: &Path, bundle: &Bundle) -> Result<(), BundleError> {
+    std::fs::write(path, encode(bundle)?)?;
+    Ok(())
+}
@@ -0,0 +1,155 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! CBOR Diagnostics
+//!
+//! `jitos cbor diag`: prints a CBOR value in diagnostic notation (RFC 8949
+//! §8), checks it against the canonical rules of SPEC-0001 with
+//! [`canonical::check`], and can re-canonicalize it.
+//!
+//! The value is read with a lenient decoder, so non-canonical input (unsorted
+//! maps, over-wide integers, half-precision floats, indefinite lengths) is
+//! still shown. Only input that is not CBOR at all has no diagnostic form.
+
+use std::fmt;
+
+use ciborium::value::Value;
+use jitos_core::canonical::{self, CanonicalError, Violation};
+use thiserror::Error;
+
+/// CBOR diagnostic errors.
+#[derive(Debug, Error)]
+pub enum CborError {
+    #[error("`{0}` is neither a readable file nor hex")]
+    Input(String),
+    #[error("not CBOR: {0}")]
+    NotCbor(String),
+    #[error("cannot be made canonical: {0}")]
+    Unfixable(CanonicalError),
+}
+
+/// Bytes from a file path, or else from hex (whitespace and a `0x` prefix
+/// are ignored).
+///
+/// # Errors
+///
+/// [`CborError::Input`] if `arg` is neither.
+pub fn input(arg: &str) -> Result<Vec<u8>, CborError> {
+    if let Ok(bytes) = std::fs::read(arg) {
+        return Ok(bytes);
+    }
+    let digits: String = arg.split_whitespace().collect();
+    let digits = digits.strip_prefix("0x").unwrap_or(&digits);
+    hex::decode(digits).map_err(|_| CborError::Input(arg.to_string()))
+}
+
+/// Everything `jitos cbor diag` reports about one input.
+#[derive(Debug, PartialEq)]
+pub struct Diagnosis {
+    pub len: usize,
+    /// The value as read by the lenient decoder.
+    pub value: Value,
+    /// First canonical rule the input breaks, if any.
+    pub violation: Option<Violation>,
+}
+
+impl Diagnosis {
+    pub fn is_canonical(&self) -> bool {
+        self.violation.is_none()
+    }
+
+    /// The value in canonical encoding.
+    ///
+    /// # Errors
+    ///
+    /// [`CborError::Unfixable`] for values with no canonical form (tags,
+    /// duplicate map keys, unsupported simple values).
+    pub fn fix(&self) -> Result<Vec<u8>, CborError> {
+        canonical::encode(&self.value).map_err(CborError::Unfixable)
+    }
+}
+
+/// Decode `bytes` leniently and check them against the canonical rules.
+///
+/// # Errors
+///
+/// [`CborError::NotCbor`] if `bytes` do not start with a CBOR value.
+pub fn diagnose(bytes: &[u8]) -> Result<Diagnosis, CborError> {
+    let violation = canonical::check(bytes).err();
+    let value: Value = ciborium::de::from_reader(bytes).map_err(|e| {
+        // The canonical decoder locates the problem; prefer its report.
+        CborError::NotCbor(match &violation {
+            Some(violation) => violation.to_string(),
+            None => e.to_string(),
+        })
+    })?;
+    Ok(Diagnosis {
+        len: bytes.len(),
+        value,
+        violation,
+    })
+}
+
+/// A value in pretty-printed diagnostic notation.
+#[derive(Debug, Clone, Copy)]
+pub struct Diag<'a>(pub &'a Value);
+
+impl fmt::Display for Diag<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_diag(f, self.0, 0)
+    }
+}
+
+fn write_diag(f: &mut fmt::Formatter<'_>, value: &Value, depth: usize) -> fmt::Result {
+    let pad = "  ".repeat(depth + 1);
+    match value {
+        Value::Integer(n) => write!(f, "{}", i128::from(*n)),
+        Value::Bytes(bytes) => write!(f, "h'{}'", hex::encode(bytes)),
+        Value::Float(x) if x.is_nan() => write!(f, "NaN"),
+        Value::Float(x) if x.is_infinite() => {
+            write!(f, "{}Infinity", if *x < 0.0 { "-" } else { "" })
+        }
+        Value::Float(x) => write!(f, "{x:?}"),
+        Value::Text(text) => write!(f, "{text:?}"),
+        Value::Bool(b) => write!(f, "{b}"),
+        Value::Null => write!(f, "null"),
+        Value::Tag(tag, inner) => {
+            write!(f, "{tag}(")?;
+            write_diag(f, inner, depth)?;
+            write!(f, ")")
+        }
+        Value::Array(items) if items.is_empty() => write!(f, "[]"),
+        Value::Array(items) => {
+            writeln!(f, "[")?;
+            for (i, item) in items.iter().enumerate() {
+                write!(f, "{pad}")?;
+                write_diag(f, item, depth + 1)?;
+                writeln!(f, "{}", if i + 1 < items.len() { "," } else { "" })?;
+            }
+            write!(f, "{}]", &pad[2..])
+        }
+        Value::Map(entries) if entries.is_empty() => write!(f, "{{}}"),
+        Value::Map(entries) => {
+            writeln!(f, "{{")?;
+            for (i, (key, item)) in entries.iter().enumerate() {
+                write!(f, "{pad}")?;
+                write_diag(f, key, depth + 1)?;
+                write!(f, ": ")?;
+                write_diag(f, item, depth + 1)?;
+                writeln!(f, "{}", if i + 1 < entries.len() { "," } else { "" })?;
+            }
+            write!(f, "{}}}", &pad[2..])
+        }
+        other => write!(f, "{other:?}"),
+    }
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", Diag(&self.value))?;
+        match &self.violation {
+            None => writeln!(f, "ok: {} bytes of canonical CBOR", self.len),
+            Some(violation) => writeln!(f, "non-canonical: {violation}"),
+        }
+    }
+}
@@ -0,0 +1,413 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Payload Codec Generation
+//!
+//! `jitos codegen` turns the payload schema registry
+//! ([`jitos_views::payload_schemas`]) into a JavaScript module of canonical
+//! CBOR codecs plus TypeScript declarations for it, so JS producers write
+//! observation and decision payloads byte-identical to the Rust canonical
+//! encoder (SPEC-0001).
+//!
+//! The codecs are plain ES module JavaScript (`loom-payloads.js`) with types
+//! in `loom-payloads.d.ts`, so they run in Node or a browser without a
+//! TypeScript build step. Each payload type gets an interface, an
+//! `encode<Type>` and a `decode<Type>`; observation types also get their type
+//! tag and an entry in `OBSERVATION_CODECS`.
+//!
+//! Wire shapes follow serde under the canonical encoder: a struct is a map
+//! keyed by field name in canonical key order (fixed here, at generation
+//! time), `u64` is a shortest-form integer (a `bigint` in JS), and `Vec<u8>`
+//! and `Hash` are arrays of small integers, not byte strings. Decoders accept
+//! only canonical bytes with exactly the schema's fields.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use jitos_views::{EnumSchema, FieldType, PayloadKind, PayloadSchema};
+
+/// File name of the generated JavaScript module.
+pub const JS_FILE: &str = "loom-payloads.js";
+
+/// File name of the generated TypeScript declarations.
+pub const DTS_FILE: &str = "loom-payloads.d.ts";
+
+const HEADER: &str = "\
+// Generated by `jitos codegen` from the jitos-views payload schema registry.
+// Do not edit; regenerate instead.
+";
+
+/// The canonical CBOR reader and writers every generated codec uses.
+const RUNTIME: &str = r#"
+const textEncoder = new TextEncoder();
+const textDecoder = new TextDecoder("utf-8", { fatal: true });
+const U64_MAX = 0xffffffffffffffffn;
+
+/** Bytes that are not the canonical encoding of the expected payload. */
+export class CanonicalError extends Error {
+  constructor(message, offset) {
+    super(`${message} at byte ${offset}`);
+    this.name = "CanonicalError";
+    this.offset = offset;
+  }
+}
+
+function writeHead(out, major, n) {
+  if (n < 24n) {
+    out.push((major << 5) | Number(n));
+    return;
+  }
+  const [ai, width] =
+    n < 0x100n ? [24, 1] : n < 0x10000n ? [25, 2] : n < 0x100000000n ? [26, 4] : [27, 8];
+  out.push((major << 5) | ai);
+  for (let i = width - 1; i >= 0; i--) {
+    out.push(Number((n >> BigInt(8 * i)) & 0xffn));
+  }
+}
+
+function writeU64(out, value, path) {
+  if (typeof value === "number" && Number.isSafeInteger(value)) {
+    value = BigInt(value);
+  }
+  if (typeof value !== "bigint" || value < 0n || value > U64_MAX) {
+    throw new TypeError(`${path}: expected an unsigned 64-bit integer`);
+  }
+  writeHead(out, 0, value);
+}
+
+function writeText(out, value, path) {
+  if (typeof value !== "string") {
+    throw new TypeError(`${path}: expected a string`);
+  }
+  const bytes = textEncoder.encode(value);
+  writeHead(out, 3, BigInt(bytes.length));
+  for (const b of bytes) out.push(b);
+}
+
+function writeAgentId(out, value, path) {
+  if (value === "") {
+    throw new TypeError(`${path}: agent id must not be empty`);
+  }
+  writeText(out, value, path);
+}
+
+function writeBytes(out, value, path, length) {
+  if (!(value instanceof Uint8Array)) {
+    throw new TypeError(`${path}: expected a Uint8Array`);
+  }
+  if (length !== undefined && value.length !== length) {
+    throw new TypeError(`${path}: expected ${length} bytes`);
+  }
+  writeHead(out, 4, BigInt(value.length));
+  for (const b of value) writeHead(out, 0, BigInt(b));
+}
+
+function writeEnum(out, value, variants, path) {
+  if (!variants.includes(value)) {
+    throw new TypeError(`${path}: expected one of ${variants.join(", ")}`);
+  }
+  writeText(out, value, path);
+}
+
+class Reader {
+  constructor(bytes) {
+    if (!(bytes instanceof Uint8Array)) {
+      throw new TypeError("expected a Uint8Array");
+    }
+    this.bytes = bytes;
+    this.pos = 0;
+  }
+
+  head(major, what) {
+    const at = this.pos;
+    if (at >= this.bytes.length) throw new CanonicalError("incomplete", at);
+    const initial = this.bytes[this.pos++];
+    if (initial >> 5 !== major) throw new CanonicalError(`expected ${what}`, at);
+    const ai = initial & 0x1f;
+    if (ai < 24) return BigInt(ai);
+    if (ai > 27) throw new CanonicalError("indefinite length or reserved value", at);
+    const width = 1 << (ai - 24);
+    if (this.pos + width > this.bytes.length) throw new CanonicalError("incomplete", at);
+    let n = 0n;
+    for (let i = 0; i < width; i++) n = (n << 8n) | BigInt(this.bytes[this.pos++]);
+    const min = ai === 24 ? 24n : 1n << BigInt(4 * width);
+    if (n < min) throw new CanonicalError("non-canonical integer width", at);
+    return n;
+  }
+
+  u64() {
+    return this.head(0, "an unsigned integer");
+  }
+
+  text() {
+    const at = this.pos;
+    const length = this.head(3, "text");
+    if (length > BigInt(this.bytes.length - this.pos)) throw new CanonicalError("incomplete", at);
+    const bytes = this.bytes.subarray(this.pos, this.pos + Number(length));
+    this.pos += bytes.length;
+    try {
+      return textDecoder.decode(bytes);
+    } catch {
+      throw new CanonicalError("invalid UTF-8", at);
+    }
+  }
+
+  agentId() {
+    const at = this.pos;
+    const id = this.text();
+    if (id === "") throw new CanonicalError("empty agent id", at);
+    return id;
+  }
+
+  bytesArray(length) {
+    const at = this.pos;
+    const n = this.head(4, "an array of bytes");
+    if (length !== undefined && n !== BigInt(length)) {
+      throw new CanonicalError(`expected ${length} bytes`, at);
+    }
+    if (n > BigInt(this.bytes.length - this.pos)) throw new CanonicalError("incomplete", at);
+    const out = new Uint8Array(Number(n));
+    for (let i = 0; i < out.length; i++) {
+      const itemAt = this.pos;
+      const b = this.u64();
+      if (b > 0xffn) throw new CanonicalError("byte out of range", itemAt);
+      out[i] = Number(b);
+    }
+    return out;
+  }
+
+  enumeration(variants) {
+    const at = this.pos;
+    const value = this.text();
+    if (!variants.includes(value)) throw new CanonicalError(`unknown variant ${value}`, at);
+    return value;
+  }
+
+  map(fields) {
+    const at = this.pos;
+    if (this.head(5, "a map") !== BigInt(fields)) {
+      throw new CanonicalError(`expected ${fields} fields`, at);
+    }
+  }
+
+  key(name) {
+    const at = this.pos;
+    if (this.text() !== name) throw new CanonicalError(`expected field ${name}`, at);
+  }
+
+  end() {
+    if (this.pos !== this.bytes.length) throw new CanonicalError("trailing bytes", this.pos);
+  }
+}
+"#;
+
+const RUNTIME_DECLARATIONS: &str = r#"
+/** Bytes that are not the canonical encoding of the expected payload. */
+export declare class CanonicalError extends Error {
+  /** Offset of the offending item. */
+  readonly offset: number;
+}
+
+/** Canonical CBOR codec of one payload type. */
+export interface Codec<T> {
+  encode(value: T): Uint8Array;
+  decode(bytes: Uint8Array): T;
+}
+"#;
+
+/// Generated codec module and its declarations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    /// Contents of [`JS_FILE`].
+    pub javascript: String,
+    /// Contents of [`DTS_FILE`].
+    pub declarations: String,
+}
+
+/// Generate codecs and declarations for `schemas`.
+pub fn generate(schemas: &[PayloadSchema]) -> Generated {
+    let mut js = String::from(HEADER);
+    let mut dts = String::from(HEADER);
+    js.push_str(RUNTIME);
+    dts.push_str(RUNTIME_DECLARATIONS);
+
+    for schema in enums(schemas) {
+        let constant = screaming_snake(schema.name);
+        let variants: Vec<_> = schema.variants.iter().map(|v| format!("{v:?}")).collect();
+        let _ = writeln!(
+            js,
+            "\nexport const {constant} = Object.freeze([{}]);",
+            variants.join(", ")
+        );
+        let _ = writeln!(
+            dts,
+            "\nexport type {} = {};\nexport declare const {constant}: readonly {}[];",
+            schema.name,
+            variants.join(" | "),
+            schema.name
+        );
+    }
+
+    for schema in schemas {
+        payload_js(&mut js, schema);
+        payload_dts(&mut dts, schema);
+    }
+
+    let observations: Vec<_> = schemas
+        .iter()
+        .filter_map(|s| s.observation_type.map(|tag| (tag, s.name)))
+        .collect();
+    js.push_str("\n/** Codecs by observation type tag. */\nexport const OBSERVATION_CODECS = Object.freeze({\n");
+    dts.push_str(
+        "\n/** Codecs by observation type tag. */\nexport declare const OBSERVATION_CODECS: {\n",
+    );
+    for (tag, name) in observations {
+        let _ = writeln!(
+            js,
+            "  [{tag}]: Object.freeze({{ encode: encode{name}, decode: decode{name} }}),"
+        );
+        let _ = writeln!(dts, "  readonly {tag}: Codec<{name}>;");
+    }
+    js.push_str("});\n");
+    dts.push_str("};\n");
+
+    Generated {
+        javascript: js,
+        declarations: dts,
+    }
+}
+
+/// Write [`JS_FILE`] and [`DTS_FILE`] for `schemas` into `dir`, creating it
+/// if needed. Returns the paths written.
+pub fn write(dir: &Path, schemas: &[PayloadSchema]) -> std::io::Result<Vec<PathBuf>> {
+    let generated = generate(schemas);
+    std::fs::create_dir_all(dir)?;
+    let files = [
+        (dir.join(JS_FILE), generated.javascript),
+        (dir.join(DTS_FILE), generated.declarations),
+    ];
+    let mut written = Vec::new();
+    for (path, contents) in files {
+        std::fs::write(&path, contents)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Enums used by `schemas`, each once, in order of first use.
+fn enums(schemas: &[PayloadSchema]) -> Vec<&'static EnumSchema> {
+    let mut found: Vec<&'static EnumSchema> = Vec::new();
+    for field in schemas.iter().flat_map(|s| s.fields) {
+        if let FieldType::Enum(schema) = field.ty {
+            if !found.iter().any(|f| f.name == schema.name) {
+                found.push(schema);
+            }
+        }
+    }
+    found
+}
+
+fn payload_js(js: &mut String, schema: &PayloadSchema) {
+    let name = schema.name;
+    let fields = schema.canonical_fields();
+    if let Some(tag) = schema.observation_type {
+        let _ = writeln!(js, "\nexport const {tag} = {tag:?};");
+    }
+
+    let _ = writeln!(js, "\nexport function encode{name}(value) {{");
+    let _ = writeln!(js, "  const out = [];");
+    let _ = writeln!(js, "  writeHead(out, 5, {}n);", fields.len());
+    for field in &fields {
+        let path = format!("\"{name}.{}\"", field.name);
+        let value = format!("value.{}", field.name);
+        let _ = writeln!(js, "  writeText(out, {:?}, {path});", field.name);
+        let write = match field.ty {
+            FieldType::U64 => format!("writeU64(out, {value}, {path})"),
+            FieldType::Text => format!("writeText(out, {value}, {path})"),
+            FieldType::AgentId => format!("writeAgentId(out, {value}, {path})"),
+            FieldType::Bytes => format!("writeBytes(out, {value}, {path})"),
+            FieldType::Hash => format!("writeBytes(out, {value}, {path}, 32)"),
+            FieldType::Enum(e) => {
+                format!(
+                    "writeEnum(out, {value}, {}, {path})",
+                    screaming_snake(e.name)
+                )
+            }
+        };
+        let _ = writeln!(js, "  {write};");
+    }
+    let _ = writeln!(js, "  return Uint8Array.from(out);\n}}");
+
+    let _ = writeln!(js, "\nexport function decode{name}(bytes) {{");
+    let _ = writeln!(js, "  const r = new Reader(bytes);");
+    let _ = writeln!(js, "  r.map({});", fields.len());
+    for field in &fields {
+        let read = match field.ty {
+            FieldType::U64 => "r.u64()".to_string(),
+            FieldType::Text => "r.text()".to_string(),
+            FieldType::AgentId => "r.agentId()".to_string(),
+            FieldType::Bytes => "r.bytesArray()".to_string(),
+            FieldType::Hash => "r.bytesArray(32)".to_string(),
+            FieldType::Enum(e) => format!("r.enumeration({})", screaming_snake(e.name)),
+        };
+        let _ = writeln!(js, "  r.key({:?});", field.name);
+        let _ = writeln!(js, "  const {} = {read};", field.name);
+    }
+    let _ = writeln!(js, "  r.end();");
+    let names: Vec<_> = schema.fields.iter().map(|f| f.name).collect();
+    let _ = writeln!(js, "  return {{ {} }};\n}}", names.join(", "));
+}
+
+fn payload_dts(dts: &mut String, schema: &PayloadSchema) {
+    let name = schema.name;
+    let carried = match (schema.kind, schema.observation_type) {
+        (PayloadKind::Observation, Some(tag)) => format!("Observation payload `{tag}`."),
+        (PayloadKind::Observation, None) => "Observation payload.".to_string(),
+        (PayloadKind::Decision, _) => "Decision payload.".to_string(),
+    };
+    let _ = writeln!(dts, "\n/** {carried} */\nexport interface {name} {{");
+    for field in schema.fields {
+        let ty = match field.ty {
+            FieldType::U64 => {
+                let _ = writeln!(
+                    dts,
+                    "  /** u64; encoders also accept a safe-integer number. */"
+                );
+                "bigint".to_string()
+            }
+            FieldType::Text => "string".to_string(),
+            FieldType::AgentId => {
+                let _ = writeln!(dts, "  /** Agent id; must not be empty. */");
+                "string".to_string()
+            }
+            FieldType::Bytes => "Uint8Array".to_string(),
+            FieldType::Hash => {
+                let _ = writeln!(dts, "  /** 32-byte hash. */");
+                "Uint8Array".to_string()
+            }
+            FieldType::Enum(e) => e.name.to_string(),
+        };
+        let _ = writeln!(dts, "  {}: {ty};", field.name);
+    }
+    dts.push_str("}\n");
+    if let Some(tag) = schema.observation_type {
+        let _ = writeln!(dts, "export declare const {tag}: {tag:?};");
+    }
+    let _ = writeln!(
+        dts,
+        "export declare function encode{name}(value: {name}): Uint8Array;\n\
+         export declare function decode{name}(bytes: Uint8Array): {name};"
+    );
+}
+
+/// `ClockSource` -> `CLOCK_SOURCE`.
+fn screaming_snake(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.push(c.to_ascii_uppercase());
+    }
+    out
+}
@@ -0,0 +1,203 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Worldline Forks
+//!
+//! Parts a recorded worldline at a cut under a [`DeltaSpec`] for
+//! `jitos fork`. The prefix is replayed to rebuild the runtime at the cut
+//! (see [`crate::replay`]), then forked through the delta engine
+//! ([`BranchManager::fork`]), which records the fork's policy context on the
+//! new branch. The two runtimes, base and fork, are then compared with
+//! [`whatif::compare`]: views, graph digest and scheduler policy per branch.
+//!
+//! A SchedulerPolicy delta names its policy by hash only, so the policy
+//! itself must be one of the caller's candidates, the recorded policy or a
+//! built-in preset.
+//!
+//! [`rerun`] goes on from the fork: it re-executes the recorded ticks after
+//! the cut on both branches (see [`engine::reexecute`]), so the comparison
+//! shows what the delta would have changed by the end of the worldline.
+
+use std::fmt;
+
+use jitos_core::delta::{DeltaKind, DeltaSpec};
+use jitos_core::events::EventEnvelope;
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_runtime::replay as engine;
+use jitos_runtime::{BranchError, BranchManager, ForkOrigin};
+use jitos_scheduler::{EchoScheduler, SchedulerPolicy};
+use jitos_views::ClockPolicyId;
+use thiserror::Error;
+
+use crate::replay::{self, ReplayError};
+use crate::whatif::{self, Comparison, WhatIfError};
+
+/// Fork errors.
+#[derive(Debug, Error)]
+pub enum ForkError {
+    #[error("cut {cut} exceeds event sequence length {len}")]
+    CutOutOfBounds { cut: usize, len: usize },
+    #[error("cut {cut} is inside a tick; the nearest tick boundary before it is {boundary}")]
+    NotATickBoundary { cut: usize, boundary: usize },
+    #[error("the worldline prefix does not replay: {0}")]
+    Prefix(#[from] ReplayError),
+    #[error("re-executing the ticks after the cut failed: {0}")]
+    Suffix(Box<engine::ReplayError>),
+    #[error("scheduler policy {0} is neither the recorded policy, a preset nor a given candidate")]
+    UnknownSchedulerPolicy(Hash),
+    #[error("fork: {0}")]
+    Branch(#[from] BranchError),
+    #[error("divergence summary: {0}")]
+    WhatIf(#[from] WhatIfError),
+}
+
+/// A branch parted from a recorded worldline.
+pub struct Forked {
+    pub name: String,
+    pub origin: ForkOrigin,
+    pub description: String,
+    /// The branch's worldline: the shared prefix, then the fork's policy
+    /// contexts.
+    pub events: Vec<EventEnvelope>,
+    pub heads: Vec<Hash>,
+    /// Base and fork branches at the cut.
+    pub comparison: Comparison,
+}
+
+/// Fork `store` at `cut` as branch `name`, running under `delta`.
+///
+/// `clock_policy` is the one the worldline was recorded under.
+/// `candidates` are extra scheduler policies a SchedulerPolicy delta may
+/// name.
+///
+/// # Errors
+///
+/// Fails if the cut is out of bounds or inside a tick, if the prefix does
+/// not replay to its recorded receipts, if the delta names an unknown policy
+/// or is not a ClockPolicy or SchedulerPolicy delta, and if the base branch
+/// has SLAPs deferred at the cut.
+pub fn fork(
+    store: &MemoryEventStore,
+    cut: usize,
+    clock_policy: ClockPolicyId,
+    name: &str,
+    delta: &DeltaSpec,
+    candidates: &[SchedulerPolicy],
+) -> Result<Forked, ForkError> {
+    let branches = branch_at(store, cut, clock_policy, name, delta, candidates)?;
+    let base = branches.active().runtime();
+    let branch = branches.branch(name).expect("the fork was just added");
+    let origin = branch.origin().expect("forks have an origin").clone();
+    let fork_store = branch.runtime().store();
+
+    Ok(Forked {
+        name: name.to_string(),
+        origin,
+        description: delta.description.clone(),
+        events: fork_store.events().to_vec(),
+        heads: fork_store.heads(),
+        comparison: whatif::compare(cut, delta, base, branch.runtime())?,
+    })
+}
+
+/// Fork `store` at `cut` under `delta`, re-execute the recorded ticks after
+/// the cut on both branches and compare them at the end.
+///
+/// Arguments are as for [`fork`]. The base branch re-executes under the
+/// recorded policies, so it ends where the worldline does; the fork makes
+/// its own scheduling decisions from the same observations and proposals.
+///
+/// # Errors
+///
+/// As [`fork`]; additionally [`ForkError::Suffix`] if a tick after the cut
+/// fails on either branch.
+pub fn rerun(
+    store: &MemoryEventStore,
+    cut: usize,
+    clock_policy: ClockPolicyId,
+    delta: &DeltaSpec,
+    candidates: &[SchedulerPolicy],
+) -> Result<Comparison, ForkError> {
+    const NAME: &str = "what-if";
+    let mut branches = branch_at(store, cut, clock_policy, NAME, delta, candidates)?;
+    let ticks = engine::recorded_ticks(store.events());
+    engine::reexecute(branches.active_mut().runtime_mut(), &ticks)
+        .map_err(|error| ForkError::Suffix(Box::new(error)))?;
+    let fork = branches.branch_mut(NAME).expect("the fork was just added");
+    engine::reexecute(fork.runtime_mut(), &ticks)
+        .map_err(|error| ForkError::Suffix(Box::new(error)))?;
+
+    let fork = branches.branch(NAME).expect("the fork was just added");
+    Ok(whatif::compare(
+        cut,
+        delta,
+        branches.active().runtime(),
+        fork.runtime(),
+    )?)
+}
+
+/// Replay `store` up to `cut` and fork branch `name` off it under `delta`.
+fn branch_at(
+    store: &MemoryEventStore,
+    cut: usize,
+    clock_policy: ClockPolicyId,
+    name: &str,
+    delta: &DeltaSpec,
+    candidates: &[SchedulerPolicy],
+) -> Result<BranchManager, ForkError> {
+    if cut > store.len() {
+        return Err(ForkError::CutOutOfBounds {
+            cut,
+            len: store.len(),
+        });
+    }
+    let runtime = replay::runtime_for(store, clock_policy)?;
+    let replayed = engine::run(store, cut, runtime).map_err(ReplayError::Engine)?;
+    let boundary = replayed.runtime.store().len();
+    if boundary != cut {
+        return Err(ForkError::NotATickBoundary { cut, boundary });
+    }
+
+    let recorded = replayed.runtime.scheduler().policy().clone();
+    let scheduler = match &delta.kind {
+        DeltaKind::SchedulerPolicy { new_policy } => {
+            let policy = candidates
+                .iter()
+                .cloned()
+                .chain([
+                    recorded,
+                    SchedulerPolicy::default(),
+                    SchedulerPolicy::priority_deadline(),
+                ])
+                .find(|p| p.policy_hash() == *new_policy)
+                .ok_or(ForkError::UnknownSchedulerPolicy(*new_policy))?;
+            EchoScheduler::with_policy(policy)
+        }
+        _ => EchoScheduler::with_policy(recorded),
+    };
+
+    let mut branches = BranchManager::new(replayed.runtime);
+    branches.fork(name, delta, scheduler)?;
+    Ok(branches)
+}
+
+impl fmt::Display for Forked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "forked {} from {} at tick {} (cut {})",
+            self.name, self.origin.parent, self.origin.tick, self.comparison.cut
+        )?;
+        writeln!(
+            f,
+            "  delta     {} ({})",
+            self.origin.delta, self.description
+        )?;
+        writeln!(f, "  origin    {}", self.origin.event)?;
+        for head in &self.heads {
+            writeln!(f, "  head      {head}")?;
+        }
+        write!(f, "{}", self.comparison)
+    }
+}
@@ -0,0 +1,294 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Graph Export
+//!
+//! Rebuilds the [`WarpGraph`] at a point in a recorded worldline for
+//! `jitos graph dump` and renders it as Graphviz DOT, GraphML or a canonical
+//! CBOR snapshot (see [`jitos_graph::snapshot`]).
+//!
+//! The graph is rebuilt by replaying the worldline up to the point (see
+//! [`crate::replay`]), and its commit digest is checked against the recorded
+//! receipt of the last tick before it, so an export is always a state the
+//! worldline actually committed. Nodes and edges are listed by NodeId and
+//! EdgeId, so the output depends only on graph content.
+
+use std::fmt::Write as _;
+
+use jitos_core::canonical::CanonicalError;
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_graph::snapshot::{encode_snapshot, SnapshotError};
+use jitos_graph::{PersistentGraph, PersistentGraphError, WarpGraph};
+use jitos_runtime::replay as engine;
+use jitos_views::ClockPolicyId;
+use thiserror::Error;
+
+use crate::replay::{self, ReplayError};
+
+/// Graph export errors.
+#[derive(Debug, Error)]
+pub enum GraphDumpError {
+    #[error("cut {cut} exceeds event sequence length {len}")]
+    CutOutOfBounds { cut: usize, len: usize },
+    #[error("no recorded tick has receipt or commit {0}")]
+    UnknownReceipt(Hash),
+    #[error("no tick was recorded before cut {0}")]
+    NoTick(usize),
+    #[error("`{0}` is neither a cut nor a 64-digit hex receipt or commit id")]
+    MalformedPoint(String),
+    #[error("unknown format `{0}` (known: dot, graphml, cbor)")]
+    UnknownFormat(String),
+    #[error("the worldline does not replay: {0}")]
+    Replay(#[from] ReplayError),
+    #[error("reconstructed graph {found} does not match tick {tick}'s recorded state {expected}")]
+    DigestMismatch {
+        tick: u64,
+        expected: Hash,
+        found: Hash,
+    },
+    #[error("graph cannot be normalized: {0}")]
+    Graph(#[from] PersistentGraphError),
+    #[error("snapshot: {0}")]
+    Snapshot(#[from] SnapshotError),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+}
+
+/// A point in history to export the graph at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Point {
+    /// After the last tick recorded in `events[..cut]`.
+    Cut(usize),
+    /// After the tick whose receipt digest, or Commit event id, this is.
+    Receipt(Hash),
+}
+
+impl Point {
+    /// A decimal cut, or a receipt digest or Commit event id in hex.
+    ///
+    /// # Errors
+    ///
+    /// [`GraphDumpError::MalformedPoint`] for anything else.
+    pub fn parse(point: &str) -> Result<Self, GraphDumpError> {
+        if let Ok(cut) = point.parse() {
+            return Ok(Point::Cut(cut));
+        }
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(point, &mut bytes)
+            .map_err(|_| GraphDumpError::MalformedPoint(point.to_string()))?;
+        Ok(Point::Receipt(Hash(bytes)))
+    }
+}
+
+/// Output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Dot,
+    Graphml,
+    /// Canonical CBOR graph snapshot, loadable by any snapshot store.
+    Cbor,
+}
+
+impl Format {
+    /// Parse a format name as given on the command line.
+    ///
+    /// # Errors
+    ///
+    /// [`GraphDumpError::UnknownFormat`] for names other than `dot`,
+    /// `graphml` and `cbor`.
+    pub fn from_name(name: &str) -> Result<Self, GraphDumpError> {
+        match name {
+            "dot" => Ok(Format::Dot),
+            "graphml" => Ok(Format::Graphml),
+            "cbor" => Ok(Format::Cbor),
+            _ => Err(GraphDumpError::UnknownFormat(name.to_string())),
+        }
+    }
+}
+
+/// The graph after a recorded tick, its digest verified.
+#[derive(Debug, Clone)]
+pub struct Reconstructed {
+    pub tick: u64,
+    /// Commit digest, equal to the tick's recorded `state_hash`.
+    pub state: Hash,
+    /// Events replayed to rebuild the graph.
+    pub cut: usize,
+    pub graph: WarpGraph,
+}
+
+/// Rebuild the graph at `point` by replaying `store` under `clock_policy`.
+///
+/// # Errors
+///
+/// Fails if the point is out of range or names no recorded tick, if no tick
+/// was recorded before it, if the worldline does not replay, and if the
+/// rebuilt graph does not hash to the recorded state.
+pub fn reconstruct(
+    store: &MemoryEventStore,
+    point: Point,
+    clock_policy: ClockPolicyId,
+) -> Result<Reconstructed, GraphDumpError> {
+    let cut = match point {
+        Point::Cut(cut) if cut > store.len() => {
+            return Err(GraphDumpError::CutOutOfBounds {
+                cut,
+                len: store.len(),
+            })
+        }
+        Point::Cut(cut) => cut,
+        Point::Receipt(hash) => {
+            let tick = engine::recorded_ticks(store.events())
+                .into_iter()
+                .find(|t| {
+                    t.commit.event_id() == hash || t.receipt.digest().is_ok_and(|d| d == hash)
+                })
+                .ok_or(GraphDumpError::UnknownReceipt(hash))?;
+            store
+                .position(&tick.commit.event_id())
+                .expect("recorded ticks come from the store")
+                + 1
+        }
+    };
+    let recorded = engine::recorded_ticks(&store.events()[..cut])
+        .pop()
+        .ok_or(GraphDumpError::NoTick(cut))?;
+
+    let runtime = replay::runtime_for(store, clock_policy)?;
+    let replayed = engine::run(store, cut, runtime).map_err(ReplayError::Engine)?;
+    let graph = replayed.runtime.graph().clone();
+    let found = graph.compute_hash_checked()?;
+    if found != recorded.receipt.state_hash {
+        return Err(GraphDumpError::DigestMismatch {
+            tick: recorded.receipt.tick,
+            expected: recorded.receipt.state_hash,
+            found,
+        });
+    }
+    Ok(Reconstructed {
+        tick: recorded.receipt.tick,
+        state: found,
+        cut,
+        graph,
+    })
+}
+
+impl Reconstructed {
+    /// The graph in `format`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the graph cannot be normalized (dangling edges, duplicate
+    /// NodeIds) or encoded.
+    pub fn render(&self, format: Format) -> Result<Vec<u8>, GraphDumpError> {
+        if format == Format::Cbor {
+            return Ok(encode_snapshot(&self.graph)?.1);
+        }
+        let graph = PersistentGraph::from_graph(&self.graph)?;
+        let text = match format {
+            Format::Dot => dot(&graph, self.tick, &self.state),
+            Format::Graphml => graphml(&graph, self.tick, &self.state),
+            Format::Cbor => unreachable!("handled above"),
+        };
+        Ok(text.into_bytes())
+    }
+}
+
+/// Abbreviated hash for labels.
+fn short(hash: &Hash) -> String {
+    hash.to_string()[..12].to_string()
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn dot(graph: &PersistentGraph, tick: u64, state: &Hash) -> String {
+    let mut out = format!("// state {state} after tick {tick}\ndigraph warp {{\n");
+    for node in graph.nodes() {
+        let _ = writeln!(
+            out,
+            "  \"{}\" [label=\"{}\\n{}\"];",
+            node.id,
+            dot_escape(&node.node_type),
+            short(&node.id.hash())
+        );
+    }
+    for (_, edge) in graph.edges() {
+        let _ = writeln!(
+            out,
+            "  \"{}\" -> \"{}\" [label=\"{}\"];",
+            edge.from,
+            edge.to,
+            dot_escape(&edge.edge_type)
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn graphml(graph: &PersistentGraph, tick: u64, state: &Hash) -> String {
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"type\" for=\"all\" attr.name=\"type\" attr.type=\"string\"/>\n",
+        "  <key id=\"payload\" for=\"all\" attr.name=\"payload_hex\" attr.type=\"string\"/>\n",
+        "  <key id=\"attachment\" for=\"all\" attr.name=\"attachment\" attr.type=\"string\"/>\n",
+    ));
+    let _ = writeln!(
+        out,
+        "  <graph id=\"{state}\" edgedefault=\"directed\">\n    <desc>state {state} after tick {tick}</desc>"
+    );
+    let data = |out: &mut String, payload: Option<&[u8]>, attachment: Option<&Hash>| {
+        if let Some(payload) = payload {
+            let _ = writeln!(
+                out,
+                "      <data key=\"payload\">{}</data>",
+                hex::encode(payload)
+            );
+        }
+        if let Some(attachment) = attachment {
+            let _ = writeln!(out, "      <data key=\"attachment\">{attachment}</data>");
+        }
+    };
+    for node in graph.nodes() {
+        let _ = writeln!(
+            out,
+            "    <node id=\"{}\">\n      <data key=\"type\">{}</data>",
+            node.id,
+            xml_escape(&node.node_type)
+        );
+        data(
+            &mut out,
+            Some(&node.payload_bytes),
+            node.attachment.as_ref(),
+        );
+        out.push_str("    </node>\n");
+    }
+    for (id, edge) in graph.edges() {
+        let _ = writeln!(
+            out,
+            "    <edge id=\"{id}\" source=\"{}\" target=\"{}\">\n      <data key=\"type\">{}</data>",
+            edge.from,
+            edge.to,
+            xml_escape(&edge.edge_type)
+        );
+        data(
+            &mut out,
+            edge.payload_bytes.as_deref(),
+            edge.attachment.as_ref(),
+        );
+        out.push_str("    </edge>\n");
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
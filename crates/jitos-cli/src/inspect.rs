@@ -0,0 +1,184 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Store Inspection
+//!
+//! Read-only views of a worldline file for `jitos log`, `show`, `verify` and
+//! `heads`. Each is a value with a [`fmt::Display`] impl, so the binary
+//! only prints and tests can check the values directly.
+
+use std::fmt;
+
+use jitos_core::events::{EventEnvelope, EventKind};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use thiserror::Error;
+
+use crate::worldline::{self, WorldlineError};
+
+/// Errors looking up an event.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InspectError {
+    #[error("`{0}` is not a hex event id or id prefix")]
+    MalformedId(String),
+    #[error("no event with id {0}")]
+    NotFound(String),
+    #[error("id prefix {prefix} matches {matches} events")]
+    Ambiguous { prefix: String, matches: usize },
+}
+
+/// Abbreviated hash for listings.
+fn short(hash: &Hash) -> String {
+    hash.to_string()[..12].to_string()
+}
+
+pub(crate) fn kind_name(kind: &EventKind) -> &'static str {
+    match kind {
+        EventKind::Observation => "Observation",
+        EventKind::PolicyContext => "PolicyContext",
+        EventKind::Decision => "Decision",
+        EventKind::Commit => "Commit",
+    }
+}
+
+/// Events in worldline (topological) order, one line each.
+#[derive(Debug, Clone, Copy)]
+pub struct Log<'a>(pub &'a [EventEnvelope]);
+
+impl fmt::Display for Log<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, event) in self.0.iter().enumerate() {
+            let parents: Vec<String> = event.parents().iter().map(short).collect();
+            writeln!(
+                f,
+                "{index:>5}  {}  {:<13}  {:<24}  {:<12}  {}",
+                short(&event.event_id()),
+                kind_name(event.kind()),
+                event.observation_type().unwrap_or("-"),
+                event.agent_id().map_or("-", |a| a.as_str()),
+                if parents.is_empty() {
+                    "-".to_string()
+                } else {
+                    parents.join(",")
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The event whose id is `id` or starts with it (at least 4 hex digits).
+///
+/// # Errors
+///
+/// Fails if `id` is not hex, or matches no event or several.
+pub fn find<'a>(store: &'a MemoryEventStore, id: &str) -> Result<&'a EventEnvelope, InspectError> {
+    let prefix = id.to_ascii_lowercase();
+    if prefix.len() < 4 || prefix.len() > 64 || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(InspectError::MalformedId(id.to_string()));
+    }
+    let mut matches = store
+        .events()
+        .iter()
+        .filter(|e| e.event_id().to_string().starts_with(&prefix));
+    match (matches.next(), matches.count()) {
+        (Some(event), 0) => Ok(event),
+        (Some(_), more) => Err(InspectError::Ambiguous {
+            prefix,
+            matches: more + 1,
+        }),
+        (None, _) => Err(InspectError::NotFound(id.to_string())),
+    }
+}
+
+/// One event in full, its payload decoded to canonical JSON.
+#[derive(Debug, Clone, Copy)]
+pub struct Show<'a>(pub &'a EventEnvelope);
+
+impl fmt::Display for Show<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let event = self.0;
+        writeln!(f, "event     {}", event.event_id())?;
+        writeln!(f, "kind      {}", kind_name(event.kind()))?;
+        if let Some(observation_type) = event.observation_type() {
+            writeln!(f, "type      {observation_type}")?;
+        }
+        if let Some(agent) = event.agent_id() {
+            writeln!(f, "agent     {}", agent.as_str())?;
+        }
+        if let Some(signature) = event.signature() {
+            writeln!(f, "signature {}", hex::encode(signature.as_bytes()))?;
+        }
+        for parent in event.parents() {
+            writeln!(f, "parent    {parent}")?;
+        }
+        // serde_json maps keep their keys sorted, so this is canonical JSON.
+        match event.payload().to_value::<serde_json::Value>() {
+            Ok(payload) => writeln!(f, "payload   {payload}"),
+            Err(_) => writeln!(
+                f,
+                "payload   (not representable as JSON) cbor {}",
+                hex::encode(event.payload().as_bytes())
+            ),
+        }
+    }
+}
+
+/// Summary of a store that passed [`verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verified {
+    pub events: usize,
+    pub observations: usize,
+    pub policy_contexts: usize,
+    pub decisions: usize,
+    pub commits: usize,
+    /// Events carrying a signature.
+    pub signed: usize,
+    pub heads: Vec<Hash>,
+}
+
+/// Validate a worldline file body in full: canonical encoding, every event
+/// id, parent order and presence, and the Decision/Commit parent rules.
+///
+/// # Errors
+///
+/// The first violation, with the index of the offending event where known.
+pub fn verify(bytes: &[u8]) -> Result<Verified, WorldlineError> {
+    let store = worldline::decode(bytes)?;
+    let events = store.events();
+    let count = |kind: EventKind| events.iter().filter(|e| *e.kind() == kind).count();
+    Ok(Verified {
+        events: events.len(),
+        observations: count(EventKind::Observation),
+        policy_contexts: count(EventKind::PolicyContext),
+        decisions: count(EventKind::Decision),
+        commits: count(EventKind::Commit),
+        signed: events.iter().filter(|e| e.signature().is_some()).count(),
+        heads: store.heads(),
+    })
+}
+
+impl fmt::Display for Verified {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "ok: {} events verified", self.events)?;
+        writeln!(f, "  observations     {}", self.observations)?;
+        writeln!(f, "  policy contexts  {}", self.policy_contexts)?;
+        writeln!(f, "  decisions        {}", self.decisions)?;
+        writeln!(f, "  commits          {}", self.commits)?;
+        writeln!(f, "  signed           {}", self.signed)?;
+        writeln!(f, "  heads            {}", self.heads.len())
+    }
+}
+
+/// Head event ids, in worldline order, one per line.
+#[derive(Debug, Clone)]
+pub struct Heads(pub Vec<Hash>);
+
+impl fmt::Display for Heads {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for head in &self.0 {
+            writeln!(f, "{head}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Signing Keys
+//!
+//! The CLI signs with Ed25519. A key file holds an agent's 32-byte secret
+//! seed, or the seed is kept encrypted in a [`crate::keystore`]; other agents
+//! are trusted by naming their public key, as `agent=<hex public key>`.
+//!
+//! An event is signed by signing its [`event_statement`], a hash over a
+//! domain separator and the event id. The signature and signing agent are
+//! not part of the event id, so signing an event does not change it.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use jitos_core::canonical::CanonicalError;
+use jitos_core::events::{AgentId, EventEnvelope, EventError, EventId, Signature};
+use jitos_core::Hash;
+pub use jitos_net::{event_statement, EVENT_SIGNATURE_V0};
+use jitos_net::{PeerSigner, PeerVerifier};
+use thiserror::Error;
+
+/// Errors loading keys.
+#[derive(Debug, Error)]
+pub enum KeyError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("key file must hold a 32-byte seed, got {0} bytes")]
+    SeedLength(usize),
+    #[error("invalid agent id: {0}")]
+    Agent(#[from] EventError),
+    #[error("trusted key must be `agent=<hex public key>`, got `{0}`")]
+    Malformed(String),
+    #[error("`{0}` is not an Ed25519 public key")]
+    PublicKey(String),
+    #[error("no randomness for a new key: {0}")]
+    Entropy(getrandom::Error),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("event {0} is not attributed to an agent")]
+    Unattributed(EventId),
+    #[error("event {0} is not signed")]
+    Unsigned(EventId),
+    #[error("signature on event {event} does not verify as {}", .agent.as_str())]
+    BadSignature { event: EventId, agent: AgentId },
+}
+
+/// An agent's Ed25519 signing key.
+pub struct Ed25519Signer {
+    agent: AgentId,
+    key: SigningKey,
+}
+
+impl Ed25519Signer {
+    pub fn new(agent: AgentId, seed: [u8; 32]) -> Self {
+        Self {
+            agent,
+            key: SigningKey::from_bytes(&seed),
+        }
+    }
+
+    /// A new key for `agent` from the operating system's randomness.
+    pub fn generate(agent: AgentId) -> Result<Self, KeyError> {
+        let mut seed = [0u8; 32];
+        getrandom::getrandom(&mut seed).map_err(KeyError::Entropy)?;
+        Ok(Self::new(agent, seed))
+    }
+
+    /// Read `agent`'s seed from a key file.
+    pub fn load(agent: AgentId, path: &Path) -> Result<Self, KeyError> {
+        let bytes = std::fs::read(path)?;
+        let seed: [u8; 32] = bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| KeyError::SeedLength(bytes.len()))?;
+        Ok(Self::new(agent, seed))
+    }
+
+    /// The public key, hex-encoded, as [`Keyring::trust`] expects it.
+    pub fn public_hex(&self) -> String {
+        hex::encode(self.key.verifying_key().as_bytes())
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.key.verifying_key()
+    }
+
+    /// The secret seed, for [`crate::keystore`] to encrypt.
+    pub(crate) fn seed(&self) -> [u8; 32] {
+        self.key.to_bytes()
+    }
+}
+
+impl PeerSigner for Ed25519Signer {
+    fn agent(&self) -> &AgentId {
+        &self.agent
+    }
+
+    fn sign(&self, statement: &Hash) -> Signature {
+        Signature::new(self.key.sign(&statement.0).to_bytes().to_vec())
+            .expect("ed25519 signatures are not empty")
+    }
+}
+
+/// Public keys of trusted agents.
+#[derive(Debug, Clone, Default)]
+pub struct Keyring {
+    keys: HashMap<AgentId, VerifyingKey>,
+}
+
+impl Keyring {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust the key named by `entry` (`agent=<hex public key>`).
+    pub fn trust(&mut self, entry: &str) -> Result<(), KeyError> {
+        let (agent, key) = entry
+            .split_once('=')
+            .ok_or_else(|| KeyError::Malformed(entry.to_string()))?;
+        let key = hex::decode(key)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or_else(|| KeyError::PublicKey(key.to_string()))?;
+        self.insert(AgentId::new(agent)?, key);
+        Ok(())
+    }
+
+    /// Trust `key` as `agent`'s, replacing any key trusted for it before.
+    pub fn insert(&mut self, agent: AgentId, key: VerifyingKey) {
+        self.keys.insert(agent, key);
+    }
+}
+
+impl PeerVerifier for Keyring {
+    fn verify(&self, agent: &AgentId, statement: &Hash, signature: &Signature) -> bool {
+        let Some(key) = self.keys.get(agent) else {
+            return false;
+        };
+        ed25519_dalek::Signature::from_slice(signature.as_bytes())
+            .is_ok_and(|signature| key.verify(&statement.0, &signature).is_ok())
+    }
+}
+
+/// `event`, attributed to `signer`'s agent and signed by it.
+///
+/// Any previous agent and signature are replaced; the event id is unchanged.
+pub fn sign_event(
+    event: EventEnvelope,
+    signer: &dyn PeerSigner,
+) -> Result<EventEnvelope, KeyError> {
+    let signature = signer.sign(&event_statement(&event.event_id()));
+    Ok(event.with_signature(signer.agent().clone(), signature))
+}
+
+/// Check that `event` carries a signature by the agent it is attributed to.
+///
+/// Returns that agent.
+pub fn verify_event<'a>(
+    event: &'a EventEnvelope,
+    verifier: &dyn PeerVerifier,
+) -> Result<&'a AgentId, KeyError> {
+    let id = event.event_id();
+    let agent = event.agent_id().ok_or(KeyError::Unattributed(id))?;
+    let signature = event.signature().ok_or(KeyError::Unsigned(id))?;
+    if !verifier.verify(agent, &event_statement(&id), signature) {
+        return Err(KeyError::BadSignature {
+            event: id,
+            agent: agent.clone(),
+        });
+    }
+    Ok(agent)
+}
@@ -0,0 +1,349 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Keystore
+//!
+//! A directory of passphrase-encrypted Ed25519 keys, one file per agent, for
+//! `jitos keygen`, `jitos sign-event` and `jitos verify-event`.
+//!
+//! A key file is the canonical CBOR encoding of a [`KeyFile`]. The agent's
+//! secret seed is sealed with ChaCha20-Poly1305 under a key derived from the
+//! passphrase with Argon2id; the agent and public key are stored in the clear
+//! and bound to the ciphertext as associated data, so anyone can read the
+//! public keys (see [`Keystore::keyring`]) but a key file cannot be renamed
+//! to another agent or paired with another public key without unlocking
+//! failing.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ed25519_dalek::VerifyingKey;
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::events::AgentId;
+use jitos_net::PeerSigner;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::keys::{Ed25519Signer, KeyError, Keyring};
+
+/// Format tag of key files, and part of the data their seal covers.
+pub const KEYSTORE_V0: &str = "loom-keystore-v0";
+
+/// Environment variable naming the keystore directory.
+pub const KEYSTORE_ENV: &str = "JITOS_KEYSTORE";
+
+/// Environment variable holding the passphrase, when no passphrase file is
+/// given.
+pub const PASSPHRASE_ENV: &str = "JITOS_PASSPHRASE";
+
+const KEY_EXTENSION: &str = "key";
+
+/// Errors reading or writing a keystore.
+#[derive(Debug, Error)]
+pub enum KeystoreError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("unsupported key file format {0}")]
+    Version(String),
+    #[error("the keystore already holds a key for {}", .0.as_str())]
+    Exists(AgentId),
+    #[error("the keystore holds no key for {}", .0.as_str())]
+    Missing(AgentId),
+    #[error("key file {0} is for another agent")]
+    WrongAgent(PathBuf),
+    #[error("passphrase must not be empty")]
+    EmptyPassphrase,
+    #[error("wrong passphrase, or the key file was altered")]
+    Unlock,
+    #[error("key derivation failed: {0}")]
+    Kdf(String),
+    #[error(transparent)]
+    Key(#[from] KeyError),
+}
+
+/// Argon2id cost of deriving a key file's sealing key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfCost {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfCost {
+    /// Argon2's recommended defaults: 19 MiB, 2 iterations, 1 lane.
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// One agent's key as stored on disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyFile {
+    /// Always [`KEYSTORE_V0`].
+    pub version: String,
+    pub agent: AgentId,
+    /// Ed25519 public key, hex-encoded.
+    pub public_key: String,
+    pub cost: KdfCost,
+    pub salt: Vec<u8>,
+    pub nonce: Vec<u8>,
+    /// The 32-byte seed, sealed; the tag is appended.
+    pub sealed_seed: Vec<u8>,
+}
+
+impl KeyFile {
+    /// Associated data of the seal: the format tag, agent and public key.
+    fn aad(&self) -> Result<Vec<u8>, CanonicalError> {
+        canonical::encode(&(&self.version, &self.agent, &self.public_key))
+    }
+
+    fn cipher(&self, passphrase: &str) -> Result<ChaCha20Poly1305, KeystoreError> {
+        if passphrase.is_empty() {
+            return Err(KeystoreError::EmptyPassphrase);
+        }
+        let params = Params::new(
+            self.cost.memory_kib,
+            self.cost.iterations,
+            self.cost.parallelism,
+            Some(32),
+        )
+        .map_err(|e| KeystoreError::Kdf(e.to_string()))?;
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &self.salt, &mut key)
+            .map_err(|e| KeystoreError::Kdf(e.to_string()))?;
+        Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+    }
+
+    /// Seal `signer`'s seed under `passphrase`.
+    pub fn seal(
+        signer: &Ed25519Signer,
+        passphrase: &str,
+        cost: KdfCost,
+    ) -> Result<Self, KeystoreError> {
+        let (mut salt, mut nonce) = ([0u8; 16], [0u8; 12]);
+        getrandom::getrandom(&mut salt).map_err(KeyError::Entropy)?;
+        getrandom::getrandom(&mut nonce).map_err(KeyError::Entropy)?;
+        let mut file = KeyFile {
+            version: KEYSTORE_V0.to_string(),
+            agent: signer.agent().clone(),
+            public_key: signer.public_hex(),
+            cost,
+            salt: salt.to_vec(),
+            nonce: nonce.to_vec(),
+            sealed_seed: Vec::new(),
+        };
+        let seed = signer.seed();
+        let payload = Payload {
+            msg: &seed,
+            aad: &file.aad()?,
+        };
+        file.sealed_seed = file
+            .cipher(passphrase)?
+            .encrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|_| KeystoreError::Unlock)?;
+        Ok(file)
+    }
+
+    /// Recover the signer with `passphrase`.
+    ///
+    /// # Errors
+    ///
+    /// [`KeystoreError::Unlock`] if the passphrase is wrong or any part of
+    /// the file was changed.
+    pub fn unlock(&self, passphrase: &str) -> Result<Ed25519Signer, KeystoreError> {
+        if self.nonce.len() != 12 {
+            return Err(KeystoreError::Unlock);
+        }
+        let payload = Payload {
+            msg: &self.sealed_seed,
+            aad: &self.aad()?,
+        };
+        let seed: [u8; 32] = self
+            .cipher(passphrase)?
+            .decrypt(Nonce::from_slice(&self.nonce), payload)
+            .ok()
+            .and_then(|seed| seed.try_into().ok())
+            .ok_or(KeystoreError::Unlock)?;
+        let signer = Ed25519Signer::new(self.agent.clone(), seed);
+        if signer.public_hex() != self.public_key {
+            return Err(KeystoreError::Unlock);
+        }
+        Ok(signer)
+    }
+
+    pub fn verifying_key(&self) -> Result<VerifyingKey, KeystoreError> {
+        hex::decode(&self.public_key)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or_else(|| KeyError::PublicKey(self.public_key.clone()).into())
+    }
+}
+
+/// A directory of key files.
+#[derive(Debug, Clone)]
+pub struct Keystore {
+    dir: PathBuf,
+    cost: KdfCost,
+}
+
+impl Keystore {
+    /// The keystore in `dir`, created (readable only by its owner) if
+    /// missing.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, KeystoreError> {
+        let dir = dir.into();
+        if !dir.exists() {
+            create_private_dir(&dir)?;
+        }
+        Ok(Self {
+            dir,
+            cost: KdfCost::default(),
+        })
+    }
+
+    /// `$JITOS_KEYSTORE`, or else `~/.jitos/keys`.
+    pub fn default_dir() -> Option<PathBuf> {
+        std::env::var_os(KEYSTORE_ENV)
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".jitos/keys")))
+    }
+
+    /// Seal keys added from now on at `cost`.
+    pub fn with_cost(mut self, cost: KdfCost) -> Self {
+        self.cost = cost;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// File holding `agent`'s key. The name is the hex-encoded agent id, so
+    /// any agent id is a safe file name.
+    pub fn path(&self, agent: &AgentId) -> PathBuf {
+        self.dir
+            .join(hex::encode(agent.as_str()))
+            .with_extension(KEY_EXTENSION)
+    }
+
+    /// Generate a key for `agent` and store it sealed under `passphrase`.
+    pub fn generate(
+        &self,
+        agent: AgentId,
+        passphrase: &str,
+    ) -> Result<Ed25519Signer, KeystoreError> {
+        let signer = Ed25519Signer::generate(agent)?;
+        self.insert(&signer, passphrase)?;
+        Ok(signer)
+    }
+
+    /// Store `signer`'s key sealed under `passphrase`.
+    ///
+    /// # Errors
+    ///
+    /// [`KeystoreError::Exists`] if the agent already has a key here; keys
+    /// are never overwritten.
+    pub fn insert(&self, signer: &Ed25519Signer, passphrase: &str) -> Result<(), KeystoreError> {
+        let agent = signer.agent();
+        let file = KeyFile::seal(signer, passphrase, self.cost)?;
+        let bytes = canonical::encode(&file)?;
+        // Created exclusively and owner-only in one step, so a concurrent
+        // insert cannot be overwritten and the key is never readable by others.
+        let mut out = match create_private(&self.path(agent)) {
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                return Err(KeystoreError::Exists(agent.clone()))
+            }
+            out => out?,
+        };
+        out.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// Read `agent`'s key file without unlocking it.
+    pub fn key_file(&self, agent: &AgentId) -> Result<KeyFile, KeystoreError> {
+        let path = self.path(agent);
+        if !path.exists() {
+            return Err(KeystoreError::Missing(agent.clone()));
+        }
+        let file = read_key_file(&path)?;
+        if file.agent != *agent {
+            return Err(KeystoreError::WrongAgent(path));
+        }
+        Ok(file)
+    }
+
+    /// `agent`'s signer, unlocked with `passphrase`.
+    pub fn unlock(
+        &self,
+        agent: &AgentId,
+        passphrase: &str,
+    ) -> Result<Ed25519Signer, KeystoreError> {
+        self.key_file(agent)?.unlock(passphrase)
+    }
+
+    /// Agents with a key here, sorted.
+    pub fn agents(&self) -> Result<Vec<AgentId>, KeystoreError> {
+        let mut agents = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == KEY_EXTENSION) {
+                agents.push(read_key_file(&path)?.agent);
+            }
+        }
+        agents.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        Ok(agents)
+    }
+
+    /// A keyring trusting the public key of every agent here. Needs no
+    /// passphrase.
+    pub fn keyring(&self) -> Result<Keyring, KeystoreError> {
+        let mut keyring = Keyring::new();
+        for agent in self.agents()? {
+            let key = self.key_file(&agent)?.verifying_key()?;
+            keyring.insert(agent, key);
+        }
+        Ok(keyring)
+    }
+}
+
+fn read_key_file(path: &Path) -> Result<KeyFile, KeystoreError> {
+    let file: KeyFile = canonical::decode(&std::fs::read(path)?)?;
+    if file.version != KEYSTORE_V0 {
+        return Err(KeystoreError::Version(file.version));
+    }
+    Ok(file)
+}
+
+/// Create `path` readable only by its owner, failing if it exists.
+fn create_private(path: &Path) -> std::io::Result<std::fs::File> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+/// Create `dir` and any missing parents, accessible only by its owner.
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(dir)
+}
@@ -0,0 +1,22 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! # jitos-cli
+//!
+//! Command-line access to JITOS worldlines. The `jitos` binary is a thin
+//! argument parser over the modules here, so every command is also usable
+//! (and testable) as a library call.
+
+pub mod bundle;
+pub mod cbor;
+pub mod codegen;
+pub mod fork;
+pub mod graph;
+pub mod inspect;
+pub mod keys;
+pub mod keystore;
+pub mod replay;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod whatif;
+pub mod worldline;
@@ -0,0 +1,667 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use jitos_core::blob::{blob_hash, BlobStore, MemoryBlobStore};
+use jitos_core::canonical;
+use jitos_core::delta::DeltaSpec;
+use jitos_core::events::AgentId;
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_graph::snapshot::FileSnapshotStore;
+use jitos_graph::WarpGraph;
+use jitos_net::PeerSigner;
+use jitos_views::ClockPolicyId;
+
+use jitos_cli::graph::{Format, Point};
+use jitos_cli::keys::{self, Ed25519Signer, Keyring};
+use jitos_cli::keystore::{Keystore, PASSPHRASE_ENV};
+use jitos_cli::{bundle, cbor, codegen, fork, inspect, replay, whatif, worldline};
+
+#[derive(Parser)]
+#[command(
+    name = "jitos",
+    version,
+    about = "Inspect and explore JITOS worldlines"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List events in worldline (topological) order.
+    Log(StoreArgs),
+    /// Print one event, its payload decoded to canonical JSON.
+    Show(ShowArgs),
+    /// Validate every event in a worldline file.
+    Verify(StoreArgs),
+    /// Print the head event ids.
+    Heads(StoreArgs),
+    /// Compare views on the worldline against a policy-delta fork at a cut.
+    #[command(name = "whatif")]
+    WhatIf(WhatIfArgs),
+    /// Part a worldline at a cut under a DeltaSpec and write the new branch.
+    Fork(ForkArgs),
+    /// Export the graph at a point in a worldline's history.
+    #[command(subcommand)]
+    Graph(GraphCommand),
+    /// Re-execute a worldline and compare recomputed receipts with the record.
+    Replay(ReplayArgs),
+    /// Inspect CBOR against the canonical encoding rules.
+    #[command(subcommand)]
+    Cbor(CborCommand),
+    /// Carry events between nodes as a signed offline bundle file.
+    #[command(subcommand)]
+    Bundle(BundleCommand),
+    /// Generate an agent's Ed25519 key into the passphrase-encrypted keystore.
+    Keygen(KeygenArgs),
+    /// Sign an event in a worldline with a key from the keystore.
+    SignEvent(SignEventArgs),
+    /// Check an event's signature against the keystore and trusted keys.
+    VerifyEvent(VerifyEventArgs),
+    /// Browse a worldline interactively: events, payloads, links and views.
+    #[cfg(feature = "tui")]
+    Tui(TuiArgs),
+    /// Generate TypeScript types and canonical CBOR codecs for the registered payload types.
+    Codegen(CodegenArgs),
+}
+
+#[derive(Args)]
+struct StoreArgs {
+    /// Worldline file (canonical CBOR event array).
+    #[arg(long)]
+    store: PathBuf,
+}
+
+#[derive(Args)]
+struct ShowArgs {
+    /// Worldline file (canonical CBOR event array).
+    #[arg(long)]
+    store: PathBuf,
+    /// Event id, or a unique prefix of at least 4 hex digits.
+    event_id: String,
+}
+
+#[derive(Args)]
+struct ForkArgs {
+    /// Worldline file (canonical CBOR event array).
+    #[arg(long)]
+    store: PathBuf,
+    /// DeltaSpec as JSON (ClockPolicy or SchedulerPolicy); its hash is checked.
+    #[arg(long)]
+    delta: PathBuf,
+    /// Cut to fork at (number of events in the shared prefix, at a tick boundary).
+    #[arg(long)]
+    at: usize,
+    /// Name of the new branch.
+    #[arg(long, default_value = "fork")]
+    name: String,
+    /// Clock policy the worldline was recorded under.
+    #[arg(long, default_value = "trust_monotonic_latest", value_parser = parse_clock_policy)]
+    clock_policy: ClockPolicyId,
+    /// SchedulerPolicy JSON files a SchedulerPolicy delta may name.
+    #[arg(long = "scheduler-policy")]
+    scheduler_policies: Vec<PathBuf>,
+    /// Where to write the branch worldline; defaults to `<store>.<name>.cbor`.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum GraphCommand {
+    /// Rebuild the graph at a cut or receipt, verify its digest and export it.
+    Dump(DumpArgs),
+}
+
+#[derive(Args)]
+struct DumpArgs {
+    /// Worldline file (canonical CBOR event array).
+    #[arg(long)]
+    store: PathBuf,
+    /// Output format: dot, graphml or cbor (a canonical graph snapshot).
+    #[arg(long, default_value = "dot", value_parser = parse_format)]
+    format: Format,
+    /// Cut (number of events), or receipt digest or Commit event id in hex.
+    #[arg(long, value_parser = parse_point)]
+    at: Point,
+    /// Clock policy the worldline was recorded under.
+    #[arg(long, default_value = "trust_monotonic_latest", value_parser = parse_clock_policy)]
+    clock_policy: ClockPolicyId,
+    /// File to write; defaults to stdout.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ReplayArgs {
+    /// Worldline file (canonical CBOR event array).
+    #[arg(long)]
+    store: PathBuf,
+    /// Clock policy the worldline was recorded under.
+    #[arg(long, default_value = "trust_monotonic_latest", value_parser = parse_clock_policy)]
+    clock_policy: ClockPolicyId,
+    /// Start after the recorded tick with this graph state instead of genesis.
+    #[arg(long, value_parser = parse_hash, requires = "snapshots")]
+    from_checkpoint: Option<Hash>,
+    /// Snapshot directory holding the checkpoint (`<hash>.cbor` files).
+    #[arg(long)]
+    snapshots: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum CborCommand {
+    /// Print CBOR in diagnostic notation and report the first canonical-rule violation.
+    Diag(DiagArgs),
+}
+
+#[derive(Args)]
+struct DiagArgs {
+    /// CBOR file, or the bytes in hex.
+    input: String,
+    /// Re-encode the value canonically and print it as hex.
+    #[arg(long)]
+    fix: bool,
+    /// With --fix, also write the canonical bytes to this file.
+    #[arg(long, requires = "fix")]
+    out: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum BundleCommand {
+    /// Write the events after a cut, blobs and heads to a signed bundle.
+    Export(ExportArgs),
+    /// Verify a bundle and add its events and blobs.
+    Import(ImportArgs),
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    /// Worldline file (canonical CBOR event array).
+    #[arg(long)]
+    store: PathBuf,
+    /// Cut to export after (number of events the importer already holds).
+    #[arg(long, default_value_t = 0)]
+    since: usize,
+    /// Blob files to include.
+    #[arg(long = "blob")]
+    blobs: Vec<PathBuf>,
+    /// Agent attesting to the bundle, with a key in the keystore.
+    #[arg(long)]
+    agent: String,
+    /// Bundle file to write.
+    #[arg(long)]
+    out: PathBuf,
+    #[command(flatten)]
+    keystore: KeystoreArgs,
+    #[command(flatten)]
+    passphrase: PassphraseArgs,
+}
+
+#[derive(Args)]
+struct ImportArgs {
+    /// Worldline file to extend; created if missing.
+    #[arg(long)]
+    store: PathBuf,
+    /// Bundle file to import.
+    #[arg(long)]
+    bundle: PathBuf,
+    /// Trusted attester, as `agent=<hex Ed25519 public key>`.
+    #[arg(long = "trust", required = true)]
+    trusted: Vec<String>,
+    /// Directory to write blobs to, one file per blob named by its hash.
+    #[arg(long)]
+    blobs: Option<PathBuf>,
+}
+
+#[cfg(feature = "tui")]
+#[derive(Args)]
+struct TuiArgs {
+    /// Worldline file (canonical CBOR event array).
+    #[arg(long)]
+    store: PathBuf,
+    /// Clock policy to fold the clock view under.
+    #[arg(long, default_value = "trust_monotonic_latest", value_parser = parse_clock_policy)]
+    clock_policy: ClockPolicyId,
+}
+
+#[derive(Args)]
+struct CodegenArgs {
+    /// Directory to write `loom-payloads.js` and `loom-payloads.d.ts` to.
+    #[arg(long, default_value = ".")]
+    out: PathBuf,
+}
+
+#[derive(Args)]
+struct KeystoreArgs {
+    /// Keystore directory; defaults to $JITOS_KEYSTORE, then ~/.jitos/keys.
+    #[arg(long)]
+    keystore: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct PassphraseArgs {
+    /// File holding the keystore passphrase; defaults to $JITOS_PASSPHRASE.
+    #[arg(long)]
+    passphrase_file: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct KeygenArgs {
+    /// Agent the key belongs to.
+    #[arg(long)]
+    agent: String,
+    /// Store this existing key file (32-byte secret seed) instead of a new key.
+    #[arg(long)]
+    import: Option<PathBuf>,
+    #[command(flatten)]
+    keystore: KeystoreArgs,
+    #[command(flatten)]
+    passphrase: PassphraseArgs,
+}
+
+#[derive(Args)]
+struct SignEventArgs {
+    /// Worldline file (canonical CBOR event array).
+    #[arg(long)]
+    store: PathBuf,
+    /// Event id, or a unique prefix of at least 4 hex digits.
+    event_id: String,
+    /// Agent to sign as.
+    #[arg(long)]
+    agent: String,
+    /// Where to write the worldline; defaults to rewriting the store.
+    #[arg(long)]
+    out: Option<PathBuf>,
+    #[command(flatten)]
+    keystore: KeystoreArgs,
+    #[command(flatten)]
+    passphrase: PassphraseArgs,
+}
+
+#[derive(Args)]
+struct VerifyEventArgs {
+    /// Worldline file (canonical CBOR event array).
+    #[arg(long)]
+    store: PathBuf,
+    /// Event id, or a unique prefix of at least 4 hex digits.
+    event_id: String,
+    /// Trusted signer besides the keystore's agents, as `agent=<hex Ed25519 public key>`.
+    #[arg(long = "trust")]
+    trusted: Vec<String>,
+    #[command(flatten)]
+    keystore: KeystoreArgs,
+}
+
+#[derive(Args)]
+struct WhatIfArgs {
+    /// Worldline file (canonical CBOR event array).
+    #[arg(long)]
+    store: PathBuf,
+    /// Canonical CBOR DeltaSpec (ClockPolicy or SchedulerPolicy).
+    #[arg(long)]
+    policy: PathBuf,
+    /// Cut to fork at (number of events in the shared prefix).
+    #[arg(long)]
+    at: usize,
+    /// Clock policy the recorded worldline was interpreted under.
+    #[arg(long, default_value = "trust_monotonic_latest", value_parser = parse_clock_policy)]
+    base_clock_policy: ClockPolicyId,
+    /// Canonical CBOR WarpGraph snapshot at the cut, for the digest row.
+    #[arg(long)]
+    graph: Option<PathBuf>,
+    /// SchedulerPolicy JSON files a SchedulerPolicy delta may name.
+    #[arg(long = "scheduler-policy")]
+    scheduler_policies: Vec<PathBuf>,
+}
+
+fn parse_clock_policy(name: &str) -> Result<ClockPolicyId, String> {
+    ClockPolicyId::from_name(name).ok_or_else(|| {
+        let known: Vec<_> = ClockPolicyId::ALL.iter().map(|p| p.name()).collect();
+        format!(
+            "unknown clock policy `{name}` (known: {})",
+            known.join(", ")
+        )
+    })
+}
+
+fn parse_format(name: &str) -> Result<Format, String> {
+    Format::from_name(name).map_err(|e| e.to_string())
+}
+
+fn parse_point(point: &str) -> Result<Point, String> {
+    Point::parse(point).map_err(|e| e.to_string())
+}
+
+fn parse_hash(hex: &str) -> Result<Hash, String> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(hex, &mut bytes)
+        .map_err(|_| format!("`{hex}` is not a 64-digit hex hash"))?;
+    Ok(Hash(bytes))
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Log(args) => print!("{}", inspect::Log(load(&args.store)?.events())),
+        Command::Show(args) => {
+            let store = load(&args.store)?;
+            print!("{}", inspect::Show(inspect::find(&store, &args.event_id)?));
+        }
+        Command::Verify(args) => {
+            let bytes = std::fs::read(&args.store)
+                .with_context(|| format!("failed to read {}", args.store.display()))?;
+            let verified = inspect::verify(&bytes)
+                .with_context(|| format!("{} failed verification", args.store.display()))?;
+            print!("{verified}");
+        }
+        Command::Heads(args) => print!("{}", inspect::Heads(load(&args.store)?.heads())),
+        Command::WhatIf(args) => what_if(args)?,
+        Command::Fork(args) => fork_worldline(args)?,
+        Command::Graph(GraphCommand::Dump(args)) => dump_graph(args)?,
+        Command::Replay(args) => replay_worldline(args)?,
+        Command::Cbor(CborCommand::Diag(args)) => cbor_diag(args)?,
+        Command::Bundle(BundleCommand::Export(args)) => export(args)?,
+        Command::Bundle(BundleCommand::Import(args)) => import(args)?,
+        Command::Keygen(args) => keygen(args)?,
+        Command::SignEvent(args) => sign_event(args)?,
+        Command::VerifyEvent(args) => verify_event(args)?,
+        #[cfg(feature = "tui")]
+        Command::Tui(args) => jitos_cli::tui::run(&load(&args.store)?, args.clock_policy)?,
+        Command::Codegen(args) => {
+            for path in codegen::write(&args.out, jitos_views::payload_schemas())? {
+                println!("wrote {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn load(path: &std::path::Path) -> Result<MemoryEventStore> {
+    worldline::load(path).with_context(|| format!("failed to load worldline {}", path.display()))
+}
+
+fn what_if(args: WhatIfArgs) -> Result<()> {
+    let store = worldline::load(&args.store)
+        .with_context(|| format!("failed to load worldline {}", args.store.display()))?;
+    let delta: DeltaSpec = read_canonical(&args.policy)?;
+    let graph: Option<WarpGraph> = args.graph.as_deref().map(read_canonical).transpose()?;
+    let candidates = args
+        .scheduler_policies
+        .iter()
+        .map(|path| read_json(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let comparison = whatif::what_if(
+        &store,
+        args.at,
+        args.base_clock_policy,
+        &delta,
+        graph.as_ref(),
+        &candidates,
+    )?;
+    print!("{comparison}");
+    Ok(())
+}
+
+fn fork_worldline(args: ForkArgs) -> Result<()> {
+    let store = load(&args.store)?;
+    let delta: DeltaSpec = read_json(&args.delta)?;
+    let candidates = args
+        .scheduler_policies
+        .iter()
+        .map(|path| read_json(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let forked = fork::fork(
+        &store,
+        args.at,
+        args.clock_policy,
+        &args.name,
+        &delta,
+        &candidates,
+    )?;
+    let out = args
+        .out
+        .unwrap_or_else(|| args.store.with_extension(format!("{}.cbor", args.name)));
+    worldline::save(&out, &forked.events)
+        .with_context(|| format!("failed to write worldline {}", out.display()))?;
+    print!("{forked}");
+    println!("wrote {} events to {}", forked.events.len(), out.display());
+    Ok(())
+}
+
+fn dump_graph(args: DumpArgs) -> Result<()> {
+    let store = load(&args.store)?;
+    let graph = jitos_cli::graph::reconstruct(&store, args.at, args.clock_policy)?;
+    let bytes = graph.render(args.format)?;
+    match &args.out {
+        Some(out) => std::fs::write(out, &bytes)
+            .with_context(|| format!("failed to write {}", out.display()))?,
+        None => std::io::stdout().write_all(&bytes)?,
+    }
+    eprintln!(
+        "verified state {} after tick {} ({} events replayed)",
+        graph.state, graph.tick, graph.cut
+    );
+    Ok(())
+}
+
+fn replay_worldline(args: ReplayArgs) -> Result<()> {
+    let store = load(&args.store)?;
+    let outcome = match (&args.from_checkpoint, &args.snapshots) {
+        (Some(checkpoint), Some(dir)) => {
+            let snapshots = FileSnapshotStore::open(dir)
+                .with_context(|| format!("failed to open snapshots {}", dir.display()))?;
+            replay::replay_from_checkpoint(&store, args.clock_policy, checkpoint, &snapshots)?
+        }
+        _ => replay::replay(&store, args.clock_policy)?,
+    };
+    print!("{outcome}");
+    if outcome.diverges() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn cbor_diag(args: DiagArgs) -> Result<()> {
+    let bytes = cbor::input(&args.input)?;
+    let diagnosis = cbor::diagnose(&bytes)?;
+    print!("{diagnosis}");
+    if args.fix {
+        let fixed = diagnosis.fix()?;
+        println!("fixed: {}", hex::encode(&fixed));
+        if let Some(out) = &args.out {
+            std::fs::write(out, &fixed)
+                .with_context(|| format!("failed to write {}", out.display()))?;
+        }
+    } else if !diagnosis.is_canonical() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn export(args: ExportArgs) -> Result<()> {
+    let store = worldline::load(&args.store)
+        .with_context(|| format!("failed to load worldline {}", args.store.display()))?;
+    let signer = args
+        .keystore
+        .open()?
+        .unlock(&AgentId::new(args.agent)?, &args.passphrase.read()?)?;
+    let blobs = args
+        .blobs
+        .iter()
+        .map(|path| {
+            std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let bundle = bundle::export(&store, args.since, blobs, &signer)?;
+    bundle::save(&args.out, &bundle)
+        .with_context(|| format!("failed to write bundle {}", args.out.display()))?;
+    println!(
+        "exported {} events and {} blobs to {}",
+        bundle.events.len(),
+        bundle.blobs.len(),
+        args.out.display()
+    );
+    println!(
+        "attested by {}={}",
+        signer.agent().as_str(),
+        signer.public_hex()
+    );
+    Ok(())
+}
+
+fn import(args: ImportArgs) -> Result<()> {
+    let mut store = if args.store.exists() {
+        worldline::load(&args.store)
+            .with_context(|| format!("failed to load worldline {}", args.store.display()))?
+    } else {
+        Default::default()
+    };
+    let mut keyring = Keyring::new();
+    for entry in &args.trusted {
+        keyring.trust(entry)?;
+    }
+    let bundle = bundle::load(&args.bundle)
+        .with_context(|| format!("failed to load bundle {}", args.bundle.display()))?;
+    let hashes: Vec<_> = bundle.blobs.iter().map(|b| blob_hash(b)).collect();
+
+    let mut blobs = MemoryBlobStore::new();
+    let report = bundle::import(&mut store, &mut blobs, bundle, &keyring)?;
+    worldline::save(&args.store, store.events())
+        .with_context(|| format!("failed to write worldline {}", args.store.display()))?;
+    if let Some(dir) = &args.blobs {
+        std::fs::create_dir_all(dir)?;
+        for hash in &hashes {
+            let bytes = blobs.get(hash).expect("blob was just stored");
+            std::fs::write(dir.join(hash.to_string()), bytes)?;
+        }
+    }
+    let attesters: Vec<_> = report.attested_by.iter().map(AgentId::as_str).collect();
+    println!(
+        "imported {} events ({} already present) and {} blobs, attested by {}",
+        report.added,
+        report.skipped,
+        report.blobs,
+        attesters.join(", ")
+    );
+    Ok(())
+}
+
+impl KeystoreArgs {
+    fn dir(&self) -> Result<PathBuf> {
+        self.keystore
+            .clone()
+            .or_else(Keystore::default_dir)
+            .context("no keystore: pass --keystore or set $JITOS_KEYSTORE")
+    }
+
+    fn open(&self) -> Result<Keystore> {
+        let dir = self.dir()?;
+        Keystore::open(&dir).with_context(|| format!("failed to open keystore {}", dir.display()))
+    }
+}
+
+impl PassphraseArgs {
+    fn read(&self) -> Result<String> {
+        match &self.passphrase_file {
+            Some(path) => {
+                let text = std::fs::read_to_string(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                Ok(text.trim_end_matches(['\r', '\n']).to_string())
+            }
+            None => std::env::var(PASSPHRASE_ENV).with_context(|| {
+                format!("no passphrase: pass --passphrase-file or set ${PASSPHRASE_ENV}")
+            }),
+        }
+    }
+}
+
+fn keygen(args: KeygenArgs) -> Result<()> {
+    let keystore = args.keystore.open()?;
+    let passphrase = args.passphrase.read()?;
+    let agent = AgentId::new(args.agent)?;
+    let signer = match &args.import {
+        Some(path) => {
+            let signer = Ed25519Signer::load(agent, path)
+                .with_context(|| format!("failed to load key {}", path.display()))?;
+            keystore.insert(&signer, &passphrase)?;
+            signer
+        }
+        None => keystore.generate(agent, &passphrase)?,
+    };
+    println!(
+        "stored key for {} in {}",
+        signer.agent().as_str(),
+        keystore.path(signer.agent()).display()
+    );
+    println!("trust {}={}", signer.agent().as_str(), signer.public_hex());
+    Ok(())
+}
+
+fn sign_event(args: SignEventArgs) -> Result<()> {
+    let store = load(&args.store)?;
+    let event = inspect::find(&store, &args.event_id)?.clone();
+    let signer = args
+        .keystore
+        .open()?
+        .unlock(&AgentId::new(args.agent)?, &args.passphrase.read()?)?;
+    let signed = keys::sign_event(event, &signer)?;
+
+    let id = signed.event_id();
+    let events: Vec<_> = store
+        .events()
+        .iter()
+        .map(|e| {
+            if e.event_id() == id {
+                signed.clone()
+            } else {
+                e.clone()
+            }
+        })
+        .collect();
+    let out = args.out.unwrap_or(args.store);
+    worldline::save(&out, &events)
+        .with_context(|| format!("failed to write worldline {}", out.display()))?;
+    println!("signed {id} as {}", signer.agent().as_str());
+    println!("wrote {} events to {}", events.len(), out.display());
+    Ok(())
+}
+
+fn verify_event(args: VerifyEventArgs) -> Result<()> {
+    let store = load(&args.store)?;
+    let event = inspect::find(&store, &args.event_id)?;
+    let mut keyring = match args.keystore.dir() {
+        // Verifying needs only public keys, and never creates a keystore.
+        Ok(dir) if dir.exists() => Keystore::open(&dir)?.keyring()?,
+        Ok(dir) if args.keystore.keystore.is_some() => {
+            anyhow::bail!("no keystore at {}", dir.display())
+        }
+        _ => Keyring::new(),
+    };
+    for entry in &args.trusted {
+        keyring.trust(entry)?;
+    }
+    let agent = keys::verify_event(event, &keyring)?;
+    println!("ok: {} signed by {}", event.event_id(), agent.as_str());
+    Ok(())
+}
+
+fn read_json<T: for<'de> serde::Deserialize<'de>>(path: &std::path::Path) -> Result<T> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_slice(&bytes).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn read_canonical<T: for<'de> serde::Deserialize<'de>>(path: &std::path::Path) -> Result<T> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    canonical::decode(&bytes)
+        .with_context(|| format!("{} is not valid canonical CBOR", path.display()))
+}
@@ -0,0 +1,231 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Worldline Replay
+//!
+//! Re-executes a recorded worldline through a fresh runtime for
+//! `jitos replay` and compares every recomputed receipt with the recorded
+//! one (see [`jitos_runtime::replay`]).
+//!
+//! The runtime is rebuilt from the worldline itself: its first event is the
+//! scheduler's policy context, so the scheduler policy is decoded from it.
+//! The clock policy is not recorded and must be supplied. Scheduler settings
+//! outside the policy (cost model, declared script access) are left at their
+//! defaults.
+
+use std::fmt;
+
+use jitos_core::canonical::CanonicalError;
+use jitos_core::events::{EventId, EventKind};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_graph::snapshot::{MemorySnapshotStore, SnapshotError, SnapshotStore};
+use jitos_runtime::replay::{
+    self as engine, Mismatch, ReplayDivergence, ReplayError as EngineError,
+};
+use jitos_runtime::Runtime;
+use jitos_scheduler::{BatchingStrategy, EchoScheduler, ScheduleDecision, SchedulerPolicy};
+use jitos_views::ClockPolicyId;
+use thiserror::Error;
+
+/// Errors that keep a worldline from being replayed at all.
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("worldline does not start with a policy context")]
+    NoPolicyContext,
+    #[error("first event is not a scheduler policy: {0}")]
+    Policy(#[from] CanonicalError),
+    #[error("scheduler policy allows zero batches per tick")]
+    ZeroBatches,
+    #[error("{0} is not the state of a recorded tick that left nothing deferred")]
+    NotACheckpoint(Hash),
+    #[error("checkpoint snapshot: {0}")]
+    Snapshot(#[from] SnapshotError),
+    #[error(transparent)]
+    Engine(EngineError),
+}
+
+/// Result of a replay whose receipts all matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matched {
+    /// Ticks re-executed, idle ones included (after the checkpoint, if any).
+    pub ticks: u64,
+    /// Re-executed recorded ticks whose receipts matched.
+    pub receipts: usize,
+    /// Tick of the checkpoint replay started after (`None` from genesis).
+    pub checkpoint: Option<u64>,
+}
+
+/// The first tick where replay disagreed with the record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diverged {
+    pub divergence: ReplayDivergence,
+    /// SLAP hashes the recorded receipt of that tick lists as applied.
+    pub applied_slaps: Vec<Hash>,
+}
+
+/// Outcome of [`replay`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Matched(Matched),
+    Diverged(Diverged),
+}
+
+impl Outcome {
+    /// True if some recomputed receipt differed from the record.
+    pub fn diverges(&self) -> bool {
+        matches!(self, Outcome::Diverged(_))
+    }
+}
+
+/// A fresh runtime configured like the one that recorded `store`.
+///
+/// # Errors
+///
+/// Fails if the first event is not a decodable scheduler policy context.
+pub fn runtime_for(
+    store: &MemoryEventStore,
+    clock_policy: ClockPolicyId,
+) -> Result<Runtime, ReplayError> {
+    let first = store
+        .events()
+        .first()
+        .filter(|e| *e.kind() == EventKind::PolicyContext)
+        .ok_or(ReplayError::NoPolicyContext)?;
+    let policy: SchedulerPolicy = first.payload().to_value()?;
+    if policy.batching == (BatchingStrategy::Antichains { max_batches: 0 }) {
+        return Err(ReplayError::ZeroBatches);
+    }
+    Ok(Runtime::new(
+        EchoScheduler::with_policy(policy),
+        clock_policy,
+    ))
+}
+
+/// Replay the whole worldline in `store` from genesis.
+///
+/// # Errors
+///
+/// Fails if the runtime cannot be rebuilt or the worldline cannot be
+/// replayed; a divergence is an [`Outcome`], not an error.
+pub fn replay(
+    store: &MemoryEventStore,
+    clock_policy: ClockPolicyId,
+) -> Result<Outcome, ReplayError> {
+    let runtime = runtime_for(store, clock_policy)?;
+    outcome(store, engine::run(store, store.len(), runtime))
+}
+
+/// Replay the worldline in `store`, starting after the recorded tick whose
+/// state is `checkpoint`, loaded from `snapshots`.
+///
+/// # Errors
+///
+/// As [`replay`]; additionally fails if no settled recorded tick has state
+/// `checkpoint` or its snapshot is missing or corrupt.
+pub fn replay_from_checkpoint(
+    store: &MemoryEventStore,
+    clock_policy: ClockPolicyId,
+    checkpoint: &Hash,
+    snapshots: &impl SnapshotStore,
+) -> Result<Outcome, ReplayError> {
+    let settled = engine::recorded_ticks(store.events()).iter().any(|t| {
+        t.receipt.state_hash == *checkpoint
+            && t.decision
+                .payload()
+                .to_value::<ScheduleDecision>()
+                .is_ok_and(|d| d.deferred.is_empty())
+    });
+    if !settled {
+        return Err(ReplayError::NotACheckpoint(*checkpoint));
+    }
+    // Only the requested snapshot is offered, so the engine cannot pick a
+    // later one from the same directory.
+    let mut only = MemorySnapshotStore::new();
+    only.save(&snapshots.load(checkpoint)?)?;
+
+    let runtime = runtime_for(store, clock_policy)?;
+    outcome(
+        store,
+        engine::run_from_checkpoint(store, store.len(), &only, runtime),
+    )
+}
+
+fn outcome(
+    store: &MemoryEventStore,
+    result: Result<engine::Replayed, EngineError>,
+) -> Result<Outcome, ReplayError> {
+    match result {
+        Ok(replayed) => Ok(Outcome::Matched(Matched {
+            ticks: replayed.ticks,
+            receipts: replayed.matched,
+            checkpoint: replayed.checkpoint,
+        })),
+        Err(EngineError::Diverged(divergence)) => {
+            let applied_slaps = engine::recorded_ticks(store.events())
+                .into_iter()
+                .find(|t| t.receipt.tick == divergence.tick)
+                .map(|t| t.receipt.applied_slaps)
+                .unwrap_or_default();
+            Ok(Outcome::Diverged(Diverged {
+                divergence,
+                applied_slaps,
+            }))
+        }
+        Err(e) => Err(ReplayError::Engine(e)),
+    }
+}
+
+fn mismatch_name(mismatch: &Mismatch) -> String {
+    match mismatch {
+        Mismatch::State => "graph state".to_string(),
+        Mismatch::View(name) => format!("{name} view"),
+        Mismatch::AppliedSlaps => "applied SLAPs".to_string(),
+        Mismatch::Decision => "scheduling decision".to_string(),
+        Mismatch::Receipt => "receipt".to_string(),
+        Mismatch::Missing => "missing tick".to_string(),
+    }
+}
+
+fn write_ids(f: &mut fmt::Formatter<'_>, label: &str, ids: &[EventId]) -> fmt::Result {
+    if ids.is_empty() {
+        return writeln!(f, "  {label:<9} -");
+    }
+    for id in ids {
+        writeln!(f, "  {label:<9} {id}")?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Matched(matched) => {
+                write!(
+                    f,
+                    "ok: replayed {} ticks, {} recorded receipts match",
+                    matched.ticks, matched.receipts
+                )?;
+                match matched.checkpoint {
+                    Some(tick) => writeln!(f, " (from checkpoint at tick {tick})"),
+                    None => writeln!(f),
+                }
+            }
+            Outcome::Diverged(Diverged {
+                divergence,
+                applied_slaps,
+            }) => {
+                writeln!(
+                    f,
+                    "diverged at tick {}: {} differs",
+                    divergence.tick,
+                    mismatch_name(&divergence.mismatch)
+                )?;
+                writeln!(f, "  expected  {}", divergence.expected)?;
+                writeln!(f, "  actual    {}", divergence.actual)?;
+                write_ids(f, "suspect", &divergence.suspects)?;
+                write_ids(f, "applied", applied_slaps)
+            }
+        }
+    }
+}
@@ -0,0 +1,443 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Worldline Browser
+//!
+//! `jitos tui`: an interactive terminal view of a worldline. The left pane
+//! lists events in canonical worldline order; the right pane shows the
+//! selected event (payload collapsed to one line, or expanded), its parents
+//! and children, and the clock and timer view state at the cut just after
+//! it.
+//!
+//! [`Browser`] holds the navigation state and reacts to keys, [`draw`] renders
+//! it, and [`run`] drives both on a real terminal, so everything but the
+//! terminal loop can be exercised with ratatui's `TestBackend`.
+
+use std::collections::HashMap;
+
+use jitos_core::events::{EventEnvelope, EventId};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_views::{ClockCheckpoints, ClockPolicyId, Time, TimeDomain, TimerView};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::inspect;
+
+/// Events between clock checkpoints; keeps cut queries cheap on long
+/// worldlines.
+const CHECKPOINT_INTERVAL: usize = 256;
+
+/// Rows moved by PageUp and PageDown.
+const PAGE: usize = 20;
+
+/// Collapsed payloads are cut to this many characters.
+const COLLAPSED_PAYLOAD: usize = 72;
+
+/// A parent or child of the selected event, by worldline index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Link {
+    Parent(usize),
+    Child(usize),
+}
+
+impl Link {
+    pub fn index(self) -> usize {
+        match self {
+            Link::Parent(index) | Link::Child(index) => index,
+        }
+    }
+}
+
+/// Clock and timer views folded over `events[..cut]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewState {
+    pub cut: usize,
+    pub time: Time,
+    pub requests: usize,
+    pub fired: usize,
+    /// Request IDs of timers due at `time`.
+    pub pending: Vec<Hash>,
+    pub next_fire_ns: Option<u64>,
+}
+
+/// Navigation state of the browser.
+pub struct Browser<'a> {
+    events: &'a [EventEnvelope],
+    children: Vec<Vec<usize>>,
+    parents: Vec<Vec<usize>>,
+    clocks: ClockCheckpoints,
+    selected: usize,
+    /// Highlighted entry of [`Browser::links`].
+    link: usize,
+    expanded: bool,
+    /// Selections left by following links, for going back.
+    history: Vec<usize>,
+    quit: bool,
+}
+
+impl<'a> Browser<'a> {
+    /// Browse `store`, folding clock views under `clock_policy`.
+    pub fn new(store: &'a MemoryEventStore, clock_policy: ClockPolicyId) -> Self {
+        let events = store.events();
+        let position: HashMap<EventId, usize> = events
+            .iter()
+            .enumerate()
+            .map(|(index, event)| (event.event_id(), index))
+            .collect();
+        let mut children = vec![Vec::new(); events.len()];
+        let mut parents = vec![Vec::new(); events.len()];
+        for (index, event) in events.iter().enumerate() {
+            for parent in event.parents() {
+                let parent = position[parent];
+                parents[index].push(parent);
+                children[parent].push(index);
+            }
+        }
+        Self {
+            events,
+            children,
+            parents,
+            clocks: ClockCheckpoints::build(events, clock_policy, CHECKPOINT_INTERVAL),
+            selected: 0,
+            link: 0,
+            expanded: false,
+            history: Vec::new(),
+            quit: false,
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn expanded(&self) -> bool {
+        self.expanded
+    }
+
+    pub fn quit(&self) -> bool {
+        self.quit
+    }
+
+    /// The selected event, if the worldline has any.
+    pub fn event(&self) -> Option<&'a EventEnvelope> {
+        self.events.get(self.selected)
+    }
+
+    /// Parents, then children, of the selected event, each in worldline
+    /// order.
+    pub fn links(&self) -> Vec<Link> {
+        if self.events.is_empty() {
+            return Vec::new();
+        }
+        let mut parents = self.parents[self.selected].clone();
+        parents.sort_unstable();
+        parents
+            .into_iter()
+            .map(Link::Parent)
+            .chain(
+                self.children[self.selected]
+                    .iter()
+                    .copied()
+                    .map(Link::Child),
+            )
+            .collect()
+    }
+
+    /// The highlighted link, if the selected event has any.
+    pub fn link(&self) -> Option<Link> {
+        self.links().get(self.link).copied()
+    }
+
+    /// Select event `index`, clamped to the worldline.
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.events.len().saturating_sub(1));
+        self.link = 0;
+    }
+
+    /// Jump to `index`, remembering where we came from.
+    fn jump(&mut self, index: usize) {
+        self.history.push(self.selected);
+        self.select(index);
+    }
+
+    /// React to a key press.
+    pub fn handle(&mut self, key: KeyCode) {
+        let links = self.links();
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.select(self.selected + 1),
+            KeyCode::Up | KeyCode::Char('k') => self.select(self.selected.saturating_sub(1)),
+            KeyCode::PageDown => self.select(self.selected + PAGE),
+            KeyCode::PageUp => self.select(self.selected.saturating_sub(PAGE)),
+            KeyCode::Home | KeyCode::Char('g') => self.select(0),
+            KeyCode::End | KeyCode::Char('G') => self.select(usize::MAX),
+            KeyCode::Char(' ') | KeyCode::Char('e') => self.expanded = !self.expanded,
+            KeyCode::Tab if !links.is_empty() => self.link = (self.link + 1) % links.len(),
+            KeyCode::BackTab if !links.is_empty() => {
+                self.link = (self.link + links.len() - 1) % links.len()
+            }
+            KeyCode::Enter => {
+                if let Some(link) = links.get(self.link) {
+                    self.jump(link.index());
+                }
+            }
+            KeyCode::Char('p') => {
+                if let Some(Link::Parent(index)) = links.first() {
+                    self.jump(*index);
+                }
+            }
+            KeyCode::Char('c') => {
+                if let Some(Link::Child(index)) = links.iter().find(|l| matches!(l, Link::Child(_)))
+                {
+                    self.jump(*index);
+                }
+            }
+            KeyCode::Backspace | KeyCode::Char('b') => {
+                if let Some(index) = self.history.pop() {
+                    self.select(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Views at the cut just after the selected event.
+    ///
+    /// # Errors
+    ///
+    /// A view's rejection of an event, as text.
+    pub fn view_state(&self) -> Result<ViewState, String> {
+        let cut = (self.selected + 1).min(self.events.len());
+        let time = self
+            .clocks
+            .now_at_cut(self.events, cut)
+            .map_err(|e| format!("clock view: {e}"))?;
+        let mut timers = TimerView::new();
+        for event in &self.events[..cut] {
+            timers
+                .apply_event(event)
+                .map_err(|e| format!("timer view: {e}"))?;
+        }
+        Ok(ViewState {
+            cut,
+            pending: timers
+                .pending_timers(&time)
+                .into_iter()
+                .map(|r| r.request.request_id)
+                .collect(),
+            requests: timers.requests().count(),
+            fired: timers.fired().count(),
+            next_fire_ns: timers.next_fire_time(),
+            time,
+        })
+    }
+}
+
+/// Abbreviated hash for listings.
+fn short(hash: &Hash) -> String {
+    hash.to_string()[..12].to_string()
+}
+
+fn list_line(index: usize, event: &EventEnvelope) -> String {
+    format!(
+        "{index:>5} {} {:<13} {}",
+        short(&event.event_id()),
+        inspect::kind_name(event.kind()),
+        event.observation_type().unwrap_or("")
+    )
+}
+
+fn payload(event: &EventEnvelope, expanded: bool) -> Vec<Line<'static>> {
+    let Ok(value) = event.payload().to_value::<serde_json::Value>() else {
+        return vec![Line::from(format!(
+            "cbor {}",
+            hex::encode(event.payload().as_bytes())
+        ))];
+    };
+    if expanded {
+        return format!("{value:#}")
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect();
+    }
+    let text = value.to_string();
+    let text = match text.char_indices().nth(COLLAPSED_PAYLOAD) {
+        Some((end, _)) => format!("{}… (space expands)", &text[..end]),
+        None => text,
+    };
+    vec![Line::from(text)]
+}
+
+fn details(browser: &Browser<'_>, event: &EventEnvelope) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(format!("event     {}", event.event_id())),
+        Line::from(format!("kind      {}", inspect::kind_name(event.kind()))),
+    ];
+    if let Some(observation_type) = event.observation_type() {
+        lines.push(Line::from(format!("type      {observation_type}")));
+    }
+    if let Some(agent) = event.agent_id() {
+        lines.push(Line::from(format!("agent     {}", agent.as_str())));
+    }
+    if let Some(signature) = event.signature() {
+        lines.push(Line::from(format!(
+            "signature {}",
+            hex::encode(signature.as_bytes())
+        )));
+    }
+    lines.push(Line::from("payload"));
+    lines.extend(payload(event, browser.expanded));
+    lines
+}
+
+fn link_item(browser: &Browser<'_>, link: Link) -> ListItem<'static> {
+    let (label, index) = match link {
+        Link::Parent(index) => ("parent", index),
+        Link::Child(index) => ("child ", index),
+    };
+    let event = &browser.events[index];
+    ListItem::new(format!(
+        "{label} {index:>5} {} {}",
+        short(&event.event_id()),
+        inspect::kind_name(event.kind())
+    ))
+}
+
+fn time_lines(time: &Time) -> Vec<Line<'static>> {
+    if time.domain() == TimeDomain::Unknown {
+        return vec![Line::from("time      unknown")];
+    }
+    vec![
+        Line::from(format!(
+            "time      {} ns ±{}",
+            time.ns(),
+            time.uncertainty_ns()
+        )),
+        Line::from(format!(
+            "from      {}",
+            time.provenance()
+                .iter()
+                .map(short)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    ]
+}
+
+fn view_lines(browser: &Browser<'_>) -> Vec<Line<'static>> {
+    let state = match browser.view_state() {
+        Ok(state) => state,
+        Err(error) => return vec![Line::from(error)],
+    };
+    let mut lines = vec![Line::from(format!("cut       {}", state.cut))];
+    lines.extend(time_lines(&state.time));
+    lines.push(Line::from(format!(
+        "timers    {} requested, {} fired, {} due",
+        state.requests,
+        state.fired,
+        state.pending.len()
+    )));
+    if let Some(next) = state.next_fire_ns {
+        lines.push(Line::from(format!("next fire {next} ns")));
+    }
+    for request in &state.pending {
+        lines.push(Line::from(format!("due       {}", short(request))));
+    }
+    lines
+}
+
+const HELP: &str =
+    "↑↓/jk move  PgUp/PgDn  g/G  space payload  Tab link  ⏎ follow  p/c parent/child  b back  q quit";
+
+/// Render `browser` into `frame`.
+pub fn draw(frame: &mut Frame<'_>, browser: &Browser<'_>) {
+    let [main, help] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+    let [list_area, side] =
+        Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)]).areas(main);
+    let links = browser.links();
+    let [detail_area, links_area, views_area] = Layout::vertical([
+        Constraint::Min(6),
+        Constraint::Length(links.len().min(8) as u16 + 2),
+        Constraint::Length(8),
+    ])
+    .areas(side);
+
+    let highlight = Style::default().add_modifier(Modifier::REVERSED);
+    let items: Vec<ListItem<'_>> = browser
+        .events
+        .iter()
+        .enumerate()
+        .map(|(index, event)| ListItem::new(list_line(index, event)))
+        .collect();
+    let mut list_state = ListState::default().with_selected(Some(browser.selected));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" worldline ({} events) ", browser.events.len())),
+            )
+            .highlight_style(highlight),
+        list_area,
+        &mut list_state,
+    );
+
+    let detail = browser
+        .event()
+        .map(|event| details(browser, event))
+        .unwrap_or_else(|| vec![Line::from("empty worldline")]);
+    frame.render_widget(
+        Paragraph::new(detail)
+            .block(Block::default().borders(Borders::ALL).title(" event "))
+            .wrap(Wrap { trim: false }),
+        detail_area,
+    );
+
+    let mut link_state = ListState::default().with_selected(browser.link().map(|_| browser.link));
+    frame.render_stateful_widget(
+        List::new(links.iter().map(|link| link_item(browser, *link)))
+            .block(Block::default().borders(Borders::ALL).title(" links "))
+            .highlight_style(highlight),
+        links_area,
+        &mut link_state,
+    );
+
+    frame.render_widget(
+        Paragraph::new(view_lines(browser)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" views ({}) ", browser.clocks.policy().name())),
+        ),
+        views_area,
+    );
+    frame.render_widget(Paragraph::new(HELP), help);
+}
+
+/// Browse `store` on the terminal until the user quits.
+///
+/// # Errors
+///
+/// Terminal I/O errors. The terminal is restored either way.
+pub fn run(store: &MemoryEventStore, clock_policy: ClockPolicyId) -> std::io::Result<()> {
+    let mut browser = Browser::new(store, clock_policy);
+    let mut terminal = ratatui::init();
+    let result = (|| {
+        while !browser.quit {
+            terminal.draw(|frame| draw(frame, &browser))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    browser.handle(key.code);
+                }
+            }
+        }
+        Ok(())
+    })();
+    ratatui::restore();
+    result
+}
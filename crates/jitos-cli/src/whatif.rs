@@ -0,0 +1,309 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Policy What-If
+//!
+//! Forks a worldline prefix under a ClockPolicy or SchedulerPolicy
+//! [`DeltaSpec`] and compares the registered views on both branches.
+//!
+//! A ClockPolicy delta only changes how evidence is interpreted, so the
+//! views are refolded over the same prefix (`events[..cut]`) under each
+//! policy. Rewrites recorded before the cut are facts, so the graph digest
+//! is shared — it is reported so a reviewer can confirm which state the
+//! comparison was made against.
+//!
+//! Refolding never re-executes a SLAP, so it could not show a scheduler
+//! policy's effect. SchedulerPolicy deltas are run by [`crate::fork::rerun`]
+//! instead: the fork re-executes the recorded ticks after the cut under the
+//! new policy, and both branches are compared at the end of the worldline,
+//! each with its own graph.
+//!
+//! [`compare`] builds the same table from two live runtimes instead, e.g. a
+//! parent and the branch [`jitos_runtime::BranchManager::fork`] parted from
+//! it, each with its own views, graph and scheduler policy.
+
+use jitos_core::canonical::CanonicalError;
+use jitos_core::delta::{DeltaKind, DeltaSpec};
+use jitos_core::events::EventEnvelope;
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_graph::WarpGraph;
+use jitos_runtime::Runtime;
+use jitos_scheduler::SchedulerPolicy;
+use jitos_views::{ClockError, ClockPolicyId, ClockView, Time, TimeDomain, TimerError, TimerView};
+use std::collections::BTreeSet;
+use std::fmt;
+use thiserror::Error;
+
+use crate::fork::{self, ForkError};
+
+/// What-if errors.
+#[derive(Debug, Error)]
+pub enum WhatIfError {
+    #[error("cut {cut} exceeds event sequence length {len}")]
+    CutOutOfBounds { cut: usize, len: usize },
+    #[error("what-if supports ClockPolicy and SchedulerPolicy deltas only, got {0}")]
+    UnsupportedDelta(&'static str),
+    #[error("clock policy {0} is not a known ClockPolicyId")]
+    UnknownClockPolicy(Hash),
+    #[error("clock view: {0}")]
+    Clock(#[from] ClockError),
+    #[error("timer view: {0}")]
+    Timer(#[from] TimerError),
+    #[error("graph digest: {0}")]
+    Graph(#[from] CanonicalError),
+    #[error("re-execution: {0}")]
+    Rerun(Box<ForkError>),
+}
+
+/// View state on one side of the comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Branch {
+    pub clock_policy: ClockPolicyId,
+    pub time: Time,
+    /// Request IDs of timers due at `time`, in (fire time, request ID) order.
+    pub pending_timers: Vec<Hash>,
+    pub graph_digest: Option<Hash>,
+    /// Scheduler policy the branch runs under, if known.
+    pub scheduler_policy: Option<Hash>,
+}
+
+/// Side-by-side result of a what-if run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub cut: usize,
+    pub delta: Hash,
+    pub description: String,
+    pub base: Branch,
+    pub fork: Branch,
+}
+
+impl Comparison {
+    /// True if the delta changed any view or the graph.
+    pub fn diverges(&self) -> bool {
+        self.base.time != self.fork.time
+            || self.base.pending_timers != self.fork.pending_timers
+            || self.base.graph_digest != self.fork.graph_digest
+    }
+}
+
+/// Compare `store` at `cut` under `base` and under `base` + `delta`.
+///
+/// For a ClockPolicy delta the views are refolded over `events[..cut]`;
+/// `graph` is the state at the cut, if known, and its digest is reported on
+/// both sides. A SchedulerPolicy delta is handed to [`fork::rerun`], which
+/// needs `store` to be a worldline recorded by the runtime; `candidates` are
+/// the extra scheduler policies it may name, and `graph` is not used.
+///
+/// # Errors
+///
+/// Fails for other deltas, for clock policy hashes that do not name a
+/// [`ClockPolicyId`], if a view rejects an event, and with
+/// [`WhatIfError::Rerun`] if re-execution fails.
+pub fn what_if(
+    store: &MemoryEventStore,
+    cut: usize,
+    base: ClockPolicyId,
+    delta: &DeltaSpec,
+    graph: Option<&WarpGraph>,
+    candidates: &[SchedulerPolicy],
+) -> Result<Comparison, WhatIfError> {
+    let events = store.events();
+    if cut > events.len() {
+        return Err(WhatIfError::CutOutOfBounds {
+            cut,
+            len: events.len(),
+        });
+    }
+
+    let fork_policy = match &delta.kind {
+        DeltaKind::ClockPolicy { new_policy } => ClockPolicyId::from_policy_hash(new_policy)
+            .ok_or(WhatIfError::UnknownClockPolicy(*new_policy))?,
+        DeltaKind::SchedulerPolicy { .. } => {
+            return fork::rerun(store, cut, base, delta, candidates)
+                .map_err(|error| WhatIfError::Rerun(Box::new(error)))
+        }
+        DeltaKind::InputMutation { .. } => {
+            return Err(WhatIfError::UnsupportedDelta("InputMutation"))
+        }
+        DeltaKind::TrustPolicy { .. } => return Err(WhatIfError::UnsupportedDelta("TrustPolicy")),
+    };
+
+    let graph_digest = graph.map(WarpGraph::compute_hash_checked).transpose()?;
+    let prefix = &events[..cut];
+
+    Ok(Comparison {
+        cut,
+        delta: delta.hash(),
+        description: delta.description.clone(),
+        base: fold(prefix, base, graph_digest)?,
+        fork: fold(prefix, fork_policy, graph_digest)?,
+    })
+}
+
+/// Compare two live runtimes, `base` and the `fork` parted from it at `cut`
+/// under `delta`.
+///
+/// Each side reports its own views, graph digest and scheduler policy.
+///
+/// # Errors
+///
+/// Fails if a graph cannot be hashed.
+pub fn compare(
+    cut: usize,
+    delta: &DeltaSpec,
+    base: &Runtime,
+    fork: &Runtime,
+) -> Result<Comparison, WhatIfError> {
+    Ok(Comparison {
+        cut,
+        delta: delta.hash(),
+        description: delta.description.clone(),
+        base: branch(base)?,
+        fork: branch(fork)?,
+    })
+}
+
+fn branch(runtime: &Runtime) -> Result<Branch, WhatIfError> {
+    let views = runtime.views();
+    let time = views.clock().now().clone();
+    let pending_timers = views
+        .timers()
+        .pending_timers(&time)
+        .into_iter()
+        .map(|r| r.request.request_id)
+        .collect();
+    Ok(Branch {
+        clock_policy: views.clock().policy(),
+        time,
+        pending_timers,
+        graph_digest: Some(runtime.graph().compute_hash_checked()?),
+        scheduler_policy: Some(runtime.scheduler().policy().policy_hash()),
+    })
+}
+
+fn fold(
+    events: &[EventEnvelope],
+    policy: ClockPolicyId,
+    graph_digest: Option<Hash>,
+) -> Result<Branch, WhatIfError> {
+    let mut clock = ClockView::new(policy);
+    let mut timers = TimerView::new();
+    for event in events {
+        clock.apply_event(event)?;
+        timers.apply_event(event)?;
+    }
+    let time = clock.now().clone();
+    let pending_timers = timers
+        .pending_timers(&time)
+        .into_iter()
+        .map(|r| r.request.request_id)
+        .collect();
+    Ok(Branch {
+        clock_policy: policy,
+        time,
+        pending_timers,
+        graph_digest,
+        scheduler_policy: None,
+    })
+}
+
+/// Abbreviated hash for tables.
+fn short(hash: &Hash) -> String {
+    hash.to_string()[..12].to_string()
+}
+
+fn time_cell(time: &Time) -> (String, String, String) {
+    if time.domain() == TimeDomain::Unknown {
+        return ("unknown".into(), "-".into(), "-".into());
+    }
+    let source = time.provenance().first().map(short).unwrap_or_default();
+    (
+        time.ns().to_string(),
+        time.uncertainty_ns().to_string(),
+        source,
+    )
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn row(f: &mut fmt::Formatter<'_>, label: &str, base: &str, fork: &str) -> fmt::Result {
+            let marker = if base == fork { "" } else { "  *" };
+            writeln!(f, "  {label:<18}{base:<26}{fork:<26}{marker}")
+        }
+
+        writeln!(
+            f,
+            "what-if at cut {}: delta {} ({})",
+            self.cut,
+            short(&self.delta),
+            self.description
+        )?;
+        writeln!(f, "  {:<18}{:<26}{:<26}", "", "base", "fork")?;
+
+        row(
+            f,
+            "clock policy",
+            self.base.clock_policy.name(),
+            self.fork.clock_policy.name(),
+        )?;
+        let policy =
+            |p: &Option<Hash>| p.as_ref().map(short).unwrap_or_else(|| "(recorded)".into());
+        if self.base.scheduler_policy.is_some() || self.fork.scheduler_policy.is_some() {
+            row(
+                f,
+                "scheduler policy",
+                &policy(&self.base.scheduler_policy),
+                &policy(&self.fork.scheduler_policy),
+            )?;
+        }
+
+        let (base_ns, base_unc, base_src) = time_cell(&self.base.time);
+        let (fork_ns, fork_unc, fork_src) = time_cell(&self.fork.time);
+        row(
+            f,
+            "time domain",
+            &format!("{:?}", self.base.time.domain()),
+            &format!("{:?}", self.fork.time.domain()),
+        )?;
+        row(f, "time ns", &base_ns, &fork_ns)?;
+        row(f, "uncertainty ns", &base_unc, &fork_unc)?;
+        row(f, "time source", &base_src, &fork_src)?;
+
+        row(
+            f,
+            "pending timers",
+            &self.base.pending_timers.len().to_string(),
+            &self.fork.pending_timers.len().to_string(),
+        )?;
+        let requests: BTreeSet<&Hash> = self
+            .base
+            .pending_timers
+            .iter()
+            .chain(&self.fork.pending_timers)
+            .collect();
+        for request in requests {
+            let state = |pending: &[Hash]| {
+                if pending.contains(request) {
+                    "due"
+                } else {
+                    "-"
+                }
+            };
+            row(
+                f,
+                &format!("  {}", short(request)),
+                state(&self.base.pending_timers),
+                state(&self.fork.pending_timers),
+            )?;
+        }
+
+        let digest = |d: &Option<Hash>| d.as_ref().map(short).unwrap_or_else(|| "-".into());
+        row(
+            f,
+            "graph digest",
+            &digest(&self.base.graph_digest),
+            &digest(&self.fork.graph_digest),
+        )
+    }
+}
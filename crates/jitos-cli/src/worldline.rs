@@ -0,0 +1,53 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Worldline Files
+//!
+//! A worldline file is the canonical CBOR (SPEC-0001) encoding of the event
+//! array in canonical worldline order. Loading re-validates every event, so a
+//! file that decodes is a valid [`MemoryEventStore`].
+
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::events::{EventEnvelope, EventError};
+use jitos_core::store::MemoryEventStore;
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors loading or saving a worldline file.
+#[derive(Debug, Error)]
+pub enum WorldlineError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("event {index} rejected: {source}")]
+    InvalidEvent { index: usize, source: EventError },
+}
+
+/// Encode events (in worldline order) as a worldline file body.
+pub fn encode(events: &[EventEnvelope]) -> Result<Vec<u8>, WorldlineError> {
+    Ok(canonical::encode(&events)?)
+}
+
+/// Decode and validate a worldline file body.
+pub fn decode(bytes: &[u8]) -> Result<MemoryEventStore, WorldlineError> {
+    let events: Vec<EventEnvelope> = canonical::decode(bytes)?;
+    let mut store = MemoryEventStore::new();
+    for (index, event) in events.into_iter().enumerate() {
+        store
+            .append(event)
+            .map_err(|source| WorldlineError::InvalidEvent { index, source })?;
+    }
+    Ok(store)
+}
+
+/// Read and validate a worldline file.
+pub fn load(path: &Path) -> Result<MemoryEventStore, WorldlineError> {
+    decode(&std::fs::read(path)?)
+}
+
+/// Write events to a worldline file.
+pub fn save(path: &Path, events: &[EventEnvelope]) -> Result<(), WorldlineError> {
+    std::fs::write(path, encode(events)?)?;
+    Ok(())
+}
@@ -0,0 +1,264 @@
+use jitos_cli::bundle::{self, BundleError};
+use jitos_cli::keys::Ed25519Signer;
+use jitos_cli::keystore::{KdfCost, Keystore};
+use jitos_cli::worldline;
+use jitos_core::blob::{blob_hash, BlobStore, MemoryBlobStore};
+use jitos_core::canonical;
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventId, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_net::{Identity, PeerSigner};
+
+const CHEAP: KdfCost = KdfCost {
+    memory_kib: 64,
+    iterations: 1,
+    parallelism: 1,
+};
+
+fn observation(value: u64, parents: Vec<EventId>) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&value).expect("encode value"),
+        parents,
+        Some("OBS_TEST_V0".to_string()),
+        None,
+        None,
+    )
+    .expect("observation")
+}
+
+/// A chain 1 <- 2 <- 3 with a side branch 4 off 1.
+fn store() -> MemoryEventStore {
+    let one = observation(1, vec![]);
+    let two = observation(2, vec![one.event_id()]);
+    let three = observation(3, vec![two.event_id()]);
+    let four = observation(4, vec![one.event_id()]);
+    let mut store = MemoryEventStore::new();
+    for event in [one, two, three, four] {
+        store.append(event).expect("append");
+    }
+    store
+}
+
+/// Toy keyed signature: only holders of `agent`'s secret can make it.
+fn sign_as(agent: &str, statement: &Hash) -> Signature {
+    let mac =
+        canonical::hash_canonical(&(format!("secret-of-{agent}"), statement)).expect("encode mac");
+    Signature::new(mac.0.to_vec()).expect("signature")
+}
+
+fn signer(agent: &'static str) -> impl PeerSigner {
+    Identity::new(AgentId::new(agent).expect("agent"), move |statement| {
+        sign_as(agent, statement)
+    })
+}
+
+/// Trusts alice only.
+fn trust_alice(agent: &AgentId, statement: &Hash, signature: &Signature) -> bool {
+    agent.as_str() == "alice" && sign_as("alice", statement) == *signature
+}
+
+#[test]
+fn bundle_after_cut_imports_events_and_blobs() {
+    let source = store();
+    let bundle = bundle::export(
+        &source,
+        2,
+        [b"script".to_vec(), b"module".to_vec(), b"script".to_vec()],
+        &signer("alice"),
+    )
+    .expect("export");
+    assert_eq!(bundle.events, source.events()[2..]);
+    let mut requires = vec![source.events()[0].event_id(), source.events()[1].event_id()];
+    requires.sort();
+    assert_eq!(bundle.requires, requires);
+    assert_eq!(bundle.blobs.len(), 2, "duplicate blobs are bundled once");
+    let bundle = bundle::decode(&bundle::encode(&bundle).expect("encode")).expect("decode");
+
+    let mut target = MemoryEventStore::new();
+    for event in &source.events()[..2] {
+        target.append(event.clone()).expect("append prefix");
+    }
+    let mut blobs = MemoryBlobStore::new();
+    let report =
+        bundle::import(&mut target, &mut blobs, bundle.clone(), &trust_alice).expect("import");
+    assert_eq!(report.added, 2);
+    assert_eq!(report.skipped, 0);
+    assert_eq!(report.blobs, 2);
+    assert_eq!(
+        report.attested_by,
+        vec![AgentId::new("alice").expect("agent")]
+    );
+    assert_eq!(target.events(), source.events());
+    assert_eq!(target.heads(), source.heads());
+    assert_eq!(blobs.get(&blob_hash(b"module")), Some(&b"module"[..]));
+
+    let again = bundle::import(&mut target, &mut blobs, bundle, &trust_alice).expect("reimport");
+    assert_eq!((again.added, again.skipped), (0, 2));
+}
+
+#[test]
+fn tampered_untrusted_and_unrooted_bundles_are_refused() {
+    let source = store();
+    let bundle = bundle::export(&source, 2, [b"blob".to_vec()], &signer("alice")).expect("export");
+    let prefix = || {
+        let mut store = MemoryEventStore::new();
+        for event in &source.events()[..2] {
+            store.append(event.clone()).expect("append prefix");
+        }
+        store
+    };
+    let refuse = |bundle: bundle::Bundle, mut store: MemoryEventStore| {
+        let before = store.events().to_vec();
+        let mut blobs = MemoryBlobStore::new();
+        let err = bundle::import(&mut store, &mut blobs, bundle, &trust_alice).unwrap_err();
+        assert_eq!(store.events(), before.as_slice(), "store untouched");
+        assert!(blobs.is_empty(), "blobs untouched");
+        err
+    };
+
+    let mut dropped = bundle.clone();
+    dropped.events.pop();
+    assert!(matches!(
+        refuse(dropped, prefix()),
+        BundleError::BadAttestation(_)
+    ));
+    let mut swapped = bundle.clone();
+    swapped.blobs = vec![b"other".to_vec()];
+    assert!(matches!(
+        refuse(swapped, prefix()),
+        BundleError::BadAttestation(_)
+    ));
+
+    let by_mallory = bundle::export(&source, 2, [], &signer("mallory")).expect("export");
+    assert!(matches!(
+        refuse(by_mallory, prefix()),
+        BundleError::BadAttestation(agent) if agent.as_str() == "mallory"
+    ));
+    let mut unattested = bundle.clone();
+    unattested.attestations.clear();
+    assert!(matches!(
+        refuse(unattested, prefix()),
+        BundleError::Unattested
+    ));
+
+    assert!(matches!(
+        refuse(bundle.clone(), MemoryEventStore::new()),
+        BundleError::MissingBase(_)
+    ));
+    assert!(matches!(
+        bundle::export(&source, 5, [], &signer("alice")),
+        Err(BundleError::CutOutOfBounds { cut: 5, len: 4 })
+    ));
+    let bytes = bundle::encode(&bundle).expect("encode");
+    assert!(bundle::decode(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn reattributed_or_stripped_events_break_the_attestation() {
+    let mut source = store();
+    let signed = observation(5, vec![]).with_signature(
+        AgentId::new("bob").expect("agent"),
+        sign_as("bob", &Hash([5; 32])),
+    );
+    source.append(signed).expect("append signed");
+    let bundle = bundle::export(&source, 4, [], &signer("alice")).expect("export");
+    let import = |bundle: bundle::Bundle| {
+        let mut store = store();
+        let mut blobs = MemoryBlobStore::new();
+        bundle::import(&mut store, &mut blobs, bundle, &trust_alice)
+    };
+    assert_eq!(import(bundle.clone()).expect("import").added, 1);
+
+    // Same event id, other author: ids leave authorship out.
+    let mut reattributed = bundle.clone();
+    reattributed.events[0] = reattributed.events[0].clone().with_signature(
+        AgentId::new("mallory").expect("agent"),
+        sign_as("mallory", &Hash([5; 32])),
+    );
+    assert_eq!(
+        reattributed.events[0].event_id(),
+        bundle.events[0].event_id()
+    );
+    assert!(matches!(
+        import(reattributed),
+        Err(BundleError::BadAttestation(agent)) if agent.as_str() == "alice"
+    ));
+
+    let mut stripped = bundle;
+    stripped.events[0] = observation(5, vec![]);
+    assert!(matches!(
+        import(stripped),
+        Err(BundleError::BadAttestation(_))
+    ));
+}
+
+#[test]
+fn bundle_commands_carry_a_worldline_with_ed25519_attestation() {
+    let dir = std::env::temp_dir().join(format!("jitos-bundle-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("tmp dir");
+    let source = store();
+    let (origin, target) = (dir.join("origin.cbor"), dir.join("target.cbor"));
+    let (keys, pass, blob, out) = (
+        dir.join("keys"),
+        dir.join("pass"),
+        dir.join("blob"),
+        dir.join("out.bundle"),
+    );
+    worldline::save(&origin, source.events()).expect("save");
+    let alice = Ed25519Signer::new(AgentId::new("alice").expect("agent"), [7; 32]);
+    Keystore::open(&keys)
+        .expect("keystore")
+        .with_cost(CHEAP)
+        .insert(&alice, "s3cret")
+        .expect("insert");
+    std::fs::write(&pass, "s3cret\n").expect("write passphrase");
+    std::fs::write(&blob, b"script source").expect("write blob");
+    let public = alice.public_hex();
+    let jitos = || std::process::Command::new(env!("CARGO_BIN_EXE_jitos"));
+
+    let export = jitos()
+        .args(["bundle", "export", "--agent", "alice"])
+        .arg("--store")
+        .arg(&origin)
+        .arg("--keystore")
+        .arg(&keys)
+        .arg("--passphrase-file")
+        .arg(&pass)
+        .arg("--blob")
+        .arg(&blob)
+        .arg("--out")
+        .arg(&out)
+        .output()
+        .expect("run jitos");
+    assert!(export.status.success(), "{export:?}");
+    let stdout = String::from_utf8(export.stdout).expect("utf8");
+    assert!(stdout.contains(&format!("alice={public}")), "{stdout}");
+
+    let import = |trust: String| {
+        jitos()
+            .args(["bundle", "import", "--trust", &trust])
+            .arg("--store")
+            .arg(&target)
+            .arg("--bundle")
+            .arg(&out)
+            .arg("--blobs")
+            .arg(dir.join("blobs"))
+            .output()
+            .expect("run jitos")
+    };
+    let wrong = Ed25519Signer::new(AgentId::new("alice").expect("agent"), [8; 32]).public_hex();
+    let refused = import(format!("alice={wrong}"));
+    assert!(!refused.status.success());
+    assert!(!target.exists(), "a refused bundle writes nothing");
+
+    let accepted = import(format!("alice={public}"));
+    let imported = worldline::load(&target);
+    let written = std::fs::read(
+        dir.join("blobs")
+            .join(blob_hash(b"script source").to_string()),
+    );
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(accepted.status.success(), "{accepted:?}");
+    assert_eq!(imported.expect("load").events(), source.events());
+    assert_eq!(written.expect("blob file"), b"script source");
+}
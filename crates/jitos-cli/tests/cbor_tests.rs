@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use jitos_cli::cbor::{self, CborError, Diag};
+use jitos_core::canonical::{self, CanonicalError};
+
+#[test]
+fn canonical_values_print_in_diagnostic_notation() {
+    let value = BTreeMap::from([
+        ("bytes", ciborium::Value::Bytes(vec![0xca, 0xfe])),
+        (
+            "list",
+            ciborium::Value::Array(vec![
+                ciborium::Value::Integer((-3).into()),
+                ciborium::Value::Float(1.5),
+                ciborium::Value::Text("say \"hi\"".to_string()),
+                ciborium::Value::Array(vec![]),
+            ]),
+        ),
+        ("none", ciborium::Value::Null),
+    ]);
+    let bytes = canonical::encode(&value).expect("encode");
+    let diagnosis = cbor::diagnose(&bytes).expect("diagnose");
+    assert!(diagnosis.is_canonical());
+    assert_eq!(
+        Diag(&diagnosis.value).to_string(),
+        concat!(
+            "{\n",
+            "  \"list\": [\n",
+            "    -3,\n",
+            "    1.5,\n",
+            "    \"say \\\"hi\\\"\",\n",
+            "    []\n",
+            "  ],\n",
+            "  \"none\": null,\n",
+            "  \"bytes\": h'cafe'\n",
+            "}"
+        )
+    );
+    assert!(diagnosis
+        .to_string()
+        .ends_with(&format!("ok: {} bytes of canonical CBOR\n", bytes.len())));
+    assert_eq!(diagnosis.fix().expect("fix"), bytes);
+}
+
+#[test]
+fn violations_are_located_and_fixed() {
+    let diagnose = |hex: &str| cbor::diagnose(&cbor::input(hex).expect("hex")).expect("diagnose");
+
+    // {"b": 1, "a": 2}: keys out of order at the second key
+    let unsorted = diagnose("a2 6162 01 6161 02");
+    let violation = unsorted.violation.as_ref().expect("violation");
+    assert_eq!(
+        (violation.offset, &violation.error),
+        (4, &CanonicalError::MapKeyOrder)
+    );
+    assert!(unsorted
+        .to_string()
+        .ends_with("non-canonical: map keys not strictly increasing at byte 4\n"));
+    assert_eq!(
+        unsorted.fix().expect("fix"),
+        hex::decode("a2616102616201").expect("hex")
+    );
+
+    // [1, 1.0 as float16]: the half float is integral
+    let half = diagnose("0x8201f93c00");
+    assert_eq!(
+        half.violation.as_ref().map(|v| (v.offset, &v.error)),
+        Some((2, &CanonicalError::NonCanonicalFloat))
+    );
+    let fixed = half.fix().expect("fix");
+    assert_eq!(fixed, [0x82, 0x01, 0x01]);
+    assert_eq!(canonical::check(&fixed), Ok(()));
+
+    // Tags have no canonical form
+    let tagged = diagnose("c11a5f5e1000");
+    assert_eq!(Diag(&tagged.value).to_string(), "1(1600000000)");
+    assert!(matches!(
+        tagged.fix(),
+        Err(CborError::Unfixable(CanonicalError::Tag))
+    ));
+
+    assert!(matches!(
+        cbor::diagnose(&[0x83, 0x01]),
+        Err(CborError::NotCbor(reason)) if reason == "incomplete input at byte 2"
+    ));
+    assert!(matches!(cbor::input("not hex"), Err(CborError::Input(_))));
+}
+
+#[test]
+fn cbor_diag_command_exits_non_zero_unless_fixed() {
+    let dir = std::env::temp_dir().join(format!("jitos-cbor-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("tmp dir");
+    let (good, fixed) = (dir.join("good.cbor"), dir.join("fixed.cbor"));
+    std::fs::write(&good, canonical::encode(&vec![1u8, 2]).expect("encode")).expect("write");
+    let jitos = |args: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_jitos"))
+            .args(["cbor", "diag"])
+            .args(args)
+            .output()
+            .expect("run jitos")
+    };
+
+    let from_file = jitos(&[good.to_str().expect("utf8 path")]);
+    let rejected = jitos(&["a1190001f5"]);
+    let repaired = jitos(&[
+        "a1190001f5",
+        "--fix",
+        "--out",
+        fixed.to_str().expect("utf8 path"),
+    ]);
+    let written = std::fs::read(&fixed);
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(from_file.status.success(), "{from_file:?}");
+    assert!(String::from_utf8_lossy(&from_file.stdout).starts_with("[\n  1,\n  2\n]\nok: "));
+    assert!(!rejected.status.success());
+    assert!(String::from_utf8_lossy(&rejected.stdout)
+        .contains("non-canonical: non-canonical integer width at byte 1"));
+    assert!(repaired.status.success(), "{repaired:?}");
+    assert!(String::from_utf8_lossy(&repaired.stdout).ends_with("fixed: a101f5\n"));
+    assert_eq!(written.expect("fixed file"), [0xa1, 0x01, 0xf5]);
+}
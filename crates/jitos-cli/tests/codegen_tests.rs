@@ -0,0 +1,181 @@
+use ciborium::Value;
+use jitos_cli::codegen;
+use jitos_core::canonical;
+use jitos_views::{payload_schemas, FieldType, PayloadSchema};
+use serde_json::json;
+
+/// A field value for each wire type: small values, or ones at the top of
+/// their range (u64::MAX, 300 bytes, multi-byte UTF-8).
+fn example(schema: &PayloadSchema, large: bool) -> (Value, serde_json::Value) {
+    let mut cbor = Vec::new();
+    let mut js = serde_json::Map::new();
+    for (i, field) in schema.fields.iter().enumerate() {
+        let (value, json) = match field.ty {
+            FieldType::U64 => {
+                let n = if large {
+                    u64::MAX - i as u64
+                } else {
+                    i as u64 * 30
+                };
+                (Value::Integer(n.into()), json!({ "u64": n.to_string() }))
+            }
+            FieldType::Text | FieldType::AgentId => {
+                let text = if large { "héllo/世界" } else { "a" };
+                (Value::Text(text.to_string()), json!({ "text": text }))
+            }
+            FieldType::Bytes | FieldType::Hash => {
+                let len = match (field.ty, large) {
+                    (FieldType::Hash, _) => 32,
+                    (_, true) => 300,
+                    (_, false) => 3,
+                };
+                let bytes: Vec<u8> = (0..len).map(|b| (b * 7 + i) as u8).collect();
+                let items = bytes.iter().map(|b| Value::Integer((*b).into())).collect();
+                (Value::Array(items), json!({ "bytes": hex::encode(&bytes) }))
+            }
+            FieldType::Enum(e) => {
+                let variant = e.variants[if large { e.variants.len() - 1 } else { 0 }];
+                (Value::Text(variant.to_string()), json!({ "text": variant }))
+            }
+        };
+        cbor.push((Value::Text(field.name.to_string()), value));
+        js.insert(field.name.to_string(), json);
+    }
+    (Value::Map(cbor), serde_json::Value::Object(js))
+}
+
+#[test]
+fn registry_matches_the_rust_payload_types() {
+    for schema in payload_schemas() {
+        for large in [false, true] {
+            let bytes = canonical::encode(&example(schema, large).0).expect("encode");
+            let round_trip = (schema.round_trip)(&bytes)
+                .unwrap_or_else(|e| panic!("{} does not decode its schema: {e}", schema.name));
+            assert_eq!(round_trip, bytes, "{}", schema.name);
+        }
+        // A field the Rust type requires is missing.
+        let (Value::Map(mut entries), _) = example(schema, false) else {
+            unreachable!()
+        };
+        entries.pop();
+        let short = canonical::encode(&Value::Map(entries)).expect("encode");
+        assert!((schema.round_trip)(&short).is_err(), "{}", schema.name);
+
+        let order: Vec<_> = schema.canonical_fields().iter().map(|f| f.name).collect();
+        let mut sorted = order.clone();
+        sorted.sort_by_key(|name| canonical::encode(name).expect("encode"));
+        assert_eq!(order, sorted, "{}", schema.name);
+    }
+}
+
+#[test]
+fn generated_module_declares_every_payload() {
+    let generated = codegen::generate(payload_schemas());
+    let (js, dts) = (&generated.javascript, &generated.declarations);
+
+    for schema in payload_schemas() {
+        let name = schema.name;
+        assert!(js.contains(&format!("export function encode{name}(value) {{")));
+        assert!(js.contains(&format!("export function decode{name}(bytes) {{")));
+        assert!(dts.contains(&format!("export interface {name} {{")));
+        assert!(dts.contains(&format!(
+            "export declare function decode{name}(bytes: Uint8Array): {name};"
+        )));
+        if let Some(tag) = schema.observation_type {
+            assert!(js.contains(&format!("  [{tag}]: Object.freeze({{ encode: encode{name}")));
+            assert!(dts.contains(&format!("  readonly {tag}: Codec<{name}>;")));
+        }
+    }
+    assert!(dts
+        .contains("export type ClockSource = \"Monotonic\" | \"Rtc\" | \"Ntp\" | \"PeerClaim\";"));
+    assert!(dts.contains("  request_id: Uint8Array;"));
+    assert!(dts.contains("  value_ns: bigint;"));
+    assert!(
+        js.contains(
+            "  r.key(\"unix_ns\");\n  const unix_ns = r.u64();\n  r.key(\"monotonic_ns\");"
+        ),
+        "fields are read in canonical key order, shorter names first"
+    );
+    assert_eq!(
+        codegen::generate(payload_schemas()),
+        generated,
+        "generation is deterministic"
+    );
+}
+
+#[test]
+fn javascript_codecs_are_byte_compatible_with_rust() {
+    if std::process::Command::new("node")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        eprintln!("node not found; skipping the JavaScript round trip");
+        return;
+    }
+    let dir = std::env::temp_dir().join(format!("jitos-codegen-{}", std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    let written = codegen::write(&dir, payload_schemas()).expect("write");
+    assert_eq!(written.len(), 2);
+
+    let mut cases = Vec::new();
+    let mut rejects = Vec::new();
+    for schema in payload_schemas() {
+        for large in [false, true] {
+            let (value, js) = example(schema, large);
+            let cbor = hex::encode(canonical::encode(&value).expect("encode"));
+            if !large {
+                rejects.push(json!({ "schema": schema.name, "cbor": format!("{cbor}00") }));
+            }
+            cases.push(json!({ "schema": schema.name, "value": js, "cbor": cbor }));
+        }
+    }
+    // { "key": "a" } with the text length in a non-shortest form.
+    rejects.push(json!({ "schema": "KvDelete", "cbor": "a1636b6579780161" }));
+    // Keys out of canonical order.
+    rejects.push(json!({ "schema": "LogicalTime", "cbor": "a26564745f6e7301647469636b02" }));
+    let fixture = json!({ "cases": cases, "rejects": rejects });
+    std::fs::write(dir.join("fixture.json"), fixture.to_string()).expect("write fixture");
+    std::fs::write(
+        dir.join("check.mjs"),
+        r#"
+import * as loom from "./loom-payloads.js";
+import { readFileSync } from "node:fs";
+
+const { cases, rejects } = JSON.parse(readFileSync(new URL("./fixture.json", import.meta.url)));
+const hex = (bytes) => Buffer.from(bytes).toString("hex");
+const unhex = (text) => new Uint8Array(Buffer.from(text, "hex"));
+const fromJson = (v) => ("u64" in v ? BigInt(v.u64) : "bytes" in v ? unhex(v.bytes) : v.text);
+
+for (const { schema, value, cbor } of cases) {
+  const payload = Object.fromEntries(Object.entries(value).map(([k, v]) => [k, fromJson(v)]));
+  const encoded = hex(loom[`encode${schema}`](payload));
+  if (encoded !== cbor) throw new Error(`${schema}: encoded ${encoded}, Rust wrote ${cbor}`);
+  const again = hex(loom[`encode${schema}`](loom[`decode${schema}`](unhex(cbor))));
+  if (again !== cbor) throw new Error(`${schema}: round trip gave ${again}`);
+}
+for (const { schema, cbor } of rejects) {
+  try {
+    loom[`decode${schema}`](unhex(cbor));
+  } catch (e) {
+    if (e instanceof loom.CanonicalError) continue;
+    throw e;
+  }
+  throw new Error(`${schema}: accepted non-canonical ${cbor}`);
+}
+console.log(`ok ${cases.length} ${rejects.length}`);
+"#,
+    )
+    .expect("write script");
+
+    let output = std::process::Command::new("node")
+        .arg(dir.join("check.mjs"))
+        .output()
+        .expect("run node");
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("ok {} {}\n", cases.len(), rejects.len())
+    );
+}
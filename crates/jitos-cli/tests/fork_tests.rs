@@ -0,0 +1,246 @@
+use jitos_cli::fork::{self, ForkError};
+use jitos_cli::{whatif, worldline};
+use jitos_core::delta::DeltaSpec;
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::{Proposal, Slap};
+use jitos_runtime::{BranchError, Host, Runtime, Views};
+use jitos_scheduler::{EchoScheduler, SchedulerPolicy};
+use jitos_views::{ClockPolicyId, ClockSample, ClockSource, OBS_CLOCK_SAMPLE_V0};
+
+fn sample(source: ClockSource, value_ns: u64) -> EventEnvelope {
+    let sample = ClockSample {
+        source,
+        value_ns,
+        uncertainty_ns: 10,
+    };
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&sample).expect("encode sample"),
+        vec![],
+        Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+        None,
+        None,
+    )
+    .expect("observation")
+}
+
+/// Monotonic and NTP samples every tick, and two new nodes.
+struct Sampler;
+
+impl Host for Sampler {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        vec![
+            sample(ClockSource::Monotonic, 100 * (tick + 1)),
+            sample(ClockSource::Ntp, 5_000 + tick),
+        ]
+    }
+
+    fn proposals(&mut self, tick: u64, _views: &Views) -> Vec<Proposal> {
+        [tick, tick + 100]
+            .map(|n| {
+                Slap::CreateNode {
+                    node_type: "task".to_string(),
+                    payload_bytes: n.to_le_bytes().to_vec(),
+                }
+                .into()
+            })
+            .to_vec()
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![7]).expect("signature")
+    }
+}
+
+/// Four recorded ticks and the cut after the second.
+fn recorded() -> (Runtime, usize) {
+    let mut runtime = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+    let mut cut = 0;
+    for tick in 0..4 {
+        runtime.tick(&mut Sampler).expect("tick");
+        if tick == 1 {
+            cut = runtime.store().len();
+        }
+    }
+    (runtime, cut)
+}
+
+fn prefer_ntp() -> DeltaSpec {
+    DeltaSpec::new_clock_policy(
+        ClockPolicyId::TrustNtpLatest.policy_hash(),
+        "prefer ntp".to_string(),
+    )
+    .expect("delta")
+}
+
+#[test]
+fn clock_fork_shares_the_prefix_and_diverges_on_the_clock() {
+    let (runtime, cut) = recorded();
+    let store = runtime.store();
+    let forked = fork::fork(
+        store,
+        cut,
+        ClockPolicyId::TrustMonotonicLatest,
+        "ntp",
+        &prefer_ntp(),
+        &[],
+    )
+    .expect("fork");
+
+    assert_eq!(forked.origin.parent, "main");
+    assert_eq!(forked.origin.tick, 2);
+    assert_eq!(forked.origin.delta, prefer_ntp().hash());
+    assert_eq!(&forked.events[..cut], &store.events()[..cut]);
+    assert_eq!(forked.events.len(), cut + 1, "the fork records its origin");
+    assert_eq!(forked.events[cut].event_id(), forked.origin.event);
+    assert!(forked.heads.contains(&forked.origin.event));
+    assert!(!forked.heads.contains(&store.events()[cut - 1].event_id()));
+
+    assert!(forked.comparison.diverges());
+    assert_eq!(
+        forked.comparison.fork.clock_policy,
+        ClockPolicyId::TrustNtpLatest
+    );
+    assert_eq!(
+        forked.comparison.base.graph_digest,
+        Some(runtime.receipts()[1].state_hash)
+    );
+    assert_eq!(
+        forked.comparison.fork.graph_digest,
+        forked.comparison.base.graph_digest
+    );
+    let report = forked.to_string();
+    assert!(report.starts_with("forked ntp from main at tick 2"));
+    assert!(report.contains(&format!("head      {}", forked.origin.event)));
+}
+
+#[test]
+fn forks_need_a_tick_boundary_and_a_known_policy() {
+    let (runtime, cut) = recorded();
+    let store = runtime.store();
+    let at = |cut: usize, delta: &DeltaSpec, candidates: &[SchedulerPolicy]| {
+        fork::fork(
+            store,
+            cut,
+            ClockPolicyId::TrustMonotonicLatest,
+            "fork",
+            delta,
+            candidates,
+        )
+    };
+
+    assert!(matches!(
+        at(cut + 1, &prefer_ntp(), &[]),
+        Err(ForkError::NotATickBoundary { boundary, .. }) if boundary == cut
+    ));
+    assert!(matches!(
+        at(store.len() + 1, &prefer_ntp(), &[]),
+        Err(ForkError::CutOutOfBounds { .. })
+    ));
+
+    let budgeted = SchedulerPolicy {
+        tick_budget: Some(5),
+        ..SchedulerPolicy::default()
+    };
+    let delta = DeltaSpec::new_scheduler_policy(budgeted.policy_hash(), "budget".to_string())
+        .expect("delta");
+    assert!(matches!(
+        at(cut, &delta, &[]),
+        Err(ForkError::UnknownSchedulerPolicy(hash)) if hash == budgeted.policy_hash()
+    ));
+    let forked = at(cut, &delta, std::slice::from_ref(&budgeted)).expect("fork");
+    assert_eq!(forked.events.len(), cut + 2, "origin and the new policy");
+    assert_eq!(
+        forked.events[cut + 1].event_id(),
+        budgeted.to_policy_context().expect("policy").event_id()
+    );
+    assert!(!forked.comparison.diverges());
+    assert_eq!(
+        forked.comparison.base.scheduler_policy,
+        Some(SchedulerPolicy::default().policy_hash())
+    );
+    assert_eq!(
+        forked.comparison.fork.scheduler_policy,
+        Some(budgeted.policy_hash())
+    );
+
+    let trust =
+        DeltaSpec::new_trust_policy(vec![AgentId::new("alice").expect("agent")], "t".into())
+            .expect("delta");
+    assert!(matches!(
+        at(cut, &trust, &[]),
+        Err(ForkError::Branch(BranchError::UnsupportedDelta(
+            "TrustPolicy"
+        )))
+    ));
+}
+
+#[test]
+fn scheduler_what_if_reexecutes_the_ticks_after_the_cut() {
+    let (runtime, cut) = recorded();
+    // Only the first SLAP of each tick runs.
+    let starved = SchedulerPolicy {
+        tick_budget: Some(0),
+        ..SchedulerPolicy::default()
+    };
+    let delta =
+        DeltaSpec::new_scheduler_policy(starved.policy_hash(), "one slap per tick".to_string())
+            .expect("delta");
+    let cmp = whatif::what_if(
+        runtime.store(),
+        cut,
+        ClockPolicyId::TrustMonotonicLatest,
+        &delta,
+        None,
+        std::slice::from_ref(&starved),
+    )
+    .expect("what-if");
+
+    let last = runtime.receipts().last().expect("receipt");
+    assert_eq!(cmp.base.graph_digest, Some(last.state_hash));
+    assert_ne!(cmp.fork.graph_digest, cmp.base.graph_digest);
+    assert_eq!(cmp.fork.scheduler_policy, Some(starved.policy_hash()));
+    assert!(cmp.diverges());
+}
+
+#[test]
+fn fork_command_writes_the_branch_and_rejects_a_tampered_delta() {
+    let (runtime, cut) = recorded();
+    let dir = std::env::temp_dir().join(format!("jitos-fork-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("tmp dir");
+    let (store, delta, tampered) = (
+        dir.join("worldline.cbor"),
+        dir.join("delta.json"),
+        dir.join("tampered.json"),
+    );
+    worldline::save(&store, runtime.store().events()).expect("save");
+    let json = serde_json::to_value(prefer_ntp()).expect("json");
+    std::fs::write(&delta, json.to_string()).expect("write");
+    let mut altered = json;
+    altered["description"] = "prefer monotonic".into();
+    std::fs::write(&tampered, altered.to_string()).expect("write");
+    let jitos = |delta: &std::path::Path| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_jitos"))
+            .args(["fork", "--name", "ntp", "--at", &cut.to_string()])
+            .arg("--store")
+            .arg(&store)
+            .arg("--delta")
+            .arg(delta)
+            .output()
+            .expect("run jitos")
+    };
+
+    let (ok, refused) = (jitos(&delta), jitos(&tampered));
+    let branch = worldline::load(&dir.join("worldline.ntp.cbor"));
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(ok.status.success(), "{ok:?}");
+    let stdout = String::from_utf8_lossy(&ok.stdout);
+    let branch = branch.expect("branch worldline");
+    assert_eq!(branch.len(), cut + 1);
+    for head in branch.heads() {
+        assert!(stdout.contains(&format!("head      {head}")), "{stdout}");
+    }
+    assert!(stdout.contains("trust_ntp_latest"));
+    assert!(!refused.status.success());
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("Invalid hash"));
+}
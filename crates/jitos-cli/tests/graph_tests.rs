@@ -0,0 +1,221 @@
+use jitos_cli::graph::{self, Format, GraphDumpError, Point};
+use jitos_cli::worldline;
+use jitos_core::events::{CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::{Hash, Proposal, Slap};
+use jitos_graph::snapshot::decode_snapshot;
+use jitos_runtime::{Host, Runtime, Views};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::{ClockPolicyId, ClockSample, ClockSource, OBS_CLOCK_SAMPLE_V0};
+
+/// One clock sample per tick and whatever SLAPs the test queued.
+#[derive(Default)]
+struct Queued {
+    next: Vec<Proposal>,
+}
+
+impl Host for Queued {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        let sample = ClockSample {
+            source: ClockSource::Monotonic,
+            value_ns: 1_000 * (tick + 1),
+            uncertainty_ns: 0,
+        };
+        vec![EventEnvelope::new_observation(
+            CanonicalBytes::from_value(&sample).expect("encode sample"),
+            vec![],
+            Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+            None,
+            None,
+        )
+        .expect("observation")]
+    }
+
+    fn proposals(&mut self, _tick: u64, _views: &Views) -> Vec<Proposal> {
+        std::mem::take(&mut self.next)
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![5]).expect("signature")
+    }
+}
+
+/// Tick 0 creates a task and a "quoted" worker, tick 1 connects them, tick
+/// 2 adds a third node.
+fn recorded() -> Runtime {
+    let mut runtime = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+    let node = |node_type: &str| -> Proposal {
+        Slap::CreateNode {
+            node_type: node_type.to_string(),
+            payload_bytes: vec![0xab],
+        }
+        .into()
+    };
+    let mut host = Queued {
+        next: vec![node("task"), node("\"worker\"")],
+    };
+    runtime.tick(&mut host).expect("tick 0");
+    let id = |node_type: &str| {
+        runtime
+            .graph()
+            .nodes
+            .values()
+            .find(|n| n.node_type == node_type)
+            .expect("node")
+            .id
+    };
+    host.next = vec![Slap::Connect {
+        source: id("task"),
+        target: id("\"worker\""),
+        edge_type: "assigned<to>".to_string(),
+    }
+    .into()];
+    runtime.tick(&mut host).expect("tick 1");
+    host.next = vec![node("late")];
+    runtime.tick(&mut host).expect("tick 2");
+    runtime
+}
+
+/// Number of events up to and including tick `tick`'s Commit.
+fn cut_after(runtime: &Runtime, tick: usize) -> usize {
+    let digest = runtime.receipts()[tick].digest().expect("digest");
+    runtime
+        .store()
+        .events()
+        .iter()
+        .position(|e| {
+            e.payload()
+                .to_value::<jitos_core::Receipt>()
+                .is_ok_and(|r| r.digest().is_ok_and(|d| d == digest))
+        })
+        .expect("commit")
+        + 1
+}
+
+#[test]
+fn graph_is_rebuilt_at_a_cut_or_receipt_and_rendered() {
+    let runtime = recorded();
+    let store = runtime.store();
+    let state = runtime.receipts()[1].state_hash;
+    let cut = cut_after(&runtime, 1);
+
+    let at_cut = graph::reconstruct(store, Point::Cut(cut), ClockPolicyId::TrustMonotonicLatest)
+        .expect("at cut");
+    assert_eq!((at_cut.tick, at_cut.state, at_cut.cut), (1, state, cut));
+    let digest = runtime.receipts()[1].digest().expect("digest");
+    let by_receipt = graph::reconstruct(
+        store,
+        Point::Receipt(digest),
+        ClockPolicyId::TrustMonotonicLatest,
+    )
+    .expect("by receipt");
+    assert_eq!(by_receipt.state, state);
+    let commit = store.events()[cut - 1].event_id();
+    let by_commit = graph::reconstruct(
+        store,
+        Point::Receipt(commit),
+        ClockPolicyId::TrustMonotonicLatest,
+    )
+    .expect("by commit");
+    assert_eq!(by_commit.cut, cut);
+    let mid_tick = graph::reconstruct(
+        store,
+        Point::Cut(cut + 1),
+        ClockPolicyId::TrustMonotonicLatest,
+    )
+    .expect("mid tick");
+    assert_eq!(mid_tick.state, state, "a partial tick is not applied");
+
+    let dot = String::from_utf8(at_cut.render(Format::Dot).expect("dot")).expect("utf8");
+    assert!(dot.starts_with(&format!("// state {state} after tick 1\ndigraph warp {{\n")));
+    assert_eq!(dot.matches(" [label=").count(), 3, "{dot}");
+    assert!(dot.contains(r#"\"worker\"\n"#), "{dot}");
+    assert!(dot.contains("-> ") && dot.contains("[label=\"assigned<to>\"]"));
+
+    let xml = String::from_utf8(at_cut.render(Format::Graphml).expect("graphml")).expect("utf8");
+    assert_eq!(xml.matches("<node id=").count(), 2);
+    assert_eq!(xml.matches("<edge id=").count(), 1);
+    assert!(xml.contains("<data key=\"type\">assigned&lt;to&gt;</data>"));
+    assert!(xml.contains("<data key=\"type\">&quot;worker&quot;</data>"));
+    assert!(xml.contains("<data key=\"payload\">ab</data>"));
+
+    let cbor = at_cut.render(Format::Cbor).expect("cbor");
+    let decoded = decode_snapshot(&state, &cbor).expect("snapshot");
+    assert_eq!(decoded.compute_hash(), state);
+}
+
+#[test]
+fn points_without_a_recorded_tick_are_refused() {
+    let runtime = recorded();
+    let store = runtime.store();
+    let reconstruct = |point| graph::reconstruct(store, point, ClockPolicyId::TrustMonotonicLatest);
+
+    assert!(matches!(
+        reconstruct(Point::Cut(1)),
+        Err(GraphDumpError::NoTick(1))
+    ));
+    assert!(matches!(
+        reconstruct(Point::Cut(store.len() + 1)),
+        Err(GraphDumpError::CutOutOfBounds { .. })
+    ));
+    let unknown = Hash([3; 32]);
+    assert!(matches!(
+        reconstruct(Point::Receipt(unknown)),
+        Err(GraphDumpError::UnknownReceipt(hash)) if hash == unknown
+    ));
+
+    assert_eq!(Point::parse("12").expect("cut"), Point::Cut(12));
+    assert_eq!(
+        Point::parse(&unknown.to_string()).expect("hash"),
+        Point::Receipt(unknown)
+    );
+    assert!(matches!(
+        Point::parse("abc"),
+        Err(GraphDumpError::MalformedPoint(_))
+    ));
+    assert_eq!(
+        Format::from_name("graphml").expect("format"),
+        Format::Graphml
+    );
+    assert!(matches!(
+        Format::from_name("svg"),
+        Err(GraphDumpError::UnknownFormat(_))
+    ));
+}
+
+#[test]
+fn graph_dump_command_writes_a_verified_snapshot() {
+    let runtime = recorded();
+    let dir = std::env::temp_dir().join(format!("jitos-graph-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("tmp dir");
+    let (store, out) = (dir.join("worldline.cbor"), dir.join("graph.cbor"));
+    worldline::save(&store, runtime.store().events()).expect("save");
+    let jitos = |format: &str, at: &str, out: Option<&std::path::Path>| {
+        let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_jitos"));
+        command
+            .args(["graph", "dump", "--format", format, "--at", at])
+            .arg("--store")
+            .arg(&store);
+        if let Some(out) = out {
+            command.arg("--out").arg(out);
+        }
+        command.output().expect("run jitos")
+    };
+
+    let len = runtime.store().len().to_string();
+    let (dot, cbor, unknown) = (
+        jitos("dot", &len, None),
+        jitos("cbor", &len, Some(&out)),
+        jitos("dot", &Hash([3; 32]).to_string(), None),
+    );
+    let written = std::fs::read(&out);
+    std::fs::remove_dir_all(&dir).ok();
+
+    let state = runtime.receipts()[2].state_hash;
+    assert!(dot.status.success(), "{dot:?}");
+    assert!(String::from_utf8_lossy(&dot.stdout).starts_with(&format!("// state {state}")));
+    assert!(String::from_utf8_lossy(&dot.stderr).contains("verified state"));
+    assert!(cbor.status.success(), "{cbor:?}");
+    let decoded = decode_snapshot(&state, &written.expect("graph file")).expect("snapshot");
+    assert_eq!(decoded.nodes.len(), 3);
+    assert!(!unknown.status.success());
+}
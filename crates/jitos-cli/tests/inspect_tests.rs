@@ -0,0 +1,151 @@
+use jitos_cli::inspect::{self, find, InspectError, Log, Show};
+use jitos_cli::worldline::{self, WorldlineError};
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::store::MemoryEventStore;
+use serde_json::json;
+
+/// A policy, a sensor reading by alice, a decision and a signed commit.
+fn worldline_events() -> Vec<EventEnvelope> {
+    let bytes = |v: serde_json::Value| CanonicalBytes::from_value(&v).expect("encode payload");
+    let policy = EventEnvelope::new_policy_context(bytes(json!("fifo")), vec![], None, None)
+        .expect("policy");
+    let reading = EventEnvelope::new_observation(
+        bytes(json!({"unit": "C", "reading": 21})),
+        vec![],
+        Some("OBS_SENSOR_V0".to_string()),
+        Some(AgentId::new("alice").expect("agent")),
+        None,
+    )
+    .expect("observation");
+    let decision = EventEnvelope::new_decision(
+        bytes(json!({"heat": true})),
+        vec![reading.event_id()],
+        policy.event_id(),
+        None,
+        None,
+    )
+    .expect("decision");
+    let commit = EventEnvelope::new_commit(
+        bytes(json!("heater on")),
+        decision.event_id(),
+        vec![],
+        None,
+        Signature::new(vec![0xca, 0xfe]).expect("signature"),
+    )
+    .expect("commit");
+    vec![policy, reading, decision, commit]
+}
+
+fn store() -> MemoryEventStore {
+    worldline::decode(&worldline::encode(&worldline_events()).expect("encode")).expect("decode")
+}
+
+#[test]
+fn log_and_heads_list_events_in_worldline_order() {
+    let store = store();
+    let log = Log(store.events()).to_string();
+    let lines: Vec<&str> = log.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].contains("PolicyContext"));
+    assert!(lines[1].contains("Observation") && lines[1].contains("OBS_SENSOR_V0"));
+    assert!(lines[1].contains("alice"));
+    let reading = &store.events()[1].event_id().to_string()[..12];
+    assert!(lines[2].contains("Decision") && lines[2].contains(reading));
+    assert!(lines[3].contains("Commit"));
+
+    let heads = inspect::Heads(store.heads()).to_string();
+    assert_eq!(heads, format!("{}\n", store.events()[3].event_id()));
+}
+
+#[test]
+fn show_finds_by_prefix_and_prints_canonical_json() {
+    let store = store();
+    let reading = &store.events()[1];
+    let id = reading.event_id().to_string();
+    assert_eq!(find(&store, &id[..8]).expect("prefix"), reading);
+    assert_eq!(find(&store, &id.to_uppercase()).expect("full id"), reading);
+
+    let shown = Show(reading).to_string();
+    assert!(shown.contains(&format!("event     {id}")));
+    assert!(shown.contains("agent     alice"));
+    assert!(
+        shown.contains(r#"payload   {"reading":21,"unit":"C"}"#),
+        "{shown}"
+    );
+    assert!(Show(&store.events()[3])
+        .to_string()
+        .contains("signature cafe"));
+
+    assert_eq!(
+        find(&store, "xyz0"),
+        Err(InspectError::MalformedId("xyz0".to_string()))
+    );
+    assert_eq!(
+        find(&store, "12"),
+        Err(InspectError::MalformedId("12".to_string()))
+    );
+    let unknown = ["0000", "ffff", "1234"]
+        .into_iter()
+        .find(|p| {
+            store
+                .events()
+                .iter()
+                .all(|e| !e.event_id().to_string().starts_with(p))
+        })
+        .expect("an unused prefix");
+    assert_eq!(
+        find(&store, unknown),
+        Err(InspectError::NotFound(unknown.to_string()))
+    );
+}
+
+#[test]
+fn verify_reports_a_valid_store_and_rejects_a_broken_one() {
+    let events = worldline_events();
+    let verified = inspect::verify(&worldline::encode(&events).expect("encode")).expect("verify");
+    assert_eq!(verified.events, 4);
+    assert_eq!(
+        (
+            verified.observations,
+            verified.policy_contexts,
+            verified.decisions,
+            verified.commits,
+            verified.signed
+        ),
+        (1, 1, 1, 1, 1)
+    );
+    assert_eq!(verified.heads, vec![events[3].event_id()]);
+
+    let reordered = vec![events[2].clone(), events[0].clone(), events[1].clone()];
+    let bytes = worldline::encode(&reordered).expect("encode");
+    assert!(matches!(
+        inspect::verify(&bytes),
+        Err(WorldlineError::InvalidEvent { index: 0, .. })
+    ));
+
+    let dir = std::env::temp_dir().join(format!("jitos-inspect-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("tmp dir");
+    let (good, bad) = (dir.join("good.cbor"), dir.join("bad.cbor"));
+    worldline::save(&good, &events).expect("save");
+    std::fs::write(&bad, &bytes).expect("write");
+    let jitos = |command: &str, store: &std::path::Path| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_jitos"))
+            .arg(command)
+            .arg("--store")
+            .arg(store)
+            .output()
+            .expect("run jitos")
+    };
+    let (ok, broken, log) = (
+        jitos("verify", &good),
+        jitos("verify", &bad),
+        jitos("log", &good),
+    );
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(ok.status.success(), "{ok:?}");
+    assert!(String::from_utf8_lossy(&ok.stdout).starts_with("ok: 4 events verified"));
+    assert!(!broken.status.success());
+    assert!(String::from_utf8_lossy(&broken.stderr).contains("event 0 rejected"));
+    assert_eq!(String::from_utf8_lossy(&log.stdout).lines().count(), 4);
+}
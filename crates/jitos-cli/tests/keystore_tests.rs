@@ -0,0 +1,256 @@
+use jitos_cli::keys::{self, Ed25519Signer, KeyError, Keyring};
+use jitos_cli::keystore::{KdfCost, Keystore, KeystoreError};
+use jitos_cli::worldline;
+use jitos_core::canonical;
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventKind, Signature};
+use jitos_core::{Proposal, Slap};
+use jitos_net::PeerSigner;
+use jitos_runtime::{Host, Runtime, Views};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::{ClockPolicyId, ClockSample, ClockSource, OBS_CLOCK_SAMPLE_V0};
+
+/// Small enough to keep tests fast; key files record their own cost.
+const CHEAP: KdfCost = KdfCost {
+    memory_kib: 64,
+    iterations: 1,
+    parallelism: 1,
+};
+
+fn agent(name: &str) -> AgentId {
+    AgentId::new(name).expect("agent")
+}
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("jitos-{name}-{}", std::process::id()));
+    std::fs::remove_dir_all(&dir).ok();
+    dir
+}
+
+/// One clock sample and one new node per tick, Commits signed with a
+/// placeholder.
+struct Recorder;
+
+impl Host for Recorder {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        let sample = ClockSample {
+            source: ClockSource::Monotonic,
+            value_ns: 1_000 * (tick + 1),
+            uncertainty_ns: 0,
+        };
+        vec![EventEnvelope::new_observation(
+            CanonicalBytes::from_value(&sample).expect("encode sample"),
+            vec![],
+            Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+            None,
+            None,
+        )
+        .expect("observation")]
+    }
+
+    fn proposals(&mut self, _tick: u64, _views: &Views) -> Vec<Proposal> {
+        vec![Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: vec![],
+        }
+        .into()]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![1]).expect("signature")
+    }
+}
+
+fn recorded() -> Runtime {
+    let mut runtime = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+    for _ in 0..2 {
+        runtime.tick(&mut Recorder).expect("tick");
+    }
+    runtime
+}
+
+#[test]
+fn keystore_seals_keys_under_a_passphrase() {
+    let dir = temp_dir("keystore");
+    let keystore = Keystore::open(&dir).expect("open").with_cost(CHEAP);
+    let alice = keystore
+        .generate(agent("alice"), "correct horse")
+        .expect("generate");
+    let bob = Ed25519Signer::new(agent("bob/../carol"), [9; 32]);
+    keystore.insert(&bob, "battery staple").expect("insert");
+
+    let unlocked = keystore
+        .unlock(&agent("alice"), "correct horse")
+        .expect("unlock");
+    assert_eq!(unlocked.public_hex(), alice.public_hex());
+    let wrong = keystore.unlock(&agent("alice"), "correct horsf");
+    let empty = keystore.unlock(&agent("alice"), "");
+    let again = keystore.generate(agent("alice"), "other");
+    let missing = keystore.unlock(&agent("dave"), "correct horse");
+    let agents = keystore.agents().expect("agents");
+    let keyring = keystore.keyring().expect("keyring");
+
+    // Swap in a public key the seed does not belong to.
+    let path = keystore.path(&agent("alice"));
+    let mut file = keystore.key_file(&agent("alice")).expect("key file");
+    file.public_key = bob.public_hex();
+    std::fs::write(&path, canonical::encode(&file).expect("encode")).expect("write");
+    let swapped = keystore.unlock(&agent("alice"), "correct horse");
+    let raw = std::fs::read(keystore.path(&agent("bob/../carol"))).expect("read");
+    #[cfg(unix)]
+    let modes = {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = |p: &std::path::Path| std::fs::metadata(p).expect("stat").permissions().mode();
+        (
+            mode(&dir) & 0o777,
+            mode(&keystore.path(&agent("bob/../carol"))) & 0o777,
+        )
+    };
+    std::fs::remove_dir_all(&dir).ok();
+
+    #[cfg(unix)]
+    assert_eq!(modes, (0o700, 0o600), "owner-only keystore and key files");
+
+    assert!(matches!(wrong, Err(KeystoreError::Unlock)));
+    assert!(matches!(empty, Err(KeystoreError::EmptyPassphrase)));
+    assert!(matches!(again, Err(KeystoreError::Exists(a)) if a == agent("alice")));
+    assert!(matches!(missing, Err(KeystoreError::Missing(_))));
+    assert!(matches!(swapped, Err(KeystoreError::Unlock)));
+    assert_eq!(agents, [agent("alice"), agent("bob/../carol")]);
+    assert!(!raw.windows(32).any(|w| w == [9; 32]), "seed is not stored");
+    let statement = keys::event_statement(&jitos_core::Hash([4; 32]));
+    assert!(jitos_net::PeerVerifier::verify(
+        &keyring,
+        &agent("bob/../carol"),
+        &statement,
+        &bob.sign(&statement)
+    ));
+}
+
+#[test]
+fn signing_an_event_keeps_its_id_and_verifies_against_the_keyring() {
+    let runtime = recorded();
+    let commit = runtime
+        .store()
+        .events()
+        .iter()
+        .find(|e| *e.kind() == EventKind::Commit)
+        .expect("commit")
+        .clone();
+    let alice = Ed25519Signer::new(agent("alice"), [1; 32]);
+    let mallory = Ed25519Signer::new(agent("alice"), [2; 32]);
+    let mut keyring = Keyring::new();
+    keyring
+        .trust(&format!("alice={}", alice.public_hex()))
+        .expect("trust");
+
+    let signed = keys::sign_event(commit.clone(), &alice).expect("sign");
+    assert_eq!(signed.event_id(), commit.event_id());
+    assert_eq!(signed.agent_id(), Some(&agent("alice")));
+    assert_eq!(
+        keys::verify_event(&signed, &keyring).expect("verify"),
+        &agent("alice")
+    );
+    let events: Vec<_> = runtime
+        .store()
+        .events()
+        .iter()
+        .map(|e| {
+            if *e == commit {
+                signed.clone()
+            } else {
+                e.clone()
+            }
+        })
+        .collect();
+    let reloaded = worldline::decode(&worldline::encode(&events).expect("encode")).expect("valid");
+    assert_eq!(reloaded.heads(), runtime.store().heads());
+
+    let forged = keys::sign_event(commit.clone(), &mallory).expect("sign");
+    assert!(matches!(
+        keys::verify_event(&forged, &keyring),
+        Err(KeyError::BadSignature { event, .. }) if event == commit.event_id()
+    ));
+    let observation = &runtime.store().events()[0];
+    assert!(matches!(
+        keys::verify_event(observation, &keyring),
+        Err(KeyError::Unattributed(_))
+    ));
+}
+
+#[test]
+fn keygen_sign_and_verify_commands_round_trip() {
+    let runtime = recorded();
+    let dir = temp_dir("sign");
+    std::fs::create_dir_all(&dir).expect("tmp dir");
+    let (store, keystore, pass) = (
+        dir.join("worldline.cbor"),
+        dir.join("keys"),
+        dir.join("pass"),
+    );
+    worldline::save(&store, runtime.store().events()).expect("save");
+    std::fs::write(&pass, "s3cret\n").expect("write");
+    let commit = runtime
+        .store()
+        .events()
+        .iter()
+        .rfind(|e| *e.kind() == EventKind::Commit)
+        .expect("commit")
+        .event_id()
+        .to_string();
+    let jitos = |args: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_jitos"))
+            .args(args)
+            .env("JITOS_KEYSTORE", &keystore)
+            .env_remove("JITOS_PASSPHRASE")
+            .output()
+            .expect("run jitos")
+    };
+    let store_arg = store.to_str().expect("utf8 path");
+    let pass_arg = pass.to_str().expect("utf8 path");
+
+    let keygen = jitos(&["keygen", "--agent", "ops", "--passphrase-file", pass_arg]);
+    let no_passphrase = jitos(&["keygen", "--agent", "other"]);
+    let sign = jitos(&[
+        "sign-event",
+        "--store",
+        store_arg,
+        "--agent",
+        "ops",
+        "--passphrase-file",
+        pass_arg,
+        &commit[..8],
+    ]);
+    let verify = jitos(&["verify-event", "--store", store_arg, &commit]);
+    let stranger = format!(
+        "ops={}",
+        Ed25519Signer::new(agent("ops"), [3; 32]).public_hex()
+    );
+    let untrusted = std::process::Command::new(env!("CARGO_BIN_EXE_jitos"))
+        .args(["verify-event", "--store", store_arg, &commit, "--trust"])
+        .arg(&stranger)
+        .env("JITOS_KEYSTORE", dir.join("absent"))
+        .output()
+        .expect("run jitos");
+    let signed = worldline::load(&store);
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(keygen.status.success(), "{keygen:?}");
+    let trust = String::from_utf8_lossy(&keygen.stdout)
+        .lines()
+        .find_map(|l| l.strip_prefix("trust ").map(str::to_string))
+        .expect("trust line");
+    assert!(trust.starts_with("ops=") && trust != stranger);
+    assert!(!no_passphrase.status.success());
+    assert!(String::from_utf8_lossy(&no_passphrase.stderr).contains("no passphrase"));
+    assert!(sign.status.success(), "{sign:?}");
+    assert!(String::from_utf8_lossy(&sign.stdout).starts_with(&format!("signed {commit} as ops")));
+    let signed = signed.expect("signed worldline");
+    assert_eq!(signed.heads(), runtime.store().heads());
+    assert!(verify.status.success(), "{verify:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&verify.stdout),
+        format!("ok: {commit} signed by ops\n")
+    );
+    assert!(!untrusted.status.success());
+    assert!(String::from_utf8_lossy(&untrusted.stderr).contains("does not verify as ops"));
+}
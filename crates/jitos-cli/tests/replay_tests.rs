@@ -0,0 +1,183 @@
+use jitos_cli::replay::{self, Outcome, ReplayError};
+use jitos_cli::worldline;
+use jitos_core::events::{CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::{Hash, Proposal, Slap};
+use jitos_graph::snapshot::{FileSnapshotStore, SnapshotStore};
+use jitos_runtime::replay::Mismatch;
+use jitos_runtime::{Host, Runtime, Views};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::{ClockPolicyId, ClockSample, ClockSource, OBS_CLOCK_SAMPLE_V0};
+
+fn sample(source: ClockSource, value_ns: u64) -> EventEnvelope {
+    let sample = ClockSample {
+        source,
+        value_ns,
+        uncertainty_ns: 10,
+    };
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&sample).expect("encode sample"),
+        vec![],
+        Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+        None,
+        None,
+    )
+    .expect("observation")
+}
+
+/// Monotonic and NTP samples every tick, and a node stamped with the time.
+struct Sampler;
+
+impl Host for Sampler {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        vec![
+            sample(ClockSource::Monotonic, 100 * (tick + 1)),
+            sample(ClockSource::Ntp, 5_000 + tick),
+        ]
+    }
+
+    fn proposals(&mut self, _tick: u64, views: &Views) -> Vec<Proposal> {
+        vec![Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: views.clock().now().ns().to_le_bytes().to_vec(),
+        }
+        .into()]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![7]).expect("signature")
+    }
+}
+
+/// Four ticks under the monotonic clock policy.
+fn recorded() -> Runtime {
+    let mut runtime = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+    for _ in 0..4 {
+        runtime.tick(&mut Sampler).expect("tick");
+    }
+    runtime
+}
+
+#[test]
+fn recorded_worldline_replays_to_the_same_receipts() {
+    let runtime = recorded();
+    let outcome =
+        replay::replay(runtime.store(), ClockPolicyId::TrustMonotonicLatest).expect("replay");
+    let Outcome::Matched(matched) = &outcome else {
+        panic!("expected a match, got {outcome}");
+    };
+    assert_eq!((matched.ticks, matched.receipts), (4, 4));
+    assert_eq!(matched.checkpoint, None);
+    assert!(!outcome.diverges());
+    assert_eq!(
+        outcome.to_string(),
+        "ok: replayed 4 ticks, 4 recorded receipts match\n"
+    );
+
+    let mut headless = MemoryEventStore::new();
+    headless
+        .append(sample(ClockSource::Monotonic, 1))
+        .expect("append");
+    assert!(matches!(
+        replay::replay(&headless, ClockPolicyId::TrustMonotonicLatest),
+        Err(ReplayError::NoPolicyContext)
+    ));
+}
+
+#[test]
+fn divergence_names_the_tick_and_its_applied_slaps() {
+    let runtime = recorded();
+    let outcome = replay::replay(runtime.store(), ClockPolicyId::TrustNtpLatest).expect("replay");
+    let Outcome::Diverged(diverged) = &outcome else {
+        panic!("expected a divergence, got {outcome}");
+    };
+    let first = &runtime.receipts()[0];
+    assert_eq!(diverged.divergence.tick, 0);
+    assert_eq!(
+        diverged.divergence.mismatch,
+        Mismatch::View("clock".to_string())
+    );
+    assert_eq!(diverged.divergence.expected, first.view_hashes["clock"]);
+    assert_eq!(diverged.applied_slaps, first.applied_slaps);
+    assert!(!diverged.applied_slaps.is_empty());
+
+    let report = outcome.to_string();
+    assert!(report.starts_with("diverged at tick 0: clock view differs"));
+    for slap in &first.applied_slaps {
+        assert!(report.contains(&format!("applied   {slap}")), "{report}");
+    }
+
+    let dir = std::env::temp_dir().join(format!("jitos-replay-diverge-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("tmp dir");
+    let store = dir.join("worldline.cbor");
+    worldline::save(&store, runtime.store().events()).expect("save");
+    let jitos = |clock: &str| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_jitos"))
+            .args(["replay", "--clock-policy", clock])
+            .arg("--store")
+            .arg(&store)
+            .output()
+            .expect("run jitos")
+    };
+    let (ok, diverged) = (jitos("trust_monotonic_latest"), jitos("trust_ntp_latest"));
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(ok.status.success(), "{ok:?}");
+    assert!(!diverged.status.success());
+    assert_eq!(String::from_utf8_lossy(&diverged.stdout), report);
+}
+
+#[test]
+fn replay_resumes_from_a_snapshotted_checkpoint() {
+    let dir = std::env::temp_dir().join(format!("jitos-replay-checkpoint-{}", std::process::id()));
+    let mut snapshots = FileSnapshotStore::open(dir.join("snapshots")).expect("snapshots");
+    let mut runtime = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+    for tick in 0..4 {
+        runtime.tick(&mut Sampler).expect("tick");
+        if tick == 1 {
+            snapshots.save(runtime.graph()).expect("snapshot");
+        }
+    }
+    let checkpoint = runtime.receipts()[1].state_hash;
+
+    let outcome = replay::replay_from_checkpoint(
+        runtime.store(),
+        ClockPolicyId::TrustMonotonicLatest,
+        &checkpoint,
+        &snapshots,
+    )
+    .expect("replay");
+    assert!(outcome
+        .to_string()
+        .ends_with("(from checkpoint at tick 1)\n"));
+    let unknown = Hash([9; 32]);
+    assert!(matches!(
+        replay::replay_from_checkpoint(
+            runtime.store(),
+            ClockPolicyId::TrustMonotonicLatest,
+            &unknown,
+            &snapshots,
+        ),
+        Err(ReplayError::NotACheckpoint(hash)) if hash == unknown
+    ));
+
+    let store = dir.join("worldline.cbor");
+    worldline::save(&store, runtime.store().events()).expect("save");
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_jitos"))
+        .arg("replay")
+        .arg("--store")
+        .arg(&store)
+        .arg("--from-checkpoint")
+        .arg(checkpoint.to_string())
+        .arg("--snapshots")
+        .arg(dir.join("snapshots"))
+        .output()
+        .expect("run jitos");
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "ok: replayed 2 ticks, 2 recorded receipts match (from checkpoint at tick 1)\n"
+    );
+}
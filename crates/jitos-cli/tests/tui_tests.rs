@@ -0,0 +1,177 @@
+#![cfg(feature = "tui")]
+
+use jitos_cli::tui::{self, Browser, Link};
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventId};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_views::{
+    ClockPolicyId, ClockSample, ClockSource, TimerRequest, OBS_CLOCK_SAMPLE_V0,
+    OBS_TIMER_REQUEST_V0,
+};
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::Terminal;
+
+fn observation<T: serde::Serialize>(
+    value: &T,
+    observation_type: &str,
+    parents: Vec<EventId>,
+) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(value).expect("encode"),
+        parents,
+        Some(observation_type.to_string()),
+        None,
+        None,
+    )
+    .expect("observation")
+}
+
+fn sample(value_ns: u64, parents: Vec<EventId>) -> EventEnvelope {
+    let sample = ClockSample {
+        source: ClockSource::Monotonic,
+        value_ns,
+        uncertainty_ns: 5,
+    };
+    observation(&sample, OBS_CLOCK_SAMPLE_V0, parents)
+}
+
+/// 0: clock at 1000; 1: a timer due at 1500; 2: clock at 2000; 3: a note
+/// citing 0 and 2.
+fn store() -> MemoryEventStore {
+    let zero = sample(1_000, vec![]);
+    let request = TimerRequest {
+        request_id: Hash([7; 32]),
+        duration_ns: 500,
+        requested_at_ns: 1_000,
+    };
+    let one = observation(&request, OBS_TIMER_REQUEST_V0, vec![zero.event_id()]);
+    let two = sample(2_000, vec![one.event_id()]);
+    let three = observation(
+        &"note".to_string(),
+        "OBS_NOTE_V0",
+        vec![zero.event_id(), two.event_id()],
+    );
+    let mut store = MemoryEventStore::new();
+    for event in [zero, one, two, three] {
+        store.append(event).expect("append");
+    }
+    store
+}
+
+fn screen(browser: &Browser<'_>) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(140, 40)).expect("terminal");
+    terminal
+        .draw(|frame| tui::draw(frame, browser))
+        .expect("draw");
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn keys_scroll_and_follow_parents_and_children() {
+    let store = store();
+    let mut browser = Browser::new(&store, ClockPolicyId::TrustMonotonicLatest);
+
+    browser.handle(KeyCode::Up);
+    assert_eq!(browser.selected(), 0);
+    assert_eq!(browser.links(), [Link::Child(1), Link::Child(3)]);
+    browser.handle(KeyCode::Char('G'));
+    assert_eq!(browser.selected(), 3);
+    browser.handle(KeyCode::Down);
+    assert_eq!(browser.selected(), 3, "selection stays on the worldline");
+    assert_eq!(browser.links(), [Link::Parent(0), Link::Parent(2)]);
+
+    browser.handle(KeyCode::Tab);
+    assert_eq!(browser.link(), Some(Link::Parent(2)));
+    browser.handle(KeyCode::Enter);
+    assert_eq!(browser.selected(), 2);
+    browser.handle(KeyCode::Char('p'));
+    assert_eq!(browser.selected(), 1);
+    browser.handle(KeyCode::Char('c'));
+    assert_eq!(browser.selected(), 2);
+    browser.handle(KeyCode::BackTab);
+    assert_eq!(browser.link(), Some(Link::Child(3)));
+
+    for expected in [1, 2, 3] {
+        browser.handle(KeyCode::Char('b'));
+        assert_eq!(browser.selected(), expected);
+    }
+    browser.handle(KeyCode::Backspace);
+    assert_eq!(browser.selected(), 3, "history is exhausted");
+
+    browser.handle(KeyCode::Char(' '));
+    assert!(browser.expanded());
+    assert!(!browser.quit());
+    browser.handle(KeyCode::Char('q'));
+    assert!(browser.quit());
+}
+
+#[test]
+fn view_state_is_folded_up_to_the_selected_event() {
+    let store = store();
+    let mut browser = Browser::new(&store, ClockPolicyId::TrustMonotonicLatest);
+
+    let first = browser.view_state().expect("views");
+    assert_eq!((first.cut, first.time.ns(), first.requests), (1, 1_000, 0));
+
+    browser.select(1);
+    let requested = browser.view_state().expect("views");
+    assert_eq!(requested.time.ns(), 1_000);
+    assert_eq!(requested.requests, 1);
+    assert!(requested.pending.is_empty(), "not due until 1500");
+    assert_eq!(requested.next_fire_ns, Some(1_500));
+
+    browser.select(2);
+    let due = browser.view_state().expect("views");
+    assert_eq!(due.time.ns(), 2_000);
+    assert_eq!(due.pending, [Hash([7; 32])]);
+    assert_eq!(due.time.provenance(), [store.events()[2].event_id()]);
+
+    let empty = MemoryEventStore::new();
+    let browser = Browser::new(&empty, ClockPolicyId::TrustMonotonicLatest);
+    assert!(browser.event().is_none());
+    assert_eq!(browser.view_state().expect("views").cut, 0);
+}
+
+#[test]
+fn screen_shows_the_selected_event_links_and_views() {
+    let store = store();
+    let mut browser = Browser::new(&store, ClockPolicyId::TrustMonotonicLatest);
+    browser.select(1);
+    let id = store.events()[1].event_id().to_string();
+
+    let collapsed = screen(&browser);
+    assert!(collapsed.contains("worldline (4 events)"), "{collapsed}");
+    assert!(
+        collapsed.contains(&format!("event     {id}")),
+        "{collapsed}"
+    );
+    assert!(collapsed.contains("type      OBS_TIMER_REQUEST_V0"));
+    assert!(collapsed.contains("(space expands)"));
+    assert!(collapsed.contains(&format!(
+        "parent     0 {}",
+        &store.events()[0].event_id().to_string()[..12]
+    )));
+    assert!(collapsed.contains("views (trust_monotonic_latest)"));
+    assert!(collapsed.contains("timers    1 requested, 0 fired, 0 due"));
+    assert!(collapsed.contains("next fire 1500 ns"));
+
+    browser.handle(KeyCode::Char('e'));
+    let expanded = screen(&browser);
+    assert!(expanded.contains("\"duration_ns\": 500,"), "{expanded}");
+    assert!(!expanded.contains("(space expands)"));
+
+    let empty = MemoryEventStore::new();
+    assert!(
+        screen(&Browser::new(&empty, ClockPolicyId::TrustMonotonicLatest))
+            .contains("empty worldline")
+    );
+}
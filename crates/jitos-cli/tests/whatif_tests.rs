@@ -0,0 +1,207 @@
+use jitos_cli::whatif::{what_if, WhatIfError};
+use jitos_cli::worldline;
+use jitos_core::canonical;
+use jitos_core::delta::DeltaSpec;
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_graph::{NodeId, WarpGraph, WarpNode};
+use jitos_views::{
+    ClockPolicyId, ClockSample, ClockSource, TimerRequest, OBS_CLOCK_SAMPLE_V0,
+    OBS_TIMER_REQUEST_V0,
+};
+
+fn clock_event(source: ClockSource, value_ns: u64) -> EventEnvelope {
+    let sample = ClockSample {
+        source,
+        value_ns,
+        uncertainty_ns: 1_000,
+    };
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&sample).expect("encode sample"),
+        vec![],
+        Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+        None,
+        None,
+    )
+    .expect("observation")
+}
+
+fn timer_request(id: u8, requested_at_ns: u64) -> EventEnvelope {
+    let request = TimerRequest {
+        request_id: Hash([id; 32]),
+        duration_ns: 500_000_000,
+        requested_at_ns,
+    };
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&request).expect("encode request"),
+        vec![],
+        Some(OBS_TIMER_REQUEST_V0.to_string()),
+        None,
+        None,
+    )
+    .expect("observation")
+}
+
+/// Monotonic says 1s, NTP says 2s; the timer is due at 1.5s.
+fn worldline_events() -> Vec<EventEnvelope> {
+    vec![
+        clock_event(ClockSource::Monotonic, 1_000_000_000),
+        clock_event(ClockSource::Ntp, 2_000_000_000),
+        timer_request(7, 1_000_000_000),
+        clock_event(ClockSource::Monotonic, 1_100_000_000),
+    ]
+}
+
+fn worldline() -> MemoryEventStore {
+    let mut store = MemoryEventStore::new();
+    for event in worldline_events() {
+        store.append(event).expect("append");
+    }
+    store
+}
+
+fn prefer_ntp() -> DeltaSpec {
+    DeltaSpec::new_clock_policy(
+        ClockPolicyId::TrustNtpLatest.policy_hash(),
+        "prefer ntp".to_string(),
+    )
+    .expect("delta")
+}
+
+#[test]
+fn clock_policy_delta_changes_beliefs_and_pending_timers() {
+    let store = worldline();
+    let cmp = what_if(
+        &store,
+        store.len(),
+        ClockPolicyId::TrustMonotonicLatest,
+        &prefer_ntp(),
+        None,
+        &[],
+    )
+    .expect("what-if");
+
+    assert_eq!(cmp.base.time.ns(), 1_100_000_000);
+    assert_eq!(cmp.fork.time.ns(), 2_000_000_000);
+    assert!(cmp.base.pending_timers.is_empty());
+    assert_eq!(cmp.fork.pending_timers, vec![Hash([7; 32])]);
+    assert!(cmp.diverges());
+
+    let table = cmp.to_string();
+    assert!(table.contains("trust_monotonic_latest"));
+    assert!(table.contains("trust_ntp_latest"));
+    assert!(table.contains(&Hash([7; 32]).to_string()[..12]));
+}
+
+#[test]
+fn cut_limits_the_shared_prefix() {
+    let store = worldline();
+    // Before the NTP sample both policies agree the timer is not due.
+    let cmp = what_if(
+        &store,
+        1,
+        ClockPolicyId::TrustMonotonicLatest,
+        &prefer_ntp(),
+        None,
+        &[],
+    )
+    .expect("what-if");
+    assert_eq!(cmp.fork.time, jitos_views::Time::unknown());
+    assert!(cmp.fork.pending_timers.is_empty());
+
+    let err = what_if(
+        &store,
+        store.len() + 1,
+        ClockPolicyId::TrustMonotonicLatest,
+        &prefer_ntp(),
+        None,
+        &[],
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        WhatIfError::CutOutOfBounds { cut: 5, len: 4 }
+    ));
+}
+
+#[test]
+fn graph_digest_is_shared_by_clock_deltas() {
+    let store = worldline();
+    let mut graph = WarpGraph::new();
+    graph.insert_node(WarpNode {
+        id: NodeId::from_hash(Hash([1; 32])),
+        node_type: "task".to_string(),
+        payload_bytes: vec![],
+        attachment: None,
+    });
+
+    let cmp = what_if(
+        &store,
+        store.len(),
+        ClockPolicyId::TrustMonotonicLatest,
+        &prefer_ntp(),
+        Some(&graph),
+        &[],
+    )
+    .expect("what-if");
+    assert_eq!(cmp.base.graph_digest, Some(graph.compute_hash()));
+    assert_eq!(cmp.base.graph_digest, cmp.fork.graph_digest);
+}
+
+#[test]
+fn unsupported_and_unknown_deltas_are_rejected() {
+    let store = worldline();
+    let trust = DeltaSpec::new_trust_policy(
+        vec![AgentId::new("alice").expect("agent")],
+        "trust alice".to_string(),
+    )
+    .expect("delta");
+    assert!(matches!(
+        what_if(&store, 0, ClockPolicyId::TrustNtpLatest, &trust, None, &[]).unwrap_err(),
+        WhatIfError::UnsupportedDelta("TrustPolicy")
+    ));
+
+    let unknown = DeltaSpec::new_clock_policy(Hash([0; 32]), "mystery".to_string()).expect("delta");
+    assert!(matches!(
+        what_if(&store, 0, ClockPolicyId::TrustNtpLatest, &unknown, None, &[]).unwrap_err(),
+        WhatIfError::UnknownClockPolicy(hash) if hash == Hash([0; 32])
+    ));
+}
+
+#[test]
+fn worldline_file_round_trips_and_validates() {
+    let events = worldline_events();
+    let bytes = worldline::encode(&events).expect("encode");
+    let store = worldline::decode(&bytes).expect("decode");
+    assert_eq!(store.events(), events.as_slice());
+
+    assert!(worldline::decode(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn whatif_command_prints_side_by_side_table() {
+    let dir = std::env::temp_dir().join(format!("jitos-whatif-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("tmp dir");
+    let store = dir.join("worldline.cbor");
+    let policy = dir.join("policy.cbor");
+    worldline::save(&store, &worldline_events()).expect("save");
+    std::fs::write(&policy, canonical::encode(&prefer_ntp()).expect("encode")).expect("write");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_jitos"))
+        .arg("whatif")
+        .arg("--store")
+        .arg(&store)
+        .arg("--policy")
+        .arg(&policy)
+        .args(["--at", "4"])
+        .output()
+        .expect("run jitos");
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).expect("utf8");
+    assert!(stdout.starts_with("what-if at cut 4"));
+    assert!(stdout.contains("pending timers"));
+    assert!(stdout.contains("graph digest"));
+}
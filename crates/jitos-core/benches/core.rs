@@ -0,0 +1,162 @@
+//! Throughput of jitos-core hot paths: canonical encode/decode, event-id
+//! computation, event construction and store validation (up to a million
+//! events).
+//!
+//! Run with `cargo xtask bench`, which collects the results as JSON.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use jitos_core::canonical;
+use jitos_core::events::{validate_store, CanonicalBytes, EventEnvelope, EventKind};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use serde::{Deserialize, Serialize};
+
+/// Shaped like a typical observation payload: a few scalars, a string, a
+/// byte blob and a nested list of records.
+#[derive(Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    name: String,
+    weight: f64,
+    flags: Vec<bool>,
+    blob: Vec<u8>,
+    children: Vec<Child>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Child {
+    key: String,
+    value: i64,
+}
+
+fn record(children: usize) -> Record {
+    Record {
+        id: 42,
+        name: "sensor/temperature".to_string(),
+        weight: 0.75,
+        flags: vec![true, false, true],
+        blob: vec![0xab; 64],
+        children: (0..children)
+            .map(|i| Child {
+                key: format!("child-{i}"),
+                value: i as i64 - 8,
+            })
+            .collect(),
+    }
+}
+
+/// A chain of `n` observations, each parented on the previous one.
+fn chain(n: u64) -> Vec<EventEnvelope> {
+    let mut events: Vec<EventEnvelope> = Vec::new();
+    for i in 0..n {
+        let parents = events
+            .last()
+            .map(|e| vec![e.event_id()])
+            .unwrap_or_default();
+        events.push(
+            EventEnvelope::new_observation(
+                CanonicalBytes::from_value(&i).unwrap(),
+                parents,
+                Some("OBS_BENCH_V0".to_string()),
+                None,
+                None,
+            )
+            .unwrap(),
+        );
+    }
+    events
+}
+
+fn canonical_codec(c: &mut Criterion) {
+    let mut group = c.benchmark_group("canonical");
+    for children in [1, 16, 256] {
+        let value = record(children);
+        let bytes = canonical::encode(&value).unwrap();
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::new("encode", children), &value, |b, value| {
+            b.iter(|| canonical::encode(black_box(value)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("decode", children), &bytes, |b, bytes| {
+            b.iter(|| canonical::decode::<Record>(black_box(bytes)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn event_id(c: &mut Criterion) {
+    let mut group = c.benchmark_group("event_id");
+    let parents = [Hash([1; 32]), Hash([2; 32])];
+    for size in [64, 1024, 16 * 1024] {
+        let payload = CanonicalBytes::from_value(&blob(size)).unwrap();
+        group.throughput(Throughput::Bytes(payload.as_bytes().len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &payload, |b, payload| {
+            b.iter(|| {
+                EventEnvelope::compute_event_id(
+                    &EventKind::Observation,
+                    black_box(payload),
+                    &parents,
+                )
+                .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// A `size`-byte payload encoded as a CBOR byte string, not an array.
+fn blob(size: usize) -> ciborium::Value {
+    ciborium::Value::Bytes(vec![0x5a; size])
+}
+
+/// Construction of an observation with `n` parents, given in reverse order
+/// with one duplicate, so canonicalization has work to do.
+fn event_new(c: &mut Criterion) {
+    let mut group = c.benchmark_group("event_new");
+    let payload = CanonicalBytes::from_value(&7u64).unwrap();
+    for n in [0u8, 1, 2, 8] {
+        let mut parents: Vec<Hash> = (0..n).rev().map(|i| Hash([i; 32])).collect();
+        if let Some(&first) = parents.first() {
+            parents.push(first);
+        }
+        group.bench_with_input(BenchmarkId::from_parameter(n), &parents, |b, parents| {
+            b.iter(|| {
+                EventEnvelope::new_observation(
+                    payload.clone(),
+                    black_box(parents).clone(),
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn validate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("validate_store");
+    let base = MemoryEventStore::new();
+    for n in [100, 1_000] {
+        let events = chain(n);
+        group.throughput(Throughput::Elements(n));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &events, |b, events| {
+            b.iter(|| validate_store(&base, black_box(events)).unwrap())
+        });
+    }
+    group.finish();
+
+    // A million-event import: seconds per iteration, so few samples.
+    let mut group = c.benchmark_group("validate_store_large");
+    group.sample_size(10);
+    let n = 1_000_000;
+    let events = chain(n);
+    group.throughput(Throughput::Elements(n));
+    group.bench_with_input(BenchmarkId::from_parameter(n), &events, |b, events| {
+        b.iter(|| validate_store(&base, black_box(events)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, canonical_codec, event_id, event_new, validate);
+criterion_main!(benches);
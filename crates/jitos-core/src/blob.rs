@@ -0,0 +1,58 @@
+//! Content-addressed blob store.
+//!
+//! Blobs are opaque byte strings (script source, module bytes) named by the
+//! BLAKE3 hash of their raw bytes. Like node payloads (SPEC-WARP-0001) they
+//! are hashed as-is: the store never decodes or normalizes them.
+
+use crate::Hash;
+use std::collections::BTreeMap;
+
+/// Name of `bytes` in a [`BlobStore`].
+pub fn blob_hash(bytes: &[u8]) -> Hash {
+    Hash(*blake3::hash(bytes).as_bytes())
+}
+
+/// Content-addressed storage for opaque blobs.
+pub trait BlobStore {
+    /// Store `bytes` and return their [`blob_hash`]. Storing the same bytes twice is a no-op.
+    fn put(&mut self, bytes: Vec<u8>) -> Hash;
+
+    /// The blob named `hash`, if present.
+    fn get(&self, hash: &Hash) -> Option<&[u8]>;
+
+    fn contains(&self, hash: &Hash) -> bool {
+        self.get(hash).is_some()
+    }
+}
+
+/// In-memory blob store.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBlobStore {
+    blobs: BTreeMap<Hash, Vec<u8>>,
+}
+
+impl MemoryBlobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.blobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blobs.is_empty()
+    }
+}
+
+impl BlobStore for MemoryBlobStore {
+    fn put(&mut self, bytes: Vec<u8>) -> Hash {
+        let hash = blob_hash(&bytes);
+        self.blobs.entry(hash).or_insert(bytes);
+        hash
+    }
+
+    fn get(&self, hash: &Hash) -> Option<&[u8]> {
+        self.blobs.get(hash).map(Vec::as_slice)
+    }
+}
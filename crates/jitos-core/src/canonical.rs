@@ -66,6 +66,16 @@ pub enum CanonicalError {
 
 type Result<T> = std::result::Result<T, CanonicalError>;
 
+/// The first canonical-encoding rule a byte string breaks, and where.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[error("{error} at byte {offset}")]
+pub struct Violation {
+    /// Offset of the head byte of the offending item (the key, for map key
+    /// order and duplicates; the first extra byte, for trailing bytes).
+    pub offset: usize,
+    pub error: CanonicalError,
+}
+
 // Public API
 
 /// Encode a value using canonical CBOR.
@@ -85,6 +95,31 @@ pub fn decode<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<T> {
     ciborium::value::Value::deserialized(&v).map_err(|e| CanonicalError::Decode(e.to_string()))
 }
 
+/// Check that `bytes` are exactly one canonical CBOR value.
+///
+/// Applies the same rules as [`decode`], but reports the byte offset of the
+/// first violation for diagnostics.
+pub fn check(bytes: &[u8]) -> std::result::Result<(), Violation> {
+    decode_located(bytes).map(|_| ())
+}
+
+/// Write the head of a definite-length array of `len` items.
+///
+/// For callers that assemble the canonical encoding of an array from items
+/// encoded separately with [`encode`]: the head followed by the items, in
+/// order, is exactly what [`encode`] produces for the whole array.
+pub fn encode_array_head(len: usize, out: &mut Vec<u8>) {
+    enc_len(4, len as u64, out);
+}
+
+/// Write the head of a definite-length map of `len` entries.
+///
+/// As with [`encode_array_head`], the caller must write the entries that
+/// follow in canonical key order.
+pub fn encode_map_head(len: usize, out: &mut Vec<u8>) {
+    enc_len(5, len as u64, out);
+}
+
 /// Hash a value using canonical encoding.
 ///
 /// This is the ONLY valid way to hash data for determinism.
@@ -102,10 +137,17 @@ fn encode_value(val: &Value) -> Result<Vec<u8>> {
 }
 
 fn decode_value(bytes: &[u8]) -> Result<Value> {
+    decode_located(bytes).map_err(|v| v.error)
+}
+
+fn decode_located(bytes: &[u8]) -> std::result::Result<Value, Violation> {
     let mut idx = 0usize;
     let v = dec_value(bytes, &mut idx, true)?;
     if idx != bytes.len() {
-        return Err(CanonicalError::Trailing);
+        return Err(Violation {
+            offset: idx,
+            error: CanonicalError::Trailing,
+        });
     }
     Ok(v)
 }
@@ -263,9 +305,14 @@ fn write_major(major: u8, n: u128, out: &mut Vec<u8>) {
 
 // --- Decoder --------------------------------------------------------------
 
-fn dec_value(bytes: &[u8], idx: &mut usize, strict: bool) -> Result<Value> {
+fn dec_value(bytes: &[u8], idx: &mut usize, strict: bool) -> std::result::Result<Value, Violation> {
+    let start = *idx;
+    let at = |error| Violation {
+        offset: start,
+        error,
+    };
     if *idx >= bytes.len() {
-        return Err(CanonicalError::Incomplete);
+        return Err(at(CanonicalError::Incomplete));
     }
     let b0 = bytes[*idx];
     *idx += 1;
@@ -274,12 +321,12 @@ fn dec_value(bytes: &[u8], idx: &mut usize, strict: bool) -> Result<Value> {
 
     // forbid tags
     if major == 6 {
-        return Err(CanonicalError::Tag);
+        return Err(at(CanonicalError::Tag));
     }
 
     // forbid indefinite
     if ai == 31 {
-        return Err(CanonicalError::Indefinite);
+        return Err(at(CanonicalError::Indefinite));
     }
 
     // For major type 7 (floats/simples), handle ai directly without parsing length
@@ -288,15 +335,17 @@ fn dec_value(bytes: &[u8], idx: &mut usize, strict: bool) -> Result<Value> {
             20 => Ok(Value::Bool(false)),
             21 => Ok(Value::Bool(true)),
             22 | 23 => Ok(Value::Null),
-            24 => Err(CanonicalError::Decode("simple value not supported".into())),
+            24 => Err(at(CanonicalError::Decode(
+                "simple value not supported".into(),
+            ))),
             25 | 26 => {
                 // Per SPEC-0001: Reject float16/float32, require float64
-                Err(CanonicalError::NonCanonicalFloat)
+                Err(at(CanonicalError::NonCanonicalFloat))
             }
             27 => {
                 // Check for truncated input before reading
                 if *idx + 8 > bytes.len() {
-                    return Err(CanonicalError::Incomplete);
+                    return Err(at(CanonicalError::Incomplete));
                 }
 
                 let bits = take_u(bytes, idx, 8);
@@ -304,20 +353,20 @@ fn dec_value(bytes: &[u8], idx: &mut usize, strict: bool) -> Result<Value> {
 
                 // Per SPEC-0001: Integral floats MUST be encoded as integers
                 if strict && float_should_be_int(f) {
-                    return Err(CanonicalError::FloatShouldBeInt);
+                    return Err(at(CanonicalError::FloatShouldBeInt));
                 }
 
                 // Verify canonicalization (NaN/±0/subnormal)
                 if strict {
                     let canonical_f = canonicalize_f64(f);
                     if canonical_f.to_bits() != f.to_bits() {
-                        return Err(CanonicalError::NonCanonicalFloat);
+                        return Err(at(CanonicalError::NonCanonicalFloat));
                     }
                 }
 
                 Ok(Value::Float(f))
             }
-            _ => Err(CanonicalError::Decode("unknown simple/float".into())),
+            _ => Err(at(CanonicalError::Decode("unknown simple/float".into()))),
         };
     }
 
@@ -327,25 +376,19 @@ fn dec_value(bytes: &[u8], idx: &mut usize, strict: bool) -> Result<Value> {
         25 => take_u(bytes, idx, 2),
         26 => take_u(bytes, idx, 4),
         27 => take_u(bytes, idx, 8),
-        _ => return Err(CanonicalError::Decode("invalid additional info".into())),
+        _ => return Err(at(CanonicalError::Decode("invalid additional info".into()))),
     };
+    // Integers, lengths and counts alike use the shortest argument width.
+    check_min_int(ai, n, major == 1, strict).map_err(at)?;
 
     match major {
-        0 => {
-            // unsigned int
-            check_min_int(ai, n, false, strict)?;
-            Ok(int_to_value(n as u128, false))
-        }
-        1 => {
-            // negative
-            check_min_int(ai, n, true, strict)?;
-            Ok(int_to_value(n as u128, true))
-        }
+        0 => Ok(int_to_value(n as u128, false)),
+        1 => Ok(int_to_value(n as u128, true)),
         2 => {
             let len = n as usize;
             let end = *idx + len;
             if end > bytes.len() {
-                return Err(CanonicalError::Incomplete);
+                return Err(at(CanonicalError::Incomplete));
             }
             let v = Value::Bytes(bytes[*idx..end].to_vec());
             *idx = end;
@@ -355,10 +398,10 @@ fn dec_value(bytes: &[u8], idx: &mut usize, strict: bool) -> Result<Value> {
             let len = n as usize;
             let end = *idx + len;
             if end > bytes.len() {
-                return Err(CanonicalError::Incomplete);
+                return Err(at(CanonicalError::Incomplete));
             }
             let s = std::str::from_utf8(&bytes[*idx..end])
-                .map_err(|e| CanonicalError::Decode(e.to_string()))?
+                .map_err(|e| at(CanonicalError::Decode(e.to_string())))?
                 .to_string();
             *idx = end;
             Ok(Value::Text(s))
@@ -384,8 +427,18 @@ fn dec_value(bytes: &[u8], idx: &mut usize, strict: bool) -> Result<Value> {
                 if let Some(pb) = &prev_bytes {
                     match pb.cmp(&curr_bytes) {
                         std::cmp::Ordering::Less => {}
-                        std::cmp::Ordering::Equal => return Err(CanonicalError::DuplicateKey),
-                        std::cmp::Ordering::Greater => return Err(CanonicalError::MapKeyOrder),
+                        std::cmp::Ordering::Equal => {
+                            return Err(Violation {
+                                offset: key_start,
+                                error: CanonicalError::DuplicateKey,
+                            })
+                        }
+                        std::cmp::Ordering::Greater => {
+                            return Err(Violation {
+                                offset: key_start,
+                                error: CanonicalError::MapKeyOrder,
+                            })
+                        }
                     }
                 }
                 prev_bytes = Some(curr_bytes);
@@ -396,7 +449,7 @@ fn dec_value(bytes: &[u8], idx: &mut usize, strict: bool) -> Result<Value> {
         }
         6 => unreachable!(),
         7 => unreachable!(), // handled above
-        _ => Err(CanonicalError::Decode("unknown major".into())),
+        _ => Err(at(CanonicalError::Decode("unknown major".into()))),
     }
 }
 
@@ -552,6 +605,23 @@ mod tests {
         assert!(matches!(res, Err(CanonicalError::NonCanonicalInt)));
     }
 
+    #[test]
+    fn reject_non_canonical_lengths() {
+        // Text, bytes, array and map lengths follow the integer width rule.
+        for bytes in [
+            vec![0x78, 0x01, 0x61],
+            vec![0x58, 0x00],
+            vec![0x99, 0x00, 0x01, 0x00],
+            vec![0xb8, 0x00],
+        ] {
+            let res = decode_value(&bytes);
+            assert!(
+                matches!(res, Err(CanonicalError::NonCanonicalInt)),
+                "{bytes:02x?}"
+            );
+        }
+    }
+
     #[test]
     fn dc03_reject_tag() {
         // Ported from echo dc04_reject_tag
@@ -9,7 +9,6 @@
 use crate::canonical::{self, CanonicalError};
 use crate::Hash;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
 
 /// Event ID - content-addressed hash of the canonical event bytes
 pub type EventId = Hash;
@@ -186,6 +185,18 @@ pub enum EventKind {
     Commit,
 }
 
+impl EventKind {
+    /// The variant's serde name, as it appears under `"type"`.
+    fn variant_name(&self) -> &'static str {
+        match self {
+            EventKind::Observation => "Observation",
+            EventKind::PolicyContext => "PolicyContext",
+            EventKind::Decision => "Decision",
+            EventKind::Commit => "Commit",
+        }
+    }
+}
+
 /// The universal event envelope for the Loom worldline DAG (v2).
 ///
 /// Events are content-addressed and cryptographically linked to form a DAG
@@ -232,25 +243,27 @@ impl EventEnvelope {
         payload: &CanonicalBytes,
         parents: &[EventId],
     ) -> Result<EventId, CanonicalError> {
-        // Canonical structure for hashing
-        #[derive(Serialize)]
-        struct EventIdInput<'a> {
-            kind: &'a EventKind,
-            payload: &'a [u8],
-            parents: &'a [EventId],
+        // Canonical encoding of
+        //
+        //     struct EventIdInput { kind, payload: &[u8], parents: &[EventId] }
+        //
+        // written straight into the hasher: serde encodes byte slices and
+        // hashes as arrays of integers, and the keys sort as "kind" <
+        // "parents" < "payload" (shorter first, then bytewise).
+        let mut out = HashWriter::new();
+        out.head(5, 3);
+        out.text("kind");
+        out.head(5, 1);
+        out.text("type");
+        out.text(kind.variant_name());
+        out.text("parents");
+        out.head(4, parents.len() as u64);
+        for parent in parents {
+            out.byte_array(&parent.0);
         }
-
-        let input = EventIdInput {
-            kind,
-            payload: payload.as_bytes(),
-            parents, // Already sorted at construction
-        };
-
-        // Canonical encode and hash
-        let canonical_bytes = canonical::encode(&input)?;
-        let hash_bytes = blake3::hash(&canonical_bytes);
-
-        Ok(Hash(*hash_bytes.as_bytes()))
+        out.text("payload");
+        out.byte_array(payload.as_bytes());
+        Ok(out.finish())
     }
 
     /// Create a new Observation event.
@@ -375,12 +388,24 @@ impl EventEnvelope {
         })
     }
 
+    /// Attribute this event to `agent_id` and replace its signature.
+    ///
+    /// Neither the agent nor the signature is part of the event_id, so the
+    /// re-signed event keeps its identity and its place in the worldline.
+    pub fn with_signature(mut self, agent_id: AgentId, signature: Signature) -> Self {
+        self.agent_id = Some(agent_id);
+        self.signature = Some(signature);
+        self
+    }
+
     /// Canonicalize parent list: sort lexicographically and deduplicate.
     ///
     /// This ensures that H(parents) is deterministic regardless of insertion order.
-    fn canonicalize_parents(parents: Vec<EventId>) -> Vec<EventId> {
-        let unique: BTreeSet<EventId> = parents.into_iter().collect();
-        unique.into_iter().collect()
+    fn canonicalize_parents(mut parents: Vec<EventId>) -> Vec<EventId> {
+        // In place: most events have 0-2 parents, already sorted.
+        parents.sort_unstable();
+        parents.dedup();
+        parents
     }
 
     /// Verify that the event_id matches the computed hash.
@@ -656,7 +681,79 @@ impl<'a, S: EventStore> EventStore for CombinedStore<'a, S> {
     }
 }
 
+/// Canonical CBOR written into a BLAKE3 hasher through a fixed buffer, so
+/// event ids are computed without allocating.
+struct HashWriter {
+    hasher: blake3::Hasher,
+    buf: [u8; 256],
+    len: usize,
+}
+
+impl HashWriter {
+    fn new() -> Self {
+        HashWriter {
+            hasher: blake3::Hasher::new(),
+            buf: [0; 256],
+            len: 0,
+        }
+    }
+
+    fn put(&mut self, bytes: &[u8]) {
+        if self.len + bytes.len() > self.buf.len() {
+            self.hasher.update(&self.buf[..self.len]);
+            self.len = 0;
+        }
+        if bytes.len() > self.buf.len() {
+            self.hasher.update(bytes);
+        } else {
+            self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+        }
+    }
+
+    /// Shortest head for major type `major` with argument `n`.
+    fn head(&mut self, major: u8, n: u64) {
+        let major = major << 5;
+        match n {
+            0..=23 => self.put(&[major | n as u8]),
+            24..=0xff => self.put(&[major | 24, n as u8]),
+            0x100..=0xffff => {
+                self.put(&[major | 25]);
+                self.put(&(n as u16).to_be_bytes());
+            }
+            0x1_0000..=0xffff_ffff => {
+                self.put(&[major | 26]);
+                self.put(&(n as u32).to_be_bytes());
+            }
+            _ => {
+                self.put(&[major | 27]);
+                self.put(&n.to_be_bytes());
+            }
+        }
+    }
+
+    fn text(&mut self, s: &str) {
+        self.head(3, s.len() as u64);
+        self.put(s.as_bytes());
+    }
+
+    /// Bytes as serde serializes them: an array of unsigned integers.
+    fn byte_array(&mut self, bytes: &[u8]) {
+        self.head(4, bytes.len() as u64);
+        for &b in bytes {
+            self.head(0, b as u64);
+        }
+    }
+
+    fn finish(mut self) -> Hash {
+        self.hasher.update(&self.buf[..self.len]);
+        Hash(*self.hasher.finalize().as_bytes())
+    }
+}
+
 #[cfg(test)]
+// Tests deliberately build non-canonical CBOR with ciborium to exercise rejection paths.
+#[allow(clippy::disallowed_methods)]
 mod tests {
     use super::*;
     use std::collections::HashMap;
@@ -1614,4 +1711,38 @@ mod tests {
             "Deserialization should reject duplicate parents"
         );
     }
+
+    #[test]
+    fn test_event_id_matches_serde_encoding() {
+        #[derive(Serialize)]
+        struct EventIdInput<'a> {
+            kind: &'a EventKind,
+            payload: &'a [u8],
+            parents: &'a [EventId],
+        }
+
+        let kinds = [
+            EventKind::Observation,
+            EventKind::PolicyContext,
+            EventKind::Decision,
+            EventKind::Commit,
+        ];
+        for (i, kind) in kinds.iter().enumerate() {
+            for len in [0usize, 1, 23, 24, 255, 256, 300, 70_000] {
+                let payload = CanonicalBytes((0..len).map(|b| (b * 7 + i) as u8).collect());
+                let parents: Vec<EventId> = (0..i as u8).map(|p| Hash([p * 40; 32])).collect();
+                let reference = canonical::hash_canonical(&EventIdInput {
+                    kind,
+                    payload: payload.as_bytes(),
+                    parents: &parents,
+                })
+                .unwrap();
+                assert_eq!(
+                    EventEnvelope::compute_event_id(kind, &payload, &parents).unwrap(),
+                    reference,
+                    "{kind:?} with {len} payload bytes"
+                );
+            }
+        }
+    }
 }
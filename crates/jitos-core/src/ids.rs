@@ -0,0 +1,70 @@
+//! Typed Identifiers
+//!
+//! SLAPs name their targets with these types rather than raw strings, so a
+//! node id cannot be passed where an SWS id is expected and both hash
+//! canonically (SPEC-0001) as their inner value.
+
+use crate::Hash;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Deterministic node ID (content-addressed, not insertion-order-dependent)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct NodeId(pub Hash);
+
+impl NodeId {
+    /// Create NodeId from hash
+    pub fn from_hash(hash: Hash) -> Self {
+        Self(hash)
+    }
+
+    /// Get the underlying hash
+    pub fn hash(&self) -> Hash {
+        self.0
+    }
+
+    /// Parse the 64-digit hex form produced by `Display`.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(hex, &mut bytes).ok()?;
+        Some(Self(Hash(bytes)))
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Name of a Shadow Working Set.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SwsId(pub String);
+
+impl SwsId {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SwsId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for SwsId {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for SwsId {
+    fn from(name: String) -> Self {
+        Self(name)
+    }
+}
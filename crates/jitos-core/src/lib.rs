@@ -1,9 +1,17 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
+pub mod blob;
 pub mod canonical;
 pub mod delta;
 pub mod events;
+pub mod ids;
+pub mod store;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use ids::{NodeId, SwsId};
 
 /// A 256-bit BLAKE3 hash.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -17,7 +25,10 @@ impl fmt::Debug for Hash {
 
 impl fmt::Display for Hash {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", hex::encode(self.0))
+        // Encode on the stack: formatting a hash must not allocate.
+        let mut buf = [0u8; 64];
+        hex::encode_to_slice(self.0, &mut buf).expect("64 hex digits for 32 bytes");
+        f.write_str(std::str::from_utf8(&buf).expect("hex is ASCII"))
     }
 }
 
@@ -29,25 +40,101 @@ pub enum Slap {
     /// Create a new node in the graph.
     CreateNode {
         node_type: String,
-        data: serde_json::Value,
+        /// Opaque node payload (SPEC-WARP-0001), stored and hashed as-is.
+        payload_bytes: Vec<u8>,
     },
     /// Delete an existing node.
-    DeleteNode { id: String },
+    DeleteNode { id: NodeId },
     /// Connect two nodes.
     Connect {
-        source: String,
-        target: String,
+        source: NodeId,
+        target: NodeId,
         edge_type: String,
     },
     /// Invoke a sandboxed Rhai script.
     InvokeScript {
         script_id: Hash,
-        args: Vec<serde_json::Value>,
+        /// Script arguments, each canonically encoded (SPEC-0001).
+        args: Vec<events::CanonicalBytes>,
     },
     /// Set the logical time.
     SetTime { tick: u64, dt: f64 },
     /// Collapse a Shadow Working Set (SWS).
-    Collapse { sws_id: String },
+    Collapse { sws_id: SwsId },
+    /// Apply SLAPs as one unit (see [`Transaction`]).
+    Transaction(Transaction),
+}
+
+/// SLAPs that apply together: all in the same batch of the same tick, or
+/// all deferred (or rejected) together.
+///
+/// Members apply in order, each seeing the writes of the ones before it. A
+/// member can reference the node created by an earlier `CreateNode` member
+/// through [`Transaction::created`], since real ids are only allocated at
+/// execution. Transactions do not nest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub slaps: Vec<Slap>,
+}
+
+impl Transaction {
+    pub fn new(slaps: Vec<Slap>) -> Self {
+        Self { slaps }
+    }
+
+    /// Placeholder id for the node created by member `index` (a
+    /// `CreateNode`), resolved to the allocated id when the transaction
+    /// executes.
+    pub fn created(index: usize) -> NodeId {
+        NodeId::from_hash(
+            canonical::hash_canonical(&("transaction-created", index as u64))
+                .expect("a tag and an integer always encode"),
+        )
+    }
+}
+
+impl From<Transaction> for Slap {
+    fn from(transaction: Transaction) -> Self {
+        Slap::Transaction(transaction)
+    }
+}
+
+/// Scheduling metadata for a proposed SLAP.
+///
+/// Metadata is not part of the SLAP's identity: canonical hashes cover the
+/// SLAP alone, so the same SLAP proposed twice is still one operation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlapMeta {
+    /// Higher priorities are scheduled first.
+    pub priority: u32,
+    /// Tick by which the SLAP should run, for latency-sensitive work.
+    pub deadline: Option<u64>,
+    /// Agent the SLAP is charged to, for per-agent fuel limits. Omitted
+    /// from the encoding when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<events::AgentId>,
+}
+
+/// A SLAP proposed for scheduling, with its metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    pub slap: Slap,
+    pub meta: SlapMeta,
+}
+
+impl Proposal {
+    pub fn new(slap: Slap, meta: SlapMeta) -> Self {
+        Self { slap, meta }
+    }
+}
+
+impl From<Slap> for Proposal {
+    fn from(slap: Slap) -> Self {
+        Self {
+            slap,
+            meta: SlapMeta::default(),
+        }
+    }
 }
 
 /// A deterministic record of a single tick's execution.
@@ -58,13 +145,83 @@ pub struct Receipt {
     pub applied_slaps: Vec<Hash>,
     pub timestamp: u64,
     pub signature: Option<String>,
+    /// State hashes of the views the receipt commits to, keyed by view name.
+    ///
+    /// Omitted from the encoding when empty, so receipts without view hashes
+    /// encode exactly as before.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub view_hashes: BTreeMap<String, Hash>,
+    /// [`Receipt::digest`] of the previous tick's receipt (`None` for the
+    /// first receipt of a chain). Omitted from the encoding when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_receipt: Option<Hash>,
+    /// Fuel the tick's applied SLAPs used (`None` if it applied none).
+    /// Omitted from the encoding when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuel: Option<FuelUsage>,
+}
+
+/// Fuel used by one tick, in cost model units.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuelUsage {
+    pub total: u64,
+    /// Fuel charged to each agent, keyed by agent id; SLAPs without an agent
+    /// count towards `total` only.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub by_agent: BTreeMap<String, u64>,
+}
+
+impl Receipt {
+    /// Commit to a view's state hash under `name`, replacing any previous one.
+    pub fn with_view_hash(mut self, name: impl Into<String>, hash: Hash) -> Self {
+        self.view_hashes.insert(name.into(), hash);
+        self
+    }
+
+    /// Chain this receipt after the receipt with digest `prev`.
+    pub fn chained_to(mut self, prev: Hash) -> Self {
+        self.prev_receipt = Some(prev);
+        self
+    }
+
+    /// Canonical hash of the receipt without its signature.
+    ///
+    /// This is what receipts chain over and what the node signs, so adding
+    /// or checking a signature never changes the chain.
+    pub fn digest(&self) -> Result<Hash, canonical::CanonicalError> {
+        canonical::hash_canonical(&Receipt {
+            signature: None,
+            ..self.clone()
+        })
+    }
+
+    /// Attach `signature` (over [`Receipt::digest`]), hex-encoded.
+    pub fn with_signature(mut self, signature: &events::Signature) -> Self {
+        self.signature = Some(hex::encode(signature.as_bytes()));
+        self
+    }
+}
+
+/// A broken invariant, as data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    /// Hash identifying the invariant.
+    pub invariant: Hash,
+    /// Tick after which the invariant stopped holding.
+    pub tick: u64,
+    /// Canonical hashes of the SLAPs that tick applied.
+    pub slaps: Vec<Hash>,
+    /// The measured quantity the invariant bounds (a count).
+    pub observed: u64,
+    /// Ids of the offending nodes, ascending (empty when none apply).
+    pub witnesses: Vec<Hash>,
 }
 
 /// Standard Error types for the Loom universe.
 #[derive(thiserror::Error, Debug, Serialize, Deserialize)]
 pub enum JitosError {
-    #[error("Invariant violation: {0}")]
-    InvariantViolation(String),
+    #[error("Invariant {} violated after tick {}", .0.invariant, .0.tick)]
+    InvariantViolation(Violation),
     #[error("Conflict detected: {0}")]
     Conflict(String),
     #[error("Access denied: {0}")]
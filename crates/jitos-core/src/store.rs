@@ -0,0 +1,153 @@
+//! In-memory worldline store with stable segment iteration.
+//!
+//! The store keeps events in canonical worldline order (append order, which is
+//! topological because every append is validated against the events already
+//! present). Positions in that order are *cuts*: cut `n` is the prefix
+//! `events[..n]`, matching `ClockView::now_at_cut`.
+//!
+//! Analytics can split the worldline into [`Segment`]s with fixed cut
+//! boundaries, process them independently (in parallel), and merge the partial
+//! results in segment order — the boundaries and merge order are a pure
+//! function of the worldline, so results are deterministic.
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+use crate::events::{validate_event, EventEnvelope, EventError, EventId, EventStore};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Append-only, validated, in-memory event store.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryEventStore {
+    events: Vec<EventEnvelope>,
+    index: HashMap<EventId, usize>,
+}
+
+impl MemoryEventStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate and append an event.
+    ///
+    /// Appending an event that is already present is a no-op (events are
+    /// content-addressed, so the duplicate is identical).
+    pub fn append(&mut self, event: EventEnvelope) -> Result<(), EventError> {
+        if self.index.contains_key(&event.event_id()) {
+            return Ok(());
+        }
+        validate_event(&event, self)?;
+        self.index.insert(event.event_id(), self.events.len());
+        self.events.push(event);
+        Ok(())
+    }
+
+    /// Number of events (the cut at the head of the worldline).
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// All events in canonical worldline order.
+    pub fn events(&self) -> &[EventEnvelope] {
+        &self.events
+    }
+
+    /// Position of an event in the worldline (`events()[position(id)]` is the event).
+    pub fn position(&self, event_id: &EventId) -> Option<usize> {
+        self.index.get(event_id).copied()
+    }
+
+    /// Events that are not a parent of any other event, in worldline order.
+    pub fn heads(&self) -> Vec<EventId> {
+        let mut has_child = vec![false; self.events.len()];
+        for event in &self.events {
+            for parent in event.parents() {
+                if let Some(&pos) = self.index.get(parent) {
+                    has_child[pos] = true;
+                }
+            }
+        }
+        self.events
+            .iter()
+            .zip(has_child)
+            .filter(|(_, child)| !child)
+            .map(|(e, _)| e.event_id())
+            .collect()
+    }
+
+    /// A single segment covering the cut range `range`.
+    ///
+    /// Returns `None` if the range exceeds the worldline.
+    pub fn segment(&self, index: usize, range: Range<usize>) -> Option<Segment<'_>> {
+        let events = self.events.get(range.clone())?;
+        Some(Segment {
+            index,
+            range,
+            events,
+        })
+    }
+
+    /// Split the worldline into consecutive segments of `segment_len` events.
+    ///
+    /// The last segment may be shorter. Boundaries depend only on `len()` and
+    /// `segment_len`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `segment_len == 0`.
+    pub fn segments(&self, segment_len: usize) -> impl Iterator<Item = Segment<'_>> + '_ {
+        assert!(segment_len > 0, "segment_len must be non-zero");
+        self.events
+            .chunks(segment_len)
+            .enumerate()
+            .map(move |(index, events)| {
+                let start = index * segment_len;
+                Segment {
+                    index,
+                    range: start..start + events.len(),
+                    events,
+                }
+            })
+    }
+
+    /// Map every segment concurrently and return the results in segment order.
+    ///
+    /// Segments run on the rayon thread pool, not a thread each. Thread
+    /// scheduling cannot affect the output: result `i` is always `f` of segment
+    /// `i`. Fold the returned Vec front-to-back to merge deterministically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `segment_len == 0`.
+    pub fn par_map_segments<T, F>(&self, segment_len: usize, f: F) -> Vec<T>
+    where
+        T: Send,
+        F: Fn(Segment<'_>) -> T + Sync,
+    {
+        let segments: Vec<Segment<'_>> = self.segments(segment_len).collect();
+        segments.into_par_iter().map(&f).collect()
+    }
+}
+
+impl EventStore for MemoryEventStore {
+    fn get(&self, event_id: &EventId) -> Option<&EventEnvelope> {
+        self.index.get(event_id).map(|&pos| &self.events[pos])
+    }
+}
+
+/// A contiguous slice of the worldline with well-defined cut boundaries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment<'a> {
+    /// Position of this segment in the segmentation (0-based).
+    pub index: usize,
+    /// Cut range covered: events `range.start..range.end` of the worldline.
+    pub range: Range<usize>,
+    /// The events in this segment.
+    pub events: &'a [EventEnvelope],
+}
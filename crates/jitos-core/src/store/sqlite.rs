@@ -0,0 +1,355 @@
+//! SQLite-backed worldline store (feature `sqlite`).
+//!
+//! [`SqliteEventStore`] keeps a worldline in a single SQLite file. Appends go
+//! through the same validation pipeline as [`MemoryEventStore`]
+//! ([`validate_store`]), and a batch is committed in one transaction: either
+//! every new event in it is stored or none is.
+//!
+//! The schema is meant for ad-hoc querying through [`SqliteEventStore::connection`]:
+//!
+//! ```sql
+//! events  (event_id BLOB PRIMARY KEY, seq INTEGER UNIQUE, kind TEXT,
+//!          observation_type TEXT, agent_id TEXT, payload BLOB, envelope BLOB)
+//! parents (event_id BLOB, position INTEGER, parent_id BLOB)
+//! ```
+//!
+//! `seq` is the worldline position (the cut before the event), `kind` is the
+//! `EventKind` variant name, `payload` the canonical payload bytes and
+//! `envelope` the canonical encoding of the whole event. `parents` holds one
+//! row per parent edge in canonical order. `kind`, `observation_type`,
+//! `agent_id` and `parent_id` are indexed, so queries such as
+//!
+//! ```sql
+//! SELECT hex(event_id) FROM events
+//! WHERE kind = 'Observation' AND observation_type = 'OBS_CLOCK_SAMPLE_V0'
+//! ORDER BY seq
+//! ```
+//!
+//! do not scan the worldline.
+//!
+//! The inherent reads query the database and return owned events. The store
+//! also implements [`EventStore`], which hands out borrowed events, from a
+//! read cache of decoded events: it is loaded when the store is opened and
+//! kept in step by every append, and it validates new batches too.
+//! [`SqliteEventStore::to_memory`] loads the worldline into a
+//! [`MemoryEventStore`] for code that needs the full in-memory API.
+
+use crate::canonical::{self, CanonicalError};
+use crate::events::{validate_store, EventEnvelope, EventError, EventId, EventKind, EventStore};
+use crate::store::MemoryEventStore;
+use crate::Hash;
+use rusqlite::{params, Connection, OptionalExtension, Params};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Version of the schema below, kept in SQLite's `user_version`.
+const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE events (
+    event_id BLOB PRIMARY KEY,
+    seq INTEGER NOT NULL UNIQUE,
+    kind TEXT NOT NULL,
+    observation_type TEXT,
+    agent_id TEXT,
+    payload BLOB NOT NULL,
+    envelope BLOB NOT NULL
+) WITHOUT ROWID;
+CREATE TABLE parents (
+    event_id BLOB NOT NULL REFERENCES events(event_id),
+    position INTEGER NOT NULL,
+    parent_id BLOB NOT NULL REFERENCES events(event_id),
+    PRIMARY KEY (event_id, position)
+) WITHOUT ROWID;
+CREATE INDEX events_by_kind ON events(kind);
+CREATE INDEX events_by_observation_type ON events(observation_type);
+CREATE INDEX events_by_agent ON events(agent_id);
+CREATE INDEX parents_by_parent ON parents(parent_id);
+";
+
+/// Errors from the SQLite event store.
+#[derive(Debug, thiserror::Error)]
+pub enum SqliteStoreError {
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("Canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+
+    #[error("Event rejected: {0}")]
+    Event(#[from] EventError),
+
+    #[error("Unsupported store schema version {0} (expected {SCHEMA_VERSION})")]
+    UnsupportedSchema(i64),
+
+    #[error("Corrupt store: {0}")]
+    Corrupt(String),
+}
+
+/// Append-only, validated event store in a SQLite database.
+#[derive(Debug)]
+pub struct SqliteEventStore {
+    conn: Connection,
+    /// Every stored event, decoded; backs [`EventStore`].
+    cache: EventCache,
+}
+
+impl SqliteEventStore {
+    /// Open the store at `path`, creating the file and schema if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SqliteStoreError> {
+        let conn = Connection::open(path)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        Self::init(conn)
+    }
+
+    /// Open a store that lives only as long as the returned value.
+    pub fn open_in_memory() -> Result<Self, SqliteStoreError> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, SqliteStoreError> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        match version {
+            0 => {
+                conn.execute_batch(&format!(
+                    "BEGIN; {SCHEMA} PRAGMA user_version = {SCHEMA_VERSION}; COMMIT;"
+                ))?;
+            }
+            SCHEMA_VERSION => {}
+            other => return Err(SqliteStoreError::UnsupportedSchema(other)),
+        }
+        let mut store = Self {
+            conn,
+            cache: EventCache::default(),
+        };
+        store.cache = EventCache(
+            store
+                .events()?
+                .into_iter()
+                .map(|event| (event.event_id(), event))
+                .collect(),
+        );
+        Ok(store)
+    }
+
+    /// The underlying connection, for ad-hoc queries (see the module docs for
+    /// the schema). Writes through it bypass validation and are not seen
+    /// through [`EventStore`] until the store is reopened.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Validate and append an event.
+    ///
+    /// Appending an event that is already present is a no-op, as in
+    /// [`MemoryEventStore::append`].
+    pub fn append(&mut self, event: EventEnvelope) -> Result<(), SqliteStoreError> {
+        self.append_batch([event]).map(drop)
+    }
+
+    /// Validate and append a batch of events in one transaction.
+    ///
+    /// Events must be in topological order; they may name parents already in
+    /// the store or earlier in the batch. Events already present are skipped.
+    /// If any event fails validation nothing is written. Returns the number of
+    /// events added.
+    pub fn append_batch(
+        &mut self,
+        events: impl IntoIterator<Item = EventEnvelope>,
+    ) -> Result<usize, SqliteStoreError> {
+        let tx = self.conn.transaction()?;
+
+        let mut new_ids = HashSet::new();
+        let mut batch = Vec::new();
+        for event in events {
+            let id = event.event_id();
+            if !new_ids.contains(&id) && !contains(&tx, &id)? {
+                new_ids.insert(id);
+                batch.push(event);
+            }
+        }
+
+        // Validate exactly as `validate_store` does for in-memory imports,
+        // with stored parents resolved from the cache.
+        validate_store(&self.cache, &batch)?;
+
+        let mut seq = count(&tx)? as i64;
+        {
+            let mut insert_event = tx.prepare_cached(
+                "INSERT INTO events \
+                 (event_id, seq, kind, observation_type, agent_id, payload, envelope) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            let mut insert_parent = tx.prepare_cached(
+                "INSERT INTO parents (event_id, position, parent_id) VALUES (?1, ?2, ?3)",
+            )?;
+            for event in &batch {
+                let id = event.event_id();
+                insert_event.execute(params![
+                    &id.0[..],
+                    seq,
+                    kind_name(event.kind()),
+                    event.observation_type(),
+                    event.agent_id().map(|a| a.as_str()),
+                    event.payload().as_bytes(),
+                    canonical::encode(event)?,
+                ])?;
+                for (position, parent) in event.parents().iter().enumerate() {
+                    insert_parent.execute(params![&id.0[..], position as i64, &parent.0[..]])?;
+                }
+                seq += 1;
+            }
+        }
+        tx.commit()?;
+        let added = batch.len();
+        self.cache
+            .0
+            .extend(batch.into_iter().map(|event| (event.event_id(), event)));
+        Ok(added)
+    }
+
+    /// Number of events (the cut at the head of the worldline).
+    pub fn len(&self) -> Result<usize, SqliteStoreError> {
+        count(&self.conn)
+    }
+
+    pub fn is_empty(&self) -> Result<bool, SqliteStoreError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Whether the event is in the store.
+    pub fn contains(&self, event_id: &EventId) -> Result<bool, SqliteStoreError> {
+        contains(&self.conn, event_id)
+    }
+
+    /// The event with this ID, if present.
+    pub fn get(&self, event_id: &EventId) -> Result<Option<EventEnvelope>, SqliteStoreError> {
+        get(&self.conn, event_id)
+    }
+
+    /// Position of an event in the worldline.
+    pub fn position(&self, event_id: &EventId) -> Result<Option<usize>, SqliteStoreError> {
+        let seq: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT seq FROM events WHERE event_id = ?1",
+                [&event_id.0[..]],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(seq.map(|seq| seq as usize))
+    }
+
+    /// All events in canonical worldline order.
+    pub fn events(&self) -> Result<Vec<EventEnvelope>, SqliteStoreError> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT event_id, envelope FROM events ORDER BY seq")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.map(|row| {
+            let (id, envelope): (Vec<u8>, Vec<u8>) = row?;
+            decode(&id, &envelope)
+        })
+        .collect()
+    }
+
+    /// Events that name `event_id` as a parent, in worldline order.
+    pub fn children(&self, event_id: &EventId) -> Result<Vec<EventId>, SqliteStoreError> {
+        self.ids(
+            "SELECT p.event_id FROM parents p JOIN events e ON e.event_id = p.event_id \
+             WHERE p.parent_id = ?1 ORDER BY e.seq",
+            [&event_id.0[..]],
+        )
+    }
+
+    /// Events that are not a parent of any other event, in worldline order.
+    pub fn heads(&self) -> Result<Vec<EventId>, SqliteStoreError> {
+        self.ids(
+            "SELECT event_id FROM events \
+             WHERE event_id NOT IN (SELECT parent_id FROM parents) ORDER BY seq",
+            [],
+        )
+    }
+
+    /// Load the whole worldline into memory, re-validating every event.
+    pub fn to_memory(&self) -> Result<MemoryEventStore, SqliteStoreError> {
+        let mut store = MemoryEventStore::new();
+        for event in self.events()? {
+            store.append(event)?;
+        }
+        Ok(store)
+    }
+
+    fn ids(&self, sql: &str, params: impl Params) -> Result<Vec<EventId>, SqliteStoreError> {
+        let mut stmt = self.conn.prepare_cached(sql)?;
+        let rows = stmt.query_map(params, |row| row.get::<_, Vec<u8>>(0))?;
+        rows.map(|id| event_id(&id?)).collect()
+    }
+}
+
+impl EventStore for SqliteEventStore {
+    fn get(&self, event_id: &EventId) -> Option<&EventEnvelope> {
+        self.cache.get(event_id)
+    }
+}
+
+/// Decoded stored events, by ID.
+#[derive(Debug, Default)]
+struct EventCache(HashMap<EventId, EventEnvelope>);
+
+impl EventStore for EventCache {
+    fn get(&self, event_id: &EventId) -> Option<&EventEnvelope> {
+        self.0.get(event_id)
+    }
+}
+
+fn kind_name(kind: &EventKind) -> &'static str {
+    match kind {
+        EventKind::Observation => "Observation",
+        EventKind::PolicyContext => "PolicyContext",
+        EventKind::Decision => "Decision",
+        EventKind::Commit => "Commit",
+    }
+}
+
+fn count(conn: &Connection) -> Result<usize, SqliteStoreError> {
+    let n: i64 = conn.query_row("SELECT COUNT(*) FROM events", [], |row| row.get(0))?;
+    Ok(n as usize)
+}
+
+fn contains(conn: &Connection, event_id: &EventId) -> Result<bool, SqliteStoreError> {
+    Ok(conn
+        .prepare_cached("SELECT 1 FROM events WHERE event_id = ?1")?
+        .exists([&event_id.0[..]])?)
+}
+
+fn get(conn: &Connection, event_id: &EventId) -> Result<Option<EventEnvelope>, SqliteStoreError> {
+    let envelope: Option<Vec<u8>> = conn
+        .prepare_cached("SELECT envelope FROM events WHERE event_id = ?1")?
+        .query_row([&event_id.0[..]], |row| row.get(0))
+        .optional()?;
+    envelope
+        .map(|envelope| decode(&event_id.0, &envelope))
+        .transpose()
+}
+
+/// Decode a stored envelope and check it is the event its row is keyed by.
+fn decode(id: &[u8], envelope: &[u8]) -> Result<EventEnvelope, SqliteStoreError> {
+    let event: EventEnvelope = canonical::decode(envelope)?;
+    if event.event_id().0[..] != *id {
+        return Err(SqliteStoreError::Corrupt(format!(
+            "row {} holds event {}",
+            hex::encode(id),
+            event.event_id()
+        )));
+    }
+    Ok(event)
+}
+
+fn event_id(bytes: &[u8]) -> Result<EventId, SqliteStoreError> {
+    let bytes: [u8; 32] = bytes.try_into().map_err(|_| {
+        SqliteStoreError::Corrupt(format!("event id {} is not 32 bytes", hex::encode(bytes)))
+    })?;
+    Ok(Hash(bytes))
+}
@@ -0,0 +1,114 @@
+//! Test support: allocation counting for determinism-critical hot paths.
+//!
+//! Enabled with the `testing` feature. Intended for tests and benches only.
+//!
+//! A test binary installs [`CountingAllocator`] as its global allocator and then
+//! wraps a hot path in [`count_allocations`] to assert it stays within a budget:
+//!
+//! ```ignore
+//! use jitos_core::testing::{count_allocations, CountingAllocator};
+//!
+//! #[global_allocator]
+//! static ALLOC: CountingAllocator = CountingAllocator;
+//!
+//! let (_, stats) = count_allocations(|| event.verify_event_id());
+//! assert!(stats.allocations <= 8);
+//! ```
+//!
+//! Counters are thread-local, so tests running in parallel on other threads do
+//! not pollute each other's measurements.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Global allocator wrapper around [`System`] that counts allocations per thread.
+///
+/// Reallocations count as allocations: a `Vec` that grows three times is three
+/// allocations, which is exactly the kind of creep this harness exists to catch.
+pub struct CountingAllocator;
+
+// SAFETY: every call is forwarded unchanged to the System allocator; the only
+// extra work is bumping const-initialized thread-local counters, which never allocates.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+fn record(size: usize) {
+    // try_with: the thread may be tearing down its locals while still freeing/allocating.
+    let _ = ALLOCATIONS.try_with(|c| c.set(c.get() + 1));
+    let _ = BYTES.try_with(|c| c.set(c.get() + size as u64));
+}
+
+/// Allocation statistics observed on the current thread.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of alloc/alloc_zeroed/realloc calls.
+    pub allocations: u64,
+    /// Total bytes requested across those calls.
+    pub bytes: u64,
+}
+
+impl AllocStats {
+    /// Snapshot the current thread's counters.
+    pub fn current() -> Self {
+        Self {
+            allocations: ALLOCATIONS.with(Cell::get),
+            bytes: BYTES.with(Cell::get),
+        }
+    }
+
+    fn since(self, start: Self) -> Self {
+        Self {
+            allocations: self.allocations - start.allocations,
+            bytes: self.bytes - start.bytes,
+        }
+    }
+}
+
+/// Run `f` and return its result together with the allocations it performed.
+///
+/// Only meaningful when [`CountingAllocator`] is the global allocator; otherwise
+/// the returned stats are always zero.
+pub fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let start = AllocStats::current();
+    let result = f();
+    let stats = AllocStats::current().since(start);
+    (result, stats)
+}
+
+/// Assert that `f` performs at most `max_allocations` allocations.
+///
+/// Panics with the observed count and the `label` so budget regressions are
+/// attributable without re-running under a profiler.
+pub fn assert_alloc_budget<R>(label: &str, max_allocations: u64, f: impl FnOnce() -> R) -> R {
+    let (result, stats) = count_allocations(f);
+    assert!(
+        stats.allocations <= max_allocations,
+        "{label}: {} allocations ({} bytes) exceeds budget of {max_allocations}",
+        stats.allocations,
+        stats.bytes
+    );
+    result
+}
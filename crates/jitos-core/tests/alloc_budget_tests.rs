@@ -0,0 +1,77 @@
+//! Allocation budgets for determinism-critical hot paths in jitos-core.
+//!
+//! Budgets are deliberately a little above today's measured counts: they exist to
+//! catch convenience clones creeping into hashing, not to micro-optimize. Event
+//! ids are hashed straight from the envelope's fields, so their budget is zero.
+
+use jitos_core::canonical;
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventKind};
+use jitos_core::testing::{assert_alloc_budget, count_allocations, CountingAllocator};
+use jitos_core::Hash;
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+fn sample_event() -> EventEnvelope {
+    let payload = CanonicalBytes::from_value(&("clock", 1_000_000_000u64)).unwrap();
+    EventEnvelope::new_observation(
+        payload,
+        vec![Hash([1u8; 32]), Hash([2u8; 32])],
+        Some("OBS_TEST_V0".to_string()),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn counter_observes_allocations() {
+    let (_, stats) = count_allocations(|| vec![0u8; 64]);
+    assert_eq!(stats.allocations, 1);
+    assert!(stats.bytes >= 64);
+
+    let (_, none) = count_allocations(|| 1 + 1);
+    assert_eq!(none.allocations, 0);
+}
+
+#[test]
+fn compute_event_id_within_budget() {
+    let event = sample_event();
+    let id = assert_alloc_budget("compute_event_id", 0, || {
+        EventEnvelope::compute_event_id(&EventKind::Observation, event.payload(), event.parents())
+            .unwrap()
+    });
+    assert_eq!(id, event.event_id());
+}
+
+#[test]
+fn verify_event_id_within_budget() {
+    let event = sample_event();
+    let ok = assert_alloc_budget("verify_event_id", 0, || event.verify_event_id().unwrap());
+    assert!(ok);
+}
+
+#[test]
+fn construction_reuses_the_parents_vec() {
+    let payload = CanonicalBytes::from_value(&7u64).unwrap();
+    let (event, stats) = count_allocations(|| {
+        EventEnvelope::new_observation(
+            payload,
+            vec![Hash([2u8; 32]), Hash([1u8; 32]), Hash([2u8; 32])],
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+    });
+    assert_eq!(stats.allocations, 1, "only the parents vec itself");
+    assert_eq!(event.parents(), [Hash([1u8; 32]), Hash([2u8; 32])]);
+}
+
+#[test]
+fn hash_canonical_within_budget() {
+    let value = (Hash([7u8; 32]), 42u64, "kind");
+    assert_alloc_budget("hash_canonical", 12, || {
+        canonical::hash_canonical(&value).unwrap()
+    });
+}
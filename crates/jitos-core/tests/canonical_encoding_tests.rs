@@ -276,3 +276,83 @@ fn test_encoding_determinism() {
         assert_eq!(encoding, first_encoding, "Encoding must be deterministic");
     }
 }
+
+#[test]
+fn test_receipt_view_hashes_are_backward_compatible() {
+    use jitos_core::{Hash, Receipt};
+
+    // Receipt layout before view hashes were added
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct LegacyReceipt {
+        tick: u64,
+        state_hash: Hash,
+        applied_slaps: Vec<Hash>,
+        timestamp: u64,
+        signature: Option<String>,
+    }
+
+    let receipt = Receipt {
+        tick: 7,
+        state_hash: Hash([1u8; 32]),
+        applied_slaps: vec![Hash([2u8; 32])],
+        timestamp: 42,
+        signature: None,
+        view_hashes: BTreeMap::new(),
+        prev_receipt: None,
+        fuel: None,
+    };
+    let legacy = LegacyReceipt {
+        tick: 7,
+        state_hash: Hash([1u8; 32]),
+        applied_slaps: vec![Hash([2u8; 32])],
+        timestamp: 42,
+        signature: None,
+    };
+
+    // No view hashes: byte-identical to the legacy encoding, and legacy bytes decode
+    let legacy_bytes = canonical::encode(&legacy).unwrap();
+    assert_eq!(canonical::encode(&receipt).unwrap(), legacy_bytes);
+    let decoded: Receipt = canonical::decode(&legacy_bytes).unwrap();
+    assert!(decoded.view_hashes.is_empty());
+    assert!(decoded.prev_receipt.is_none());
+
+    // With view hashes: they change the encoding and round-trip
+    let receipt = receipt
+        .with_view_hash("timer", Hash([4u8; 32]))
+        .with_view_hash("clock", Hash([3u8; 32]));
+    let bytes = canonical::encode(&receipt).unwrap();
+    assert_ne!(bytes, legacy_bytes);
+    let decoded: Receipt = canonical::decode(&bytes).unwrap();
+    assert_eq!(decoded.view_hashes, receipt.view_hashes);
+}
+
+#[test]
+fn test_check_reports_violation_offset() {
+    // {"a": 1, "b": [2]} is canonical
+    let good = canonical::encode(&BTreeMap::from([("a", vec![1]), ("b", vec![2])])).unwrap();
+    assert_eq!(canonical::check(&good), Ok(()));
+
+    // Keys out of order: the violation is at the second key
+    let unsorted = [0xA2, 0x61, b'b', 0x01, 0x61, b'a', 0x02];
+    let violation = canonical::check(&unsorted).unwrap_err();
+    assert_eq!(violation.offset, 4);
+    assert_eq!(violation.error, CanonicalError::MapKeyOrder);
+
+    // Over-wide integer nested in an array: [1, 24-as-two-bytes(5)]
+    let wide = [0x82, 0x01, 0x19, 0x00, 0x05];
+    assert_eq!(
+        canonical::check(&wide).unwrap_err(),
+        canonical::Violation {
+            offset: 2,
+            error: CanonicalError::NonCanonicalInt
+        }
+    );
+
+    // Trailing bytes are reported where they start
+    let violation = canonical::check(&[0x01, 0x02]).unwrap_err();
+    assert_eq!(
+        violation.to_string(),
+        "trailing bytes after value at byte 1"
+    );
+    assert_eq!(violation.offset, 1);
+}
@@ -0,0 +1,201 @@
+//! Behavioral tests for SqliteEventStore.
+
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventId, EventStore, Signature};
+use jitos_core::store::sqlite::{SqliteEventStore, SqliteStoreError};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+
+fn observation(value: u64, parents: Vec<EventId>, agent: &str) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&value).unwrap(),
+        parents,
+        Some("OBS_TEST_V0".to_string()),
+        Some(AgentId::new(agent).unwrap()),
+        None,
+    )
+    .unwrap()
+}
+
+/// policy, two observations, a decision over them and its commit.
+fn worldline() -> Vec<EventEnvelope> {
+    let policy = EventEnvelope::new_policy_context(
+        CanonicalBytes::from_value(&"fifo").unwrap(),
+        vec![],
+        None,
+        None,
+    )
+    .unwrap();
+    let a = observation(1, vec![], "alice");
+    let b = observation(2, vec![a.event_id()], "bob");
+    let decision = EventEnvelope::new_decision(
+        CanonicalBytes::from_value(&"fire").unwrap(),
+        vec![b.event_id()],
+        policy.event_id(),
+        None,
+        None,
+    )
+    .unwrap();
+    let commit = EventEnvelope::new_commit(
+        CanonicalBytes::from_value(&"fired").unwrap(),
+        decision.event_id(),
+        vec![],
+        Some(AgentId::new("alice").unwrap()),
+        Signature::new(vec![7; 64]).unwrap(),
+    )
+    .unwrap();
+    vec![policy, a, b, decision, commit]
+}
+
+#[test]
+fn file_store_persists_worldline_order_and_supports_sql() {
+    let path = std::env::temp_dir().join(format!("jitos-sqlite-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let events = worldline();
+    {
+        let mut store = SqliteEventStore::open(&path).unwrap();
+        assert_eq!(store.append_batch(events[..2].to_vec()).unwrap(), 2);
+        for event in &events[2..] {
+            store.append(event.clone()).unwrap();
+        }
+    }
+
+    let store = SqliteEventStore::open(&path).unwrap();
+    assert_eq!(store.len().unwrap(), 5);
+    assert_eq!(store.events().unwrap(), events);
+    assert_eq!(
+        store.get(&events[3].event_id()).unwrap(),
+        Some(events[3].clone())
+    );
+    assert_eq!(store.get(&Hash([0xAB; 32])).unwrap(), None);
+    assert_eq!(store.position(&events[2].event_id()).unwrap(), Some(2));
+    assert_eq!(store.heads().unwrap(), vec![events[4].event_id()]);
+    assert_eq!(
+        store.children(&events[0].event_id()).unwrap(),
+        vec![events[3].event_id()]
+    );
+
+    let observed_by: Vec<String> = store
+        .connection()
+        .prepare(
+            "SELECT agent_id FROM events \
+             WHERE kind = 'Observation' AND observation_type = 'OBS_TEST_V0' ORDER BY seq",
+        )
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(observed_by, ["alice", "bob"]);
+    let edges: i64 = store
+        .connection()
+        .query_row("SELECT COUNT(*) FROM parents", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(edges, 1 + 2 + 1);
+
+    drop(store);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn batches_are_all_or_nothing_and_idempotent() {
+    let events = worldline();
+    let mut store = SqliteEventStore::open_in_memory().unwrap();
+    store.append(events[0].clone()).unwrap();
+
+    // The decision names an observation where its policy should be.
+    let bad_decision = EventEnvelope::new_decision(
+        CanonicalBytes::from_value(&"fire").unwrap(),
+        vec![events[2].event_id()],
+        events[1].event_id(),
+        None,
+        None,
+    )
+    .unwrap();
+    let err = store
+        .append_batch([events[1].clone(), events[2].clone(), bad_decision])
+        .unwrap_err();
+    assert!(matches!(err, SqliteStoreError::Event(_)), "{err}");
+    assert_eq!(store.len().unwrap(), 1, "a rejected batch writes nothing");
+
+    let orphan = observation(9, vec![Hash([0xAB; 32])], "carol");
+    assert!(matches!(
+        store.append(orphan),
+        Err(SqliteStoreError::Event(_))
+    ));
+
+    // Stored parents and parents earlier in the batch both resolve; events
+    // already present or repeated in the batch are skipped.
+    let mut batch = events.clone();
+    batch.insert(3, events[2].clone());
+    assert_eq!(store.append_batch(batch).unwrap(), 4);
+    assert_eq!(store.append_batch(events.clone()).unwrap(), 0);
+    assert_eq!(store.events().unwrap(), events);
+}
+
+#[test]
+fn loads_into_a_memory_store_with_identical_validation() {
+    let events = worldline();
+    let mut sqlite = SqliteEventStore::open_in_memory().unwrap();
+    let mut memory = MemoryEventStore::new();
+    for event in &events {
+        sqlite.append(event.clone()).unwrap();
+        memory.append(event.clone()).unwrap();
+    }
+
+    let loaded = sqlite.to_memory().unwrap();
+    assert_eq!(loaded.events(), memory.events());
+    assert_eq!(loaded.heads(), sqlite.heads().unwrap());
+    assert_eq!(
+        loaded.get(&events[4].event_id()),
+        sqlite.get(&events[4].event_id()).unwrap().as_ref()
+    );
+
+    // A commit without a decision parent fails both stores the same way.
+    let no_decision = EventEnvelope::new_commit(
+        CanonicalBytes::from_value(&"fired").unwrap(),
+        events[1].event_id(),
+        vec![],
+        None,
+        Signature::new(vec![7; 64]).unwrap(),
+    )
+    .unwrap();
+    let memory_err = memory.append(no_decision.clone()).unwrap_err();
+    match sqlite.append(no_decision) {
+        Err(SqliteStoreError::Event(err)) => assert_eq!(err.to_string(), memory_err.to_string()),
+        other => panic!("expected a validation error, got {other:?}"),
+    }
+}
+
+#[test]
+fn event_store_reads_follow_appends_and_reopening() {
+    fn borrowed<S: EventStore>(store: &S, id: &EventId) -> Option<EventEnvelope> {
+        store.get(id).cloned()
+    }
+
+    let path = std::env::temp_dir().join(format!("jitos-sqlite-cache-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let events = worldline();
+    {
+        let mut store = SqliteEventStore::open(&path).unwrap();
+        store.append_batch(events[..3].to_vec()).unwrap();
+        assert_eq!(
+            borrowed(&store, &events[2].event_id()),
+            Some(events[2].clone())
+        );
+        assert_eq!(borrowed(&store, &events[3].event_id()), None);
+    }
+
+    let mut store = SqliteEventStore::open(&path).unwrap();
+    assert_eq!(
+        borrowed(&store, &events[1].event_id()),
+        Some(events[1].clone())
+    );
+    store.append_batch(events[3..].to_vec()).unwrap();
+    for event in &events {
+        assert_eq!(borrowed(&store, &event.event_id()).as_ref(), Some(event));
+    }
+    assert_eq!(borrowed(&store, &Hash([0xAB; 32])), None);
+
+    drop(store);
+    let _ = std::fs::remove_file(&path);
+}
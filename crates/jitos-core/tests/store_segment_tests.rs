@@ -0,0 +1,98 @@
+//! Behavioral tests for MemoryEventStore and segment iteration.
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventStore};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+
+/// A linear chain of `n` observations, each parented on the previous one.
+fn chain(n: u64) -> MemoryEventStore {
+    let mut store = MemoryEventStore::new();
+    let mut parent: Option<Hash> = None;
+    for i in 0..n {
+        let event = EventEnvelope::new_observation(
+            CanonicalBytes::from_value(&i).unwrap(),
+            parent.into_iter().collect(),
+            Some("OBS_TEST_V0".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+        parent = Some(event.event_id());
+        store.append(event).unwrap();
+    }
+    store
+}
+
+fn payload_sum(events: &[EventEnvelope]) -> u64 {
+    events
+        .iter()
+        .map(|e| e.payload().to_value::<u64>().unwrap())
+        .sum()
+}
+
+#[test]
+fn append_validates_parents_and_is_idempotent() {
+    let mut store = chain(3);
+    let head = store.events()[2].clone();
+    store
+        .append(head.clone())
+        .expect("duplicate append is a no-op");
+    assert_eq!(store.len(), 3);
+    assert_eq!(store.get(&head.event_id()), Some(&head));
+    assert_eq!(store.position(&head.event_id()), Some(2));
+
+    let orphan = EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&99u64).unwrap(),
+        vec![Hash([0xAB; 32])],
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(
+        store.append(orphan).is_err(),
+        "unknown parent must be rejected"
+    );
+}
+
+#[test]
+fn heads_are_events_without_children() {
+    let store = chain(4);
+    assert_eq!(store.heads(), vec![store.events()[3].event_id()]);
+    assert!(MemoryEventStore::new().heads().is_empty());
+}
+
+#[test]
+fn segments_cover_the_worldline_with_stable_boundaries() {
+    let store = chain(10);
+    let segments: Vec<_> = store.segments(4).collect();
+
+    let ranges: Vec<_> = segments.iter().map(|s| s.range.clone()).collect();
+    assert_eq!(ranges, vec![0..4, 4..8, 8..10]);
+    assert_eq!(
+        segments.iter().map(|s| s.index).collect::<Vec<_>>(),
+        vec![0, 1, 2]
+    );
+    for segment in &segments {
+        assert_eq!(segment.events, &store.events()[segment.range.clone()]);
+    }
+
+    assert_eq!(store.segment(7, 2..5).unwrap().events.len(), 3);
+    assert!(store.segment(0, 8..11).is_none());
+}
+
+#[test]
+fn parallel_segment_results_merge_to_sequential_result() {
+    let store = chain(100);
+    let sequential = payload_sum(store.events());
+
+    for segment_len in [1, 7, 33, 100, 1000] {
+        let partials = store.par_map_segments(segment_len, |s| (s.index, payload_sum(s.events)));
+        assert!(
+            partials.windows(2).all(|w| w[0].0 < w[1].0),
+            "results must come back in segment order"
+        );
+        let merged: u64 = partials.iter().map(|(_, sum)| sum).sum();
+        assert_eq!(merged, sequential, "segment_len={segment_len}");
+    }
+}
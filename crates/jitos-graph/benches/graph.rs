@@ -0,0 +1,81 @@
+//! Throughput of the graph commit digest (SPEC-WARP-0001).
+//!
+//! Run with `cargo xtask bench`, which collects the results as JSON.
+
+use criterion::{
+    black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput,
+};
+use jitos_core::Hash;
+use jitos_graph::{NodeId, WarpEdge, WarpGraph, WarpNode};
+
+/// `n` nodes of two types with 32-byte payloads; each node after the first
+/// has an edge from its predecessor and one from its "parent" `i / 2`.
+fn graph(n: u32) -> WarpGraph {
+    let mut graph = WarpGraph::new();
+    let keys: Vec<_> = (0..n)
+        .map(|i| {
+            let mut id = [0u8; 32];
+            id[..4].copy_from_slice(&i.to_be_bytes());
+            graph.insert_node(WarpNode {
+                id: NodeId::from_hash(Hash(id)),
+                node_type: if i % 2 == 0 { "task" } else { "note" }.to_string(),
+                payload_bytes: vec![i as u8; 32],
+                attachment: None,
+            })
+        })
+        .collect();
+    for i in 1..keys.len() {
+        for (source, edge_type) in [(i - 1, "next"), (i / 2, "child_of")] {
+            graph.edges.insert(WarpEdge {
+                source: keys[source],
+                target: keys[i],
+                edge_type: edge_type.to_string(),
+                payload_bytes: None,
+                attachment: None,
+            });
+        }
+    }
+    graph
+}
+
+fn compute_hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("graph_compute_hash");
+    for n in [100, 1_000, 10_000] {
+        let graph = graph(n);
+        group.throughput(Throughput::Elements(
+            (graph.nodes.len() + graph.edges.len()) as u64,
+        ));
+        // Re-hashing an unchanged graph, as replay and receipts do every tick.
+        group.bench_with_input(BenchmarkId::new("warm", n), &graph, |b, graph| {
+            b.iter(|| black_box(graph).compute_hash())
+        });
+        // A graph hashed for the first time encodes every node.
+        let cold = |graph: &WarpGraph| {
+            let mut cold = WarpGraph::new();
+            cold.nodes = graph.nodes.clone();
+            cold.edges = graph.edges.clone();
+            cold
+        };
+        group.bench_with_input(BenchmarkId::new("cold", n), &graph, |b, graph| {
+            b.iter_batched(
+                || cold(graph),
+                |cold| cold.compute_hash(),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("parallel_warm", n), &graph, |b, graph| {
+            b.iter(|| black_box(graph).compute_hash_parallel())
+        });
+        group.bench_with_input(BenchmarkId::new("parallel_cold", n), &graph, |b, graph| {
+            b.iter_batched(
+                || cold(graph),
+                |cold| cold.compute_hash_parallel(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, compute_hash);
+criterion_main!(benches);
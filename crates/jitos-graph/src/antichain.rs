@@ -0,0 +1,126 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Antichain Detection
+//!
+//! Paper II: operations whose footprints do not interfere form an antichain and
+//! may be applied in any order (or concurrently) with identical results.
+//!
+//! This module partitions candidate operations (SLAPs, rewrite matches, ...)
+//! into a sequence of independent sets. It is shared by the scheduler and the
+//! rewrite engine, so it is generic over the footprint representation.
+
+use jitos_core::Hash;
+
+/// Footprint-level independence test.
+///
+/// Implementations MUST be symmetric: `a.independent_of(b) == b.independent_of(a)`.
+pub trait Independent {
+    fn independent_of(&self, other: &Self) -> bool;
+}
+
+/// Partition candidates into antichains.
+///
+/// Each candidate is `(ordering_key, footprint)`; the key is normally the
+/// candidate's canonical hash. Candidates are visited in ascending key order and
+/// greedily packed: a candidate joins the current antichain if it is independent
+/// of every member, otherwise it is carried to the next one. Each antichain is
+/// therefore maximal with respect to the candidates that remained when it was built.
+///
+/// Returns indices into `candidates`, each antichain sorted by key. The result
+/// depends only on the set of `(key, footprint)` pairs, not on input order
+/// (candidates with equal keys are assumed identical and are ordered by index).
+pub fn partition_antichains<F: Independent>(candidates: &[(Hash, F)]) -> Vec<Vec<usize>> {
+    let mut remaining: Vec<usize> = (0..candidates.len()).collect();
+    remaining.sort_by(|&a, &b| candidates[a].0.cmp(&candidates[b].0).then(a.cmp(&b)));
+
+    let mut antichains = Vec::new();
+    while !remaining.is_empty() {
+        let mut chain: Vec<usize> = Vec::new();
+        let mut deferred: Vec<usize> = Vec::new();
+
+        for idx in remaining {
+            let fp = &candidates[idx].1;
+            if chain.iter().all(|&m| candidates[m].1.independent_of(fp)) {
+                chain.push(idx);
+            } else {
+                deferred.push(idx);
+            }
+        }
+
+        antichains.push(chain);
+        remaining = deferred;
+    }
+
+    antichains
+}
+
+/// True if every pair of candidates in `members` is independent.
+pub fn is_antichain<F: Independent>(candidates: &[(Hash, F)], members: &[usize]) -> bool {
+    members.iter().enumerate().all(|(i, &a)| {
+        members[i + 1..]
+            .iter()
+            .all(|&b| candidates[a].1.independent_of(&candidates[b].1))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// Minimal write-set footprint: conflicting iff the sets intersect.
+    struct Writes(BTreeSet<u8>);
+
+    impl Independent for Writes {
+        fn independent_of(&self, other: &Self) -> bool {
+            self.0.is_disjoint(&other.0)
+        }
+    }
+
+    fn c(key: u8, writes: &[u8]) -> (Hash, Writes) {
+        (Hash([key; 32]), Writes(writes.iter().copied().collect()))
+    }
+
+    #[test]
+    fn test_independent_candidates_form_one_antichain() {
+        let candidates = vec![c(3, &[1]), c(1, &[2]), c(2, &[3])];
+        let chains = partition_antichains(&candidates);
+        assert_eq!(chains, vec![vec![1, 2, 0]], "one chain, sorted by key");
+    }
+
+    #[test]
+    fn test_conflicts_are_pushed_to_later_antichains() {
+        // 1 and 2 both write node 7; 3 is independent of both.
+        let candidates = vec![c(1, &[7]), c(2, &[7]), c(3, &[8])];
+        let chains = partition_antichains(&candidates);
+        assert_eq!(chains, vec![vec![0, 2], vec![1]]);
+        for chain in &chains {
+            assert!(is_antichain(&candidates, chain));
+        }
+    }
+
+    #[test]
+    fn test_partition_is_input_order_independent() {
+        let a = vec![c(1, &[7]), c(2, &[7, 8]), c(3, &[8]), c(4, &[9])];
+        let b = vec![c(4, &[9]), c(3, &[8]), c(2, &[7, 8]), c(1, &[7])];
+
+        let keys = |cands: &[(Hash, Writes)], chains: Vec<Vec<usize>>| -> Vec<Vec<Hash>> {
+            chains
+                .into_iter()
+                .map(|ch| ch.into_iter().map(|i| cands[i].0).collect())
+                .collect()
+        };
+
+        assert_eq!(
+            keys(&a, partition_antichains(&a)),
+            keys(&b, partition_antichains(&b))
+        );
+    }
+
+    #[test]
+    fn test_empty_input_yields_no_antichains() {
+        let candidates: Vec<(Hash, Writes)> = vec![];
+        assert!(partition_antichains(&candidates).is_empty());
+    }
+}
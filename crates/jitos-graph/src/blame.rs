@@ -0,0 +1,114 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Graph Blame
+//!
+//! Maps a graph node back to the events that shaped it: the Decision that
+//! recorded the rewrite, the rule that produced it, the PolicyContext that
+//! governed the Decision, and the agent that authored it.
+//!
+//! Rewrites enter history as Decision events whose payload is a canonical
+//! [`RewriteLog`]. Blame is a pure fold over a canonical worldline slice;
+//! Decisions with other payloads (e.g. timer fires) are skipped.
+
+use crate::rewrite::{GraphChange, RewriteLog};
+use crate::NodeId;
+use jitos_core::events::{AgentId, EventEnvelope, EventId, EventKind};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// One link in a node's causal chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameEntry {
+    /// Decision event that recorded the rewrite.
+    pub decision: EventId,
+    /// PolicyContext parent of that Decision.
+    pub policy: EventId,
+    /// Author of the Decision, if recorded.
+    pub agent: Option<AgentId>,
+    /// Rewrite rule that produced the change.
+    pub rule: String,
+    /// The change as recorded in the log.
+    pub change: GraphChange,
+}
+
+/// Causal summary for a single node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blame {
+    pub node: NodeId,
+    /// The rewrite that created the node, if it is in the slice.
+    pub created: Option<BlameEntry>,
+    /// The latest rewrite that touched the node (creation, payload, or removal).
+    pub last_modified: BlameEntry,
+}
+
+/// Blame query errors.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BlameError {
+    #[error("decision {0} has no PolicyContext parent in the event slice")]
+    MissingPolicy(EventId),
+}
+
+/// Find which Decisions, rules, policies and agents created and last modified `node`.
+///
+/// `events` must be in canonical worldline order and include each Decision's
+/// PolicyContext parent. Returns `Ok(None)` if no rewrite in the slice touched
+/// the node.
+pub fn blame(events: &[EventEnvelope], node: NodeId) -> Result<Option<Blame>, BlameError> {
+    let kinds: HashMap<EventId, &EventKind> =
+        events.iter().map(|e| (e.event_id(), e.kind())).collect();
+
+    let mut created: Option<BlameEntry> = None;
+    let mut last: Option<BlameEntry> = None;
+
+    for event in events {
+        if !matches!(event.kind(), EventKind::Decision) {
+            continue;
+        }
+        let Ok(log) = event.payload().to_value::<RewriteLog>() else {
+            continue;
+        };
+
+        for applied in &log.applied {
+            for change in &applied.changes {
+                if !touches(change, node) {
+                    continue;
+                }
+                let policy = event
+                    .parents()
+                    .iter()
+                    .find(|p| matches!(kinds.get(*p), Some(EventKind::PolicyContext)))
+                    .copied()
+                    .ok_or(BlameError::MissingPolicy(event.event_id()))?;
+
+                let entry = BlameEntry {
+                    decision: event.event_id(),
+                    policy,
+                    agent: event.agent_id().cloned(),
+                    rule: applied.rule.clone(),
+                    change: change.clone(),
+                };
+                if matches!(change, GraphChange::NodeAdded { .. }) {
+                    created = Some(entry.clone());
+                }
+                last = Some(entry);
+            }
+        }
+    }
+
+    Ok(last.map(|last_modified| Blame {
+        node,
+        created,
+        last_modified,
+    }))
+}
+
+/// Whether a change mutates `node` itself (edges blame neither endpoint).
+fn touches(change: &GraphChange, node: NodeId) -> bool {
+    match change {
+        GraphChange::NodeAdded { id, .. }
+        | GraphChange::PayloadSet { id }
+        | GraphChange::NodeRemoved { id } => *id == node,
+        GraphChange::EdgeAdded { .. } => false,
+    }
+}
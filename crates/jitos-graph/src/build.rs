@@ -0,0 +1,136 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bulk Deterministic Graph Loading
+//!
+//! [`WarpGraph::build_from_ops`] is the single entry point replay and
+//! snapshot-restore use to construct a graph from a tick's operations:
+//! - the op set is normalized (stable sort by operation hash) and hashed into
+//!   the tick's [`DeterministicIdAllocator`]
+//! - NodeIds come from H(tick_hash || operation_hash || counter); EdgeIds from
+//!   edge content (see [`crate::edge_id`])
+//! - all nodes are created before any edge, so an edge may reference a node
+//!   created by any op in the tick
+//!
+//! Reordering ops with distinct hashes (an antichain swap) therefore yields an
+//! identical graph. Ops sharing a hash keep their relative order: they are
+//! sub-steps of one operation and their counters follow that order.
+
+use crate::{DeterministicIdAllocator, NodeId, NodeKey, WarpEdge, WarpGraph, WarpNode};
+use jitos_core::Hash;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Endpoint of an edge created by [`GraphOp::AddEdge`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OpNodeRef {
+    /// A node with a known id, created earlier in the same build.
+    Id(NodeId),
+    /// The `index`-th node created by the op with hash `op` (0-based).
+    Created { op: Hash, index: usize },
+}
+
+/// A graph construction operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op")]
+pub enum GraphOp {
+    AddNode {
+        node_type: String,
+        payload_bytes: Vec<u8>,
+        attachment: Option<Hash>,
+    },
+    AddEdge {
+        from: OpNodeRef,
+        to: OpNodeRef,
+        edge_type: String,
+        payload_bytes: Option<Vec<u8>>,
+        attachment: Option<Hash>,
+    },
+}
+
+/// Bulk build errors.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BuildError {
+    #[error("op {op} created {created} nodes; node #{index} does not exist")]
+    UnknownCreated {
+        op: Hash,
+        index: usize,
+        created: usize,
+    },
+    #[error("node {0:?} is not part of this build")]
+    UnknownNode(NodeId),
+}
+
+impl WarpGraph {
+    /// Build a graph from one tick's `(operation_hash, op)` pairs.
+    ///
+    /// The result is independent of the order of ops with distinct hashes.
+    pub fn build_from_ops(tick_ops: &[(Hash, GraphOp)]) -> Result<WarpGraph, BuildError> {
+        let mut ops: Vec<&(Hash, GraphOp)> = tick_ops.iter().collect();
+        ops.sort_by_key(|(op_hash, _)| *op_hash);
+
+        let op_hashes: Vec<Hash> = ops.iter().map(|(h, _)| *h).collect();
+        let mut alloc = DeterministicIdAllocator::new_for_tick(&op_hashes);
+
+        let mut graph = WarpGraph::new();
+        let mut keys: HashMap<NodeId, NodeKey> = HashMap::new();
+        let mut created: HashMap<Hash, Vec<NodeId>> = HashMap::new();
+
+        for (op_hash, op) in &ops {
+            if let GraphOp::AddNode {
+                node_type,
+                payload_bytes,
+                attachment,
+            } = op
+            {
+                let id = alloc.alloc_node_id(*op_hash);
+                let key = graph.insert_node(WarpNode {
+                    id,
+                    node_type: node_type.clone(),
+                    payload_bytes: payload_bytes.clone(),
+                    attachment: *attachment,
+                });
+                keys.insert(id, key);
+                created.entry(*op_hash).or_default().push(id);
+            }
+        }
+
+        let resolve = |r: &OpNodeRef| -> Result<NodeKey, BuildError> {
+            let id = match r {
+                OpNodeRef::Id(id) => *id,
+                OpNodeRef::Created { op, index } => {
+                    let ids = created.get(op).map(Vec::as_slice).unwrap_or_default();
+                    *ids.get(*index).ok_or(BuildError::UnknownCreated {
+                        op: *op,
+                        index: *index,
+                        created: ids.len(),
+                    })?
+                }
+            };
+            keys.get(&id).copied().ok_or(BuildError::UnknownNode(id))
+        };
+
+        for (_, op) in &ops {
+            if let GraphOp::AddEdge {
+                from,
+                to,
+                edge_type,
+                payload_bytes,
+                attachment,
+            } = op
+            {
+                let edge = WarpEdge {
+                    source: resolve(from)?,
+                    target: resolve(to)?,
+                    edge_type: edge_type.clone(),
+                    payload_bytes: payload_bytes.clone(),
+                    attachment: *attachment,
+                };
+                graph.edges.insert(edge);
+            }
+        }
+
+        Ok(graph)
+    }
+}
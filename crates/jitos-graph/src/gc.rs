@@ -0,0 +1,96 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Graph Garbage Collection
+//!
+//! Long-running universes accumulate nodes nothing points at anymore. GC
+//! removes everything not reachable from an explicit root set by following
+//! edges source → target, and returns a tombstone report that is a pure
+//! function of graph content (sorted by NodeId / EdgeId), so it can be
+//! recorded in history and replayed.
+
+use crate::{edge_id, EdgeKey, NodeId, NodeKey, WarpGraph};
+use jitos_core::{canonical::CanonicalError, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// What a garbage collection pass removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcReport {
+    /// Removed nodes, ascending by NodeId.
+    pub removed_nodes: Vec<NodeId>,
+    /// Removed edges by deterministic EdgeId, ascending.
+    ///
+    /// Edges whose endpoints were already missing (dangling) have no EdgeId and
+    /// are counted in `dangling_edges` instead.
+    pub removed_edges: Vec<Hash>,
+    pub dangling_edges: u64,
+}
+
+impl GcReport {
+    /// True if the pass removed nothing.
+    pub fn is_empty(&self) -> bool {
+        self.removed_nodes.is_empty() && self.removed_edges.is_empty() && self.dangling_edges == 0
+    }
+}
+
+impl WarpGraph {
+    /// Remove all nodes and edges unreachable from `roots`.
+    ///
+    /// Reachability follows edges from source to target. Roots that are not in
+    /// the graph are ignored. Dangling edges (missing endpoints) are always removed.
+    pub fn collect_garbage(&mut self, roots: &[NodeId]) -> Result<GcReport, CanonicalError> {
+        let mut outgoing: HashMap<NodeKey, Vec<NodeKey>> = HashMap::new();
+        for edge in self.edges.values() {
+            outgoing.entry(edge.source).or_default().push(edge.target);
+        }
+
+        let mut live: HashSet<NodeKey> = HashSet::new();
+        let mut stack: Vec<NodeKey> = roots.iter().filter_map(|id| self.node_key(id)).collect();
+        while let Some(key) = stack.pop() {
+            if !self.nodes.contains_key(key) || !live.insert(key) {
+                continue;
+            }
+            if let Some(targets) = outgoing.get(&key) {
+                stack.extend(targets.iter().copied());
+            }
+        }
+
+        let mut report = GcReport::default();
+
+        // Edges first: their identity depends on endpoint NodeIds.
+        let mut dead_edges: Vec<EdgeKey> = Vec::new();
+        let mut removed_edges: BTreeSet<Hash> = BTreeSet::new();
+        for (key, edge) in self.edges.iter() {
+            if live.contains(&edge.source) && self.nodes.contains_key(edge.target) {
+                continue;
+            }
+            match (self.nodes.get(edge.source), self.nodes.get(edge.target)) {
+                (Some(from), Some(to)) => {
+                    removed_edges.insert(edge_id(from.id, to.id, edge)?);
+                }
+                _ => report.dangling_edges += 1,
+            }
+            dead_edges.push(key);
+        }
+        for key in dead_edges {
+            self.edges.remove(key);
+        }
+
+        let dead_nodes: Vec<NodeKey> = self
+            .nodes
+            .keys()
+            .filter(|key| !live.contains(key))
+            .collect();
+        let mut removed_nodes: BTreeSet<NodeId> = BTreeSet::new();
+        for key in dead_nodes {
+            if let Some(node) = self.remove_node(key) {
+                removed_nodes.insert(node.id);
+            }
+        }
+
+        report.removed_nodes = removed_nodes.into_iter().collect();
+        report.removed_edges = removed_edges.into_iter().collect();
+        Ok(report)
+    }
+}
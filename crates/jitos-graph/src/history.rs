@@ -0,0 +1,217 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Graph Commit Chain
+//!
+//! Receipts name a graph state hash; [`GraphHistory`] is the chain that links
+//! those states together. Each generation records its graph commit digest, its
+//! parent commit, and the SLAPs applied since the parent, so an auditor can
+//! walk from any state back to genesis and see what moved the graph.
+//!
+//! CommitId = H("graph-history-v0", generation, parent, state_hash, applied_slaps).
+//! The chain serializes canonically as its commit list; deserialization
+//! re-verifies every link.
+
+use jitos_core::{canonical, canonical::CanonicalError, Hash, Receipt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use thiserror::Error;
+
+/// One generation of graph state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphCommit {
+    /// 0 for genesis, parent + 1 otherwise.
+    pub generation: u64,
+    /// CommitId of the previous generation (`None` only for genesis).
+    pub parent: Option<Hash>,
+    /// Graph commit digest (SPEC-WARP-0001) after this generation.
+    pub state_hash: Hash,
+    /// SLAP hashes applied between the parent and this state, ascending.
+    pub applied_slaps: Vec<Hash>,
+}
+
+impl GraphCommit {
+    /// Content-addressed id of this commit.
+    pub fn commit_id(&self) -> Result<Hash, CanonicalError> {
+        canonical::hash_canonical(&(
+            "graph-history-v0",
+            self.generation,
+            self.parent,
+            self.state_hash,
+            &self.applied_slaps,
+        ))
+    }
+}
+
+/// History verification errors.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum HistoryError {
+    #[error("history is empty (genesis required)")]
+    Empty,
+    #[error("commit at generation {generation} has parent {found:?}, expected {expected:?}")]
+    BrokenLink {
+        generation: u64,
+        expected: Option<Hash>,
+        found: Option<Hash>,
+    },
+    #[error("commit at index {index} claims generation {found}")]
+    BadGeneration { index: usize, found: u64 },
+    #[error("applied SLAPs of generation {0} are not sorted and unique")]
+    UnsortedSlaps(u64),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+}
+
+/// Append-only chain of graph commits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<GraphCommit>", into = "Vec<GraphCommit>")]
+pub struct GraphHistory {
+    commits: Vec<GraphCommit>,
+    ids: Vec<Hash>,
+    by_id: HashMap<Hash, usize>,
+}
+
+impl GraphHistory {
+    /// Start a chain at the genesis state.
+    pub fn new(genesis_state: Hash) -> Result<Self, CanonicalError> {
+        let genesis = GraphCommit {
+            generation: 0,
+            parent: None,
+            state_hash: genesis_state,
+            applied_slaps: Vec::new(),
+        };
+        let id = genesis.commit_id()?;
+        Ok(Self {
+            commits: vec![genesis],
+            ids: vec![id],
+            by_id: HashMap::from([(id, 0)]),
+        })
+    }
+
+    /// Append a generation on top of the head and return its CommitId.
+    ///
+    /// `applied_slaps` is normalized (sorted, deduplicated), so the id does not
+    /// depend on the order SLAPs are reported in.
+    pub fn record(
+        &mut self,
+        state_hash: Hash,
+        applied_slaps: impl IntoIterator<Item = Hash>,
+    ) -> Result<Hash, CanonicalError> {
+        let applied_slaps: BTreeSet<Hash> = applied_slaps.into_iter().collect();
+        let commit = GraphCommit {
+            generation: self.head().generation + 1,
+            parent: Some(self.head_id()),
+            state_hash,
+            applied_slaps: applied_slaps.into_iter().collect(),
+        };
+        let id = commit.commit_id()?;
+        self.by_id.insert(id, self.commits.len());
+        self.commits.push(commit);
+        self.ids.push(id);
+        Ok(id)
+    }
+
+    pub fn head(&self) -> &GraphCommit {
+        self.commits.last().expect("history always has genesis")
+    }
+
+    pub fn head_id(&self) -> Hash {
+        *self.ids.last().expect("history always has genesis")
+    }
+
+    pub fn genesis(&self) -> &GraphCommit {
+        &self.commits[0]
+    }
+
+    /// Number of generations, including genesis.
+    pub fn len(&self) -> usize {
+        self.commits.len()
+    }
+
+    /// Always false: a history has at least its genesis commit.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn get(&self, commit_id: &Hash) -> Option<&GraphCommit> {
+        self.by_id.get(commit_id).map(|&i| &self.commits[i])
+    }
+
+    /// Commits with their ids, genesis first.
+    pub fn commits(&self) -> impl DoubleEndedIterator<Item = (Hash, &GraphCommit)> {
+        self.ids.iter().copied().zip(&self.commits)
+    }
+
+    /// Latest commit that produced `state_hash`.
+    pub fn find_state(&self, state_hash: &Hash) -> Option<(Hash, &GraphCommit)> {
+        self.commits()
+            .rev()
+            .find(|(_, c)| c.state_hash == *state_hash)
+    }
+
+    /// Walk parent links from `commit_id` back to genesis.
+    pub fn ancestry(&self, commit_id: &Hash) -> impl Iterator<Item = (Hash, &GraphCommit)> {
+        let mut next = self.by_id.get(commit_id).copied();
+        std::iter::from_fn(move || {
+            let index = next?;
+            let commit = &self.commits[index];
+            next = commit.parent.and_then(|p| self.by_id.get(&p).copied());
+            Some((self.ids[index], commit))
+        })
+    }
+
+    /// Find the commit a receipt attests to: same state hash and same applied SLAPs.
+    pub fn commit_for_receipt(&self, receipt: &Receipt) -> Option<(Hash, &GraphCommit)> {
+        let slaps: BTreeSet<&Hash> = receipt.applied_slaps.iter().collect();
+        self.commits().rev().find(|(_, c)| {
+            c.state_hash == receipt.state_hash
+                && c.applied_slaps.len() == slaps.len()
+                && c.applied_slaps.iter().all(|s| slaps.contains(s))
+        })
+    }
+}
+
+impl TryFrom<Vec<GraphCommit>> for GraphHistory {
+    type Error = HistoryError;
+
+    fn try_from(commits: Vec<GraphCommit>) -> Result<Self, Self::Error> {
+        if commits.is_empty() {
+            return Err(HistoryError::Empty);
+        }
+        let mut ids = Vec::with_capacity(commits.len());
+        let mut by_id = HashMap::with_capacity(commits.len());
+        for (index, commit) in commits.iter().enumerate() {
+            if commit.generation != index as u64 {
+                return Err(HistoryError::BadGeneration {
+                    index,
+                    found: commit.generation,
+                });
+            }
+            let expected = ids.last().copied();
+            if commit.parent != expected {
+                return Err(HistoryError::BrokenLink {
+                    generation: commit.generation,
+                    expected,
+                    found: commit.parent,
+                });
+            }
+            if !commit.applied_slaps.windows(2).all(|w| w[0] < w[1]) {
+                return Err(HistoryError::UnsortedSlaps(commit.generation));
+            }
+            let id = commit.commit_id()?;
+            by_id.insert(id, index);
+            ids.push(id);
+        }
+        Ok(Self {
+            commits,
+            ids,
+            by_id,
+        })
+    }
+}
+
+impl From<GraphHistory> for Vec<GraphCommit> {
+    fn from(history: GraphHistory) -> Self {
+        history.commits
+    }
+}
@@ -7,24 +7,9 @@
 //! This ensures antichain swaps (reordering independent operations) produce identical IDs.
 
 use jitos_core::{canonical, Hash};
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// Deterministic node ID (content-addressed, not insertion-order-dependent)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
-pub struct NodeId(pub Hash);
-
-impl NodeId {
-    /// Create NodeId from hash
-    pub fn from_hash(hash: Hash) -> Self {
-        Self(hash)
-    }
-
-    /// Get the underlying hash
-    pub fn hash(&self) -> Hash {
-        self.0
-    }
-}
+pub use jitos_core::NodeId;
 
 /// Deterministic ID allocator for a single tick/batch
 ///
@@ -65,6 +50,28 @@ impl DeterministicIdAllocator {
         }
     }
 
+    /// Create allocator for a tick within a named allocation domain
+    ///
+    /// Subsystems (timers, planner tasks, scripts, ...) each get an isolated
+    /// id space: the domain is mixed into the tick hash, so the same operations
+    /// allocate different ids in different domains, and identical ids within a
+    /// domain. `new_for_tick` is the unnamed default domain.
+    ///
+    /// tick_hash = H("id-domain-v0" || domain || sorted operations)
+    pub fn new_for_tick_in_domain(domain: &str, operations: &[Hash]) -> Self {
+        let mut sorted = operations.to_vec();
+        sorted.sort_by_key(|h| h.0);
+
+        let tick_hash = canonical::encode(&("id-domain-v0", domain, &sorted))
+            .map(|bytes| Hash(*blake3::hash(&bytes).as_bytes()))
+            .unwrap_or(Hash([0u8; 32]));
+
+        Self {
+            tick_hash,
+            counters: HashMap::new(),
+        }
+    }
+
     /// Allocate next node ID for an operation
     ///
     /// ID = H(tick_hash || operation_hash || counter)
@@ -235,6 +242,26 @@ mod tests {
         assert_eq!(results1, results2, "replay must produce identical IDs");
     }
 
+    #[test]
+    fn test_domains_isolate_id_spaces() {
+        let op1 = Hash([1u8; 32]);
+        let op2 = Hash([2u8; 32]);
+
+        let mut timers = DeterministicIdAllocator::new_for_tick_in_domain("timers", &[op1, op2]);
+        let mut planner = DeterministicIdAllocator::new_for_tick_in_domain("planner", &[op1, op2]);
+        let mut default = DeterministicIdAllocator::new_for_tick(&[op1, op2]);
+
+        let t = timers.alloc_node_id(op1);
+        let p = planner.alloc_node_id(op1);
+        let d = default.alloc_node_id(op1);
+        assert_ne!(t, p, "domains must not share ids");
+        assert_ne!(t, d, "named domain must differ from default domain");
+
+        // Within a domain the antichain swap property still holds.
+        let mut swapped = DeterministicIdAllocator::new_for_tick_in_domain("timers", &[op2, op1]);
+        assert_eq!(swapped.alloc_node_id(op1), t);
+    }
+
     #[test]
     fn test_antichain_swap_stress_1000_permutations() {
         // NEXT-MOVES.md requirement: "swap independent rewrites 1000 times → same graph hash every time"
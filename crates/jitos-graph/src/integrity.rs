@@ -0,0 +1,155 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Graph Consistency Validation
+//!
+//! `compute_hash_checked` fails on the first dangling edge it meets. Integrity
+//! checking reports every structural problem at once, as data:
+//! - edges whose source/target slot no longer holds a node
+//! - the same `NodeId` stored in more than one slot (identity must be unique)
+//! - attachments that do not resolve to a known WARP graph
+
+use crate::{edge_id, EdgeKey, NodeId, WarpGraph};
+use jitos_core::Hash;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use thiserror::Error;
+
+/// Which end of an edge is broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Endpoint {
+    Source,
+    Target,
+}
+
+/// What owns an attachment reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AttachmentOwner {
+    Node(NodeId),
+    /// Edge by deterministic EdgeId.
+    Edge(Hash),
+}
+
+/// A structural integrity violation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Error)]
+pub enum GraphError {
+    #[error("edge {edge:?} ({edge_type}) has a dangling {endpoint:?} endpoint")]
+    DanglingEdge {
+        edge: EdgeKey,
+        edge_type: String,
+        endpoint: Endpoint,
+    },
+    #[error("node id {id:?} occupies {slots} slots")]
+    DuplicateNodeId { id: NodeId, slots: usize },
+    #[error("attachment {attachment:?} of {owner:?} does not resolve")]
+    UnresolvedAttachment {
+        owner: AttachmentOwner,
+        attachment: Hash,
+    },
+}
+
+/// Resolves attachment hashes to known WARP graphs.
+pub trait AttachmentResolver {
+    fn resolves(&self, attachment: &Hash) -> bool;
+}
+
+impl AttachmentResolver for BTreeSet<Hash> {
+    fn resolves(&self, attachment: &Hash) -> bool {
+        self.contains(attachment)
+    }
+}
+
+impl AttachmentResolver for HashSet<Hash> {
+    fn resolves(&self, attachment: &Hash) -> bool {
+        self.contains(attachment)
+    }
+}
+
+impl WarpGraph {
+    /// Check structural integrity (dangling edges, duplicate NodeIds).
+    ///
+    /// Attachments are not resolved; use [`WarpGraph::check_integrity_with`].
+    /// Errors are returned sorted, so the report is stable for a given graph.
+    pub fn check_integrity(&self) -> Result<(), Vec<GraphError>> {
+        finish(self.structural_errors())
+    }
+
+    /// Check structural integrity and that every attachment resolves.
+    pub fn check_integrity_with<R: AttachmentResolver + ?Sized>(
+        &self,
+        resolver: &R,
+    ) -> Result<(), Vec<GraphError>> {
+        let mut errors = self.structural_errors();
+
+        for node in self.nodes.values() {
+            if let Some(attachment) = node.attachment {
+                if !resolver.resolves(&attachment) {
+                    errors.push(GraphError::UnresolvedAttachment {
+                        owner: AttachmentOwner::Node(node.id),
+                        attachment,
+                    });
+                }
+            }
+        }
+
+        for edge in self.edges.values() {
+            let Some(attachment) = edge.attachment else {
+                continue;
+            };
+            let (Some(from), Some(to)) = (self.nodes.get(edge.source), self.nodes.get(edge.target))
+            else {
+                continue; // already reported as dangling
+            };
+            if resolver.resolves(&attachment) {
+                continue;
+            }
+            // Encoding a tuple of fixed-shape fields cannot fail.
+            let id = edge_id(from.id, to.id, edge).expect("edge id encoding");
+            errors.push(GraphError::UnresolvedAttachment {
+                owner: AttachmentOwner::Edge(id),
+                attachment,
+            });
+        }
+
+        finish(errors)
+    }
+
+    fn structural_errors(&self) -> Vec<GraphError> {
+        let mut errors = Vec::new();
+
+        for (key, edge) in self.edges.iter() {
+            for (endpoint, slot) in [
+                (Endpoint::Source, edge.source),
+                (Endpoint::Target, edge.target),
+            ] {
+                if !self.nodes.contains_key(slot) {
+                    errors.push(GraphError::DanglingEdge {
+                        edge: key,
+                        edge_type: edge.edge_type.clone(),
+                        endpoint,
+                    });
+                }
+            }
+        }
+
+        let mut slots_per_id: BTreeMap<NodeId, usize> = BTreeMap::new();
+        for node in self.nodes.values() {
+            *slots_per_id.entry(node.id).or_default() += 1;
+        }
+        for (id, slots) in slots_per_id {
+            if slots > 1 {
+                errors.push(GraphError::DuplicateNodeId { id, slots });
+            }
+        }
+
+        errors
+    }
+}
+
+fn finish(mut errors: Vec<GraphError>) -> Result<(), Vec<GraphError>> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        errors.sort();
+        Err(errors)
+    }
+}
@@ -1,14 +1,36 @@
 use jitos_core::Hash;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use slotmap::{new_key_type, SlotMap};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
+pub mod antichain;
+pub mod blame;
+pub mod build;
+pub mod gc;
+pub mod history;
 pub mod ids;
+pub mod integrity;
+pub mod persistent;
+pub mod rewrite;
+pub mod snapshot;
+pub mod sws;
+pub mod txn;
 
+pub use history::{GraphCommit, GraphHistory, HistoryError};
 pub use ids::{DeterministicIdAllocator, NodeId};
+pub use integrity::{AttachmentResolver, GraphError};
+pub use persistent::{PersistentEdge, PersistentGraph, PersistentGraphError};
+pub use sws::{CollapseConflict, CollapseReceipt, ShadowWorkingSet, SwsError, SwsRegistry};
+pub use txn::{GraphTxn, TouchedEdge, TxnError, TxnFootprint};
 
 new_key_type! { pub struct NodeKey; }
 new_key_type! { pub struct EdgeKey; }
 
+/// The commit [`encoded_commit_digest`] hashes, as one value; kept to check
+/// the streamed encoding against `hash_canonical`.
+#[cfg(test)]
 #[derive(Debug, Clone, Serialize)]
 struct GraphCommitV0 {
     version: &'static str,
@@ -17,7 +39,7 @@ struct GraphCommitV0 {
 }
 
 #[derive(Debug, Clone, Serialize)]
-struct NodeCommitV0 {
+pub(crate) struct NodeCommitV0 {
     node_id: NodeId,
     kind: String,
     payload_bytes: Vec<u8>,
@@ -25,7 +47,7 @@ struct NodeCommitV0 {
 }
 
 #[derive(Debug, Clone, Serialize)]
-struct EdgeCommitV0 {
+pub(crate) struct EdgeCommitV0 {
     edge_id: Hash,
     from: NodeId,
     to: NodeId,
@@ -35,7 +57,7 @@ struct EdgeCommitV0 {
 }
 
 /// A node in the WARP graph.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WarpNode {
     pub id: NodeId,
     pub node_type: String,
@@ -69,10 +91,67 @@ pub struct WarpEdge {
 }
 
 /// The WARP Graph structure (Paper I).
+///
+/// Secondary indices (by NodeId and per-type) are maintained by [`WarpGraph::insert_node`],
+/// [`WarpGraph::replace_node`] and [`WarpGraph::remove_node`]. Code that edits
+/// `nodes` directly must call [`WarpGraph::rebuild_indices`] before running
+/// indexed queries.
+///
+/// The canonical encoding of each node's commit record is memoized for
+/// [`WarpGraph::compute_hash_checked`]. The same methods, and
+/// [`WarpGraph::set_payload`], drop the memo entry of the slot they touch;
+/// [`WarpGraph::rebuild_indices`] drops them all. Code that edits a node in
+/// place through `nodes` must go through one of them before hashing, or the
+/// digest will still commit to the old node.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "WarpGraphRepr")]
 pub struct WarpGraph {
     pub nodes: SlotMap<NodeKey, WarpNode>,
     pub edges: SlotMap<EdgeKey, WarpEdge>,
+    /// NodeId -> NodeKey. Derived state; never serialized or hashed.
+    #[serde(skip)]
+    id_index: BTreeMap<NodeId, NodeKey>,
+    /// node_type -> (NodeId -> NodeKey). Derived state; never serialized or hashed.
+    #[serde(skip)]
+    type_index: BTreeMap<String, BTreeMap<NodeId, NodeKey>>,
+    /// Memoized node commit encodings. Derived state; never serialized or hashed.
+    #[serde(skip)]
+    node_encodings: NodeEncodings,
+}
+
+/// Canonical `NodeCommitV0` encodings by slot.
+///
+/// Slot keys are versioned, so a removed node's key is never reused; an
+/// entry only goes stale when its node is edited in place.
+#[derive(Debug, Default)]
+struct NodeEncodings(Mutex<HashMap<NodeKey, Vec<u8>>>);
+
+impl Clone for NodeEncodings {
+    fn clone(&self) -> Self {
+        let cache = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        NodeEncodings(Mutex::new(cache.clone()))
+    }
+}
+
+/// Serialized form of [`WarpGraph`]; indices are rebuilt on deserialization.
+#[derive(Deserialize)]
+struct WarpGraphRepr {
+    nodes: SlotMap<NodeKey, WarpNode>,
+    edges: SlotMap<EdgeKey, WarpEdge>,
+}
+
+impl From<WarpGraphRepr> for WarpGraph {
+    fn from(repr: WarpGraphRepr) -> Self {
+        let mut graph = WarpGraph {
+            nodes: repr.nodes,
+            edges: repr.edges,
+            id_index: BTreeMap::new(),
+            type_index: BTreeMap::new(),
+            node_encodings: NodeEncodings::default(),
+        };
+        graph.rebuild_indices();
+        graph
+    }
 }
 
 impl WarpGraph {
@@ -80,6 +159,115 @@ impl WarpGraph {
         Self::default()
     }
 
+    /// Insert a node, keeping secondary indices current.
+    pub fn insert_node(&mut self, node: WarpNode) -> NodeKey {
+        let id = node.id;
+        let node_type = node.node_type.clone();
+        let key = self.nodes.insert(node);
+        self.id_index.insert(id, key);
+        self.type_index
+            .entry(node_type)
+            .or_default()
+            .insert(id, key);
+        key
+    }
+
+    /// Remove a node and every edge incident to it, keeping indices current.
+    pub fn remove_node(&mut self, key: NodeKey) -> Option<WarpNode> {
+        let node = self.nodes.remove(key)?;
+        self.edges.retain(|_, e| e.source != key && e.target != key);
+        self.forget_encoding(key);
+        self.unindex(&node);
+        Some(node)
+    }
+
+    /// Replace the node in `key`, keeping its edges and the indices current.
+    ///
+    /// Returns the old node, or `None` (leaving the graph unchanged) if the
+    /// slot is empty.
+    pub fn replace_node(&mut self, key: NodeKey, node: WarpNode) -> Option<WarpNode> {
+        let slot = self.nodes.get_mut(key)?;
+        let (id, node_type) = (node.id, node.node_type.clone());
+        let old = std::mem::replace(slot, node);
+        self.forget_encoding(key);
+        self.unindex(&old);
+        self.id_index.insert(id, key);
+        self.type_index
+            .entry(node_type)
+            .or_default()
+            .insert(id, key);
+        Some(old)
+    }
+
+    /// Replace the payload of the node in `key`, returning the old payload.
+    pub fn set_payload(&mut self, key: NodeKey, payload_bytes: Vec<u8>) -> Option<Vec<u8>> {
+        let node = self.nodes.get_mut(key)?;
+        let old = std::mem::replace(&mut node.payload_bytes, payload_bytes);
+        self.forget_encoding(key);
+        Some(old)
+    }
+
+    fn unindex(&mut self, node: &WarpNode) {
+        self.id_index.remove(&node.id);
+        if let Some(by_id) = self.type_index.get_mut(&node.node_type) {
+            by_id.remove(&node.id);
+            if by_id.is_empty() {
+                self.type_index.remove(&node.node_type);
+            }
+        }
+    }
+
+    fn forget_encoding(&mut self, key: NodeKey) {
+        self.node_encodings
+            .0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&key);
+    }
+
+    /// Nodes of the given type, in ascending NodeId order.
+    ///
+    /// O(k) in the number of matching nodes.
+    pub fn nodes_of_type<'a>(
+        &'a self,
+        node_type: &str,
+    ) -> impl Iterator<Item = (NodeKey, &'a WarpNode)> + 'a {
+        self.type_index
+            .get(node_type)
+            .into_iter()
+            .flat_map(|by_id| by_id.values())
+            .filter_map(|&k| self.nodes.get(k).map(|n| (k, n)))
+    }
+
+    /// Recompute all secondary indices from `nodes` and drop every memoized
+    /// node encoding.
+    pub fn rebuild_indices(&mut self) {
+        self.node_encodings
+            .0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.id_index.clear();
+        self.type_index.clear();
+        for (key, node) in self.nodes.iter() {
+            self.id_index.insert(node.id, key);
+            self.type_index
+                .entry(node.node_type.clone())
+                .or_default()
+                .insert(node.id, key);
+        }
+    }
+
+    /// Find the slot holding the node with the given `NodeId`.
+    ///
+    /// O(log n) through the id index.
+    pub fn node_key(&self, id: &NodeId) -> Option<NodeKey> {
+        self.id_index
+            .get(id)
+            .copied()
+            .filter(|&k| self.nodes.get(k).is_some_and(|n| n.id == *id))
+    }
+
     /// Computes the BLAKE3 root hash of the graph state.
     pub fn compute_hash(&self) -> Hash {
         self.compute_hash_checked()
@@ -93,61 +281,312 @@ impl WarpGraph {
     /// - independent of HashMap/SlotMap iteration order
     /// - stable across runs/platforms (via SPEC-0001 canonical encoding)
     pub fn compute_hash_checked(&self) -> Result<Hash, jitos_core::canonical::CanonicalError> {
-        // Nodes: sort by NodeId bytes ascending.
-        let mut nodes: Vec<NodeCommitV0> = Vec::with_capacity(self.nodes.len());
-        for (_k, n) in self.nodes.iter() {
-            nodes.push(NodeCommitV0 {
-                node_id: n.id,
-                kind: n.node_type.clone(),
-                payload_bytes: n.payload_bytes.clone(),
-                attachment: n.attachment,
-            });
+        let edges = self
+            .edge_endpoints()?
+            .into_iter()
+            .map(|(from, to, e)| encode_edge(from, to, e))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let cache = self.lock_node_encodings();
+        let misses = self
+            .stale_nodes(&cache)
+            .into_iter()
+            .map(|(key, n)| Ok((key, encode_node(n)?)))
+            .collect::<Result<Vec<_>, jitos_core::canonical::CanonicalError>>()?;
+
+        Ok(self.finish_digest(cache, misses, edges))
+    }
+
+    /// [`WarpGraph::compute_hash`] on the rayon thread pool.
+    pub fn compute_hash_parallel(&self) -> Hash {
+        self.compute_hash_parallel_checked()
+            .expect("canonical graph hashing must succeed")
+    }
+
+    /// [`WarpGraph::compute_hash_checked`] on the rayon thread pool, for large graphs.
+    ///
+    /// Edge ids and the canonical encodings of edge and (changed) node
+    /// records are computed in parallel; the sort and the final hash are
+    /// sequential, so the digest is byte-identical to the sequential path.
+    /// Errors are also the same: dangling edges are found before any
+    /// parallel work starts.
+    pub fn compute_hash_parallel_checked(
+        &self,
+    ) -> Result<Hash, jitos_core::canonical::CanonicalError> {
+        let edges = self
+            .edge_endpoints()?
+            .into_par_iter()
+            .map(|(from, to, e)| encode_edge(from, to, e))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let cache = self.lock_node_encodings();
+        let misses = self
+            .stale_nodes(&cache)
+            .into_par_iter()
+            .map(|(key, n)| Ok((key, encode_node(n)?)))
+            .collect::<Result<Vec<_>, jitos_core::canonical::CanonicalError>>()?;
+
+        Ok(self.finish_digest(cache, misses, edges))
+    }
+
+    /// Every edge with its endpoints' NodeIds, or an error for the first
+    /// dangling edge in slot order.
+    fn edge_endpoints(
+        &self,
+    ) -> Result<Vec<(NodeId, NodeId, &WarpEdge)>, jitos_core::canonical::CanonicalError> {
+        self.edges
+            .values()
+            .map(|e| {
+                let from = self.nodes.get(e.source).map(|n| n.id).ok_or_else(|| {
+                    jitos_core::canonical::CanonicalError::Decode(
+                        "edge source references missing node".into(),
+                    )
+                })?;
+                let to = self.nodes.get(e.target).map(|n| n.id).ok_or_else(|| {
+                    jitos_core::canonical::CanonicalError::Decode(
+                        "edge target references missing node".into(),
+                    )
+                })?;
+                Ok((from, to, e))
+            })
+            .collect()
+    }
+
+    fn lock_node_encodings(&self) -> MutexGuard<'_, HashMap<NodeKey, Vec<u8>>> {
+        self.node_encodings
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Nodes without a memoized encoding.
+    fn stale_nodes(&self, cache: &HashMap<NodeKey, Vec<u8>>) -> Vec<(NodeKey, &WarpNode)> {
+        self.nodes
+            .iter()
+            .filter(|(key, _)| !cache.contains_key(key))
+            .collect()
+    }
+
+    /// Store fresh node encodings, drop those of removed slots, and hash.
+    fn finish_digest(
+        &self,
+        mut cache: MutexGuard<'_, HashMap<NodeKey, Vec<u8>>>,
+        misses: Vec<(NodeKey, Vec<u8>)>,
+        edges: Vec<(Hash, Vec<u8>)>,
+    ) -> Hash {
+        cache.extend(misses);
+        if cache.len() > self.nodes.len() {
+            cache.retain(|key, _| self.nodes.contains_key(*key));
         }
-        nodes.sort_by(|a, b| a.node_id.cmp(&b.node_id));
-
-        // Edges: derive a deterministic EdgeId from semantic content (endpoints + kind + attachment),
-        // then sort by that ID bytes ascending.
-        let mut edges: Vec<EdgeCommitV0> = Vec::with_capacity(self.edges.len());
-        for (_k, e) in self.edges.iter() {
-            let from = self.nodes.get(e.source).map(|n| n.id).ok_or_else(|| {
-                jitos_core::canonical::CanonicalError::Decode(
-                    "edge source references missing node".into(),
-                )
-            })?;
-            let to = self.nodes.get(e.target).map(|n| n.id).ok_or_else(|| {
-                jitos_core::canonical::CanonicalError::Decode(
-                    "edge target references missing node".into(),
-                )
-            })?;
-
-            // Domain-separated edge id input to avoid accidental ambiguity if fields evolve.
-            let edge_id_input = (
-                "warp-edge-v0",
-                from,
-                to,
-                e.edge_type.as_str(),
-                e.attachment,
-                &e.payload_bytes,
-            );
-            let edge_id = jitos_core::canonical::hash_canonical(&edge_id_input)?;
-
-            edges.push(EdgeCommitV0 {
-                edge_id,
-                from,
-                to,
-                kind: e.edge_type.clone(),
-                payload_bytes: e.payload_bytes.clone(),
-                attachment: e.attachment,
-            });
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|(key, n)| (n.id, cache[&key].as_slice()))
+            .collect();
+        encoded_commit_digest(nodes, edges)
+    }
+}
+
+/// Edge commit record: a deterministic EdgeId derived from semantic content
+/// (endpoints + kind + attachment + payload), and the record's encoding.
+fn encode_edge(
+    from: NodeId,
+    to: NodeId,
+    e: &WarpEdge,
+) -> Result<(Hash, Vec<u8>), jitos_core::canonical::CanonicalError> {
+    let edge_id = edge_id(from, to, e)?;
+    let bytes = jitos_core::canonical::encode(&EdgeCommitV0 {
+        edge_id,
+        from,
+        to,
+        kind: e.edge_type.clone(),
+        payload_bytes: e.payload_bytes.clone(),
+        attachment: e.attachment,
+    })?;
+    Ok((edge_id, bytes))
+}
+
+fn encode_node(n: &WarpNode) -> Result<Vec<u8>, jitos_core::canonical::CanonicalError> {
+    jitos_core::canonical::encode(&NodeCommitV0 {
+        node_id: n.id,
+        kind: n.node_type.clone(),
+        payload_bytes: n.payload_bytes.clone(),
+        attachment: n.attachment,
+    })
+}
+
+/// Sort commit records into canonical order and hash them (SPEC-WARP-0001).
+///
+/// Shared by every graph representation so they agree on the digest.
+pub(crate) fn commit_digest(
+    nodes: Vec<NodeCommitV0>,
+    edges: Vec<EdgeCommitV0>,
+) -> Result<Hash, jitos_core::canonical::CanonicalError> {
+    let nodes = nodes
+        .into_iter()
+        .map(|n| Ok((n.node_id, jitos_core::canonical::encode(&n)?)))
+        .collect::<Result<Vec<_>, jitos_core::canonical::CanonicalError>>()?;
+    let nodes = nodes.iter().map(|(id, b)| (*id, b.as_slice())).collect();
+    Ok(encoded_commit_digest(nodes, encode_edges(edges)?))
+}
+
+fn encode_edges(
+    edges: Vec<EdgeCommitV0>,
+) -> Result<Vec<(Hash, Vec<u8>)>, jitos_core::canonical::CanonicalError> {
+    edges
+        .into_iter()
+        .map(|e| Ok((e.edge_id, jitos_core::canonical::encode(&e)?)))
+        .collect()
+}
+
+/// Hash of the canonical `GraphCommitV0` built from individually encoded
+/// records: nodes sorted by NodeId bytes ascending, edges by EdgeId bytes
+/// ascending.
+///
+/// Streams the same bytes `hash_canonical` would see for the whole commit:
+/// a canonical map is its head followed by its entries in key order, and an
+/// array its head followed by its items.
+fn encoded_commit_digest(mut nodes: Vec<(NodeId, &[u8])>, mut edges: Vec<(Hash, Vec<u8>)>) -> Hash {
+    use jitos_core::canonical::{encode_array_head, encode_map_head};
+
+    nodes.sort_by_key(|(id, _)| *id);
+    edges.sort_by_key(|(id, _)| *id);
+
+    let mut hasher = blake3::Hasher::new();
+    let mut head = Vec::new();
+    // Keys in canonical order: shorter first, then bytewise.
+    encode_map_head(3, &mut head);
+    head.extend_from_slice(b"\x65edges");
+    encode_array_head(edges.len(), &mut head);
+    hasher.update(&head);
+    for (_, bytes) in &edges {
+        hasher.update(bytes);
+    }
+    head.clear();
+    head.extend_from_slice(b"\x65nodes");
+    encode_array_head(nodes.len(), &mut head);
+    hasher.update(&head);
+    for (_, bytes) in &nodes {
+        hasher.update(bytes);
+    }
+    hasher.update(b"\x67version\x6fgraph-commit-v0");
+    Hash(*hasher.finalize().as_bytes())
+}
+
+/// Deterministic edge identity derived from semantic content (SPEC-WARP-0001).
+///
+/// EdgeId = H("warp-edge-v0" || from || to || kind || attachment || payload_bytes).
+pub fn edge_id(
+    from: NodeId,
+    to: NodeId,
+    edge: &WarpEdge,
+) -> Result<Hash, jitos_core::canonical::CanonicalError> {
+    edge_id_parts(
+        from,
+        to,
+        &edge.edge_type,
+        edge.attachment,
+        &edge.payload_bytes,
+    )
+}
+
+pub(crate) fn edge_id_parts(
+    from: NodeId,
+    to: NodeId,
+    edge_type: &str,
+    attachment: Option<Hash>,
+    payload_bytes: &Option<Vec<u8>>,
+) -> Result<Hash, jitos_core::canonical::CanonicalError> {
+    // Domain-separated edge id input to avoid accidental ambiguity if fields evolve.
+    let edge_id_input = (
+        "warp-edge-v0",
+        from,
+        to,
+        edge_type,
+        attachment,
+        payload_bytes,
+    );
+    jitos_core::canonical::hash_canonical(&edge_id_input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(i: u32, payload_len: usize) -> WarpNode {
+        let mut id = [0u8; 32];
+        id[28..].copy_from_slice(&(i.wrapping_mul(0x9E37_79B9)).to_be_bytes());
+        WarpNode {
+            id: NodeId::from_hash(Hash(id)),
+            node_type: format!("type{}", i % 3),
+            payload_bytes: (0..payload_len).map(|b| (b as u32 + i) as u8).collect(),
+            attachment: i.is_multiple_of(4).then_some(Hash([i as u8; 32])),
         }
-        edges.sort_by(|a, b| a.edge_id.cmp(&b.edge_id));
+    }
 
-        let commit = GraphCommitV0 {
-            version: "graph-commit-v0",
-            nodes,
-            edges,
-        };
+    #[test]
+    fn streamed_digest_matches_hashing_the_whole_commit() {
+        for n in [0u32, 1, 30, 300] {
+            let mut g = WarpGraph::new();
+            let keys: Vec<_> = (0..n)
+                .map(|i| g.insert_node(node(i, (i as usize * 7) % 40)))
+                .collect();
+            for i in 1..keys.len() {
+                g.edges.insert(WarpEdge {
+                    source: keys[i / 2],
+                    target: keys[i],
+                    edge_type: "child_of".to_string(),
+                    payload_bytes: i.is_multiple_of(2).then(|| vec![i as u8; i % 30]),
+                    attachment: None,
+                });
+            }
+
+            let mut nodes: Vec<NodeCommitV0> = g
+                .nodes
+                .values()
+                .map(|n| NodeCommitV0 {
+                    node_id: n.id,
+                    kind: n.node_type.clone(),
+                    payload_bytes: n.payload_bytes.clone(),
+                    attachment: n.attachment,
+                })
+                .collect();
+            let mut edges: Vec<EdgeCommitV0> = g
+                .edges
+                .values()
+                .map(|e| {
+                    let (from, to) = (g.nodes[e.source].id, g.nodes[e.target].id);
+                    EdgeCommitV0 {
+                        edge_id: edge_id(from, to, e).unwrap(),
+                        from,
+                        to,
+                        kind: e.edge_type.clone(),
+                        payload_bytes: e.payload_bytes.clone(),
+                        attachment: e.attachment,
+                    }
+                })
+                .collect();
+            nodes.sort_by_key(|n| n.node_id);
+            edges.sort_by_key(|e| e.edge_id);
+            let whole = jitos_core::canonical::hash_canonical(&GraphCommitV0 {
+                version: "graph-commit-v0",
+                nodes,
+                edges,
+            })
+            .unwrap();
 
-        jitos_core::canonical::hash_canonical(&commit)
+            assert_eq!(
+                g.clone().compute_hash_parallel(),
+                whole,
+                "{n} nodes, parallel"
+            );
+            assert_eq!(g.compute_hash(), whole, "{n} nodes");
+            assert_eq!(g.compute_hash(), whole, "{n} nodes, cached");
+            assert_eq!(
+                g.compute_hash_parallel(),
+                whole,
+                "{n} nodes, parallel cached"
+            );
+        }
     }
 }
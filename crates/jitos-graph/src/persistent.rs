@@ -0,0 +1,219 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Persistent (Structurally Shared) WARP Graph
+//!
+//! Counterfactual forks need cheap copies. [`PersistentGraph`] stores nodes by
+//! `NodeId` and edges by deterministic `EdgeId` in persistent ordered maps, so
+//! `clone()` is O(1) and every edit copies only the path it touches; a fork and
+//! its base share all untouched structure.
+//!
+//! The commit digest is identical to [`WarpGraph::compute_hash_checked`] for
+//! the same content. Because edges are keyed by content, two edges with
+//! identical endpoints, type, payload and attachment are one edge here;
+//! [`PersistentGraph::from_graph`] rejects graphs that contain such duplicates.
+
+use crate::{
+    commit_digest, edge_id_parts, EdgeCommitV0, GraphError, NodeCommitV0, NodeId, WarpEdge,
+    WarpGraph, WarpNode,
+};
+use im::OrdMap;
+use jitos_core::{canonical::CanonicalError, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// An edge addressed by endpoint `NodeId`s instead of slot keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersistentEdge {
+    pub from: NodeId,
+    pub to: NodeId,
+    pub edge_type: String,
+    pub payload_bytes: Option<Vec<u8>>,
+    pub attachment: Option<Hash>,
+}
+
+impl PersistentEdge {
+    /// Deterministic EdgeId (same derivation as [`crate::edge_id`]).
+    pub fn edge_id(&self) -> Result<Hash, CanonicalError> {
+        edge_id_parts(
+            self.from,
+            self.to,
+            &self.edge_type,
+            self.attachment,
+            &self.payload_bytes,
+        )
+    }
+}
+
+/// Persistent graph errors.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PersistentGraphError {
+    #[error("edge endpoint {0:?} is not in the graph")]
+    MissingEndpoint(NodeId),
+    #[error("edge {0} occurs more than once")]
+    DuplicateEdge(Hash),
+    #[error("graph failed integrity checks: {0:?}")]
+    Integrity(Vec<GraphError>),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+}
+
+/// Immutable-by-default WARP graph with O(1) clone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PersistentGraph {
+    nodes: OrdMap<NodeId, WarpNode>,
+    edges: OrdMap<Hash, PersistentEdge>,
+}
+
+impl PersistentGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert a mutable graph.
+    ///
+    /// Fails if the graph has integrity errors (dangling edges, duplicate
+    /// NodeIds) or content-identical parallel edges.
+    pub fn from_graph(graph: &WarpGraph) -> Result<Self, PersistentGraphError> {
+        graph
+            .check_integrity()
+            .map_err(PersistentGraphError::Integrity)?;
+
+        let mut out = Self::new();
+        for node in graph.nodes.values() {
+            out.nodes.insert(node.id, node.clone());
+        }
+        for edge in graph.edges.values() {
+            // Integrity check above guarantees both endpoints exist.
+            let edge = PersistentEdge {
+                from: graph.nodes[edge.source].id,
+                to: graph.nodes[edge.target].id,
+                edge_type: edge.edge_type.clone(),
+                payload_bytes: edge.payload_bytes.clone(),
+                attachment: edge.attachment,
+            };
+            let id = edge.edge_id()?;
+            if out.edges.insert(id, edge).is_some() {
+                return Err(PersistentGraphError::DuplicateEdge(id));
+            }
+        }
+        Ok(out)
+    }
+
+    /// Convert to a mutable graph. Nodes and edges are inserted in NodeId /
+    /// EdgeId order, so slot assignment is deterministic.
+    pub fn to_graph(&self) -> WarpGraph {
+        let mut graph = WarpGraph::new();
+        let mut keys = HashMap::with_capacity(self.nodes.len());
+        for (id, node) in &self.nodes {
+            keys.insert(*id, graph.insert_node(node.clone()));
+        }
+        for edge in self.edges.values() {
+            graph.edges.insert(WarpEdge {
+                source: keys[&edge.from],
+                target: keys[&edge.to],
+                edge_type: edge.edge_type.clone(),
+                payload_bytes: edge.payload_bytes.clone(),
+                attachment: edge.attachment,
+            });
+        }
+        graph
+    }
+
+    pub fn node(&self, id: &NodeId) -> Option<&WarpNode> {
+        self.nodes.get(id)
+    }
+
+    pub fn edge(&self, edge_id: &Hash) -> Option<&PersistentEdge> {
+        self.edges.get(edge_id)
+    }
+
+    /// Nodes in ascending NodeId order.
+    pub fn nodes(&self) -> impl Iterator<Item = &WarpNode> {
+        self.nodes.values()
+    }
+
+    /// Edges with their EdgeIds, in ascending EdgeId order.
+    pub fn edges(&self) -> impl Iterator<Item = (&Hash, &PersistentEdge)> {
+        self.edges.iter()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Insert or replace the node with `node.id`, returning the previous node.
+    pub fn insert_node(&mut self, node: WarpNode) -> Option<WarpNode> {
+        self.nodes.insert(node.id, node)
+    }
+
+    /// Remove a node and every edge incident to it.
+    pub fn remove_node(&mut self, id: &NodeId) -> Option<WarpNode> {
+        let node = self.nodes.remove(id)?;
+        let incident: Vec<Hash> = self
+            .edges
+            .iter()
+            .filter(|(_, e)| e.from == *id || e.to == *id)
+            .map(|(edge_id, _)| *edge_id)
+            .collect();
+        for edge_id in incident {
+            self.edges.remove(&edge_id);
+        }
+        Some(node)
+    }
+
+    /// Insert an edge, returning its EdgeId. Re-inserting an identical edge is a no-op.
+    pub fn insert_edge(&mut self, edge: PersistentEdge) -> Result<Hash, PersistentGraphError> {
+        for endpoint in [edge.from, edge.to] {
+            if !self.nodes.contains_key(&endpoint) {
+                return Err(PersistentGraphError::MissingEndpoint(endpoint));
+            }
+        }
+        let id = edge.edge_id()?;
+        self.edges.insert(id, edge);
+        Ok(id)
+    }
+
+    pub fn remove_edge(&mut self, edge_id: &Hash) -> Option<PersistentEdge> {
+        self.edges.remove(edge_id)
+    }
+
+    /// Computes the BLAKE3 root hash of the graph state.
+    pub fn compute_hash(&self) -> Hash {
+        self.compute_hash_checked()
+            .expect("canonical graph hashing must succeed")
+    }
+
+    /// Canonical graph commit digest; equal to the [`WarpGraph`] digest of the
+    /// same content.
+    pub fn compute_hash_checked(&self) -> Result<Hash, CanonicalError> {
+        let nodes = self
+            .nodes
+            .values()
+            .map(|n| NodeCommitV0 {
+                node_id: n.id,
+                kind: n.node_type.clone(),
+                payload_bytes: n.payload_bytes.clone(),
+                attachment: n.attachment,
+            })
+            .collect();
+        let edges = self
+            .edges
+            .iter()
+            .map(|(id, e)| EdgeCommitV0 {
+                edge_id: *id,
+                from: e.from,
+                to: e.to,
+                kind: e.edge_type.clone(),
+                payload_bytes: e.payload_bytes.clone(),
+                attachment: e.attachment,
+            })
+            .collect();
+        commit_digest(nodes, edges)
+    }
+}
@@ -0,0 +1,326 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! WARP Rewrite Rules
+//!
+//! Paper I: state evolves by graph rewriting. A [`RewriteRule`] pairs a
+//! structural [`Pattern`] with an optional guard and a producer that emits
+//! [`RewriteOp`]s for each match.
+//!
+//! Determinism contract:
+//! - Matches are enumerated against the graph as it was when `apply_rules` was
+//!   called, sorted by (rule index, matched NodeIds). SlotMap order never leaks.
+//! - New node IDs come from the caller's [`DeterministicIdAllocator`], keyed by
+//!   H(rule name || matched NodeIds), so reordering independent matches cannot
+//!   change allocated IDs.
+//! - The returned [`RewriteLog`] is canonically encodable and is intended to be
+//!   the payload of the Decision event that records the rewrite.
+
+use crate::{DeterministicIdAllocator, NodeId, NodeKey, WarpEdge, WarpGraph, WarpNode};
+use jitos_core::{canonical, Hash};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Structural pattern a rule matches against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    /// Any single node of the given type. Binds `[node]`.
+    Node { node_type: String },
+    /// A directed edge between typed endpoints. Binds `[from, to]`.
+    Edge {
+        from_type: String,
+        edge_type: String,
+        to_type: String,
+    },
+}
+
+/// One occurrence of a pattern in the graph.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Match {
+    /// Bound nodes, in pattern order.
+    pub nodes: Vec<NodeId>,
+}
+
+/// Guard predicate evaluated against the current graph before producing.
+pub type Guard = fn(&WarpGraph, &Match) -> bool;
+
+/// Producer mapping a match to the rewrite operations it performs.
+pub type Producer = fn(&WarpGraph, &Match) -> Vec<RewriteOp>;
+
+/// A named graph rewrite rule.
+///
+/// Guards and producers are plain function pointers: they cannot capture
+/// ambient state, which keeps rule evaluation a pure function of the graph.
+#[derive(Debug, Clone)]
+pub struct RewriteRule {
+    /// Stable rule name; part of the allocation key and the change log.
+    pub name: String,
+    pub pattern: Pattern,
+    pub guard: Option<Guard>,
+    pub producer: Producer,
+}
+
+/// Reference to a node from within a producer's output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeRef {
+    /// A node that already exists in the graph.
+    Existing(NodeId),
+    /// The n-th `AddNode` emitted by the same producer invocation.
+    Created(usize),
+}
+
+/// A single mutation emitted by a rule producer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RewriteOp {
+    AddNode {
+        node_type: String,
+        payload_bytes: Vec<u8>,
+    },
+    SetPayload {
+        node: NodeRef,
+        payload_bytes: Vec<u8>,
+    },
+    /// Removes the node and every edge incident to it.
+    DeleteNode { node: NodeRef },
+    AddEdge {
+        from: NodeRef,
+        to: NodeRef,
+        edge_type: String,
+        payload_bytes: Option<Vec<u8>>,
+    },
+}
+
+/// A graph change as recorded in the log (all references resolved to NodeIds).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op")]
+pub enum GraphChange {
+    NodeAdded {
+        id: NodeId,
+        node_type: String,
+    },
+    PayloadSet {
+        id: NodeId,
+    },
+    NodeRemoved {
+        id: NodeId,
+    },
+    EdgeAdded {
+        from: NodeId,
+        to: NodeId,
+        edge_type: String,
+    },
+}
+
+/// The changes one rule application made.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppliedRewrite {
+    pub rule: String,
+    pub matched: Vec<NodeId>,
+    pub changes: Vec<GraphChange>,
+}
+
+/// Ordered record of every rule application in one `apply_rules` call.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewriteLog {
+    pub applied: Vec<AppliedRewrite>,
+}
+
+impl RewriteLog {
+    /// True if no rule fired.
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty()
+    }
+}
+
+/// Rewrite engine errors.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RewriteError {
+    #[error("rule {rule} references unknown node {node:?}")]
+    UnknownNode { rule: String, node: NodeId },
+    #[error("rule {rule} references created node #{index} but produced only {created}")]
+    UnknownCreated {
+        rule: String,
+        index: usize,
+        created: usize,
+    },
+    #[error("canonical encoding failed: {0}")]
+    Canonical(#[from] canonical::CanonicalError),
+}
+
+impl Pattern {
+    /// Enumerate all matches of this pattern, sorted by bound NodeIds.
+    pub fn matches(&self, graph: &WarpGraph) -> Vec<Match> {
+        let mut out = Vec::new();
+        match self {
+            Pattern::Node { node_type } => {
+                for (_, node) in graph.nodes_of_type(node_type) {
+                    out.push(Match {
+                        nodes: vec![node.id],
+                    });
+                }
+            }
+            Pattern::Edge {
+                from_type,
+                edge_type,
+                to_type,
+            } => {
+                for edge in graph.edges.values() {
+                    if &edge.edge_type != edge_type {
+                        continue;
+                    }
+                    let (Some(from), Some(to)) =
+                        (graph.nodes.get(edge.source), graph.nodes.get(edge.target))
+                    else {
+                        continue;
+                    };
+                    if &from.node_type == from_type && &to.node_type == to_type {
+                        out.push(Match {
+                            nodes: vec![from.id, to.id],
+                        });
+                    }
+                }
+            }
+        }
+        out.sort();
+        // Parallel edges of the same type bind the same nodes; they are one match.
+        out.dedup();
+        out
+    }
+}
+
+/// Apply `rules` once over `graph`.
+///
+/// All matches are enumerated up front (rule order, then NodeId order) and then
+/// applied sequentially. A match is skipped if one of its nodes was removed by an
+/// earlier application, or if its guard rejects the graph as it is at that point.
+pub fn apply_rules(
+    graph: &mut WarpGraph,
+    rules: &[RewriteRule],
+    alloc: &mut DeterministicIdAllocator,
+) -> Result<RewriteLog, RewriteError> {
+    let mut pending: Vec<(&RewriteRule, Match)> = Vec::new();
+    for rule in rules {
+        for m in rule.pattern.matches(graph) {
+            pending.push((rule, m));
+        }
+    }
+
+    let mut log = RewriteLog::default();
+    for (rule, m) in pending {
+        if m.nodes.iter().any(|id| graph.node_key(id).is_none()) {
+            continue;
+        }
+        if let Some(guard) = rule.guard {
+            if !guard(graph, &m) {
+                continue;
+            }
+        }
+
+        let ops = (rule.producer)(graph, &m);
+        let op_hash = rule_op_hash(&rule.name, &m)?;
+        let changes = apply_ops(graph, &rule.name, ops, op_hash, alloc)?;
+
+        log.applied.push(AppliedRewrite {
+            rule: rule.name.clone(),
+            matched: m.nodes,
+            changes,
+        });
+    }
+
+    Ok(log)
+}
+
+/// Allocation key for one rule application: H("warp-rewrite-v0" || rule || matched).
+fn rule_op_hash(rule: &str, m: &Match) -> Result<Hash, canonical::CanonicalError> {
+    canonical::hash_canonical(&("warp-rewrite-v0", rule, &m.nodes))
+}
+
+fn apply_ops(
+    graph: &mut WarpGraph,
+    rule: &str,
+    ops: Vec<RewriteOp>,
+    op_hash: Hash,
+    alloc: &mut DeterministicIdAllocator,
+) -> Result<Vec<GraphChange>, RewriteError> {
+    let mut created: Vec<NodeId> = Vec::new();
+    let mut changes = Vec::with_capacity(ops.len());
+
+    let resolve =
+        |graph: &WarpGraph, created: &[NodeId], r: &NodeRef| -> Result<NodeKey, RewriteError> {
+            let id = match r {
+                NodeRef::Existing(id) => *id,
+                NodeRef::Created(index) => {
+                    *created
+                        .get(*index)
+                        .ok_or_else(|| RewriteError::UnknownCreated {
+                            rule: rule.to_string(),
+                            index: *index,
+                            created: created.len(),
+                        })?
+                }
+            };
+            graph
+                .node_key(&id)
+                .ok_or_else(|| RewriteError::UnknownNode {
+                    rule: rule.to_string(),
+                    node: id,
+                })
+        };
+
+    for op in ops {
+        match op {
+            RewriteOp::AddNode {
+                node_type,
+                payload_bytes,
+            } => {
+                let id = alloc.alloc_node_id(op_hash);
+                graph.insert_node(WarpNode {
+                    id,
+                    node_type: node_type.clone(),
+                    payload_bytes,
+                    attachment: None,
+                });
+                created.push(id);
+                changes.push(GraphChange::NodeAdded { id, node_type });
+            }
+            RewriteOp::SetPayload {
+                node,
+                payload_bytes,
+            } => {
+                let key = resolve(graph, &created, &node)?;
+                graph.set_payload(key, payload_bytes);
+                changes.push(GraphChange::PayloadSet {
+                    id: graph.nodes[key].id,
+                });
+            }
+            RewriteOp::DeleteNode { node } => {
+                let key = resolve(graph, &created, &node)?;
+                let removed = graph.remove_node(key).expect("resolved key exists");
+                changes.push(GraphChange::NodeRemoved { id: removed.id });
+            }
+            RewriteOp::AddEdge {
+                from,
+                to,
+                edge_type,
+                payload_bytes,
+            } => {
+                let source = resolve(graph, &created, &from)?;
+                let target = resolve(graph, &created, &to)?;
+                graph.edges.insert(WarpEdge {
+                    source,
+                    target,
+                    edge_type: edge_type.clone(),
+                    payload_bytes,
+                    attachment: None,
+                });
+                changes.push(GraphChange::EdgeAdded {
+                    from: graph.nodes[source].id,
+                    to: graph.nodes[target].id,
+                    edge_type,
+                });
+            }
+        }
+    }
+
+    Ok(changes)
+}
@@ -0,0 +1,195 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Graph Snapshot Store
+//!
+//! Maps graph commit digests to serialized graphs so replay can start from a
+//! checkpoint instead of genesis.
+//!
+//! Snapshots are encoded in a normalized form (nodes by NodeId, edges by
+//! EdgeId with NodeId endpoints), so the bytes depend only on graph content,
+//! never on slot layout. Every load recomputes the commit digest and rejects
+//! a snapshot that does not match the hash it was requested by.
+
+use crate::{PersistentEdge, PersistentGraph, PersistentGraphError, WarpGraph, WarpNode};
+use jitos_core::{canonical, canonical::CanonicalError, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Snapshot store errors.
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("no snapshot for graph {0}")]
+    NotFound(Hash),
+    #[error("snapshot {expected} decodes to graph {found}")]
+    DigestMismatch { expected: Hash, found: Hash },
+    #[error("graph cannot be snapshotted: {0}")]
+    Graph(#[from] PersistentGraphError),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Content-addressed graph snapshots.
+pub trait SnapshotStore {
+    /// Store `graph` and return its commit digest. Saving the same content twice is a no-op.
+    fn save(&mut self, graph: &WarpGraph) -> Result<Hash, SnapshotError>;
+
+    /// Load the graph with commit digest `hash`, verifying the digest.
+    fn load(&self, hash: &Hash) -> Result<WarpGraph, SnapshotError>;
+
+    fn contains(&self, hash: &Hash) -> bool;
+}
+
+/// Normalized on-disk form of a graph.
+#[derive(Debug, Serialize, Deserialize)]
+struct GraphSnapshotV0 {
+    version: String,
+    nodes: Vec<WarpNode>,
+    edges: Vec<PersistentEdge>,
+}
+
+const SNAPSHOT_VERSION: &str = "graph-snapshot-v0";
+
+/// Encode a graph as a snapshot, returning `(commit digest, bytes)`.
+pub fn encode_snapshot(graph: &WarpGraph) -> Result<(Hash, Vec<u8>), SnapshotError> {
+    let graph = PersistentGraph::from_graph(graph)?;
+    let snapshot = GraphSnapshotV0 {
+        version: SNAPSHOT_VERSION.to_string(),
+        nodes: graph.nodes().cloned().collect(),
+        edges: graph.edges().map(|(_, e)| e.clone()).collect(),
+    };
+    Ok((graph.compute_hash_checked()?, canonical::encode(&snapshot)?))
+}
+
+/// Decode snapshot bytes and check they hash to `expected`.
+pub fn decode_snapshot(expected: &Hash, bytes: &[u8]) -> Result<WarpGraph, SnapshotError> {
+    let snapshot: GraphSnapshotV0 = canonical::decode(bytes)?;
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Err(CanonicalError::Decode(format!(
+            "unsupported snapshot version {}",
+            snapshot.version
+        ))
+        .into());
+    }
+    let mut graph = PersistentGraph::new();
+    for node in snapshot.nodes {
+        graph.insert_node(node);
+    }
+    for edge in snapshot.edges {
+        graph.insert_edge(edge)?;
+    }
+    let found = graph.compute_hash_checked()?;
+    if found != *expected {
+        return Err(SnapshotError::DigestMismatch {
+            expected: *expected,
+            found,
+        });
+    }
+    Ok(graph.to_graph())
+}
+
+/// In-memory snapshot store.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySnapshotStore {
+    snapshots: BTreeMap<Hash, Vec<u8>>,
+}
+
+impl MemorySnapshotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stored digests, ascending.
+    pub fn hashes(&self) -> impl Iterator<Item = &Hash> {
+        self.snapshots.keys()
+    }
+
+    /// Raw snapshot bytes (e.g. for shipping to another node).
+    pub fn bytes(&self, hash: &Hash) -> Option<&[u8]> {
+        self.snapshots.get(hash).map(Vec::as_slice)
+    }
+
+    /// Store pre-encoded snapshot bytes under `hash` after verifying them.
+    pub fn insert_bytes(&mut self, hash: Hash, bytes: Vec<u8>) -> Result<(), SnapshotError> {
+        decode_snapshot(&hash, &bytes)?;
+        self.snapshots.insert(hash, bytes);
+        Ok(())
+    }
+}
+
+impl SnapshotStore for MemorySnapshotStore {
+    fn save(&mut self, graph: &WarpGraph) -> Result<Hash, SnapshotError> {
+        let (hash, bytes) = encode_snapshot(graph)?;
+        self.snapshots.entry(hash).or_insert(bytes);
+        Ok(hash)
+    }
+
+    fn load(&self, hash: &Hash) -> Result<WarpGraph, SnapshotError> {
+        let bytes = self
+            .snapshots
+            .get(hash)
+            .ok_or(SnapshotError::NotFound(*hash))?;
+        decode_snapshot(hash, bytes)
+    }
+
+    fn contains(&self, hash: &Hash) -> bool {
+        self.snapshots.contains_key(hash)
+    }
+}
+
+/// Directory-backed snapshot store: one `<digest-hex>.cbor` file per snapshot.
+#[derive(Debug, Clone)]
+pub struct FileSnapshotStore {
+    dir: PathBuf,
+}
+
+impl FileSnapshotStore {
+    /// Open (creating if needed) a snapshot directory.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, SnapshotError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, hash: &Hash) -> PathBuf {
+        self.dir.join(format!("{hash}.cbor"))
+    }
+}
+
+impl SnapshotStore for FileSnapshotStore {
+    fn save(&mut self, graph: &WarpGraph) -> Result<Hash, SnapshotError> {
+        let (hash, bytes) = encode_snapshot(graph)?;
+        let path = self.path(&hash);
+        if !path.exists() {
+            // Write-then-rename so a crash never leaves a truncated snapshot
+            // under a valid name.
+            let tmp = self.dir.join(format!("{hash}.cbor.tmp"));
+            std::fs::write(&tmp, &bytes)?;
+            std::fs::rename(&tmp, &path)?;
+        }
+        Ok(hash)
+    }
+
+    fn load(&self, hash: &Hash) -> Result<WarpGraph, SnapshotError> {
+        let bytes = match std::fs::read(self.path(hash)) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(SnapshotError::NotFound(*hash))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        decode_snapshot(hash, &bytes)
+    }
+
+    fn contains(&self, hash: &Hash) -> bool {
+        self.path(hash).exists()
+    }
+}
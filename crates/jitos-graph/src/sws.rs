@@ -0,0 +1,385 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shadow Working Sets
+//!
+//! A [`ShadowWorkingSet`] (SWS) is a speculative overlay over the System
+//! graph. It is forked from a System state, records tentative mutations
+//! against its own view, and leaves System untouched until it is collapsed
+//! (`Slap::Collapse { sws_id }`).
+//!
+//! Both the fork base and the view are [`PersistentGraph`]s, so the view
+//! shares all untouched structure with the base.
+//!
+//! Collapse is validation plus an atomic merge (M3 fail-fast policy): every
+//! node the SWS wrote or connected to must be unchanged in System since the
+//! fork, and a removed node must still have the incident edges it had then.
+//! If any check fails, System is left as it was and the caller gets a
+//! [`CollapseConflict`] listing every violation. Otherwise all recorded ops
+//! are replayed onto System in recording order.
+//!
+//! The SWS digest is H("sws-v0", id, base digest, ops), so two SWSes with the
+//! same base and the same ops are interchangeable.
+
+use crate::{
+    NodeId, NodeKey, PersistentEdge, PersistentGraph, PersistentGraphError, WarpEdge, WarpGraph,
+    WarpNode,
+};
+use jitos_core::{canonical, canonical::CanonicalError, Hash, SwsId};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use thiserror::Error;
+
+/// A tentative mutation, as recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op")]
+pub enum SwsOp {
+    AddNode {
+        node: WarpNode,
+    },
+    SetPayload {
+        id: NodeId,
+        payload_bytes: Vec<u8>,
+    },
+    /// Removes the node and every edge incident to it.
+    RemoveNode {
+        id: NodeId,
+    },
+    AddEdge {
+        edge: PersistentEdge,
+    },
+}
+
+/// How System diverged from an SWS's base at a node the SWS depends on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ConflictCause {
+    /// The node was created in System after the fork.
+    Added,
+    /// The node was removed from System after the fork.
+    Removed,
+    /// The node's type, payload or attachment changed.
+    Modified,
+    /// Edges incident to a node the SWS removes changed.
+    EdgesChanged,
+}
+
+/// One node at which collapse failed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SwsConflict {
+    pub node: NodeId,
+    pub cause: ConflictCause,
+}
+
+/// Structured report of a rejected collapse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollapseConflict {
+    pub sws_id: SwsId,
+    /// System digest the SWS was forked from.
+    pub base: Hash,
+    /// System digest at collapse.
+    pub system: Hash,
+    /// Every violation, ascending by node.
+    pub conflicts: Vec<SwsConflict>,
+}
+
+/// Outcome of a successful collapse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollapseReceipt {
+    pub sws_id: SwsId,
+    /// Digest of the collapsed SWS.
+    pub sws_digest: Hash,
+    /// System digest before the merge.
+    pub before: Hash,
+    /// System digest after the merge.
+    pub after: Hash,
+    /// Number of ops replayed.
+    pub ops: usize,
+}
+
+/// SWS errors.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SwsError {
+    #[error("node {0:?} already exists in the SWS view")]
+    NodeExists(NodeId),
+    #[error("node {0:?} is not in the SWS view")]
+    UnknownNode(NodeId),
+    #[error("SWS {0} does not exist")]
+    UnknownSws(SwsId),
+    #[error("SWS {0} already exists")]
+    SwsExists(SwsId),
+    #[error("collapse of SWS {} rejected with {} conflict(s)", .0.sws_id, .0.conflicts.len())]
+    Conflict(Box<CollapseConflict>),
+    #[error(transparent)]
+    Graph(#[from] PersistentGraphError),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+}
+
+/// A speculative overlay over a System state.
+#[derive(Debug, Clone)]
+pub struct ShadowWorkingSet {
+    id: SwsId,
+    base_digest: Hash,
+    base: PersistentGraph,
+    view: PersistentGraph,
+    ops: Vec<SwsOp>,
+}
+
+impl ShadowWorkingSet {
+    /// Fork an SWS from the current System graph.
+    pub fn fork(id: impl Into<SwsId>, system: &WarpGraph) -> Result<Self, SwsError> {
+        let base = PersistentGraph::from_graph(system)?;
+        Ok(Self {
+            id: id.into(),
+            base_digest: base.compute_hash_checked()?,
+            view: base.clone(),
+            base,
+            ops: Vec::new(),
+        })
+    }
+
+    pub fn id(&self) -> &SwsId {
+        &self.id
+    }
+
+    /// System digest at fork.
+    pub fn base_digest(&self) -> Hash {
+        self.base_digest
+    }
+
+    /// The base with every recorded op applied.
+    pub fn view(&self) -> &PersistentGraph {
+        &self.view
+    }
+
+    /// Recorded ops, in recording order.
+    pub fn ops(&self) -> &[SwsOp] {
+        &self.ops
+    }
+
+    /// H("sws-v0", id, base digest, ops).
+    pub fn digest(&self) -> Result<Hash, CanonicalError> {
+        canonical::hash_canonical(&("sws-v0", &self.id, self.base_digest, &self.ops))
+    }
+
+    pub fn add_node(&mut self, node: WarpNode) -> Result<(), SwsError> {
+        if self.view.node(&node.id).is_some() {
+            return Err(SwsError::NodeExists(node.id));
+        }
+        self.view.insert_node(node.clone());
+        self.ops.push(SwsOp::AddNode { node });
+        Ok(())
+    }
+
+    pub fn set_payload(&mut self, id: NodeId, payload_bytes: Vec<u8>) -> Result<(), SwsError> {
+        let mut node = self
+            .view
+            .node(&id)
+            .cloned()
+            .ok_or(SwsError::UnknownNode(id))?;
+        node.payload_bytes = payload_bytes.clone();
+        self.view.insert_node(node);
+        self.ops.push(SwsOp::SetPayload { id, payload_bytes });
+        Ok(())
+    }
+
+    pub fn remove_node(&mut self, id: NodeId) -> Result<(), SwsError> {
+        self.view
+            .remove_node(&id)
+            .ok_or(SwsError::UnknownNode(id))?;
+        self.ops.push(SwsOp::RemoveNode { id });
+        Ok(())
+    }
+
+    /// Add an edge between nodes of the view, returning its EdgeId.
+    pub fn add_edge(&mut self, edge: PersistentEdge) -> Result<Hash, SwsError> {
+        let edge_id = self.view.insert_edge(edge.clone())?;
+        self.ops.push(SwsOp::AddEdge { edge });
+        Ok(edge_id)
+    }
+
+    /// Nodes whose System state the ops depend on, with whether the SWS
+    /// removes them.
+    fn dependencies(&self) -> BTreeMap<NodeId, bool> {
+        let mut deps = BTreeMap::new();
+        for op in &self.ops {
+            match op {
+                SwsOp::AddNode { node } => {
+                    deps.entry(node.id).or_insert(false);
+                }
+                SwsOp::SetPayload { id, .. } => {
+                    deps.entry(*id).or_insert(false);
+                }
+                SwsOp::RemoveNode { id } => {
+                    deps.insert(*id, true);
+                }
+                SwsOp::AddEdge { edge } => {
+                    deps.entry(edge.from).or_insert(false);
+                    deps.entry(edge.to).or_insert(false);
+                }
+            }
+        }
+        deps
+    }
+
+    /// Check that System still agrees with the base wherever the ops depend
+    /// on it.
+    pub fn validate(&self, system: &WarpGraph) -> Result<(), SwsError> {
+        let current = PersistentGraph::from_graph(system)?;
+        let mut conflicts = Vec::new();
+        for (id, removes) in self.dependencies() {
+            let cause = match (self.base.node(&id), current.node(&id)) {
+                (None, Some(_)) => Some(ConflictCause::Added),
+                (Some(_), None) => Some(ConflictCause::Removed),
+                (Some(then), Some(now)) if then != now => Some(ConflictCause::Modified),
+                _ if removes && incident(&self.base, &id) != incident(&current, &id) => {
+                    Some(ConflictCause::EdgesChanged)
+                }
+                _ => None,
+            };
+            if let Some(cause) = cause {
+                conflicts.push(SwsConflict { node: id, cause });
+            }
+        }
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+        Err(SwsError::Conflict(Box::new(CollapseConflict {
+            sws_id: self.id.clone(),
+            base: self.base_digest,
+            system: current.compute_hash_checked()?,
+            conflicts,
+        })))
+    }
+
+    /// Validate against `system` and merge every op into it, atomically.
+    ///
+    /// On error `system` is unchanged.
+    pub fn collapse(&self, system: &mut WarpGraph) -> Result<CollapseReceipt, SwsError> {
+        self.validate(system)?;
+        let before = system.compute_hash_checked()?;
+
+        let mut merged = system.clone();
+        let mut keys: HashMap<NodeId, NodeKey> =
+            merged.nodes.iter().map(|(k, n)| (n.id, k)).collect();
+        for op in &self.ops {
+            // Ops were valid against the view, and System agrees with the base
+            // on every node they touch, so lookups cannot fail.
+            match op {
+                SwsOp::AddNode { node } => {
+                    keys.insert(node.id, merged.insert_node(node.clone()));
+                }
+                SwsOp::SetPayload { id, payload_bytes } => {
+                    merged.set_payload(keys[id], payload_bytes.clone());
+                }
+                SwsOp::RemoveNode { id } => {
+                    if let Some(key) = keys.remove(id) {
+                        merged.remove_node(key);
+                    }
+                }
+                SwsOp::AddEdge { edge } => {
+                    let edge = WarpEdge {
+                        source: keys[&edge.from],
+                        target: keys[&edge.to],
+                        edge_type: edge.edge_type.clone(),
+                        payload_bytes: edge.payload_bytes.clone(),
+                        attachment: edge.attachment,
+                    };
+                    // Edges are content-addressed: an identical edge is one edge.
+                    let present = merged.edges.values().any(|e| {
+                        e.source == edge.source
+                            && e.target == edge.target
+                            && e.edge_type == edge.edge_type
+                            && e.payload_bytes == edge.payload_bytes
+                            && e.attachment == edge.attachment
+                    });
+                    if !present {
+                        merged.edges.insert(edge);
+                    }
+                }
+            }
+        }
+
+        let after = merged.compute_hash_checked()?;
+        *system = merged;
+        Ok(CollapseReceipt {
+            sws_id: self.id.clone(),
+            sws_digest: self.digest()?,
+            before,
+            after,
+            ops: self.ops.len(),
+        })
+    }
+}
+
+/// EdgeIds incident to `id`.
+fn incident(graph: &PersistentGraph, id: &NodeId) -> BTreeSet<Hash> {
+    graph
+        .edges()
+        .filter(|(_, e)| e.from == *id || e.to == *id)
+        .map(|(edge_id, _)| *edge_id)
+        .collect()
+}
+
+/// Live SWSes by id, as referenced by `Slap::Collapse`.
+#[derive(Debug, Clone, Default)]
+pub struct SwsRegistry {
+    sets: BTreeMap<SwsId, ShadowWorkingSet>,
+}
+
+impl SwsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Fork a new SWS named `id` from `system`.
+    pub fn fork(
+        &mut self,
+        id: impl Into<SwsId>,
+        system: &WarpGraph,
+    ) -> Result<&mut ShadowWorkingSet, SwsError> {
+        let id = id.into();
+        if self.sets.contains_key(&id) {
+            return Err(SwsError::SwsExists(id));
+        }
+        let sws = ShadowWorkingSet::fork(id.clone(), system)?;
+        Ok(self.sets.entry(id).or_insert(sws))
+    }
+
+    pub fn get(&self, id: &SwsId) -> Option<&ShadowWorkingSet> {
+        self.sets.get(id)
+    }
+
+    pub fn get_mut(&mut self, id: &SwsId) -> Option<&mut ShadowWorkingSet> {
+        self.sets.get_mut(id)
+    }
+
+    /// Drop an SWS without merging it.
+    pub fn discard(&mut self, id: &SwsId) -> Option<ShadowWorkingSet> {
+        self.sets.remove(id)
+    }
+
+    /// Collapse the SWS named `id` into `system`. A collapsed SWS is removed;
+    /// a rejected one stays so it can be inspected or discarded.
+    pub fn collapse(
+        &mut self,
+        id: &SwsId,
+        system: &mut WarpGraph,
+    ) -> Result<CollapseReceipt, SwsError> {
+        let sws = self
+            .sets
+            .get(id)
+            .ok_or_else(|| SwsError::UnknownSws(id.clone()))?;
+        let receipt = sws.collapse(system)?;
+        self.sets.remove(id);
+        Ok(receipt)
+    }
+}
@@ -0,0 +1,314 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Graph Transactions
+//!
+//! A [`GraphTxn`] buffers mutations against a base graph. Reads see the base
+//! with the buffered writes applied; the base itself is untouched until
+//! [`GraphTxn::commit`], and [`GraphTxn::abort`] discards everything.
+//!
+//! Every read and write is recorded in a [`TxnFootprint`] — the resources the
+//! transaction actually touched, as opposed to what was declared for it — so a
+//! scheduler can execute possibly-conflicting SLAPs optimistically, compare
+//! the touched footprints, and commit or roll back each transaction.
+//!
+//! Edges are addressed by deterministic EdgeId (see [`crate::edge_id`]);
+//! content-identical edges are one edge.
+
+use crate::{EdgeKey, NodeId, NodeKey, PersistentEdge, WarpEdge, WarpGraph, WarpNode};
+use jitos_core::{canonical::CanonicalError, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use thiserror::Error;
+
+/// An edge, by endpoints and type.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TouchedEdge {
+    pub from: NodeId,
+    pub to: NodeId,
+    pub edge_type: String,
+}
+
+impl From<&PersistentEdge> for TouchedEdge {
+    fn from(edge: &PersistentEdge) -> Self {
+        Self {
+            from: edge.from,
+            to: edge.to,
+            edge_type: edge.edge_type.clone(),
+        }
+    }
+}
+
+/// Resources a transaction read and wrote.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxnFootprint {
+    pub nodes_read: BTreeSet<NodeId>,
+    pub nodes_written: BTreeSet<NodeId>,
+    pub edges_read: BTreeSet<TouchedEdge>,
+    pub edges_written: BTreeSet<TouchedEdge>,
+}
+
+impl TxnFootprint {
+    pub fn is_empty(&self) -> bool {
+        self.nodes_read.is_empty()
+            && self.nodes_written.is_empty()
+            && self.edges_read.is_empty()
+            && self.edges_written.is_empty()
+    }
+}
+
+/// Transaction errors. A failed operation buffers nothing.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TxnError {
+    #[error("node {0:?} already exists")]
+    NodeExists(NodeId),
+    #[error("node {0:?} does not exist")]
+    UnknownNode(NodeId),
+    #[error("edge {0} does not exist")]
+    UnknownEdge(Hash),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+}
+
+/// Buffered mutations against a base graph.
+pub struct GraphTxn<'g> {
+    base: &'g mut WarpGraph,
+    /// NodeId -> slot in the base.
+    keys: HashMap<NodeId, NodeKey>,
+    /// EdgeId -> (edge, slots) in the base; built on first edge access.
+    base_edges: Option<BTreeMap<Hash, (PersistentEdge, Vec<EdgeKey>)>>,
+    /// Buffered node state: `None` is removed.
+    nodes: BTreeMap<NodeId, Option<WarpNode>>,
+    /// Buffered edges not in the base.
+    added_edges: BTreeMap<Hash, PersistentEdge>,
+    /// Base edges removed.
+    removed_edges: BTreeSet<Hash>,
+    footprint: TxnFootprint,
+}
+
+impl<'g> GraphTxn<'g> {
+    /// Begin a transaction on `base`.
+    pub fn begin(base: &'g mut WarpGraph) -> Self {
+        let keys = base.nodes.iter().map(|(k, n)| (n.id, k)).collect();
+        Self {
+            base,
+            keys,
+            base_edges: None,
+            nodes: BTreeMap::new(),
+            added_edges: BTreeMap::new(),
+            removed_edges: BTreeSet::new(),
+            footprint: TxnFootprint::default(),
+        }
+    }
+
+    /// Everything touched so far.
+    pub fn footprint(&self) -> &TxnFootprint {
+        &self.footprint
+    }
+
+    /// True if any mutation is buffered.
+    pub fn is_dirty(&self) -> bool {
+        !self.nodes.is_empty() || !self.added_edges.is_empty() || !self.removed_edges.is_empty()
+    }
+
+    /// Read a node.
+    pub fn node(&mut self, id: &NodeId) -> Option<&WarpNode> {
+        self.footprint.nodes_read.insert(*id);
+        self.lookup(id)
+    }
+
+    /// Insert a new node.
+    pub fn insert_node(&mut self, node: WarpNode) -> Result<(), TxnError> {
+        self.footprint.nodes_written.insert(node.id);
+        if self.lookup(&node.id).is_some() {
+            return Err(TxnError::NodeExists(node.id));
+        }
+        self.nodes.insert(node.id, Some(node));
+        Ok(())
+    }
+
+    /// Replace a node's payload.
+    pub fn set_payload(&mut self, id: NodeId, payload_bytes: Vec<u8>) -> Result<(), TxnError> {
+        self.footprint.nodes_written.insert(id);
+        let mut node = self.lookup(&id).cloned().ok_or(TxnError::UnknownNode(id))?;
+        node.payload_bytes = payload_bytes;
+        self.nodes.insert(id, Some(node));
+        Ok(())
+    }
+
+    /// Remove a node and every edge incident to it.
+    pub fn remove_node(&mut self, id: NodeId) -> Result<WarpNode, TxnError> {
+        self.footprint.nodes_written.insert(id);
+        let node = self.lookup(&id).cloned().ok_or(TxnError::UnknownNode(id))?;
+        for (edge_id, edge) in self.incident(&id)? {
+            self.footprint.edges_written.insert((&edge).into());
+            self.drop_edge(&edge_id);
+        }
+        self.nodes.insert(id, None);
+        Ok(node)
+    }
+
+    /// Edges incident to `id`, ascending by EdgeId.
+    pub fn edges_of(&mut self, id: NodeId) -> Result<Vec<(Hash, PersistentEdge)>, TxnError> {
+        self.footprint.nodes_read.insert(id);
+        if self.lookup(&id).is_none() {
+            return Err(TxnError::UnknownNode(id));
+        }
+        let edges = self.incident(&id)?;
+        self.footprint
+            .edges_read
+            .extend(edges.iter().map(|(_, e)| TouchedEdge::from(e)));
+        Ok(edges)
+    }
+
+    /// Add an edge between existing nodes, returning its EdgeId.
+    pub fn add_edge(&mut self, edge: PersistentEdge) -> Result<Hash, TxnError> {
+        for endpoint in [edge.from, edge.to] {
+            self.footprint.nodes_read.insert(endpoint);
+            if self.lookup(&endpoint).is_none() {
+                return Err(TxnError::UnknownNode(endpoint));
+            }
+        }
+        let edge_id = edge.edge_id()?;
+        self.footprint.edges_written.insert((&edge).into());
+        if !self.removed_edges.remove(&edge_id) && !self.base_edges()?.contains_key(&edge_id) {
+            self.added_edges.insert(edge_id, edge);
+        }
+        Ok(edge_id)
+    }
+
+    /// Remove an edge by EdgeId.
+    pub fn remove_edge(&mut self, edge_id: &Hash) -> Result<PersistentEdge, TxnError> {
+        let edge = self.edge(edge_id)?.ok_or(TxnError::UnknownEdge(*edge_id))?;
+        self.footprint.edges_written.insert((&edge).into());
+        self.drop_edge(edge_id);
+        Ok(edge)
+    }
+
+    /// Apply every buffered mutation to the base, returning the footprint.
+    pub fn commit(self) -> TxnFootprint {
+        let Self {
+            base,
+            mut keys,
+            base_edges,
+            nodes,
+            added_edges,
+            removed_edges,
+            footprint,
+        } = self;
+
+        if let Some(base_edges) = &base_edges {
+            for edge_id in &removed_edges {
+                for key in &base_edges[edge_id].1 {
+                    base.edges.remove(*key);
+                }
+            }
+        }
+        for (id, state) in nodes {
+            match (keys.get(&id).copied(), state) {
+                // Same type: replace in place, keeping the slot.
+                (Some(key), Some(node)) if base.nodes[key].node_type == node.node_type => {
+                    base.replace_node(key, node);
+                }
+                (Some(key), Some(node)) => {
+                    base.remove_node(key);
+                    keys.insert(id, base.insert_node(node));
+                }
+                (Some(key), None) => {
+                    base.remove_node(key);
+                    keys.remove(&id);
+                }
+                (None, Some(node)) => {
+                    keys.insert(id, base.insert_node(node));
+                }
+                (None, None) => {}
+            }
+        }
+        for edge in added_edges.into_values() {
+            base.edges.insert(WarpEdge {
+                source: keys[&edge.from],
+                target: keys[&edge.to],
+                edge_type: edge.edge_type,
+                payload_bytes: edge.payload_bytes,
+                attachment: edge.attachment,
+            });
+        }
+        footprint
+    }
+
+    /// Discard every buffered mutation, returning the footprint.
+    pub fn abort(self) -> TxnFootprint {
+        self.footprint
+    }
+
+    fn lookup(&self, id: &NodeId) -> Option<&WarpNode> {
+        match self.nodes.get(id) {
+            Some(state) => state.as_ref(),
+            None => self.keys.get(id).and_then(|k| self.base.nodes.get(*k)),
+        }
+    }
+
+    fn base_edges(&mut self) -> Result<&BTreeMap<Hash, (PersistentEdge, Vec<EdgeKey>)>, TxnError> {
+        if self.base_edges.is_none() {
+            let mut index: BTreeMap<Hash, (PersistentEdge, Vec<EdgeKey>)> = BTreeMap::new();
+            for (key, e) in self.base.edges.iter() {
+                let (Some(from), Some(to)) =
+                    (self.base.nodes.get(e.source), self.base.nodes.get(e.target))
+                else {
+                    continue;
+                };
+                let edge = PersistentEdge {
+                    from: from.id,
+                    to: to.id,
+                    edge_type: e.edge_type.clone(),
+                    payload_bytes: e.payload_bytes.clone(),
+                    attachment: e.attachment,
+                };
+                let edge_id = edge.edge_id()?;
+                index
+                    .entry(edge_id)
+                    .or_insert((edge, Vec::new()))
+                    .1
+                    .push(key);
+            }
+            self.base_edges = Some(index);
+        }
+        Ok(self.base_edges.as_ref().expect("edge index built above"))
+    }
+
+    /// Current state of an edge.
+    fn edge(&mut self, edge_id: &Hash) -> Result<Option<PersistentEdge>, TxnError> {
+        if let Some(edge) = self.added_edges.get(edge_id) {
+            return Ok(Some(edge.clone()));
+        }
+        if self.removed_edges.contains(edge_id) {
+            return Ok(None);
+        }
+        Ok(self.base_edges()?.get(edge_id).map(|(e, _)| e.clone()))
+    }
+
+    fn incident(&mut self, id: &NodeId) -> Result<Vec<(Hash, PersistentEdge)>, TxnError> {
+        self.base_edges()?;
+        let touches = |e: &PersistentEdge| e.from == *id || e.to == *id;
+        let mut edges: BTreeMap<Hash, PersistentEdge> = self
+            .base_edges
+            .iter()
+            .flatten()
+            .filter(|(h, (e, _))| touches(e) && !self.removed_edges.contains(h))
+            .map(|(h, (e, _))| (*h, e.clone()))
+            .collect();
+        edges.extend(
+            self.added_edges
+                .iter()
+                .filter(|(_, e)| touches(e))
+                .map(|(h, e)| (*h, e.clone())),
+        );
+        Ok(edges.into_iter().collect())
+    }
+
+    fn drop_edge(&mut self, edge_id: &Hash) {
+        if self.added_edges.remove(edge_id).is_none() {
+            self.removed_edges.insert(*edge_id);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope};
+use jitos_core::Hash;
+use jitos_graph::blame::{blame, BlameError};
+use jitos_graph::rewrite::{AppliedRewrite, GraphChange, RewriteLog};
+use jitos_graph::NodeId;
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn observation(tag: u64) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&tag).unwrap(),
+        vec![],
+        None,
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+fn policy(name: &str) -> EventEnvelope {
+    EventEnvelope::new_policy_context(
+        CanonicalBytes::from_value(&name).unwrap(),
+        vec![],
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+fn rewrite_decision(
+    evidence: &EventEnvelope,
+    policy: &EventEnvelope,
+    agent: &str,
+    rule: &str,
+    changes: Vec<GraphChange>,
+) -> EventEnvelope {
+    let log = RewriteLog {
+        applied: vec![AppliedRewrite {
+            rule: rule.to_string(),
+            matched: vec![],
+            changes,
+        }],
+    };
+    EventEnvelope::new_decision(
+        CanonicalBytes::from_value(&log).unwrap(),
+        vec![evidence.event_id()],
+        policy.event_id(),
+        Some(AgentId::new(agent).unwrap()),
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn blame_reports_creator_and_last_modifier() {
+    let obs = observation(1);
+    let p1 = policy("rewrite-v1");
+    let p2 = policy("rewrite-v2");
+
+    let create = rewrite_decision(
+        &obs,
+        &p1,
+        "alice",
+        "spawn",
+        vec![GraphChange::NodeAdded {
+            id: node_id(1),
+            node_type: "task".to_string(),
+        }],
+    );
+    let modify = rewrite_decision(
+        &create,
+        &p2,
+        "bob",
+        "update",
+        vec![GraphChange::PayloadSet { id: node_id(1) }],
+    );
+    let events = vec![obs, p1.clone(), p2.clone(), create.clone(), modify.clone()];
+
+    let b = blame(&events, node_id(1))
+        .unwrap()
+        .expect("node was touched");
+
+    let created = b.created.expect("creation is in slice");
+    assert_eq!(created.decision, create.event_id());
+    assert_eq!(created.policy, p1.event_id());
+    assert_eq!(created.agent.as_ref().map(AgentId::as_str), Some("alice"));
+    assert_eq!(created.rule, "spawn");
+
+    assert_eq!(b.last_modified.decision, modify.event_id());
+    assert_eq!(b.last_modified.policy, p2.event_id());
+    assert_eq!(b.last_modified.rule, "update");
+    assert_eq!(
+        b.last_modified.change,
+        GraphChange::PayloadSet { id: node_id(1) }
+    );
+}
+
+#[test]
+fn untouched_node_has_no_blame() {
+    let obs = observation(1);
+    let p = policy("rewrite-v1");
+    let d = rewrite_decision(
+        &obs,
+        &p,
+        "alice",
+        "spawn",
+        vec![GraphChange::NodeAdded {
+            id: node_id(1),
+            node_type: "task".to_string(),
+        }],
+    );
+
+    assert_eq!(blame(&[obs, p, d], node_id(2)).unwrap(), None);
+}
+
+#[test]
+fn decision_without_policy_in_slice_is_an_error() {
+    let obs = observation(1);
+    let p = policy("rewrite-v1");
+    let d = rewrite_decision(
+        &obs,
+        &p,
+        "alice",
+        "spawn",
+        vec![GraphChange::NodeRemoved { id: node_id(1) }],
+    );
+    let decision_id = d.event_id();
+
+    // PolicyContext deliberately omitted from the slice.
+    let err = blame(&[obs, d], node_id(1)).unwrap_err();
+    assert_eq!(err, BlameError::MissingPolicy(decision_id));
+}
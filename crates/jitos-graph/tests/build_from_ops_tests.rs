@@ -0,0 +1,115 @@
+use jitos_core::{canonical, Hash};
+use jitos_graph::build::{BuildError, GraphOp, OpNodeRef};
+use jitos_graph::{DeterministicIdAllocator, NodeId, WarpGraph};
+
+fn op(byte: u8) -> Hash {
+    Hash([byte; 32])
+}
+
+fn add_node(node_type: &str) -> GraphOp {
+    GraphOp::AddNode {
+        node_type: node_type.to_string(),
+        payload_bytes: node_type.as_bytes().to_vec(),
+        attachment: None,
+    }
+}
+
+fn link(from: (u8, usize), to: (u8, usize)) -> GraphOp {
+    GraphOp::AddEdge {
+        from: OpNodeRef::Created {
+            op: op(from.0),
+            index: from.1,
+        },
+        to: OpNodeRef::Created {
+            op: op(to.0),
+            index: to.1,
+        },
+        edge_type: "depends_on".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    }
+}
+
+fn tick_ops() -> Vec<(Hash, GraphOp)> {
+    vec![
+        (op(1), add_node("task")),
+        (op(1), add_node("receipt")),
+        (op(2), add_node("task")),
+        // Edge from op 3 references nodes created by other ops.
+        (op(3), link((2, 0), (1, 0))),
+        (op(1), link((1, 0), (1, 1))),
+    ]
+}
+
+#[test]
+fn builds_nodes_and_cross_op_edges() {
+    let graph = WarpGraph::build_from_ops(&tick_ops()).expect("build");
+    assert_eq!(graph.nodes.len(), 3);
+    assert_eq!(graph.edges.len(), 2);
+    assert!(graph.check_integrity().is_ok());
+
+    // Node ids are exactly what the tick allocator hands out.
+    let hashes: Vec<Hash> = tick_ops().iter().map(|(h, _)| *h).collect();
+    let mut alloc = DeterministicIdAllocator::new_for_tick(&hashes);
+    let first_task = alloc.alloc_node_id(op(1));
+    let receipt = alloc.alloc_node_id(op(1));
+    assert_eq!(graph.nodes_of_type("receipt").next().unwrap().1.id, receipt);
+    assert!(graph.node_key(&first_task).is_some());
+}
+
+#[test]
+fn antichain_swaps_produce_identical_graphs() {
+    let ops = tick_ops();
+    let expected = WarpGraph::build_from_ops(&ops)
+        .expect("build")
+        .compute_hash();
+
+    // Move whole operations around while keeping each op's sub-steps in order.
+    for shift in 0..3u8 {
+        let order: Vec<u8> = (0..3).map(|i| (i + shift) % 3 + 1).collect();
+        let permuted: Vec<(Hash, GraphOp)> = order
+            .iter()
+            .rev()
+            .flat_map(|b| ops.iter().filter(move |(h, _)| *h == op(*b)).cloned())
+            .collect();
+        let graph = WarpGraph::build_from_ops(&permuted).expect("build");
+        assert_eq!(graph.compute_hash(), expected, "order {order:?}");
+    }
+}
+
+#[test]
+fn unresolvable_references_are_errors() {
+    let ops = vec![(op(1), add_node("task")), (op(2), link((1, 0), (1, 5)))];
+    assert_eq!(
+        WarpGraph::build_from_ops(&ops).unwrap_err(),
+        BuildError::UnknownCreated {
+            op: op(1),
+            index: 5,
+            created: 1
+        }
+    );
+
+    let stranger = NodeId::from_hash(op(9));
+    let ops = vec![(
+        op(1),
+        GraphOp::AddEdge {
+            from: OpNodeRef::Id(stranger),
+            to: OpNodeRef::Id(stranger),
+            edge_type: "self".to_string(),
+            payload_bytes: None,
+            attachment: None,
+        },
+    )];
+    assert_eq!(
+        WarpGraph::build_from_ops(&ops).unwrap_err(),
+        BuildError::UnknownNode(stranger)
+    );
+}
+
+#[test]
+fn graph_ops_round_trip_canonically() {
+    let ops = tick_ops();
+    let bytes = canonical::encode(&ops).expect("encode");
+    let decoded: Vec<(Hash, GraphOp)> = canonical::decode(&bytes).expect("decode");
+    assert_eq!(decoded, ops);
+}
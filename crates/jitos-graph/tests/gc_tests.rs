@@ -0,0 +1,114 @@
+use jitos_core::Hash;
+use jitos_graph::{edge_id, NodeId, NodeKey, WarpEdge, WarpGraph, WarpNode};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn insert_node(graph: &mut WarpGraph, byte: u8) -> NodeKey {
+    graph.insert_node(WarpNode {
+        id: node_id(byte),
+        node_type: "demo".to_string(),
+        payload_bytes: vec![byte],
+        attachment: None,
+    })
+}
+
+fn connect(graph: &mut WarpGraph, source: NodeKey, target: NodeKey) {
+    graph.edges.insert(WarpEdge {
+        source,
+        target,
+        edge_type: "demo.edge".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    });
+}
+
+/// root(1) -> 2 -> 3, plus an island 4 -> 5 and a back-edge 4 -> 2.
+fn sample_graph() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    let n1 = insert_node(&mut g, 1);
+    let n2 = insert_node(&mut g, 2);
+    let n3 = insert_node(&mut g, 3);
+    let n4 = insert_node(&mut g, 4);
+    let n5 = insert_node(&mut g, 5);
+    connect(&mut g, n1, n2);
+    connect(&mut g, n2, n3);
+    connect(&mut g, n4, n5);
+    connect(&mut g, n4, n2);
+    g
+}
+
+#[test]
+fn unreachable_nodes_and_their_edges_are_removed() {
+    let mut g = sample_graph();
+    let island_edge = {
+        let e = g
+            .edges
+            .values()
+            .find(|e| g.nodes[e.source].id == node_id(4) && g.nodes[e.target].id == node_id(5))
+            .unwrap();
+        edge_id(node_id(4), node_id(5), e).unwrap()
+    };
+
+    let report = g.collect_garbage(&[node_id(1)]).expect("gc");
+
+    assert_eq!(report.removed_nodes, vec![node_id(4), node_id(5)]);
+    assert_eq!(report.removed_edges.len(), 2);
+    assert!(report.removed_edges.contains(&island_edge));
+    assert_eq!(g.nodes.len(), 3);
+    assert_eq!(g.edges.len(), 2);
+    assert!(g.compute_hash_checked().is_ok());
+}
+
+#[test]
+fn gc_result_is_independent_of_insertion_order() {
+    let mut g1 = sample_graph();
+
+    let mut g2 = WarpGraph::new();
+    let n5 = insert_node(&mut g2, 5);
+    let n4 = insert_node(&mut g2, 4);
+    let n3 = insert_node(&mut g2, 3);
+    let n2 = insert_node(&mut g2, 2);
+    let n1 = insert_node(&mut g2, 1);
+    connect(&mut g2, n4, n2);
+    connect(&mut g2, n4, n5);
+    connect(&mut g2, n2, n3);
+    connect(&mut g2, n1, n2);
+
+    let r1 = g1.collect_garbage(&[node_id(1)]).unwrap();
+    let r2 = g2.collect_garbage(&[node_id(1)]).unwrap();
+
+    assert_eq!(r1, r2, "tombstone report must be deterministic");
+    assert_eq!(g1.compute_hash(), g2.compute_hash());
+}
+
+#[test]
+fn fully_reachable_graph_is_untouched() {
+    let mut g = sample_graph();
+    let before = g.compute_hash();
+
+    let report = g.collect_garbage(&[node_id(1), node_id(4)]).unwrap();
+
+    assert!(report.is_empty());
+    assert_eq!(g.compute_hash(), before);
+}
+
+#[test]
+fn unknown_roots_are_ignored_and_dangling_edges_removed() {
+    let mut g = sample_graph();
+    let ghost = g.nodes.insert(WarpNode {
+        id: node_id(9),
+        node_type: "ghost".to_string(),
+        payload_bytes: vec![],
+        attachment: None,
+    });
+    let root = g.node_key(&node_id(1)).unwrap();
+    connect(&mut g, root, ghost);
+    g.nodes.remove(ghost);
+
+    let report = g.collect_garbage(&[node_id(1), node_id(42)]).unwrap();
+
+    assert_eq!(report.dangling_edges, 1);
+    assert!(g.compute_hash_checked().is_ok(), "no dangling edges remain");
+}
@@ -188,3 +188,100 @@ fn edge_payload_byte_level_sensitivity() {
         "edge payloads must be treated as opaque bytes"
     );
 }
+
+#[test]
+fn memoized_node_encodings_follow_every_kind_of_edit() {
+    // Build the expected digest from scratch each time: a fresh graph holds no
+    // memoized encodings, so it always encodes every node.
+    fn fresh(g: &WarpGraph) -> Hash {
+        let mut copy = WarpGraph::new();
+        copy.nodes = g.nodes.clone();
+        copy.edges = g.edges.clone();
+        copy.compute_hash()
+    }
+
+    let mut g = WarpGraph::new();
+    let a = insert_node(&mut g, node_id(1), "demo.A", br#"{"k":"A"}"#.to_vec());
+    let b = insert_node(&mut g, node_id(2), "demo.B", br#"{"k":"B"}"#.to_vec());
+    let before = g.compute_hash();
+    assert_eq!(g.compute_hash(), before, "re-hashing unchanged nodes");
+
+    // In-place edits through the API.
+    g.set_payload(a, br#"{"k":"A2"}"#.to_vec());
+    let edited = g.compute_hash();
+    assert_ne!(edited, before);
+    assert_eq!(edited, fresh(&g));
+    g.replace_node(
+        b,
+        WarpNode {
+            id: node_id(3),
+            node_type: "demo.B".to_string(),
+            payload_bytes: br#"{"k":"B"}"#.to_vec(),
+            attachment: Some(h(9)),
+        },
+    );
+    assert_eq!(g.compute_hash(), fresh(&g));
+
+    // Direct edits through the public fields, announced by rebuild_indices.
+    g.nodes[b].node_type = "demo.C".to_string();
+    g.rebuild_indices();
+    assert_eq!(g.compute_hash(), fresh(&g));
+
+    // Removal and reinsertion, through the slot map and through the API.
+    g.nodes.remove(a);
+    assert_eq!(g.compute_hash(), fresh(&g));
+    let a = insert_node(&mut g, node_id(1), "demo.A", br#"{"k":"A"}"#.to_vec());
+    assert_eq!(g.compute_hash(), fresh(&g));
+    g.remove_node(a);
+    assert_eq!(g.compute_hash(), fresh(&g));
+
+    // A clone carries the memoized encodings but hashes its own edits.
+    let mut clone = g.clone();
+    clone.set_payload(b, b"changed".to_vec());
+    assert_eq!(clone.compute_hash(), fresh(&clone));
+    assert_ne!(clone.compute_hash(), g.compute_hash());
+}
+
+#[test]
+fn parallel_digest_is_byte_identical_to_sequential() {
+    let mut g = WarpGraph::new();
+    let keys: Vec<_> = (0..2_000u32)
+        .map(|i| {
+            let mut id = [0u8; 32];
+            id[..4].copy_from_slice(&i.wrapping_mul(2_654_435_761).to_be_bytes());
+            insert_node(
+                &mut g,
+                NodeId::from_hash(Hash(id)),
+                ["demo.A", "demo.B", "demo.C"][i as usize % 3],
+                vec![i as u8; i as usize % 64],
+            )
+        })
+        .collect();
+    for i in 1..keys.len() {
+        g.edges.insert(WarpEdge {
+            source: keys[i / 3],
+            target: keys[i],
+            edge_type: "demo.edge".to_string(),
+            payload_bytes: (i % 5 == 0).then(|| vec![i as u8; 3]),
+            attachment: (i % 7 == 0).then_some(h(i as u8)),
+        });
+    }
+
+    let sequential = g.clone().compute_hash();
+    assert_eq!(g.compute_hash_parallel(), sequential, "cold");
+    assert_eq!(g.compute_hash_parallel(), sequential, "warm");
+    assert_eq!(g.compute_hash(), sequential, "warmed by the parallel path");
+
+    g.set_payload(keys[10], b"edited".to_vec());
+    g.remove_node(keys[20]);
+    let edited = g.clone().compute_hash();
+    assert_ne!(edited, sequential);
+    assert_eq!(g.compute_hash_parallel(), edited, "after edits");
+
+    // A dangling edge fails both paths with the same error.
+    g.nodes.remove(keys[1]);
+    assert_eq!(
+        g.compute_hash_parallel_checked().unwrap_err(),
+        g.compute_hash_checked().unwrap_err()
+    );
+}
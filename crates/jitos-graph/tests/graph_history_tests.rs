@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+use jitos_core::{canonical, Hash, Receipt};
+use jitos_graph::{GraphCommit, GraphHistory, HistoryError};
+
+fn h(byte: u8) -> Hash {
+    Hash([byte; 32])
+}
+
+fn three_generations() -> GraphHistory {
+    let mut history = GraphHistory::new(h(0)).expect("genesis");
+    history.record(h(10), [h(2), h(1)]).expect("gen 1");
+    history.record(h(20), [h(3)]).expect("gen 2");
+    history
+}
+
+#[test]
+fn record_links_generations_and_normalizes_slaps() {
+    let history = three_generations();
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.head().generation, 2);
+    assert_eq!(history.head().state_hash, h(20));
+
+    let (_, gen1) = history.find_state(&h(10)).expect("gen 1");
+    assert_eq!(gen1.applied_slaps, vec![h(1), h(2)]);
+
+    let mut reordered = GraphHistory::new(h(0)).expect("genesis");
+    let id = reordered.record(h(10), [h(1), h(2), h(1)]).expect("gen 1");
+    assert_eq!(Some(id), history.commits().nth(1).map(|(id, _)| id));
+}
+
+#[test]
+fn ancestry_walks_back_to_genesis() {
+    let history = three_generations();
+    let generations: Vec<u64> = history
+        .ancestry(&history.head_id())
+        .map(|(_, c)| c.generation)
+        .collect();
+    assert_eq!(generations, vec![2, 1, 0]);
+    assert_eq!(history.ancestry(&h(99)).count(), 0);
+}
+
+#[test]
+fn receipts_resolve_to_their_commit() {
+    let history = three_generations();
+    let receipt = Receipt {
+        tick: 1,
+        state_hash: h(10),
+        applied_slaps: vec![h(2), h(1)],
+        timestamp: 0,
+        signature: None,
+        view_hashes: BTreeMap::new(),
+        prev_receipt: None,
+        fuel: None,
+    };
+    let (id, commit) = history.commit_for_receipt(&receipt).expect("commit");
+    assert_eq!(commit.generation, 1);
+    assert_eq!(history.get(&id), Some(commit));
+
+    let wrong = Receipt {
+        applied_slaps: vec![h(1)],
+        ..receipt
+    };
+    assert!(history.commit_for_receipt(&wrong).is_none());
+}
+
+#[test]
+fn canonical_round_trip_preserves_chain() {
+    let history = three_generations();
+    let bytes = canonical::encode(&history).expect("encode");
+    let decoded: GraphHistory = canonical::decode(&bytes).expect("decode");
+    assert_eq!(decoded, history);
+    assert_eq!(decoded.head_id(), history.head_id());
+}
+
+#[test]
+fn tampered_chains_are_rejected() {
+    let history = three_generations();
+    let mut commits: Vec<GraphCommit> = history.commits().map(|(_, c)| c.clone()).collect();
+
+    let mut broken = commits.clone();
+    broken[2].parent = Some(h(7));
+    assert!(matches!(
+        GraphHistory::try_from(broken),
+        Err(HistoryError::BrokenLink { generation: 2, .. })
+    ));
+
+    commits[1].applied_slaps.reverse();
+    assert_eq!(
+        GraphHistory::try_from(commits),
+        Err(HistoryError::UnsortedSlaps(1))
+    );
+
+    let bytes = canonical::encode(&Vec::<GraphCommit>::new()).expect("encode");
+    assert!(canonical::decode::<GraphHistory>(&bytes).is_err());
+}
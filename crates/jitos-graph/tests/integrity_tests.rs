@@ -0,0 +1,108 @@
+use jitos_core::Hash;
+use jitos_graph::integrity::{AttachmentOwner, Endpoint};
+use jitos_graph::{GraphError, NodeId, NodeKey, WarpEdge, WarpGraph, WarpNode};
+use std::collections::BTreeSet;
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn insert_node(graph: &mut WarpGraph, byte: u8, attachment: Option<Hash>) -> NodeKey {
+    graph.insert_node(WarpNode {
+        id: node_id(byte),
+        node_type: "demo".to_string(),
+        payload_bytes: vec![],
+        attachment,
+    })
+}
+
+fn connect(graph: &mut WarpGraph, source: NodeKey, target: NodeKey, attachment: Option<Hash>) {
+    graph.edges.insert(WarpEdge {
+        source,
+        target,
+        edge_type: "demo.edge".to_string(),
+        payload_bytes: None,
+        attachment,
+    });
+}
+
+#[test]
+fn well_formed_graph_passes() {
+    let mut g = WarpGraph::new();
+    let a = insert_node(&mut g, 1, None);
+    let b = insert_node(&mut g, 2, None);
+    connect(&mut g, a, b, None);
+
+    assert_eq!(g.check_integrity(), Ok(()));
+    assert_eq!(g.check_integrity_with(&BTreeSet::new()), Ok(()));
+}
+
+#[test]
+fn reports_every_dangling_endpoint() {
+    let mut g = WarpGraph::new();
+    let a = insert_node(&mut g, 1, None);
+    let b = insert_node(&mut g, 2, None);
+    connect(&mut g, a, b, None);
+    connect(&mut g, b, b, None);
+    g.nodes.remove(b);
+
+    let errors = g.check_integrity().unwrap_err();
+    let endpoints: Vec<Endpoint> = errors
+        .iter()
+        .map(|e| match e {
+            GraphError::DanglingEdge { endpoint, .. } => *endpoint,
+            other => panic!("unexpected error {other:?}"),
+        })
+        .collect();
+    assert_eq!(endpoints.len(), 3, "a->b target, b->b source and target");
+    assert!(endpoints.contains(&Endpoint::Source));
+    assert!(endpoints.contains(&Endpoint::Target));
+}
+
+#[test]
+fn reports_duplicate_node_ids() {
+    let mut g = WarpGraph::new();
+    insert_node(&mut g, 1, None);
+    insert_node(&mut g, 1, None);
+    insert_node(&mut g, 2, None);
+
+    assert_eq!(
+        g.check_integrity(),
+        Err(vec![GraphError::DuplicateNodeId {
+            id: node_id(1),
+            slots: 2
+        }])
+    );
+}
+
+#[test]
+fn reports_unresolved_attachments_on_nodes_and_edges() {
+    let known = Hash([0xAA; 32]);
+    let unknown = Hash([0xBB; 32]);
+
+    let mut g = WarpGraph::new();
+    let a = insert_node(&mut g, 1, Some(known));
+    let b = insert_node(&mut g, 2, Some(unknown));
+    connect(&mut g, a, b, Some(unknown));
+
+    assert_eq!(
+        g.check_integrity(),
+        Ok(()),
+        "structural check ignores attachments"
+    );
+
+    let resolver: BTreeSet<Hash> = [known].into_iter().collect();
+    let errors = g.check_integrity_with(&resolver).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.contains(&GraphError::UnresolvedAttachment {
+        owner: AttachmentOwner::Node(node_id(2)),
+        attachment: unknown,
+    }));
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        GraphError::UnresolvedAttachment {
+            owner: AttachmentOwner::Edge(_),
+            ..
+        }
+    )));
+}
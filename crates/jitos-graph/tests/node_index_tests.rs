@@ -0,0 +1,89 @@
+use jitos_core::{canonical, Hash};
+use jitos_graph::{NodeId, NodeKey, WarpEdge, WarpGraph, WarpNode};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn node(id: NodeId, node_type: &str) -> WarpNode {
+    WarpNode {
+        id,
+        node_type: node_type.to_string(),
+        payload_bytes: vec![],
+        attachment: None,
+    }
+}
+
+fn ids_of_type(graph: &WarpGraph, node_type: &str) -> Vec<NodeId> {
+    graph.nodes_of_type(node_type).map(|(_, n)| n.id).collect()
+}
+
+#[test]
+fn nodes_of_type_is_sorted_by_node_id_regardless_of_insertion_order() {
+    let mut g = WarpGraph::new();
+    g.insert_node(node(node_id(3), "timer"));
+    g.insert_node(node(node_id(1), "timer"));
+    g.insert_node(node(node_id(2), "task"));
+    g.insert_node(node(node_id(0), "timer"));
+
+    assert_eq!(
+        ids_of_type(&g, "timer"),
+        vec![node_id(0), node_id(1), node_id(3)]
+    );
+    assert_eq!(ids_of_type(&g, "task"), vec![node_id(2)]);
+    assert!(ids_of_type(&g, "missing").is_empty());
+    assert_eq!(g.nodes[g.node_key(&node_id(2)).unwrap()].id, node_id(2));
+}
+
+#[test]
+fn remove_node_updates_index_and_drops_incident_edges() {
+    let mut g = WarpGraph::new();
+    let a: NodeKey = g.insert_node(node(node_id(1), "timer"));
+    let b = g.insert_node(node(node_id(2), "task"));
+    g.edges.insert(WarpEdge {
+        source: b,
+        target: a,
+        edge_type: "waits_on".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    });
+
+    let removed = g.remove_node(a).expect("node exists");
+    assert_eq!(removed.id, node_id(1));
+    assert!(ids_of_type(&g, "timer").is_empty());
+    assert_eq!(g.node_key(&node_id(1)), None);
+    assert!(g.edges.is_empty());
+    assert!(g.remove_node(a).is_none(), "double remove is a no-op");
+}
+
+#[test]
+fn rebuild_indices_covers_direct_slotmap_edits() {
+    let mut g = WarpGraph::new();
+    let key = g.nodes.insert(node(node_id(1), "timer"));
+    assert!(
+        ids_of_type(&g, "timer").is_empty(),
+        "direct edits bypass the index"
+    );
+    assert_eq!(g.node_key(&node_id(1)), None);
+
+    g.rebuild_indices();
+    assert_eq!(ids_of_type(&g, "timer"), vec![node_id(1)]);
+    assert_eq!(g.node_key(&node_id(1)), Some(key));
+
+    g.nodes.remove(key);
+    assert_eq!(g.node_key(&node_id(1)), None, "stale entries never resolve");
+}
+
+#[test]
+fn index_is_rebuilt_on_deserialize_and_not_hashed() {
+    let mut g = WarpGraph::new();
+    g.insert_node(node(node_id(2), "timer"));
+    g.insert_node(node(node_id(1), "timer"));
+
+    let bytes = canonical::encode(&g).expect("encode graph");
+    let decoded: WarpGraph = canonical::decode(&bytes).expect("decode graph");
+
+    assert_eq!(ids_of_type(&decoded, "timer"), vec![node_id(1), node_id(2)]);
+    assert!(decoded.node_key(&node_id(1)).is_some());
+    assert_eq!(decoded.compute_hash(), g.compute_hash());
+}
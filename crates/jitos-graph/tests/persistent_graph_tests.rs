@@ -0,0 +1,124 @@
+use jitos_core::Hash;
+use jitos_graph::{
+    NodeId, PersistentEdge, PersistentGraph, PersistentGraphError, WarpEdge, WarpGraph, WarpNode,
+};
+
+fn node(byte: u8, node_type: &str) -> WarpNode {
+    WarpNode {
+        id: NodeId::from_hash(Hash([byte; 32])),
+        node_type: node_type.to_string(),
+        payload_bytes: vec![byte],
+        attachment: None,
+    }
+}
+
+fn sample_graph() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    let a = g.insert_node(node(1, "task"));
+    let b = g.insert_node(node(2, "task"));
+    let c = g.insert_node(node(3, "receipt"));
+    g.edges.insert(WarpEdge {
+        source: a,
+        target: b,
+        edge_type: "depends_on".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    });
+    g.edges.insert(WarpEdge {
+        source: b,
+        target: c,
+        edge_type: "produced".to_string(),
+        payload_bytes: Some(b"ok".to_vec()),
+        attachment: Some(Hash([9; 32])),
+    });
+    g
+}
+
+#[test]
+fn conversion_preserves_commit_digest_both_ways() {
+    let g = sample_graph();
+    let p = PersistentGraph::from_graph(&g).expect("convert");
+
+    assert_eq!(p.node_count(), 3);
+    assert_eq!(p.edge_count(), 2);
+    assert_eq!(p.compute_hash(), g.compute_hash());
+    assert_eq!(p.to_graph().compute_hash(), g.compute_hash());
+}
+
+#[test]
+fn fork_edits_do_not_affect_the_base() {
+    let base = PersistentGraph::from_graph(&sample_graph()).expect("convert");
+    let base_hash = base.compute_hash();
+
+    let mut fork = base.clone();
+    fork.remove_node(&NodeId::from_hash(Hash([2; 32])));
+    fork.insert_node(node(4, "task"));
+
+    assert_eq!(base.compute_hash(), base_hash);
+    assert_eq!(base.edge_count(), 2);
+    assert_eq!(fork.node_count(), 3);
+    assert_eq!(fork.edge_count(), 0, "incident edges removed with the node");
+    assert_ne!(fork.compute_hash(), base_hash);
+}
+
+#[test]
+fn same_content_built_in_different_orders_has_same_digest() {
+    let ids = [1u8, 2, 3];
+    let edge = |from: u8, to: u8| PersistentEdge {
+        from: NodeId::from_hash(Hash([from; 32])),
+        to: NodeId::from_hash(Hash([to; 32])),
+        edge_type: "link".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    };
+
+    let mut p1 = PersistentGraph::new();
+    for id in ids {
+        p1.insert_node(node(id, "task"));
+    }
+    p1.insert_edge(edge(1, 2)).expect("edge");
+    p1.insert_edge(edge(2, 3)).expect("edge");
+
+    let mut p2 = PersistentGraph::new();
+    for id in ids.iter().rev() {
+        p2.insert_node(node(*id, "task"));
+    }
+    p2.insert_edge(edge(2, 3)).expect("edge");
+    p2.insert_edge(edge(1, 2)).expect("edge");
+
+    assert_eq!(p1, p2);
+    assert_eq!(p1.compute_hash(), p2.compute_hash());
+    assert_eq!(
+        p1.insert_edge(edge(1, 9)).unwrap_err(),
+        PersistentGraphError::MissingEndpoint(NodeId::from_hash(Hash([9; 32])))
+    );
+}
+
+#[test]
+fn invalid_mutable_graphs_are_rejected() {
+    let mut dangling = sample_graph();
+    let key = dangling
+        .node_key(&NodeId::from_hash(Hash([3; 32])))
+        .expect("key");
+    dangling.nodes.remove(key);
+    assert!(matches!(
+        PersistentGraph::from_graph(&dangling),
+        Err(PersistentGraphError::Integrity(_))
+    ));
+
+    let mut parallel = WarpGraph::new();
+    let a = parallel.insert_node(node(1, "task"));
+    for _ in 0..2 {
+        parallel.edges.insert(WarpEdge {
+            source: a,
+            target: a,
+            edge_type: "self".to_string(),
+            payload_bytes: None,
+            attachment: None,
+        });
+    }
+    assert!(matches!(
+        PersistentGraph::from_graph(&parallel),
+        Err(PersistentGraphError::DuplicateEdge(_))
+    ));
+}
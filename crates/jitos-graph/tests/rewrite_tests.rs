@@ -0,0 +1,200 @@
+use jitos_core::{canonical, Hash};
+use jitos_graph::rewrite::{
+    apply_rules, GraphChange, Match, NodeRef, Pattern, RewriteError, RewriteLog, RewriteOp,
+    RewriteRule,
+};
+use jitos_graph::{DeterministicIdAllocator, NodeId, NodeKey, WarpEdge, WarpGraph, WarpNode};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn insert_node(graph: &mut WarpGraph, id: NodeId, node_type: &str) -> NodeKey {
+    graph.insert_node(WarpNode {
+        id,
+        node_type: node_type.to_string(),
+        payload_bytes: vec![],
+        attachment: None,
+    })
+}
+
+fn allocator() -> DeterministicIdAllocator {
+    DeterministicIdAllocator::new_for_tick(&[Hash([9u8; 32])])
+}
+
+/// For every `task` node: create a `receipt` node and link task -> receipt.
+fn spawn_receipt(_g: &WarpGraph, m: &Match) -> Vec<RewriteOp> {
+    vec![
+        RewriteOp::AddNode {
+            node_type: "receipt".to_string(),
+            payload_bytes: b"ok".to_vec(),
+        },
+        RewriteOp::AddEdge {
+            from: NodeRef::Existing(m.nodes[0]),
+            to: NodeRef::Created(0),
+            edge_type: "produced".to_string(),
+            payload_bytes: None,
+        },
+    ]
+}
+
+fn spawn_rule() -> RewriteRule {
+    RewriteRule {
+        name: "spawn-receipt".to_string(),
+        pattern: Pattern::Node {
+            node_type: "task".to_string(),
+        },
+        guard: None,
+        producer: spawn_receipt,
+    }
+}
+
+#[test]
+fn rule_applies_to_every_match_in_node_id_order() {
+    let mut g = WarpGraph::new();
+    insert_node(&mut g, node_id(2), "task");
+    insert_node(&mut g, node_id(1), "task");
+    insert_node(&mut g, node_id(3), "other");
+
+    let log = apply_rules(&mut g, &[spawn_rule()], &mut allocator()).expect("apply");
+
+    assert_eq!(log.applied.len(), 2);
+    assert_eq!(log.applied[0].matched, vec![node_id(1)]);
+    assert_eq!(log.applied[1].matched, vec![node_id(2)]);
+    assert_eq!(g.nodes.len(), 5);
+    assert_eq!(g.edges.len(), 2);
+    assert!(matches!(
+        log.applied[0].changes[0],
+        GraphChange::NodeAdded { ref node_type, .. } if node_type == "receipt"
+    ));
+}
+
+#[test]
+fn rewrite_result_is_independent_of_insertion_order() {
+    let mut g1 = WarpGraph::new();
+    insert_node(&mut g1, node_id(1), "task");
+    insert_node(&mut g1, node_id(2), "task");
+
+    let mut g2 = WarpGraph::new();
+    insert_node(&mut g2, node_id(2), "task");
+    insert_node(&mut g2, node_id(1), "task");
+
+    let log1 = apply_rules(&mut g1, &[spawn_rule()], &mut allocator()).expect("apply");
+    let log2 = apply_rules(&mut g2, &[spawn_rule()], &mut allocator()).expect("apply");
+
+    assert_eq!(log1, log2, "change logs must be identical");
+    assert_eq!(g1.compute_hash(), g2.compute_hash());
+}
+
+#[test]
+fn guard_rejection_skips_match() {
+    fn only_first(_g: &WarpGraph, m: &Match) -> bool {
+        m.nodes[0] == NodeId::from_hash(Hash([1u8; 32]))
+    }
+
+    let mut g = WarpGraph::new();
+    insert_node(&mut g, node_id(1), "task");
+    insert_node(&mut g, node_id(2), "task");
+
+    let rule = RewriteRule {
+        guard: Some(only_first),
+        ..spawn_rule()
+    };
+    let log = apply_rules(&mut g, &[rule], &mut allocator()).expect("apply");
+
+    assert_eq!(log.applied.len(), 1);
+    assert_eq!(log.applied[0].matched, vec![node_id(1)]);
+}
+
+#[test]
+fn edge_pattern_binds_both_endpoints_and_delete_removes_incident_edges() {
+    fn delete_target(_g: &WarpGraph, m: &Match) -> Vec<RewriteOp> {
+        vec![RewriteOp::DeleteNode {
+            node: NodeRef::Existing(m.nodes[1]),
+        }]
+    }
+
+    let mut g = WarpGraph::new();
+    let a = insert_node(&mut g, node_id(1), "parent");
+    let b = insert_node(&mut g, node_id(2), "child");
+    g.edges.insert(WarpEdge {
+        source: a,
+        target: b,
+        edge_type: "owns".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    });
+
+    let rule = RewriteRule {
+        name: "prune-children".to_string(),
+        pattern: Pattern::Edge {
+            from_type: "parent".to_string(),
+            edge_type: "owns".to_string(),
+            to_type: "child".to_string(),
+        },
+        guard: None,
+        producer: delete_target,
+    };
+    let log = apply_rules(&mut g, &[rule], &mut allocator()).expect("apply");
+
+    assert_eq!(log.applied[0].matched, vec![node_id(1), node_id(2)]);
+    assert_eq!(g.nodes.len(), 1);
+    assert!(g.edges.is_empty(), "incident edges must be removed");
+    assert!(g.compute_hash_checked().is_ok());
+}
+
+#[test]
+fn matches_invalidated_by_earlier_rewrites_are_skipped() {
+    fn delete_self(_g: &WarpGraph, m: &Match) -> Vec<RewriteOp> {
+        vec![RewriteOp::DeleteNode {
+            node: NodeRef::Existing(m.nodes[0]),
+        }]
+    }
+
+    let mut g = WarpGraph::new();
+    insert_node(&mut g, node_id(1), "task");
+
+    let delete = RewriteRule {
+        name: "delete-task".to_string(),
+        pattern: Pattern::Node {
+            node_type: "task".to_string(),
+        },
+        guard: None,
+        producer: delete_self,
+    };
+    let log = apply_rules(&mut g, &[delete, spawn_rule()], &mut allocator()).expect("apply");
+
+    assert_eq!(log.applied.len(), 1, "second rule's match was deleted");
+    assert!(g.nodes.is_empty());
+}
+
+#[test]
+fn producer_referencing_missing_created_node_is_an_error() {
+    fn bad(_g: &WarpGraph, m: &Match) -> Vec<RewriteOp> {
+        vec![RewriteOp::SetPayload {
+            node: NodeRef::Created(0),
+            payload_bytes: m.nodes[0].hash().0.to_vec(),
+        }]
+    }
+
+    let mut g = WarpGraph::new();
+    insert_node(&mut g, node_id(1), "task");
+    let rule = RewriteRule {
+        producer: bad,
+        ..spawn_rule()
+    };
+
+    let err = apply_rules(&mut g, &[rule], &mut allocator()).unwrap_err();
+    assert!(matches!(err, RewriteError::UnknownCreated { index: 0, .. }));
+}
+
+#[test]
+fn rewrite_log_round_trips_through_canonical_encoding() {
+    let mut g = WarpGraph::new();
+    insert_node(&mut g, node_id(1), "task");
+    let log = apply_rules(&mut g, &[spawn_rule()], &mut allocator()).expect("apply");
+
+    let bytes = canonical::encode(&log).expect("encode");
+    let decoded: RewriteLog = canonical::decode(&bytes).expect("decode");
+    assert_eq!(decoded, log);
+}
@@ -0,0 +1,100 @@
+use jitos_core::Hash;
+use jitos_graph::snapshot::{
+    encode_snapshot, FileSnapshotStore, MemorySnapshotStore, SnapshotError, SnapshotStore,
+};
+use jitos_graph::{NodeId, WarpEdge, WarpGraph, WarpNode};
+
+fn node(byte: u8) -> WarpNode {
+    WarpNode {
+        id: NodeId::from_hash(Hash([byte; 32])),
+        node_type: "task".to_string(),
+        payload_bytes: vec![byte; 3],
+        attachment: None,
+    }
+}
+
+fn graph(order: &[u8]) -> WarpGraph {
+    let mut g = WarpGraph::new();
+    for b in order {
+        g.insert_node(node(*b));
+    }
+    let a = g.node_key(&node(1).id).unwrap();
+    let b = g.node_key(&node(2).id).unwrap();
+    g.edges.insert(WarpEdge {
+        source: a,
+        target: b,
+        edge_type: "next".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    });
+    g
+}
+
+fn exercise(store: &mut impl SnapshotStore) {
+    let g = graph(&[1, 2, 3]);
+    let hash = store.save(&g).expect("save");
+    assert_eq!(hash, g.compute_hash());
+    assert!(store.contains(&hash));
+
+    let loaded = store.load(&hash).expect("load");
+    assert_eq!(loaded.compute_hash(), hash);
+    assert_eq!(loaded.nodes.len(), 3);
+    assert_eq!(loaded.nodes_of_type("task").count(), 3, "indices rebuilt");
+
+    // Same content, different slot layout: same snapshot.
+    assert_eq!(store.save(&graph(&[3, 2, 1])).expect("save"), hash);
+
+    let missing = Hash([0xee; 32]);
+    assert!(!store.contains(&missing));
+    assert!(matches!(
+        store.load(&missing),
+        Err(SnapshotError::NotFound(h)) if h == missing
+    ));
+}
+
+#[test]
+fn memory_store_saves_and_loads_by_digest() {
+    exercise(&mut MemorySnapshotStore::new());
+}
+
+#[test]
+fn file_store_saves_and_loads_by_digest() {
+    let dir = std::env::temp_dir().join(format!("jitos-snapshots-{}", std::process::id()));
+    let mut store = FileSnapshotStore::open(&dir).expect("open");
+    exercise(&mut store);
+
+    // Corrupt the file on disk: load must refuse it.
+    let hash = graph(&[1, 2, 3]).compute_hash();
+    let (_, other) = encode_snapshot(&graph(&[1, 2])).expect("encode");
+    std::fs::write(dir.join(format!("{hash}.cbor")), other).expect("overwrite");
+    let err = store.load(&hash).unwrap_err();
+    std::fs::remove_dir_all(&dir).ok();
+    assert!(matches!(err, SnapshotError::DigestMismatch { expected, .. } if expected == hash));
+}
+
+#[test]
+fn snapshot_bytes_depend_only_on_content() {
+    let (h1, b1) = encode_snapshot(&graph(&[1, 2, 3])).expect("encode");
+    let (h2, b2) = encode_snapshot(&graph(&[2, 3, 1])).expect("encode");
+    assert_eq!(h1, h2);
+    assert_eq!(b1, b2);
+
+    let mut store = MemorySnapshotStore::new();
+    assert!(matches!(
+        store.insert_bytes(Hash([1; 32]), b1.clone()),
+        Err(SnapshotError::DigestMismatch { .. })
+    ));
+    store.insert_bytes(h1, b1).expect("verified insert");
+    assert_eq!(store.hashes().collect::<Vec<_>>(), vec![&h1]);
+}
+
+#[test]
+fn invalid_graphs_cannot_be_snapshotted() {
+    let mut g = graph(&[1, 2]);
+    let key = g.node_key(&node(2).id).unwrap();
+    g.nodes.remove(key); // leaves a dangling edge
+    assert!(matches!(
+        MemorySnapshotStore::new().save(&g),
+        Err(SnapshotError::Graph(_))
+    ));
+}
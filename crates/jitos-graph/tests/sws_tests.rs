@@ -0,0 +1,165 @@
+use jitos_core::Hash;
+use jitos_graph::sws::{ConflictCause, SwsConflict};
+use jitos_graph::{NodeId, PersistentEdge, SwsError, SwsRegistry, WarpEdge, WarpGraph, WarpNode};
+
+fn id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn node(byte: u8) -> WarpNode {
+    WarpNode {
+        id: id(byte),
+        node_type: "task".to_string(),
+        payload_bytes: vec![byte],
+        attachment: None,
+    }
+}
+
+fn edge(from: u8, to: u8) -> PersistentEdge {
+    PersistentEdge {
+        from: id(from),
+        to: id(to),
+        edge_type: "next".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    }
+}
+
+fn system() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    g.insert_node(node(1));
+    g.insert_node(node(2));
+    g
+}
+
+#[test]
+fn mutations_stay_in_the_overlay_until_collapse() {
+    let mut system = system();
+    let before = system.compute_hash();
+    let mut registry = SwsRegistry::new();
+
+    let sws = registry.fork("sws-a", &system).unwrap();
+    sws.add_node(node(3)).unwrap();
+    sws.add_edge(edge(1, 3)).unwrap();
+    sws.set_payload(id(2), vec![42]).unwrap();
+    assert_eq!(sws.view().node_count(), 3);
+    assert_eq!(sws.base_digest(), before);
+    assert_eq!(system.compute_hash(), before, "System is untouched");
+    let view_digest = sws.view().compute_hash();
+
+    let receipt = registry.collapse(&"sws-a".into(), &mut system).unwrap();
+    assert_eq!(receipt.before, before);
+    assert_eq!(receipt.after, system.compute_hash());
+    assert_eq!(receipt.after, view_digest);
+    assert_eq!(receipt.ops, 3);
+    assert!(registry.is_empty(), "collapsed SWS is retired");
+}
+
+#[test]
+fn digest_covers_base_and_ops() {
+    let system = system();
+    let mut registry = SwsRegistry::new();
+    let a = registry.fork("a", &system).unwrap();
+    let empty = a.digest().unwrap();
+    a.add_node(node(3)).unwrap();
+    let digest = a.digest().unwrap();
+    assert_ne!(digest, empty);
+
+    let mut again = SwsRegistry::new();
+    let b = again.fork("a", &system).unwrap();
+    b.add_node(node(3)).unwrap();
+    assert_eq!(b.digest().unwrap(), digest);
+}
+
+#[test]
+fn recording_is_checked_against_the_view() {
+    let system = system();
+    let mut registry = SwsRegistry::new();
+    let sws = registry.fork("a", &system).unwrap();
+    assert_eq!(sws.add_node(node(1)), Err(SwsError::NodeExists(id(1))));
+    assert_eq!(sws.remove_node(id(9)), Err(SwsError::UnknownNode(id(9))));
+    assert!(sws.add_edge(edge(1, 9)).is_err());
+    assert!(sws.ops().is_empty());
+
+    assert!(matches!(
+        registry.fork("a", &system),
+        Err(SwsError::SwsExists(_))
+    ));
+    let mut g = WarpGraph::new();
+    assert_eq!(
+        registry.collapse(&"missing".into(), &mut g),
+        Err(SwsError::UnknownSws("missing".into()))
+    );
+}
+
+#[test]
+fn concurrent_system_changes_reject_the_collapse_atomically() {
+    let mut system = system();
+    let mut registry = SwsRegistry::new();
+    let sws = registry.fork("a", &system).unwrap();
+    sws.set_payload(id(1), vec![7]).unwrap();
+    sws.add_node(node(3)).unwrap();
+    sws.remove_node(id(2)).unwrap();
+
+    // System moves on: node 1 is edited, node 3 is created independently and
+    // node 2 gains an edge.
+    let k1 = system.node_key(&id(1)).unwrap();
+    system.set_payload(k1, vec![99]);
+    let k3 = system.insert_node(node(3));
+    let k2 = system.node_key(&id(2)).unwrap();
+    system.edges.insert(WarpEdge {
+        source: k3,
+        target: k2,
+        edge_type: "next".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    });
+    let moved = system.compute_hash();
+
+    let Err(SwsError::Conflict(report)) = registry.collapse(&"a".into(), &mut system) else {
+        panic!("collapse must be rejected");
+    };
+    assert_eq!(report.system, moved);
+    assert_eq!(
+        report.conflicts,
+        vec![
+            SwsConflict {
+                node: id(1),
+                cause: ConflictCause::Modified
+            },
+            SwsConflict {
+                node: id(2),
+                cause: ConflictCause::EdgesChanged
+            },
+            SwsConflict {
+                node: id(3),
+                cause: ConflictCause::Added
+            },
+        ]
+    );
+    assert_eq!(system.compute_hash(), moved, "System is unchanged");
+    assert!(
+        registry.get(&"a".into()).is_some(),
+        "rejected SWS can be inspected"
+    );
+    assert!(registry.discard(&"a".into()).is_some());
+}
+
+#[test]
+fn unrelated_system_changes_do_not_conflict() {
+    let mut system = system();
+    let mut registry = SwsRegistry::new();
+    registry
+        .fork("a", &system)
+        .unwrap()
+        .set_payload(id(1), vec![7])
+        .unwrap();
+
+    let k2 = system.node_key(&id(2)).unwrap();
+    system.set_payload(k2, vec![8]);
+    let receipt = registry.collapse(&"a".into(), &mut system).unwrap();
+    assert_ne!(receipt.before, receipt.after);
+    let k1 = system.node_key(&id(1)).unwrap();
+    assert_eq!(system.nodes[k1].payload_bytes, vec![7]);
+    assert_eq!(system.nodes[k2].payload_bytes, vec![8]);
+}
@@ -0,0 +1,147 @@
+use jitos_core::Hash;
+use jitos_graph::{
+    GraphTxn, NodeId, PersistentEdge, PersistentGraph, TouchedEdge, TxnError, WarpEdge, WarpGraph,
+    WarpNode,
+};
+
+fn id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn node(byte: u8) -> WarpNode {
+    WarpNode {
+        id: id(byte),
+        node_type: "task".to_string(),
+        payload_bytes: vec![byte],
+        attachment: None,
+    }
+}
+
+fn edge(from: u8, to: u8) -> PersistentEdge {
+    PersistentEdge {
+        from: id(from),
+        to: id(to),
+        edge_type: "next".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    }
+}
+
+fn touched(from: u8, to: u8) -> TouchedEdge {
+    TouchedEdge {
+        from: id(from),
+        to: id(to),
+        edge_type: "next".to_string(),
+    }
+}
+
+fn graph() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    let a = g.insert_node(node(1));
+    let b = g.insert_node(node(2));
+    g.edges.insert(WarpEdge {
+        source: a,
+        target: b,
+        edge_type: "next".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    });
+    g
+}
+
+#[test]
+fn abort_leaves_the_base_untouched() {
+    let mut g = graph();
+    let before = g.compute_hash();
+    let mut txn = GraphTxn::begin(&mut g);
+    txn.insert_node(node(3)).unwrap();
+    txn.remove_node(id(1)).unwrap();
+    assert!(txn.node(&id(1)).is_none(), "reads see buffered writes");
+    assert!(txn.is_dirty());
+    txn.abort();
+    assert_eq!(g.compute_hash(), before);
+}
+
+#[test]
+fn commit_matches_applying_the_same_edits_directly() {
+    let mut g = graph();
+    let mut txn = GraphTxn::begin(&mut g);
+    txn.insert_node(node(3)).unwrap();
+    txn.add_edge(edge(2, 3)).unwrap();
+    txn.set_payload(id(2), vec![9]).unwrap();
+    txn.remove_node(id(1)).unwrap();
+    txn.commit();
+
+    let mut expected = PersistentGraph::from_graph(&graph()).unwrap();
+    expected.insert_node(node(3));
+    expected.insert_edge(edge(2, 3)).unwrap();
+    let mut two = node(2);
+    two.payload_bytes = vec![9];
+    expected.insert_node(two);
+    expected.remove_node(&id(1));
+
+    assert_eq!(g.compute_hash(), expected.compute_hash());
+    assert!(g.check_integrity().is_ok());
+}
+
+#[test]
+fn footprint_records_what_was_touched() {
+    let mut g = graph();
+    let mut txn = GraphTxn::begin(&mut g);
+    txn.node(&id(2));
+    txn.edges_of(id(2)).unwrap();
+    txn.remove_node(id(1)).unwrap();
+    txn.insert_node(node(3)).unwrap();
+    txn.add_edge(edge(2, 3)).unwrap();
+    let fp = txn.commit();
+
+    assert_eq!(
+        fp.nodes_read.iter().copied().collect::<Vec<_>>(),
+        vec![id(2), id(3)]
+    );
+    assert_eq!(
+        fp.nodes_written.iter().copied().collect::<Vec<_>>(),
+        vec![id(1), id(3)]
+    );
+    assert_eq!(
+        fp.edges_read.iter().cloned().collect::<Vec<_>>(),
+        vec![touched(1, 2)]
+    );
+    assert_eq!(
+        fp.edges_written.iter().cloned().collect::<Vec<_>>(),
+        vec![touched(1, 2), touched(2, 3)]
+    );
+}
+
+#[test]
+fn failed_operations_buffer_nothing() {
+    let mut g = graph();
+    let mut txn = GraphTxn::begin(&mut g);
+    assert_eq!(txn.insert_node(node(1)), Err(TxnError::NodeExists(id(1))));
+    assert_eq!(
+        txn.set_payload(id(9), vec![]),
+        Err(TxnError::UnknownNode(id(9)))
+    );
+    assert_eq!(txn.add_edge(edge(1, 9)), Err(TxnError::UnknownNode(id(9))));
+    let missing = edge(2, 1).edge_id().unwrap();
+    assert_eq!(
+        txn.remove_edge(&missing),
+        Err(TxnError::UnknownEdge(missing))
+    );
+    assert!(!txn.is_dirty());
+}
+
+#[test]
+fn edges_removed_and_restored_round_trip() {
+    let mut g = graph();
+    let before = g.compute_hash();
+    let existing = edge(1, 2).edge_id().unwrap();
+    let mut txn = GraphTxn::begin(&mut g);
+    txn.remove_edge(&existing).unwrap();
+    assert!(txn.edges_of(id(1)).unwrap().is_empty());
+    txn.add_edge(edge(1, 2)).unwrap();
+    assert!(!txn.is_dirty());
+    txn.commit();
+    assert_eq!(g.compute_hash(), before);
+    assert_eq!(g.edges.len(), 1);
+}
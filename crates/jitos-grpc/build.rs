@@ -0,0 +1,13 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates the Loom protobuf messages and tonic service from
+//! `proto/loom/v0/loom.proto`, with a vendored protoc so builds need no
+//! system install.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    println!("cargo:rerun-if-changed=proto");
+    tonic_prost_build::configure().compile_protos(&["proto/loom/v0/loom.proto"], &["proto"])?;
+    Ok(())
+}
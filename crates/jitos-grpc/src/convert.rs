@@ -0,0 +1,275 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversions - Core Types to and from Protobuf
+//!
+//! Outgoing conversions are infallible. Incoming ones check everything a
+//! decoded core value would be checked for: an event goes through
+//! [`EventEnvelope`]'s validating decoder (id recomputed from the payload
+//! bytes as received, canonical payload and parents, signed commits), and a
+//! delta's hash is recomputed and compared. Payload bytes are moved, never
+//! re-encoded, so an event keeps its id across the trip.
+
+use std::collections::BTreeMap;
+
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::delta::{DeltaError, DeltaKind, DeltaSpec, InputEvent};
+use jitos_core::events::{AgentId, EventEnvelope, EventError, EventKind};
+use jitos_core::{FuelUsage, Hash, Receipt};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::proto;
+
+/// A hash from 32 raw bytes; `field` names it in the error
+pub fn hash_from(bytes: &[u8], field: &'static str) -> Result<Hash, ConvertError> {
+    let hash: [u8; 32] = bytes.try_into().map_err(|_| ConvertError::HashLength {
+        field,
+        len: bytes.len(),
+    })?;
+    Ok(Hash(hash))
+}
+
+fn hashes_from(bytes: &[Vec<u8>], field: &'static str) -> Result<Vec<Hash>, ConvertError> {
+    bytes.iter().map(|b| hash_from(b, field)).collect()
+}
+
+fn hash_bytes(hash: &Hash) -> Vec<u8> {
+    hash.0.to_vec()
+}
+
+impl From<&EventKind> for proto::EventKind {
+    fn from(kind: &EventKind) -> Self {
+        match kind {
+            EventKind::Observation => proto::EventKind::Observation,
+            EventKind::PolicyContext => proto::EventKind::PolicyContext,
+            EventKind::Decision => proto::EventKind::Decision,
+            EventKind::Commit => proto::EventKind::Commit,
+        }
+    }
+}
+
+impl TryFrom<proto::EventKind> for EventKind {
+    type Error = ConvertError;
+
+    fn try_from(kind: proto::EventKind) -> Result<Self, ConvertError> {
+        match kind {
+            proto::EventKind::Observation => Ok(EventKind::Observation),
+            proto::EventKind::PolicyContext => Ok(EventKind::PolicyContext),
+            proto::EventKind::Decision => Ok(EventKind::Decision),
+            proto::EventKind::Commit => Ok(EventKind::Commit),
+            proto::EventKind::Unspecified => Err(ConvertError::Missing("kind")),
+        }
+    }
+}
+
+impl From<&EventEnvelope> for proto::EventEnvelope {
+    fn from(event: &EventEnvelope) -> Self {
+        Self {
+            event_id: hash_bytes(&event.event_id()),
+            kind: proto::EventKind::from(event.kind()) as i32,
+            payload: event.payload().as_bytes().to_vec(),
+            parents: event.parents().iter().map(hash_bytes).collect(),
+            agent_id: event.agent_id().map(|a| a.as_str().to_string()),
+            signature: event.signature().map(|s| s.as_bytes().to_vec()),
+            observation_type: event.observation_type().map(str::to_string),
+        }
+    }
+}
+
+/// [`EventEnvelope`]'s encoded shape, fed to its validating decoder
+#[derive(Serialize)]
+struct RawEnvelope {
+    event_id: Hash,
+    kind: EventKind,
+    payload: Vec<u8>,
+    parents: Vec<Hash>,
+    agent_id: Option<String>,
+    signature: Option<Vec<u8>>,
+    observation_type: Option<String>,
+}
+
+impl TryFrom<proto::EventEnvelope> for EventEnvelope {
+    type Error = ConvertError;
+
+    fn try_from(event: proto::EventEnvelope) -> Result<Self, ConvertError> {
+        let kind = proto::EventKind::try_from(event.kind)
+            .map_err(|_| ConvertError::UnknownKind(event.kind))?;
+        let raw = RawEnvelope {
+            event_id: hash_from(&event.event_id, "event_id")?,
+            kind: kind.try_into()?,
+            payload: event.payload,
+            parents: hashes_from(&event.parents, "parents")?,
+            agent_id: event.agent_id,
+            signature: event.signature,
+            observation_type: event.observation_type,
+        };
+        Ok(canonical::decode(&canonical::encode(&raw)?)?)
+    }
+}
+
+impl From<&Receipt> for proto::Receipt {
+    fn from(receipt: &Receipt) -> Self {
+        Self {
+            tick: receipt.tick,
+            state_hash: hash_bytes(&receipt.state_hash),
+            applied_slaps: receipt.applied_slaps.iter().map(hash_bytes).collect(),
+            timestamp: receipt.timestamp,
+            signature: receipt.signature.clone(),
+            view_hashes: receipt
+                .view_hashes
+                .iter()
+                .map(|(view, hash)| (view.clone(), hash_bytes(hash)))
+                .collect(),
+            prev_receipt: receipt.prev_receipt.as_ref().map(hash_bytes),
+            fuel: receipt.fuel.as_ref().map(|fuel| proto::FuelUsage {
+                total: fuel.total,
+                by_agent: fuel.by_agent.clone().into_iter().collect(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<proto::Receipt> for Receipt {
+    type Error = ConvertError;
+
+    fn try_from(receipt: proto::Receipt) -> Result<Self, ConvertError> {
+        Ok(Receipt {
+            tick: receipt.tick,
+            state_hash: hash_from(&receipt.state_hash, "state_hash")?,
+            applied_slaps: hashes_from(&receipt.applied_slaps, "applied_slaps")?,
+            timestamp: receipt.timestamp,
+            signature: receipt.signature,
+            view_hashes: receipt
+                .view_hashes
+                .into_iter()
+                .map(|(view, hash)| Ok((view, hash_from(&hash, "view_hashes")?)))
+                .collect::<Result<BTreeMap<_, _>, ConvertError>>()?,
+            prev_receipt: receipt
+                .prev_receipt
+                .map(|hash| hash_from(&hash, "prev_receipt"))
+                .transpose()?,
+            fuel: receipt.fuel.map(|fuel| FuelUsage {
+                total: fuel.total,
+                by_agent: fuel.by_agent.into_iter().collect(),
+            }),
+        })
+    }
+}
+
+impl From<&DeltaSpec> for proto::DeltaSpec {
+    fn from(delta: &DeltaSpec) -> Self {
+        use proto::delta_spec::Kind;
+        let kind = match &delta.kind {
+            DeltaKind::SchedulerPolicy { new_policy } => {
+                Kind::SchedulerPolicy(hash_bytes(new_policy))
+            }
+            DeltaKind::ClockPolicy { new_policy } => Kind::ClockPolicy(hash_bytes(new_policy)),
+            DeltaKind::TrustPolicy { new_trust_roots } => Kind::TrustPolicy(proto::TrustRoots {
+                agents: new_trust_roots
+                    .iter()
+                    .map(|a| a.as_str().to_string())
+                    .collect(),
+            }),
+            DeltaKind::InputMutation {
+                insert,
+                delete,
+                modify,
+            } => Kind::InputMutation(proto::InputMutation {
+                insert: insert.iter().map(input_to_proto).collect(),
+                delete: delete.iter().map(hash_bytes).collect(),
+                modify: modify
+                    .iter()
+                    .map(|(id, input)| proto::ModifiedInput {
+                        event_id: hash_bytes(id),
+                        input: Some(input_to_proto(input)),
+                    })
+                    .collect(),
+            }),
+        };
+        Self {
+            kind: Some(kind),
+            description: delta.description.clone(),
+            hash: hash_bytes(&delta.hash()),
+        }
+    }
+}
+
+fn input_to_proto(input: &InputEvent) -> proto::InputEvent {
+    proto::InputEvent {
+        placeholder: input.placeholder,
+    }
+}
+
+fn input_from_proto(input: proto::InputEvent) -> InputEvent {
+    InputEvent {
+        placeholder: input.placeholder,
+    }
+}
+
+impl TryFrom<proto::DeltaSpec> for DeltaSpec {
+    type Error = ConvertError;
+
+    fn try_from(delta: proto::DeltaSpec) -> Result<Self, ConvertError> {
+        use proto::delta_spec::Kind;
+        let claimed = hash_from(&delta.hash, "hash")?;
+        let description = delta.description;
+        let spec = match delta.kind.ok_or(ConvertError::Missing("kind"))? {
+            Kind::SchedulerPolicy(policy) => DeltaSpec::new_scheduler_policy(
+                hash_from(&policy, "scheduler_policy")?,
+                description,
+            )
+            .map_err(DeltaError::from)?,
+            Kind::ClockPolicy(policy) => {
+                DeltaSpec::new_clock_policy(hash_from(&policy, "clock_policy")?, description)
+                    .map_err(DeltaError::from)?
+            }
+            Kind::TrustPolicy(roots) => DeltaSpec::new_trust_policy(
+                roots
+                    .agents
+                    .into_iter()
+                    .map(AgentId::new)
+                    .collect::<Result<_, _>>()?,
+                description,
+            )?,
+            Kind::InputMutation(mutation) => DeltaSpec::new_input_mutation(
+                mutation.insert.into_iter().map(input_from_proto).collect(),
+                hashes_from(&mutation.delete, "delete")?,
+                mutation
+                    .modify
+                    .into_iter()
+                    .map(|m| {
+                        let input = m.input.ok_or(ConvertError::Missing("modify.input"))?;
+                        Ok((
+                            hash_from(&m.event_id, "modify.event_id")?,
+                            input_from_proto(input),
+                        ))
+                    })
+                    .collect::<Result<_, ConvertError>>()?,
+                description,
+            )
+            .map_err(DeltaError::from)?,
+        };
+        if spec.hash() != claimed {
+            return Err(DeltaError::InvalidHash.into());
+        }
+        Ok(spec)
+    }
+}
+
+/// Protobuf values that do not convert to core values
+#[derive(Debug, Error)]
+pub enum ConvertError {
+    #[error("{field} must be 32 bytes, got {len}")]
+    HashLength { field: &'static str, len: usize },
+    #[error("missing {0}")]
+    Missing(&'static str),
+    #[error("unknown event kind {0}")]
+    UnknownKind(i32),
+    #[error("invalid event: {0}")]
+    Event(#[from] CanonicalError),
+    #[error("invalid agent id: {0}")]
+    Agent(#[from] EventError),
+    #[error("invalid delta: {0}")]
+    Delta(#[from] DeltaError),
+}
@@ -0,0 +1,30 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! # jitos-grpc
+//!
+//! Protobuf interchange for Loom: a schema (`proto/loom/v0/loom.proto`)
+//! mirroring [`EventEnvelope`](jitos_core::events::EventEnvelope),
+//! [`Receipt`](jitos_core::Receipt) and
+//! [`DeltaSpec`](jitos_core::delta::DeltaSpec), and a tonic [`server`] to
+//! submit, fetch and sync events over it.
+//!
+//! The [`convert`] functions carry event payloads as their canonical CBOR
+//! bytes, untouched, so event ids recomputed on either side agree.
+//!
+//! With feature `otlp`, [`otel`] exports Decision/Commit chains as
+//! OpenTelemetry spans to an OTLP collector.
+
+pub mod convert;
+#[cfg(feature = "otlp")]
+pub mod otel;
+pub mod server;
+
+/// Code generated from `proto/loom/v0/loom.proto`
+pub mod proto {
+    #![allow(clippy::all)]
+    tonic::include_proto!("loom.v0");
+}
+
+pub use convert::{hash_from, ConvertError};
+pub use server::{serve, LoomService, LoomState, SharedState};
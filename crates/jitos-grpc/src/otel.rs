@@ -0,0 +1,241 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! OpenTelemetry Export - Decision/Commit Chains as OTLP Spans
+//!
+//! [`trace_request`] turns a worldline into one OTLP trace per Decision:
+//!
+//! - the Decision starts a span, and the last Commit naming it ends it;
+//!   every such Commit is also recorded as a span event
+//! - parents of the Decision and of its Commits become span links
+//! - a Decision nothing committed yet is a zero-length span with
+//!   `loom.committed = false` and an unset status
+//!
+//! Trace and span ids are prefixes of the event id (16 and 8 bytes), derived
+//! the same way for every event, so a link to a parent Decision resolves to
+//! that Decision's span and re-exporting a worldline yields the same ids.
+//! Events carry no wall-clock time; the caller maps worldline positions to
+//! Unix nanoseconds (from a clock view, or a fixed tick).
+//!
+//! [`export`] sends the request to an OTLP/gRPC collector (feature `otlp`).
+
+use std::collections::HashMap;
+
+use jitos_core::events::{EventEnvelope, EventId, EventKind};
+use opentelemetry_proto::tonic::collector::trace::v1::trace_service_client::TraceServiceClient;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use opentelemetry_proto::tonic::trace::v1::span::{Event, Link, SpanKind};
+use opentelemetry_proto::tonic::trace::v1::status::StatusCode;
+use opentelemetry_proto::tonic::trace::v1::{ResourceSpans, ScopeSpans, Span, Status};
+use thiserror::Error;
+
+/// OTLP message and service types, as generated by `opentelemetry-proto`
+pub use opentelemetry_proto::tonic as proto;
+
+/// Name of the instrumentation scope spans are reported under
+pub const SCOPE_NAME: &str = "jitos-grpc";
+
+/// OTLP trace id of an event: the first 16 bytes of its id
+pub fn trace_id(event_id: &EventId) -> Vec<u8> {
+    event_id.0[..16].to_vec()
+}
+
+/// OTLP span id of an event: the first 8 bytes of its id
+pub fn span_id(event_id: &EventId) -> Vec<u8> {
+    event_id.0[..8].to_vec()
+}
+
+/// One span per Decision in `events`, reported as service `service_name`
+///
+/// `events` must be in worldline order (parents first), as
+/// `MemoryEventStore::events` returns them; `time_at(i)` is the Unix time in
+/// nanoseconds of the event at position `i`. Commits of Decisions outside
+/// `events` are ignored.
+pub fn trace_request(
+    events: &[EventEnvelope],
+    service_name: &str,
+    time_at: impl Fn(usize) -> u64,
+) -> ExportTraceServiceRequest {
+    let by_id: HashMap<EventId, &EventEnvelope> =
+        events.iter().map(|e| (e.event_id(), e)).collect();
+
+    let mut spans: Vec<Span> = Vec::new();
+    let mut span_of: HashMap<EventId, usize> = HashMap::new();
+    for (position, event) in events.iter().enumerate() {
+        let id = event.event_id();
+        match event.kind() {
+            EventKind::Decision => {
+                let start = time_at(position);
+                let mut attributes = event_attributes(event);
+                attributes.push(bool_attr("loom.committed", false));
+                let links = event
+                    .parents()
+                    .iter()
+                    .map(|parent| link(parent, &id, by_id.get(parent).copied()))
+                    .collect();
+                span_of.insert(id, spans.len());
+                spans.push(Span {
+                    trace_id: trace_id(&id),
+                    span_id: span_id(&id),
+                    name: "loom.decision".to_string(),
+                    kind: SpanKind::Internal as i32,
+                    start_time_unix_nano: start,
+                    end_time_unix_nano: start,
+                    attributes,
+                    links,
+                    status: Some(Status::default()),
+                    ..Default::default()
+                });
+            }
+            EventKind::Commit => {
+                let time = time_at(position);
+                for decision in event.parents() {
+                    let Some(&index) = span_of.get(decision) else {
+                        continue;
+                    };
+                    let span = &mut spans[index];
+                    span.end_time_unix_nano = time;
+                    span.events.push(Event {
+                        time_unix_nano: time,
+                        name: "loom.commit".to_string(),
+                        attributes: event_attributes(event),
+                        ..Default::default()
+                    });
+                    span.links.extend(
+                        event
+                            .parents()
+                            .iter()
+                            .filter(|parent| *parent != decision)
+                            .map(|parent| link(parent, &id, by_id.get(parent).copied())),
+                    );
+                    if let Some(committed) = span
+                        .attributes
+                        .iter_mut()
+                        .find(|kv| kv.key == "loom.committed")
+                    {
+                        *committed = bool_attr("loom.committed", true);
+                    }
+                    span.status = Some(Status {
+                        code: StatusCode::Ok as i32,
+                        ..Default::default()
+                    });
+                }
+            }
+            EventKind::Observation | EventKind::PolicyContext => {}
+        }
+    }
+
+    ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: Some(Resource {
+                attributes: vec![string_attr("service.name", service_name)],
+                ..Default::default()
+            }),
+            scope_spans: vec![ScopeSpans {
+                scope: Some(InstrumentationScope {
+                    name: SCOPE_NAME.to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    ..Default::default()
+                }),
+                spans,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    }
+}
+
+/// Send `request` to the OTLP/gRPC collector at `endpoint`
+/// (e.g. `http://localhost:4317`); returns the number of spans accepted
+///
+/// # Errors
+///
+/// Errors connecting, a failed export call, or spans the collector rejected.
+pub async fn export(
+    endpoint: String,
+    request: ExportTraceServiceRequest,
+) -> Result<u64, OtlpError> {
+    let sent: u64 = request
+        .resource_spans
+        .iter()
+        .flat_map(|r| &r.scope_spans)
+        .map(|s| s.spans.len() as u64)
+        .sum();
+    let mut client = TraceServiceClient::connect(endpoint).await?;
+    let reply = client.export(request).await?.into_inner();
+    match reply.partial_success {
+        Some(partial) if partial.rejected_spans > 0 => Err(OtlpError::Rejected {
+            rejected: partial.rejected_spans as u64,
+            message: partial.error_message,
+        }),
+        _ => Ok(sent),
+    }
+}
+
+/// OTLP export errors
+#[derive(Debug, Error)]
+pub enum OtlpError {
+    #[error("cannot reach collector: {0}")]
+    Connect(#[from] tonic::transport::Error),
+    #[error("export failed: {0}")]
+    Export(#[from] tonic::Status),
+    #[error("collector rejected {rejected} spans: {message}")]
+    Rejected { rejected: u64, message: String },
+}
+
+/// A link from the event `from` to its parent `parent`
+fn link(parent: &EventId, from: &EventId, known: Option<&EventEnvelope>) -> Link {
+    let mut attributes = vec![
+        string_attr("loom.event_id", &parent.to_string()),
+        string_attr("loom.link.from", &from.to_string()),
+    ];
+    if let Some(event) = known {
+        attributes.push(string_attr("loom.kind", kind_name(event.kind())));
+        if let Some(ty) = event.observation_type() {
+            attributes.push(string_attr("loom.observation_type", ty));
+        }
+    }
+    Link {
+        trace_id: trace_id(parent),
+        span_id: span_id(parent),
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn event_attributes(event: &EventEnvelope) -> Vec<KeyValue> {
+    let mut attributes = vec![
+        string_attr("loom.event_id", &event.event_id().to_string()),
+        string_attr("loom.kind", kind_name(event.kind())),
+    ];
+    if let Some(agent) = event.agent_id() {
+        attributes.push(string_attr("loom.agent_id", agent.as_str()));
+    }
+    attributes
+}
+
+fn kind_name(kind: &EventKind) -> &'static str {
+    match kind {
+        EventKind::Observation => "Observation",
+        EventKind::PolicyContext => "PolicyContext",
+        EventKind::Decision => "Decision",
+        EventKind::Commit => "Commit",
+    }
+}
+
+fn string_attr(key: &str, value: &str) -> KeyValue {
+    attr(key, any_value::Value::StringValue(value.to_string()))
+}
+
+fn bool_attr(key: &str, value: bool) -> KeyValue {
+    attr(key, any_value::Value::BoolValue(value))
+}
+
+fn attr(key: &str, value: any_value::Value) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        value: Some(AnyValue { value: Some(value) }),
+    }
+}
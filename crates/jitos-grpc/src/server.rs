@@ -0,0 +1,189 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! gRPC Server - The `loom.v0.Loom` Service Over a Shared Event Store
+//!
+//! | RPC            | Answer                                            |
+//! |----------------|---------------------------------------------------|
+//! | `Submit`       | append an event; `created` is false if present    |
+//! | `Fetch`        | the event with `event_id`                         |
+//! | `FetchReceipt` | the receipt of tick `tick`                        |
+//! | `Sync`         | a stream of the events the caller's heads lack    |
+//!
+//! Submitted events are converted with [`crate::convert`] and appended with
+//! [`MemoryEventStore::append`], so they are validated like any other
+//! event. Unlike the HTTP API, every event kind can be submitted: gRPC
+//! peers are Loom nodes that produce signed events of their own.
+//!
+//! Malformed messages answer `INVALID_ARGUMENT`, events the store rejects
+//! `FAILED_PRECONDITION`, and unknown ids and ticks `NOT_FOUND`.
+
+use std::pin::Pin;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use jitos_core::events::EventEnvelope;
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Receipt;
+use jitos_net::missing_for;
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+
+use crate::convert::hash_from;
+use crate::proto::loom_server::{Loom, LoomServer};
+use crate::proto::{self, FetchRequest, ReceiptRequest, SubmitReply, SyncRequest};
+
+/// What the service serves
+#[derive(Debug, Default)]
+pub struct LoomState {
+    store: MemoryEventStore,
+    receipts: Vec<Receipt>,
+}
+
+/// [`LoomState`] shared between the service and its owner
+pub type SharedState = Arc<RwLock<LoomState>>;
+
+impl LoomState {
+    pub fn new(store: MemoryEventStore) -> Self {
+        Self {
+            store,
+            receipts: Vec::new(),
+        }
+    }
+
+    /// Serve `receipts` too
+    pub fn with_receipts(mut self, receipts: Vec<Receipt>) -> Self {
+        self.receipts = receipts;
+        self
+    }
+
+    /// Wrap for [`LoomService`]
+    pub fn shared(self) -> SharedState {
+        Arc::new(RwLock::new(self))
+    }
+
+    pub fn store(&self) -> &MemoryEventStore {
+        &self.store
+    }
+
+    /// The store, for appending events from elsewhere
+    pub fn store_mut(&mut self) -> &mut MemoryEventStore {
+        &mut self.store
+    }
+
+    pub fn receipts(&self) -> &[Receipt] {
+        &self.receipts
+    }
+
+    /// Serve the receipt of another tick
+    pub fn push_receipt(&mut self, receipt: Receipt) {
+        self.receipts.push(receipt);
+    }
+}
+
+/// The `loom.v0.Loom` service over a [`SharedState`]
+#[derive(Debug, Clone)]
+pub struct LoomService {
+    state: SharedState,
+}
+
+impl LoomService {
+    pub fn new(state: SharedState) -> Self {
+        Self { state }
+    }
+
+    /// Wrap for a tonic router
+    pub fn into_server(self) -> LoomServer<Self> {
+        LoomServer::new(self)
+    }
+}
+
+/// Serve `state` on `listener` until the server fails
+///
+/// # Errors
+///
+/// Errors accepting connections.
+pub async fn serve(
+    listener: tokio::net::TcpListener,
+    state: SharedState,
+) -> Result<(), tonic::transport::Error> {
+    tonic::transport::Server::builder()
+        .add_service(LoomService::new(state).into_server())
+        .serve_with_incoming(TcpListenerStream::new(listener))
+        .await
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<proto::EventEnvelope, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl Loom for LoomService {
+    async fn submit(
+        &self,
+        request: Request<proto::EventEnvelope>,
+    ) -> Result<Response<SubmitReply>, Status> {
+        let event = EventEnvelope::try_from(request.into_inner())
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let event_id = event.event_id();
+        let mut state = self.state.write().unwrap_or_else(PoisonError::into_inner);
+        let created = state.store.position(&event_id).is_none();
+        state
+            .store
+            .append(event)
+            .map_err(|e| Status::failed_precondition(format!("event rejected: {e}")))?;
+        Ok(Response::new(SubmitReply {
+            event_id: event_id.0.to_vec(),
+            created,
+        }))
+    }
+
+    async fn fetch(
+        &self,
+        request: Request<FetchRequest>,
+    ) -> Result<Response<proto::EventEnvelope>, Status> {
+        let id = hash_from(&request.into_inner().event_id, "event_id")
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+        let pos = state
+            .store
+            .position(&id)
+            .ok_or_else(|| Status::not_found(format!("no event {id}")))?;
+        Ok(Response::new((&state.store.events()[pos]).into()))
+    }
+
+    async fn fetch_receipt(
+        &self,
+        request: Request<ReceiptRequest>,
+    ) -> Result<Response<proto::Receipt>, Status> {
+        let tick = request.into_inner().tick;
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+        let receipt = state
+            .receipts
+            .iter()
+            .find(|r| r.tick == tick)
+            .ok_or_else(|| Status::not_found(format!("no receipt of tick {tick}")))?;
+        Ok(Response::new(receipt.into()))
+    }
+
+    type SyncStream = EventStream;
+
+    async fn sync(
+        &self,
+        request: Request<SyncRequest>,
+    ) -> Result<Response<Self::SyncStream>, Status> {
+        let heads = request
+            .into_inner()
+            .heads
+            .iter()
+            .map(|h| hash_from(h, "heads"))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let missing: Vec<_> = {
+            let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+            missing_for(&state.store, &heads)
+                .iter()
+                .map(|event| Ok(event.into()))
+                .collect()
+        };
+        Ok(Response::new(Box::pin(tokio_stream::iter(missing))))
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! gRPC Interchange Tests
+//!
+//! Core values survive the protobuf round trip with their ids and hashes
+//! intact, tampered messages are refused, and the service submits, fetches
+//! and syncs over a real connection.
+
+use jitos_core::delta::{DeltaError, DeltaSpec, InputEvent};
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::{FuelUsage, Hash, Receipt};
+use jitos_grpc::proto::loom_client::LoomClient;
+use jitos_grpc::proto::{self, FetchRequest, ReceiptRequest, SyncRequest};
+use jitos_grpc::{serve, ConvertError, LoomState};
+use serde_json::json;
+use tonic::Code;
+
+/// Helper: A policy, an observation, a decision and a signed commit
+fn chain() -> Vec<EventEnvelope> {
+    let agent = || Some(AgentId::new("alice").unwrap());
+    let bytes = |v: serde_json::Value| CanonicalBytes::from_value(&v).unwrap();
+    let policy =
+        EventEnvelope::new_policy_context(bytes(json!("fifo")), vec![], agent(), None).unwrap();
+    let sample = EventEnvelope::new_observation(
+        bytes(json!({"value_ns": 5, "uncertainty_ns": 1})),
+        vec![],
+        Some("OBS_CLOCK_SAMPLE_V0".to_string()),
+        agent(),
+        Some(Signature::new(vec![7, 7]).unwrap()),
+    )
+    .unwrap();
+    let decision = EventEnvelope::new_decision(
+        bytes(json!({"fire": true})),
+        vec![sample.event_id()],
+        policy.event_id(),
+        agent(),
+        None,
+    )
+    .unwrap();
+    let commit = EventEnvelope::new_commit(
+        bytes(json!("fired")),
+        decision.event_id(),
+        vec![],
+        agent(),
+        Signature::new(vec![1, 2, 3]).unwrap(),
+    )
+    .unwrap();
+    vec![policy, sample, decision, commit]
+}
+
+#[test]
+fn t1_events_keep_their_ids_and_payload_bytes() {
+    for event in chain() {
+        let wire = proto::EventEnvelope::from(&event);
+        assert_eq!(wire.payload, event.payload().as_bytes());
+        let back = EventEnvelope::try_from(wire).unwrap();
+        assert_eq!(back, event);
+        assert_eq!(back.event_id(), event.event_id());
+    }
+
+    let commit = &chain()[3];
+    let mut tampered = proto::EventEnvelope::from(commit);
+    tampered.payload = CanonicalBytes::from_value(&json!("not fired"))
+        .unwrap()
+        .as_bytes()
+        .to_vec();
+    assert!(matches!(
+        EventEnvelope::try_from(tampered),
+        Err(ConvertError::Event(_))
+    ));
+    let mut unsigned = proto::EventEnvelope::from(commit);
+    unsigned.signature = None;
+    assert!(EventEnvelope::try_from(unsigned).is_err());
+    let mut short = proto::EventEnvelope::from(commit);
+    short.parents = vec![vec![0; 31]];
+    assert!(matches!(
+        EventEnvelope::try_from(short),
+        Err(ConvertError::HashLength {
+            field: "parents",
+            len: 31
+        })
+    ));
+    let mut unspecified = proto::EventEnvelope::from(commit);
+    unspecified.kind = proto::EventKind::Unspecified as i32;
+    assert!(matches!(
+        EventEnvelope::try_from(unspecified),
+        Err(ConvertError::Missing("kind"))
+    ));
+}
+
+#[test]
+fn t2_receipts_and_deltas_round_trip_and_hashes_are_checked() {
+    let receipt = Receipt {
+        tick: 3,
+        state_hash: Hash([1; 32]),
+        applied_slaps: vec![Hash([2; 32]), Hash([3; 32])],
+        timestamp: 30,
+        signature: Some("sig".to_string()),
+        view_hashes: Default::default(),
+        prev_receipt: None,
+        fuel: Some(FuelUsage {
+            total: 9,
+            by_agent: [("alice".to_string(), 4)].into(),
+        }),
+    }
+    .with_view_hash("clock", Hash([4; 32]))
+    .chained_to(Hash([5; 32]));
+    let back = Receipt::try_from(proto::Receipt::from(&receipt)).unwrap();
+    assert_eq!(proto::Receipt::from(&back), proto::Receipt::from(&receipt));
+    assert_eq!(back.fuel, receipt.fuel);
+    assert_eq!(back.digest().unwrap(), receipt.digest().unwrap());
+
+    let deltas = [
+        DeltaSpec::new_scheduler_policy(Hash([6; 32]), "lifo".to_string()).unwrap(),
+        DeltaSpec::new_clock_policy(Hash([7; 32]), "trust gps".to_string()).unwrap(),
+        DeltaSpec::new_trust_policy(vec![AgentId::new("bob").unwrap()], "bob".to_string()).unwrap(),
+        DeltaSpec::new_input_mutation(
+            vec![InputEvent { placeholder: 1 }],
+            vec![Hash([8; 32])],
+            vec![(Hash([9; 32]), InputEvent { placeholder: 2 })],
+            "what if".to_string(),
+        )
+        .unwrap(),
+    ];
+    for delta in &deltas {
+        let back = DeltaSpec::try_from(proto::DeltaSpec::from(delta)).unwrap();
+        assert_eq!(&back, delta);
+        assert_eq!(back.hash(), delta.hash());
+    }
+
+    let mut tampered = proto::DeltaSpec::from(&deltas[2]);
+    tampered.description = "carol".to_string();
+    assert!(matches!(
+        DeltaSpec::try_from(tampered),
+        Err(ConvertError::Delta(DeltaError::InvalidHash))
+    ));
+    let mut empty = proto::DeltaSpec::from(&deltas[2]);
+    empty.kind = Some(proto::delta_spec::Kind::TrustPolicy(proto::TrustRoots {
+        agents: vec![],
+    }));
+    assert!(matches!(
+        DeltaSpec::try_from(empty),
+        Err(ConvertError::Delta(DeltaError::InvalidStructure(_)))
+    ));
+}
+
+#[tokio::test]
+async fn t3_service_submits_fetches_and_syncs() {
+    let events = chain();
+    let receipt = Receipt {
+        tick: 0,
+        state_hash: Hash([1; 32]),
+        applied_slaps: vec![],
+        timestamp: 0,
+        signature: None,
+        view_hashes: Default::default(),
+        prev_receipt: None,
+        fuel: None,
+    };
+    let state = LoomState::new(MemoryEventStore::new())
+        .with_receipts(vec![receipt.clone()])
+        .shared();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(serve(listener, state.clone()));
+    let mut client = LoomClient::connect(format!("http://{addr}")).await.unwrap();
+
+    for event in &events {
+        let reply = client
+            .submit(proto::EventEnvelope::from(event))
+            .await
+            .unwrap();
+        assert!(reply.get_ref().created);
+        assert_eq!(reply.get_ref().event_id, event.event_id().0);
+    }
+    let again = client
+        .submit(proto::EventEnvelope::from(&events[0]))
+        .await
+        .unwrap();
+    assert!(!again.get_ref().created, "resubmitting is idempotent");
+    assert_eq!(state.read().unwrap().store().events(), events.as_slice());
+
+    // The decision's policy parent is unknown to a fresh store.
+    let orphan = EventEnvelope::new_decision(
+        CanonicalBytes::from_value(&json!(1)).unwrap(),
+        vec![events[1].event_id()],
+        Hash([0xee; 32]),
+        None,
+        None,
+    )
+    .unwrap();
+    let status = client
+        .submit(proto::EventEnvelope::from(&orphan))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    let fetched = client
+        .fetch(FetchRequest {
+            event_id: events[3].event_id().0.to_vec(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(EventEnvelope::try_from(fetched).unwrap(), events[3]);
+    let missing = client
+        .fetch(FetchRequest {
+            event_id: vec![0; 32],
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(missing.code(), Code::NotFound);
+    let malformed = client
+        .fetch(FetchRequest { event_id: vec![1] })
+        .await
+        .unwrap_err();
+    assert_eq!(malformed.code(), Code::InvalidArgument);
+
+    let served = client
+        .fetch_receipt(ReceiptRequest { tick: 0 })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        Receipt::try_from(served).unwrap().digest().unwrap(),
+        receipt.digest().unwrap()
+    );
+    let status = client
+        .fetch_receipt(ReceiptRequest { tick: 5 })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+
+    // A peer holding the observation lacks the policy, decision and commit.
+    let mut stream = client
+        .sync(SyncRequest {
+            heads: vec![events[1].event_id().0.to_vec()],
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let mut synced = Vec::new();
+    while let Some(event) = stream.message().await.unwrap() {
+        synced.push(EventEnvelope::try_from(event).unwrap());
+    }
+    assert_eq!(
+        synced,
+        vec![events[0].clone(), events[2].clone(), events[3].clone()]
+    );
+}
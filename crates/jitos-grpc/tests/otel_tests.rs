@@ -0,0 +1,252 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! OTLP Export Tests
+//!
+//! Decisions become spans ended by their commits, parents become links with
+//! ids that resolve across spans, and the request reaches a collector over a
+//! real connection.
+
+use std::sync::{Arc, Mutex};
+
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventId, Signature};
+use jitos_grpc::otel::proto::collector::trace::v1::trace_service_server::{
+    TraceService, TraceServiceServer,
+};
+use jitos_grpc::otel::proto::collector::trace::v1::{
+    ExportTracePartialSuccess, ExportTraceServiceRequest, ExportTraceServiceResponse,
+};
+use jitos_grpc::otel::proto::common::v1::{any_value, KeyValue};
+use jitos_grpc::otel::proto::trace::v1::status::StatusCode;
+use jitos_grpc::otel::proto::trace::v1::Span;
+use jitos_grpc::otel::{export, span_id, trace_id, trace_request, OtlpError};
+use serde_json::json;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{Request, Response, Status};
+
+fn bytes(v: serde_json::Value) -> CanonicalBytes {
+    CanonicalBytes::from_value(&v).unwrap()
+}
+
+/// Helper: A policy and an observation, a decision over them, its commit,
+/// then a second decision over the commit that nothing has committed yet
+fn worldline() -> Vec<EventEnvelope> {
+    let agent = || Some(AgentId::new("alice").unwrap());
+    let policy =
+        EventEnvelope::new_policy_context(bytes(json!("fifo")), vec![], None, None).unwrap();
+    let sample = EventEnvelope::new_observation(
+        bytes(json!({"value_ns": 5})),
+        vec![],
+        Some("OBS_CLOCK_SAMPLE_V0".to_string()),
+        None,
+        None,
+    )
+    .unwrap();
+    let decision = EventEnvelope::new_decision(
+        bytes(json!({"fire": true})),
+        vec![sample.event_id()],
+        policy.event_id(),
+        agent(),
+        None,
+    )
+    .unwrap();
+    let commit = EventEnvelope::new_commit(
+        bytes(json!("fired")),
+        decision.event_id(),
+        vec![sample.event_id()],
+        agent(),
+        Signature::new(vec![1, 2, 3]).unwrap(),
+    )
+    .unwrap();
+    let retry = EventEnvelope::new_decision(
+        bytes(json!({"fire": false})),
+        vec![commit.event_id()],
+        policy.event_id(),
+        None,
+        None,
+    )
+    .unwrap();
+    vec![policy, sample, decision, commit, retry]
+}
+
+fn attr<'a>(attributes: &'a [KeyValue], key: &str) -> Option<&'a any_value::Value> {
+    attributes
+        .iter()
+        .find(|kv| kv.key == key)
+        .and_then(|kv| kv.value.as_ref()?.value.as_ref())
+}
+
+fn text(value: &str) -> any_value::Value {
+    any_value::Value::StringValue(value.to_string())
+}
+
+fn spans(request: &ExportTraceServiceRequest) -> &[Span] {
+    &request.resource_spans[0].scope_spans[0].spans
+}
+
+/// 1000 ns per worldline position, from 1 s after the epoch
+fn time_at(position: usize) -> u64 {
+    1_000_000_000 + 1_000 * position as u64
+}
+
+#[test]
+fn t1_decisions_become_spans_ended_by_commits() {
+    let events = worldline();
+    let ids: Vec<EventId> = events.iter().map(|e| e.event_id()).collect();
+    let request = trace_request(&events, "loom-test", time_at);
+
+    let resource = request.resource_spans[0].resource.as_ref().unwrap();
+    assert_eq!(
+        attr(&resource.attributes, "service.name"),
+        Some(&text("loom-test"))
+    );
+    let spans = spans(&request);
+    assert_eq!(spans.len(), 2, "one span per decision");
+
+    let fired = &spans[0];
+    assert_eq!(fired.trace_id, ids[2].0[..16]);
+    assert_eq!(fired.span_id, ids[2].0[..8]);
+    assert_eq!(fired.start_time_unix_nano, time_at(2));
+    assert_eq!(fired.end_time_unix_nano, time_at(3));
+    assert_eq!(fired.status.as_ref().unwrap().code, StatusCode::Ok as i32);
+    assert_eq!(
+        attr(&fired.attributes, "loom.event_id"),
+        Some(&text(&ids[2].to_string()))
+    );
+    assert_eq!(
+        attr(&fired.attributes, "loom.agent_id"),
+        Some(&text("alice"))
+    );
+    assert_eq!(
+        attr(&fired.attributes, "loom.committed"),
+        Some(&any_value::Value::BoolValue(true))
+    );
+    assert_eq!(fired.events.len(), 1);
+    assert_eq!(fired.events[0].name, "loom.commit");
+    assert_eq!(fired.events[0].time_unix_nano, time_at(3));
+    assert_eq!(
+        attr(&fired.events[0].attributes, "loom.event_id"),
+        Some(&text(&ids[3].to_string()))
+    );
+
+    // The decision's parents (in canonical order), then the commit's other parent.
+    let linked: Vec<_> = fired
+        .links
+        .iter()
+        .map(|l| attr(&l.attributes, "loom.event_id").cloned())
+        .collect();
+    let mut parents = vec![ids[0], ids[1]];
+    parents.sort();
+    parents.push(ids[1]);
+    let expected: Vec<_> = parents
+        .iter()
+        .map(|id| Some(text(&id.to_string())))
+        .collect();
+    assert_eq!(linked, expected);
+    let sample_link = fired
+        .links
+        .iter()
+        .find(|l| l.span_id == ids[1].0[..8])
+        .unwrap();
+    assert_eq!(
+        attr(&sample_link.attributes, "loom.observation_type"),
+        Some(&text("OBS_CLOCK_SAMPLE_V0"))
+    );
+    assert_eq!(
+        attr(&fired.links[2].attributes, "loom.link.from"),
+        Some(&text(&ids[3].to_string()))
+    );
+}
+
+#[test]
+fn t2_links_resolve_and_ids_are_stable() {
+    let events = worldline();
+    let request = trace_request(&events, "loom-test", time_at);
+    let [fired, retry] = spans(&request) else {
+        panic!("expected two spans")
+    };
+
+    assert_eq!(retry.start_time_unix_nano, time_at(4));
+    assert_eq!(retry.end_time_unix_nano, retry.start_time_unix_nano);
+    assert_eq!(
+        retry.status.as_ref().unwrap().code,
+        StatusCode::Unset as i32
+    );
+    assert_eq!(
+        attr(&retry.attributes, "loom.committed"),
+        Some(&any_value::Value::BoolValue(false))
+    );
+    assert!(retry.events.is_empty());
+
+    // The retry's evidence is the commit, which ended `fired`; the commit
+    // links by its own ids, derived exactly as a span's would be.
+    let commit = events[3].event_id();
+    assert!(retry
+        .links
+        .iter()
+        .any(|l| l.trace_id == trace_id(&commit) && l.span_id == span_id(&commit)));
+    assert_ne!(fired.trace_id, retry.trace_id);
+
+    assert_eq!(trace_request(&events, "loom-test", time_at), request);
+    let tail = trace_request(&events[3..], "loom-test", time_at);
+    assert_eq!(
+        spans(&tail).len(),
+        1,
+        "a commit whose decision is outside the slice is ignored"
+    );
+}
+
+/// A collector that records requests and rejects `reject` spans
+struct Collector {
+    received: Arc<Mutex<Vec<ExportTraceServiceRequest>>>,
+    reject: i64,
+}
+
+#[tonic::async_trait]
+impl TraceService for Collector {
+    async fn export(
+        &self,
+        request: Request<ExportTraceServiceRequest>,
+    ) -> Result<Response<ExportTraceServiceResponse>, Status> {
+        self.received.lock().unwrap().push(request.into_inner());
+        let partial_success = (self.reject > 0).then(|| ExportTracePartialSuccess {
+            rejected_spans: self.reject,
+            error_message: "no room".to_string(),
+        });
+        Ok(Response::new(ExportTraceServiceResponse {
+            partial_success,
+        }))
+    }
+}
+
+async fn collector(reject: i64) -> (String, Arc<Mutex<Vec<ExportTraceServiceRequest>>>) {
+    let received = Arc::default();
+    let service = Collector {
+        received: Arc::clone(&received),
+        reject,
+    };
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(TraceServiceServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    (format!("http://{addr}"), received)
+}
+
+#[tokio::test]
+async fn t3_export_reaches_an_otlp_collector() {
+    let request = trace_request(&worldline(), "loom-test", time_at);
+
+    let (endpoint, received) = collector(0).await;
+    assert_eq!(export(endpoint, request.clone()).await.unwrap(), 2);
+    assert_eq!(*received.lock().unwrap(), vec![request.clone()]);
+
+    let (endpoint, _) = collector(1).await;
+    let err = export(endpoint, request).await.unwrap_err();
+    assert!(
+        matches!(&err, OtlpError::Rejected { rejected: 1, message } if message == "no room"),
+        "{err}"
+    );
+}
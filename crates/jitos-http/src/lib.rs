@@ -0,0 +1,20 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! # jitos-http
+//!
+//! HTTP/JSON access to a Loom event store, for producers and consumers that
+//! do not speak Rust or canonical CBOR.
+//!
+//! The [`server`] reads events, heads and receipts, and accepts submitted
+//! observation events, validating them like any other event. Payloads
+//! cross the boundary as JSON through a deterministic [`projection`] of
+//! their canonical CBOR.
+
+pub mod projection;
+pub mod server;
+
+pub use projection::{
+    hash_hex, kind_name, parse_hash, payload_json, EventJson, ReceiptJson, SubmitObservation,
+};
+pub use server::{router, serve, ApiError, EventApi, SharedApi};
@@ -0,0 +1,127 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! JSON Projection - Events and Receipts as JSON
+//!
+//! The store speaks canonical CBOR; HTTP clients speak JSON. Hashes,
+//! signatures and raw payloads appear as lowercase hex. A payload is also
+//! projected to JSON by decoding its CBOR, when it holds only values JSON
+//! can represent (no byte strings). Canonical CBOR orders map keys, and
+//! the projection keeps that order, so one payload always projects to the
+//! same JSON.
+//!
+//! Submitted JSON payloads go the other way through
+//! [`CanonicalBytes::from_value`]: the event id does not depend on the key
+//! order or whitespace of the submitted JSON.
+
+use std::collections::BTreeMap;
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventKind};
+use jitos_core::{FuelUsage, Hash, Receipt};
+use serde::{Deserialize, Serialize};
+
+/// Lowercase hex of `hash`
+pub fn hash_hex(hash: &Hash) -> String {
+    hex::encode(hash.0)
+}
+
+/// Hash from 64 hex digits
+pub fn parse_hash(hex: &str) -> Option<Hash> {
+    let bytes: [u8; 32] = hex::decode(hex).ok()?.try_into().ok()?;
+    Some(Hash(bytes))
+}
+
+pub fn kind_name(kind: &EventKind) -> &'static str {
+    match kind {
+        EventKind::Observation => "Observation",
+        EventKind::PolicyContext => "PolicyContext",
+        EventKind::Decision => "Decision",
+        EventKind::Commit => "Commit",
+    }
+}
+
+/// `payload` as JSON, `None` if it holds values JSON cannot represent
+pub fn payload_json(payload: &CanonicalBytes) -> Option<serde_json::Value> {
+    payload.to_value().ok()
+}
+
+/// An event as served
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventJson {
+    pub event_id: String,
+    pub kind: String,
+    pub parents: Vec<String>,
+    pub agent_id: Option<String>,
+    pub signature: Option<String>,
+    pub observation_type: Option<String>,
+    /// JSON projection of the payload
+    pub payload: Option<serde_json::Value>,
+    /// The canonical CBOR payload
+    pub payload_cbor: String,
+}
+
+impl From<&EventEnvelope> for EventJson {
+    fn from(event: &EventEnvelope) -> Self {
+        Self {
+            event_id: hash_hex(&event.event_id()),
+            kind: kind_name(event.kind()).to_string(),
+            parents: event.parents().iter().map(hash_hex).collect(),
+            agent_id: event.agent_id().map(|a| a.as_str().to_string()),
+            signature: event.signature().map(|s| hex::encode(s.as_bytes())),
+            observation_type: event.observation_type().map(str::to_string),
+            payload: payload_json(event.payload()),
+            payload_cbor: hex::encode(event.payload().as_bytes()),
+        }
+    }
+}
+
+/// A receipt as served
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptJson {
+    pub tick: u64,
+    /// [`Receipt::digest`]
+    pub digest: String,
+    pub state_hash: String,
+    pub applied_slaps: Vec<String>,
+    pub timestamp: u64,
+    pub signature: Option<String>,
+    pub view_hashes: BTreeMap<String, String>,
+    pub prev_receipt: Option<String>,
+    pub fuel: Option<FuelUsage>,
+}
+
+impl ReceiptJson {
+    /// `None` if the receipt cannot be digested
+    pub fn new(receipt: &Receipt) -> Option<Self> {
+        Some(Self {
+            tick: receipt.tick,
+            digest: hash_hex(&receipt.digest().ok()?),
+            state_hash: hash_hex(&receipt.state_hash),
+            applied_slaps: receipt.applied_slaps.iter().map(hash_hex).collect(),
+            timestamp: receipt.timestamp,
+            signature: receipt.signature.clone(),
+            view_hashes: receipt
+                .view_hashes
+                .iter()
+                .map(|(view, hash)| (view.clone(), hash_hex(hash)))
+                .collect(),
+            prev_receipt: receipt.prev_receipt.as_ref().map(hash_hex),
+            fuel: receipt.fuel.clone(),
+        })
+    }
+}
+
+/// Body of an observation submission
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmitObservation {
+    pub observation_type: Option<String>,
+    /// Any JSON; stored as canonical CBOR
+    pub payload: serde_json::Value,
+    /// Parent event ids, hex
+    #[serde(default)]
+    pub parents: Vec<String>,
+    /// Agent claiming the observation; needs `signature`
+    pub agent_id: Option<String>,
+    /// The agent's signature over the event statement, hex
+    pub signature: Option<String>,
+}
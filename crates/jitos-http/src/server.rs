@@ -0,0 +1,306 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! HTTP Server - Routes Over a Shared Event Store
+//!
+//! | Method | Path               | Answer                                   |
+//! |--------|--------------------|------------------------------------------|
+//! | GET    | `/events/{id}`     | the event                                |
+//! | GET    | `/events`          | events in worldline order, filtered by   |
+//! |        |                    | `?observation_type=` and `?agent=`       |
+//! | GET    | `/heads`           | head event ids, in worldline order       |
+//! | GET    | `/receipts`        | every receipt, in tick order             |
+//! | GET    | `/receipts/{tick}` | the receipt of tick `tick`               |
+//! | POST   | `/observations`    | submit a [`SubmitObservation`]           |
+//!
+//! Bodies are JSON ([`crate::projection`]). A submission becomes an
+//! Observation event and goes through [`MemoryEventStore::append`], so it
+//! is validated (known parents, canonical payload) like any other event.
+//! It answers `201 Created` with the new event, or `200 OK` if the event
+//! was already present. Only observations can be submitted: policies,
+//! decisions and commits come from the runtime, not from HTTP clients.
+//!
+//! A submission that names an `agent_id` or carries a `signature` must be
+//! that agent's valid signature over the event (see
+//! [`jitos_net::event_statement`]), checked with the verifier set by
+//! [`EventApi::with_verifier`]. Without a verifier only unattributed,
+//! unsigned observations are accepted.
+//!
+//! Errors answer `{"error": "..."}` with `400` for malformed input, `403`
+//! for authorship that does not verify, `404` for unknown ids and ticks,
+//! and `422` for events the store rejects.
+
+use std::fmt;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventError, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Receipt;
+use jitos_net::{signed_author, PeerVerifier};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::projection::{hash_hex, parse_hash, EventJson, ReceiptJson, SubmitObservation};
+
+/// What the server serves
+#[derive(Default)]
+pub struct EventApi {
+    store: MemoryEventStore,
+    receipts: Vec<Receipt>,
+    /// Checks the authors of submitted observations
+    verifier: Option<Box<dyn PeerVerifier + Send + Sync>>,
+}
+
+impl fmt::Debug for EventApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventApi")
+            .field("store", &self.store)
+            .field("receipts", &self.receipts)
+            .field("verifier", &self.verifier.is_some())
+            .finish()
+    }
+}
+
+/// [`EventApi`] shared between the server and its owner
+pub type SharedApi = Arc<RwLock<EventApi>>;
+
+impl EventApi {
+    pub fn new(store: MemoryEventStore) -> Self {
+        Self {
+            store,
+            receipts: Vec::new(),
+            verifier: None,
+        }
+    }
+
+    /// Accept submissions signed by agents `verifier` knows
+    pub fn with_verifier(mut self, verifier: impl PeerVerifier + Send + Sync + 'static) -> Self {
+        self.verifier = Some(Box::new(verifier));
+        self
+    }
+
+    /// Serve `receipts` too
+    pub fn with_receipts(mut self, receipts: Vec<Receipt>) -> Self {
+        self.receipts = receipts;
+        self
+    }
+
+    /// Wrap for [`router`]
+    pub fn shared(self) -> SharedApi {
+        Arc::new(RwLock::new(self))
+    }
+
+    pub fn store(&self) -> &MemoryEventStore {
+        &self.store
+    }
+
+    /// The store, for appending events from elsewhere
+    pub fn store_mut(&mut self) -> &mut MemoryEventStore {
+        &mut self.store
+    }
+
+    pub fn receipts(&self) -> &[Receipt] {
+        &self.receipts
+    }
+
+    /// Serve the receipt of another tick
+    pub fn push_receipt(&mut self, receipt: Receipt) {
+        self.receipts.push(receipt);
+    }
+}
+
+/// Routes over `api`
+pub fn router(api: SharedApi) -> Router {
+    Router::new()
+        .route("/events", get(list_events))
+        .route("/events/{id}", get(get_event))
+        .route("/heads", get(heads))
+        .route("/receipts", get(list_receipts))
+        .route("/receipts/{tick}", get(get_receipt))
+        .route("/observations", post(submit))
+        .with_state(api)
+}
+
+/// Serve `api` on `listener` until the server fails
+///
+/// # Errors
+///
+/// Errors accepting connections.
+pub async fn serve(listener: tokio::net::TcpListener, api: SharedApi) -> std::io::Result<()> {
+    axum::serve(listener, router(api)).await
+}
+
+/// Filters of `GET /events`
+#[derive(Debug, Default, Deserialize)]
+struct EventQuery {
+    observation_type: Option<String>,
+    agent: Option<String>,
+}
+
+async fn get_event(
+    State(api): State<SharedApi>,
+    Path(id): Path<String>,
+) -> Result<Json<EventJson>, ApiError> {
+    let id = parse_hash(&id).ok_or(ApiError::BadRequest(format!("malformed event id {id}")))?;
+    let api = api.read().unwrap_or_else(PoisonError::into_inner);
+    let pos = api
+        .store
+        .position(&id)
+        .ok_or_else(|| ApiError::NotFound(format!("event {}", hash_hex(&id))))?;
+    Ok(Json(EventJson::from(&api.store.events()[pos])))
+}
+
+async fn list_events(
+    State(api): State<SharedApi>,
+    Query(query): Query<EventQuery>,
+) -> Json<Vec<EventJson>> {
+    let api = api.read().unwrap_or_else(PoisonError::into_inner);
+    let events = api
+        .store
+        .events()
+        .iter()
+        .filter(|e| {
+            query
+                .observation_type
+                .as_deref()
+                .is_none_or(|t| e.observation_type() == Some(t))
+        })
+        .filter(|e| {
+            query
+                .agent
+                .as_deref()
+                .is_none_or(|a| e.agent_id().map(AgentId::as_str) == Some(a))
+        })
+        .map(EventJson::from)
+        .collect();
+    Json(events)
+}
+
+async fn heads(State(api): State<SharedApi>) -> Json<Vec<String>> {
+    let api = api.read().unwrap_or_else(PoisonError::into_inner);
+    Json(api.store.heads().iter().map(hash_hex).collect())
+}
+
+async fn list_receipts(State(api): State<SharedApi>) -> Result<Json<Vec<ReceiptJson>>, ApiError> {
+    let api = api.read().unwrap_or_else(PoisonError::into_inner);
+    let receipts = api
+        .receipts
+        .iter()
+        .map(project_receipt)
+        .collect::<Result<_, _>>()?;
+    Ok(Json(receipts))
+}
+
+async fn get_receipt(
+    State(api): State<SharedApi>,
+    Path(tick): Path<u64>,
+) -> Result<Json<ReceiptJson>, ApiError> {
+    let api = api.read().unwrap_or_else(PoisonError::into_inner);
+    let receipt = api
+        .receipts
+        .iter()
+        .find(|r| r.tick == tick)
+        .ok_or_else(|| ApiError::NotFound(format!("receipt of tick {tick}")))?;
+    Ok(Json(project_receipt(receipt)?))
+}
+
+fn project_receipt(receipt: &Receipt) -> Result<ReceiptJson, ApiError> {
+    ReceiptJson::new(receipt).ok_or(ApiError::Internal(format!(
+        "receipt of tick {} cannot be digested",
+        receipt.tick
+    )))
+}
+
+async fn submit(
+    State(api): State<SharedApi>,
+    Json(body): Json<SubmitObservation>,
+) -> Result<(StatusCode, Json<EventJson>), ApiError> {
+    let event = observation(body)?;
+    let mut api = api.write().unwrap_or_else(PoisonError::into_inner);
+    if event.agent_id().is_some() || event.signature().is_some() {
+        let verified = api
+            .verifier
+            .as_deref()
+            .is_some_and(|v| signed_author(&event, v).is_some());
+        if !verified {
+            return Err(ApiError::Unverified(format!(
+                "signature of {} does not verify",
+                event.agent_id().map_or("an unnamed agent", AgentId::as_str)
+            )));
+        }
+    }
+    let status = if api.store.position(&event.event_id()).is_some() {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
+    let json = EventJson::from(&event);
+    api.store.append(event).map_err(ApiError::Rejected)?;
+    Ok((status, Json(json)))
+}
+
+/// The observation event `body` describes
+fn observation(body: SubmitObservation) -> Result<EventEnvelope, ApiError> {
+    let parents = body
+        .parents
+        .iter()
+        .map(|p| parse_hash(p).ok_or(ApiError::BadRequest(format!("malformed parent id {p}"))))
+        .collect::<Result<_, _>>()?;
+    let agent_id = body
+        .agent_id
+        .map(AgentId::new)
+        .transpose()
+        .map_err(|e| ApiError::BadRequest(format!("invalid agent_id: {e}")))?;
+    let signature = body
+        .signature
+        .map(|hex| {
+            let bytes = hex::decode(&hex)
+                .map_err(|_| ApiError::BadRequest(format!("malformed signature {hex}")))?;
+            Signature::new(bytes)
+                .map_err(|e| ApiError::BadRequest(format!("invalid signature: {e}")))
+        })
+        .transpose()?;
+    let payload = CanonicalBytes::from_value(&body.payload)
+        .map_err(|e| ApiError::BadRequest(format!("payload has no canonical encoding: {e}")))?;
+    EventEnvelope::new_observation(payload, parents, body.observation_type, agent_id, signature)
+        .map_err(ApiError::Rejected)
+}
+
+/// Request failures, answered as `{"error": "..."}`
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("{0}")]
+    Unverified(String),
+    #[error("no {0}")]
+    NotFound(String),
+    #[error("event rejected: {0}")]
+    Rejected(EventError),
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unverified(_) => StatusCode::FORBIDDEN,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Rejected(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({ "error": self.to_string() });
+        (self.status(), Json(body)).into_response()
+    }
+}
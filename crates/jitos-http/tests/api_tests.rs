@@ -0,0 +1,269 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! HTTP API Tests
+//!
+//! Reads project the store to JSON; submissions become validated
+//! observation events whose ids match what Rust producers would compute,
+//! and claim an author only with that author's signature.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use jitos_core::canonical;
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::{Hash, Receipt};
+use jitos_http::{hash_hex, router, EventApi, EventJson, ReceiptJson, SharedApi};
+use jitos_net::event_statement;
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+/// Helper: Create an observation carrying `payload`
+fn observation(
+    payload: Value,
+    parents: Vec<Hash>,
+    observation_type: &str,
+    agent: Option<&str>,
+) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&payload).unwrap(),
+        parents,
+        Some(observation_type.to_string()),
+        agent.map(|a| AgentId::new(a).unwrap()),
+        agent.map(|_| Signature::new(vec![0xab, 0xcd]).unwrap()),
+    )
+    .unwrap()
+}
+
+/// Toy keyed signature: only holders of `agent`'s secret can make it
+fn sign_as(agent: &str, statement: &Hash) -> Signature {
+    let mac = canonical::hash_canonical(&(format!("secret-of-{agent}"), statement)).unwrap();
+    Signature::new(mac.0.to_vec()).unwrap()
+}
+
+/// Verifies [`sign_as`] signatures
+fn verifier(agent: &AgentId, statement: &Hash, signature: &Signature) -> bool {
+    *signature == sign_as(agent.as_str(), statement)
+}
+
+/// Send `method path` with an optional JSON `body`; returns status and body
+async fn call(
+    api: &SharedApi,
+    method: &str,
+    path: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let request = Request::builder().method(method).uri(path);
+    let request = match body {
+        Some(body) => request
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+    let response = router(api.clone()).oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn t1_events_heads_and_queries_are_projected_to_json() {
+    let sample = observation(
+        json!({"source": "Ntp", "value_ns": 5, "uncertainty_ns": 1}),
+        vec![],
+        "OBS_CLOCK_SAMPLE_V0",
+        Some("alice"),
+    );
+    let other = observation(json!([1, 2]), vec![sample.event_id()], "OBS_OTHER_V0", None);
+    let mut store = MemoryEventStore::new();
+    store.append(sample.clone()).unwrap();
+    store.append(other.clone()).unwrap();
+    let api = EventApi::new(store).shared();
+
+    let (status, body) = call(
+        &api,
+        "GET",
+        &format!("/events/{}", hash_hex(&sample.event_id())),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let event: EventJson = serde_json::from_value(body).unwrap();
+    assert_eq!(event, EventJson::from(&sample));
+    assert_eq!(event.kind, "Observation");
+    assert_eq!(event.agent_id.as_deref(), Some("alice"));
+    assert_eq!(event.signature.as_deref(), Some("abcd"));
+    assert_eq!(
+        event.payload,
+        Some(json!({"source": "Ntp", "uncertainty_ns": 1, "value_ns": 5}))
+    );
+    assert_eq!(
+        hex::decode(&event.payload_cbor).unwrap(),
+        sample.payload().as_bytes()
+    );
+
+    let (_, body) = call(&api, "GET", "/heads", None).await;
+    assert_eq!(body, json!([hash_hex(&other.event_id())]));
+    let (_, body) = call(&api, "GET", "/events?agent=alice", None).await;
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    let (_, body) = call(&api, "GET", "/events?observation_type=OBS_OTHER_V0", None).await;
+    assert_eq!(body[0]["event_id"], json!(hash_hex(&other.event_id())));
+    let (_, body) = call(&api, "GET", "/events", None).await;
+    assert_eq!(body.as_array().unwrap().len(), 2);
+
+    let (status, body) = call(&api, "GET", &format!("/events/{}", "00".repeat(32)), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body["error"].as_str().unwrap().contains("no event"));
+    let (status, _) = call(&api, "GET", "/events/xyz", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn t2_submissions_are_validated_and_canonicalized() {
+    let api = EventApi::new(MemoryEventStore::new())
+        .with_verifier(verifier)
+        .shared();
+    let unsigned = EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&json!({"unit": "C", "reading": 21})).unwrap(),
+        vec![],
+        Some("OBS_SENSOR_V0".to_string()),
+        None,
+        None,
+    )
+    .unwrap();
+    let signature = sign_as("thermostat", &event_statement(&unsigned.event_id()));
+    let expected = unsigned.with_signature(AgentId::new("thermostat").unwrap(), signature.clone());
+    // Key order and whitespace do not reach the event id.
+    let submission = json!({
+        "observation_type": "OBS_SENSOR_V0",
+        "payload": {"reading": 21, "unit": "C"},
+        "agent_id": "thermostat",
+        "signature": hex::encode(signature.as_bytes()),
+    });
+
+    let (status, body) = call(&api, "POST", "/observations", Some(submission.clone())).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["event_id"], json!(hash_hex(&expected.event_id())));
+    assert_eq!(
+        api.read().unwrap().store().events(),
+        std::slice::from_ref(&expected)
+    );
+    let (status, _) = call(&api, "POST", "/observations", Some(submission)).await;
+    assert_eq!(status, StatusCode::OK, "resubmitting is idempotent");
+    assert_eq!(api.read().unwrap().store().len(), 1);
+
+    let child = json!({
+        "observation_type": "OBS_SENSOR_V0",
+        "payload": 22,
+        "parents": [hash_hex(&expected.event_id())],
+    });
+    let (status, body) = call(&api, "POST", "/observations", Some(child)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["parents"], json!([hash_hex(&expected.event_id())]));
+
+    let orphan = json!({"payload": 1, "parents": ["11".repeat(32)]});
+    let (status, body) = call(&api, "POST", "/observations", Some(orphan)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["error"].as_str().unwrap().contains("unknown parent"));
+    for bad in [
+        json!({"payload": 1, "parents": ["nope"]}),
+        json!({"payload": 1, "agent_id": ""}),
+        json!({"payload": 1, "signature": ""}),
+    ] {
+        let (status, _) = call(&api, "POST", "/observations", Some(bad)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+    assert_eq!(api.read().unwrap().store().len(), 2);
+}
+
+#[tokio::test]
+async fn t3_receipts_are_served_by_tick() {
+    let receipt = |tick, state, applied_slaps| Receipt {
+        tick,
+        state_hash: Hash([state; 32]),
+        applied_slaps,
+        timestamp: tick * 10,
+        signature: None,
+        view_hashes: Default::default(),
+        prev_receipt: None,
+        fuel: None,
+    };
+    let first = receipt(0, 1, vec![Hash([2; 32])]).with_view_hash("clock", Hash([4; 32]));
+    let second = receipt(1, 3, vec![]).chained_to(first.digest().unwrap());
+    let api = EventApi::new(MemoryEventStore::new())
+        .with_receipts(vec![first.clone()])
+        .shared();
+    api.write().unwrap().push_receipt(second.clone());
+
+    let (status, body) = call(&api, "GET", "/receipts", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let receipts: Vec<ReceiptJson> = serde_json::from_value(body).unwrap();
+    assert_eq!(receipts.len(), 2);
+    assert_eq!(receipts[0].digest, hash_hex(&first.digest().unwrap()));
+    assert_eq!(receipts[0].applied_slaps, vec!["02".repeat(32)]);
+    assert_eq!(receipts[0].view_hashes["clock"], "04".repeat(32));
+
+    let (_, body) = call(&api, "GET", "/receipts/1", None).await;
+    let receipt: ReceiptJson = serde_json::from_value(body).unwrap();
+    assert_eq!(receipt, ReceiptJson::new(&second).unwrap());
+    assert_eq!(receipt.prev_receipt, Some(receipts[0].digest.clone()));
+
+    let (status, _) = call(&api, "GET", "/receipts/7", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn t4_authorship_is_accepted_only_with_a_valid_signature() {
+    let submission = |agent: Option<&str>, signature: Option<Signature>| {
+        let mut body = json!({"observation_type": "OBS_SENSOR_V0", "payload": 7});
+        if let Some(agent) = agent {
+            body["agent_id"] = json!(agent);
+        }
+        if let Some(signature) = signature {
+            body["signature"] = json!(hex::encode(signature.as_bytes()));
+        }
+        body
+    };
+    let id = EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&7).unwrap(),
+        vec![],
+        Some("OBS_SENSOR_V0".to_string()),
+        None,
+        None,
+    )
+    .unwrap()
+    .event_id();
+    let statement = event_statement(&id);
+
+    // Without a verifier, only anonymous observations get in.
+    let open = EventApi::new(MemoryEventStore::new()).shared();
+    let signed = submission(Some("alice"), Some(sign_as("alice", &statement)));
+    let (status, body) = call(&open, "POST", "/observations", Some(signed.clone())).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(body["error"].as_str().unwrap().contains("alice"));
+    let (status, _) = call(&open, "POST", "/observations", Some(submission(None, None))).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let api = EventApi::new(MemoryEventStore::new())
+        .with_verifier(verifier)
+        .shared();
+    for forged in [
+        submission(Some("alice"), None),
+        submission(Some("alice"), Some(sign_as("mallory", &statement))),
+        submission(Some("alice"), Some(Signature::new(vec![1, 2]).unwrap())),
+        submission(None, Some(sign_as("alice", &statement))),
+    ] {
+        let (status, _) = call(&api, "POST", "/observations", Some(forged)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+    assert!(api.read().unwrap().store().is_empty());
+
+    let (status, body) = call(&api, "POST", "/observations", Some(signed)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["agent_id"], json!("alice"));
+}
@@ -0,0 +1,203 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Peer Authentication - Handshake Bound to AgentIds
+//!
+//! Every session opens with each peer announcing its [`AgentId`] and a fresh
+//! nonce in a [`Hello`]. Both sides hash the two Hellos into the same
+//! [`transcript`], and each signs the [`statement`] for its [`Role`] with the
+//! key behind its AgentId. A peer whose signature does not verify for the
+//! AgentId it claimed is refused before any event moves.
+//!
+//! The transcript covers both nonces, so a signature cannot be replayed into
+//! another session; the role is signed too, so a peer cannot reflect our own
+//! signature back at us. Keys and signature schemes stay outside this crate,
+//! behind [`PeerSigner`] and [`PeerVerifier`].
+//!
+//! The same keys sign events: an agent signs an event's
+//! [`event_statement`]. Neither the signature nor the `agent_id` is part of
+//! the event id, so an event's author is only believed once its signature
+//! verifies ([`signed_author`]).
+
+use std::io::{Read, Write};
+
+use jitos_core::canonical;
+use jitos_core::events::{AgentId, EventEnvelope, EventId, Signature};
+use jitos_core::Hash;
+use serde::{Deserialize, Serialize};
+
+use crate::peer_clock::{LocalClock, PeerTime, TimeClaim};
+use crate::sync::{unexpected, Message};
+use crate::{read_frame, write_frame, NetError, PROTOCOL_VERSION};
+
+/// Domain separator of handshake transcripts
+pub const HANDSHAKE_V0: &str = "loom-sync-handshake-v0";
+
+/// Domain separator of event signature statements
+pub const EVENT_SIGNATURE_V0: &str = "loom-event-signature-v0";
+
+/// Signs handshake statements with the key behind an [`AgentId`]
+pub trait PeerSigner {
+    fn agent(&self) -> &AgentId;
+
+    /// Signature over `statement`
+    fn sign(&self, statement: &Hash) -> Signature;
+}
+
+/// Checks that a signature was made with the key behind an [`AgentId`]
+pub trait PeerVerifier {
+    /// True if `signature` is `agent`'s valid signature over `statement`
+    fn verify(&self, agent: &AgentId, statement: &Hash, signature: &Signature) -> bool;
+}
+
+impl<F: Fn(&AgentId, &Hash, &Signature) -> bool> PeerVerifier for F {
+    fn verify(&self, agent: &AgentId, statement: &Hash, signature: &Signature) -> bool {
+        self(agent, statement, signature)
+    }
+}
+
+/// A [`PeerSigner`] made of an AgentId and a signing function
+pub struct Identity<F> {
+    agent: AgentId,
+    sign: F,
+}
+
+impl<F: Fn(&Hash) -> Signature> Identity<F> {
+    pub fn new(agent: AgentId, sign: F) -> Self {
+        Self { agent, sign }
+    }
+}
+
+impl<F: Fn(&Hash) -> Signature> PeerSigner for Identity<F> {
+    fn agent(&self) -> &AgentId {
+        &self.agent
+    }
+
+    fn sign(&self, statement: &Hash) -> Signature {
+        (self.sign)(statement)
+    }
+}
+
+/// A peer's opening frame
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hello {
+    pub version: u32,
+    /// Who the peer claims to be
+    pub agent: AgentId,
+    /// Fresh per session
+    pub nonce: Hash,
+    /// The peer's stated time, if it has a clock ([`crate::peer_clock`])
+    pub time: Option<TimeClaim>,
+}
+
+/// Which end of the session a peer is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Role {
+    Initiator,
+    Responder,
+}
+
+/// Hash of a session's two Hellos
+pub fn transcript(initiator: &Hello, responder: &Hello) -> Hash {
+    canonical::hash_canonical(&(HANDSHAKE_V0, initiator, responder)).expect("hellos always encode")
+}
+
+/// What the peer in `role` signs to authenticate
+pub fn statement(transcript: &Hash, role: Role) -> Hash {
+    canonical::hash_canonical(&(transcript, role)).expect("statements always encode")
+}
+
+/// What an agent signs to sign event `event_id`
+pub fn event_statement(event_id: &EventId) -> Hash {
+    canonical::hash_canonical(&(EVENT_SIGNATURE_V0, event_id)).expect("event ids always encode")
+}
+
+/// The agent `event` is attributed to, if its signature verifies as that
+/// agent's; `None` for unattributed, unsigned or forged events
+pub fn signed_author<'a>(
+    event: &'a EventEnvelope,
+    verifier: &dyn PeerVerifier,
+) -> Option<&'a AgentId> {
+    let agent = event.agent_id()?;
+    let signature = event.signature()?;
+    verifier
+        .verify(agent, &event_statement(&event.event_id()), signature)
+        .then_some(agent)
+}
+
+/// Run the handshake as `role`, returning the authenticated peer and, if
+/// both sides have clocks, its time claim
+pub(crate) fn handshake<S: Read + Write>(
+    stream: &mut S,
+    identity: &dyn PeerSigner,
+    verifier: &dyn PeerVerifier,
+    clock: Option<&dyn LocalClock>,
+    nonce: Hash,
+    role: Role,
+) -> Result<(AgentId, Option<PeerTime>), NetError> {
+    let ours = Hello {
+        version: PROTOCOL_VERSION,
+        agent: identity.agent().clone(),
+        nonce,
+        time: clock.map(|clock| clock.claim()),
+    };
+    let monotonic = || clock.map(|clock| clock.monotonic_ns());
+    let (theirs, transcript, their_role, sent_ns, received_ns) = match role {
+        Role::Initiator => {
+            let sent_ns = monotonic();
+            write_frame(stream, &Message::Hello(ours.clone()))?;
+            let theirs = expect_hello(stream)?;
+            let received_ns = monotonic();
+            let transcript = transcript(&ours, &theirs);
+            (theirs, transcript, Role::Responder, sent_ns, received_ns)
+        }
+        Role::Responder => {
+            let theirs = expect_hello(stream)?;
+            let received_ns = monotonic();
+            write_frame(stream, &Message::Hello(ours.clone()))?;
+            let transcript = transcript(&theirs, &ours);
+            (theirs, transcript, Role::Initiator, None, received_ns)
+        }
+    };
+    let prove = |stream: &mut S| {
+        write_frame(
+            stream,
+            &Message::Auth(identity.sign(&statement(&transcript, role))),
+        )
+    };
+    // The initiator proves itself first; the responder signs nothing for a
+    // peer it could not authenticate.
+    if role == Role::Initiator {
+        prove(stream)?;
+    }
+    let proof = match read_frame(stream)? {
+        Message::Auth(proof) => proof,
+        other => return Err(unexpected("Auth", &other)),
+    };
+    if !verifier.verify(&theirs.agent, &statement(&transcript, their_role), &proof) {
+        return Err(NetError::Unauthenticated(theirs.agent));
+    }
+    if role == Role::Responder {
+        prove(stream)?;
+    }
+    let time = theirs
+        .time
+        .zip(received_ns)
+        .map(|(claim, received_ns)| PeerTime {
+            claim,
+            received_ns,
+            round_trip_ns: sent_ns.map(|sent| received_ns.saturating_sub(sent)),
+        });
+    Ok((theirs.agent, time))
+}
+
+fn expect_hello<R: Read>(stream: &mut R) -> Result<Hello, NetError> {
+    match read_frame(stream)? {
+        Message::Hello(hello) if hello.version == PROTOCOL_VERSION => Ok(hello),
+        Message::Hello(hello) => Err(NetError::Version {
+            ours: PROTOCOL_VERSION,
+            theirs: hello.version,
+        }),
+        other => Err(unexpected("Hello", &other)),
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Framing - Length-Prefixed Canonical CBOR
+//!
+//! A frame is a big-endian `u32` byte length followed by that many bytes of
+//! canonical CBOR. Frames longer than [`MAX_FRAME_LEN`] are refused on both
+//! ends, so a peer cannot make us allocate without bound.
+
+use std::io::{Read, Write};
+
+use jitos_core::canonical;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::NetError;
+
+/// Largest frame payload accepted or sent, in bytes
+pub const MAX_FRAME_LEN: u32 = 16 * 1024 * 1024;
+
+/// Encode `value` and write it as one frame
+///
+/// # Errors
+///
+/// [`NetError::FrameTooLarge`] if the encoding exceeds [`MAX_FRAME_LEN`];
+/// otherwise encoding and I/O errors.
+pub fn write_frame<W: Write, T: Serialize>(writer: &mut W, value: &T) -> Result<(), NetError> {
+    let bytes = canonical::encode(value)?;
+    let len = u32::try_from(bytes.len())
+        .ok()
+        .filter(|len| *len <= MAX_FRAME_LEN)
+        .ok_or(NetError::FrameTooLarge(bytes.len() as u64))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(&bytes)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read one frame and decode it
+///
+/// # Errors
+///
+/// [`NetError::FrameTooLarge`] if the announced length exceeds
+/// [`MAX_FRAME_LEN`]; otherwise I/O and decoding errors (including a
+/// payload that is not canonical CBOR).
+pub fn read_frame<R: Read, T: DeserializeOwned>(reader: &mut R) -> Result<T, NetError> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len);
+    if len > MAX_FRAME_LEN {
+        return Err(NetError::FrameTooLarge(len.into()));
+    }
+    let mut bytes = vec![0; len as usize];
+    reader.read_exact(&mut bytes)?;
+    Ok(canonical::decode(&bytes)?)
+}
@@ -0,0 +1,49 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! # jitos-net
+//!
+//! Moves worldline events between Loom nodes.
+//!
+//! Two peers synchronize their event DAGs over a byte stream (TCP in
+//! practice) carrying length-prefixed canonical CBOR [`frame`]s. Each side
+//! proves who it is ([`auth`]), learns the other's heads, works out which
+//! events the other is missing ([`negotiate`]), sends exactly those in
+//! worldline (topological) order, and validates everything it receives
+//! before it enters the store, quarantining events from authors outside
+//! its [`trust`] roots. Peers also trade time claims, which become
+//! PeerClaim clock samples ([`peer_clock`]), and a peer may subscribe to
+//! a filtered slice of the DAG instead of all of it ([`subscribe`]). See [`sync`] for the exchange.
+//!
+//! With the `libp2p` feature, nodes can instead gossip their heads and
+//! fetch events over a libp2p swarm (`p2p`), through the same validation
+//! and trust pipeline.
+
+pub mod auth;
+pub mod frame;
+pub mod negotiate;
+#[cfg(feature = "libp2p")]
+pub mod p2p;
+pub mod peer_clock;
+pub mod subscribe;
+pub mod sync;
+pub mod trust;
+
+pub use auth::{
+    event_statement, signed_author, statement, transcript, Hello, Identity, PeerSigner,
+    PeerVerifier, Role, EVENT_SIGNATURE_V0, HANDSHAKE_V0,
+};
+pub use frame::{read_frame, write_frame, MAX_FRAME_LEN};
+pub use negotiate::{fingerprint, IdRange, RangeItem, Reconciler, BRANCH, LEAF};
+#[cfg(feature = "libp2p")]
+pub use p2p::{agent_of, P2pEvent, P2pNode, FETCH_PROTOCOL, HEADS_TOPIC};
+pub use peer_clock::{clock_observations, LocalClock, PeerTime, TimeClaim};
+pub use subscribe::{
+    slice, AncestorStub, PartialReplica, ReplicaError, Slice, SliceItem, Subscription,
+};
+pub use sync::{
+    missing_for, Message, NetError, SyncNode, SyncReport, MAX_ROUNDS, PROTOCOL_VERSION,
+};
+pub use trust::{
+    Quarantine, QuarantineReason, Quarantined, TrustPolicy, TrustRoots, TRUST_POLICY_V0,
+};
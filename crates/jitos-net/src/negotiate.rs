@@ -0,0 +1,183 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Have/Want Negotiation - Range-Based Set Reconciliation
+//!
+//! Each peer sorts its EventIds and summarizes ranges of the id space as
+//! `(count, fingerprint)`. A range whose summaries agree holds the same ids
+//! on both sides and is settled in one comparison. A range that disagrees is
+//! answered with the full id list if the answering side holds at most
+//! [`LEAF`] ids in it, or else split into up to [`BRANCH`] sub-ranges
+//! summarized again. An id list is answered with the ids the other side
+//! wants. Rounds alternate until one side has nothing to say.
+//!
+//! Summaries are exact (no probabilistic filter), so the ids a peer learns
+//! it must send are exactly the ones the other side is missing, and traffic
+//! grows with the difference rather than with the DAG.
+
+use std::collections::BTreeSet;
+
+use jitos_core::canonical;
+use jitos_core::events::EventId;
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use serde::{Deserialize, Serialize};
+
+/// Sub-ranges a disagreeing range is split into
+pub const BRANCH: usize = 16;
+/// Most ids answered as a list instead of split further
+pub const LEAF: usize = 32;
+
+/// Ids from `lower` (inclusive) up to `upper` (exclusive; `None` is the end
+/// of the id space)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdRange {
+    pub lower: EventId,
+    pub upper: Option<EventId>,
+}
+
+impl IdRange {
+    /// The whole id space
+    pub fn full() -> Self {
+        Self {
+            lower: Hash([0; 32]),
+            upper: None,
+        }
+    }
+
+    /// The ids of `sorted` inside the range
+    pub fn slice<'a>(&self, sorted: &'a [EventId]) -> &'a [EventId] {
+        let start = sorted.partition_point(|id| *id < self.lower);
+        let end = match &self.upper {
+            Some(upper) => sorted.partition_point(|id| id < upper),
+            None => sorted.len(),
+        };
+        &sorted[start..end.max(start)]
+    }
+}
+
+/// One statement about a range
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RangeItem {
+    /// The sender holds `count` ids in `range`, summarized by `fingerprint`
+    Fingerprint {
+        range: IdRange,
+        count: u64,
+        fingerprint: Hash,
+    },
+    /// The sender holds exactly `ids` in `range`
+    Have { range: IdRange, ids: Vec<EventId> },
+    /// The sender lacks these ids, which the receiver listed
+    Want(Vec<EventId>),
+}
+
+/// Summary of a sorted id list
+pub fn fingerprint(ids: &[EventId]) -> Hash {
+    canonical::hash_canonical(&ids).expect("id lists always encode")
+}
+
+/// One side of a negotiation
+#[derive(Debug, Clone)]
+pub struct Reconciler {
+    /// Every id in the store, ascending
+    ids: Vec<EventId>,
+    /// Ids the peer has been found to lack
+    to_send: BTreeSet<EventId>,
+}
+
+impl Reconciler {
+    pub fn new(store: &MemoryEventStore) -> Self {
+        let mut ids: Vec<EventId> = store.events().iter().map(|e| e.event_id()).collect();
+        ids.sort();
+        Self {
+            ids,
+            to_send: BTreeSet::new(),
+        }
+    }
+
+    /// Opening statement: a summary of the whole id space
+    pub fn start(&self) -> Vec<RangeItem> {
+        vec![self.summarize(IdRange::full())]
+    }
+
+    /// Answer the peer's statements; empty once nothing is left to settle
+    pub fn reply(&mut self, items: &[RangeItem]) -> Vec<RangeItem> {
+        let mut reply = Vec::new();
+        for item in items {
+            match item {
+                RangeItem::Fingerprint {
+                    range,
+                    count,
+                    fingerprint: theirs,
+                } => {
+                    let ours = range.slice(&self.ids);
+                    if ours.len() as u64 == *count && fingerprint(ours) == *theirs {
+                        continue;
+                    }
+                    if ours.len() <= LEAF {
+                        reply.push(RangeItem::Have {
+                            range: range.clone(),
+                            ids: ours.to_vec(),
+                        });
+                    } else {
+                        reply.extend(self.split(range));
+                    }
+                }
+                RangeItem::Have { range, ids } => {
+                    let theirs: BTreeSet<&EventId> = ids.iter().collect();
+                    let ours = range.slice(&self.ids);
+                    self.to_send
+                        .extend(ours.iter().filter(|id| !theirs.contains(id)));
+                    let wanted: Vec<EventId> = ids
+                        .iter()
+                        .filter(|id| self.ids.binary_search(id).is_err())
+                        .copied()
+                        .collect();
+                    if !wanted.is_empty() {
+                        reply.push(RangeItem::Want(wanted));
+                    }
+                }
+                RangeItem::Want(ids) => {
+                    self.to_send
+                        .extend(ids.iter().filter(|id| self.ids.binary_search(id).is_ok()));
+                }
+            }
+        }
+        reply
+    }
+
+    /// Ids the peer turned out to lack, ascending
+    pub fn to_send(&self) -> &BTreeSet<EventId> {
+        &self.to_send
+    }
+
+    fn summarize(&self, range: IdRange) -> RangeItem {
+        let ids = range.slice(&self.ids);
+        RangeItem::Fingerprint {
+            count: ids.len() as u64,
+            fingerprint: fingerprint(ids),
+            range,
+        }
+    }
+
+    /// `range` cut at our ids into up to [`BRANCH`] summarized sub-ranges
+    fn split(&self, range: &IdRange) -> Vec<RangeItem> {
+        let ours = range.slice(&self.ids);
+        let chunk = ours.len().div_ceil(BRANCH);
+        let bounds = ours.iter().step_by(chunk).skip(1);
+        let mut lower = range.lower;
+        let mut items = Vec::with_capacity(BRANCH);
+        for bound in bounds {
+            items.push(self.summarize(IdRange {
+                lower,
+                upper: Some(*bound),
+            }));
+            lower = *bound;
+        }
+        items.push(self.summarize(IdRange {
+            lower,
+            upper: range.upper,
+        }));
+        items
+    }
+}
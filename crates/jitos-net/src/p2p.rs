@@ -0,0 +1,385 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! libp2p Transport - Gossiped Heads, Fetched Events
+//!
+//! An alternative to the TCP sessions of [`crate::sync`] for nodes that
+//! find each other on a libp2p network (feature `libp2p`):
+//!
+//! - heads are announced on the gossipsub topic [`HEADS_TOPIC`] as a
+//!   [`Message::Heads`] frame body, signed by the announcing peer
+//! - a peer that hears of heads it lacks, or that has just connected,
+//!   sends a [`Message::Heads`] request with its own heads on
+//!   [`FETCH_PROTOCOL`] and gets back a [`Message::Events`] response with
+//!   the events it is missing, in worldline order
+//!
+//! Requests and responses are [`crate::frame`]s, so the same frame limit
+//! applies. Connections are authenticated by noise, so the transport peer
+//! of a fetched event is the remote [`PeerId`], named as an [`AgentId`] by
+//! [`agent_of`]. Fetched events then take the TCP path exactly: they are
+//! validated into the node's store and tagged with that peer, or
+//! quarantined by its trust roots ([`crate::trust`]). Trust roots name
+//! libp2p peers by their base58 peer id.
+
+use std::io;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, StreamExt};
+use jitos_core::canonical;
+use jitos_core::events::{AgentId, EventId};
+use libp2p::gossipsub::{self, IdentTopic, MessageAuthenticity, PublishError};
+use libp2p::request_response::{self, ProtocolSupport};
+use libp2p::swarm::{NetworkBehaviour, SwarmEvent};
+use libp2p::{identity, noise, tcp, yamux, Multiaddr, PeerId, StreamProtocol, Swarm};
+
+use crate::sync::{missing_for, Message, NetError, SyncNode};
+use crate::MAX_FRAME_LEN;
+
+/// Gossipsub topic of head announcements
+pub const HEADS_TOPIC: &str = "loom/heads/v0";
+
+/// Request/response protocol of event fetches
+pub const FETCH_PROTOCOL: &str = "/loom/fetch/0";
+
+/// The [`AgentId`] a libp2p peer is known by: its base58 peer id
+pub fn agent_of(peer: &PeerId) -> AgentId {
+    AgentId::new(peer.to_base58()).expect("peer ids are never empty")
+}
+
+/// What driving a [`P2pNode`] produced
+#[derive(Debug)]
+pub enum P2pEvent {
+    /// The node listens on this address
+    Listening(Multiaddr),
+    /// A peer connected; a fetch from it is under way
+    Connected(AgentId),
+    /// A peer announced its heads
+    Announced { peer: AgentId, heads: Vec<EventId> },
+    /// A peer answered a fetch
+    Fetched {
+        peer: AgentId,
+        /// Events that were new to the store
+        received: usize,
+        /// Events that trust policy quarantined
+        quarantined: usize,
+    },
+    /// A peer's fetch was answered
+    Served { peer: AgentId, sent: usize },
+    /// A fetch from a peer failed, or it sent an invalid event
+    Failed { peer: AgentId, error: NetError },
+}
+
+#[derive(NetworkBehaviour)]
+struct Behaviour {
+    gossipsub: gossipsub::Behaviour,
+    fetch: request_response::Behaviour<FrameCodec>,
+}
+
+/// A [`SyncNode`] on a libp2p swarm
+pub struct P2pNode {
+    node: SyncNode,
+    swarm: Swarm<Behaviour>,
+    topic: IdentTopic,
+}
+
+impl P2pNode {
+    /// Put `node` on a swarm keyed by `keypair`, over TCP with noise and
+    /// yamux; must be called within a tokio runtime
+    ///
+    /// # Errors
+    ///
+    /// [`NetError::P2p`] if the swarm cannot be built.
+    pub fn new(node: SyncNode, keypair: identity::Keypair) -> Result<Self, NetError> {
+        let mut swarm = libp2p::SwarmBuilder::with_existing_identity(keypair)
+            .with_tokio()
+            .with_tcp(
+                tcp::Config::default().nodelay(true),
+                noise::Config::new,
+                yamux::Config::default,
+            )
+            .map_err(p2p)?
+            .with_behaviour(|key| {
+                let config = gossipsub::ConfigBuilder::default()
+                    .heartbeat_interval(Duration::from_millis(250))
+                    .max_transmit_size(MAX_FRAME_LEN as usize)
+                    .build()?;
+                let gossipsub =
+                    gossipsub::Behaviour::new(MessageAuthenticity::Signed(key.clone()), config)?;
+                let fetch = request_response::Behaviour::new(
+                    [(StreamProtocol::new(FETCH_PROTOCOL), ProtocolSupport::Full)],
+                    request_response::Config::default(),
+                );
+                Ok(Behaviour { gossipsub, fetch })
+            })
+            .map_err(p2p)?
+            .with_swarm_config(|config| {
+                config.with_idle_connection_timeout(Duration::from_secs(60))
+            })
+            .build();
+        let topic = IdentTopic::new(HEADS_TOPIC);
+        swarm
+            .behaviour_mut()
+            .gossipsub
+            .subscribe(&topic)
+            .map_err(p2p)?;
+        Ok(Self { node, swarm, topic })
+    }
+
+    pub fn peer_id(&self) -> PeerId {
+        *self.swarm.local_peer_id()
+    }
+
+    pub fn node(&self) -> &SyncNode {
+        &self.node
+    }
+
+    /// The node, for appending local events and setting trust roots
+    pub fn node_mut(&mut self) -> &mut SyncNode {
+        &mut self.node
+    }
+
+    /// Listen on `addr`; the bound address arrives as [`P2pEvent::Listening`]
+    ///
+    /// # Errors
+    ///
+    /// [`NetError::P2p`] if the transport refuses the address.
+    pub fn listen(&mut self, addr: Multiaddr) -> Result<(), NetError> {
+        self.swarm.listen_on(addr).map_err(p2p)?;
+        Ok(())
+    }
+
+    /// Connect to `addr`
+    ///
+    /// # Errors
+    ///
+    /// [`NetError::P2p`] if the dial cannot start.
+    pub fn dial(&mut self, addr: Multiaddr) -> Result<(), NetError> {
+        self.swarm.dial(addr).map_err(p2p)
+    }
+
+    /// Announce the store's heads to subscribed peers, returning false if
+    /// none is subscribed yet
+    ///
+    /// # Errors
+    ///
+    /// [`NetError::P2p`] if publishing fails for another reason.
+    pub fn announce(&mut self) -> Result<bool, NetError> {
+        let heads = canonical::encode(&Message::Heads(self.node.store().heads()))?;
+        match self
+            .swarm
+            .behaviour_mut()
+            .gossipsub
+            .publish(self.topic.clone(), heads)
+        {
+            Ok(_) | Err(PublishError::Duplicate) => Ok(true),
+            Err(PublishError::InsufficientPeers) => Ok(false),
+            Err(e) => Err(p2p(e)),
+        }
+    }
+
+    /// Drive the swarm until something happens
+    pub async fn next_event(&mut self) -> P2pEvent {
+        loop {
+            let event = match self.swarm.select_next_some().await {
+                SwarmEvent::NewListenAddr { address, .. } => Some(P2pEvent::Listening(address)),
+                SwarmEvent::ConnectionEstablished {
+                    peer_id,
+                    num_established,
+                    ..
+                } if num_established.get() == 1 => {
+                    self.fetch(&peer_id);
+                    Some(P2pEvent::Connected(agent_of(&peer_id)))
+                }
+                SwarmEvent::Behaviour(BehaviourEvent::Gossipsub(event)) => self.on_gossip(event),
+                SwarmEvent::Behaviour(BehaviourEvent::Fetch(event)) => self.on_fetch(event),
+                _ => None,
+            };
+            if let Some(event) = event {
+                return event;
+            }
+        }
+    }
+
+    /// Ask `peer` for the events our heads lack
+    fn fetch(&mut self, peer: &PeerId) {
+        let heads = Message::Heads(self.node.store().heads());
+        self.swarm.behaviour_mut().fetch.send_request(peer, heads);
+    }
+
+    fn on_gossip(&mut self, event: gossipsub::Event) -> Option<P2pEvent> {
+        let gossipsub::Event::Message {
+            propagation_source,
+            message,
+            ..
+        } = event
+        else {
+            return None;
+        };
+        let Ok(Message::Heads(heads)) = canonical::decode(&message.data) else {
+            return None;
+        };
+        let source = message
+            .source
+            .filter(|source| self.swarm.is_connected(source))
+            .unwrap_or(propagation_source);
+        let store = self.node.store();
+        let quarantine = self.node.quarantine();
+        if heads
+            .iter()
+            .any(|h| store.position(h).is_none() && !quarantine.contains(h))
+        {
+            self.fetch(&source);
+        }
+        Some(P2pEvent::Announced {
+            peer: agent_of(&message.source.unwrap_or(propagation_source)),
+            heads,
+        })
+    }
+
+    fn on_fetch(&mut self, event: request_response::Event<Message, Message>) -> Option<P2pEvent> {
+        match event {
+            request_response::Event::Message {
+                peer,
+                message:
+                    request_response::Message::Request {
+                        request, channel, ..
+                    },
+            } => {
+                let Message::Heads(heads) = request else {
+                    return None;
+                };
+                let events = missing_for(self.node.store(), &heads);
+                let sent = events.len();
+                // A dropped channel means the peer went away; nothing to do.
+                let _ = self
+                    .swarm
+                    .behaviour_mut()
+                    .fetch
+                    .send_response(channel, Message::Events(events));
+                Some(P2pEvent::Served {
+                    peer: agent_of(&peer),
+                    sent,
+                })
+            }
+            request_response::Event::Message {
+                peer,
+                message: request_response::Message::Response { response, .. },
+            } => {
+                let agent = agent_of(&peer);
+                let result = match response {
+                    Message::Events(events) => self.node.ingest(events, &agent),
+                    other => Err(crate::sync::unexpected("Events", &other)),
+                };
+                Some(match result {
+                    Ok((received, quarantined)) => {
+                        if received > 0 {
+                            // Relay: our new heads may be news to others.
+                            let _ = self.announce();
+                        }
+                        P2pEvent::Fetched {
+                            peer: agent,
+                            received,
+                            quarantined,
+                        }
+                    }
+                    Err(error) => P2pEvent::Failed { peer: agent, error },
+                })
+            }
+            request_response::Event::OutboundFailure { peer, error, .. } => {
+                Some(P2pEvent::Failed {
+                    peer: agent_of(&peer),
+                    error: p2p(error),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn p2p(error: impl std::fmt::Display) -> NetError {
+    NetError::P2p(error.to_string())
+}
+
+/// [`crate::frame`]s over libp2p streams
+#[derive(Debug, Clone, Default)]
+struct FrameCodec;
+
+impl FrameCodec {
+    async fn read<T: AsyncRead + Unpin + Send>(io: &mut T) -> io::Result<Message> {
+        let mut len = [0; 4];
+        io.read_exact(&mut len).await?;
+        let len = u32::from_be_bytes(len);
+        if len > MAX_FRAME_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                NetError::FrameTooLarge(len.into()),
+            ));
+        }
+        let mut bytes = vec![0; len as usize];
+        io.read_exact(&mut bytes).await?;
+        canonical::decode(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    async fn write<T: AsyncWrite + Unpin + Send>(io: &mut T, message: Message) -> io::Result<()> {
+        let bytes = canonical::encode(&message)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let len = u32::try_from(bytes.len())
+            .ok()
+            .filter(|len| *len <= MAX_FRAME_LEN)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    NetError::FrameTooLarge(bytes.len() as u64),
+                )
+            })?;
+        io.write_all(&len.to_be_bytes()).await?;
+        io.write_all(&bytes).await?;
+        io.close().await
+    }
+}
+
+#[async_trait]
+impl request_response::Codec for FrameCodec {
+    type Protocol = StreamProtocol;
+    type Request = Message;
+    type Response = Message;
+
+    async fn read_request<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<Message>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        Self::read(io).await
+    }
+
+    async fn read_response<T>(&mut self, _: &StreamProtocol, io: &mut T) -> io::Result<Message>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        Self::read(io).await
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        request: Message,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        Self::write(io, request).await
+    }
+
+    async fn write_response<T>(
+        &mut self,
+        _: &StreamProtocol,
+        io: &mut T,
+        response: Message,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        Self::write(io, response).await
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Peer Clock Claims - PeerClaim Samples From Sync Sessions
+//!
+//! A node with a [`LocalClock`] states its time in every [`Hello`] it sends
+//! as a [`TimeClaim`]. The Hello is part of the signed handshake transcript,
+//! so a claim is attributed to the peer only once the peer authenticates.
+//!
+//! For each authenticated claim the node emits two observations tagged
+//! `OBS_CLOCK_SAMPLE_V0` ([`clock_observations`]): a
+//! [`ClockSource::Monotonic`] sample read the moment the peer's Hello
+//! arrived, then the [`ClockSource::PeerClaim`] sample citing it as parent
+//! and naming the peer as `agent_id`. Folded in that order, clock views see
+//! the claim paired with the local monotonic reading it was received at.
+//!
+//! The initiator sent its own Hello before the peer's arrived, so the claim
+//! was made within that round trip; it widens the claim's uncertainty by
+//! the round trip. The responder has no such bound and keeps the claimed
+//! uncertainty.
+//!
+//! [`Hello`]: crate::Hello
+
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventError};
+use jitos_views::{ClockSample, ClockSource, OBS_CLOCK_SAMPLE_V0};
+use serde::{Deserialize, Serialize};
+
+/// Clocks a node reads during the handshake
+pub trait LocalClock {
+    /// Local monotonic reading, in nanoseconds
+    fn monotonic_ns(&self) -> u64;
+
+    /// The time this node claims to peers (e.g. its NTP-disciplined clock)
+    fn claim(&self) -> TimeClaim;
+}
+
+/// A peer's stated time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeClaim {
+    pub value_ns: u64,
+    pub uncertainty_ns: u64,
+}
+
+/// An authenticated claim and when it was received
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerTime {
+    pub claim: TimeClaim,
+    /// Local monotonic reading when the claim arrived
+    pub received_ns: u64,
+    /// Monotonic time from sending our Hello to receiving the peer's (the
+    /// initiator only)
+    pub round_trip_ns: Option<u64>,
+}
+
+impl PeerTime {
+    /// The PeerClaim sample: the claim, its uncertainty widened by the
+    /// round trip
+    pub fn sample(&self) -> ClockSample {
+        ClockSample {
+            source: ClockSource::PeerClaim,
+            value_ns: self.claim.value_ns,
+            uncertainty_ns: self
+                .claim
+                .uncertainty_ns
+                .saturating_add(self.round_trip_ns.unwrap_or(0)),
+        }
+    }
+}
+
+/// The monotonic receive sample and the PeerClaim sample citing it, in
+/// that order
+pub fn clock_observations(
+    peer: &AgentId,
+    time: &PeerTime,
+) -> Result<[EventEnvelope; 2], EventError> {
+    let received = EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&ClockSample {
+            source: ClockSource::Monotonic,
+            value_ns: time.received_ns,
+            uncertainty_ns: 0,
+        })?,
+        vec![],
+        Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+        None,
+        None,
+    )?;
+    let claim = EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&time.sample())?,
+        vec![received.event_id()],
+        Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+        Some(peer.clone()),
+        None,
+    )?;
+    Ok([received, claim])
+}
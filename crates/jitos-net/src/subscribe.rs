@@ -0,0 +1,293 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Filtered Sync - Parent-Closed Partial Replicas
+//!
+//! A subscriber asks a peer for the events matching a [`Subscription`]
+//! (observation types, authors) instead of the whole DAG. Events only make
+//! sense with their ancestry, so the peer answers with a parent-closed
+//! [`Slice`]: every matching event in full, and an [`AncestorStub`] (id,
+//! kind, parents) for each ancestor that does not match. Following parents
+//! from any event in a slice ends at roots, never at an unknown id.
+//!
+//! A [`PartialReplica`] holds the result. Full events are verified as
+//! usual: their ids are recomputed when decoded, and an event whose parents
+//! are all full goes through [`validate_event`]. A stub cannot be verified
+//! without its payload, so it is taken on the authenticated peer's word;
+//! the replica only checks that its parents are known. A stub is replaced
+//! by the full event if it later arrives, provided the two agree on kind
+//! and parents. Rules that look at the kind of a parent (a Decision's
+//! PolicyContext, a Commit's Decision) are only checked when every parent
+//! is a full event.
+//!
+//! The replica is parent-closed, so its heads stand for everything it holds
+//! and a later subscription only receives what is new.
+
+use std::collections::{BTreeSet, HashMap};
+
+use jitos_core::events::{
+    validate_event, AgentId, EventEnvelope, EventError, EventId, EventKind, EventStore,
+};
+use jitos_core::store::MemoryEventStore;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::sync::missing_for;
+
+/// Which events a subscriber wants; an empty list does not filter
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subscription {
+    /// Observation types to receive (only observations match when set)
+    pub observation_types: Vec<String>,
+    /// Authors (`agent_id`) to receive
+    pub agents: Vec<AgentId>,
+}
+
+impl Subscription {
+    /// Every event
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Observations of the given types
+    pub fn observation_types<I, S>(types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            observation_types: types.into_iter().map(Into::into).collect(),
+            agents: Vec::new(),
+        }
+    }
+
+    /// Also require one of `agents` as author
+    pub fn from_agents(mut self, agents: impl IntoIterator<Item = AgentId>) -> Self {
+        self.agents = agents.into_iter().collect();
+        self
+    }
+
+    pub fn matches(&self, event: &EventEnvelope) -> bool {
+        let typed = self.observation_types.is_empty()
+            || matches!(event.kind(), EventKind::Observation)
+                && event
+                    .observation_type()
+                    .is_some_and(|t| self.observation_types.iter().any(|o| o == t));
+        let authored = self.agents.is_empty()
+            || event
+                .agent_id()
+                .is_some_and(|agent| self.agents.contains(agent));
+        typed && authored
+    }
+}
+
+/// An ancestor sent without its payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AncestorStub {
+    pub event: EventId,
+    pub kind: EventKind,
+    pub parents: Vec<EventId>,
+}
+
+impl AncestorStub {
+    pub fn of(event: &EventEnvelope) -> Self {
+        Self {
+            event: event.event_id(),
+            kind: event.kind().clone(),
+            parents: event.parents().to_vec(),
+        }
+    }
+}
+
+/// One entry of a [`Slice`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SliceItem {
+    Event(EventEnvelope),
+    Stub(AncestorStub),
+}
+
+impl SliceItem {
+    pub fn id(&self) -> EventId {
+        match self {
+            SliceItem::Event(event) => event.event_id(),
+            SliceItem::Stub(stub) => stub.event,
+        }
+    }
+}
+
+/// Parent-closed answer to a subscription, in worldline order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Slice {
+    pub items: Vec<SliceItem>,
+}
+
+impl Slice {
+    /// Full events in the slice
+    pub fn events(&self) -> impl Iterator<Item = &EventEnvelope> {
+        self.items.iter().filter_map(|item| match item {
+            SliceItem::Event(event) => Some(event),
+            SliceItem::Stub(_) => None,
+        })
+    }
+}
+
+/// The slice of `store` matching `subscription` that a replica with `heads`
+/// lacks
+///
+/// Events the replica lacks that neither match nor are ancestors of a match
+/// are left out.
+pub fn slice(store: &MemoryEventStore, subscription: &Subscription, heads: &[EventId]) -> Slice {
+    let missing = missing_for(store, heads);
+    let mut needed = vec![false; missing.len()];
+    let mut wanted: BTreeSet<EventId> = BTreeSet::new();
+    for (i, event) in missing.iter().enumerate().rev() {
+        let id = event.event_id();
+        if subscription.matches(event) || wanted.contains(&id) {
+            needed[i] = true;
+            wanted.extend(event.parents().iter().copied());
+        }
+    }
+    let items = missing
+        .into_iter()
+        .zip(needed)
+        .filter(|(_, needed)| *needed)
+        .map(|(event, _)| {
+            if subscription.matches(&event) {
+                SliceItem::Event(event)
+            } else {
+                SliceItem::Stub(AncestorStub::of(&event))
+            }
+        })
+        .collect();
+    Slice { items }
+}
+
+/// Events matching a subscription, with stubs for their other ancestors
+#[derive(Debug, Clone, Default)]
+pub struct PartialReplica {
+    /// Full events, in arrival order
+    events: Vec<EventEnvelope>,
+    index: HashMap<EventId, usize>,
+    stubs: HashMap<EventId, AncestorStub>,
+    /// Ids, full or stub, that are a parent of another
+    parents: BTreeSet<EventId>,
+    /// Every id held, in arrival order
+    order: Vec<EventId>,
+}
+
+impl PartialReplica {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Full events, in arrival order (topological, except that an event
+    /// replacing a stub follows the descendants that arrived before it)
+    pub fn events(&self) -> &[EventEnvelope] {
+        &self.events
+    }
+
+    pub fn stub(&self, id: &EventId) -> Option<&AncestorStub> {
+        self.stubs.get(id)
+    }
+
+    pub fn stubs(&self) -> usize {
+        self.stubs.len()
+    }
+
+    /// True if `id` is held, in full or as a stub
+    pub fn knows(&self, id: &EventId) -> bool {
+        self.index.contains_key(id) || self.stubs.contains_key(id)
+    }
+
+    /// Ids no held event or stub names as parent, in arrival order
+    pub fn heads(&self) -> Vec<EventId> {
+        self.order
+            .iter()
+            .filter(|id| !self.parents.contains(id))
+            .copied()
+            .collect()
+    }
+
+    /// Add `slice` item by item; returns how many full events were new
+    ///
+    /// # Errors
+    ///
+    /// [`ReplicaError`] for the first item that would break parent closure
+    /// or fails validation; items before it stay.
+    pub fn apply(&mut self, slice: Slice) -> Result<usize, ReplicaError> {
+        let mut added = 0;
+        for item in slice.items {
+            if self.insert(item)? {
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Add one item, returning true for a new full event
+    fn insert(&mut self, item: SliceItem) -> Result<bool, ReplicaError> {
+        let id = item.id();
+        if self.index.contains_key(&id) {
+            return Ok(false);
+        }
+        let parents = match &item {
+            SliceItem::Event(event) => event.parents(),
+            SliceItem::Stub(stub) => &stub.parents,
+        };
+        if let Some(parent) = parents.iter().find(|p| !self.knows(p)) {
+            return Err(ReplicaError::NotClosed {
+                event: id,
+                parent: *parent,
+            });
+        }
+        match item {
+            SliceItem::Stub(stub) => {
+                if !self.stubs.contains_key(&id) {
+                    self.parents.extend(stub.parents.iter().copied());
+                    self.stubs.insert(id, stub);
+                    self.order.push(id);
+                }
+                Ok(false)
+            }
+            SliceItem::Event(event) => {
+                if let Some(stub) = self.stubs.get(&id) {
+                    if stub.kind != *event.kind() || stub.parents != event.parents() {
+                        return Err(ReplicaError::StubMismatch(id));
+                    }
+                }
+                if event.parents().iter().all(|p| self.index.contains_key(p)) {
+                    validate_event(&event, self)
+                        .map_err(|source| ReplicaError::Invalid { event: id, source })?;
+                }
+                if self.stubs.remove(&id).is_none() {
+                    self.order.push(id);
+                }
+                self.parents.extend(event.parents().iter().copied());
+                self.index.insert(id, self.events.len());
+                self.events.push(event);
+                Ok(true)
+            }
+        }
+    }
+}
+
+impl EventStore for PartialReplica {
+    fn get(&self, event_id: &EventId) -> Option<&EventEnvelope> {
+        self.index.get(event_id).map(|&i| &self.events[i])
+    }
+}
+
+/// Partial replica errors
+#[derive(Debug, Error)]
+pub enum ReplicaError {
+    #[error("slice is not parent-closed: {event} names unknown parent {parent}")]
+    NotClosed { event: EventId, parent: EventId },
+    #[error("event {0} does not match the stub held for it")]
+    StubMismatch(EventId),
+    #[error("invalid event {event}: {source}")]
+    Invalid {
+        event: EventId,
+        #[source]
+        source: EventError,
+    },
+}
@@ -0,0 +1,637 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Peer Sync - Event DAG Exchange
+//!
+//! One sync session between an initiator and a responder:
+//!
+//! 1. both send [`Message::Hello`], check the protocol version and
+//!    authenticate each other ([`crate::auth`])
+//! 2. both send [`Message::Heads`], their store's heads
+//! 3. [`Message::Reconcile`] rounds (see [`crate::negotiate`]) tell the
+//!    initiator which of its events the responder lacks; if the initiator
+//!    already holds every responder head, it opens with nothing to reconcile
+//!    and the responder's heads alone say what it has
+//! 4. the initiator sends [`Message::Events`] with exactly those events
+//! 5. the responder ingests them and answers with the events the initiator
+//!    lacks; having just learned every initiator head, it needs no
+//!    negotiation to know which
+//!
+//! Events travel in worldline order, which is topological, so parents always
+//! arrive before their children. Every received event goes through
+//! [`MemoryEventStore::append`], which checks its id and that its parents are
+//! present; the first invalid event ends the session. Imported events are
+//! tagged with the authenticated peer ([`SyncNode::imported_from`]), and
+//! events from authors outside the node's trust roots, or whose claimed
+//! author did not sign them, are quarantined instead of merged
+//! ([`crate::trust`]). A node with a clock also turns
+//! the peer's time claim into clock observations ([`crate::peer_clock`]).
+//!
+//! Instead of [`Message::Heads`], an initiator may send
+//! [`Message::Subscribe`] to receive only a filtered, parent-closed slice
+//! of the responder's DAG ([`crate::subscribe`]).
+//!
+//! The peers alternate, each reading before it writes a batch, so neither
+//! blocks on a full socket buffer while the other does the same.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+use jitos_core::canonical::CanonicalError;
+use jitos_core::delta::DeltaSpec;
+use jitos_core::events::{AgentId, EventEnvelope, EventError, EventId, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::auth::{handshake, signed_author, Hello, PeerSigner, PeerVerifier, Role};
+use crate::negotiate::{RangeItem, Reconciler};
+use crate::peer_clock::{clock_observations, LocalClock, PeerTime};
+use crate::subscribe::{self, PartialReplica, ReplicaError, Slice, SliceItem, Subscription};
+use crate::trust::{Quarantine, QuarantineReason, Quarantined, TrustPolicy, TrustRoots};
+use crate::{read_frame, write_frame};
+
+/// Version spoken in [`Message::Hello`]
+pub const PROTOCOL_VERSION: u32 = 4;
+
+/// Most [`Message::Reconcile`] frames one session exchanges
+pub const MAX_ROUNDS: usize = 64;
+
+/// One protocol frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Message {
+    Hello(Hello),
+    /// The sender's signature over its handshake statement
+    Auth(Signature),
+    /// The sender's heads, in worldline order
+    Heads(Vec<EventId>),
+    /// One negotiation round; empty ends the negotiation
+    Reconcile(Vec<RangeItem>),
+    /// Events for the receiver, in worldline order
+    Events(Vec<EventEnvelope>),
+    /// Ask for the events matching `subscription` that a replica with
+    /// `heads` lacks, instead of [`Message::Heads`]
+    Subscribe {
+        subscription: Subscription,
+        heads: Vec<EventId>,
+    },
+    /// Answer to [`Message::Subscribe`]: a parent-closed slice
+    Slice(Vec<SliceItem>),
+}
+
+impl Message {
+    fn name(&self) -> &'static str {
+        match self {
+            Message::Hello(_) => "Hello",
+            Message::Auth(_) => "Auth",
+            Message::Heads(_) => "Heads",
+            Message::Reconcile(_) => "Reconcile",
+            Message::Events(_) => "Events",
+            Message::Subscribe { .. } => "Subscribe",
+            Message::Slice(_) => "Slice",
+        }
+    }
+}
+
+/// What one session moved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncReport {
+    /// The authenticated peer
+    pub peer: AgentId,
+    /// Events sent to the peer
+    pub sent: usize,
+    /// Events received that were new to the store
+    pub received: usize,
+    /// Events received that trust policy quarantined
+    pub quarantined: usize,
+    /// [`Message::Reconcile`] frames exchanged
+    pub rounds: usize,
+}
+
+/// An event store that syncs with authenticated peers
+///
+/// Every event a session imports is tagged with the peer it came from, so
+/// trust policy can be enforced on what each peer contributed.
+pub struct SyncNode {
+    store: MemoryEventStore,
+    identity: Box<dyn PeerSigner + Send + Sync>,
+    verifier: Box<dyn PeerVerifier + Send + Sync>,
+    /// Transport peer of each imported event
+    imported: HashMap<EventId, AgentId>,
+    /// Trust roots set by a TrustPolicy delta
+    trust: Option<TrustRoots>,
+    quarantine: Quarantine,
+    clock: Option<Box<dyn LocalClock + Send + Sync>>,
+    /// Clock observations not yet taken
+    clock_observations: Vec<EventEnvelope>,
+}
+
+impl SyncNode {
+    /// Node over `store`, authenticating as `identity` and checking peers
+    /// with `verifier`
+    pub fn new(
+        store: MemoryEventStore,
+        identity: impl PeerSigner + Send + Sync + 'static,
+        verifier: impl PeerVerifier + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            store,
+            identity: Box::new(identity),
+            verifier: Box::new(verifier),
+            imported: HashMap::new(),
+            trust: None,
+            quarantine: Quarantine::new(),
+            clock: None,
+            clock_observations: Vec::new(),
+        }
+    }
+
+    /// Claim `clock`'s time to peers and record theirs
+    pub fn with_clock(mut self, clock: impl LocalClock + Send + Sync + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    pub fn agent(&self) -> &AgentId {
+        self.identity.agent()
+    }
+
+    pub fn store(&self) -> &MemoryEventStore {
+        &self.store
+    }
+
+    /// The store, for appending local events
+    pub fn store_mut(&mut self) -> &mut MemoryEventStore {
+        &mut self.store
+    }
+
+    /// Peer `event` was imported from (`None` for local events)
+    pub fn imported_from(&self, event: &EventId) -> Option<&AgentId> {
+        self.imported.get(event)
+    }
+
+    /// Enforce the trust roots of a TrustPolicy `delta` on later imports,
+    /// overriding any trust policy context in the store
+    ///
+    /// # Errors
+    ///
+    /// [`NetError::NotTrustPolicy`] if `delta` is of another kind.
+    pub fn set_trust(&mut self, delta: &DeltaSpec) -> Result<(), NetError> {
+        self.trust =
+            Some(TrustRoots::from_delta(delta).ok_or(NetError::NotTrustPolicy(delta.hash()))?);
+        Ok(())
+    }
+
+    /// Trust roots enforced on imports, `None` if every peer is trusted
+    pub fn trust_roots(&self) -> Option<TrustRoots> {
+        self.trust
+            .clone()
+            .or_else(|| TrustRoots::latest(&self.store))
+    }
+
+    /// Clock observations of peer time claims since the last call, in
+    /// session order (see [`crate::peer_clock`])
+    ///
+    /// They are not in the store; the host hands them to the runtime as
+    /// observations.
+    pub fn take_clock_observations(&mut self) -> Vec<EventEnvelope> {
+        std::mem::take(&mut self.clock_observations)
+    }
+
+    /// Imported events held out of the store by trust policy
+    pub fn quarantine(&self) -> &Quarantine {
+        &self.quarantine
+    }
+
+    /// Merge the quarantined events the current trust roots accept, in
+    /// arrival order, returning how many were merged
+    ///
+    /// # Errors
+    ///
+    /// [`NetError::Invalid`] if a released event does not fit the store;
+    /// it and every event after it stay quarantined.
+    pub fn release(&mut self) -> Result<usize, NetError> {
+        let trust = self.trust_roots();
+        let mut held = self.quarantine.take().into_iter();
+        let mut released = 0;
+        while let Some(entry) = held.next() {
+            if let Some(reason) = self.refusal(&entry.event, &entry.peer, trust.as_ref()) {
+                self.quarantine.insert(Quarantined { reason, ..entry });
+                continue;
+            }
+            let id = entry.event.event_id();
+            if let Err(source) = self.store.append(entry.event.clone()) {
+                for entry in std::iter::once(entry).chain(held) {
+                    self.quarantine.insert(entry);
+                }
+                return Err(NetError::Invalid { event: id, source });
+            }
+            self.imported.insert(id, entry.peer);
+            released += 1;
+        }
+        Ok(released)
+    }
+
+    /// Run the initiator's side of a session over `stream`
+    ///
+    /// `nonce` must be fresh for every session (e.g. from the OS random
+    /// number generator); see [`crate::auth`].
+    ///
+    /// # Errors
+    ///
+    /// Any [`NetError`]; events ingested before the error stay in the store.
+    pub fn initiate<S: Read + Write>(
+        &mut self,
+        stream: &mut S,
+        nonce: Hash,
+    ) -> Result<SyncReport, NetError> {
+        let peer = self.open(stream, nonce, Role::Initiator)?;
+        let store = &self.store;
+        write_frame(stream, &Message::Heads(store.heads()))?;
+        let theirs = expect_heads(stream)?;
+        let (events, rounds) = if theirs.iter().all(|h| store.position(h).is_some()) {
+            let rounds = reconcile(stream, None, Some(vec![]))?;
+            (missing_for(store, &theirs), rounds)
+        } else {
+            let mut reconciler = Reconciler::new(store);
+            let start = reconciler.start();
+            let rounds = reconcile(stream, Some(&mut reconciler), Some(start))?;
+            let to_send = reconciler.to_send();
+            let events = store
+                .events()
+                .iter()
+                .filter(|e| to_send.contains(&e.event_id()))
+                .cloned()
+                .collect();
+            (events, rounds)
+        };
+        let sent = events.len();
+        write_frame(stream, &Message::Events(events))?;
+        let (received, quarantined) = self.expect_events(stream, &peer)?;
+        Ok(SyncReport {
+            peer,
+            sent,
+            received,
+            quarantined,
+            rounds,
+        })
+    }
+
+    /// Run the responder's side of a session over `stream`
+    ///
+    /// `nonce` must be fresh for every session, as for [`Self::initiate`].
+    ///
+    /// # Errors
+    ///
+    /// Any [`NetError`]; events ingested before the error stay in the store.
+    pub fn respond<S: Read + Write>(
+        &mut self,
+        stream: &mut S,
+        nonce: Hash,
+    ) -> Result<SyncReport, NetError> {
+        let peer = self.open(stream, nonce, Role::Responder)?;
+        let theirs = match read_frame(stream)? {
+            Message::Heads(heads) => heads,
+            Message::Subscribe {
+                subscription,
+                heads,
+            } => {
+                let slice = subscribe::slice(&self.store, &subscription, &heads);
+                let sent = slice.events().count();
+                write_frame(stream, &Message::Slice(slice.items))?;
+                return Ok(SyncReport {
+                    peer,
+                    sent,
+                    received: 0,
+                    quarantined: 0,
+                    rounds: 0,
+                });
+            }
+            other => return Err(unexpected("Heads", &other)),
+        };
+        write_frame(stream, &Message::Heads(self.store.heads()))?;
+        let mut reconciler = Reconciler::new(&self.store);
+        let rounds = reconcile(stream, Some(&mut reconciler), None)?;
+        let (received, quarantined) = self.expect_events(stream, &peer)?;
+        let events = missing_for(&self.store, &theirs);
+        let sent = events.len();
+        write_frame(stream, &Message::Events(events))?;
+        Ok(SyncReport {
+            peer,
+            sent,
+            received,
+            quarantined,
+            rounds,
+        })
+    }
+
+    /// Fetch the slice of the peer's DAG matching `subscription` over
+    /// `stream` into `replica`, as initiator
+    ///
+    /// Only `replica` changes; the node's store is left alone. `nonce` must
+    /// be fresh for every session, as for [`Self::initiate`].
+    ///
+    /// # Errors
+    ///
+    /// Any [`NetError`]; [`NetError::Replica`] if the slice is not
+    /// parent-closed or holds an invalid event, in which case the items
+    /// before it stay in `replica`.
+    pub fn subscribe<S: Read + Write>(
+        &mut self,
+        stream: &mut S,
+        nonce: Hash,
+        subscription: &Subscription,
+        replica: &mut PartialReplica,
+    ) -> Result<SyncReport, NetError> {
+        let peer = self.open(stream, nonce, Role::Initiator)?;
+        write_frame(
+            stream,
+            &Message::Subscribe {
+                subscription: subscription.clone(),
+                heads: replica.heads(),
+            },
+        )?;
+        let items = match read_frame(stream)? {
+            Message::Slice(items) => items,
+            other => return Err(unexpected("Slice", &other)),
+        };
+        let received = replica.apply(Slice { items })?;
+        Ok(SyncReport {
+            peer,
+            sent: 0,
+            received,
+            quarantined: 0,
+            rounds: 0,
+        })
+    }
+
+    /// Connect to `addr` and [`Self::subscribe`] to it
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::subscribe`], plus connection errors.
+    pub fn subscribe_to(
+        &mut self,
+        addr: impl ToSocketAddrs,
+        nonce: Hash,
+        subscription: &Subscription,
+        replica: &mut PartialReplica,
+    ) -> Result<SyncReport, NetError> {
+        let mut stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        self.subscribe(&mut stream, nonce, subscription, replica)
+    }
+
+    /// Connect to `addr` and sync with it as initiator
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::initiate`], plus connection errors.
+    pub fn connect(
+        &mut self,
+        addr: impl ToSocketAddrs,
+        nonce: Hash,
+    ) -> Result<SyncReport, NetError> {
+        let mut stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        self.initiate(&mut stream, nonce)
+    }
+
+    /// Accept one connection on `listener` and sync with it as responder
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::respond`], plus connection errors.
+    pub fn accept(&mut self, listener: &TcpListener, nonce: Hash) -> Result<SyncReport, NetError> {
+        let (mut stream, _) = listener.accept()?;
+        stream.set_nodelay(true)?;
+        self.respond(&mut stream, nonce)
+    }
+
+    /// Read an [`Message::Events`] frame from `peer` into the store,
+    /// returning how many were new and how many were quarantined
+    fn expect_events<R: Read>(
+        &mut self,
+        stream: &mut R,
+        peer: &AgentId,
+    ) -> Result<(usize, usize), NetError> {
+        match read_frame(stream)? {
+            Message::Events(events) => self.ingest(events, peer),
+            other => Err(unexpected("Events", &other)),
+        }
+    }
+
+    /// Validate `events` from `peer`, in worldline order, into the store or
+    /// the quarantine, returning how many were new and how many were
+    /// quarantined
+    pub(crate) fn ingest(
+        &mut self,
+        events: Vec<EventEnvelope>,
+        peer: &AgentId,
+    ) -> Result<(usize, usize), NetError> {
+        let trust = self.trust_roots();
+        let (mut received, mut quarantined) = (0, 0);
+        for event in events {
+            let id = event.event_id();
+            if self.store.position(&id).is_some() || self.quarantine.contains(&id) {
+                continue;
+            }
+            if let Some(reason) = self.refusal(&event, peer, trust.as_ref()) {
+                self.quarantine.insert(Quarantined {
+                    event,
+                    peer: peer.clone(),
+                    reason,
+                });
+                quarantined += 1;
+                continue;
+            }
+            self.store
+                .append(event)
+                .map_err(|source| NetError::Invalid { event: id, source })?;
+            self.imported.insert(id, peer.clone());
+            received += 1;
+        }
+        Ok((received, quarantined))
+    }
+
+    /// Authenticate the peer as `role` and record its time claim
+    fn open<S: Read + Write>(
+        &mut self,
+        stream: &mut S,
+        nonce: Hash,
+        role: Role,
+    ) -> Result<AgentId, NetError> {
+        let (peer, time) = handshake(
+            stream,
+            self.identity.as_ref(),
+            self.verifier.as_ref(),
+            self.clock.as_deref().map(|clock| clock as &dyn LocalClock),
+            nonce,
+            role,
+        )?;
+        self.record_time(&peer, time)?;
+        Ok(peer)
+    }
+
+    /// Queue the clock observations of `peer`'s claim, if it made one
+    fn record_time(&mut self, peer: &AgentId, time: Option<PeerTime>) -> Result<(), NetError> {
+        if let Some(time) = time {
+            self.clock_observations
+                .extend(clock_observations(peer, &time).map_err(NetError::Clock)?);
+        }
+        Ok(())
+    }
+
+    /// Why `event`, delivered by `peer`, must be quarantined, if it must
+    fn refusal(
+        &self,
+        event: &EventEnvelope,
+        peer: &AgentId,
+        trust: Option<&TrustRoots>,
+    ) -> Option<QuarantineReason> {
+        if let Some(parent) = event.parents().iter().find(|p| self.quarantine.contains(p)) {
+            return Some(QuarantineReason::ParentQuarantined(*parent));
+        }
+        let author = match (
+            event.agent_id(),
+            signed_author(event, self.verifier.as_ref()),
+        ) {
+            (_, Some(author)) => author,
+            (Some(claimed), None) => {
+                return Some(QuarantineReason::Unverified {
+                    author: claimed.clone(),
+                })
+            }
+            (None, None) => peer,
+        };
+        if TrustPolicy::from_event(event).is_some() {
+            let by_root = event.agent_id().is_some() && trust.is_some_and(|t| t.trusts(author));
+            if !by_root {
+                return Some(QuarantineReason::PolicyNotByRoot {
+                    policy: trust.map(|t| t.source),
+                });
+            }
+        }
+        let trust = trust?;
+        (!trust.trusts(author)).then(|| QuarantineReason::Untrusted {
+            author: author.clone(),
+            policy: trust.source,
+        })
+    }
+}
+
+/// Events in `store` that a peer with `heads` is missing, in worldline order
+///
+/// The peer has every ancestor of each of its heads; heads unknown to
+/// `store` say nothing, so events reachable only from them may be sent
+/// anyway (the peer skips duplicates).
+pub fn missing_for(store: &MemoryEventStore, heads: &[EventId]) -> Vec<EventEnvelope> {
+    let events = store.events();
+    let mut have = vec![false; events.len()];
+    let mut stack: Vec<usize> = heads.iter().filter_map(|h| store.position(h)).collect();
+    while let Some(pos) = stack.pop() {
+        if std::mem::replace(&mut have[pos], true) {
+            continue;
+        }
+        stack.extend(
+            events[pos]
+                .parents()
+                .iter()
+                .filter_map(|p| store.position(p)),
+        );
+    }
+    events
+        .iter()
+        .zip(have)
+        .filter(|(_, have)| !have)
+        .map(|(event, _)| event.clone())
+        .collect()
+}
+
+/// Exchange [`Message::Reconcile`] rounds until one side sends an empty one,
+/// opening with `opening` if given; returns the frames exchanged
+///
+/// Without a `reconciler`, every round is answered with an empty one.
+fn reconcile<S: Read + Write>(
+    stream: &mut S,
+    mut reconciler: Option<&mut Reconciler>,
+    mut outgoing: Option<Vec<RangeItem>>,
+) -> Result<usize, NetError> {
+    let mut rounds = 0;
+    while rounds < MAX_ROUNDS {
+        if let Some(items) = outgoing.take() {
+            let done = items.is_empty();
+            write_frame(stream, &Message::Reconcile(items))?;
+            rounds += 1;
+            if done {
+                return Ok(rounds);
+            }
+        }
+        let incoming = match read_frame(stream)? {
+            Message::Reconcile(items) => items,
+            other => return Err(unexpected("Reconcile", &other)),
+        };
+        rounds += 1;
+        if incoming.is_empty() {
+            return Ok(rounds);
+        }
+        outgoing = Some(match reconciler.as_deref_mut() {
+            Some(reconciler) => reconciler.reply(&incoming),
+            None => vec![],
+        });
+    }
+    Err(NetError::Negotiation(MAX_ROUNDS))
+}
+
+fn expect_heads<R: Read>(stream: &mut R) -> Result<Vec<EventId>, NetError> {
+    match read_frame(stream)? {
+        Message::Heads(heads) => Ok(heads),
+        other => Err(unexpected("Heads", &other)),
+    }
+}
+
+pub(crate) fn unexpected(expected: &'static str, found: &Message) -> NetError {
+    NetError::Unexpected {
+        expected,
+        found: found.name(),
+    }
+}
+
+/// Sync errors
+#[derive(Debug, Error)]
+pub enum NetError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("frame of {0} bytes exceeds the frame limit")]
+    FrameTooLarge(u64),
+    #[error("peer speaks protocol version {theirs}, we speak {ours}")]
+    Version { ours: u32, theirs: u32 },
+    #[error("expected a {expected} frame, got {found}")]
+    Unexpected {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[error("peer could not prove it holds the key of {}", .0.as_str())]
+    Unauthenticated(AgentId),
+    #[error("partial replica error: {0}")]
+    Replica(#[from] ReplicaError),
+    #[error("clock observation error: {0}")]
+    Clock(#[source] EventError),
+    #[error("delta {0} is not a TrustPolicy delta")]
+    NotTrustPolicy(Hash),
+    #[error("negotiation did not settle within {0} rounds")]
+    Negotiation(usize),
+    #[cfg(feature = "libp2p")]
+    #[error("libp2p error: {0}")]
+    P2p(String),
+    #[error("peer sent invalid event {event}: {source}")]
+    Invalid {
+        event: EventId,
+        #[source]
+        source: EventError,
+    },
+}
@@ -0,0 +1,171 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Trust Policy - Who May Author Imported Events
+//!
+//! A node's trust roots come from a [`DeltaKind::TrustPolicy`] it was given
+//! ([`crate::SyncNode::set_trust`]) or else from the latest
+//! [`TrustPolicy`] PolicyContext in its store. With no trust roots, every
+//! authenticated peer is trusted.
+//!
+//! An imported event is authored by its `agent_id`, or, if it names none,
+//! by the peer that delivered it. The `agent_id` is not part of the event
+//! id, so an event naming an author must carry that author's valid
+//! signature ([`crate::auth::signed_author`]); one that does not is
+//! quarantined whatever the roots. An imported trust policy context may only
+//! change the roots if a current root signed it, so with no roots in force
+//! none is adopted.
+//!
+//! Events whose author is outside the trust roots, and events descending
+//! from them, are quarantined instead of merged: they stay out of the store
+//! and are recorded in the [`Quarantine`] with the reason and the policy
+//! that rejected them, until [`crate::SyncNode::release`] finds them
+//! trusted under newer roots.
+
+use std::collections::HashMap;
+
+use jitos_core::delta::{DeltaKind, DeltaSpec};
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventError, EventId, EventKind};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use serde::{Deserialize, Serialize};
+
+/// Payload tag of a trust policy context
+pub const TRUST_POLICY_V0: &str = "trust-policy-v0";
+
+/// Trust policy, carried as a `PolicyContext` payload
+/// `(TRUST_POLICY_V0, policy)`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustPolicy {
+    pub trust_roots: Vec<AgentId>,
+}
+
+impl TrustPolicy {
+    /// The PolicyContext event publishing this policy
+    pub fn to_policy_context(&self) -> Result<EventEnvelope, EventError> {
+        EventEnvelope::new_policy_context(
+            CanonicalBytes::from_value(&(TRUST_POLICY_V0, self))?,
+            vec![],
+            None,
+            None,
+        )
+    }
+
+    /// The policy `event` publishes, if it is a trust policy context
+    pub fn from_event(event: &EventEnvelope) -> Option<Self> {
+        if !matches!(event.kind(), EventKind::PolicyContext) {
+            return None;
+        }
+        let (tag, policy): (String, Self) = event.payload().to_value().ok()?;
+        (tag == TRUST_POLICY_V0).then_some(policy)
+    }
+}
+
+/// Trust roots in force, with their source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustRoots {
+    /// DeltaSpec hash or PolicyContext event id the roots come from
+    pub source: Hash,
+    pub roots: Vec<AgentId>,
+}
+
+impl TrustRoots {
+    /// Roots set by a TrustPolicy delta
+    pub fn from_delta(delta: &DeltaSpec) -> Option<Self> {
+        match &delta.kind {
+            DeltaKind::TrustPolicy { new_trust_roots } => Some(Self {
+                source: delta.hash(),
+                roots: new_trust_roots.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Roots of the latest trust policy context in `store`
+    pub fn latest(store: &MemoryEventStore) -> Option<Self> {
+        store.events().iter().rev().find_map(|event| {
+            TrustPolicy::from_event(event).map(|policy| Self {
+                source: event.event_id(),
+                roots: policy.trust_roots,
+            })
+        })
+    }
+
+    pub fn trusts(&self, agent: &AgentId) -> bool {
+        self.roots.contains(agent)
+    }
+}
+
+/// Why an event was quarantined
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuarantineReason {
+    /// `author` is not among the roots of trust policy `policy`
+    Untrusted { author: AgentId, policy: Hash },
+    /// The event names `author` but carries no valid signature by it
+    Unverified { author: AgentId },
+    /// A trust policy context not signed by a root of trust policy `policy`
+    /// (`None` if no roots are in force)
+    PolicyNotByRoot { policy: Option<Hash> },
+    /// The event descends from quarantined `parent`
+    ParentQuarantined(EventId),
+}
+
+/// An event held out of the store
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quarantined {
+    pub event: EventEnvelope,
+    /// Peer that delivered it
+    pub peer: AgentId,
+    pub reason: QuarantineReason,
+}
+
+/// Imported events refused by trust policy, in arrival order
+#[derive(Debug, Clone, Default)]
+pub struct Quarantine {
+    entries: Vec<Quarantined>,
+    index: HashMap<EventId, usize>,
+}
+
+impl Quarantine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, event: &EventId) -> bool {
+        self.index.contains_key(event)
+    }
+
+    pub fn get(&self, event: &EventId) -> Option<&Quarantined> {
+        self.index.get(event).map(|&i| &self.entries[i])
+    }
+
+    /// Every quarantined event, in arrival order
+    pub fn iter(&self) -> impl Iterator<Item = &Quarantined> {
+        self.entries.iter()
+    }
+
+    /// Empty the quarantine, returning its records in arrival order
+    pub(crate) fn take(&mut self) -> Vec<Quarantined> {
+        self.index.clear();
+        std::mem::take(&mut self.entries)
+    }
+
+    /// Record `entry`; an event already quarantined keeps its first record
+    pub(crate) fn insert(&mut self, entry: Quarantined) -> bool {
+        let id = entry.event.event_id();
+        if self.index.contains_key(&id) {
+            return false;
+        }
+        self.index.insert(id, self.entries.len());
+        self.entries.push(entry);
+        true
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Peer Authentication Tests
+//!
+//! Peers prove the key behind their AgentId before any event moves, and
+//! imported events remember which peer delivered them.
+
+use std::net::TcpListener;
+
+use jitos_core::events::AgentId;
+use jitos_core::store::MemoryEventStore;
+use jitos_net::{Identity, NetError, SyncNode};
+
+mod common;
+use common::{node, nonce, observation, sign_as, verify};
+
+#[test]
+fn t1_imported_events_are_tagged_with_the_authenticated_peer() {
+    let local = observation(1, vec![]);
+    let remote = observation(2, vec![]);
+    let mut alice = node("alice", MemoryEventStore::new());
+    alice.store_mut().append(local.clone()).unwrap();
+    let mut bob = node("bob", MemoryEventStore::new());
+    bob.store_mut().append(remote.clone()).unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let report = bob.accept(&listener, nonce(2)).unwrap();
+        (bob, report)
+    });
+    let report = alice.connect(addr, nonce(1)).unwrap();
+    let (bob, served) = server.join().unwrap();
+
+    assert_eq!(report.peer.as_str(), "bob");
+    assert_eq!(served.peer.as_str(), "alice");
+    assert_eq!(
+        alice.imported_from(&remote.event_id()).map(AgentId::as_str),
+        Some("bob")
+    );
+    assert_eq!(alice.imported_from(&local.event_id()), None);
+    assert_eq!(
+        bob.imported_from(&local.event_id()).map(AgentId::as_str),
+        Some("alice")
+    );
+}
+
+/// Run `initiator` against `responder` over TCP
+fn session(mut initiator: SyncNode, mut responder: SyncNode) -> (NetError, SyncNode) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let result = responder.accept(&listener, nonce(2));
+        (result, responder)
+    });
+    let _ = initiator.connect(addr, nonce(1));
+    let (result, responder) = server.join().unwrap();
+    (result.unwrap_err(), responder)
+}
+
+#[test]
+fn t2_impostor_is_refused_before_any_event_moves() {
+    // Mallory claims to be alice but only holds her own key.
+    let mut store = MemoryEventStore::new();
+    store.append(observation(7, vec![])).unwrap();
+    let impostor = SyncNode::new(
+        store,
+        Identity::new(AgentId::new("alice").unwrap(), |statement| {
+            sign_as("mallory", statement)
+        }),
+        verify,
+    );
+    let (error, bob) = session(impostor, node("bob", MemoryEventStore::new()));
+    assert!(matches!(error, NetError::Unauthenticated(agent) if agent.as_str() == "alice"));
+    assert!(bob.store().is_empty());
+}
+
+#[test]
+fn t3_responder_that_cannot_sign_is_refused_too() {
+    let mut store = MemoryEventStore::new();
+    store.append(observation(8, vec![])).unwrap();
+    let mut alice = node("alice", store);
+    // Bob accepts anyone but signs with a key that is not his.
+    let bob = SyncNode::new(
+        MemoryEventStore::new(),
+        Identity::new(AgentId::new("bob").unwrap(), |statement| {
+            sign_as("not-bob", statement)
+        }),
+        |_: &AgentId, _: &_, _: &_| true,
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let mut bob = bob;
+        let _ = bob.accept(&listener, nonce(2));
+        bob
+    });
+    let error = alice.connect(addr, nonce(1)).unwrap_err();
+    assert!(matches!(error, NetError::Unauthenticated(agent) if agent.as_str() == "bob"));
+    let bob = server.join().unwrap();
+    assert!(bob.store().is_empty(), "alice sent nothing to an impostor");
+}
@@ -0,0 +1,62 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Common test utilities for jitos-net tests
+
+#![allow(dead_code)]
+
+use jitos_core::canonical;
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventId, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_net::{event_statement, Identity, SyncNode};
+
+/// Helper: Create an observation carrying `value`
+pub fn observation(value: u64, parents: Vec<EventId>) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&value).unwrap(),
+        parents,
+        Some("OBS_TEST_V0".to_string()),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+/// Toy keyed signature: only holders of `agent`'s secret can make it
+pub fn sign_as(agent: &str, statement: &Hash) -> Signature {
+    let secret = format!("secret-of-{agent}");
+    let mac = canonical::hash_canonical(&(secret, statement)).unwrap();
+    Signature::new(mac.0.to_vec()).unwrap()
+}
+
+pub fn verify(agent: &AgentId, statement: &Hash, signature: &Signature) -> bool {
+    sign_as(agent.as_str(), statement) == *signature
+}
+
+/// Helper: A node over `store` holding `agent`'s key
+pub fn node(agent: &str, store: MemoryEventStore) -> SyncNode {
+    let name = agent.to_string();
+    SyncNode::new(
+        store,
+        Identity::new(AgentId::new(agent).unwrap(), move |statement| {
+            sign_as(&name, statement)
+        }),
+        verify,
+    )
+}
+
+pub fn nonce(byte: u8) -> Hash {
+    Hash([byte; 32])
+}
+
+/// Helper: `event`, attributed to `agent` and signed with its key
+pub fn signed(event: EventEnvelope, agent: &str) -> EventEnvelope {
+    let signature = sign_as(agent, &event_statement(&event.event_id()));
+    event.with_signature(AgentId::new(agent).unwrap(), signature)
+}
+
+/// Helper: An observation carrying `value`, authored and signed by `agent`
+pub fn authored(value: u64, parents: Vec<EventId>, agent: &str) -> EventEnvelope {
+    signed(observation(value, parents), agent)
+}
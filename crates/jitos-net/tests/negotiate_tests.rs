@@ -0,0 +1,101 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Negotiation Tests
+//!
+//! Range summaries settle what each side lacks exactly, with traffic that
+//! follows the difference rather than the size of the DAG.
+
+use std::collections::BTreeSet;
+
+use jitos_core::canonical;
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventId};
+use jitos_core::store::MemoryEventStore;
+use jitos_net::{RangeItem, Reconciler, MAX_ROUNDS};
+
+fn observation(value: u64) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&value).unwrap(),
+        vec![],
+        Some("OBS_TEST_V0".to_string()),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+fn store(values: impl IntoIterator<Item = u64>) -> MemoryEventStore {
+    let mut store = MemoryEventStore::new();
+    for value in values {
+        store.append(observation(value)).unwrap();
+    }
+    store
+}
+
+fn ids(values: impl IntoIterator<Item = u64>) -> BTreeSet<EventId> {
+    values
+        .into_iter()
+        .map(|v| observation(v).event_id())
+        .collect()
+}
+
+/// Run a negotiation to the end; returns the rounds and encoded bytes
+fn negotiate(a: &mut Reconciler, b: &mut Reconciler) -> (usize, usize) {
+    let mut outgoing = a.start();
+    let (mut rounds, mut bytes) = (0, 0);
+    let mut turn = [b, a];
+    while !outgoing.is_empty() {
+        assert!(rounds < MAX_ROUNDS, "negotiation must settle");
+        rounds += 1;
+        bytes += canonical::encode(&outgoing).unwrap().len();
+        outgoing = turn[0].reply(&outgoing);
+        turn.swap(0, 1);
+    }
+    (rounds, bytes)
+}
+
+#[test]
+fn t1_each_side_learns_exactly_what_the_other_lacks() {
+    let mut a = Reconciler::new(&store((0..2_000).chain([5_000, 5_001])));
+    let mut b = Reconciler::new(&store((0..2_000).chain([7_000, 7_001, 7_002])));
+    let (rounds, _) = negotiate(&mut a, &mut b);
+
+    assert_eq!(a.to_send(), &ids([5_000, 5_001]));
+    assert_eq!(b.to_send(), &ids([7_000, 7_001, 7_002]));
+    assert!(rounds <= 8, "took {rounds} rounds");
+}
+
+#[test]
+fn t2_traffic_follows_the_difference_not_the_dag() {
+    let naive = |n: u64| canonical::encode(&ids(0..n)).unwrap().len();
+    let mut a = Reconciler::new(&store(0..5_000));
+    let mut b = Reconciler::new(&store((0..5_000).chain([9_999])));
+    let (_, bytes) = negotiate(&mut a, &mut b);
+    assert!(
+        bytes * 10 < naive(5_000),
+        "{bytes} bytes vs {} for a full id list",
+        naive(5_000)
+    );
+    assert_eq!(b.to_send(), &ids([9_999]));
+    assert!(a.to_send().is_empty());
+
+    // Identical sets settle on the opening summary.
+    let same = store(0..5_000);
+    let opening = Reconciler::new(&same).start();
+    assert_eq!(opening.len(), 1);
+    assert!(Reconciler::new(&same).reply(&opening).is_empty());
+}
+
+#[test]
+fn t3_an_empty_side_is_sent_everything() {
+    let mut empty = Reconciler::new(&MemoryEventStore::new());
+    let mut full = Reconciler::new(&store(0..100));
+    let opening = empty.start();
+    let reply = full.reply(&opening);
+    assert!(reply
+        .iter()
+        .all(|item| matches!(item, RangeItem::Fingerprint { .. })));
+    negotiate(&mut empty, &mut full);
+    assert!(empty.to_send().is_empty());
+    assert_eq!(full.to_send(), &ids(0..100));
+}
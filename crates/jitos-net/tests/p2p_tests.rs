@@ -0,0 +1,177 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! libp2p Transport Tests
+//!
+//! Nodes on a swarm catch up when they connect, fetch announced heads they
+//! lack, and run fetched events through the same trust policy as TCP.
+
+use std::time::Duration;
+
+use jitos_core::delta::DeltaSpec;
+use jitos_core::events::AgentId;
+use jitos_core::store::MemoryEventStore;
+use jitos_net::{agent_of, P2pEvent, P2pNode, QuarantineReason};
+use libp2p::identity::Keypair;
+
+mod common;
+use common::{node, observation};
+
+/// Helper: A node with `store` on a fresh swarm
+fn p2p(agent: &str, store: MemoryEventStore) -> P2pNode {
+    P2pNode::new(node(agent, store), Keypair::generate_ed25519()).unwrap()
+}
+
+/// Drive both nodes, recording their events, until `done` holds
+async fn drive(
+    a: &mut P2pNode,
+    b: &mut P2pNode,
+    log: &mut Vec<P2pEvent>,
+    mut done: impl FnMut(&mut P2pNode, &mut P2pNode, &[P2pEvent]) -> bool,
+) {
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while !done(a, b, log) {
+            tokio::select! {
+                event = a.next_event() => log.push(event),
+                event = b.next_event() => log.push(event),
+            }
+        }
+    })
+    .await
+    .expect("swarm did not settle");
+}
+
+/// Helper: Connect `b` to `a`
+async fn connect(a: &mut P2pNode, b: &mut P2pNode, log: &mut Vec<P2pEvent>) {
+    a.listen("/ip4/127.0.0.1/tcp/0".parse().unwrap()).unwrap();
+    drive(a, b, log, |_, _, log| {
+        log.iter().any(|e| matches!(e, P2pEvent::Listening(_)))
+    })
+    .await;
+    let addr = log
+        .iter()
+        .find_map(|e| match e {
+            P2pEvent::Listening(addr) => Some(addr.clone()),
+            _ => None,
+        })
+        .unwrap();
+    b.dial(addr).unwrap();
+}
+
+fn fetched(log: &[P2pEvent]) -> usize {
+    log.iter()
+        .filter(|e| matches!(e, P2pEvent::Fetched { .. }))
+        .count()
+}
+
+#[tokio::test]
+async fn t1_connecting_nodes_fetch_what_they_lack() {
+    let root = observation(1, vec![]);
+    let child = observation(2, vec![root.event_id()]);
+    let mut store = MemoryEventStore::new();
+    store.append(root.clone()).unwrap();
+    store.append(child.clone()).unwrap();
+    let mut alice = p2p("alice", store);
+    let mut bob_store = MemoryEventStore::new();
+    bob_store.append(observation(3, vec![])).unwrap();
+    let mut bob = p2p("bob", bob_store);
+
+    let mut log = Vec::new();
+    connect(&mut alice, &mut bob, &mut log).await;
+    drive(&mut alice, &mut bob, &mut log, |a, b, log| {
+        fetched(log) == 2 && a.node().store().len() == 3 && b.node().store().len() == 3
+    })
+    .await;
+
+    let alice_agent = agent_of(&alice.peer_id());
+    assert_eq!(
+        bob.node().imported_from(&child.event_id()),
+        Some(&alice_agent)
+    );
+    let heads = |p: &P2pNode| {
+        let mut heads = p.node().store().heads();
+        heads.sort();
+        heads
+    };
+    assert_eq!(heads(&alice).len(), 2);
+    assert_eq!(heads(&alice), heads(&bob), "both sides converge");
+    assert!(log.iter().any(
+        |e| matches!(e, P2pEvent::Served { peer, sent: 2 } if *peer == agent_of(&bob.peer_id()))
+    ));
+}
+
+#[tokio::test]
+async fn t2_announced_heads_are_fetched() {
+    let mut alice = p2p("alice", MemoryEventStore::new());
+    let mut bob = p2p("bob", MemoryEventStore::new());
+    let mut log = Vec::new();
+    connect(&mut alice, &mut bob, &mut log).await;
+    drive(&mut alice, &mut bob, &mut log, |_, _, log| {
+        fetched(log) == 2
+    })
+    .await;
+
+    let first = observation(10, vec![]);
+    let second = observation(11, vec![first.event_id()]);
+    for event in [&first, &second] {
+        alice.node_mut().store_mut().append(event.clone()).unwrap();
+    }
+    // Gossipsub learns bob's subscription on its own schedule.
+    drive(&mut alice, &mut bob, &mut log, |a, _, _| {
+        a.announce().unwrap()
+    })
+    .await;
+    drive(&mut alice, &mut bob, &mut log, |_, b, _| {
+        b.node().store().len() == 2
+    })
+    .await;
+
+    assert!(log.iter().any(|e| matches!(
+        e,
+        P2pEvent::Announced { heads, .. } if *heads == vec![second.event_id()]
+    )));
+    assert_eq!(bob.node().store().events(), &[first, second]);
+}
+
+#[tokio::test]
+async fn t3_fetched_events_pass_through_trust_policy() {
+    let mut store = MemoryEventStore::new();
+    let event = observation(20, vec![]);
+    store.append(event.clone()).unwrap();
+    let mut alice = p2p("alice", store);
+    let mut bob = p2p("bob", MemoryEventStore::new());
+    let only_carol =
+        DeltaSpec::new_trust_policy(vec![AgentId::new("carol").unwrap()], "carol".into()).unwrap();
+    bob.node_mut().set_trust(&only_carol).unwrap();
+
+    let mut log = Vec::new();
+    connect(&mut alice, &mut bob, &mut log).await;
+    drive(&mut alice, &mut bob, &mut log, |_, b, _| {
+        !b.node().quarantine().is_empty()
+    })
+    .await;
+
+    let alice_agent = agent_of(&alice.peer_id());
+    assert!(bob.node().store().is_empty());
+    assert!(matches!(
+        &bob.node().quarantine().get(&event.event_id()).unwrap().reason,
+        QuarantineReason::Untrusted { author, .. } if *author == alice_agent
+    ));
+    assert!(log.iter().any(|e| matches!(
+        e,
+        P2pEvent::Fetched {
+            received: 0,
+            quarantined: 1,
+            ..
+        }
+    )));
+
+    let with_alice =
+        DeltaSpec::new_trust_policy(vec![alice_agent.clone()], "alice".into()).unwrap();
+    bob.node_mut().set_trust(&with_alice).unwrap();
+    assert_eq!(bob.node_mut().release().unwrap(), 1);
+    assert_eq!(
+        bob.node().imported_from(&event.event_id()),
+        Some(&alice_agent)
+    );
+}
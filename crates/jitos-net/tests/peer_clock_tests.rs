@@ -0,0 +1,164 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Peer Clock Claim Tests
+//!
+//! Authenticated peers' time claims become PeerClaim clock samples, paired
+//! with the local monotonic reading they arrived at.
+
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use jitos_core::events::{AgentId, EventEnvelope};
+use jitos_core::store::MemoryEventStore;
+use jitos_net::{Identity, LocalClock, NetError, SyncNode, TimeClaim};
+use jitos_views::{ClockSample, ClockSource, DriftView, OBS_CLOCK_SAMPLE_V0};
+
+mod common;
+use common::{node, nonce, sign_as, verify};
+
+/// Monotonic clock advancing `step` per reading; claims `offset` past it
+struct FakeClock {
+    monotonic: AtomicU64,
+    step: u64,
+    offset: u64,
+}
+
+impl FakeClock {
+    fn new(start: u64, step: u64, offset: u64) -> Self {
+        Self {
+            monotonic: AtomicU64::new(start),
+            step,
+            offset,
+        }
+    }
+}
+
+impl LocalClock for FakeClock {
+    fn monotonic_ns(&self) -> u64 {
+        self.monotonic.fetch_add(self.step, Ordering::SeqCst)
+    }
+
+    fn claim(&self) -> TimeClaim {
+        TimeClaim {
+            value_ns: self.monotonic.load(Ordering::SeqCst) + self.offset,
+            uncertainty_ns: 50,
+        }
+    }
+}
+
+/// Sync `initiator` with `responder` over TCP
+fn sync(
+    initiator: &mut SyncNode,
+    responder: SyncNode,
+) -> (Result<(), NetError>, Result<(), NetError>, SyncNode) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let mut responder = responder;
+        let result = responder.accept(&listener, nonce(2)).map(|_| ());
+        (result, responder)
+    });
+    let initiated = initiator.connect(addr, nonce(1)).map(|_| ());
+    let (responded, responder) = server.join().unwrap();
+    (initiated, responded, responder)
+}
+
+fn sample(event: &EventEnvelope) -> ClockSample {
+    assert_eq!(event.observation_type(), Some(OBS_CLOCK_SAMPLE_V0));
+    event.payload().to_value().unwrap()
+}
+
+#[test]
+fn t1_claims_become_peer_samples_paired_with_the_receive_reading() {
+    let mut alice =
+        node("alice", MemoryEventStore::new()).with_clock(FakeClock::new(0, 1_000, 7_000));
+    let bob = node("bob", MemoryEventStore::new()).with_clock(FakeClock::new(500, 10, 9_000));
+    let (initiated, responded, mut bob) = sync(&mut alice, bob);
+    initiated.unwrap();
+    responded.unwrap();
+
+    let [received, claim] =
+        <[EventEnvelope; 2]>::try_from(alice.take_clock_observations()).unwrap();
+    assert_eq!(
+        sample(&received),
+        ClockSample {
+            source: ClockSource::Monotonic,
+            value_ns: 1_000,
+            uncertainty_ns: 0,
+        }
+    );
+    assert_eq!(claim.parents(), &[received.event_id()]);
+    assert_eq!(claim.agent_id().map(AgentId::as_str), Some("bob"));
+    assert_eq!(
+        sample(&claim),
+        ClockSample {
+            source: ClockSource::PeerClaim,
+            value_ns: 9_500,
+            uncertainty_ns: 1_050,
+        },
+        "the initiator widens the claim by its round trip"
+    );
+    assert!(alice.take_clock_observations().is_empty());
+    assert!(
+        alice.store().position(&claim.event_id()).is_none(),
+        "observations go to the host, not the store"
+    );
+
+    let [received, claim] = <[EventEnvelope; 2]>::try_from(bob.take_clock_observations()).unwrap();
+    assert_eq!(sample(&received).value_ns, 500);
+    assert_eq!(claim.agent_id().map(AgentId::as_str), Some("alice"));
+    assert_eq!(
+        sample(&claim),
+        ClockSample {
+            source: ClockSource::PeerClaim,
+            value_ns: 7_000,
+            uncertainty_ns: 50,
+        }
+    );
+}
+
+#[test]
+fn t2_drift_views_track_peers_across_sessions() {
+    let mut alice = node("alice", MemoryEventStore::new()).with_clock(FakeClock::new(0, 1_000, 0));
+    let mut bob = node("bob", MemoryEventStore::new()).with_clock(FakeClock::new(0, 4_000, 0));
+    for _ in 0..2 {
+        let (initiated, responded, returned) = sync(&mut alice, bob);
+        initiated.unwrap();
+        responded.unwrap();
+        bob = returned;
+    }
+
+    let mut drift = DriftView::new();
+    for event in alice.take_clock_observations() {
+        drift.apply_event(&event).unwrap();
+    }
+    let estimate = drift.estimate(ClockSource::PeerClaim).unwrap();
+    assert_eq!(estimate.samples, 2);
+    assert_eq!(estimate.span_ns, 2_000);
+    assert!(estimate.drift_ppb > 0, "bob's clock runs fast");
+}
+
+#[test]
+fn t3_claims_need_both_clocks_and_an_authenticated_peer() {
+    let mut alice = node("alice", MemoryEventStore::new());
+    let bob = node("bob", MemoryEventStore::new()).with_clock(FakeClock::new(0, 1, 0));
+    let (initiated, responded, mut bob) = sync(&mut alice, bob);
+    initiated.unwrap();
+    responded.unwrap();
+    assert!(alice.take_clock_observations().is_empty());
+    assert!(bob.take_clock_observations().is_empty());
+
+    let mut impostor = SyncNode::new(
+        MemoryEventStore::new(),
+        Identity::new(AgentId::new("alice").unwrap(), |statement| {
+            sign_as("mallory", statement)
+        }),
+        verify,
+    )
+    .with_clock(FakeClock::new(0, 1, 0));
+    let bob = node("bob", MemoryEventStore::new()).with_clock(FakeClock::new(0, 1, 0));
+    let (_, responded, mut bob) = sync(&mut impostor, bob);
+    assert!(matches!(responded, Err(NetError::Unauthenticated(_))));
+    assert!(bob.take_clock_observations().is_empty());
+}
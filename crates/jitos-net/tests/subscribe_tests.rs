@@ -0,0 +1,187 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Filtered Sync Tests
+//!
+//! Subscribers receive only matching events, with stubs for the rest of
+//! their ancestry, so every partial replica stays parent-closed.
+
+use std::net::TcpListener;
+
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventId};
+use jitos_core::store::MemoryEventStore;
+use jitos_net::{
+    AncestorStub, PartialReplica, ReplicaError, Slice, SliceItem, Subscription, SyncNode,
+    SyncReport,
+};
+use jitos_views::{OBS_CLOCK_SAMPLE_V0, OBS_TIMER_REQUEST_V0};
+
+mod common;
+use common::{authored, node, nonce, observation};
+
+/// Helper: An observation of `observation_type` carrying `value`
+fn typed(value: u64, parents: Vec<EventId>, observation_type: &str) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&value).unwrap(),
+        parents,
+        Some(observation_type.to_string()),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+/// Subscribe `subscriber` to `publisher` over TCP
+fn subscribe(
+    subscriber: &mut SyncNode,
+    publisher: SyncNode,
+    subscription: &Subscription,
+    replica: &mut PartialReplica,
+) -> (SyncReport, SyncReport, SyncNode) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let mut publisher = publisher;
+        let report = publisher.accept(&listener, nonce(2)).unwrap();
+        (report, publisher)
+    });
+    let report = subscriber
+        .subscribe_to(addr, nonce(1), subscription, replica)
+        .unwrap();
+    let (served, publisher) = server.join().unwrap();
+    (report, served, publisher)
+}
+
+fn timing() -> Subscription {
+    Subscription::observation_types([OBS_CLOCK_SAMPLE_V0, OBS_TIMER_REQUEST_V0])
+}
+
+#[test]
+fn t1_matching_events_arrive_with_stubs_for_their_ancestry() {
+    let other = typed(1, vec![], "OBS_OTHER_V0");
+    let clock = typed(2, vec![other.event_id()], OBS_CLOCK_SAMPLE_V0);
+    let unrelated = typed(3, vec![], "OBS_OTHER_V0");
+    let timer = typed(4, vec![clock.event_id()], OBS_TIMER_REQUEST_V0);
+    let mut store = MemoryEventStore::new();
+    for event in [&other, &clock, &unrelated, &timer] {
+        store.append(event.clone()).unwrap();
+    }
+
+    let mut alice = node("alice", MemoryEventStore::new());
+    let mut replica = PartialReplica::new();
+    let (report, served, _) = subscribe(&mut alice, node("bob", store), &timing(), &mut replica);
+
+    assert_eq!((report.received, served.sent), (2, 2));
+    assert_eq!(report.peer.as_str(), "bob");
+    assert_eq!(replica.events(), &[clock.clone(), timer.clone()]);
+    assert_eq!(
+        replica.stub(&other.event_id()),
+        Some(&AncestorStub::of(&other))
+    );
+    assert_eq!(replica.stubs(), 1);
+    assert!(
+        !replica.knows(&unrelated.event_id()),
+        "events that neither match nor lead to a match stay behind"
+    );
+    assert_eq!(replica.heads(), vec![timer.event_id()]);
+    assert!(
+        alice.store().is_empty(),
+        "subscribing leaves the store alone"
+    );
+}
+
+#[test]
+fn t2_later_subscriptions_receive_only_new_matches() {
+    let bob_id = AgentId::new("bob").unwrap();
+    let first = authored(1, vec![], "bob");
+    let by_carol = authored(2, vec![first.event_id()], "carol");
+    let mut store = MemoryEventStore::new();
+    store.append(first.clone()).unwrap();
+    store.append(by_carol.clone()).unwrap();
+    let from_bob = Subscription::all().from_agents([bob_id]);
+
+    let mut alice = node("alice", MemoryEventStore::new());
+    let mut replica = PartialReplica::new();
+    let (report, _, mut bob) = subscribe(&mut alice, node("bob", store), &from_bob, &mut replica);
+    assert_eq!(report.received, 1);
+    assert_eq!(replica.events(), std::slice::from_ref(&first));
+    assert!(
+        !replica.knows(&by_carol.event_id()),
+        "carol's event has no bob-authored descendant yet"
+    );
+
+    let second = authored(3, vec![by_carol.event_id()], "bob");
+    let untyped = observation(4, vec![second.event_id()]);
+    bob.store_mut().append(second.clone()).unwrap();
+    bob.store_mut().append(untyped).unwrap();
+    let (report, served, _) = subscribe(&mut alice, bob, &from_bob, &mut replica);
+    assert_eq!((report.received, served.sent), (1, 1));
+    assert_eq!(replica.events(), &[first, second.clone()]);
+    assert_eq!(
+        replica.stub(&by_carol.event_id()).map(|s| &s.parents),
+        Some(&vec![replica.events()[0].event_id()])
+    );
+    assert_eq!(replica.heads(), vec![second.event_id()]);
+}
+
+#[test]
+fn t3_replicas_refuse_slices_that_are_not_parent_closed() {
+    let root = typed(1, vec![], "OBS_OTHER_V0");
+    let child = typed(2, vec![root.event_id()], OBS_CLOCK_SAMPLE_V0);
+    let mut replica = PartialReplica::new();
+    assert!(matches!(
+        replica.apply(Slice {
+            items: vec![SliceItem::Event(child.clone())],
+        }),
+        Err(ReplicaError::NotClosed { event, parent })
+            if event == child.event_id() && parent == root.event_id()
+    ));
+
+    let lying = AncestorStub {
+        parents: vec![child.event_id()],
+        ..AncestorStub::of(&root)
+    };
+    assert!(matches!(
+        replica.apply(Slice {
+            items: vec![SliceItem::Stub(lying)],
+        }),
+        Err(ReplicaError::NotClosed { .. })
+    ));
+
+    replica
+        .apply(Slice {
+            items: vec![
+                SliceItem::Stub(AncestorStub::of(&root)),
+                SliceItem::Event(child.clone()),
+            ],
+        })
+        .unwrap();
+    let other_root = typed(3, vec![], "OBS_OTHER_V0");
+    let forged = AncestorStub {
+        parents: vec![child.event_id()],
+        ..AncestorStub::of(&other_root)
+    };
+    replica
+        .apply(Slice {
+            items: vec![SliceItem::Stub(forged)],
+        })
+        .unwrap();
+    assert!(matches!(
+        replica.apply(Slice {
+            items: vec![SliceItem::Event(other_root.clone())],
+        }),
+        Err(ReplicaError::StubMismatch(id)) if id == other_root.event_id()
+    ));
+
+    // The full event replaces its stub once it arrives.
+    assert_eq!(
+        replica
+            .apply(Slice {
+                items: vec![SliceItem::Event(root.clone())],
+            })
+            .unwrap(),
+        1
+    );
+    assert_eq!(replica.stub(&root.event_id()), None);
+    assert_eq!(replica.events().last(), Some(&root));
+}
@@ -0,0 +1,213 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Peer Sync Tests
+//!
+//! Two stores converge over TCP; frames and events from a peer are checked
+//! before anything is ingested.
+
+use std::collections::BTreeSet;
+use std::io::{Cursor, Read, Write};
+use std::net::TcpListener;
+
+use jitos_core::events::{AgentId, EventEnvelope, EventId};
+use jitos_core::store::MemoryEventStore;
+use jitos_net::{
+    read_frame, statement, transcript, write_frame, Hello, Message, NetError, Role, SyncNode,
+    MAX_FRAME_LEN, PROTOCOL_VERSION,
+};
+
+mod common;
+use common::{node, nonce, observation, sign_as};
+
+fn ids(store: &MemoryEventStore) -> BTreeSet<EventId> {
+    store.events().iter().map(EventEnvelope::event_id).collect()
+}
+
+/// Scripted peer: reads `input`, records what we write
+struct Scripted {
+    input: Cursor<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+impl Scripted {
+    fn new(messages: &[Message]) -> Self {
+        let mut input = Vec::new();
+        for message in messages {
+            write_frame(&mut input, message).unwrap();
+        }
+        Self {
+            input: Cursor::new(input),
+            output: Vec::new(),
+        }
+    }
+}
+
+impl Read for Scripted {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.input.read(buf)
+    }
+}
+
+impl Write for Scripted {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The frames `peer` opens with when initiating a session with `responder`
+fn handshake_as(peer: &str, responder: &SyncNode, responder_nonce: u8) -> Vec<Message> {
+    let ours = Hello {
+        version: PROTOCOL_VERSION,
+        agent: AgentId::new(peer).unwrap(),
+        nonce: nonce(200),
+        time: None,
+    };
+    let theirs = Hello {
+        version: PROTOCOL_VERSION,
+        agent: responder.agent().clone(),
+        nonce: nonce(responder_nonce),
+        time: None,
+    };
+    let proof = sign_as(
+        peer,
+        &statement(&transcript(&ours, &theirs), Role::Initiator),
+    );
+    vec![Message::Hello(ours), Message::Auth(proof)]
+}
+
+#[test]
+fn t1_diverged_stores_converge_over_tcp() {
+    let root = observation(0, vec![]);
+    let mut ours = node("alice", MemoryEventStore::new());
+    let mut theirs = node("bob", MemoryEventStore::new());
+    for node in [&mut ours, &mut theirs] {
+        node.store_mut().append(root.clone()).unwrap();
+    }
+    let mut tip = root.event_id();
+    for value in 1..=3 {
+        let event = observation(value, vec![tip]);
+        tip = event.event_id();
+        ours.store_mut().append(event).unwrap();
+    }
+    let mut tip = root.event_id();
+    for value in 10..=11 {
+        let event = observation(value, vec![tip]);
+        tip = event.event_id();
+        theirs.store_mut().append(event).unwrap();
+    }
+    let merge = observation(99, vec![tip, ours.store().heads()[0]]);
+    let expected: BTreeSet<_> = ids(ours.store())
+        .union(&ids(theirs.store()))
+        .copied()
+        .collect();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let report = theirs.accept(&listener, nonce(2)).unwrap();
+        (theirs, report)
+    });
+    let report = ours.connect(addr, nonce(1)).unwrap();
+    let (mut theirs, served) = server.join().unwrap();
+
+    assert_eq!(ids(ours.store()), expected);
+    assert_eq!(ids(theirs.store()), expected);
+    // The initiator cannot place the responder's head, so it negotiates.
+    assert_eq!((report.sent, report.received), (3, 2));
+    assert_eq!((served.sent, served.received), (2, 3));
+    assert!(report.rounds > 1);
+    assert_eq!(served.rounds, report.rounds);
+    // Both sides hold every parent now, so a merge is valid on either.
+    ours.store().clone().append(merge.clone()).unwrap();
+    theirs.store().clone().append(merge).unwrap();
+
+    // A second session has nothing left to move.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || theirs.accept(&listener, nonce(4)).unwrap());
+    let again = ours.connect(addr, nonce(3)).unwrap();
+    assert_eq!((again.sent, again.received, again.rounds), (0, 0, 1));
+    assert_eq!(server.join().unwrap().sent, 0);
+}
+
+#[test]
+fn t2_frames_are_bounded_and_sessions_follow_the_protocol() {
+    let mut bytes = Vec::new();
+    write_frame(&mut bytes, &Message::Heads(vec![])).unwrap();
+    let len = u32::from_be_bytes(bytes[..4].try_into().unwrap()) as usize;
+    assert_eq!(bytes.len(), 4 + len);
+    assert_eq!(
+        read_frame::<_, Message>(&mut bytes.as_slice()).unwrap(),
+        Message::Heads(vec![])
+    );
+
+    let oversized = (MAX_FRAME_LEN + 1).to_be_bytes();
+    assert!(matches!(
+        read_frame::<_, Message>(&mut oversized.as_slice()),
+        Err(NetError::FrameTooLarge(len)) if len == u64::from(MAX_FRAME_LEN) + 1
+    ));
+
+    let mut bob = node("bob", MemoryEventStore::new());
+    let mut hello = handshake_as("alice", &bob, 1);
+    if let Message::Hello(hello) = &mut hello[0] {
+        hello.version += 1;
+    }
+    let mut peer = Scripted::new(&hello);
+    assert!(matches!(
+        bob.respond(&mut peer, nonce(1)),
+        Err(NetError::Version { theirs, .. }) if theirs == PROTOCOL_VERSION + 1
+    ));
+    let mut peer = Scripted::new(&[Message::Heads(vec![])]);
+    assert!(matches!(
+        bob.respond(&mut peer, nonce(1)),
+        Err(NetError::Unexpected {
+            expected: "Hello",
+            found: "Heads"
+        })
+    ));
+}
+
+#[test]
+fn t3_events_are_validated_on_ingest() {
+    let root = observation(0, vec![]);
+    let child = observation(1, vec![root.event_id()]);
+    let mut bob = node("bob", MemoryEventStore::new());
+
+    // The child arrives without its parent.
+    let mut script = handshake_as("alice", &bob, 1);
+    script.extend([
+        Message::Heads(vec![child.event_id()]),
+        Message::Reconcile(vec![]),
+        Message::Events(vec![child.clone()]),
+    ]);
+    assert!(matches!(
+        bob.respond(&mut Scripted::new(&script), nonce(1)),
+        Err(NetError::Invalid { event, .. }) if event == child.event_id()
+    ));
+    assert!(bob.store().is_empty());
+
+    // In topological order it is accepted, and nothing is sent back.
+    let mut script = handshake_as("alice", &bob, 2);
+    script.extend([
+        Message::Heads(vec![child.event_id()]),
+        Message::Reconcile(vec![]),
+        Message::Events(vec![root.clone(), child.clone(), root]),
+    ]);
+    let mut peer = Scripted::new(&script);
+    let report = bob.respond(&mut peer, nonce(2)).unwrap();
+    assert_eq!((report.sent, report.received), (0, 2));
+    let mut replies = peer.output.as_slice();
+    for expected in ["Hello", "Auth", "Heads"] {
+        let reply: Message = read_frame(&mut replies).unwrap();
+        assert_eq!(format!("{reply:?}").split('(').next().unwrap(), expected);
+    }
+    assert_eq!(
+        read_frame::<_, Message>(&mut replies).unwrap(),
+        Message::Events(vec![])
+    );
+}
@@ -0,0 +1,259 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Trust Policy Tests
+//!
+//! Events authored outside the active trust roots, or not signed by the
+//! author they name, are quarantined on import, with a record of why,
+//! instead of being merged into the store.
+
+use std::net::TcpListener;
+
+use jitos_core::delta::DeltaSpec;
+use jitos_core::events::{AgentId, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::Hash;
+use jitos_net::{NetError, QuarantineReason, SyncNode, SyncReport, TrustPolicy};
+
+mod common;
+use common::{authored, node, nonce, observation, sign_as, signed};
+
+fn agents(names: &[&str]) -> Vec<AgentId> {
+    names.iter().map(|n| AgentId::new(*n).unwrap()).collect()
+}
+
+/// Sync `initiator` with `responder` over TCP
+fn sync(initiator: &mut SyncNode, responder: SyncNode) -> (SyncReport, SyncNode) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        let mut responder = responder;
+        responder.accept(&listener, nonce(2)).unwrap();
+        responder
+    });
+    let report = initiator.connect(addr, nonce(1)).unwrap();
+    (report, server.join().unwrap())
+}
+
+fn untrusted(author: &str, policy: Hash) -> QuarantineReason {
+    QuarantineReason::Untrusted {
+        author: AgentId::new(author).unwrap(),
+        policy,
+    }
+}
+
+#[test]
+fn t1_untrusted_authors_and_their_descendants_are_quarantined() {
+    let policy = TrustPolicy {
+        trust_roots: agents(&["alice", "bob"]),
+    }
+    .to_policy_context()
+    .unwrap();
+    let mut alice = node("alice", MemoryEventStore::new());
+    alice.store_mut().append(policy.clone()).unwrap();
+
+    let by_bob = authored(1, vec![], "bob");
+    let by_carol = authored(2, vec![], "carol");
+    let child = authored(3, vec![by_carol.event_id()], "bob");
+    let unattributed = observation(4, vec![]);
+    let mut bob = node("bob", MemoryEventStore::new());
+    for event in [&by_bob, &by_carol, &child, &unattributed] {
+        bob.store_mut().append(event.clone()).unwrap();
+    }
+
+    let (report, _) = sync(&mut alice, bob);
+    assert_eq!((report.received, report.quarantined), (2, 2));
+    assert!(alice.store().position(&by_bob.event_id()).is_some());
+    assert!(
+        alice.store().position(&unattributed.event_id()).is_some(),
+        "unattributed events are vouched for by the trusted peer"
+    );
+    assert!(alice.store().position(&by_carol.event_id()).is_none());
+    assert!(alice.store().position(&child.event_id()).is_none());
+
+    let quarantine = alice.quarantine();
+    let record = quarantine.get(&by_carol.event_id()).unwrap();
+    assert_eq!(record.event, by_carol);
+    assert_eq!(record.peer.as_str(), "bob");
+    assert_eq!(record.reason, untrusted("carol", policy.event_id()));
+    assert_eq!(
+        quarantine.get(&child.event_id()).unwrap().reason,
+        QuarantineReason::ParentQuarantined(by_carol.event_id())
+    );
+    assert_eq!(
+        quarantine
+            .iter()
+            .map(|q| q.event.event_id())
+            .collect::<Vec<_>>(),
+        vec![by_carol.event_id(), child.event_id()],
+        "records are kept in arrival order"
+    );
+}
+
+#[test]
+fn t2_a_trust_policy_delta_overrides_the_store_and_judges_the_peer() {
+    let mut alice = node("alice", MemoryEventStore::new());
+    assert_eq!(alice.trust_roots(), None);
+    let scheduler = DeltaSpec::new_scheduler_policy(Hash([9; 32]), "other".to_string()).unwrap();
+    assert!(matches!(
+        alice.set_trust(&scheduler),
+        Err(NetError::NotTrustPolicy(hash)) if hash == scheduler.hash()
+    ));
+
+    let stored = TrustPolicy {
+        trust_roots: agents(&["bob"]),
+    };
+    alice
+        .store_mut()
+        .append(stored.to_policy_context().unwrap())
+        .unwrap();
+    let delta = DeltaSpec::new_trust_policy(agents(&["alice"]), "alone".to_string()).unwrap();
+    alice.set_trust(&delta).unwrap();
+    let roots = alice.trust_roots().unwrap();
+    assert_eq!(
+        (roots.source, roots.roots),
+        (delta.hash(), agents(&["alice"]))
+    );
+
+    let unattributed = observation(5, vec![]);
+    let mut bob = node("bob", MemoryEventStore::new());
+    bob.store_mut().append(unattributed.clone()).unwrap();
+    let (report, bob) = sync(&mut alice, bob);
+
+    assert_eq!((report.received, report.quarantined), (0, 1));
+    assert_eq!(
+        alice
+            .quarantine()
+            .get(&unattributed.event_id())
+            .unwrap()
+            .reason,
+        untrusted("bob", delta.hash())
+    );
+    assert_eq!(
+        bob.store().len(),
+        1,
+        "bob trusts everyone, but no root signed alice's policy context"
+    );
+    assert_eq!(
+        bob.quarantine().iter().next().unwrap().reason,
+        QuarantineReason::PolicyNotByRoot { policy: None }
+    );
+    assert_eq!(bob.trust_roots(), None);
+}
+
+#[test]
+fn t3_quarantine_is_recorded_once_and_released_under_new_roots() {
+    let mut alice = node("alice", MemoryEventStore::new());
+    alice
+        .store_mut()
+        .append(
+            TrustPolicy {
+                trust_roots: agents(&["bob"]),
+            }
+            .to_policy_context()
+            .unwrap(),
+        )
+        .unwrap();
+    let by_carol = authored(1, vec![], "carol");
+    let child = authored(2, vec![by_carol.event_id()], "bob");
+    let mut bob = node("bob", MemoryEventStore::new());
+    bob.store_mut().append(by_carol.clone()).unwrap();
+    bob.store_mut().append(child.clone()).unwrap();
+
+    let (first, bob) = sync(&mut alice, bob);
+    let (again, _) = sync(&mut alice, bob);
+    assert_eq!(first.quarantined, 2);
+    assert_eq!(
+        (again.received, again.quarantined),
+        (0, 0),
+        "a resent event keeps its first record"
+    );
+    assert_eq!(alice.quarantine().len(), 2);
+    assert_eq!(alice.release().unwrap(), 0);
+
+    let widened = TrustPolicy {
+        trust_roots: agents(&["bob", "carol"]),
+    };
+    alice
+        .store_mut()
+        .append(widened.to_policy_context().unwrap())
+        .unwrap();
+    assert_eq!(alice.release().unwrap(), 2);
+    assert!(alice.quarantine().is_empty());
+    assert!(
+        alice.store().position(&by_carol.event_id()).unwrap()
+            < alice.store().position(&child.event_id()).unwrap()
+    );
+    assert_eq!(
+        alice.imported_from(&child.event_id()).map(AgentId::as_str),
+        Some("bob")
+    );
+}
+
+#[test]
+fn t4_forged_authors_and_unsigned_policies_cannot_borrow_a_roots_trust() {
+    let roots = TrustPolicy {
+        trust_roots: agents(&["alice"]),
+    };
+    let mut alice = node("alice", MemoryEventStore::new());
+    let current = roots.to_policy_context().unwrap();
+    alice.store_mut().append(current.clone()).unwrap();
+
+    // Mallory claims alice as author: unsigned, and signed with her own key.
+    let forged = observation(1, vec![]).with_signature(
+        AgentId::new("alice").unwrap(),
+        Signature::new(vec![0; 32]).unwrap(),
+    );
+    let own = authored(2, vec![], "mallory");
+    let statement = jitos_net::event_statement(&own.event_id());
+    let borrowed = own.with_signature(
+        AgentId::new("alice").unwrap(),
+        sign_as("mallory", &statement),
+    );
+    let coup = TrustPolicy {
+        trust_roots: agents(&["alice", "mallory"]),
+    };
+    let by_mallory = signed(coup.to_policy_context().unwrap(), "mallory");
+    let by_alice = signed(
+        TrustPolicy {
+            trust_roots: agents(&["alice", "bob"]),
+        }
+        .to_policy_context()
+        .unwrap(),
+        "alice",
+    );
+    let mut mallory = node("mallory", MemoryEventStore::new());
+    for event in [&forged, &borrowed, &by_mallory, &by_alice] {
+        mallory.store_mut().append(event.clone()).unwrap();
+    }
+
+    let (report, _) = sync(&mut alice, mallory);
+    assert_eq!((report.received, report.quarantined), (1, 3));
+    let reason = |event: &jitos_core::events::EventEnvelope| {
+        alice
+            .quarantine()
+            .get(&event.event_id())
+            .unwrap()
+            .reason
+            .clone()
+    };
+    let impostor = QuarantineReason::Unverified {
+        author: AgentId::new("alice").unwrap(),
+    };
+    assert_eq!(reason(&forged), impostor);
+    assert_eq!(reason(&borrowed), impostor);
+    assert_eq!(
+        reason(&by_mallory),
+        QuarantineReason::PolicyNotByRoot {
+            policy: Some(current.event_id())
+        }
+    );
+
+    // Only the policy a root signed moved the roots.
+    let adopted = alice.trust_roots().unwrap();
+    assert_eq!(
+        (adopted.source, adopted.roots),
+        (by_alice.event_id(), agents(&["alice", "bob"]))
+    );
+    assert_eq!(alice.release().unwrap(), 0, "new roots forgive no forgery");
+}
@@ -0,0 +1,267 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Branches - Several Live Worldlines
+//!
+//! A [`BranchManager`] holds named branches, each a [`Runtime`] with its own
+//! worldline, graph, receipts and snapshots. It starts with [`MAIN_BRANCH`];
+//! [`BranchManager::fork`] adds a branch continuing the active one under a
+//! [`DeltaSpec`], and [`BranchManager::switch`] changes which branch
+//! [`BranchManager::tick`] advances.
+//!
+//! A fork shares its parent's history up to the fork point: the worldline is
+//! copied, then a PolicyContext recording the delta (payload
+//! `(FORK_POLICY_V0, delta)`, citing the parent's head) marks where the
+//! branches part. From there on each branch only sees its own ticks.
+
+use std::collections::BTreeMap;
+
+use jitos_core::canonical::CanonicalError;
+use jitos_core::delta::{DeltaKind, DeltaSpec};
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventError, EventId};
+use jitos_core::{Hash, Receipt};
+use jitos_graph::snapshot::{MemorySnapshotStore, SnapshotError, SnapshotStore};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::ClockPolicyId;
+use thiserror::Error;
+
+use crate::{Host, Runtime, RuntimeError, TickOutcome};
+
+/// Name of the branch a [`BranchManager`] starts with
+pub const MAIN_BRANCH: &str = "main";
+
+/// Payload tag of the policy context recording a fork's delta
+pub const FORK_POLICY_V0: &str = "fork-v0";
+
+/// Where a branch parted from its parent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkOrigin {
+    pub parent: String,
+    /// Hash of the [`DeltaSpec`] the branch runs under
+    pub delta: Hash,
+    /// First tick the branch runs on its own
+    pub tick: u64,
+    /// The policy context recording the fork
+    pub event: EventId,
+}
+
+/// One worldline and the state built from it
+pub struct Branch {
+    runtime: Runtime,
+    origin: Option<ForkOrigin>,
+    snapshots: MemorySnapshotStore,
+}
+
+impl Branch {
+    fn new(runtime: Runtime, origin: Option<ForkOrigin>) -> Self {
+        Self {
+            runtime,
+            origin,
+            snapshots: MemorySnapshotStore::new(),
+        }
+    }
+
+    pub fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    pub fn runtime_mut(&mut self) -> &mut Runtime {
+        &mut self.runtime
+    }
+
+    /// `None` for [`MAIN_BRANCH`]
+    pub fn origin(&self) -> Option<&ForkOrigin> {
+        self.origin.as_ref()
+    }
+
+    /// Latest event of the branch's worldline
+    pub fn head(&self) -> Option<EventId> {
+        self.runtime.store().events().last().map(|e| e.event_id())
+    }
+
+    /// Receipt of every tick on the branch, shared history included
+    pub fn receipts(&self) -> &[Receipt] {
+        self.runtime.receipts()
+    }
+
+    /// Graph snapshots taken with [`Self::checkpoint`]
+    pub fn snapshots(&self) -> &MemorySnapshotStore {
+        &self.snapshots
+    }
+
+    /// Snapshot the current graph, returning its digest
+    ///
+    /// # Errors
+    ///
+    /// [`BranchError::Snapshot`] if the graph cannot be snapshotted.
+    pub fn checkpoint(&mut self) -> Result<Hash, BranchError> {
+        Ok(self.snapshots.save(self.runtime.graph())?)
+    }
+}
+
+/// Named worldlines, one of them active
+pub struct BranchManager {
+    branches: BTreeMap<String, Branch>,
+    active: String,
+}
+
+impl BranchManager {
+    /// Manager with `main` as [`MAIN_BRANCH`], active
+    pub fn new(main: Runtime) -> Self {
+        let mut branches = BTreeMap::new();
+        branches.insert(MAIN_BRANCH.to_string(), Branch::new(main, None));
+        Self {
+            branches,
+            active: MAIN_BRANCH.to_string(),
+        }
+    }
+
+    /// Fork the active branch as `name`, running under `delta` from now on
+    ///
+    /// `scheduler` drives the fork: for a SchedulerPolicy delta it must
+    /// follow the delta's policy, for a ClockPolicy delta the parent's.
+    /// The active branch does not change.
+    ///
+    /// # Errors
+    ///
+    /// - [`BranchError::Exists`] if `name` is taken
+    /// - [`BranchError::UnsupportedDelta`] for InputMutation and TrustPolicy
+    ///   deltas, [`BranchError::UnknownClockPolicy`] for a clock policy hash
+    ///   that names no [`ClockPolicyId`]
+    /// - [`BranchError::SchedulerMismatch`] if `scheduler` follows the wrong
+    ///   policy
+    /// - [`BranchError::Unsettled`] if the active branch has SLAPs deferred
+    pub fn fork(
+        &mut self,
+        name: impl Into<String>,
+        delta: &DeltaSpec,
+        scheduler: EchoScheduler,
+    ) -> Result<&mut Branch, BranchError> {
+        let name = name.into();
+        if self.branches.contains_key(&name) {
+            return Err(BranchError::Exists(name));
+        }
+        let parent = self.active().runtime();
+        let parent_scheduler = parent.scheduler().policy().policy_hash();
+        let parent_clock = parent.views().clock().policy();
+        let (clock_policy, expected) = match &delta.kind {
+            DeltaKind::ClockPolicy { new_policy } => (
+                ClockPolicyId::from_policy_hash(new_policy)
+                    .ok_or(BranchError::UnknownClockPolicy(*new_policy))?,
+                parent_scheduler,
+            ),
+            DeltaKind::SchedulerPolicy { new_policy } => (parent_clock, *new_policy),
+            DeltaKind::InputMutation { .. } => {
+                return Err(BranchError::UnsupportedDelta("InputMutation"))
+            }
+            DeltaKind::TrustPolicy { .. } => {
+                return Err(BranchError::UnsupportedDelta("TrustPolicy"))
+            }
+        };
+        let found = scheduler.policy().policy_hash();
+        if found != expected {
+            return Err(BranchError::SchedulerMismatch { expected, found });
+        }
+        let deferred = parent.scheduler().deferred().len();
+        if deferred > 0 {
+            return Err(BranchError::Unsettled {
+                branch: self.active.clone(),
+                deferred,
+            });
+        }
+
+        let origin = EventEnvelope::new_policy_context(
+            CanonicalBytes::from_value(&(FORK_POLICY_V0, delta))?,
+            self.active().head().into_iter().collect(),
+            None,
+            None,
+        )?;
+        let origin_id = origin.event_id();
+        let runtime = parent.fork(scheduler, clock_policy, origin)?;
+        let branch = Branch::new(
+            runtime,
+            Some(ForkOrigin {
+                parent: self.active.clone(),
+                delta: delta.hash(),
+                tick: parent.next_tick(),
+                event: origin_id,
+            }),
+        );
+        Ok(self.branches.entry(name).or_insert(branch))
+    }
+
+    /// Make `name` the active branch
+    ///
+    /// # Errors
+    ///
+    /// [`BranchError::Unknown`] if there is no such branch.
+    pub fn switch(&mut self, name: &str) -> Result<(), BranchError> {
+        if !self.branches.contains_key(name) {
+            return Err(BranchError::Unknown(name.to_string()));
+        }
+        self.active = name.to_string();
+        Ok(())
+    }
+
+    /// Run one tick of the active branch
+    ///
+    /// # Errors
+    ///
+    /// As [`Runtime::tick`].
+    pub fn tick(&mut self, host: &mut impl Host) -> Result<TickOutcome, RuntimeError> {
+        self.active_mut().runtime.tick(host)
+    }
+
+    /// Name of the active branch
+    pub fn active_name(&self) -> &str {
+        &self.active
+    }
+
+    pub fn active(&self) -> &Branch {
+        &self.branches[&self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut Branch {
+        self.branches
+            .get_mut(&self.active)
+            .expect("the active branch exists")
+    }
+
+    pub fn branch(&self, name: &str) -> Option<&Branch> {
+        self.branches.get(name)
+    }
+
+    pub fn branch_mut(&mut self, name: &str) -> Option<&mut Branch> {
+        self.branches.get_mut(name)
+    }
+
+    /// Branch names, ascending
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.branches.keys().map(String::as_str)
+    }
+}
+
+/// Branch manager errors
+#[derive(Debug, Error)]
+pub enum BranchError {
+    #[error("branch {0} already exists")]
+    Exists(String),
+    #[error("no branch named {0}")]
+    Unknown(String),
+    #[error("branches fork on ClockPolicy and SchedulerPolicy deltas, got {0}")]
+    UnsupportedDelta(&'static str),
+    #[error("clock policy {0} is not a known ClockPolicyId")]
+    UnknownClockPolicy(Hash),
+    #[error("fork needs a scheduler following policy {expected}, got {found}")]
+    SchedulerMismatch { expected: Hash, found: Hash },
+    #[error("branch {branch} has {deferred} SLAPs deferred and cannot be forked")]
+    Unsettled { branch: String, deferred: usize },
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("event error: {0}")]
+    Event(#[from] EventError),
+    #[error("snapshot error: {0}")]
+    Snapshot(#[from] SnapshotError),
+    #[error(transparent)]
+    Runtime(#[from] RuntimeError),
+}
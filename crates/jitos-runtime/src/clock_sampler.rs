@@ -0,0 +1,267 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Clock Sampler - Physical Time Enters as Observations
+//!
+//! This is host-side code: it reads real clocks and is not deterministic.
+//! What it produces is an `OBS_CLOCK_SAMPLE_V0` observation per sample
+//! (SPEC-0003), which the tick loop ingests like any other observation, so
+//! replay sees the recorded samples and never the clocks themselves.
+//!
+//! A [`ClockSampler`] holds [`TimeSource`]s, each with its own cadence, and
+//! samples the ones that are due whenever it is polled. [`WithClock`] wraps
+//! a [`Host`] so every tick's observations include the due samples.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::{Hash, Proposal};
+use jitos_views::{ClockSample, ClockSource, OBS_CLOCK_SAMPLE_V0};
+
+use crate::{Host, Views};
+
+/// A physical clock
+pub trait TimeSource {
+    fn source(&self) -> ClockSource;
+
+    /// Read the clock, or `None` if it has no reading (e.g. NTP not synced)
+    fn sample(&mut self) -> Option<ClockSample>;
+}
+
+/// The host's monotonic clock, in nanoseconds since the clock was created
+#[derive(Debug, Clone)]
+pub struct MonotonicClock {
+    origin: Instant,
+    uncertainty_ns: u64,
+}
+
+impl MonotonicClock {
+    pub fn new() -> Self {
+        Self::starting_at(Instant::now())
+    }
+
+    /// A clock whose zero is `origin`
+    pub fn starting_at(origin: Instant) -> Self {
+        Self {
+            origin,
+            uncertainty_ns: 0,
+        }
+    }
+
+    pub fn with_uncertainty(mut self, uncertainty_ns: u64) -> Self {
+        self.uncertainty_ns = uncertainty_ns;
+        self
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSource for MonotonicClock {
+    fn source(&self) -> ClockSource {
+        ClockSource::Monotonic
+    }
+
+    fn sample(&mut self) -> Option<ClockSample> {
+        Some(ClockSample {
+            source: ClockSource::Monotonic,
+            value_ns: u64::try_from(self.origin.elapsed().as_nanos()).ok()?,
+            uncertainty_ns: self.uncertainty_ns,
+        })
+    }
+}
+
+/// The host's real-time clock, in nanoseconds since the Unix epoch
+#[derive(Debug, Clone)]
+pub struct SystemClock {
+    uncertainty_ns: u64,
+}
+
+impl SystemClock {
+    /// RTC readings are assumed good to a millisecond unless told otherwise.
+    pub fn new() -> Self {
+        Self {
+            uncertainty_ns: 1_000_000,
+        }
+    }
+
+    pub fn with_uncertainty(mut self, uncertainty_ns: u64) -> Self {
+        self.uncertainty_ns = uncertainty_ns;
+        self
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSource for SystemClock {
+    fn source(&self) -> ClockSource {
+        ClockSource::Rtc
+    }
+
+    /// `None` if the clock reads before the epoch
+    fn sample(&mut self) -> Option<ClockSample> {
+        let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+        Some(ClockSample {
+            source: ClockSource::Rtc,
+            value_ns: u64::try_from(since_epoch.as_nanos()).ok()?,
+            uncertainty_ns: self.uncertainty_ns,
+        })
+    }
+}
+
+/// A clock read through a callback returning `(value_ns, uncertainty_ns)`,
+/// such as an NTP client
+pub struct ExternalClock<F> {
+    source: ClockSource,
+    read: F,
+}
+
+impl<F: FnMut() -> Option<(u64, u64)>> ExternalClock<F> {
+    pub fn new(source: ClockSource, read: F) -> Self {
+        Self { source, read }
+    }
+
+    pub fn ntp(read: F) -> Self {
+        Self::new(ClockSource::Ntp, read)
+    }
+}
+
+impl<F: FnMut() -> Option<(u64, u64)>> TimeSource for ExternalClock<F> {
+    fn source(&self) -> ClockSource {
+        self.source
+    }
+
+    fn sample(&mut self) -> Option<ClockSample> {
+        let (value_ns, uncertainty_ns) = (self.read)()?;
+        Some(ClockSample {
+            source: self.source,
+            value_ns,
+            uncertainty_ns,
+        })
+    }
+}
+
+struct Scheduled {
+    clock: Box<dyn TimeSource + Send>,
+    every: Duration,
+    last: Option<Instant>,
+}
+
+/// Samples time sources at their cadence and tags the samples as observations
+#[derive(Default)]
+pub struct ClockSampler {
+    clocks: Vec<Scheduled>,
+    agent: Option<AgentId>,
+}
+
+impl ClockSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sample `clock` at most once per `every`
+    pub fn with_source(mut self, clock: impl TimeSource + Send + 'static, every: Duration) -> Self {
+        self.clocks.push(Scheduled {
+            clock: Box::new(clock),
+            every,
+            last: None,
+        });
+        self
+    }
+
+    /// Attribute samples to `agent`
+    pub fn with_agent(mut self, agent: AgentId) -> Self {
+        self.agent = Some(agent);
+        self
+    }
+
+    /// Samples of every source that is due, in the order sources were added
+    pub fn poll(&mut self) -> Vec<EventEnvelope> {
+        self.poll_at(Instant::now())
+    }
+
+    /// [`Self::poll`] as if the host's monotonic clock read `now`
+    ///
+    /// A source is due if it has never been sampled or its cadence has
+    /// elapsed since its last sample. A source with no reading stays due.
+    pub fn poll_at(&mut self, now: Instant) -> Vec<EventEnvelope> {
+        let mut events = Vec::new();
+        for scheduled in &mut self.clocks {
+            let due = scheduled
+                .last
+                .is_none_or(|last| now.saturating_duration_since(last) >= scheduled.every);
+            if !due {
+                continue;
+            }
+            let Some(sample) = scheduled.clock.sample() else {
+                continue;
+            };
+            scheduled.last = Some(now);
+            events.push(observation(&sample, self.agent.clone()));
+        }
+        events
+    }
+}
+
+fn observation(sample: &ClockSample, agent: Option<AgentId>) -> EventEnvelope {
+    let payload = CanonicalBytes::from_value(sample).expect("clock samples encode canonically");
+    EventEnvelope::new_observation(
+        payload,
+        vec![],
+        Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+        agent,
+        None,
+    )
+    .expect("a parentless observation is well formed")
+}
+
+/// A [`Host`] whose observations are preceded by the due clock samples
+pub struct WithClock<H> {
+    inner: H,
+    sampler: ClockSampler,
+}
+
+impl<H: Host> WithClock<H> {
+    pub fn new(inner: H, sampler: ClockSampler) -> Self {
+        Self { inner, sampler }
+    }
+
+    pub fn inner(&self) -> &H {
+        &self.inner
+    }
+
+    pub fn sampler_mut(&mut self) -> &mut ClockSampler {
+        &mut self.sampler
+    }
+}
+
+impl<H: Host> Host for WithClock<H> {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        let mut observations = self.sampler.poll();
+        observations.extend(self.inner.observations(tick));
+        observations
+    }
+
+    fn proposals(&mut self, tick: u64, views: &Views) -> Vec<Proposal> {
+        self.inner.proposals(tick, views)
+    }
+
+    fn sign(&mut self, payload: &CanonicalBytes) -> Signature {
+        self.inner.sign(payload)
+    }
+
+    fn sign_receipt(&mut self, digest: &Hash) -> Option<Signature> {
+        self.inner.sign_receipt(digest)
+    }
+
+    fn agent(&self) -> Option<AgentId> {
+        self.inner.agent()
+    }
+}
@@ -0,0 +1,327 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Effect Executor - Performing Commits at Most Once
+//!
+//! A Commit records an effect that escapes the system boundary; something
+//! outside the tick loop has to perform it. A Commit whose payload is an
+//! [`EffectCommit`] names a `commit_type`, and an [`EffectExecutor`] hands
+//! it to the [`Effector`] registered for that type.
+//!
+//! Each Commit's effect happens at most once, even across crashes. The
+//! executor journals the Commit's id as started before performing it and
+//! its outcome after. A Commit journaled as started but never finished was
+//! interrupted mid-effect; it is not performed again but reported as
+//! [`EffectStatus::Interrupted`], since the effect may or may not have
+//! happened.
+//!
+//! Outcomes go back into the DAG as `OBS_EFFECT_OUTCOME_V0` observations
+//! citing the Commit. They are observations, so the host delivers them
+//! through [`crate::Host::observations`]. An outcome journaled but never
+//! ingested is emitted again (as the identical event) by the next executor.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventError, EventId, EventKind};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::Runtime;
+
+/// Observation type tag for the outcome of a Commit's side effect
+pub const OBS_EFFECT_OUTCOME_V0: &str = "OBS_EFFECT_OUTCOME_V0";
+
+/// Payload of a Commit whose side effect an [`Effector`] performs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EffectCommit {
+    pub commit_type: String,
+    /// The effect's own parameters, canonically encoded
+    pub body: CanonicalBytes,
+}
+
+impl EffectCommit {
+    pub fn new<T: Serialize>(
+        commit_type: impl Into<String>,
+        body: &T,
+    ) -> Result<Self, CanonicalError> {
+        Ok(Self {
+            commit_type: commit_type.into(),
+            body: CanonicalBytes::from_value(body)?,
+        })
+    }
+}
+
+/// Performs the side effects of one commit type
+pub trait Effector {
+    /// Perform the effect `commit` records; `body` is its
+    /// [`EffectCommit::body`]
+    ///
+    /// Returns the result to record (canonically encoded), or why the effect
+    /// failed. Called at most once per Commit.
+    fn perform(
+        &mut self,
+        commit: &EventEnvelope,
+        body: &CanonicalBytes,
+    ) -> Result<CanonicalBytes, String>;
+}
+
+impl<F> Effector for F
+where
+    F: FnMut(&EventEnvelope, &CanonicalBytes) -> Result<CanonicalBytes, String>,
+{
+    fn perform(
+        &mut self,
+        commit: &EventEnvelope,
+        body: &CanonicalBytes,
+    ) -> Result<CanonicalBytes, String> {
+        self(commit, body)
+    }
+}
+
+/// How a Commit's effect ended
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectStatus {
+    /// Performed; the effector's result
+    Done(CanonicalBytes),
+    /// The effector reported a failure
+    Failed(String),
+    /// Started but never finished (a crash mid-effect); not retried
+    Interrupted,
+}
+
+/// Payload of an `OBS_EFFECT_OUTCOME_V0` observation (its parent is the
+/// Commit)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EffectOutcome {
+    pub commit_type: String,
+    pub status: EffectStatus,
+}
+
+impl EffectOutcome {
+    /// The outcome as an observation citing `commit`
+    pub fn to_observation(&self, commit: EventId) -> Result<EventEnvelope, EventError> {
+        EventEnvelope::new_observation(
+            CanonicalBytes::from_value(self)?,
+            vec![commit],
+            Some(OBS_EFFECT_OUTCOME_V0.to_string()),
+            None,
+            None,
+        )
+    }
+}
+
+/// A Commit's progress, as journaled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalEntry {
+    Started,
+    Finished(EffectStatus),
+}
+
+/// Durable record of which Commits' effects were started and finished
+///
+/// `begin` must be durable before it returns: the executor performs the
+/// effect right after.
+pub trait EffectJournal {
+    fn entry(&self, commit: &EventId) -> Result<Option<JournalEntry>, EffectError>;
+
+    fn begin(&mut self, commit: &EventId) -> Result<(), EffectError>;
+
+    fn finish(&mut self, commit: &EventId, status: &EffectStatus) -> Result<(), EffectError>;
+}
+
+/// In-memory journal (survives executor restarts, not process crashes)
+#[derive(Debug, Clone, Default)]
+pub struct MemoryJournal {
+    entries: BTreeMap<EventId, JournalEntry>,
+}
+
+impl MemoryJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Journaled Commits, ascending by id
+    pub fn entries(&self) -> impl Iterator<Item = (&EventId, &JournalEntry)> {
+        self.entries.iter()
+    }
+}
+
+impl EffectJournal for MemoryJournal {
+    fn entry(&self, commit: &EventId) -> Result<Option<JournalEntry>, EffectError> {
+        Ok(self.entries.get(commit).cloned())
+    }
+
+    fn begin(&mut self, commit: &EventId) -> Result<(), EffectError> {
+        self.entries.insert(*commit, JournalEntry::Started);
+        Ok(())
+    }
+
+    fn finish(&mut self, commit: &EventId, status: &EffectStatus) -> Result<(), EffectError> {
+        self.entries
+            .insert(*commit, JournalEntry::Finished(status.clone()));
+        Ok(())
+    }
+}
+
+/// Directory-backed journal: `<commit-hex>.started` when an effect starts,
+/// `<commit-hex>.cbor` holding its [`EffectStatus`] when it finishes
+#[derive(Debug, Clone)]
+pub struct FileJournal {
+    dir: PathBuf,
+}
+
+impl FileJournal {
+    /// Open (creating if needed) a journal directory
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, EffectError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, commit: &EventId, extension: &str) -> PathBuf {
+        self.dir.join(format!("{commit}.{extension}"))
+    }
+}
+
+impl EffectJournal for FileJournal {
+    fn entry(&self, commit: &EventId) -> Result<Option<JournalEntry>, EffectError> {
+        match std::fs::read(self.path(commit, "cbor")) {
+            Ok(bytes) => return Ok(Some(JournalEntry::Finished(canonical::decode(&bytes)?))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        Ok(self
+            .path(commit, "started")
+            .exists()
+            .then_some(JournalEntry::Started))
+    }
+
+    fn begin(&mut self, commit: &EventId) -> Result<(), EffectError> {
+        std::fs::File::create(self.path(commit, "started"))?.sync_all()?;
+        Ok(())
+    }
+
+    fn finish(&mut self, commit: &EventId, status: &EffectStatus) -> Result<(), EffectError> {
+        // Write-then-rename so a crash never leaves a truncated outcome.
+        let tmp = self.path(commit, "cbor.tmp");
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(&canonical::encode(status)?)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, self.path(commit, "cbor"))?;
+        Ok(())
+    }
+}
+
+/// Dispatches Commits to [`Effector`]s by commit type, through a journal
+pub struct EffectExecutor<J> {
+    effectors: BTreeMap<String, Box<dyn Effector>>,
+    journal: J,
+    /// Worldline position up to which Commits have been executed
+    scanned: usize,
+}
+
+impl<J: EffectJournal> EffectExecutor<J> {
+    pub fn new(journal: J) -> Self {
+        Self {
+            effectors: BTreeMap::new(),
+            journal,
+            scanned: 0,
+        }
+    }
+
+    /// Perform Commits of `commit_type` with `effector`
+    pub fn with_effector(
+        mut self,
+        commit_type: impl Into<String>,
+        effector: impl Effector + 'static,
+    ) -> Self {
+        self.effectors
+            .insert(commit_type.into(), Box::new(effector));
+        self
+    }
+
+    pub fn journal(&self) -> &J {
+        &self.journal
+    }
+
+    /// Execute every Commit recorded since the last call
+    ///
+    /// Commits are taken in worldline order; those that are not an
+    /// [`EffectCommit`], or whose type has no effector, are left alone.
+    /// Returns the outcome observations the worldline does not hold yet,
+    /// for the host to deliver.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EffectError`] if the journal fails or an outcome cannot
+    /// be encoded. Commits before the failing one stay executed; the failing
+    /// one is retried by the next call (or reported interrupted if its
+    /// effect had started).
+    pub fn execute(&mut self, runtime: &Runtime) -> Result<Vec<EventEnvelope>, EffectError> {
+        let store = runtime.store();
+        let mut outcomes = Vec::new();
+        for (position, commit) in store.events().iter().enumerate().skip(self.scanned) {
+            if let Some(outcome) = self.execute_one(commit)? {
+                if store.position(&outcome.event_id()).is_none() {
+                    outcomes.push(outcome);
+                }
+            }
+            self.scanned = position + 1;
+        }
+        Ok(outcomes)
+    }
+
+    /// Outcome observation of `event`, performing its effect if it is due
+    fn execute_one(&mut self, event: &EventEnvelope) -> Result<Option<EventEnvelope>, EffectError> {
+        if !matches!(event.kind(), EventKind::Commit) {
+            return Ok(None);
+        }
+        let Ok(effect) = event.payload().to_value::<EffectCommit>() else {
+            return Ok(None);
+        };
+        let Some(effector) = self.effectors.get_mut(&effect.commit_type) else {
+            return Ok(None);
+        };
+
+        let commit = event.event_id();
+        let status = match self.journal.entry(&commit)? {
+            Some(JournalEntry::Finished(status)) => status,
+            Some(JournalEntry::Started) => {
+                self.journal.finish(&commit, &EffectStatus::Interrupted)?;
+                EffectStatus::Interrupted
+            }
+            None => {
+                self.journal.begin(&commit)?;
+                let status = match effector.perform(event, &effect.body) {
+                    Ok(result) => EffectStatus::Done(result),
+                    Err(reason) => EffectStatus::Failed(reason),
+                };
+                self.journal.finish(&commit, &status)?;
+                status
+            }
+        };
+        let outcome = EffectOutcome {
+            commit_type: effect.commit_type,
+            status,
+        };
+        Ok(Some(outcome.to_observation(commit)?))
+    }
+}
+
+/// Effect executor errors
+#[derive(Debug, Error)]
+pub enum EffectError {
+    #[error("journal io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("cannot build outcome event: {0}")]
+    Event(#[from] EventError),
+}
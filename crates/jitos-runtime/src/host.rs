@@ -0,0 +1,39 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Host Boundary - Everything the Tick Loop Needs from Outside
+//!
+//! The tick loop never reads a clock, a socket or a key file. Whatever the
+//! world contributes to a tick (new observations, proposed SLAPs, the node's
+//! signature) comes through [`Host`], so the loop is a pure function of its
+//! state and what the host returned.
+
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::{Hash, Proposal};
+
+use crate::Views;
+
+/// The world, as seen by the tick loop
+pub trait Host {
+    /// Observation events that arrived since the previous tick, in arrival
+    /// order
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope>;
+
+    /// SLAPs to propose for this tick, given the views after ingest
+    fn proposals(&mut self, tick: u64, views: &Views) -> Vec<Proposal>;
+
+    /// Sign the canonical payload of this tick's Commit event
+    fn sign(&mut self, payload: &CanonicalBytes) -> Signature;
+
+    /// Sign a receipt's [`jitos_core::Receipt::digest`] with the node's key
+    ///
+    /// Receipts are left unsigned when this returns `None` (the default).
+    fn sign_receipt(&mut self, _digest: &Hash) -> Option<Signature> {
+        None
+    }
+
+    /// Agent the runtime's Commit events are attributed to
+    fn agent(&self) -> Option<AgentId> {
+        None
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! SLAP Interpreter - From Recorded Decisions Back to the Graph
+//!
+//! A scheduling Decision records its batches as canonical SLAP hashes
+//! ([`ScheduleDecision`]); the SLAPs themselves are in the
+//! [`OBS_SLAP_PROPOSALS_V0`] observations of the tick that proposed them,
+//! which may be an earlier tick for a deferred SLAP. [`SlapInterpreter`]
+//! learns SLAPs from those observations and applies a Decision's batches to
+//! a [`WarpGraph`] with [`execute_batch`], the same path the tick loop uses.
+//! The resulting commit digest must be the `state_hash` of the [`Receipt`]
+//! in the Commit that follows the Decision.
+//!
+//! Unlike [`crate::replay`], nothing is rescheduled: the recorded schedule
+//! is taken as given, so this checks that the worldline determines the
+//! graph, not that the scheduler would decide the same way again.
+
+use std::collections::HashMap;
+
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::events::{EventEnvelope, EventId, EventKind};
+use jitos_core::{Hash, Proposal, Receipt, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::{execute_batch, ExecError, ScheduleDecision};
+use thiserror::Error;
+
+use crate::OBS_SLAP_PROPOSALS_V0;
+
+/// Applies recorded schedules to a graph
+#[derive(Debug, Clone, Default)]
+pub struct SlapInterpreter {
+    /// Every SLAP proposed so far, by canonical hash
+    slaps: HashMap<Hash, Slap>,
+}
+
+impl SlapInterpreter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Learn the SLAPs of an [`OBS_SLAP_PROPOSALS_V0`] observation
+    ///
+    /// Other events are ignored.
+    pub fn learn(&mut self, event: &EventEnvelope) -> Result<(), InterpretError> {
+        if event.observation_type() != Some(OBS_SLAP_PROPOSALS_V0) {
+            return Ok(());
+        }
+        let proposals: Vec<Proposal> = event.payload().to_value()?;
+        for proposal in proposals {
+            let hash = canonical::hash_canonical(&proposal.slap)?;
+            self.slaps.insert(hash, proposal.slap);
+        }
+        Ok(())
+    }
+
+    /// Apply the batches `decision` schedules to `graph`, in order, and
+    /// return the new commit digest
+    ///
+    /// # Errors
+    ///
+    /// [`InterpretError::NotASchedule`] if `decision` is not a scheduling
+    /// Decision, [`InterpretError::UnknownSlap`] if it schedules a SLAP not
+    /// learned yet, [`InterpretError::Exec`] if a batch cannot be applied.
+    /// `graph` is left unchanged on error.
+    pub fn apply(
+        &self,
+        graph: &mut WarpGraph,
+        decision: &EventEnvelope,
+    ) -> Result<Hash, InterpretError> {
+        let schedule = schedule(decision)?;
+        let mut batches = Vec::with_capacity(schedule.batches.len());
+        for batch in &schedule.batches {
+            let slaps = batch
+                .iter()
+                .map(|hash| {
+                    self.slaps
+                        .get(hash)
+                        .cloned()
+                        .ok_or(InterpretError::UnknownSlap {
+                            decision: decision.event_id(),
+                            slap: *hash,
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            batches.push(slaps);
+        }
+
+        let mut next = graph.clone();
+        for batch in &batches {
+            execute_batch(&mut next, batch)?;
+        }
+        *graph = next;
+        Ok(graph.compute_hash())
+    }
+
+    /// [`Self::apply`], then check the digest against the [`Receipt`] that
+    /// `commit` carries
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::apply`]; [`InterpretError::NotAReceipt`] if `commit` does
+    /// not carry a receipt and [`InterpretError::DigestMismatch`] if the
+    /// digests differ. `graph` is left unchanged on error.
+    pub fn apply_committed(
+        &self,
+        graph: &mut WarpGraph,
+        decision: &EventEnvelope,
+        commit: &EventEnvelope,
+    ) -> Result<Hash, InterpretError> {
+        let receipt: Receipt = commit
+            .payload()
+            .to_value()
+            .map_err(|_| InterpretError::NotAReceipt(commit.event_id()))?;
+        let mut next = graph.clone();
+        let actual = self.apply(&mut next, decision)?;
+        if actual != receipt.state_hash {
+            return Err(InterpretError::DigestMismatch {
+                tick: receipt.tick,
+                expected: receipt.state_hash,
+                actual,
+            });
+        }
+        *graph = next;
+        Ok(actual)
+    }
+}
+
+/// Interpret a worldline onto `graph`, the graph it started from
+///
+/// Every Commit carrying a [`Receipt`] is checked against the schedule of
+/// its Decision parent; other events only teach the interpreter SLAPs.
+/// Returns the graph after the last such Commit.
+///
+/// # Errors
+///
+/// The first [`InterpretError`] of [`SlapInterpreter::apply_committed`].
+pub fn interpret(
+    events: &[EventEnvelope],
+    mut graph: WarpGraph,
+) -> Result<WarpGraph, InterpretError> {
+    let mut interpreter = SlapInterpreter::new();
+    let index: HashMap<EventId, &EventEnvelope> =
+        events.iter().map(|e| (e.event_id(), e)).collect();
+    for event in events {
+        interpreter.learn(event)?;
+        if !matches!(event.kind(), EventKind::Commit)
+            || event.payload().to_value::<Receipt>().is_err()
+        {
+            continue;
+        }
+        let Some(decision) = event
+            .parents()
+            .iter()
+            .filter_map(|id| index.get(id))
+            .find(|e| schedule(e).is_ok())
+        else {
+            continue;
+        };
+        interpreter.apply_committed(&mut graph, decision, event)?;
+    }
+    Ok(graph)
+}
+
+fn schedule(decision: &EventEnvelope) -> Result<ScheduleDecision, InterpretError> {
+    if !matches!(decision.kind(), EventKind::Decision) {
+        return Err(InterpretError::NotASchedule(decision.event_id()));
+    }
+    decision
+        .payload()
+        .to_value()
+        .map_err(|_| InterpretError::NotASchedule(decision.event_id()))
+}
+
+/// SLAP interpreter errors
+#[derive(Debug, Error)]
+pub enum InterpretError {
+    #[error("event {0} is not a scheduling decision")]
+    NotASchedule(EventId),
+    #[error("event {0} does not carry a receipt")]
+    NotAReceipt(EventId),
+    #[error("decision {decision} schedules SLAP {slap}, which was never proposed")]
+    UnknownSlap { decision: EventId, slap: Hash },
+    #[error("tick {tick} yields graph {actual}, receipt records {expected}")]
+    DigestMismatch {
+        tick: u64,
+        expected: Hash,
+        actual: Hash,
+    },
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("batch execution failed: {0}")]
+    Exec(#[from] ExecError),
+}
@@ -0,0 +1,222 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Invariants - Predicates Checked After Every Tick
+//!
+//! An [`InvariantRegistry`] holds declarative [`InvariantSpec`]s over the
+//! graph and the views, each identified by the canonical hash of its spec.
+//! The runtime checks them after every tick. An invariant that stops holding
+//! is reported once, as a [`Violation`] naming the SLAPs the tick applied,
+//! until it holds again.
+//!
+//! Violations are recorded as [`OBS_INVARIANT_VIOLATION_V0`] observations
+//! citing the tick's Commit, and the tick returns
+//! [`crate::RuntimeError::Invariant`]: a
+//! [`jitos_core::JitosError::InvariantViolation`] carrying the first one,
+//! with the tick's outcome and every violation.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use jitos_core::{canonical, Hash, Violation};
+use jitos_graph::WarpGraph;
+use serde::{Deserialize, Serialize};
+
+use crate::Views;
+
+/// Observation type tag for a broken invariant (the payload is a
+/// [`Violation`])
+pub const OBS_INVARIANT_VIOLATION_V0: &str = "OBS_INVARIANT_VIOLATION_V0";
+
+/// What an invariant requires
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvariantSpec {
+    /// At most `max` nodes
+    MaxNodes(u64),
+    /// At most `max` nodes of `node_type`
+    MaxNodesOfType { node_type: String, max: u64 },
+    /// Every `edge_type` edge ends at a node of `target_type`
+    EdgeTarget {
+        edge_type: String,
+        target_type: String,
+    },
+    /// At most `max` timers pending at the clock's current time
+    MaxPendingTimers(u64),
+    /// A predicate registered under this name with
+    /// [`InvariantRegistry::with_predicate`]
+    Predicate(String),
+}
+
+impl InvariantSpec {
+    /// Canonical hash identifying the invariant
+    pub fn id(&self) -> Hash {
+        canonical::hash_canonical(self).expect("invariant specs are always encodable")
+    }
+}
+
+/// How a predicate found its invariant broken
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breach {
+    /// The measured quantity the invariant bounds
+    pub observed: u64,
+    /// Ids of the offending nodes
+    pub witnesses: Vec<Hash>,
+}
+
+/// Custom invariant: `Some` breach if it does not hold
+pub type Predicate = Box<dyn Fn(&WarpGraph, &Views) -> Option<Breach>>;
+
+/// Invariants the runtime checks after every tick
+#[derive(Default)]
+pub struct InvariantRegistry {
+    invariants: BTreeMap<Hash, InvariantSpec>,
+    predicates: BTreeMap<String, Predicate>,
+    /// Invariants broken as of the last check
+    broken: BTreeSet<Hash>,
+}
+
+impl InvariantRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check `spec` after every tick
+    ///
+    /// # Panics
+    ///
+    /// Panics for an [`InvariantSpec::Predicate`]; register those with
+    /// [`Self::with_predicate`].
+    pub fn with(mut self, spec: InvariantSpec) -> Self {
+        assert!(
+            !matches!(spec, InvariantSpec::Predicate(_)),
+            "predicate invariants are registered with with_predicate"
+        );
+        self.invariants.insert(spec.id(), spec);
+        self
+    }
+
+    /// Check `predicate` after every tick, as [`InvariantSpec::Predicate`]
+    /// of `name`
+    pub fn with_predicate(
+        mut self,
+        name: impl Into<String>,
+        predicate: impl Fn(&WarpGraph, &Views) -> Option<Breach> + 'static,
+    ) -> Self {
+        let name = name.into();
+        let spec = InvariantSpec::Predicate(name.clone());
+        self.invariants.insert(spec.id(), spec);
+        self.predicates.insert(name, Box::new(predicate));
+        self
+    }
+
+    /// Registered invariants, ascending by id
+    pub fn specs(&self) -> impl Iterator<Item = (&Hash, &InvariantSpec)> {
+        self.invariants.iter()
+    }
+
+    /// Whether `invariant` was broken at the last check
+    pub fn is_broken(&self, invariant: &Hash) -> bool {
+        self.broken.contains(invariant)
+    }
+
+    /// Check every invariant against the state after `tick`
+    ///
+    /// Returns the invariants that stopped holding, ascending by id; those
+    /// still broken from an earlier tick are not reported again.
+    pub fn check(
+        &mut self,
+        tick: u64,
+        slaps: &[Hash],
+        graph: &WarpGraph,
+        views: &Views,
+    ) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for (id, spec) in &self.invariants {
+            let Some(breach) = self.evaluate(spec, graph, views) else {
+                self.broken.remove(id);
+                continue;
+            };
+            if self.broken.insert(*id) {
+                violations.push(Violation {
+                    invariant: *id,
+                    tick,
+                    slaps: slaps.to_vec(),
+                    observed: breach.observed,
+                    witnesses: breach.witnesses,
+                });
+            }
+        }
+        violations
+    }
+
+    /// The invariants [`Self::check`] would report against this state,
+    /// without recording them as broken
+    pub fn preview(
+        &self,
+        tick: u64,
+        slaps: &[Hash],
+        graph: &WarpGraph,
+        views: &Views,
+    ) -> Vec<Violation> {
+        self.invariants
+            .iter()
+            .filter(|(id, _)| !self.broken.contains(*id))
+            .filter_map(|(id, spec)| {
+                let breach = self.evaluate(spec, graph, views)?;
+                Some(Violation {
+                    invariant: *id,
+                    tick,
+                    slaps: slaps.to_vec(),
+                    observed: breach.observed,
+                    witnesses: breach.witnesses,
+                })
+            })
+            .collect()
+    }
+
+    fn evaluate(&self, spec: &InvariantSpec, graph: &WarpGraph, views: &Views) -> Option<Breach> {
+        let breach = |observed: usize, max: u64, witnesses: Vec<Hash>| {
+            let observed = observed as u64;
+            (observed > max).then_some(Breach {
+                observed,
+                witnesses,
+            })
+        };
+        match spec {
+            InvariantSpec::MaxNodes(max) => breach(graph.nodes.len(), *max, vec![]),
+            InvariantSpec::MaxNodesOfType { node_type, max } => {
+                let ids: Vec<Hash> = graph
+                    .nodes_of_type(node_type)
+                    .map(|(_, node)| node.id.hash())
+                    .collect();
+                breach(ids.len(), *max, ids)
+            }
+            InvariantSpec::EdgeTarget {
+                edge_type,
+                target_type,
+            } => {
+                let mut sources: Vec<Hash> = graph
+                    .edges
+                    .values()
+                    .filter(|e| &e.edge_type == edge_type)
+                    .filter(|e| {
+                        graph
+                            .nodes
+                            .get(e.target)
+                            .is_none_or(|t| &t.node_type != target_type)
+                    })
+                    .filter_map(|e| graph.nodes.get(e.source).map(|n| n.id.hash()))
+                    .collect();
+                sources.sort();
+                sources.dedup();
+                breach(sources.len(), 0, sources)
+            }
+            InvariantSpec::MaxPendingTimers(max) => {
+                let pending = views.timers().pending_timers(views.clock().now()).len();
+                breach(pending, *max, vec![])
+            }
+            InvariantSpec::Predicate(name) => {
+                self.predicates.get(name).and_then(|p| p(graph, views))
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! # jitos-runtime
+//!
+//! The kernel tick loop: observations in, scheduled SLAPs applied to the
+//! graph, Decision/Commit events and a [`jitos_core::Receipt`] out.
+//!
+//! The loop is pure. Everything nondeterministic (arrivals, proposals,
+//! signing) crosses the [`Host`] boundary, so replaying the same host
+//! answers reproduces the same worldline, graph and receipts. Host-side
+//! helpers that do touch the world, such as [`ClockSampler`], live here too
+//! but only ever feed the loop through [`Host`].
+
+pub mod branches;
+pub mod clock_sampler;
+pub mod effects;
+pub mod host;
+pub mod interpret;
+pub mod invariants;
+pub mod receipts;
+pub mod recovery;
+pub mod replay;
+pub mod runtime;
+pub mod timer_driver;
+
+pub use branches::{Branch, BranchError, BranchManager, ForkOrigin, FORK_POLICY_V0, MAIN_BRANCH};
+pub use clock_sampler::{
+    ClockSampler, ExternalClock, MonotonicClock, SystemClock, TimeSource, WithClock,
+};
+pub use effects::{
+    EffectCommit, EffectError, EffectExecutor, EffectJournal, EffectOutcome, EffectStatus,
+    Effector, FileJournal, JournalEntry, MemoryJournal, OBS_EFFECT_OUTCOME_V0,
+};
+pub use host::Host;
+pub use interpret::{interpret, InterpretError, SlapInterpreter};
+pub use invariants::{
+    Breach, InvariantRegistry, InvariantSpec, Predicate, OBS_INVARIANT_VIOLATION_V0,
+};
+pub use receipts::{
+    verify_chain, ChainSummary, Divergence, DivergenceReason, ReceiptVerifier, VerifyError,
+};
+pub use recovery::{
+    FileTickJournal, MemoryTickJournal, Recovery, TickIntent, TickJournal, TickJournalError,
+};
+pub use runtime::{
+    DryRun, Runtime, RuntimeError, TickOutcome, Views, CLOCK_VIEW, LOGICAL_CLOCK_POLICY_V0,
+    OBS_SLAP_PROPOSALS_V0, TIMER_VIEW,
+};
+pub use timer_driver::{
+    FiredTimer, TimerCommit, TimerDriver, TimerDriverError, TimerEffector, TimerOutcome,
+};
@@ -0,0 +1,215 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Receipt Chain Verification
+//!
+//! Receipts are only worth keeping if someone checks them. [`verify_chain`]
+//! walks a run of receipts in tick order and checks, per receipt:
+//!
+//! 1. ticks are consecutive and each receipt links to its predecessor's
+//!    [`Receipt::digest`]
+//! 2. a signature, if present, verifies over the digest
+//! 3. if the worldline holds the receipt's Commit, the applied SLAPs are
+//!    exactly the batches of the Decision it commits; a receipt that applied
+//!    SLAPs must have been committed
+//! 4. the graph state is re-derived where possible: a tick that applied
+//!    nothing leaves the state hash unchanged, and a state with a snapshot
+//!    must load and hash to itself
+//!
+//! The first receipt that fails is reported as a [`Divergence`].
+
+use std::collections::HashMap;
+
+use jitos_core::events::{EventEnvelope, EventKind, EventStore, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::{Hash, Receipt};
+use jitos_graph::snapshot::{SnapshotError, SnapshotStore};
+use jitos_scheduler::ScheduleDecision;
+use thiserror::Error;
+
+/// Checks receipt signatures against the signing node's key
+pub trait ReceiptVerifier {
+    /// True if `signature` is a valid signature over `digest`
+    fn verify(&self, digest: &Hash, signature: &Signature) -> bool;
+}
+
+impl<F: Fn(&Hash, &Signature) -> bool> ReceiptVerifier for F {
+    fn verify(&self, digest: &Hash, signature: &Signature) -> bool {
+        self(digest, signature)
+    }
+}
+
+/// What a successful verification covered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChainSummary {
+    /// Receipts verified
+    pub receipts: usize,
+    /// Receipts matched to a Commit in the worldline
+    pub committed: usize,
+    /// Receipts whose state was checked against a snapshot
+    pub snapshots: usize,
+    /// Receipts whose signature was verified
+    pub signed: usize,
+}
+
+/// The first receipt that failed verification
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("receipt chain diverges at tick {tick}: {reason}")]
+pub struct Divergence {
+    pub tick: u64,
+    /// Index of the receipt in the verified slice
+    pub index: usize,
+    pub reason: DivergenceReason,
+}
+
+/// Why a receipt failed verification
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DivergenceReason {
+    #[error("expected tick {expected}")]
+    TickGap { expected: u64 },
+    #[error("links to {found:?}, previous receipt digest is {expected}")]
+    BrokenLink { expected: Hash, found: Option<Hash> },
+    #[error("signature does not verify over digest {digest}")]
+    BadSignature { digest: Hash },
+    #[error("applied SLAPs but no Commit in the worldline carries it")]
+    Uncommitted,
+    #[error("applied SLAPs differ from the batches of Decision {decision}")]
+    ScheduleMismatch { decision: Hash },
+    #[error("applied no SLAPs but state moved from {expected} to {found}")]
+    StateChanged { expected: Hash, found: Hash },
+    #[error("snapshot of state decodes to graph {found}")]
+    SnapshotMismatch { found: Hash },
+}
+
+/// Verification errors
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    #[error(transparent)]
+    Diverged(#[from] Divergence),
+    #[error("snapshot store error: {0}")]
+    Snapshot(SnapshotError),
+}
+
+/// Verify `receipts` (consecutive ticks, oldest first) against the worldline
+/// in `store` and the graphs in `snapshots`
+///
+/// # Errors
+///
+/// Returns [`VerifyError::Diverged`] for the first receipt that fails, or
+/// [`VerifyError::Snapshot`] if a snapshot cannot be read at all.
+pub fn verify_chain(
+    receipts: &[Receipt],
+    store: &MemoryEventStore,
+    snapshots: &impl SnapshotStore,
+    verifier: &impl ReceiptVerifier,
+) -> Result<ChainSummary, VerifyError> {
+    let commits = committed_receipts(store);
+    let mut summary = ChainSummary::default();
+    let mut prev: Option<(&Receipt, Hash)> = None;
+
+    for (index, receipt) in receipts.iter().enumerate() {
+        let diverge = |reason| Divergence {
+            tick: receipt.tick,
+            index,
+            reason,
+        };
+        let digest = receipt
+            .digest()
+            .expect("receipts are always canonically encodable");
+
+        // 1. Chaining
+        if let Some((prev, prev_digest)) = prev {
+            if receipt.tick != prev.tick + 1 {
+                return Err(diverge(DivergenceReason::TickGap {
+                    expected: prev.tick + 1,
+                })
+                .into());
+            }
+            if receipt.prev_receipt != Some(prev_digest) {
+                return Err(diverge(DivergenceReason::BrokenLink {
+                    expected: prev_digest,
+                    found: receipt.prev_receipt,
+                })
+                .into());
+            }
+        }
+
+        // 2. Signature
+        if let Some(signature) = &receipt.signature {
+            let valid = hex::decode(signature)
+                .ok()
+                .and_then(|bytes| Signature::new(bytes).ok())
+                .is_some_and(|signature| verifier.verify(&digest, &signature));
+            if !valid {
+                return Err(diverge(DivergenceReason::BadSignature { digest }).into());
+            }
+            summary.signed += 1;
+        }
+
+        // 3. Commit and Decision
+        match commits.get(&digest) {
+            Some(decision) => {
+                let scheduled: Option<Vec<Hash>> = decision
+                    .payload()
+                    .to_value::<ScheduleDecision>()
+                    .ok()
+                    .map(|d| d.batches.into_iter().flatten().collect());
+                if scheduled.as_ref() != Some(&receipt.applied_slaps) {
+                    return Err(diverge(DivergenceReason::ScheduleMismatch {
+                        decision: decision.event_id(),
+                    })
+                    .into());
+                }
+                summary.committed += 1;
+            }
+            None if !receipt.applied_slaps.is_empty() => {
+                return Err(diverge(DivergenceReason::Uncommitted).into());
+            }
+            None => {}
+        }
+
+        // 4. State
+        if let Some((prev, _)) = prev {
+            if receipt.applied_slaps.is_empty() && receipt.state_hash != prev.state_hash {
+                return Err(diverge(DivergenceReason::StateChanged {
+                    expected: prev.state_hash,
+                    found: receipt.state_hash,
+                })
+                .into());
+            }
+        }
+        if snapshots.contains(&receipt.state_hash) {
+            match snapshots.load(&receipt.state_hash) {
+                Ok(_) => summary.snapshots += 1,
+                Err(SnapshotError::DigestMismatch { found, .. }) => {
+                    return Err(diverge(DivergenceReason::SnapshotMismatch { found }).into());
+                }
+                Err(e) => return Err(VerifyError::Snapshot(e)),
+            }
+        }
+
+        summary.receipts += 1;
+        prev = Some((receipt, digest));
+    }
+    Ok(summary)
+}
+
+/// Receipt digest -> the Decision its Commit commits, for every Commit in
+/// `store` whose payload is a receipt
+fn committed_receipts(store: &MemoryEventStore) -> HashMap<Hash, &EventEnvelope> {
+    store
+        .events()
+        .iter()
+        .filter(|e| matches!(e.kind(), EventKind::Commit))
+        .filter_map(|commit| {
+            let receipt: Receipt = commit.payload().to_value().ok()?;
+            let digest = receipt.digest().ok()?;
+            let decision = commit
+                .parents()
+                .iter()
+                .filter_map(|id| store.get(id))
+                .find(|e| matches!(e.kind(), EventKind::Decision))?;
+            Some((digest, decision))
+        })
+        .collect()
+}
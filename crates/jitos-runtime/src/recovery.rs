@@ -0,0 +1,157 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Crash Recovery - A Write-Ahead Journal for Ticks
+//!
+//! With a [`TickJournal`] attached, [`Runtime::tick`] journals a
+//! [`TickIntent`] (tick, scheduled batches, pre-state hash) before applying
+//! anything, and marks it finished once the tick's Commit is recorded. An
+//! intent still pending when a node restarts means a tick was cut short.
+//!
+//! The restarted runtime (rebuilt from its worldline, e.g. by
+//! [`crate::replay::run`], then given the journal) refuses to tick until
+//! [`Runtime::recover`] has resolved the intent against its state:
+//!
+//! - the tick's Commit is in the worldline: the tick completed, only the
+//!   journal missed it; the intent is finished ([`Recovery::Completed`])
+//! - the runtime is at the tick and its graph is the pre-state: nothing of
+//!   the tick took effect; the intent is discarded and the tick runs again
+//!   from the host's answers ([`Recovery::RolledBack`])
+//! - anything else is an error: the state the node restarted from is not
+//!   one the journal can vouch for.
+//!
+//! [`Runtime::tick`]: crate::Runtime::tick
+//! [`Runtime::recover`]: crate::Runtime::recover
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::{Hash, Slap};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A tick about to be applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TickIntent {
+    pub tick: u64,
+    /// Batches the scheduler chose, in application order
+    pub batches: Vec<Vec<Slap>>,
+    /// Graph commit digest before the tick
+    pub pre_state: Hash,
+}
+
+/// How [`crate::Runtime::recover`] resolved the journal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    /// No tick was pending
+    Clean,
+    /// The pending tick had committed
+    Completed { tick: u64 },
+    /// The pending tick had not taken effect and was discarded
+    RolledBack { tick: u64 },
+}
+
+/// Durable record of the tick in progress
+///
+/// `begin` must be durable before it returns: the runtime applies the tick
+/// right after.
+pub trait TickJournal {
+    /// The tick begun but not finished, if any
+    fn pending(&self) -> Result<Option<TickIntent>, TickJournalError>;
+
+    fn begin(&mut self, intent: &TickIntent) -> Result<(), TickJournalError>;
+
+    /// Mark the pending tick finished (or discarded)
+    fn finish(&mut self, tick: u64) -> Result<(), TickJournalError>;
+}
+
+/// In-memory journal (survives runtime restarts, not process crashes)
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTickJournal {
+    pending: Option<TickIntent>,
+}
+
+impl MemoryTickJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TickJournal for MemoryTickJournal {
+    fn pending(&self) -> Result<Option<TickIntent>, TickJournalError> {
+        Ok(self.pending.clone())
+    }
+
+    fn begin(&mut self, intent: &TickIntent) -> Result<(), TickJournalError> {
+        self.pending = Some(intent.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self, tick: u64) -> Result<(), TickJournalError> {
+        if self.pending.as_ref().is_some_and(|p| p.tick == tick) {
+            self.pending = None;
+        }
+        Ok(())
+    }
+}
+
+/// Directory-backed journal: `tick.cbor` holds the pending [`TickIntent`]
+/// and is removed when the tick finishes
+#[derive(Debug, Clone)]
+pub struct FileTickJournal {
+    dir: PathBuf,
+}
+
+impl FileTickJournal {
+    /// Open (creating if needed) a journal directory
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, TickJournalError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self) -> PathBuf {
+        self.dir.join("tick.cbor")
+    }
+}
+
+impl TickJournal for FileTickJournal {
+    fn pending(&self) -> Result<Option<TickIntent>, TickJournalError> {
+        match std::fs::read(self.path()) {
+            Ok(bytes) => Ok(Some(canonical::decode(&bytes)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn begin(&mut self, intent: &TickIntent) -> Result<(), TickJournalError> {
+        // Write-then-rename so a crash never leaves a truncated intent.
+        let tmp = self.dir.join("tick.cbor.tmp");
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(&canonical::encode(intent)?)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, self.path())?;
+        Ok(())
+    }
+
+    fn finish(&mut self, tick: u64) -> Result<(), TickJournalError> {
+        if self.pending()?.is_some_and(|p| p.tick == tick) {
+            std::fs::remove_file(self.path())?;
+        }
+        Ok(())
+    }
+}
+
+/// Tick journal errors
+#[derive(Debug, Error)]
+pub enum TickJournalError {
+    #[error("journal io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+}
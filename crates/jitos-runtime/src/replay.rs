@@ -0,0 +1,547 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replay Engine - Re-Execute a Worldline and Compare Receipts
+//!
+//! A worldline written by [`Runtime`] holds everything needed to run it
+//! again: each recorded tick is its observations, an
+//! [`OBS_SLAP_PROPOSALS_V0`] observation, a Decision and a Commit carrying the
+//! [`Receipt`]. [`run`] splits the worldline into those ticks, feeds them to a
+//! fresh runtime through a replaying [`Host`], and compares every receipt the
+//! runtime produces with the one recorded. The first mismatch is reported as
+//! a [`ReplayDivergence`] naming the tick, the hash that differs and the events
+//! that went into the tick.
+//!
+//! Events recorded between ticks outside the loop (timer fires and the
+//! Commits of their side effects) are re-recorded, not re-executed, before
+//! the tick that follows them. Logical time set by `SetTime` is regenerated
+//! by the tick that applies it.
+//!
+//! Ticks before the first recorded one observed nothing and are replayed as
+//! idle ticks. Events after the last Commit (an unfinished tick) are not
+//! replayed.
+//!
+//! Long worldlines need not be re-executed from genesis:
+//! [`run_from_checkpoint`] loads the graph of the latest snapshotted tick and
+//! only re-executes the ticks after it.
+//!
+//! [`reexecute`] feeds recorded ticks to a runtime the same way without
+//! comparing receipts, for counterfactual runs (e.g. a fork under another
+//! scheduler policy) that are expected to disagree with the record.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventId, EventKind, Signature};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::{canonical, Hash, Proposal, Receipt};
+use jitos_graph::snapshot::{SnapshotError, SnapshotStore};
+use jitos_scheduler::ScheduleDecision;
+use jitos_views::OBS_LOGICAL_TIME_V0;
+use thiserror::Error;
+
+use crate::{Host, Runtime, RuntimeError, Views, CLOCK_VIEW, OBS_SLAP_PROPOSALS_V0, TIMER_VIEW};
+
+/// One recorded tick, as read back from the worldline
+#[derive(Debug, Clone)]
+pub struct RecordedTick {
+    /// Events recorded outside the tick loop since the previous tick (see
+    /// [`Runtime::record`]), in worldline order
+    pub external: Vec<EventEnvelope>,
+    /// Observations the host delivered, in arrival order
+    pub observations: Vec<EventEnvelope>,
+    /// SLAPs the host proposed
+    pub proposals: Vec<Proposal>,
+    /// Event recording `proposals`, if any were proposed
+    pub proposals_event: Option<EventId>,
+    pub decision: EventEnvelope,
+    pub commit: EventEnvelope,
+    pub receipt: Receipt,
+}
+
+impl RecordedTick {
+    /// Events that went into the tick: observations, proposals, decision
+    pub fn events(&self) -> Vec<EventId> {
+        self.observations
+            .iter()
+            .map(EventEnvelope::event_id)
+            .chain(self.proposals_event)
+            .chain([self.decision.event_id()])
+            .collect()
+    }
+}
+
+/// Split `events` into the ticks [`Runtime`] recorded
+///
+/// A tick ends at each Commit whose payload is a [`Receipt`]; its
+/// observations are the ones appended since the previous such Commit, plus
+/// any re-delivered observation its Decision cites. Other Decisions, Commits
+/// and policy contexts since the previous tick are its
+/// [`RecordedTick::external`] events; uncited observations are not part of
+/// any tick.
+pub fn recorded_ticks(events: &[EventEnvelope]) -> Vec<RecordedTick> {
+    let mut ticks = Vec::new();
+    let mut pending: Vec<&EventEnvelope> = Vec::new();
+    let mut external: Vec<&EventEnvelope> = Vec::new();
+    let index: HashMap<EventId, &EventEnvelope> =
+        events.iter().map(|e| (e.event_id(), e)).collect();
+    let by_id = |id: &EventId| index.get(id).copied();
+
+    for event in events {
+        match event.kind() {
+            EventKind::Observation => pending.push(event),
+            EventKind::Commit => {
+                let Ok(receipt) = event.payload().to_value::<Receipt>() else {
+                    external.push(event);
+                    continue;
+                };
+                let Some(decision) = event
+                    .parents()
+                    .iter()
+                    .filter_map(by_id)
+                    .find(|e| matches!(e.kind(), EventKind::Decision))
+                else {
+                    external.push(event);
+                    continue;
+                };
+                let decision_id = decision.event_id();
+                let external: Vec<EventEnvelope> = external
+                    .drain(..)
+                    .filter(|e| e.event_id() != decision_id)
+                    .cloned()
+                    .collect();
+
+                let mut observations = Vec::new();
+                let mut proposals = Vec::new();
+                let mut proposals_event = None;
+                let cited: HashSet<&EventId> = decision.parents().iter().collect();
+                for obs in pending.drain(..) {
+                    if !cited.contains(&obs.event_id()) {
+                        continue;
+                    }
+                    if obs.observation_type() == Some(OBS_SLAP_PROPOSALS_V0) {
+                        proposals = obs.payload().to_value().unwrap_or_default();
+                        proposals_event = Some(obs.event_id());
+                    } else {
+                        observations.push(obs.clone());
+                    }
+                }
+                // Re-delivered observations were appended on first arrival.
+                let fresh: HashSet<EventId> = observations.iter().map(|e| e.event_id()).collect();
+                for parent in decision.parents() {
+                    if fresh.contains(parent) || Some(*parent) == proposals_event {
+                        continue;
+                    }
+                    if let Some(obs) = by_id(parent)
+                        .filter(|e| matches!(e.kind(), EventKind::Observation))
+                        .filter(|e| e.observation_type() != Some(OBS_SLAP_PROPOSALS_V0))
+                    {
+                        observations.push(obs.clone());
+                    }
+                }
+
+                ticks.push(RecordedTick {
+                    external,
+                    observations,
+                    proposals,
+                    proposals_event,
+                    decision: decision.clone(),
+                    commit: event.clone(),
+                    receipt,
+                });
+            }
+            EventKind::Decision | EventKind::PolicyContext => external.push(event),
+        }
+    }
+    ticks
+}
+
+/// Which part of a replayed tick disagreed with the record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// Graph commit digest
+    State,
+    /// A view's state hash, by view name
+    View(String),
+    /// The SLAPs applied (hashes of the canonical lists)
+    AppliedSlaps,
+    /// The scheduling Decision (event ids)
+    Decision,
+    /// Anything else in the receipt (receipt digests)
+    Receipt,
+    /// The tick was recorded but replay recorded nothing (event ids of the
+    /// recorded Commit and the zero hash)
+    Missing,
+}
+
+/// First tick where replay disagrees with the recorded worldline
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("replay diverges at tick {tick} ({mismatch:?}): expected {expected}, got {actual}")]
+pub struct ReplayDivergence {
+    pub tick: u64,
+    pub mismatch: Mismatch,
+    pub expected: Hash,
+    pub actual: Hash,
+    /// Events that went into the tick (see [`RecordedTick::events`])
+    pub suspects: Vec<EventId>,
+}
+
+/// Result of a replay that matched its record
+pub struct Replayed {
+    /// The runtime after the last recorded tick
+    pub runtime: Runtime,
+    /// Ticks run, idle ones included
+    pub ticks: u64,
+    /// Recorded ticks whose receipts matched
+    pub matched: usize,
+    /// Tick of the checkpoint replay started after (`None` from genesis)
+    pub checkpoint: Option<u64>,
+}
+
+/// Replay errors
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error(transparent)]
+    Diverged(#[from] ReplayDivergence),
+    #[error("replay failed at tick {tick}: {source}")]
+    Runtime { tick: u64, source: RuntimeError },
+    #[error("worldline starts with {found:?}, runtime expects policy context {expected}")]
+    PolicyMismatch {
+        expected: EventId,
+        found: Option<EventId>,
+    },
+    #[error("runtime has already run {0} ticks")]
+    NotFresh(u64),
+    #[error("recorded ticks are not consecutive: tick {found} after {previous}")]
+    TickOrder { previous: u64, found: u64 },
+    #[error("checkpoint at tick {tick} failed verification: {source}")]
+    Checkpoint { tick: u64, source: SnapshotError },
+}
+
+/// Re-execute the worldline cut `store.events()[..cut]` through `runtime`
+///
+/// `runtime` must be fresh and configured like the one that wrote the
+/// worldline (same scheduler policy and clock policy).
+///
+/// # Errors
+///
+/// Returns [`ReplayError::Diverged`] at the first tick whose receipt differs
+/// from the record, and the other variants if the worldline cannot be
+/// replayed at all.
+///
+/// # Panics
+///
+/// Panics if `cut` exceeds the worldline.
+pub fn run(
+    store: &MemoryEventStore,
+    cut: usize,
+    runtime: Runtime,
+) -> Result<Replayed, ReplayError> {
+    let events = &store.events()[..cut];
+    if runtime.next_tick() != 0 {
+        return Err(ReplayError::NotFresh(runtime.next_tick()));
+    }
+    let expected = runtime.scheduler().policy_context().event_id();
+    let found = events.first().map(EventEnvelope::event_id);
+    if found != Some(expected) {
+        return Err(ReplayError::PolicyMismatch { expected, found });
+    }
+
+    let ticks = recorded_ticks(events);
+    check_order(&ticks)?;
+    replay_ticks(runtime, &ticks)
+}
+
+/// Run the recorded `ticks` (consecutive, oldest first) on `runtime` as
+/// [`run`] would, without comparing receipts
+///
+/// Ticks numbered before `runtime.next_tick()` are skipped. The runtime
+/// makes its own decisions from the recorded observations and proposals, so
+/// under another policy its receipts may differ from the record.
+///
+/// # Errors
+///
+/// [`ReplayError::TickOrder`] if `ticks` are not consecutive, and
+/// [`ReplayError::Runtime`] if a tick or a re-recorded event fails.
+pub fn reexecute(runtime: &mut Runtime, ticks: &[RecordedTick]) -> Result<(), ReplayError> {
+    check_order(ticks)?;
+    let start = ticks.partition_point(|t| t.receipt.tick < runtime.next_tick());
+    drive(runtime, &ticks[start..], false)?;
+    Ok(())
+}
+
+/// [`run`], starting after the nearest verified checkpoint instead of genesis
+///
+/// A checkpoint is a recorded tick whose state has a snapshot in `snapshots`
+/// and after which nothing was left deferred. The latest one before `cut` is
+/// used: its snapshot must load and hash to the recorded state, the worldline
+/// up to it is folded into the views (no SLAP is executed), and the views
+/// must match the checkpoint receipt. Replay then continues as in [`run`].
+/// Without a checkpoint, this is [`run`].
+///
+/// # Errors
+///
+/// As [`run`]; additionally [`ReplayError::Checkpoint`] if the snapshot is
+/// corrupt, and [`ReplayError::Diverged`] at the checkpoint tick if the
+/// refolded views disagree with its receipt.
+pub fn run_from_checkpoint(
+    store: &MemoryEventStore,
+    cut: usize,
+    snapshots: &impl SnapshotStore,
+    mut runtime: Runtime,
+) -> Result<Replayed, ReplayError> {
+    let events = &store.events()[..cut];
+    if runtime.next_tick() != 0 {
+        return Err(ReplayError::NotFresh(runtime.next_tick()));
+    }
+    let expected = runtime.scheduler().policy_context().event_id();
+    let found = events.first().map(EventEnvelope::event_id);
+    if found != Some(expected) {
+        return Err(ReplayError::PolicyMismatch { expected, found });
+    }
+
+    let ticks = recorded_ticks(events);
+    check_order(&ticks)?;
+    let Some(index) = ticks.iter().rposition(|t| {
+        let settled = t
+            .decision
+            .payload()
+            .to_value::<ScheduleDecision>()
+            .is_ok_and(|d| d.deferred.is_empty());
+        settled && snapshots.contains(&t.receipt.state_hash)
+    }) else {
+        return replay_ticks(runtime, &ticks);
+    };
+    let checkpoint = &ticks[index];
+    let tick = checkpoint.receipt.tick;
+
+    // 1. The snapshot must be the recorded state.
+    let graph = snapshots
+        .load(&checkpoint.receipt.state_hash)
+        .map_err(|source| ReplayError::Checkpoint { tick, source })?;
+
+    // 2. Fold the worldline up to the checkpoint into the views.
+    let end = store
+        .position(&checkpoint.commit.event_id())
+        .expect("recorded ticks come from the store");
+    runtime
+        .restore(
+            &events[1..=end],
+            graph,
+            checkpoint.receipt.clone(),
+            checkpoint.commit.event_id(),
+        )
+        .map_err(|source| ReplayError::Runtime { tick, source })?;
+    let views = runtime.views();
+    for (name, actual) in [
+        (CLOCK_VIEW, views.clock().state_hash()),
+        (TIMER_VIEW, views.timers().state_hash()),
+    ] {
+        let expected = checkpoint
+            .receipt
+            .view_hashes
+            .get(name)
+            .copied()
+            .unwrap_or(Hash([0u8; 32]));
+        if actual != expected {
+            return Err(ReplayDivergence {
+                tick,
+                mismatch: Mismatch::View(name.to_string()),
+                expected,
+                actual,
+                suspects: checkpoint.events(),
+            }
+            .into());
+        }
+    }
+
+    // Logical time the checkpoint tick set follows its Commit.
+    let trailing = events[end + 1..]
+        .iter()
+        .take_while(|e| {
+            matches!(e.kind(), EventKind::PolicyContext)
+                || (e.observation_type() == Some(OBS_LOGICAL_TIME_V0)
+                    && e.parents().contains(&checkpoint.commit.event_id()))
+        })
+        .count();
+    runtime
+        .fold(&events[end + 1..end + 1 + trailing])
+        .map_err(|source| ReplayError::Runtime { tick, source })?;
+
+    // 3. Replay the rest.
+    let replayed = replay_ticks(runtime, &ticks[index + 1..])?;
+    Ok(Replayed {
+        checkpoint: Some(tick),
+        ..replayed
+    })
+}
+
+/// Recorded ticks must be consecutive
+fn check_order(ticks: &[RecordedTick]) -> Result<(), ReplayError> {
+    for pair in ticks.windows(2) {
+        if pair[1].receipt.tick != pair[0].receipt.tick + 1 {
+            return Err(ReplayError::TickOrder {
+                previous: pair[0].receipt.tick,
+                found: pair[1].receipt.tick,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Run `ticks` (consecutive, oldest first) on `runtime`, idling up to the
+/// first one
+fn replay_ticks(mut runtime: Runtime, ticks: &[RecordedTick]) -> Result<Replayed, ReplayError> {
+    let (ticks, matched) = drive(&mut runtime, ticks, true)?;
+    Ok(Replayed {
+        runtime,
+        ticks,
+        matched,
+        checkpoint: None,
+    })
+}
+
+/// [`replay_ticks`], comparing each receipt with its record only if
+/// `check`; returns the ticks run and the receipts matched
+fn drive(
+    runtime: &mut Runtime,
+    ticks: &[RecordedTick],
+    check: bool,
+) -> Result<(u64, usize), ReplayError> {
+    let first = ticks
+        .first()
+        .map_or(runtime.next_tick(), |t| t.receipt.tick);
+    let end = ticks.last().map_or(first, |t| t.receipt.tick + 1);
+    let mut host = ReplayHost {
+        ticks,
+        first,
+        current: None,
+    };
+    let mut matched = 0;
+    let start = runtime.next_tick();
+    for tick in start..end {
+        let record = tick.checked_sub(first).and_then(|i| ticks.get(i as usize));
+        for event in record.into_iter().flat_map(|t| &t.external) {
+            runtime
+                .record(event.clone())
+                .map_err(|source| ReplayError::Runtime { tick, source })?;
+        }
+        let outcome = runtime
+            .tick(&mut host)
+            .map_err(|source| ReplayError::Runtime { tick, source })?;
+        if let Some(record) = host.current.filter(|_| check) {
+            compare(record, &outcome.receipt, outcome.decision.as_ref())?;
+            matched += 1;
+        }
+    }
+    Ok((end - start, matched))
+}
+
+/// First difference between a recorded tick and its replay
+fn compare(
+    record: &RecordedTick,
+    receipt: &Receipt,
+    decision: Option<&EventEnvelope>,
+) -> Result<(), ReplayDivergence> {
+    let expected = &record.receipt;
+    let diverge = |mismatch, expected, actual| ReplayDivergence {
+        tick: record.receipt.tick,
+        mismatch,
+        expected,
+        actual,
+        suspects: record.events(),
+    };
+    let Some(decision) = decision else {
+        return Err(diverge(
+            Mismatch::Missing,
+            record.commit.event_id(),
+            Hash([0u8; 32]),
+        ));
+    };
+    if receipt.state_hash != expected.state_hash {
+        return Err(diverge(
+            Mismatch::State,
+            expected.state_hash,
+            receipt.state_hash,
+        ));
+    }
+    let names: BTreeSet<&String> = expected
+        .view_hashes
+        .keys()
+        .chain(receipt.view_hashes.keys())
+        .collect();
+    for name in names {
+        let zero = Hash([0u8; 32]);
+        let want = expected.view_hashes.get(name).copied().unwrap_or(zero);
+        let got = receipt.view_hashes.get(name).copied().unwrap_or(zero);
+        if want != got {
+            return Err(diverge(Mismatch::View(name.clone()), want, got));
+        }
+    }
+    if receipt.applied_slaps != expected.applied_slaps {
+        let hash = |slaps: &Vec<Hash>| {
+            canonical::hash_canonical(slaps).expect("hash lists are always encodable")
+        };
+        return Err(diverge(
+            Mismatch::AppliedSlaps,
+            hash(&expected.applied_slaps),
+            hash(&receipt.applied_slaps),
+        ));
+    }
+    if decision.event_id() != record.decision.event_id() {
+        return Err(diverge(
+            Mismatch::Decision,
+            record.decision.event_id(),
+            decision.event_id(),
+        ));
+    }
+    let digest = |r: &Receipt| r.digest().expect("receipts are always encodable");
+    if digest(receipt) != digest(expected) {
+        return Err(diverge(
+            Mismatch::Receipt,
+            digest(expected),
+            digest(receipt),
+        ));
+    }
+    Ok(())
+}
+
+/// Host answering each tick from the record
+struct ReplayHost<'a> {
+    ticks: &'a [RecordedTick],
+    /// Tick number of `ticks[0]`
+    first: u64,
+    /// Record of the tick being replayed (`None` while idling)
+    current: Option<&'a RecordedTick>,
+}
+
+impl Host for ReplayHost<'_> {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        self.current = tick
+            .checked_sub(self.first)
+            .and_then(|i| self.ticks.get(i as usize));
+        self.current
+            .map(|t| t.observations.clone())
+            .unwrap_or_default()
+    }
+
+    fn proposals(&mut self, _tick: u64, _views: &Views) -> Vec<Proposal> {
+        self.current
+            .map(|t| t.proposals.clone())
+            .unwrap_or_default()
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        self.current
+            .and_then(|t| t.commit.signature().cloned())
+            .unwrap_or_else(|| Signature::new(vec![0]).expect("non-empty signature"))
+    }
+
+    fn sign_receipt(&mut self, _digest: &Hash) -> Option<Signature> {
+        let hex = self.current?.receipt.signature.as_ref()?;
+        Signature::new(hex::decode(hex).ok()?).ok()
+    }
+
+    fn agent(&self) -> Option<AgentId> {
+        self.current?.commit.agent_id().cloned()
+    }
+}
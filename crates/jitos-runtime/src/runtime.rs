@@ -0,0 +1,748 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! The Tick Loop
+//!
+//! One [`Runtime::tick`] is:
+//!
+//! 1. ingest the host's observations into the worldline and the views
+//! 2. ask the host for proposals, given the views, and record them as an
+//!    [`OBS_SLAP_PROPOSALS_V0`] observation (once step 4 has applied them)
+//! 3. schedule them (with anything deferred earlier) via [`EchoScheduler`]
+//! 4. apply the batches to the [`WarpGraph`], metering the fuel each agent's
+//!    SLAPs used (their scheduler cost) into the [`Receipt`]
+//! 5. record the schedule as a Decision (citing this tick's observations and
+//!    the previous Commit, under the scheduler's policy context) and the
+//!    [`Receipt`] as the Commit that follows it
+//! 6. record each applied `SetTime` as an [`OBS_LOGICAL_TIME_V0`]
+//!    observation citing the Commit and a logical clock policy context, so
+//!    the clock view believes the new time from the next tick on
+//! 7. check the [`InvariantRegistry`] and record each newly broken invariant
+//!    as an [`OBS_INVARIANT_VIOLATION_V0`] observation citing the Commit
+//!
+//! A tick with nothing to cite (no observation has ever been ingested)
+//! records no events.
+//!
+//! Every tick, recorded or not, produces a [`Receipt`] chained to the
+//! previous one by digest, so a receipt commits to the whole run before it.
+//!
+//! With a [`TickJournal`] attached, steps 4-5 are bracketed by a write-ahead
+//! [`TickIntent`] (see [`crate::recovery`]).
+//!
+//! [`Runtime::submit_dry`] previews steps 3, 4, 6 and 7 for some proposals
+//! without recording anything.
+
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::events::AgentId;
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventError, EventId, EventKind};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::{FuelUsage, Hash, JitosError, Proposal, Receipt, Slap, Violation};
+use jitos_graph::rewrite::GraphChange;
+use jitos_graph::WarpGraph;
+use jitos_scheduler::{
+    execute_batch, execute_batch_logged, EchoScheduler, ExecError, Schedule, ScheduleDecision,
+    ScheduleReport,
+};
+use jitos_views::{
+    ClockError, ClockPolicyId, ClockView, LogicalTime, TimerError, TimerView, OBS_LOGICAL_TIME_V0,
+};
+use std::collections::HashMap;
+use thiserror::Error;
+
+use crate::invariants::{InvariantRegistry, OBS_INVARIANT_VIOLATION_V0};
+use crate::recovery::{Recovery, TickIntent, TickJournal, TickJournalError};
+use crate::Host;
+
+/// Observation type tag for the SLAPs proposed in a tick
+///
+/// The payload is the host's `Vec<Proposal>`, in the order returned. Recording
+/// proposals makes every tick re-executable from the worldline alone.
+pub const OBS_SLAP_PROPOSALS_V0: &str = "OBS_SLAP_PROPOSALS_V0";
+
+/// Payload of the policy context logical time observations cite
+///
+/// Paired with the tick length in nanoseconds, so each `dt` has its own
+/// policy context.
+pub const LOGICAL_CLOCK_POLICY_V0: &str = "logical-clock-v0";
+
+/// View name of the clock in receipts
+pub const CLOCK_VIEW: &str = "clock";
+/// View name of the timers in receipts
+pub const TIMER_VIEW: &str = "timers";
+
+/// The views the runtime maintains over its worldline
+#[derive(Debug, Clone)]
+pub struct Views {
+    clock: ClockView,
+    timers: TimerView,
+}
+
+impl Views {
+    fn new(clock_policy: ClockPolicyId) -> Self {
+        Self {
+            clock: ClockView::new(clock_policy),
+            timers: TimerView::new(),
+        }
+    }
+
+    pub fn clock(&self) -> &ClockView {
+        &self.clock
+    }
+
+    pub fn timers(&self) -> &TimerView {
+        &self.timers
+    }
+
+    fn apply(&mut self, event: &EventEnvelope) -> Result<(), RuntimeError> {
+        self.clock.apply_event(event)?;
+        self.timers.apply_event(event)?;
+        Ok(())
+    }
+}
+
+/// What one tick did
+#[derive(Debug, Clone)]
+pub struct TickOutcome {
+    pub tick: u64,
+    /// Observations ingested this tick, in arrival order
+    pub observations: Vec<EventId>,
+    pub schedule: Schedule,
+    /// Decision recording `schedule` (`None` for a tick with nothing to cite)
+    pub decision: Option<EventEnvelope>,
+    /// Commit carrying `receipt` (`None` with `decision`)
+    pub commit: Option<EventEnvelope>,
+    pub receipt: Receipt,
+    /// Invariants this tick newly broke, ascending by id
+    pub violations: Vec<Violation>,
+}
+
+/// What the next tick would do with some proposals ([`Runtime::submit_dry`])
+#[derive(Debug, Clone)]
+pub struct DryRun {
+    /// Tick the preview is for
+    pub tick: u64,
+    /// Ordering key, footprint, conflicts and placement of each proposal,
+    /// waiting SLAPs included
+    pub report: ScheduleReport,
+    /// Graph changes the admitted batches would make, in application order
+    pub patch: Vec<GraphChange>,
+    /// Graph digest after the admitted batches: the next receipt's
+    /// `state_hash`
+    pub state_hash: Hash,
+    /// Invariants the tick would newly break, ascending by id
+    pub violations: Vec<Violation>,
+}
+
+/// Deterministic kernel: worldline, graph, views and scheduler
+pub struct Runtime {
+    store: MemoryEventStore,
+    graph: WarpGraph,
+    scheduler: EchoScheduler,
+    views: Views,
+    /// Number of the next tick
+    tick: u64,
+    /// Commit recorded by the most recent tick that recorded one
+    last_commit: Option<EventId>,
+    /// Receipt of every tick, in tick order
+    receipts: Vec<Receipt>,
+    invariants: InvariantRegistry,
+    journal: Option<Box<dyn TickJournal>>,
+}
+
+impl Runtime {
+    /// Runtime over an empty graph
+    ///
+    /// The scheduler's policy context is the first event of the worldline.
+    pub fn new(scheduler: EchoScheduler, clock_policy: ClockPolicyId) -> Self {
+        let mut views = Views::new(clock_policy);
+        let mut store = MemoryEventStore::new();
+        let policy = scheduler.policy_context().clone();
+        views.apply(&policy).expect("views accept policy contexts");
+        store.append(policy).expect("policy context has no parents");
+        Self {
+            store,
+            graph: WarpGraph::new(),
+            scheduler,
+            views,
+            tick: 0,
+            last_commit: None,
+            receipts: Vec::new(),
+            invariants: InvariantRegistry::new(),
+            journal: None,
+        }
+    }
+
+    /// Start from `graph` instead of an empty graph
+    pub fn with_graph(mut self, graph: WarpGraph) -> Self {
+        self.graph = graph;
+        self
+    }
+
+    /// Check `invariants` after every tick
+    pub fn with_invariants(mut self, invariants: InvariantRegistry) -> Self {
+        self.invariants = invariants;
+        self
+    }
+
+    /// Journal every tick to `journal` before applying it
+    ///
+    /// If `journal` holds a pending tick, the runtime does not tick until
+    /// [`Self::recover`] resolves it.
+    pub fn with_journal(mut self, journal: impl TickJournal + 'static) -> Self {
+        self.journal = Some(Box::new(journal));
+        self
+    }
+
+    /// Resolve a tick the journal has pending (see [`crate::recovery`])
+    ///
+    /// # Errors
+    ///
+    /// - [`RuntimeError::StaleState`] if the runtime is behind the pending
+    ///   tick
+    /// - [`RuntimeError::PartialTick`] if the runtime is at the pending tick
+    ///   but its graph is not the journaled pre-state
+    /// - [`RuntimeError::Journal`] if the journal fails
+    pub fn recover(&mut self) -> Result<Recovery, RuntimeError> {
+        let Some(journal) = self.journal.as_mut() else {
+            return Ok(Recovery::Clean);
+        };
+        let Some(intent) = journal.pending()? else {
+            return Ok(Recovery::Clean);
+        };
+        let tick = intent.tick;
+        if self.tick > tick {
+            journal.finish(tick)?;
+            return Ok(Recovery::Completed { tick });
+        }
+        if self.tick < tick {
+            return Err(RuntimeError::StaleState {
+                journaled: tick,
+                next: self.tick,
+            });
+        }
+        let actual = self.graph.compute_hash();
+        if actual != intent.pre_state {
+            return Err(RuntimeError::PartialTick {
+                tick,
+                pre_state: intent.pre_state,
+                actual,
+            });
+        }
+        journal.finish(tick)?;
+        Ok(Recovery::RolledBack { tick })
+    }
+
+    /// Run one tick against `host`
+    ///
+    /// # Errors
+    ///
+    /// - [`RuntimeError::NotAnObservation`] if the host hands over another
+    ///   kind of event; nothing from this tick is ingested
+    /// - [`RuntimeError::Event`] / [`RuntimeError::Clock`] /
+    ///   [`RuntimeError::Timer`] if an observation is rejected by the store
+    ///   or a view; observations before it stay ingested
+    /// - [`RuntimeError::NoEvidence`] if SLAPs are proposed before anything
+    ///   was observed
+    /// - [`RuntimeError::InvalidTime`] if a `SetTime` proposal has a negative
+    ///   or non-finite `dt`; nothing is scheduled
+    /// - [`RuntimeError::Invariant`] if the tick broke an invariant; the tick
+    ///   is recorded as usual, followed by its violations, and the error
+    ///   carries its outcome
+    /// - [`RuntimeError::RecoveryPending`] if the journal holds a tick that
+    ///   [`Self::recover`] has not resolved; nothing is ingested
+    /// - [`RuntimeError::Exec`] if a batch cannot be applied; the tick's
+    ///   observations stay ingested, but the graph, the deferred queue and
+    ///   the rest of the worldline are left as they were before the tick
+    ///   (no proposals observation is recorded) and a journaled intent is
+    ///   finished
+    ///
+    /// Only [`RuntimeError::Invariant`] advances the tick counter; any other
+    /// error leaves it at the failed tick.
+    pub fn tick(&mut self, host: &mut impl Host) -> Result<TickOutcome, RuntimeError> {
+        let tick = self.tick;
+        if let Some(journal) = &self.journal {
+            if let Some(pending) = journal.pending()? {
+                return Err(RuntimeError::RecoveryPending { tick: pending.tick });
+            }
+        }
+
+        // 1. Ingest
+        let observations = host.observations(tick);
+        if let Some(event) = observations
+            .iter()
+            .find(|e| !matches!(e.kind(), EventKind::Observation))
+        {
+            return Err(RuntimeError::NotAnObservation(event.event_id()));
+        }
+        let observed: Vec<EventId> = observations.iter().map(|e| e.event_id()).collect();
+        for event in observations {
+            self.append(event)?;
+        }
+
+        // 2. Propose
+        let proposals = host.proposals(tick, &self.views);
+        let mut evidence: Vec<EventId> = observed.iter().copied().chain(self.last_commit).collect();
+        if evidence.is_empty() && !proposals.is_empty() {
+            return Err(RuntimeError::NoEvidence { tick });
+        }
+        for (to, dt) in proposals.iter().flat_map(|p| set_times(&p.slap)) {
+            if LogicalTime::from_set_time(to, dt).is_none() {
+                return Err(RuntimeError::InvalidTime { tick, dt });
+            }
+        }
+        // Recorded once the batches have applied.
+        let recorded = if proposals.is_empty() {
+            None
+        } else {
+            let recorded = EventEnvelope::new_observation(
+                CanonicalBytes::from_value(&proposals)?,
+                self.last_commit.into_iter().collect(),
+                Some(OBS_SLAP_PROPOSALS_V0.to_string()),
+                None,
+                None,
+            )?;
+            evidence.push(recorded.event_id());
+            Some(recorded)
+        };
+
+        // 3. Schedule
+        // A waiting SLAP keeps the agent it was deferred with.
+        let mut agents: HashMap<Hash, AgentId> = HashMap::new();
+        for proposal in &proposals {
+            if let Some(agent) = &proposal.meta.agent {
+                agents.insert(canonical::hash_canonical(&proposal.slap)?, agent.clone());
+            }
+        }
+        for (hash, entry) in self.scheduler.deferred().iter() {
+            match &entry.meta.agent {
+                Some(agent) => agents.insert(*hash, agent.clone()),
+                None => agents.remove(hash),
+            };
+        }
+        let waiting = self.scheduler.deferred().clone();
+        let schedule = self.scheduler.tick_proposals(&self.graph, proposals);
+
+        // 4. Apply
+        if let Some(journal) = self.journal.as_mut() {
+            journal.begin(&TickIntent {
+                tick,
+                batches: schedule.batches.clone(),
+                pre_state: self.graph.compute_hash(),
+            })?;
+        }
+        let mut graph = self.graph.clone();
+        let applied = schedule
+            .batches
+            .iter()
+            .try_for_each(|batch| execute_batch(&mut graph, batch).map(drop));
+        if let Err(error) = applied {
+            self.scheduler.restore_deferred(waiting);
+            if let Some(journal) = self.journal.as_mut() {
+                journal.finish(tick)?;
+            }
+            return Err(error.into());
+        }
+        self.graph = graph;
+        if let Some(recorded) = recorded {
+            self.append(recorded)?;
+        }
+
+        let hash = |slap: &Slap| canonical::hash_canonical(slap);
+        let applied_slaps = schedule
+            .batches
+            .iter()
+            .flatten()
+            .map(hash)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut fuel = FuelUsage::default();
+        for (slap, hash) in schedule.batches.iter().flatten().zip(&applied_slaps) {
+            let cost = self.scheduler.cost(slap);
+            fuel.total = fuel.total.saturating_add(cost);
+            if let Some(agent) = agents.get(hash) {
+                let used = fuel.by_agent.entry(agent.as_str().to_string()).or_insert(0);
+                *used = used.saturating_add(cost);
+            }
+        }
+        let fuel = (!applied_slaps.is_empty()).then_some(fuel);
+        let receipt = Receipt {
+            tick,
+            state_hash: self.graph.compute_hash(),
+            applied_slaps,
+            timestamp: self.views.clock.now().ns(),
+            signature: None,
+            view_hashes: Default::default(),
+            prev_receipt: None,
+            fuel,
+        }
+        .with_view_hash(CLOCK_VIEW, self.views.clock.state_hash())
+        .with_view_hash(TIMER_VIEW, self.views.timers.state_hash());
+        let receipt = match self.receipts.last() {
+            Some(prev) => receipt.chained_to(prev.digest()?),
+            None => receipt,
+        };
+        let receipt = match host.sign_receipt(&receipt.digest()?) {
+            Some(signature) => receipt.with_signature(&signature),
+            None => receipt,
+        };
+
+        // 5. Record
+        let (decision, commit) = if evidence.is_empty() {
+            (None, None)
+        } else {
+            let payload = ScheduleDecision {
+                policy: self.scheduler.policy().policy_hash(),
+                batches: schedule
+                    .batches
+                    .iter()
+                    .map(|batch| batch.iter().map(hash).collect())
+                    .collect::<Result<_, _>>()?,
+                deferred: schedule
+                    .deferred
+                    .iter()
+                    .map(|(slap, _)| hash(slap))
+                    .collect::<Result<_, _>>()?,
+            };
+            let decision = EventEnvelope::new_decision(
+                CanonicalBytes::from_value(&payload)?,
+                evidence,
+                self.scheduler.policy_context().event_id(),
+                None,
+                None,
+            )?;
+            let payload = CanonicalBytes::from_value(&receipt)?;
+            let signature = host.sign(&payload);
+            let commit = EventEnvelope::new_commit(
+                payload,
+                decision.event_id(),
+                vec![],
+                host.agent(),
+                signature,
+            )?;
+            self.append(decision.clone())?;
+            self.append(commit.clone())?;
+            self.last_commit = Some(commit.event_id());
+            (Some(decision), Some(commit))
+        };
+        if let Some(journal) = self.journal.as_mut() {
+            journal.finish(tick)?;
+        }
+
+        // 6. Logical time
+        if let Some(commit) = &commit {
+            for (to, dt) in schedule.batches.iter().flatten().flat_map(set_times) {
+                let time = LogicalTime::from_set_time(to, dt)
+                    .expect("SetTime proposals are validated before scheduling");
+                self.record_logical_time(time, commit.event_id())?;
+            }
+        }
+
+        // 7. Invariants
+        let violations =
+            self.invariants
+                .check(tick, &receipt.applied_slaps, &self.graph, &self.views);
+        for violation in &violations {
+            let observation = EventEnvelope::new_observation(
+                CanonicalBytes::from_value(violation)?,
+                commit.iter().map(EventEnvelope::event_id).collect(),
+                Some(OBS_INVARIANT_VIOLATION_V0.to_string()),
+                None,
+                None,
+            )?;
+            self.append(observation)?;
+        }
+
+        self.tick += 1;
+        self.receipts.push(receipt.clone());
+        let outcome = TickOutcome {
+            tick,
+            observations: observed,
+            schedule,
+            decision,
+            commit,
+            receipt,
+            violations,
+        };
+        match outcome.violations.first() {
+            Some(first) => Err(RuntimeError::Invariant {
+                error: JitosError::InvariantViolation(first.clone()),
+                outcome: Box::new(outcome),
+            }),
+            None => Ok(outcome),
+        }
+    }
+
+    /// Preview how the next tick would schedule and apply `proposals`,
+    /// recording nothing
+    ///
+    /// Waiting SLAPs are scheduled along with `proposals`, as the tick would
+    /// schedule them, by a copy of the scheduler; the admitted batches are
+    /// applied to a copy of the graph, their `SetTime`s to a copy of the
+    /// views, and the invariants checked against both. Nothing in the
+    /// runtime changes, so a tick given the same proposals (and no new
+    /// observations) next produces the previewed `patch` and `state_hash`.
+    ///
+    /// There are no capability checks: the tick runs no scripts, so an
+    /// `InvokeScript` fails as [`RuntimeError::Exec`] here as it would there.
+    ///
+    /// # Errors
+    ///
+    /// - [`RuntimeError::InvalidTime`] for a `SetTime` with a negative or
+    ///   non-finite `dt`
+    /// - [`RuntimeError::Exec`] if a batch cannot be applied, as the tick
+    ///   would fail
+    pub fn submit_dry(&self, proposals: Vec<Proposal>) -> Result<DryRun, RuntimeError> {
+        let tick = self.tick;
+        for (to, dt) in proposals.iter().flat_map(|p| set_times(&p.slap)) {
+            if LogicalTime::from_set_time(to, dt).is_none() {
+                return Err(RuntimeError::InvalidTime { tick, dt });
+            }
+        }
+        let report = self
+            .scheduler
+            .clone()
+            .explain_tick_proposals(&self.graph, proposals);
+        let batches = report.batches();
+        let mut graph = self.graph.clone();
+        let mut views = self.views.clone();
+        let mut patch = Vec::new();
+        for batch in &batches {
+            let slaps: Vec<Slap> = batch
+                .iter()
+                .map(|hash| {
+                    report
+                        .get(hash)
+                        .expect("batched SLAPs are reported")
+                        .slap
+                        .clone()
+                })
+                .collect();
+            let (_, changes) = execute_batch_logged(&mut graph, &slaps)?;
+            patch.extend(changes);
+            for (to, dt) in slaps.iter().flat_map(set_times) {
+                let time = LogicalTime::from_set_time(to, dt)
+                    .expect("SetTime proposals are validated before scheduling");
+                for event in logical_time_events(time, None)? {
+                    views.apply(&event)?;
+                }
+            }
+        }
+        let applied: Vec<Hash> = batches.into_iter().flatten().collect();
+        let violations = self.invariants.preview(tick, &applied, &graph, &views);
+        Ok(DryRun {
+            tick,
+            report,
+            patch,
+            state_hash: graph.compute_hash(),
+            violations,
+        })
+    }
+
+    /// Fast-forward a fresh runtime to just after a recorded tick
+    ///
+    /// `events` is the worldline up to and including the tick's Commit
+    /// (starting with the policy context already present); they are appended
+    /// and folded into the views, but no SLAP is executed: the graph is taken
+    /// as given. The scheduler's deferred queue must have been empty after
+    /// the tick. `receipts()` starts at `receipt`.
+    pub(crate) fn restore(
+        &mut self,
+        events: &[EventEnvelope],
+        graph: WarpGraph,
+        receipt: Receipt,
+        commit: EventId,
+    ) -> Result<(), RuntimeError> {
+        self.fold(events)?;
+        self.graph = graph;
+        self.tick = receipt.tick + 1;
+        self.last_commit = Some(commit);
+        self.receipts = vec![receipt];
+        Ok(())
+    }
+
+    /// A runtime continuing this one's worldline under `scheduler` and
+    /// `clock_policy`
+    ///
+    /// The fork copies the worldline, graph and receipts, refolds the views
+    /// under `clock_policy`, then records `origin` and the scheduler's
+    /// policy context. Invariants and the journal are not carried over. The
+    /// scheduler's deferred queue must be empty.
+    pub(crate) fn fork(
+        &self,
+        scheduler: EchoScheduler,
+        clock_policy: ClockPolicyId,
+        origin: EventEnvelope,
+    ) -> Result<Runtime, RuntimeError> {
+        let policy = scheduler.policy_context().clone();
+        let mut fork = Self {
+            store: MemoryEventStore::new(),
+            graph: self.graph.clone(),
+            scheduler,
+            views: Views::new(clock_policy),
+            tick: self.tick,
+            last_commit: self.last_commit,
+            receipts: self.receipts.clone(),
+            invariants: InvariantRegistry::new(),
+            journal: None,
+        };
+        fork.fold(self.store.events())?;
+        fork.append(origin)?;
+        fork.append(policy)?;
+        Ok(fork)
+    }
+
+    /// Append already recorded `events` to the worldline and the views,
+    /// executing nothing
+    pub(crate) fn fold(&mut self, events: &[EventEnvelope]) -> Result<(), RuntimeError> {
+        for event in events {
+            self.append(event.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Record an event produced outside the tick loop
+    ///
+    /// For host-side drivers that act on the views between ticks, e.g. a
+    /// timer fire Decision and the Commit of its side effect. Observations
+    /// go through [`Host::observations`] instead. Recording an event that is
+    /// already in the worldline is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// [`RuntimeError::NotRecordable`] for an observation, otherwise as for
+    /// an observation rejected in [`Self::tick`].
+    pub fn record(&mut self, event: EventEnvelope) -> Result<(), RuntimeError> {
+        if matches!(event.kind(), EventKind::Observation) {
+            return Err(RuntimeError::NotRecordable(event.event_id()));
+        }
+        self.append(event)
+    }
+
+    /// Record `time` as set by the tick that recorded `commit`
+    fn record_logical_time(
+        &mut self,
+        time: LogicalTime,
+        commit: EventId,
+    ) -> Result<(), RuntimeError> {
+        let [policy, observation] = logical_time_events(time, Some(commit))?;
+        self.append(policy)?;
+        self.append(observation)
+    }
+
+    /// Validate `event` into the worldline, then fold it into the views
+    fn append(&mut self, event: EventEnvelope) -> Result<(), RuntimeError> {
+        if self.store.position(&event.event_id()).is_some() {
+            return Ok(());
+        }
+        self.store.append(event.clone())?;
+        self.views.apply(&event)
+    }
+
+    pub fn invariants(&self) -> &InvariantRegistry {
+        &self.invariants
+    }
+
+    /// Number of the next tick
+    pub fn next_tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// The worldline so far
+    pub fn store(&self) -> &MemoryEventStore {
+        &self.store
+    }
+
+    pub fn graph(&self) -> &WarpGraph {
+        &self.graph
+    }
+
+    pub fn views(&self) -> &Views {
+        &self.views
+    }
+
+    pub fn scheduler(&self) -> &EchoScheduler {
+        &self.scheduler
+    }
+
+    /// Receipt of every tick so far, in tick order
+    pub fn receipts(&self) -> &[Receipt] {
+        &self.receipts
+    }
+
+    /// Commit recorded by the most recent tick that recorded one
+    pub fn last_commit(&self) -> Option<EventId> {
+        self.last_commit
+    }
+}
+
+/// The policy context and observation recording `time`, the observation
+/// citing `commit` (a dry run has none to cite)
+fn logical_time_events(
+    time: LogicalTime,
+    commit: Option<EventId>,
+) -> Result<[EventEnvelope; 2], RuntimeError> {
+    let policy = EventEnvelope::new_policy_context(
+        CanonicalBytes::from_value(&(LOGICAL_CLOCK_POLICY_V0, time.dt_ns))?,
+        vec![],
+        None,
+        None,
+    )?;
+    let observation = EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&time)?,
+        std::iter::once(policy.event_id()).chain(commit).collect(),
+        Some(OBS_LOGICAL_TIME_V0.to_string()),
+        None,
+        None,
+    )?;
+    Ok([policy, observation])
+}
+
+/// `(tick, dt)` of each `SetTime` in `slap`, transaction members included
+fn set_times(slap: &Slap) -> Vec<(u64, f64)> {
+    match slap {
+        Slap::SetTime { tick, dt } => vec![(*tick, *dt)],
+        Slap::Transaction(transaction) => transaction.slaps.iter().flat_map(set_times).collect(),
+        _ => vec![],
+    }
+}
+
+/// Tick loop errors
+#[derive(Debug, Error)]
+pub enum RuntimeError {
+    #[error("host supplied event {0} that is not an observation")]
+    NotAnObservation(Hash),
+    #[error("observation {0} must be ingested by a tick, not recorded")]
+    NotRecordable(Hash),
+    #[error("tick {tick} has proposals but nothing to cite as evidence")]
+    NoEvidence { tick: u64 },
+    #[error("tick {tick} proposes SetTime with invalid dt {dt}")]
+    InvalidTime { tick: u64, dt: f64 },
+    #[error("event rejected: {0}")]
+    Event(#[from] EventError),
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("clock view rejected event: {0}")]
+    Clock(#[from] ClockError),
+    #[error("timer view rejected event: {0}")]
+    Timer(#[from] TimerError),
+    #[error("batch execution failed: {0}")]
+    Exec(#[from] ExecError),
+    #[error("tick {} broke an invariant: {error}", outcome.tick)]
+    Invariant {
+        /// [`JitosError::InvariantViolation`] with the first violation
+        error: JitosError,
+        /// The recorded tick, every violation included
+        outcome: Box<TickOutcome>,
+    },
+    #[error("tick journal failed: {0}")]
+    Journal(#[from] TickJournalError),
+    #[error("tick {tick} is pending in the journal; recover before ticking")]
+    RecoveryPending { tick: u64 },
+    #[error("journal has tick {journaled} pending, runtime is only at tick {next}")]
+    StaleState { journaled: u64, next: u64 },
+    #[error("tick {tick} was cut short: graph is {actual}, journal expects {pre_state}")]
+    PartialTick {
+        tick: u64,
+        pre_state: Hash,
+        actual: Hash,
+    },
+}
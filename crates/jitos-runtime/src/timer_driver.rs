@@ -0,0 +1,226 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Timer Driver - Firing Due Timers Between Ticks
+//!
+//! Timers never fire on their own (SPEC-0004): something has to look at
+//! [`TimerView::due_timers`], decide, act, and record what it did. A
+//! [`TimerDriver`] does that for every due timer, in order:
+//!
+//! 1. record the fire Decision (derived by [`TimerView::propose_fires`], so
+//!    it cites the timer request and the clock samples behind `now`, with the
+//!    timer policy as its policy parent)
+//! 2. perform the side effect through a [`TimerEffector`]
+//! 3. record a signed Commit of the outcome, parented by the Decision
+//!
+//! The Decision is written ahead of the effect. A driver that crashes
+//! between 1 and 3 leaves a fire with no Commit; the next driver over the
+//! same worldline finds it and performs the effect again before anything
+//! new, under the same key (the Decision's id), so effectors can make the
+//! retry idempotent. A fire with a Commit is never performed again.
+//!
+//! [`TimerView::due_timers`]: jitos_views::TimerView::due_timers
+//! [`TimerView::propose_fires`]: jitos_views::TimerView::propose_fires
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventError, EventId, EventKind};
+use jitos_core::Hash;
+use jitos_views::{DeriveError, FireSemantics, TimerFire};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{Host, Runtime, RuntimeError};
+
+/// Performs the side effect of a fired timer
+pub trait TimerEffector {
+    /// Act on `fire`
+    ///
+    /// `key` is the id of the fire Decision. It is the same every time the
+    /// fire is retried, including by a driver restarted after a crash. An
+    /// `Err` is recorded as [`TimerOutcome::Failed`] and not retried.
+    fn fire(&mut self, key: &EventId, fire: &TimerFire) -> Result<(), String>;
+}
+
+impl<F: FnMut(&EventId, &TimerFire) -> Result<(), String>> TimerEffector for F {
+    fn fire(&mut self, key: &EventId, fire: &TimerFire) -> Result<(), String> {
+        self(key, fire)
+    }
+}
+
+/// What performing a timer's side effect came to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimerOutcome {
+    Done,
+    Failed(String),
+}
+
+/// Payload of the Commit recording a timer's side effect
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimerCommit {
+    pub request_id: Hash,
+    pub fired_at_ns: u64,
+    pub outcome: TimerOutcome,
+}
+
+/// One timer the driver fired
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FiredTimer {
+    pub decision: EventId,
+    pub commit: EventId,
+    pub fire: TimerFire,
+    pub outcome: TimerOutcome,
+    /// The Decision was recorded by an earlier, interrupted drive (possibly
+    /// before a crash) and this drive completed it
+    pub recovered: bool,
+}
+
+/// Fires due timers: Decision, side effect, signed Commit
+pub struct TimerDriver {
+    policy: EventEnvelope,
+    semantics: FireSemantics,
+    /// Fire Decisions under `policy` that have no Commit yet, in worldline
+    /// order
+    open: Vec<EventEnvelope>,
+    /// Worldline position up to which `open` is up to date
+    scanned: usize,
+}
+
+impl TimerDriver {
+    /// Driver firing under `policy`, a `PolicyContext` event, with
+    /// [`FireSemantics::Definitely`]
+    ///
+    /// The policy is recorded into the worldline on the first
+    /// [`Self::drive`].
+    pub fn new(policy: EventEnvelope) -> Self {
+        Self {
+            policy,
+            semantics: FireSemantics::Definitely,
+            open: Vec::new(),
+            scanned: 0,
+        }
+    }
+
+    /// Compare fire times against the clock with `semantics` instead
+    pub fn with_semantics(mut self, semantics: FireSemantics) -> Self {
+        self.semantics = semantics;
+        self
+    }
+
+    pub fn policy(&self) -> &EventEnvelope {
+        &self.policy
+    }
+
+    /// Fire every due timer of `runtime`
+    ///
+    /// First completes fires left without a Commit (in worldline order),
+    /// then fires newly due timers in [`jitos_views::TimerView::due_timers`]
+    /// order. Commits are signed with [`Host::sign`] and attributed to
+    /// [`Host::agent`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TimerDriverError`] if an event cannot be built or is
+    /// rejected by the runtime. Fires already completed stay recorded; an
+    /// effect performed whose Commit failed is retried by the next call.
+    pub fn drive(
+        &mut self,
+        runtime: &mut Runtime,
+        host: &mut impl Host,
+        effector: &mut impl TimerEffector,
+    ) -> Result<Vec<FiredTimer>, TimerDriverError> {
+        runtime.record(self.policy.clone())?;
+        self.catch_up(runtime);
+
+        let mut fired = Vec::new();
+        for decision in self.open.clone() {
+            fired.push(self.complete(runtime, host, effector, decision, true)?);
+        }
+
+        let views = runtime.views();
+        let decisions =
+            views
+                .timers()
+                .propose_fires(views.clock().now(), self.semantics, &self.policy)?;
+        for decision in decisions {
+            runtime.record(decision.clone())?;
+            self.open.push(decision.clone());
+            fired.push(self.complete(runtime, host, effector, decision, false)?);
+        }
+        self.scanned = runtime.store().len();
+        Ok(fired)
+    }
+
+    /// Perform and commit the fire `decision` records
+    ///
+    /// `decision` is open until its Commit is recorded, so a failure leaves
+    /// it for the next [`Self::drive`] to retry.
+    fn complete(
+        &mut self,
+        runtime: &mut Runtime,
+        host: &mut impl Host,
+        effector: &mut impl TimerEffector,
+        decision: EventEnvelope,
+        recovered: bool,
+    ) -> Result<FiredTimer, TimerDriverError> {
+        let fire: TimerFire = decision
+            .payload()
+            .to_value()
+            .map_err(|e| TimerDriverError::Event(e.into()))?;
+        let key = decision.event_id();
+        let outcome = match effector.fire(&key, &fire) {
+            Ok(()) => TimerOutcome::Done,
+            Err(reason) => TimerOutcome::Failed(reason),
+        };
+        let payload = CanonicalBytes::from_value(&TimerCommit {
+            request_id: fire.request_id,
+            fired_at_ns: fire.fired_at_ns,
+            outcome: outcome.clone(),
+        })
+        .map_err(|e| TimerDriverError::Event(e.into()))?;
+        let signature = host.sign(&payload);
+        let commit = EventEnvelope::new_commit(payload, key, vec![], host.agent(), signature)?;
+        let commit_id = commit.event_id();
+        runtime.record(commit)?;
+        self.open.retain(|d| d.event_id() != key);
+        Ok(FiredTimer {
+            decision: key,
+            commit: commit_id,
+            fire,
+            outcome,
+            recovered,
+        })
+    }
+
+    /// Bring `open` up to date with the worldline
+    fn catch_up(&mut self, runtime: &Runtime) {
+        let policy = self.policy.event_id();
+        let events = runtime.store().events();
+        for event in events.get(self.scanned..).unwrap_or_default() {
+            match event.kind() {
+                EventKind::Decision
+                    if event.parents().contains(&policy)
+                        && event.payload().to_value::<TimerFire>().is_ok()
+                        && !self.open.iter().any(|d| d.event_id() == event.event_id()) =>
+                {
+                    self.open.push(event.clone());
+                }
+                EventKind::Commit => {
+                    self.open
+                        .retain(|d| !event.parents().contains(&d.event_id()));
+                }
+                _ => {}
+            }
+        }
+        self.scanned = events.len();
+    }
+}
+
+/// Timer driver errors
+#[derive(Debug, Error)]
+pub enum TimerDriverError {
+    #[error("cannot derive timer fire: {0}")]
+    Derive(#[from] DeriveError),
+    #[error("cannot build timer event: {0}")]
+    Event(#[from] EventError),
+    #[error("runtime rejected timer event: {0}")]
+    Runtime(#[from] RuntimeError),
+}
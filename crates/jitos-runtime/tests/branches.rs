@@ -0,0 +1,241 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Branch Manager Tests
+//!
+//! Forks share history up to the fork point, then tick, snapshot and
+//! receipt independently of their parent.
+
+use jitos_core::delta::DeltaSpec;
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventStore, Signature};
+use jitos_core::{Proposal, Slap};
+use jitos_graph::snapshot::SnapshotStore;
+use jitos_runtime::{
+    BranchError, BranchManager, Host, Runtime, Views, FORK_POLICY_V0, MAIN_BRANCH,
+};
+use jitos_scheduler::{EchoScheduler, SchedulerPolicy};
+use jitos_views::{ClockPolicyId, ClockSample, ClockSource, OBS_CLOCK_SAMPLE_V0};
+
+fn sample(source: ClockSource, value_ns: u64) -> EventEnvelope {
+    let sample = ClockSample {
+        source,
+        value_ns,
+        uncertainty_ns: 0,
+    };
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&sample).unwrap(),
+        vec![],
+        Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+/// Monotonic and NTP disagree; one node is created per tick
+struct TwoClocks;
+
+impl Host for TwoClocks {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        vec![
+            sample(ClockSource::Monotonic, 1_000 * (tick + 1)),
+            sample(ClockSource::Ntp, 5_000_000 + tick),
+        ]
+    }
+
+    fn proposals(&mut self, tick: u64, _views: &Views) -> Vec<Proposal> {
+        vec![Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: tick.to_le_bytes().to_vec(),
+        }
+        .into()]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![8]).unwrap()
+    }
+}
+
+/// [`TwoClocks`], proposing two nodes per tick
+struct Twice;
+
+impl Host for Twice {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        TwoClocks.observations(tick)
+    }
+
+    fn proposals(&mut self, tick: u64, views: &Views) -> Vec<Proposal> {
+        let mut proposals = TwoClocks.proposals(tick, views);
+        proposals.extend(TwoClocks.proposals(tick + 100, views));
+        proposals
+    }
+
+    fn sign(&mut self, payload: &CanonicalBytes) -> Signature {
+        TwoClocks.sign(payload)
+    }
+}
+
+fn manager() -> BranchManager {
+    BranchManager::new(Runtime::new(
+        EchoScheduler::new(),
+        ClockPolicyId::TrustMonotonicLatest,
+    ))
+}
+
+fn prefer_ntp() -> DeltaSpec {
+    DeltaSpec::new_clock_policy(
+        ClockPolicyId::TrustNtpLatest.policy_hash(),
+        "prefer ntp".to_string(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn t1_fork_shares_history_then_runs_under_its_delta() {
+    let mut branches = manager();
+    branches.tick(&mut TwoClocks).unwrap();
+    branches.tick(&mut TwoClocks).unwrap();
+    let main_head = branches.active().head().unwrap();
+
+    let delta = prefer_ntp();
+    let fork = branches.fork("ntp", &delta, EchoScheduler::new()).unwrap();
+    let origin = fork.origin().unwrap().clone();
+    assert_eq!(origin.parent, MAIN_BRANCH);
+    assert_eq!(origin.delta, delta.hash());
+    assert_eq!(origin.tick, 2);
+    assert_eq!(fork.runtime().views().clock().now().ns(), 5_000_001);
+    let recorded = fork.runtime().store().get(&origin.event).unwrap();
+    assert_eq!(recorded.parents(), &[main_head]);
+    assert_eq!(
+        recorded
+            .payload()
+            .to_value::<(String, DeltaSpec)>()
+            .unwrap(),
+        (FORK_POLICY_V0.to_string(), delta)
+    );
+    assert_eq!(
+        branches.active_name(),
+        MAIN_BRANCH,
+        "forking does not switch"
+    );
+
+    branches.tick(&mut TwoClocks).unwrap();
+    branches.switch("ntp").unwrap();
+    branches.tick(&mut TwoClocks).unwrap();
+    let main = branches.branch(MAIN_BRANCH).unwrap().runtime();
+    let ntp = branches.active().runtime();
+    assert_eq!(main.views().clock().now().ns(), 3_000);
+    assert_eq!(ntp.views().clock().now().ns(), 5_000_002);
+    assert!(main.store().get(&origin.event).is_none());
+    let shared = main.store().position(&main_head).unwrap() + 1;
+    assert_eq!(
+        &ntp.store().events()[..shared],
+        &main.store().events()[..shared]
+    );
+    assert_eq!(main.graph().compute_hash(), ntp.graph().compute_hash());
+    assert_eq!(
+        branches.names().collect::<Vec<_>>(),
+        vec![MAIN_BRANCH, "ntp"]
+    );
+}
+
+#[test]
+fn t2_forks_need_a_matching_scheduler_and_a_settled_parent() {
+    let mut branches = manager();
+    branches.tick(&mut TwoClocks).unwrap();
+
+    let priority = SchedulerPolicy::priority_deadline();
+    let delta =
+        DeltaSpec::new_scheduler_policy(priority.policy_hash(), "by priority".to_string()).unwrap();
+    assert!(matches!(
+        branches.fork("prio", &delta, EchoScheduler::new()),
+        Err(BranchError::SchedulerMismatch { expected, .. }) if expected == priority.policy_hash()
+    ));
+    assert!(matches!(
+        branches.fork(
+            "ntp",
+            &prefer_ntp(),
+            EchoScheduler::with_policy(priority.clone())
+        ),
+        Err(BranchError::SchedulerMismatch { .. })
+    ));
+    let fork = branches
+        .fork("prio", &delta, EchoScheduler::with_policy(priority.clone()))
+        .unwrap();
+    assert_eq!(fork.runtime().scheduler().policy(), &priority);
+    assert_eq!(
+        fork.runtime().store().events().last().unwrap().event_id(),
+        fork.runtime().scheduler().policy_context().event_id(),
+        "decisions on the fork cite its own policy"
+    );
+
+    assert!(matches!(
+        branches.fork("prio", &delta, EchoScheduler::with_policy(priority)),
+        Err(BranchError::Exists(name)) if name == "prio"
+    ));
+    let trust = DeltaSpec::new_trust_policy(
+        vec![AgentId::new("alice").unwrap()],
+        "alice only".to_string(),
+    )
+    .unwrap();
+    assert!(matches!(
+        branches.fork("trust", &trust, EchoScheduler::new()),
+        Err(BranchError::UnsupportedDelta("TrustPolicy"))
+    ));
+    assert!(matches!(
+        branches.switch("nope"),
+        Err(BranchError::Unknown(_))
+    ));
+
+    // A parent with SLAPs deferred has no settled state to fork from.
+    let budget = SchedulerPolicy {
+        tick_budget: Some(5),
+        ..SchedulerPolicy::default()
+    };
+    let mut tight = BranchManager::new(Runtime::new(
+        EchoScheduler::with_policy(budget.clone()),
+        ClockPolicyId::TrustMonotonicLatest,
+    ));
+    tight.tick(&mut Twice).unwrap();
+    assert!(matches!(
+        tight.fork("ntp", &prefer_ntp(), EchoScheduler::with_policy(budget)),
+        Err(BranchError::Unsettled { deferred: 1, .. })
+    ));
+}
+
+#[test]
+fn t3_each_branch_keeps_its_own_snapshots_and_receipts() {
+    let mut branches = manager();
+    branches.tick(&mut TwoClocks).unwrap();
+    let shared = branches.active_mut().checkpoint().unwrap();
+    branches
+        .fork("ntp", &prefer_ntp(), EchoScheduler::new())
+        .unwrap();
+
+    branches.tick(&mut TwoClocks).unwrap();
+    let main_only = branches.active_mut().checkpoint().unwrap();
+    branches.switch("ntp").unwrap();
+    for _ in 0..2 {
+        branches.tick(&mut TwoClocks).unwrap();
+    }
+    let ntp_only = branches.active_mut().checkpoint().unwrap();
+
+    let main = branches.branch(MAIN_BRANCH).unwrap();
+    let ntp = branches.branch("ntp").unwrap();
+    assert!(main.snapshots().contains(&shared));
+    assert!(main.snapshots().contains(&main_only));
+    assert!(!main.snapshots().contains(&ntp_only));
+    assert!(
+        !ntp.snapshots().contains(&shared),
+        "snapshots are not inherited"
+    );
+    assert_eq!(ntp.snapshots().load(&ntp_only).unwrap().nodes.len(), 3);
+
+    assert_eq!(main.receipts().len(), 2);
+    assert_eq!(ntp.receipts().len(), 3);
+    assert_eq!(
+        main.receipts()[0].digest().unwrap(),
+        ntp.receipts()[0].digest().unwrap()
+    );
+    assert_ne!(main.head(), ntp.head());
+}
@@ -0,0 +1,140 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checkpointed Replay Tests
+//!
+//! Starting from a verified snapshot re-executes only the ticks after it and
+//! reaches the same receipts as a replay from genesis.
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::{Hash, Proposal, Receipt, Slap};
+use jitos_graph::snapshot::{MemorySnapshotStore, SnapshotError, SnapshotStore};
+use jitos_graph::WarpGraph;
+use jitos_runtime::replay::{self, ReplayError};
+use jitos_runtime::{Host, Runtime, Views};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::ClockPolicyId;
+
+mod common;
+use common::sample;
+
+struct NodePerTick;
+
+impl Host for NodePerTick {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        vec![sample(1_000 * (tick + 1))]
+    }
+
+    fn proposals(&mut self, tick: u64, _views: &Views) -> Vec<Proposal> {
+        vec![Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: tick.to_le_bytes().to_vec(),
+        }
+        .into()]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![3]).unwrap()
+    }
+}
+
+fn runtime() -> Runtime {
+    Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest)
+}
+
+fn digests(receipts: &[Receipt]) -> Vec<Hash> {
+    receipts.iter().map(|r| r.digest().unwrap()).collect()
+}
+
+/// Eight ticks, snapshotting the graph after each tick in `snapshot_ticks`.
+fn recorded(snapshot_ticks: &[u64]) -> (Runtime, MemorySnapshotStore) {
+    let mut rt = runtime();
+    let mut snapshots = MemorySnapshotStore::new();
+    for tick in 0..8 {
+        rt.tick(&mut NodePerTick).unwrap();
+        if snapshot_ticks.contains(&tick) {
+            snapshots.save(rt.graph()).unwrap();
+        }
+    }
+    (rt, snapshots)
+}
+
+#[test]
+fn t1_replay_resumes_after_latest_checkpoint() {
+    let (rt, snapshots) = recorded(&[1, 4]);
+    let replayed =
+        replay::run_from_checkpoint(rt.store(), rt.store().len(), &snapshots, runtime()).unwrap();
+    assert_eq!(replayed.checkpoint, Some(4));
+    assert_eq!(replayed.ticks, 3);
+    assert_eq!(replayed.matched, 3);
+    assert_eq!(
+        digests(replayed.runtime.receipts()),
+        digests(&rt.receipts()[4..])
+    );
+    assert_eq!(replayed.runtime.store().events(), rt.store().events());
+    assert_eq!(
+        replayed.runtime.graph().compute_hash(),
+        rt.graph().compute_hash()
+    );
+}
+
+#[test]
+fn t2_checkpoint_respects_the_cut() {
+    let (rt, snapshots) = recorded(&[1, 4]);
+    let ticks = replay::recorded_ticks(rt.store().events());
+    let cut = rt.store().position(&ticks[3].commit.event_id()).unwrap() + 1;
+    let replayed = replay::run_from_checkpoint(rt.store(), cut, &snapshots, runtime()).unwrap();
+    assert_eq!(replayed.checkpoint, Some(1));
+    assert_eq!(replayed.ticks, 2);
+    assert_eq!(
+        replayed
+            .runtime
+            .receipts()
+            .last()
+            .unwrap()
+            .digest()
+            .unwrap(),
+        rt.receipts()[3].digest().unwrap()
+    );
+}
+
+#[test]
+fn t3_without_checkpoint_replays_from_genesis() {
+    let (rt, snapshots) = recorded(&[]);
+    let replayed =
+        replay::run_from_checkpoint(rt.store(), rt.store().len(), &snapshots, runtime()).unwrap();
+    assert_eq!(replayed.checkpoint, None);
+    assert_eq!(replayed.ticks, 8);
+}
+
+/// Claims to hold every snapshot but serves a different graph.
+struct CorruptStore;
+
+impl SnapshotStore for CorruptStore {
+    fn save(&mut self, graph: &WarpGraph) -> Result<Hash, SnapshotError> {
+        Ok(graph.compute_hash())
+    }
+
+    fn load(&self, hash: &Hash) -> Result<WarpGraph, SnapshotError> {
+        Err(SnapshotError::DigestMismatch {
+            expected: *hash,
+            found: WarpGraph::new().compute_hash(),
+        })
+    }
+
+    fn contains(&self, _hash: &Hash) -> bool {
+        true
+    }
+}
+
+#[test]
+fn t4_corrupt_checkpoint_is_rejected() {
+    let (rt, _) = recorded(&[]);
+    match replay::run_from_checkpoint(rt.store(), rt.store().len(), &CorruptStore, runtime()) {
+        Err(ReplayError::Checkpoint { tick, source }) => {
+            assert_eq!(tick, 7);
+            assert!(matches!(source, SnapshotError::DigestMismatch { .. }));
+        }
+        other => panic!("expected checkpoint error, got {:?}", other.err()),
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Clock Sampler Tests
+//!
+//! Physical clocks are sampled at their cadence and enter the runtime as
+//! `OBS_CLOCK_SAMPLE_V0` observations.
+
+use std::time::{Duration, Instant};
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::Proposal;
+use jitos_runtime::{ClockSampler, ExternalClock, Host, MonotonicClock, Runtime, Views, WithClock};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::{ClockPolicyId, ClockSample, ClockSource, OBS_CLOCK_SAMPLE_V0};
+
+fn decode(event: &EventEnvelope) -> ClockSample {
+    assert_eq!(event.observation_type(), Some(OBS_CLOCK_SAMPLE_V0));
+    event.payload().to_value().unwrap()
+}
+
+struct Idle;
+
+impl Host for Idle {
+    fn observations(&mut self, _tick: u64) -> Vec<EventEnvelope> {
+        vec![]
+    }
+
+    fn proposals(&mut self, _tick: u64, _views: &Views) -> Vec<Proposal> {
+        vec![]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![1]).unwrap()
+    }
+}
+
+#[test]
+fn t1_sources_are_sampled_at_their_cadence() {
+    let start = Instant::now();
+    let mut sampler = ClockSampler::new()
+        .with_source(
+            MonotonicClock::starting_at(start),
+            Duration::from_millis(10),
+        )
+        .with_source(
+            ExternalClock::ntp(|| Some((42, 7))),
+            Duration::from_millis(25),
+        );
+
+    let first = sampler.poll_at(start);
+    let sources: Vec<_> = first.iter().map(|e| decode(e).source).collect();
+    assert_eq!(sources, [ClockSource::Monotonic, ClockSource::Ntp]);
+
+    assert!(sampler.poll_at(start + Duration::from_millis(5)).is_empty());
+    let second = sampler.poll_at(start + Duration::from_millis(10));
+    assert_eq!(second.len(), 1);
+    assert_eq!(decode(&second[0]).source, ClockSource::Monotonic);
+
+    let third = sampler.poll_at(start + Duration::from_millis(25));
+    let ntp = decode(&third[1]);
+    assert_eq!(
+        (ntp.source, ntp.value_ns, ntp.uncertainty_ns),
+        (ClockSource::Ntp, 42, 7)
+    );
+}
+
+#[test]
+fn t2_a_source_without_a_reading_stays_due() {
+    let start = Instant::now();
+    let mut synced = false;
+    let mut sampler = ClockSampler::new().with_source(
+        ExternalClock::ntp(move || {
+            let reading = synced.then_some((1_000, 50));
+            synced = true;
+            reading
+        }),
+        Duration::from_secs(60),
+    );
+
+    assert!(sampler.poll_at(start).is_empty(), "not synced yet");
+    let events = sampler.poll_at(start + Duration::from_millis(1));
+    assert_eq!(events.len(), 1, "retried on the next poll");
+    assert_eq!(decode(&events[0]).value_ns, 1_000);
+}
+
+#[test]
+fn t3_monotonic_samples_never_go_backwards() {
+    let mut sampler = ClockSampler::new().with_source(MonotonicClock::new(), Duration::ZERO);
+    let values: Vec<u64> = (0..5)
+        .flat_map(|_| sampler.poll())
+        .map(|e| decode(&e).value_ns)
+        .collect();
+    assert_eq!(values.len(), 5);
+    assert!(values.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn t4_wrapped_host_feeds_the_clock_view() {
+    let sampler = ClockSampler::new().with_source(
+        ExternalClock::new(ClockSource::Monotonic, || Some((5_000, 0))),
+        Duration::ZERO,
+    );
+    let mut host = WithClock::new(Idle, sampler);
+    let mut rt = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+
+    rt.tick(&mut host).unwrap();
+    assert_eq!(rt.views().clock().now().ns(), 5_000);
+    let samples = rt
+        .store()
+        .events()
+        .iter()
+        .filter(|e| e.observation_type() == Some(OBS_CLOCK_SAMPLE_V0))
+        .count();
+    assert_eq!(samples, 1, "the sample is recorded for replay");
+}
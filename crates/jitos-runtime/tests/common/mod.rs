@@ -0,0 +1,48 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Common test utilities for jitos-runtime tests
+
+#![allow(dead_code)]
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::Proposal;
+use jitos_runtime::{Host, Views};
+use jitos_views::{ClockSample, ClockSource, OBS_CLOCK_SAMPLE_V0};
+
+/// Helper: A monotonic clock sample reading `value_ns`
+pub fn sample(value_ns: u64) -> EventEnvelope {
+    let sample = ClockSample {
+        source: ClockSource::Monotonic,
+        value_ns,
+        uncertainty_ns: 0,
+    };
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&sample).unwrap(),
+        vec![],
+        Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+/// Samples the clock every tick and proposes whatever the test queued
+#[derive(Default)]
+pub struct Queued {
+    pub proposals: Vec<Proposal>,
+}
+
+impl Host for Queued {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        vec![sample(1_000 * (tick + 1))]
+    }
+
+    fn proposals(&mut self, _tick: u64, _views: &Views) -> Vec<Proposal> {
+        std::mem::take(&mut self.proposals)
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![6]).unwrap()
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Crash Recovery Tests
+//!
+//! A journaled tick left pending by a crash is resolved on restart, before
+//! the runtime ticks again.
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::{Proposal, Slap};
+use jitos_runtime::replay;
+use jitos_runtime::{
+    FileTickJournal, Host, MemoryTickJournal, Recovery, Runtime, RuntimeError, TickIntent,
+    TickJournal, Views,
+};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::ClockPolicyId;
+
+mod common;
+use common::sample;
+
+/// Samples the clock and creates one node every tick
+struct Builder;
+
+impl Host for Builder {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        vec![sample(1_000 * (tick + 1))]
+    }
+
+    fn proposals(&mut self, tick: u64, _views: &Views) -> Vec<Proposal> {
+        vec![create(tick)]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![7]).unwrap()
+    }
+}
+
+fn create(tick: u64) -> Proposal {
+    Slap::CreateNode {
+        node_type: "task".to_string(),
+        payload_bytes: tick.to_le_bytes().to_vec(),
+    }
+    .into()
+}
+
+fn runtime() -> Runtime {
+    Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest)
+}
+
+fn journal_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "jitos-crash-recovery-{name}-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+/// The intent `rt` would journal for its next tick under [`Builder`]
+fn next_intent(rt: &Runtime) -> TickIntent {
+    TickIntent {
+        tick: rt.next_tick(),
+        batches: vec![vec![create(rt.next_tick()).slap]],
+        pre_state: rt.graph().compute_hash(),
+    }
+}
+
+#[test]
+fn t1_completed_ticks_leave_nothing_pending() {
+    let dir = journal_dir("clean");
+    let journal = FileTickJournal::open(&dir).unwrap();
+    let mut rt = runtime().with_journal(journal.clone());
+    for _ in 0..3 {
+        rt.tick(&mut Builder).unwrap();
+    }
+    assert!(journal.pending().unwrap().is_none());
+    assert!(!dir.join("tick.cbor").exists());
+    assert_eq!(rt.recover().unwrap(), Recovery::Clean);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn t2_tick_cut_short_before_applying_is_rolled_back_and_rerun() {
+    let dir = journal_dir("rollback");
+    let mut rt = runtime().with_journal(FileTickJournal::open(&dir).unwrap());
+    for _ in 0..2 {
+        rt.tick(&mut Builder).unwrap();
+    }
+    // Crash right after journaling tick 2.
+    let mut journal = FileTickJournal::open(&dir).unwrap();
+    journal.begin(&next_intent(&rt)).unwrap();
+
+    let replayed = replay::run(rt.store(), rt.store().len(), runtime()).unwrap();
+    let mut restarted = replayed
+        .runtime
+        .with_journal(FileTickJournal::open(&dir).unwrap());
+    assert!(matches!(
+        restarted.tick(&mut Builder),
+        Err(RuntimeError::RecoveryPending { tick: 2 })
+    ));
+    assert_eq!(restarted.next_tick(), 2, "nothing ingested while pending");
+
+    assert_eq!(
+        restarted.recover().unwrap(),
+        Recovery::RolledBack { tick: 2 }
+    );
+    assert!(journal.pending().unwrap().is_none());
+    let rerun = restarted.tick(&mut Builder).unwrap();
+    rt.tick(&mut Builder).unwrap();
+    assert_eq!(
+        rerun.receipt.digest().unwrap(),
+        rt.receipts()[2].digest().unwrap()
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn t3_committed_tick_is_completed_and_foreign_state_is_refused() {
+    let mut rt = runtime();
+    rt.tick(&mut Builder).unwrap();
+    let committed = next_intent(&rt);
+    rt.tick(&mut Builder).unwrap();
+
+    // Crash after tick 1's Commit was recorded, before the journal heard.
+    let mut journal = MemoryTickJournal::new();
+    journal.begin(&committed).unwrap();
+    let mut restarted = rt.with_journal(journal);
+    assert_eq!(
+        restarted.recover().unwrap(),
+        Recovery::Completed { tick: 1 }
+    );
+    assert_eq!(restarted.recover().unwrap(), Recovery::Clean);
+    restarted.tick(&mut Builder).unwrap();
+
+    // The graph is neither before nor after the pending tick.
+    let mut journal = MemoryTickJournal::new();
+    let mut partial = next_intent(&restarted);
+    partial.pre_state = jitos_core::Hash([9; 32]);
+    journal.begin(&partial).unwrap();
+    let mut restarted = restarted.with_journal(journal);
+    assert!(matches!(
+        restarted.recover(),
+        Err(RuntimeError::PartialTick { tick: 3, .. })
+    ));
+
+    let mut ahead = next_intent(&restarted);
+    ahead.tick += 1;
+    let mut journal = MemoryTickJournal::new();
+    journal.begin(&ahead).unwrap();
+    let mut restarted = restarted.with_journal(journal);
+    assert!(matches!(
+        restarted.recover(),
+        Err(RuntimeError::StaleState {
+            journaled: 4,
+            next: 3
+        })
+    ));
+}
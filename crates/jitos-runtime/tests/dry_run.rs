@@ -0,0 +1,194 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dry-Run Submission Tests
+//!
+//! `submit_dry` previews placement, conflicts, the graph patch and digest
+//! and broken invariants of the next tick without changing anything, and
+//! the next tick given the same proposals does exactly what it previewed.
+
+use jitos_core::{canonical, Hash, JitosError, Proposal, Slap};
+use jitos_graph::rewrite::GraphChange;
+use jitos_runtime::{Breach, InvariantRegistry, InvariantSpec, Runtime, RuntimeError, Views};
+use jitos_scheduler::{ConflictReason, EchoScheduler, Placement};
+use jitos_views::ClockPolicyId;
+
+mod common;
+use common::Queued;
+
+fn create(payload: u8) -> Proposal {
+    Slap::CreateNode {
+        node_type: "task".to_string(),
+        payload_bytes: vec![payload],
+    }
+    .into()
+}
+
+fn set_time(dt: f64) -> Proposal {
+    Slap::SetTime { tick: 1, dt }.into()
+}
+
+fn hash(proposal: &Proposal) -> Hash {
+    canonical::hash_canonical(&proposal.slap).unwrap()
+}
+
+fn runtime(scheduler: EchoScheduler) -> Runtime {
+    Runtime::new(scheduler, ClockPolicyId::TrustMonotonicLatest)
+}
+
+/// Everything a dry run must leave alone
+#[derive(Debug, PartialEq)]
+struct State {
+    events: usize,
+    next_tick: u64,
+    receipts: usize,
+    graph: Hash,
+    deferred: Vec<(Hash, u32)>,
+    footprints: usize,
+    clock: Hash,
+}
+
+fn state(rt: &Runtime) -> State {
+    State {
+        events: rt.store().len(),
+        next_tick: rt.next_tick(),
+        receipts: rt.receipts().len(),
+        graph: rt.graph().compute_hash(),
+        deferred: rt.scheduler().deferred().state(),
+        footprints: rt.scheduler().footprint_cache().len(),
+        clock: rt.views().clock().state_hash(),
+    }
+}
+
+#[test]
+fn t1_preview_records_nothing_and_the_tick_matches_it() {
+    let mut rt = runtime(EchoScheduler::new());
+    let mut host = Queued {
+        proposals: vec![create(1)],
+    };
+    rt.tick(&mut host).unwrap();
+
+    let proposals = vec![create(2), create(3)];
+    let before = state(&rt);
+    let dry = rt.submit_dry(proposals.clone()).unwrap();
+    assert_eq!(state(&rt), before, "a dry run changes nothing");
+    assert_eq!(dry.tick, 1);
+    assert_eq!(dry.report.proposals.len(), 2);
+    assert!(dry.violations.is_empty());
+    assert_ne!(dry.state_hash, rt.graph().compute_hash());
+    assert_eq!(dry.patch.len(), 2);
+
+    host.proposals = proposals;
+    let outcome = rt.tick(&mut host).unwrap();
+    assert_eq!(outcome.receipt.state_hash, dry.state_hash);
+    assert_eq!(outcome.receipt.applied_slaps, dry.report.batches().concat());
+    for change in &dry.patch {
+        let GraphChange::NodeAdded { id, node_type } = change else {
+            panic!("expected node creations, got {change:?}")
+        };
+        let key = rt.graph().node_key(id).expect("the tick created it");
+        assert_eq!(&rt.graph().nodes[key].node_type, node_type);
+    }
+}
+
+#[test]
+fn t2_conflicts_and_waiting_slaps_are_previewed_at_their_age() {
+    let mut rt = runtime(EchoScheduler::with_max_batches(1));
+    let (first, second) = (set_time(1.0), set_time(2.0));
+    let dry = rt.submit_dry(vec![first.clone(), second.clone()]).unwrap();
+    let deferred = dry.report.deferred();
+    assert_eq!(deferred.len(), 1);
+    let loser = dry.report.get(&deferred[0]).unwrap();
+    let winner = if deferred[0] == hash(&first) {
+        &second
+    } else {
+        &first
+    };
+    assert!(matches!(
+        &loser.placement,
+        Placement::Deferred(ConflictReason::Conflict { blocked_by, .. }) if *blocked_by == hash(winner)
+    ));
+    assert_eq!(loser.conflicts.len(), 1, "the write/write on system time");
+    assert!(rt.scheduler().deferred().is_empty());
+
+    let mut host = Queued {
+        proposals: vec![first, second],
+    };
+    rt.tick(&mut host).unwrap();
+    assert_eq!(rt.scheduler().deferred().len(), 1);
+
+    // Next tick the waiting SLAP is scheduled, one tick old, ahead of a
+    // newcomer it conflicts with.
+    let newcomer = set_time(3.0);
+    let dry = rt.submit_dry(vec![newcomer.clone()]).unwrap();
+    let waiting = dry.report.get(&deferred[0]).unwrap();
+    assert_eq!(waiting.key.age, 1);
+    assert_eq!(waiting.placement, Placement::Batch(0));
+    assert_eq!(dry.report.deferred(), vec![hash(&newcomer)]);
+    assert_eq!(rt.scheduler().deferred().age(&deferred[0]), 1);
+
+    host.proposals = vec![newcomer];
+    let outcome = rt.tick(&mut host).unwrap();
+    assert_eq!(outcome.receipt.state_hash, dry.state_hash);
+    assert_eq!(outcome.receipt.applied_slaps, vec![deferred[0]]);
+}
+
+#[test]
+fn t3_broken_invariants_are_previewed_without_being_raised() {
+    let spec = InvariantSpec::MaxNodes(1);
+    let rt =
+        runtime(EchoScheduler::new()).with_invariants(InvariantRegistry::new().with(spec.clone()));
+
+    let dry = rt.submit_dry(vec![create(1), create(2)]).unwrap();
+    let [violation] = dry.violations.as_slice() else {
+        panic!("expected one violation, got {:?}", dry.violations)
+    };
+    assert_eq!(violation.invariant, spec.id());
+    assert_eq!(violation.observed, 2);
+    assert_eq!(violation.slaps, dry.report.batches().concat());
+    assert!(!rt.invariants().is_broken(&spec.id()));
+    assert!(rt
+        .submit_dry(vec![create(1)])
+        .unwrap()
+        .violations
+        .is_empty());
+
+    assert!(matches!(
+        rt.submit_dry(vec![set_time(f64::NAN)]),
+        Err(RuntimeError::InvalidTime { tick: 0, .. })
+    ));
+    assert_eq!(rt.store().len(), 1, "only the policy context");
+}
+
+#[test]
+fn t4_set_time_reaches_the_views_invariants_are_previewed_against() {
+    let late = |_: &jitos_graph::WarpGraph, views: &Views| {
+        let now = views.clock().now().ns();
+        (now >= 1_000_000_000).then_some(Breach {
+            observed: now,
+            witnesses: vec![],
+        })
+    };
+    let mut rt = runtime(EchoScheduler::new())
+        .with_invariants(InvariantRegistry::new().with_predicate("before-1s", late));
+    let mut host = Queued::default();
+    rt.tick(&mut host).unwrap();
+
+    let before = state(&rt);
+    let dry = rt.submit_dry(vec![set_time(1.0)]).unwrap();
+    assert_eq!(state(&rt), before);
+    assert!(dry.patch.is_empty(), "SetTime does not touch the graph");
+    let [previewed] = dry.violations.as_slice() else {
+        panic!("expected one violation, got {:?}", dry.violations)
+    };
+    assert_eq!(previewed.observed, 1_000_000_000);
+
+    host.proposals = vec![set_time(1.0)];
+    match rt.tick(&mut host) {
+        Err(RuntimeError::Invariant {
+            error: JitosError::InvariantViolation(raised),
+            ..
+        }) => assert_eq!(&raised, previewed),
+        other => panic!("expected the previewed violation, got {other:?}"),
+    }
+}
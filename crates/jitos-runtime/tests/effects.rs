@@ -0,0 +1,160 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Effect Executor Tests
+//!
+//! Commits are dispatched by type, performed at most once (even across a
+//! restart or a crash mid-effect) and their outcomes observed back into the
+//! worldline.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventKind, Signature};
+use jitos_core::Proposal;
+use jitos_runtime::{
+    EffectCommit, EffectExecutor, EffectJournal, EffectOutcome, EffectStatus, FileJournal, Host,
+    MemoryJournal, Runtime, Views, OBS_EFFECT_OUTCOME_V0,
+};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::{derive_decision, ClockPolicyId};
+
+/// Delivers `inbox` on the next tick
+#[derive(Default)]
+struct Inbox {
+    inbox: Vec<EventEnvelope>,
+}
+
+impl Host for Inbox {
+    fn observations(&mut self, _tick: u64) -> Vec<EventEnvelope> {
+        std::mem::take(&mut self.inbox)
+    }
+
+    fn proposals(&mut self, _tick: u64, _views: &Views) -> Vec<Proposal> {
+        vec![]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![9]).unwrap()
+    }
+}
+
+/// Runtime holding one observed request and a Commit per effect
+fn runtime_with_commits(effects: &[EffectCommit]) -> (Runtime, Vec<EventEnvelope>) {
+    let mut rt = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+    let request = EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&"send the report").unwrap(),
+        vec![],
+        Some("OBS_USER_REQUEST_V0".to_string()),
+        None,
+        None,
+    )
+    .unwrap();
+    let mut host = Inbox {
+        inbox: vec![request.clone()],
+    };
+    rt.tick(&mut host).unwrap();
+
+    let policy = EventEnvelope::new_policy_context(
+        CanonicalBytes::from_value(&"effects-v0").unwrap(),
+        vec![],
+        None,
+        None,
+    )
+    .unwrap();
+    rt.record(policy.clone()).unwrap();
+    let mut commits = Vec::new();
+    for effect in effects {
+        let decision = derive_decision(effect, [request.event_id()], &policy).unwrap();
+        let commit = EventEnvelope::new_commit(
+            CanonicalBytes::from_value(effect).unwrap(),
+            decision.event_id(),
+            vec![],
+            None,
+            Signature::new(vec![1]).unwrap(),
+        )
+        .unwrap();
+        rt.record(decision).unwrap();
+        rt.record(commit.clone()).unwrap();
+        commits.push(commit);
+    }
+    (rt, commits)
+}
+
+fn outcome(event: &EventEnvelope) -> EffectOutcome {
+    assert_eq!(event.kind(), &EventKind::Observation);
+    assert_eq!(event.observation_type(), Some(OBS_EFFECT_OUTCOME_V0));
+    event.payload().to_value().unwrap()
+}
+
+/// Effector counting its calls and answering with the body's length
+fn counting(
+    calls: &Rc<RefCell<u32>>,
+) -> impl FnMut(&EventEnvelope, &CanonicalBytes) -> Result<CanonicalBytes, String> {
+    let calls = Rc::clone(calls);
+    move |_, body| {
+        *calls.borrow_mut() += 1;
+        Ok(CanonicalBytes::from_value(&body.as_bytes().len()).unwrap())
+    }
+}
+
+#[test]
+fn t1_commits_dispatch_by_type_once_and_outcomes_are_observed() {
+    let (mut rt, commits) = runtime_with_commits(&[
+        EffectCommit::new("email", &"ops@example.com").unwrap(),
+        EffectCommit::new("pager", &3u8).unwrap(),
+    ]);
+    let emails = Rc::new(RefCell::new(0));
+    let mut executor =
+        EffectExecutor::new(MemoryJournal::new()).with_effector("email", counting(&emails));
+
+    let outcomes = executor.execute(&rt).unwrap();
+    assert_eq!(*emails.borrow(), 1);
+    assert_eq!(outcomes.len(), 1, "no effector for pager commits");
+    assert_eq!(outcomes[0].parents(), &[commits[0].event_id()]);
+    let recorded = outcome(&outcomes[0]);
+    assert_eq!(recorded.commit_type, "email");
+    assert!(matches!(recorded.status, EffectStatus::Done(_)));
+
+    let mut host = Inbox { inbox: outcomes };
+    rt.tick(&mut host).unwrap();
+    assert!(executor.execute(&rt).unwrap().is_empty());
+    assert_eq!(*emails.borrow(), 1);
+}
+
+#[test]
+fn t2_restarted_executor_reemits_outcomes_without_performing_again() {
+    let dir = std::env::temp_dir().join(format!("jitos-effects-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let (rt, _) = runtime_with_commits(&[EffectCommit::new("email", &"a@example.com").unwrap()]);
+    let calls = Rc::new(RefCell::new(0));
+
+    let mut first = EffectExecutor::new(FileJournal::open(&dir).unwrap())
+        .with_effector("email", counting(&calls));
+    let lost = first.execute(&rt).unwrap();
+    drop(first);
+
+    // The outcome was never ingested; a restarted executor emits it again.
+    let mut restarted = EffectExecutor::new(FileJournal::open(&dir).unwrap())
+        .with_effector("email", counting(&calls));
+    let again = restarted.execute(&rt).unwrap();
+    assert_eq!(*calls.borrow(), 1);
+    assert_eq!(again, lost);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn t3_effect_interrupted_by_a_crash_is_not_retried() {
+    let (rt, commits) =
+        runtime_with_commits(&[EffectCommit::new("email", &"b@example.com").unwrap()]);
+    let mut journal = MemoryJournal::new();
+    // Crashed after journaling the start, before the effect returned.
+    journal.begin(&commits[0].event_id()).unwrap();
+
+    let calls = Rc::new(RefCell::new(0));
+    let mut executor = EffectExecutor::new(journal).with_effector("email", counting(&calls));
+    let outcomes = executor.execute(&rt).unwrap();
+    assert_eq!(*calls.borrow(), 0);
+    assert_eq!(outcome(&outcomes[0]).status, EffectStatus::Interrupted);
+}
@@ -0,0 +1,108 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fuel Metering Tests
+//!
+//! Receipts record the fuel each tick's SLAPs used, per agent, and the
+//! scheduler policy caps what one agent can spend in a tick.
+
+use jitos_core::events::AgentId;
+use jitos_core::{Proposal, Slap, SlapMeta};
+use jitos_runtime::replay;
+use jitos_runtime::Runtime;
+use jitos_scheduler::{ConflictReason, EchoScheduler, SchedulerPolicy};
+use jitos_views::ClockPolicyId;
+
+mod common;
+use common::Queued;
+
+/// A `CreateNode` (cost 5) charged to `agent`
+fn create(agent: &str, payload: u8) -> Proposal {
+    Proposal::new(
+        Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: vec![payload],
+        },
+        SlapMeta {
+            agent: Some(AgentId::new(agent).unwrap()),
+            ..SlapMeta::default()
+        },
+    )
+}
+
+fn runtime(agent_budget: Option<u64>) -> Runtime {
+    let scheduler = EchoScheduler::with_policy(SchedulerPolicy {
+        agent_budget,
+        ..SchedulerPolicy::default()
+    });
+    Runtime::new(scheduler, ClockPolicyId::TrustMonotonicLatest)
+}
+
+#[test]
+fn t1_receipts_record_fuel_per_agent() {
+    let mut rt = runtime(None);
+    let mut host = Queued::default();
+    let idle = rt.tick(&mut host).unwrap();
+    assert_eq!(idle.receipt.fuel, None);
+
+    host.proposals = vec![
+        create("alice", 1),
+        create("alice", 2),
+        create("bob", 3),
+        Slap::SetTime { tick: 1, dt: 1.0 }.into(),
+    ];
+    let outcome = rt.tick(&mut host).unwrap();
+    let fuel = outcome.receipt.fuel.unwrap();
+    assert_eq!(fuel.total, 5 + 5 + 5 + 1);
+    assert_eq!(fuel.by_agent["alice"], 10);
+    assert_eq!(fuel.by_agent["bob"], 5);
+    assert_eq!(
+        fuel.by_agent.len(),
+        2,
+        "unattributed SLAPs count in total only"
+    );
+}
+
+#[test]
+fn t2_agent_budget_keeps_one_agent_from_monopolizing_a_tick() {
+    let mut rt = runtime(Some(10));
+    let mut host = Queued {
+        proposals: (1..=4)
+            .map(|i| create("alice", i))
+            .chain([create("bob", 9)])
+            .collect(),
+    };
+    let first = rt.tick(&mut host).unwrap();
+    let fuel = first.receipt.fuel.unwrap();
+    assert_eq!(fuel.by_agent["alice"], 10);
+    assert_eq!(fuel.by_agent["bob"], 5);
+    assert_eq!(first.schedule.deferred.len(), 2);
+    for (_, reason) in &first.schedule.deferred {
+        assert!(matches!(
+            reason,
+            ConflictReason::AgentOverBudget { agent, cost: 5, remaining: 0 }
+                if agent.as_str() == "alice"
+        ));
+    }
+
+    // The deferred SLAPs run next tick, still charged to alice.
+    let second = rt.tick(&mut host).unwrap();
+    assert_eq!(second.receipt.fuel.unwrap().by_agent["alice"], 10);
+    assert_eq!(rt.graph().nodes.len(), 5);
+}
+
+#[test]
+fn t3_metered_worldline_replays() {
+    let mut rt = runtime(Some(5));
+    let mut host = Queued {
+        proposals: vec![create("alice", 1), create("alice", 2), create("bob", 3)],
+    };
+    for _ in 0..3 {
+        rt.tick(&mut host).unwrap();
+    }
+    let replayed = replay::run(rt.store(), rt.store().len(), runtime(Some(5))).unwrap();
+    assert_eq!(replayed.matched, 3);
+    let fuel = |r: &Runtime| -> Vec<_> { r.receipts().iter().map(|r| r.fuel.clone()).collect() };
+    assert_eq!(fuel(&replayed.runtime), fuel(&rt));
+    assert!(fuel(&rt)[1].is_some());
+}
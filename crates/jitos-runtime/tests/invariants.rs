@@ -0,0 +1,170 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Invariant Engine Tests
+//!
+//! Invariants are checked after every tick; a newly broken one is recorded
+//! as a violation observation and raised as structured data.
+
+use jitos_core::{JitosError, Proposal, Slap, Violation};
+use jitos_runtime::{
+    Breach, InvariantRegistry, InvariantSpec, Runtime, RuntimeError, Views,
+    OBS_INVARIANT_VIOLATION_V0,
+};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::ClockPolicyId;
+
+mod common;
+use common::Queued;
+
+fn create(node_type: &str, payload: u8) -> Proposal {
+    Slap::CreateNode {
+        node_type: node_type.to_string(),
+        payload_bytes: vec![payload],
+    }
+    .into()
+}
+
+fn violation(result: Result<jitos_runtime::TickOutcome, RuntimeError>) -> Violation {
+    match result {
+        Err(RuntimeError::Invariant {
+            error: JitosError::InvariantViolation(violation),
+            outcome,
+        }) => {
+            assert_eq!(outcome.violations.first(), Some(&violation));
+            violation
+        }
+        other => panic!("expected an invariant violation, got {other:?}"),
+    }
+}
+
+fn runtime(invariants: InvariantRegistry) -> Runtime {
+    Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest)
+        .with_invariants(invariants)
+}
+
+#[test]
+fn t1_broken_invariant_is_recorded_and_raised_once() {
+    let spec = InvariantSpec::MaxNodesOfType {
+        node_type: "task".to_string(),
+        max: 2,
+    };
+    let mut rt = runtime(InvariantRegistry::new().with(spec.clone()));
+    let mut host = Queued {
+        proposals: vec![create("task", 1), create("note", 2)],
+    };
+    rt.tick(&mut host).unwrap();
+
+    host.proposals = vec![create("task", 3), create("task", 4)];
+    let Err(RuntimeError::Invariant { outcome, .. }) = rt.tick(&mut host) else {
+        panic!("expected an invariant violation");
+    };
+    assert_eq!(rt.next_tick(), 2, "the breaking tick is still recorded");
+    assert_eq!(outcome.receipt.state_hash, rt.receipts()[1].state_hash);
+    assert_eq!(outcome.violations.len(), 1);
+    let violation = outcome.violations[0].clone();
+    assert_eq!(violation.invariant, spec.id());
+    assert_eq!(violation.tick, 1);
+    assert_eq!(violation.slaps, rt.receipts()[1].applied_slaps);
+    assert_eq!(violation.observed, 3);
+    let mut tasks: Vec<_> = rt
+        .graph()
+        .nodes_of_type("task")
+        .map(|(_, n)| n.id.hash())
+        .collect();
+    tasks.sort();
+    assert_eq!(violation.witnesses, tasks);
+
+    let recorded = rt.store().events().last().unwrap();
+    assert_eq!(
+        recorded.observation_type(),
+        Some(OBS_INVARIANT_VIOLATION_V0)
+    );
+    assert_eq!(
+        recorded.payload().to_value::<Violation>().unwrap(),
+        violation
+    );
+    let commit = &rt.store().events()[rt.store().len() - 2];
+    assert_eq!(recorded.parents(), &[commit.event_id()]);
+
+    // Still broken, but already reported.
+    rt.tick(&mut host).unwrap();
+    assert!(rt.invariants().is_broken(&spec.id()));
+}
+
+#[test]
+fn t2_repaired_invariant_is_reported_again_when_broken_again() {
+    let spec = InvariantSpec::EdgeTarget {
+        edge_type: "assigned".to_string(),
+        target_type: "person".to_string(),
+    };
+    let mut rt = runtime(InvariantRegistry::new().with(spec.clone()));
+    let mut host = Queued {
+        proposals: vec![create("task", 1), create("person", 2), create("task", 3)],
+    };
+    rt.tick(&mut host).unwrap();
+    let id = |node_type: &str| rt.graph().nodes_of_type(node_type).next().unwrap().1.id;
+    let (person, task) = (id("person"), id("task"));
+    let other_task = rt
+        .graph()
+        .nodes_of_type("task")
+        .map(|(_, n)| n.id)
+        .find(|n| *n != task)
+        .unwrap();
+    let assign = |target| -> Proposal {
+        Slap::Connect {
+            source: task,
+            target,
+            edge_type: "assigned".to_string(),
+        }
+        .into()
+    };
+
+    host.proposals = vec![assign(person)];
+    rt.tick(&mut host).unwrap();
+    host.proposals = vec![assign(other_task)];
+    let first = violation(rt.tick(&mut host));
+    assert_eq!(first.witnesses, vec![task.hash()]);
+
+    host.proposals = vec![Slap::DeleteNode { id: other_task }.into()];
+    rt.tick(&mut host).unwrap();
+    assert!(!rt.invariants().is_broken(&spec.id()));
+
+    host.proposals = vec![create("task", 5)];
+    rt.tick(&mut host).unwrap();
+    let new_task = rt
+        .graph()
+        .nodes_of_type("task")
+        .map(|(_, n)| n.id)
+        .find(|n| *n != task)
+        .unwrap();
+    host.proposals = vec![assign(new_task)];
+    let again = violation(rt.tick(&mut host));
+    assert_eq!(again.invariant, first.invariant);
+    assert_eq!(again.tick, 5);
+}
+
+#[test]
+fn t3_custom_predicates_see_the_views_and_are_identified_by_name() {
+    let deadline = |graph: &jitos_graph::WarpGraph, views: &Views| {
+        let now = views.clock().now().ns();
+        (now > 2_000 && graph.nodes.is_empty()).then_some(Breach {
+            observed: now,
+            witnesses: vec![],
+        })
+    };
+    let registry = InvariantRegistry::new()
+        .with_predicate("work-started-by-2us", deadline)
+        .with(InvariantSpec::MaxNodes(10));
+    let ids: Vec<_> = registry.specs().map(|(id, _)| *id).collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&InvariantSpec::Predicate("work-started-by-2us".to_string()).id()));
+
+    let mut rt = runtime(registry);
+    let mut host = Queued::default();
+    rt.tick(&mut host).unwrap();
+    rt.tick(&mut host).unwrap();
+    let late = violation(rt.tick(&mut host));
+    assert_eq!(late.observed, 3_000);
+    assert!(late.slaps.is_empty());
+}
@@ -0,0 +1,124 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Logical Time Tests
+//!
+//! `SetTime` SLAPs drive the clock view through recorded logical time
+//! observations, and worldlines that set time replay.
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventKind, Signature};
+use jitos_core::{Proposal, Slap};
+use jitos_graph::snapshot::{MemorySnapshotStore, SnapshotStore};
+use jitos_runtime::replay;
+use jitos_runtime::{Host, Runtime, RuntimeError, Views};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::{ClockPolicyId, LogicalTime, TimeDomain, OBS_LOGICAL_TIME_V0};
+
+mod common;
+use common::sample;
+
+/// Samples the monotonic clock every tick and sets logical time on odd ones
+struct Simulation {
+    dt: f64,
+}
+
+impl Host for Simulation {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        vec![sample(1_000 * (tick + 1))]
+    }
+
+    fn proposals(&mut self, tick: u64, _views: &Views) -> Vec<Proposal> {
+        if tick.is_multiple_of(2) {
+            return vec![];
+        }
+        vec![Slap::SetTime {
+            tick: tick * 10,
+            dt: self.dt,
+        }
+        .into()]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![4]).unwrap()
+    }
+}
+
+fn runtime() -> Runtime {
+    Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest)
+}
+
+#[test]
+fn t1_set_time_drives_the_clock_from_the_next_tick() {
+    let mut rt = runtime();
+    let mut host = Simulation { dt: 0.5 };
+    let first = rt.tick(&mut host).unwrap();
+    assert_eq!(rt.views().clock().now().domain(), TimeDomain::Monotonic);
+
+    let second = rt.tick(&mut host).unwrap();
+    assert_eq!(second.receipt.timestamp, first.receipt.timestamp + 1_000);
+    let logical = rt.store().events().last().unwrap();
+    assert_eq!(logical.observation_type(), Some(OBS_LOGICAL_TIME_V0));
+    assert!(logical
+        .parents()
+        .contains(&second.commit.as_ref().unwrap().event_id()));
+    let policy = &rt.store().events()[rt.store().len() - 2];
+    assert_eq!(policy.kind(), &EventKind::PolicyContext);
+    assert!(logical.parents().contains(&policy.event_id()));
+
+    // Logical time overrides later monotonic samples.
+    let now = rt.views().clock().now();
+    assert_eq!(now.domain(), TimeDomain::Logical);
+    assert_eq!(now.ns(), 5_000_000_000);
+    assert_eq!(now.provenance(), &[logical.event_id()]);
+    let third = rt.tick(&mut host).unwrap();
+    assert_eq!(third.receipt.timestamp, 5_000_000_000);
+    rt.tick(&mut host).unwrap();
+    assert_eq!(rt.views().clock().now().ns(), 15_000_000_000);
+}
+
+#[test]
+fn t2_worldline_setting_time_replays_from_genesis_and_checkpoint() {
+    let mut rt = runtime();
+    let mut snapshots = MemorySnapshotStore::new();
+    let mut host = Simulation { dt: 0.25 };
+    for tick in 0..6 {
+        rt.tick(&mut host).unwrap();
+        if tick == 5 {
+            snapshots.save(rt.graph()).unwrap();
+        }
+    }
+
+    let replayed = replay::run(rt.store(), rt.store().len(), runtime()).unwrap();
+    assert_eq!(replayed.matched, 6);
+    assert_eq!(replayed.runtime.store().events(), rt.store().events());
+
+    let resumed =
+        replay::run_from_checkpoint(rt.store(), rt.store().len(), &snapshots, runtime()).unwrap();
+    assert_eq!(resumed.checkpoint, Some(5));
+    assert_eq!(
+        resumed.runtime.views().clock().state_hash(),
+        rt.views().clock().state_hash()
+    );
+}
+
+#[test]
+fn t3_invalid_dt_is_rejected_before_scheduling() {
+    assert_eq!(LogicalTime::from_set_time(3, 1e-9).unwrap().value_ns(), 3);
+    assert!(LogicalTime::from_set_time(3, -0.5).is_none());
+    assert!(LogicalTime::from_set_time(3, f64::NAN).is_none());
+
+    let mut rt = runtime();
+    let mut host = Simulation { dt: f64::INFINITY };
+    rt.tick(&mut host).unwrap();
+    let len = rt.store().len();
+    assert!(matches!(
+        rt.tick(&mut host),
+        Err(RuntimeError::InvalidTime { tick: 1, .. })
+    ));
+    assert_eq!(
+        rt.store().len(),
+        len + 1,
+        "only the observation is ingested"
+    );
+    assert!(rt.scheduler().deferred().is_empty());
+}
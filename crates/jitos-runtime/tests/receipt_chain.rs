@@ -0,0 +1,91 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Receipt Chain Tests
+//!
+//! Every tick's receipt links to the previous one by digest.
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::{Hash, Proposal, Receipt, Slap};
+use jitos_runtime::{Host, Runtime, Views};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::ClockPolicyId;
+
+mod common;
+use common::sample;
+
+/// Observes one sample and proposes one node per tick; optionally signs
+/// receipts with the first byte of their digest.
+struct CountingHost {
+    sign_receipts: bool,
+}
+
+impl Host for CountingHost {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        vec![sample(1_000 * (tick + 1))]
+    }
+
+    fn proposals(&mut self, tick: u64, _views: &Views) -> Vec<Proposal> {
+        vec![Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: tick.to_le_bytes().to_vec(),
+        }
+        .into()]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![1]).unwrap()
+    }
+
+    fn sign_receipt(&mut self, digest: &Hash) -> Option<Signature> {
+        self.sign_receipts
+            .then(|| Signature::new(vec![digest.0[0], 0xEE]).unwrap())
+    }
+}
+
+fn run(sign_receipts: bool, ticks: usize) -> Vec<Receipt> {
+    let mut rt = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+    let mut host = CountingHost { sign_receipts };
+    for _ in 0..ticks {
+        rt.tick(&mut host).unwrap();
+    }
+    rt.receipts().to_vec()
+}
+
+#[test]
+fn t1_receipts_chain_by_digest() {
+    let receipts = run(false, 4);
+    assert_eq!(receipts.len(), 4);
+    assert_eq!(receipts[0].prev_receipt, None);
+    for pair in receipts.windows(2) {
+        assert_eq!(pair[1].prev_receipt, Some(pair[0].digest().unwrap()));
+        assert_eq!(pair[1].tick, pair[0].tick + 1);
+    }
+    assert!(receipts.iter().all(|r| r.signature.is_none()));
+}
+
+#[test]
+fn t2_signatures_do_not_change_the_chain() {
+    let unsigned = run(false, 3);
+    let signed = run(true, 3);
+    for (u, s) in unsigned.iter().zip(&signed) {
+        let digest = u.digest().unwrap();
+        assert_eq!(s.digest().unwrap(), digest);
+        assert_eq!(
+            s.signature.as_deref(),
+            Some(format!("{:02x}ee", digest.0[0]).as_str())
+        );
+    }
+}
+
+#[test]
+fn t3_commit_carries_the_signed_receipt() {
+    let mut rt = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+    let mut host = CountingHost {
+        sign_receipts: true,
+    };
+    let out = rt.tick(&mut host).unwrap();
+    let recorded: Receipt = out.commit.unwrap().payload().to_value().unwrap();
+    assert_eq!(recorded.signature, out.receipt.signature);
+    assert_eq!(recorded.digest().unwrap(), out.receipt.digest().unwrap());
+}
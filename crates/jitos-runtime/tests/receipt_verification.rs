@@ -0,0 +1,153 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Receipt Chain Verification Tests
+//!
+//! An honest run verifies; every kind of tampering is caught at its tick.
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::{Hash, Proposal, Receipt, Slap};
+use jitos_graph::snapshot::{MemorySnapshotStore, SnapshotStore};
+use jitos_runtime::{verify_chain, DivergenceReason, Host, Runtime, VerifyError, Views};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::ClockPolicyId;
+
+mod common;
+use common::sample;
+
+/// Toy key: a signature is the digest's first byte followed by 0x5A.
+fn toy_signature(digest: &Hash) -> Signature {
+    Signature::new(vec![digest.0[0], 0x5A]).unwrap()
+}
+
+fn toy_verify(digest: &Hash, signature: &Signature) -> bool {
+    signature == &toy_signature(digest)
+}
+
+/// Proposes a node on even ticks and nothing on odd ones.
+struct AlternatingHost;
+
+impl Host for AlternatingHost {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        vec![sample(1_000 * (tick + 1))]
+    }
+
+    fn proposals(&mut self, tick: u64, _views: &Views) -> Vec<Proposal> {
+        if tick % 2 == 1 {
+            return vec![];
+        }
+        vec![Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: tick.to_le_bytes().to_vec(),
+        }
+        .into()]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![1]).unwrap()
+    }
+
+    fn sign_receipt(&mut self, digest: &Hash) -> Option<Signature> {
+        Some(toy_signature(digest))
+    }
+}
+
+fn honest_run() -> (Runtime, MemorySnapshotStore) {
+    let mut rt = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+    let mut snapshots = MemorySnapshotStore::new();
+    for _ in 0..5 {
+        rt.tick(&mut AlternatingHost).unwrap();
+        snapshots.save(rt.graph()).unwrap();
+    }
+    (rt, snapshots)
+}
+
+fn divergence(
+    receipts: &[Receipt],
+    rt: &Runtime,
+    snapshots: &MemorySnapshotStore,
+) -> (u64, DivergenceReason) {
+    match verify_chain(receipts, rt.store(), snapshots, &toy_verify) {
+        Err(VerifyError::Diverged(d)) => (d.tick, d.reason),
+        other => panic!("expected divergence, got {other:?}"),
+    }
+}
+
+#[test]
+fn t1_honest_chain_verifies() {
+    let (rt, snapshots) = honest_run();
+    let summary = verify_chain(rt.receipts(), rt.store(), &snapshots, &toy_verify).unwrap();
+    assert_eq!(summary.receipts, 5);
+    assert_eq!(summary.committed, 5);
+    assert_eq!(summary.snapshots, 5);
+    assert_eq!(summary.signed, 5);
+
+    // Any suffix verifies on its own.
+    assert!(verify_chain(&rt.receipts()[2..], rt.store(), &snapshots, &toy_verify).is_ok());
+}
+
+#[test]
+fn t2_tampered_receipts_diverge_at_their_tick() {
+    let (rt, snapshots) = honest_run();
+
+    // Rewriting applied SLAPs detaches the receipt from its Commit.
+    let mut receipts = rt.receipts().to_vec();
+    receipts[2].applied_slaps = vec![Hash([9u8; 32])];
+    receipts[2].signature = Some(hex_signature(&receipts[2]));
+    assert_eq!(
+        divergence(&receipts, &rt, &snapshots),
+        (2, DivergenceReason::Uncommitted)
+    );
+
+    // A quiet tick cannot move the state.
+    let mut receipts = rt.receipts().to_vec();
+    receipts[1].state_hash = Hash([7u8; 32]);
+    receipts[1].signature = Some(hex_signature(&receipts[1]));
+    let (tick, reason) = divergence(&receipts, &rt, &snapshots);
+    assert_eq!(tick, 1);
+    assert!(matches!(reason, DivergenceReason::StateChanged { .. }));
+
+    // Dropping a receipt leaves a gap.
+    let mut receipts = rt.receipts().to_vec();
+    receipts.remove(3);
+    assert_eq!(
+        divergence(&receipts, &rt, &snapshots),
+        (4, DivergenceReason::TickGap { expected: 3 })
+    );
+
+    // A forged signature fails.
+    let mut receipts = rt.receipts().to_vec();
+    receipts[4].signature = Some("00".to_string());
+    let (tick, reason) = divergence(&receipts, &rt, &snapshots);
+    assert_eq!(tick, 4);
+    assert!(matches!(reason, DivergenceReason::BadSignature { .. }));
+}
+
+#[test]
+fn t3_relinked_receipt_breaks_the_chain() {
+    let (rt, snapshots) = honest_run();
+    let mut receipts = rt.receipts().to_vec();
+    let expected = receipts[2].digest().unwrap();
+    receipts[3] = receipts[3].clone().chained_to(Hash([0u8; 32]));
+    receipts[3].signature = Some(hex_signature(&receipts[3]));
+    assert_eq!(
+        divergence(&receipts, &rt, &snapshots),
+        (
+            3,
+            DivergenceReason::BrokenLink {
+                expected,
+                found: Some(Hash([0u8; 32])),
+            }
+        )
+    );
+}
+
+fn hex_signature(receipt: &Receipt) -> String {
+    let digest = receipt.digest().unwrap();
+    let signature = toy_signature(&digest);
+    receipt
+        .clone()
+        .with_signature(&signature)
+        .signature
+        .unwrap()
+}
@@ -0,0 +1,190 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replay Engine Tests
+//!
+//! A recorded worldline replays to the same receipts; a differently
+//! configured runtime diverges at the first tick it disagrees on.
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::{Hash, NodeId, Proposal, Receipt, Slap};
+use jitos_graph::{WarpGraph, WarpNode};
+use jitos_runtime::replay::{self, Mismatch, ReplayError};
+use jitos_runtime::{Host, Runtime, Views};
+use jitos_scheduler::{EchoScheduler, SchedulerPolicy};
+use jitos_views::{ClockPolicyId, ClockSample, ClockSource, OBS_CLOCK_SAMPLE_V0};
+
+fn sample(source: ClockSource, value_ns: u64) -> EventEnvelope {
+    let sample = ClockSample {
+        source,
+        value_ns,
+        uncertainty_ns: 10,
+    };
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&sample).unwrap(),
+        vec![],
+        Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+/// Two idle ticks, then samples (one re-delivered) and node proposals.
+struct BusyHost;
+
+impl Host for BusyHost {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        match tick {
+            0 | 1 => vec![],
+            2 => vec![
+                sample(ClockSource::Monotonic, 100),
+                sample(ClockSource::Ntp, 5_000),
+            ],
+            // Re-delivers tick 2's monotonic sample.
+            4 => vec![
+                sample(ClockSource::Monotonic, 100),
+                sample(ClockSource::Monotonic, 300),
+            ],
+            _ => vec![sample(ClockSource::Monotonic, 100 * tick)],
+        }
+    }
+
+    fn proposals(&mut self, tick: u64, views: &Views) -> Vec<Proposal> {
+        if tick < 2 || tick == 5 {
+            return vec![];
+        }
+        // Proposals may depend on the views; replay must reproduce them.
+        let now = views.clock().now().ns();
+        vec![Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: now.to_le_bytes().to_vec(),
+        }
+        .into()]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![7]).unwrap()
+    }
+
+    fn sign_receipt(&mut self, digest: &Hash) -> Option<Signature> {
+        Some(Signature::new(digest.0[..4].to_vec()).unwrap())
+    }
+}
+
+fn runtime(clock: ClockPolicyId) -> Runtime {
+    Runtime::new(EchoScheduler::new(), clock)
+}
+
+fn digests(receipts: &[Receipt]) -> Vec<Hash> {
+    receipts.iter().map(|r| r.digest().unwrap()).collect()
+}
+
+fn recorded() -> Runtime {
+    let mut rt = runtime(ClockPolicyId::TrustMonotonicLatest);
+    for _ in 0..7 {
+        rt.tick(&mut BusyHost).unwrap();
+    }
+    rt
+}
+
+#[test]
+fn t1_replay_reproduces_every_receipt() {
+    let rt = recorded();
+    let replayed = replay::run(
+        rt.store(),
+        rt.store().len(),
+        runtime(ClockPolicyId::TrustMonotonicLatest),
+    )
+    .unwrap();
+    assert_eq!(replayed.ticks, 7);
+    assert_eq!(replayed.matched, 5);
+    assert_eq!(digests(replayed.runtime.receipts()), digests(rt.receipts()));
+    assert_eq!(replayed.runtime.store().events(), rt.store().events());
+    assert_eq!(
+        replayed.runtime.graph().compute_hash(),
+        rt.graph().compute_hash()
+    );
+}
+
+#[test]
+fn t2_replay_stops_at_the_cut() {
+    let rt = recorded();
+    let ticks = replay::recorded_ticks(rt.store().events());
+    assert_eq!(ticks.len(), 5);
+    assert_eq!(
+        ticks[2].observations.len(),
+        2,
+        "re-delivered sample included"
+    );
+
+    // Cut just after tick 3's Commit.
+    let cut = rt.store().position(&ticks[1].commit.event_id()).unwrap() + 1;
+    let replayed = replay::run(
+        rt.store(),
+        cut,
+        runtime(ClockPolicyId::TrustMonotonicLatest),
+    )
+    .unwrap();
+    assert_eq!(replayed.ticks, 4);
+    assert_eq!(
+        digests(replayed.runtime.receipts()),
+        digests(&rt.receipts()[..4])
+    );
+}
+
+#[test]
+fn t3_divergence_names_tick_hash_and_suspects() {
+    let rt = recorded();
+    let ticks = replay::recorded_ticks(rt.store().events());
+
+    // A different clock policy disagrees on the clock view at the first
+    // recorded tick.
+    match replay::run(
+        rt.store(),
+        rt.store().len(),
+        runtime(ClockPolicyId::TrustNtpLatest),
+    ) {
+        Err(ReplayError::Diverged(d)) => {
+            assert_eq!(d.tick, 2);
+            assert_eq!(d.mismatch, Mismatch::View("clock".to_string()));
+            assert_eq!(d.expected, rt.receipts()[2].view_hashes["clock"]);
+            assert_eq!(d.suspects, ticks[0].events());
+        }
+        other => panic!("expected divergence, got {:?}", other.err()),
+    }
+
+    // A different starting graph disagrees on state.
+    let mut graph = WarpGraph::new();
+    graph.insert_node(WarpNode {
+        id: NodeId::from_hash(Hash([1u8; 32])),
+        node_type: "seed".to_string(),
+        payload_bytes: vec![],
+        attachment: None,
+    });
+    let rt2 = runtime(ClockPolicyId::TrustMonotonicLatest).with_graph(graph);
+    match replay::run(rt.store(), rt.store().len(), rt2) {
+        Err(ReplayError::Diverged(d)) => {
+            assert_eq!(d.tick, 2);
+            assert_eq!(d.mismatch, Mismatch::State);
+            assert_eq!(d.expected, rt.receipts()[2].state_hash);
+        }
+        other => panic!("expected divergence, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn t4_runtime_must_match_the_worldline_policy() {
+    let rt = recorded();
+    let other = Runtime::new(
+        EchoScheduler::with_policy(SchedulerPolicy {
+            tick_budget: Some(1),
+            ..SchedulerPolicy::default()
+        }),
+        ClockPolicyId::TrustMonotonicLatest,
+    );
+    assert!(matches!(
+        replay::run(rt.store(), rt.store().len(), other),
+        Err(ReplayError::PolicyMismatch { .. })
+    ));
+}
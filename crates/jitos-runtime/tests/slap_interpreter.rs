@@ -0,0 +1,135 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! SLAP Interpreter Tests
+//!
+//! Applying the recorded schedules to the starting graph reproduces the
+//! digest every receipt records.
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, Signature};
+use jitos_core::{Proposal, Slap};
+use jitos_graph::{WarpGraph, WarpNode};
+use jitos_runtime::{
+    interpret, Host, InterpretError, Runtime, SlapInterpreter, Views, OBS_SLAP_PROPOSALS_V0,
+};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::ClockPolicyId;
+
+mod common;
+use common::sample;
+
+/// Host proposing whatever the test queued for the next tick
+#[derive(Default)]
+struct Queued {
+    observations: Vec<EventEnvelope>,
+    proposals: Vec<Proposal>,
+}
+
+impl Host for Queued {
+    fn observations(&mut self, _tick: u64) -> Vec<EventEnvelope> {
+        std::mem::take(&mut self.observations)
+    }
+
+    fn proposals(&mut self, _tick: u64, _views: &Views) -> Vec<Proposal> {
+        std::mem::take(&mut self.proposals)
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![5]).unwrap()
+    }
+}
+
+fn create(payload: u8) -> Proposal {
+    Slap::CreateNode {
+        node_type: "task".to_string(),
+        payload_bytes: vec![payload],
+    }
+    .into()
+}
+
+/// Creates, connects and deletes nodes over four ticks
+fn recorded() -> Runtime {
+    let mut rt = Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest);
+    let mut host = Queued {
+        observations: vec![sample(1_000)],
+        proposals: vec![create(1), create(2), create(3)],
+    };
+    rt.tick(&mut host).unwrap();
+
+    let mut ids: Vec<_> = rt.graph().nodes.values().map(|n| n.id).collect();
+    ids.sort();
+    host.proposals = vec![
+        Slap::Connect {
+            source: ids[0],
+            target: ids[1],
+            edge_type: "next".to_string(),
+        }
+        .into(),
+        create(4),
+    ];
+    rt.tick(&mut host).unwrap();
+
+    host.observations = vec![sample(2_000)];
+    host.proposals = vec![Slap::DeleteNode { id: ids[2] }.into()];
+    rt.tick(&mut host).unwrap();
+    rt.tick(&mut host).unwrap();
+    rt
+}
+
+#[test]
+fn t1_worldline_interprets_to_the_runtime_graph() {
+    let rt = recorded();
+    let graph = interpret(rt.store().events(), WarpGraph::new()).unwrap();
+    assert_eq!(graph.compute_hash(), rt.graph().compute_hash());
+    assert_eq!(graph.nodes.len(), 3);
+    assert_eq!(graph.edges.len(), 1);
+}
+
+#[test]
+fn t2_each_decision_reaches_its_receipt_digest() {
+    let rt = recorded();
+    let events = rt.store().events();
+    let mut interpreter = SlapInterpreter::new();
+    let mut graph = WarpGraph::new();
+    let mut applied = 0;
+    for (i, event) in events.iter().enumerate() {
+        interpreter.learn(event).unwrap();
+        if let Ok(receipt) = event.payload().to_value::<jitos_core::Receipt>() {
+            let digest = interpreter
+                .apply_committed(&mut graph, &events[i - 1], event)
+                .unwrap();
+            assert_eq!(digest, receipt.state_hash);
+            assert_eq!(digest, rt.receipts()[receipt.tick as usize].state_hash);
+            applied += 1;
+        }
+    }
+    assert_eq!(applied, 4);
+}
+
+#[test]
+fn t3_divergent_start_and_missing_proposals_are_reported() {
+    let rt = recorded();
+    let events = rt.store().events();
+
+    let mut other = WarpGraph::new();
+    other.insert_node(WarpNode {
+        id: jitos_graph::NodeId::from_hash(jitos_core::Hash([9; 32])),
+        node_type: "stray".to_string(),
+        payload_bytes: vec![],
+        attachment: None,
+    });
+    assert!(matches!(
+        interpret(events, other),
+        Err(InterpretError::DigestMismatch { tick: 0, .. })
+    ));
+
+    let without_proposals: Vec<EventEnvelope> = events
+        .iter()
+        .filter(|e| e.observation_type() != Some(OBS_SLAP_PROPOSALS_V0))
+        .cloned()
+        .collect();
+    assert!(matches!(
+        interpret(&without_proposals, WarpGraph::new()),
+        Err(InterpretError::UnknownSlap { .. })
+    ));
+}
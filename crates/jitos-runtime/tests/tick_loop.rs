@@ -0,0 +1,197 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tick Loop Tests
+//!
+//! The loop is a pure function of its state and the host's answers.
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventKind, Signature};
+use jitos_core::{Proposal, Receipt, Slap};
+use jitos_runtime::{
+    Host, Runtime, RuntimeError, Views, CLOCK_VIEW, OBS_SLAP_PROPOSALS_V0, TIMER_VIEW,
+};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::ClockPolicyId;
+
+mod common;
+use common::sample;
+
+fn create(payload: u8) -> Proposal {
+    Slap::CreateNode {
+        node_type: "task".to_string(),
+        payload_bytes: vec![payload],
+    }
+    .into()
+}
+
+/// Host replaying a fixed script: (observations, proposals) per tick
+struct ScriptedHost {
+    script: Vec<(Vec<EventEnvelope>, Vec<Proposal>)>,
+}
+
+impl Host for ScriptedHost {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        self.script
+            .get(tick as usize)
+            .map(|(obs, _)| obs.clone())
+            .unwrap_or_default()
+    }
+
+    fn proposals(&mut self, tick: u64, _views: &Views) -> Vec<Proposal> {
+        self.script
+            .get(tick as usize)
+            .map(|(_, proposals)| proposals.clone())
+            .unwrap_or_default()
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![0xAA]).unwrap()
+    }
+}
+
+fn runtime() -> Runtime {
+    Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest)
+}
+
+fn script() -> Vec<(Vec<EventEnvelope>, Vec<Proposal>)> {
+    vec![
+        (vec![], vec![]),
+        (vec![sample(1_000)], vec![create(1), create(2)]),
+        (vec![], vec![create(3)]),
+        (vec![sample(2_000), sample(2_500)], vec![]),
+    ]
+}
+
+#[test]
+fn t1_tick_records_decision_commit_and_receipt() {
+    let mut rt = runtime();
+    let mut host = ScriptedHost { script: script() };
+
+    // Nothing observed yet: nothing to record.
+    let idle = rt.tick(&mut host).unwrap();
+    assert!(idle.decision.is_none() && idle.commit.is_none());
+    assert_eq!(rt.store().len(), 1, "only the scheduler policy context");
+
+    let out = rt.tick(&mut host).unwrap();
+    assert_eq!(out.tick, 1);
+    assert_eq!(rt.graph().nodes.len(), 2);
+    assert_eq!(out.receipt.applied_slaps.len(), 2);
+    assert_eq!(out.receipt.state_hash, rt.graph().compute_hash());
+    assert_eq!(out.receipt.timestamp, 1_000);
+    assert_eq!(
+        out.receipt.view_hashes.keys().collect::<Vec<_>>(),
+        vec![CLOCK_VIEW, TIMER_VIEW]
+    );
+
+    let decision = out.decision.unwrap();
+    let commit = out.commit.unwrap();
+    assert_eq!(decision.kind(), &EventKind::Decision);
+    // The proposals are recorded right after the observation, as evidence.
+    let proposed = &rt.store().events()[2];
+    assert_eq!(proposed.observation_type(), Some(OBS_SLAP_PROPOSALS_V0));
+    let recorded: Vec<Proposal> = proposed.payload().to_value().unwrap();
+    assert_eq!(recorded.len(), 2);
+    let mut parents = vec![
+        out.observations[0],
+        proposed.event_id(),
+        rt.scheduler().policy_context().event_id(),
+    ];
+    parents.sort();
+    assert_eq!(decision.parents(), parents.as_slice());
+    assert_eq!(commit.parents(), &[decision.event_id()]);
+    let recorded: Receipt = commit.payload().to_value().unwrap();
+    assert_eq!(recorded.state_hash, out.receipt.state_hash);
+    assert_eq!(rt.last_commit(), Some(commit.event_id()));
+
+    // With nothing new observed, the next tick cites the previous commit.
+    let next = rt.tick(&mut host).unwrap();
+    assert!(next
+        .decision
+        .unwrap()
+        .parents()
+        .contains(&commit.event_id()));
+    assert_eq!(rt.graph().nodes.len(), 3);
+    assert_eq!(rt.store().len(), 1 + 4 + 3);
+}
+
+#[test]
+fn t2_same_host_answers_reproduce_the_worldline() {
+    let run = || {
+        let mut rt = runtime();
+        let mut host = ScriptedHost { script: script() };
+        let receipts: Vec<_> = (0..4)
+            .map(|_| {
+                let out = rt.tick(&mut host).unwrap();
+                (out.receipt.state_hash, out.receipt.view_hashes, out.commit)
+            })
+            .collect();
+        (receipts, rt.store().events().to_vec())
+    };
+    let (a, events_a) = run();
+    let (b, events_b) = run();
+    assert_eq!(a, b);
+    assert_eq!(events_a, events_b);
+}
+
+#[test]
+fn t3_host_errors_are_reported() {
+    let mut rt = runtime();
+    let mut host = ScriptedHost {
+        script: vec![(vec![], vec![create(1)])],
+    };
+    assert!(matches!(
+        rt.tick(&mut host),
+        Err(RuntimeError::NoEvidence { tick: 0 })
+    ));
+    assert_eq!(rt.next_tick(), 0);
+
+    let policy = rt.scheduler().policy_context().clone();
+    let mut host = ScriptedHost {
+        script: vec![(vec![policy.clone()], vec![])],
+    };
+    assert!(matches!(
+        rt.tick(&mut host),
+        Err(RuntimeError::NotAnObservation(id)) if id == policy.event_id()
+    ));
+}
+
+#[test]
+fn t4_failed_batch_leaves_graph_queue_and_worldline_untouched() {
+    let set_time = |dt| -> Proposal { Slap::SetTime { tick: 1, dt }.into() };
+    let mut rt = Runtime::new(
+        EchoScheduler::with_max_batches(1),
+        ClockPolicyId::TrustMonotonicLatest,
+    );
+    let unknown = jitos_graph::NodeId::from_hash(jitos_core::Hash([9; 32]));
+    let mut host = ScriptedHost {
+        script: vec![
+            (vec![sample(1_000)], vec![set_time(1.0), set_time(2.0)]),
+            (vec![], vec![Slap::DeleteNode { id: unknown }.into()]),
+        ],
+    };
+    rt.tick(&mut host).unwrap();
+    let waiting = |rt: &Runtime| -> Vec<_> {
+        rt.scheduler()
+            .deferred()
+            .iter()
+            .map(|(hash, entry)| (*hash, entry.age))
+            .collect()
+    };
+    let (queue, events, state) = (waiting(&rt), rt.store().len(), rt.graph().compute_hash());
+    assert_eq!(queue.len(), 1);
+
+    assert!(matches!(rt.tick(&mut host), Err(RuntimeError::Exec(_))));
+    assert_eq!(rt.next_tick(), 1);
+    assert_eq!(waiting(&rt), queue);
+    assert_eq!(rt.store().len(), events, "no proposals observation");
+    assert_eq!(rt.graph().compute_hash(), state);
+
+    host.script.truncate(1);
+    let outcome = rt.tick(&mut host).unwrap();
+    assert_eq!(
+        outcome.schedule.batches.concat().len(),
+        1,
+        "the waiting SLAP"
+    );
+    assert!(rt.scheduler().deferred().is_empty());
+}
@@ -0,0 +1,195 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Timer Driver Tests
+//!
+//! Due timers are fired as Decision, side effect, signed Commit - at most
+//! once per Commit, and completed after a crash under the same key.
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventId, EventKind, Signature};
+use jitos_core::{Hash, Proposal};
+use jitos_runtime::replay;
+use jitos_runtime::{Host, Runtime, TimerCommit, TimerDriver, TimerOutcome, Views};
+use jitos_scheduler::EchoScheduler;
+use jitos_views::{
+    ClockPolicyId, ClockSample, ClockSource, FireSemantics, TimerFire, TimerRequest,
+    OBS_CLOCK_SAMPLE_V0, OBS_TIMER_REQUEST_V0,
+};
+
+fn observation<T: serde::Serialize>(tag: &str, payload: &T) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(payload).unwrap(),
+        vec![],
+        Some(tag.to_string()),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+fn sample(value_ns: u64) -> EventEnvelope {
+    let sample = ClockSample {
+        source: ClockSource::Monotonic,
+        value_ns,
+        uncertainty_ns: 0,
+    };
+    observation(OBS_CLOCK_SAMPLE_V0, &sample)
+}
+
+fn request(id: u8, duration_ns: u64) -> EventEnvelope {
+    let request = TimerRequest {
+        request_id: Hash([id; 32]),
+        duration_ns,
+        requested_at_ns: 1_000,
+    };
+    observation(OBS_TIMER_REQUEST_V0, &request)
+}
+
+/// Delivers queued observations, one batch per tick
+struct Scripted {
+    batches: Vec<Vec<EventEnvelope>>,
+}
+
+impl Host for Scripted {
+    fn observations(&mut self, tick: u64) -> Vec<EventEnvelope> {
+        self.batches.get(tick as usize).cloned().unwrap_or_default()
+    }
+
+    fn proposals(&mut self, _tick: u64, _views: &Views) -> Vec<Proposal> {
+        vec![]
+    }
+
+    fn sign(&mut self, _payload: &CanonicalBytes) -> Signature {
+        Signature::new(vec![7]).unwrap()
+    }
+}
+
+fn policy() -> EventEnvelope {
+    let payload = CanonicalBytes::from_value(&"timers-definitely-v0").unwrap();
+    EventEnvelope::new_policy_context(payload, vec![], None, None).unwrap()
+}
+
+fn runtime() -> Runtime {
+    Runtime::new(EchoScheduler::new(), ClockPolicyId::TrustMonotonicLatest)
+}
+
+/// Runtime that has ticked through `batches`
+fn ticked(batches: Vec<Vec<EventEnvelope>>) -> (Runtime, Scripted) {
+    let mut rt = runtime();
+    let mut host = Scripted { batches };
+    for _ in 0..host.batches.len() {
+        rt.tick(&mut host).unwrap();
+    }
+    (rt, host)
+}
+
+#[test]
+fn t1_due_timers_fire_once_with_decision_and_signed_commit() {
+    let (mut rt, mut host) = ticked(vec![vec![
+        sample(1_000),
+        request(1, 500),
+        request(2, 5_000),
+    ]]);
+    let mut driver = TimerDriver::new(policy());
+    let mut calls: Vec<TimerFire> = Vec::new();
+    let mut effector = |_: &EventId, fire: &TimerFire| {
+        calls.push(fire.clone());
+        Ok(())
+    };
+
+    assert!(driver
+        .drive(&mut rt, &mut host, &mut effector)
+        .unwrap()
+        .is_empty());
+
+    host.batches.push(vec![sample(1_600)]);
+    rt.tick(&mut host).unwrap();
+    let fired = driver.drive(&mut rt, &mut host, &mut effector).unwrap();
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].fire.request_id, Hash([1; 32]));
+    assert_eq!(fired[0].fire.fired_at_ns, 1_600);
+    assert!(!fired[0].recovered);
+
+    let store = rt.store();
+    let decision = &store.events()[store.position(&fired[0].decision).unwrap()];
+    assert_eq!(decision.kind(), &EventKind::Decision);
+    assert!(decision.parents().contains(&policy().event_id()));
+    assert!(decision.parents().contains(&request(1, 500).event_id()));
+    let commit = &store.events()[store.position(&fired[0].commit).unwrap()];
+    assert_eq!(commit.parents(), &[fired[0].decision]);
+    assert_eq!(commit.signature(), Some(&Signature::new(vec![7]).unwrap()));
+    let recorded: TimerCommit = commit.payload().to_value().unwrap();
+    assert_eq!(recorded.outcome, TimerOutcome::Done);
+
+    assert!(driver
+        .drive(&mut rt, &mut host, &mut effector)
+        .unwrap()
+        .is_empty());
+    assert_eq!(calls.len(), 1);
+}
+
+#[test]
+fn t2_fire_interrupted_before_commit_is_completed_under_the_same_key() {
+    let (mut rt, mut host) = ticked(vec![vec![sample(2_000), request(1, 500)]]);
+
+    // A driver recorded the Decision, then crashed before committing.
+    let views = rt.views();
+    let decisions = views
+        .timers()
+        .propose_fires(views.clock().now(), FireSemantics::Definitely, &policy())
+        .unwrap();
+    rt.record(policy()).unwrap();
+    rt.record(decisions[0].clone()).unwrap();
+    assert!(rt
+        .views()
+        .timers()
+        .pending_timers(rt.views().clock().now())
+        .is_empty());
+
+    let mut keys = Vec::new();
+    let mut effector = |key: &EventId, _: &TimerFire| {
+        keys.push(*key);
+        Err("device busy".to_string())
+    };
+    let mut restarted = TimerDriver::new(policy());
+    let fired = restarted.drive(&mut rt, &mut host, &mut effector).unwrap();
+    assert_eq!(fired.len(), 1);
+    assert!(fired[0].recovered);
+    assert_eq!(
+        fired[0].outcome,
+        TimerOutcome::Failed("device busy".to_string())
+    );
+
+    // Once committed, no driver performs it again.
+    assert!(restarted
+        .drive(&mut rt, &mut host, &mut effector)
+        .unwrap()
+        .is_empty());
+    let mut fresh = TimerDriver::new(policy());
+    assert!(fresh
+        .drive(&mut rt, &mut host, &mut effector)
+        .unwrap()
+        .is_empty());
+    assert_eq!(keys, [decisions[0].event_id()]);
+}
+
+#[test]
+fn t3_worldline_with_timer_fires_replays() {
+    let (mut rt, mut host) = ticked(vec![vec![sample(1_000), request(1, 500)]]);
+    let mut driver = TimerDriver::new(policy());
+    host.batches.push(vec![sample(2_000)]);
+    rt.tick(&mut host).unwrap();
+    let fired = driver
+        .drive(&mut rt, &mut host, &mut |_: &EventId, _: &TimerFire| Ok(()))
+        .unwrap();
+    assert_eq!(fired.len(), 1);
+    host.batches.push(vec![sample(3_000)]);
+    rt.tick(&mut host).unwrap();
+
+    let replayed = replay::run(rt.store(), rt.store().len(), runtime()).unwrap();
+    assert_eq!(replayed.matched, 3);
+    assert_eq!(
+        replayed.runtime.views().timers().state_hash(),
+        rt.views().timers().state_hash()
+    );
+}
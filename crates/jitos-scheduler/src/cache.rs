@@ -0,0 +1,145 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Footprint Cache
+//!
+//! Inferred footprints are cached by [`CacheKey`]: the SLAP's canonical hash,
+//! plus the commit digest of the graph it was inferred against when the
+//! footprint depends on graph state (`DeleteNode` writes the node's incident
+//! edges). A graph-dependent entry can never be served for a different graph,
+//! so nothing goes stale; old entries simply stop being hit and age out.
+//!
+//! The cache is bounded: once `capacity` entries are held, inserting evicts
+//! the least recently used one. Hit, miss and eviction counts are kept for
+//! monitoring long-running nodes.
+
+use crate::Footprint;
+use jitos_core::Hash;
+use std::collections::{BTreeMap, HashMap};
+
+/// Default number of cached footprints.
+pub const DEFAULT_CAPACITY: usize = 4096;
+
+/// Cache key for one inferred footprint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// Canonical hash of the SLAP.
+    pub slap: Hash,
+    /// Commit digest of the graph consulted, if the footprint read the graph.
+    pub graph: Option<Hash>,
+}
+
+/// Cache counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+/// Bounded LRU cache of inferred footprints.
+#[derive(Debug, Clone)]
+pub struct FootprintCache {
+    capacity: usize,
+    /// key -> (footprint, last use stamp)
+    entries: HashMap<CacheKey, (Footprint, u64)>,
+    /// last use stamp -> key, oldest first
+    recency: BTreeMap<u64, CacheKey>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl Default for FootprintCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl FootprintCache {
+    /// Cache holding at most `capacity` footprints (0 disables caching).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the bound, evicting least recently used entries if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > self.capacity {
+            self.evict_oldest();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// True if `key` is cached. Does not count as a use.
+    pub fn contains(&self, key: &CacheKey) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Drop every entry. Counters are kept.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// Look up `key`, marking it most recently used and counting a hit or miss.
+    pub(crate) fn get(&mut self, key: &CacheKey) -> Option<&Footprint> {
+        let stamp = self.clock;
+        match self.entries.get_mut(key) {
+            Some((fp, last)) => {
+                self.recency.remove(last);
+                self.recency.insert(stamp, *key);
+                *last = stamp;
+                self.clock += 1;
+                self.stats.hits += 1;
+                Some(fp)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn insert(&mut self, key: CacheKey, footprint: Footprint) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some((_, last)) = self.entries.remove(&key) {
+            self.recency.remove(&last);
+        } else if self.entries.len() >= self.capacity {
+            self.evict_oldest();
+        }
+        let stamp = self.clock;
+        self.clock += 1;
+        self.recency.insert(stamp, key);
+        self.entries.insert(key, (footprint, stamp));
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some((_, key)) = self.recency.pop_first() {
+            self.entries.remove(&key);
+            self.stats.evictions += 1;
+        }
+    }
+}
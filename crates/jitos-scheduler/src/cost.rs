@@ -0,0 +1,98 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tick Cost Model
+//!
+//! A [`CostModel`] assigns each SLAP a deterministic cost in abstract units.
+//! When the scheduler policy sets a `tick_budget`, the scheduler stops filling
+//! a tick once the next admissible SLAP would exceed the budget and defers the
+//! rest. This bounds the work per tick without any wall-clock measurement, so
+//! the schedule stays a pure function of its inputs.
+//!
+//! Costs must depend only on the SLAP itself: never on time, randomness or
+//! host state. A transaction costs the sum of its members.
+
+use jitos_core::{Hash, Slap};
+use std::collections::BTreeMap;
+
+/// Deterministic per-SLAP cost.
+pub trait CostModel {
+    fn cost(&self, slap: &Slap) -> u64;
+}
+
+/// Cost by SLAP kind plus payload size, plus declared fuel for scripts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefaultCostModel {
+    pub create_node: u64,
+    pub delete_node: u64,
+    pub connect: u64,
+    pub invoke_script: u64,
+    pub set_time: u64,
+    pub collapse: u64,
+    /// Added per payload byte (`CreateNode` payload, `InvokeScript` args).
+    pub per_byte: u64,
+    /// Payload bytes covered by one `per_byte` charge.
+    pub bytes_per_unit: u64,
+    /// Fuel budget of each script (Rhai operations or WASM fuel), by script
+    /// id, charged on every `InvokeScript` of it. The budget, not the fuel
+    /// actually used: the cost must be known before the script runs.
+    pub script_fuel: BTreeMap<Hash, u64>,
+    /// Script fuel covered by one cost unit.
+    pub fuel_per_unit: u64,
+}
+
+impl Default for DefaultCostModel {
+    fn default() -> Self {
+        Self {
+            create_node: 4,
+            delete_node: 2,
+            connect: 2,
+            invoke_script: 16,
+            set_time: 1,
+            collapse: 32,
+            per_byte: 1,
+            bytes_per_unit: 64,
+            script_fuel: BTreeMap::new(),
+            fuel_per_unit: 10_000,
+        }
+    }
+}
+
+impl DefaultCostModel {
+    fn payload(&self, bytes: usize) -> u64 {
+        (bytes as u64)
+            .div_ceil(self.bytes_per_unit.max(1))
+            .saturating_mul(self.per_byte)
+    }
+
+    /// Charge `fuel` for every invocation of `script_id`.
+    pub fn with_script_fuel(mut self, script_id: Hash, fuel: u64) -> Self {
+        self.script_fuel.insert(script_id, fuel);
+        self
+    }
+}
+
+impl CostModel for DefaultCostModel {
+    fn cost(&self, slap: &Slap) -> u64 {
+        match slap {
+            Slap::CreateNode { payload_bytes, .. } => self
+                .create_node
+                .saturating_add(self.payload(payload_bytes.len())),
+            Slap::DeleteNode { .. } => self.delete_node,
+            Slap::Connect { .. } => self.connect,
+            Slap::InvokeScript { script_id, args } => {
+                let bytes = args.iter().map(|a| a.as_bytes().len()).sum();
+                let fuel = self.script_fuel.get(script_id).copied().unwrap_or(0);
+                self.invoke_script
+                    .saturating_add(self.payload(bytes))
+                    .saturating_add(fuel.div_ceil(self.fuel_per_unit.max(1)))
+            }
+            Slap::SetTime { .. } => self.set_time,
+            Slap::Collapse { .. } => self.collapse,
+            Slap::Transaction(transaction) => transaction
+                .slaps
+                .iter()
+                .fold(0u64, |total, slap| total.saturating_add(self.cost(slap))),
+        }
+    }
+}
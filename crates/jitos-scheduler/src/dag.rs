@@ -0,0 +1,147 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dependency Graph
+//!
+//! The conflicts between a set of proposals, oriented by the order the
+//! scheduler visited them in. An edge `from -> to` means the two footprints
+//! conflict and `from` was offered a batch slot first: `to` can only run in a
+//! later batch (or tick) than `from`. Visit order is total, so the graph is
+//! acyclic.
+//!
+//! The graph is canonical — nodes ascending by hash, edges ascending by
+//! `(from, to)` — so its [`DependencyGraph::digest`] can be recorded in
+//! receipts alongside the schedule it explains.
+
+use crate::report::{Placement, ScheduleReport};
+use crate::ConflictKind;
+use jitos_core::{canonical, Hash};
+use serde::Serialize;
+use std::fmt::Write as _;
+
+/// A proposal in the dependency graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyNode {
+    /// Canonical SLAP hash.
+    pub hash: Hash,
+    /// Position in visit order.
+    pub rank: usize,
+    /// Batch it was admitted to; `None` if deferred.
+    pub batch: Option<usize>,
+}
+
+/// `to` conflicts with, and was visited after, `from`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyEdge {
+    pub from: Hash,
+    pub to: Hash,
+    /// `from`'s footprint against `to`'s.
+    pub conflict: ConflictKind,
+}
+
+/// Conflict DAG over one scheduling pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DependencyGraph {
+    /// Ascending by hash; a SLAP proposed twice appears once.
+    pub nodes: Vec<DependencyNode>,
+    /// Ascending by `(from, to)`.
+    pub edges: Vec<DependencyEdge>,
+}
+
+impl DependencyGraph {
+    /// Build the graph from an explained scheduling pass.
+    pub fn from_report(report: &ScheduleReport) -> Self {
+        let mut nodes: Vec<DependencyNode> = report
+            .proposals
+            .iter()
+            .map(|p| DependencyNode {
+                hash: p.key.hash,
+                rank: p.rank,
+                batch: match p.placement {
+                    Placement::Batch(b) => Some(b),
+                    Placement::Deferred(_) => None,
+                },
+            })
+            .collect();
+        // Keep the first-visited copy of a SLAP proposed twice.
+        nodes.sort_by_key(|n| (n.hash, n.rank));
+        nodes.dedup_by_key(|n| n.hash);
+        let mut graph = Self {
+            nodes,
+            edges: Vec::new(),
+        };
+
+        for p in &report.proposals {
+            let Some(ours) = graph.node(&p.key.hash).map(|n| n.rank) else {
+                continue;
+            };
+            for (other, conflict) in &p.conflicts {
+                // Each conflicting pair is listed from both sides; keep the
+                // side visited first.
+                if graph.node(other).is_some_and(|n| n.rank > ours) {
+                    graph.edges.push(DependencyEdge {
+                        from: p.key.hash,
+                        to: *other,
+                        conflict: conflict.clone(),
+                    });
+                }
+            }
+        }
+        graph.edges.sort_by_key(|e| (e.from, e.to));
+        graph.edges.dedup_by_key(|e| (e.from, e.to));
+        graph
+    }
+
+    /// Node for the SLAP with canonical hash `hash`.
+    pub fn node(&self, hash: &Hash) -> Option<&DependencyNode> {
+        self.nodes
+            .binary_search_by_key(hash, |n| n.hash)
+            .ok()
+            .map(|i| &self.nodes[i])
+    }
+
+    /// Proposals that must go before `hash`.
+    pub fn predecessors<'a>(&'a self, hash: &'a Hash) -> impl Iterator<Item = &'a Hash> + 'a {
+        self.edges
+            .iter()
+            .filter(move |e| e.to == *hash)
+            .map(|e| &e.from)
+    }
+
+    /// Proposals that must go after `hash`.
+    pub fn successors<'a>(&'a self, hash: &'a Hash) -> impl Iterator<Item = &'a Hash> + 'a {
+        self.edges
+            .iter()
+            .filter(move |e| e.from == *hash)
+            .map(|e| &e.to)
+    }
+
+    /// Canonical hash of the graph, for receipts.
+    pub fn digest(&self) -> Hash {
+        // Hashes, integers and footprint enums always encode.
+        canonical::hash_canonical(&("dependency-graph-v0", self))
+            .expect("dependency graph encoding")
+    }
+
+    /// Graphviz rendering; nodes are labelled by short hash and batch.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph dependencies {\n");
+        for n in &self.nodes {
+            let placement = n
+                .batch
+                .map_or("deferred".to_string(), |b| format!("batch {b}"));
+            let _ = writeln!(
+                out,
+                "  \"{}\" [label=\"{}\\n{}\"];",
+                n.hash,
+                &n.hash.to_string()[..12],
+                placement
+            );
+        }
+        for e in &self.edges {
+            let _ = writeln!(out, "  \"{}\" -> \"{}\";", e.from, e.to);
+        }
+        out.push_str("}\n");
+        out
+    }
+}
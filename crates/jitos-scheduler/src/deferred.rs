@@ -0,0 +1,87 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deferred-Conflict Retry Queue
+//!
+//! SLAPs that lose a footprint conflict are not dropped: they wait in the
+//! [`DeferredQueue`] and are re-proposed on every following tick. Each tick an
+//! entry waits, its age grows by one, and the scheduler packs older entries
+//! before younger ones (then by the policy's ordering rules, then by hash).
+//! The oldest waiting SLAP is therefore always admitted, and nothing proposed
+//! later can overtake an entry, so every deferred SLAP runs after finitely
+//! many ticks.
+//!
+//! The queue's content is canonical — entries keyed by SLAP hash — so its
+//! [`DeferredQueue::state_hash`] can be recorded in receipts.
+
+use crate::ConflictReason;
+use jitos_core::{canonical, Hash, Slap, SlapMeta};
+use std::collections::BTreeMap;
+
+/// A SLAP waiting for a later tick.
+#[derive(Debug, Clone)]
+pub struct DeferredEntry {
+    pub slap: Slap,
+    /// Metadata the SLAP was first proposed with.
+    pub meta: SlapMeta,
+    /// Ticks spent waiting (1 after the first deferral).
+    pub age: u32,
+    /// Why it was deferred most recently.
+    pub reason: ConflictReason,
+}
+
+/// Deferred SLAPs, keyed by canonical hash.
+#[derive(Debug, Clone, Default)]
+pub struct DeferredQueue {
+    entries: BTreeMap<Hash, DeferredEntry>,
+}
+
+impl DeferredQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entry for the SLAP with canonical hash `hash`.
+    pub fn get(&self, hash: &Hash) -> Option<&DeferredEntry> {
+        self.entries.get(hash)
+    }
+
+    /// Entries in canonical hash order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Hash, &DeferredEntry)> {
+        self.entries.iter()
+    }
+
+    /// Age of the entry for `hash`, or 0 if it is not waiting.
+    pub fn age(&self, hash: &Hash) -> u32 {
+        self.entries.get(hash).map_or(0, |e| e.age)
+    }
+
+    /// Canonical queue state: `(slap hash, age)` pairs, ascending by hash.
+    pub fn state(&self) -> Vec<(Hash, u32)> {
+        self.entries.iter().map(|(h, e)| (*h, e.age)).collect()
+    }
+
+    /// Hash of [`Self::state`], for receipts.
+    pub fn state_hash(&self) -> Hash {
+        // Hashes and integers always encode.
+        canonical::hash_canonical(&("deferred-queue-v0", self.state()))
+            .expect("deferred queue encoding")
+    }
+
+    /// Remove every entry, returning them in hash order.
+    pub(crate) fn drain(&mut self) -> BTreeMap<Hash, DeferredEntry> {
+        std::mem::take(&mut self.entries)
+    }
+
+    pub(crate) fn insert(&mut self, hash: Hash, entry: DeferredEntry) {
+        self.entries.insert(hash, entry);
+    }
+}
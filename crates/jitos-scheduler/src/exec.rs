@@ -0,0 +1,333 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Batch Execution
+//!
+//! Applies a scheduled batch of SLAPs to a [`WarpGraph`].
+//!
+//! Members of a batch have pairwise independent footprints, so each SLAP's
+//! effect can be resolved against the pre-batch graph without seeing the
+//! others. [`execute_batch`] does that resolution in parallel (rayon), one
+//! task per SLAP, then commits the resulting mutations in canonical hash
+//! order. [`execute_sequential`] applies the SLAPs one after another, each
+//! seeing its predecessors' writes; it is the reference semantics. In debug
+//! builds `execute_batch` checks that both produce the same commit digest.
+//!
+//! New node ids come from a [`DeterministicIdAllocator`] in the `"slap"`
+//! domain, keyed by the batch's SLAP hashes, so they do not depend on
+//! execution order either.
+//!
+//! A transaction is planned as one SLAP: its members resolve in order, each
+//! seeing the nodes the earlier ones created and deleted, and its new nodes
+//! are allocated under the transaction's hash. If any member fails, the
+//! whole batch does.
+//!
+//! Only graph SLAPs are executable here: `SetTime` is a no-op on the graph
+//! (the runtime records it as logical time for the clock view), and
+//! `InvokeScript` / `Collapse` are rejected (collapse an SWS through
+//! [`jitos_graph::SwsRegistry::collapse`] instead), as are nested
+//! transactions.
+
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::{Hash, Slap, Transaction};
+use jitos_graph::rewrite::GraphChange;
+use jitos_graph::{DeterministicIdAllocator, NodeId, WarpEdge, WarpGraph, WarpNode};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// Allocation domain for nodes created by SLAPs.
+pub const SLAP_ID_DOMAIN: &str = "slap";
+
+/// Batch execution errors.
+#[derive(Debug, Error)]
+pub enum ExecError {
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("SLAP {slap} references unknown node {node}")]
+    UnknownNode { slap: Hash, node: NodeId },
+    #[error("SLAP {slap} ({op}) cannot be executed on the graph")]
+    Unsupported { slap: Hash, op: &'static str },
+}
+
+/// A primitive graph write, resolved by NodeId.
+#[derive(Debug, Clone)]
+enum Mutation {
+    AddNode(WarpNode),
+    RemoveNode(NodeId),
+    AddEdge {
+        from: NodeId,
+        to: NodeId,
+        edge_type: String,
+    },
+}
+
+/// Apply `batch` in parallel and return the new commit digest.
+///
+/// `batch` must be conflict-free (one batch of a [`crate::Schedule`]); if it
+/// is not, debug builds panic when the parallel result diverges from
+/// [`execute_sequential`]. On error the graph is left unchanged.
+pub fn execute_batch(graph: &mut WarpGraph, batch: &[Slap]) -> Result<Hash, ExecError> {
+    apply_batch(graph, batch, None)
+}
+
+/// [`execute_batch`], also returning the changes it made, in the order it
+/// made them.
+///
+/// A node deleted by the batch also loses its incident edges; that is
+/// implied by its [`GraphChange::NodeRemoved`] rather than listed.
+pub fn execute_batch_logged(
+    graph: &mut WarpGraph,
+    batch: &[Slap],
+) -> Result<(Hash, Vec<GraphChange>), ExecError> {
+    let mut changes = Vec::new();
+    let digest = apply_batch(graph, batch, Some(&mut changes))?;
+    Ok((digest, changes))
+}
+
+fn apply_batch(
+    graph: &mut WarpGraph,
+    batch: &[Slap],
+    mut changes: Option<&mut Vec<GraphChange>>,
+) -> Result<Hash, ExecError> {
+    let hashes = batch
+        .par_iter()
+        .map(canonical::hash_canonical)
+        .collect::<Result<Vec<_>, _>>()?;
+    let alloc = DeterministicIdAllocator::new_for_tick_in_domain(SLAP_ID_DOMAIN, &hashes);
+
+    let base: &WarpGraph = graph;
+    let mut planned = batch
+        .par_iter()
+        .zip(hashes.par_iter())
+        .map(|(slap, hash)| Ok((*hash, plan(base, slap, *hash, &mut alloc.clone())?)))
+        .collect::<Result<Vec<_>, ExecError>>()?;
+    planned.sort_by_key(|(hash, _)| *hash);
+
+    #[cfg(debug_assertions)]
+    let expected = {
+        let mut reference = graph.clone();
+        execute_sequential(&mut reference, batch)
+    };
+
+    for (_, mutations) in planned {
+        commit(graph, mutations, changes.as_deref_mut());
+    }
+    let digest = graph.compute_hash_checked()?;
+
+    #[cfg(debug_assertions)]
+    {
+        let expected = expected.expect(
+            "sequential application failed where parallel succeeded; batch is not conflict-free",
+        );
+        assert_eq!(
+            digest, expected,
+            "parallel batch diverged from sequential application; batch is not conflict-free"
+        );
+    }
+
+    Ok(digest)
+}
+
+/// Apply `batch` one SLAP at a time, in canonical hash order, and return the
+/// new commit digest. On error the graph may be partially updated.
+pub fn execute_sequential(graph: &mut WarpGraph, batch: &[Slap]) -> Result<Hash, ExecError> {
+    let mut ordered = batch
+        .iter()
+        .map(|slap| Ok((canonical::hash_canonical(slap)?, slap)))
+        .collect::<Result<Vec<_>, ExecError>>()?;
+    ordered.sort_by_key(|(hash, _)| *hash);
+    let hashes: Vec<Hash> = ordered.iter().map(|(hash, _)| *hash).collect();
+    let mut alloc = DeterministicIdAllocator::new_for_tick_in_domain(SLAP_ID_DOMAIN, &hashes);
+
+    for (hash, slap) in ordered {
+        let mutations = plan(graph, slap, hash, &mut alloc)?;
+        commit(graph, mutations, None);
+    }
+    Ok(graph.compute_hash_checked()?)
+}
+
+/// Resolve `slap` against `graph` without modifying it.
+fn plan(
+    graph: &WarpGraph,
+    slap: &Slap,
+    hash: Hash,
+    alloc: &mut DeterministicIdAllocator,
+) -> Result<Vec<Mutation>, ExecError> {
+    let existing = |id: &NodeId| -> Result<NodeId, ExecError> {
+        match graph.node_key(id) {
+            Some(_) => Ok(*id),
+            None => Err(ExecError::UnknownNode {
+                slap: hash,
+                node: *id,
+            }),
+        }
+    };
+
+    Ok(match slap {
+        Slap::CreateNode {
+            node_type,
+            payload_bytes,
+        } => vec![Mutation::AddNode(WarpNode {
+            id: alloc.alloc_node_id(hash),
+            node_type: node_type.clone(),
+            payload_bytes: payload_bytes.clone(),
+            attachment: None,
+        })],
+        Slap::DeleteNode { id } => vec![Mutation::RemoveNode(existing(id)?)],
+        Slap::Connect {
+            source,
+            target,
+            edge_type,
+        } => vec![Mutation::AddEdge {
+            from: existing(source)?,
+            to: existing(target)?,
+            edge_type: edge_type.clone(),
+        }],
+        Slap::SetTime { .. } => vec![],
+        Slap::InvokeScript { .. } => {
+            return Err(ExecError::Unsupported {
+                slap: hash,
+                op: "InvokeScript",
+            })
+        }
+        Slap::Collapse { .. } => {
+            return Err(ExecError::Unsupported {
+                slap: hash,
+                op: "Collapse",
+            })
+        }
+        Slap::Transaction(transaction) => plan_transaction(graph, transaction, hash, alloc)?,
+    })
+}
+
+/// Resolve the members of the transaction `hash` in order, against `graph`
+/// plus the writes of the members before each one.
+fn plan_transaction(
+    graph: &WarpGraph,
+    transaction: &Transaction,
+    hash: Hash,
+    alloc: &mut DeterministicIdAllocator,
+) -> Result<Vec<Mutation>, ExecError> {
+    // Placeholder -> allocated id, for `Transaction::created`.
+    let mut created: HashMap<NodeId, NodeId> = HashMap::new();
+    let mut added: HashSet<NodeId> = HashSet::new();
+    let mut removed: HashSet<NodeId> = HashSet::new();
+    let mut mutations = Vec::new();
+    for (index, member) in transaction.slaps.iter().enumerate() {
+        let resolve = |id: &NodeId| -> Result<NodeId, ExecError> {
+            let id = created.get(id).copied().unwrap_or(*id);
+            let exists =
+                added.contains(&id) || (graph.node_key(&id).is_some() && !removed.contains(&id));
+            if !exists {
+                return Err(ExecError::UnknownNode {
+                    slap: hash,
+                    node: id,
+                });
+            }
+            Ok(id)
+        };
+        match member {
+            Slap::CreateNode {
+                node_type,
+                payload_bytes,
+            } => {
+                let id = alloc.alloc_node_id(hash);
+                created.insert(Transaction::created(index), id);
+                added.insert(id);
+                mutations.push(Mutation::AddNode(WarpNode {
+                    id,
+                    node_type: node_type.clone(),
+                    payload_bytes: payload_bytes.clone(),
+                    attachment: None,
+                }));
+            }
+            Slap::DeleteNode { id } => {
+                let id = resolve(id)?;
+                added.remove(&id);
+                removed.insert(id);
+                mutations.push(Mutation::RemoveNode(id));
+            }
+            Slap::Connect {
+                source,
+                target,
+                edge_type,
+            } => mutations.push(Mutation::AddEdge {
+                from: resolve(source)?,
+                to: resolve(target)?,
+                edge_type: edge_type.clone(),
+            }),
+            Slap::Transaction(_) => {
+                return Err(ExecError::Unsupported {
+                    slap: hash,
+                    op: "nested Transaction",
+                })
+            }
+            other => mutations.extend(plan(graph, other, hash, alloc)?),
+        }
+    }
+    Ok(mutations)
+}
+
+/// Apply planned mutations, logging each one made to `changes`. Plans only
+/// name nodes that exist, so every lookup succeeds for a conflict-free batch.
+fn commit(
+    graph: &mut WarpGraph,
+    mutations: Vec<Mutation>,
+    mut changes: Option<&mut Vec<GraphChange>>,
+) {
+    for mutation in mutations {
+        if let Some(changes) = changes.as_deref_mut() {
+            changes.extend(change(graph, &mutation));
+        }
+        match mutation {
+            Mutation::AddNode(node) => {
+                graph.insert_node(node);
+            }
+            Mutation::RemoveNode(id) => {
+                if let Some(key) = graph.node_key(&id) {
+                    graph.remove_node(key);
+                }
+            }
+            Mutation::AddEdge {
+                from,
+                to,
+                edge_type,
+            } => {
+                if let (Some(source), Some(target)) = (graph.node_key(&from), graph.node_key(&to)) {
+                    graph.edges.insert(WarpEdge {
+                        source,
+                        target,
+                        edge_type,
+                        payload_bytes: None,
+                        attachment: None,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// The change `mutation` makes to `graph`, if any.
+fn change(graph: &WarpGraph, mutation: &Mutation) -> Option<GraphChange> {
+    match mutation {
+        Mutation::AddNode(node) => Some(GraphChange::NodeAdded {
+            id: node.id,
+            node_type: node.node_type.clone(),
+        }),
+        Mutation::RemoveNode(id) => graph
+            .node_key(id)
+            .map(|_| GraphChange::NodeRemoved { id: *id }),
+        Mutation::AddEdge {
+            from,
+            to,
+            edge_type,
+        } => (graph.node_key(from).is_some() && graph.node_key(to).is_some()).then(|| {
+            GraphChange::EdgeAdded {
+                from: *from,
+                to: *to,
+                edge_type: edge_type.clone(),
+            }
+        }),
+    }
+}
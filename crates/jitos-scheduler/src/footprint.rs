@@ -0,0 +1,300 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! SLAP Footprints
+//!
+//! A footprint is the set of graph resources a SLAP reads and writes. Keys are
+//! namespaced strings:
+//! - `node:<id>` — a node, by the id the SLAP names it with
+//! - `node:new:<slap-hash>` — the node a `CreateNode` will allocate
+//! - `edge:<from>-><to>:<edge_type>` — an edge by endpoints and type
+//! - `sys:time`, `sws:<id>` — system resources
+//! - `obs:<type>` — emitting observations of a type (a node-class write)
+//! - a key ending in `*` covers every key with that prefix (`node:*` is all
+//!   nodes); unknown effects are declared this way, conservatively
+//! - a key `lo..hi` covers the half-open key interval `[lo, hi)`
+//!
+//! Two footprints conflict when a write set of one overlaps a read or write
+//! set of the other, in the same resource class (nodes or edges).
+//!
+//! Sets are kept sorted and deduplicated so footprints compare and hash
+//! deterministically.
+
+use jitos_graph::antichain::Independent;
+use jitos_graph::{NodeId, TouchedEdge, TxnFootprint};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Every node.
+pub const ALL_NODES: &str = "node:*";
+/// Every edge.
+pub const ALL_EDGES: &str = "edge:*";
+/// Logical time.
+pub const SYS_TIME: &str = "sys:time";
+
+/// Resource class a conflict occurred in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Resource {
+    Node,
+    Edge,
+}
+
+/// Why two footprints cannot be co-scheduled.
+///
+/// `ours`/`theirs` are the overlapping keys from the receiver and the argument
+/// of [`Footprint::conflicts_with`] (they differ when a wildcard is involved).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConflictKind {
+    /// Both write the resource.
+    WriteWrite {
+        resource: Resource,
+        ours: String,
+        theirs: String,
+    },
+    /// We read what they write.
+    ReadWrite {
+        resource: Resource,
+        ours: String,
+        theirs: String,
+    },
+    /// We write what they read.
+    WriteRead {
+        resource: Resource,
+        ours: String,
+        theirs: String,
+    },
+}
+
+/// Footprint of a SLAP operation (Read/Write sets).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Footprint {
+    pub n_read: Vec<String>,
+    pub n_write: Vec<String>,
+    pub e_read: Vec<String>,
+    pub e_write: Vec<String>,
+}
+
+impl Footprint {
+    /// Footprint that conflicts with everything: writes all nodes and edges.
+    pub fn global() -> Self {
+        Self {
+            n_write: vec![ALL_NODES.to_string()],
+            e_write: vec![ALL_EDGES.to_string()],
+            ..Self::default()
+        }
+    }
+
+    /// True if the footprint touches nothing.
+    pub fn is_empty(&self) -> bool {
+        self.n_read.is_empty()
+            && self.n_write.is_empty()
+            && self.e_read.is_empty()
+            && self.e_write.is_empty()
+    }
+
+    /// Sort and deduplicate every set.
+    pub fn normalize(&mut self) {
+        for set in [
+            &mut self.n_read,
+            &mut self.n_write,
+            &mut self.e_read,
+            &mut self.e_write,
+        ] {
+            set.sort();
+            set.dedup();
+        }
+    }
+
+    /// First conflict between `self` and `other`, or `None` if they are independent.
+    ///
+    /// Checked in a fixed order — write/write, then read/write, then
+    /// write/read; nodes before edges; keys ascending — so the reported reason
+    /// is deterministic.
+    pub fn conflicts_with(&self, other: &Footprint) -> Option<ConflictKind> {
+        type Make = fn(Resource, String, String) -> ConflictKind;
+        let checks: [(Make, Resource, &[String], &[String]); 6] = [
+            (ww, Resource::Node, &self.n_write, &other.n_write),
+            (ww, Resource::Edge, &self.e_write, &other.e_write),
+            (rw, Resource::Node, &self.n_read, &other.n_write),
+            (rw, Resource::Edge, &self.e_read, &other.e_write),
+            (wr, Resource::Node, &self.n_write, &other.n_read),
+            (wr, Resource::Edge, &self.e_write, &other.e_read),
+        ];
+        checks
+            .into_iter()
+            .find_map(|(make, resource, ours, theirs)| {
+                first_overlap(ours, theirs).map(|(a, b)| make(resource, a.clone(), b.clone()))
+            })
+    }
+
+    /// Union `other` into `self` (result is normalized).
+    pub fn merge(&mut self, other: &Footprint) {
+        self.n_read.extend_from_slice(&other.n_read);
+        self.n_write.extend_from_slice(&other.n_write);
+        self.e_read.extend_from_slice(&other.e_read);
+        self.e_write.extend_from_slice(&other.e_write);
+        self.normalize();
+    }
+
+    /// True if reading `key` stays within the footprint: some read or write
+    /// key of the same class covers it.
+    ///
+    /// Coverage is containment, not overlap: `node:*` covers `node:a`, but
+    /// `node:a` does not cover `node:*`. A range covers only exact keys and
+    /// ranges inside it.
+    pub fn permits_read(&self, resource: Resource, key: &str) -> bool {
+        let (read, write) = match resource {
+            Resource::Node => (&self.n_read, &self.n_write),
+            Resource::Edge => (&self.e_read, &self.e_write),
+        };
+        covered(read, key) || covered(write, key)
+    }
+
+    /// True if some write key of the same class covers `key`.
+    pub fn permits_write(&self, resource: Resource, key: &str) -> bool {
+        match resource {
+            Resource::Node => covered(&self.n_write, key),
+            Resource::Edge => covered(&self.e_write, key),
+        }
+    }
+}
+
+/// Key for a node named `id`.
+pub fn node_key(id: impl fmt::Display) -> String {
+    format!("node:{id}")
+}
+
+/// Key for the edge `from -> to` of type `edge_type`.
+pub fn edge_key(from: impl fmt::Display, to: impl fmt::Display, edge_type: &str) -> String {
+    format!("edge:{from}->{to}:{edge_type}")
+}
+
+/// Key for emitting observations of `observation_type`.
+pub fn obs_key(observation_type: &str) -> String {
+    format!("obs:{observation_type}")
+}
+
+/// Key covering every key that starts with `prefix`.
+pub fn prefix_key(prefix: &str) -> String {
+    format!("{prefix}*")
+}
+
+/// Key covering the half-open interval `[lo, hi)`.
+pub fn range_key(lo: &str, hi: &str) -> String {
+    format!("{lo}..{hi}")
+}
+
+/// What a [`jitos_graph::GraphTxn`] actually touched, as a footprint, so it
+/// can be checked against declared or inferred ones.
+impl From<&TxnFootprint> for Footprint {
+    fn from(touched: &TxnFootprint) -> Self {
+        let node = |id: &NodeId| node_key(id);
+        let edge = |e: &TouchedEdge| edge_key(e.from, e.to, &e.edge_type);
+        let mut fp = Self {
+            n_read: touched.nodes_read.iter().map(node).collect(),
+            n_write: touched.nodes_written.iter().map(node).collect(),
+            e_read: touched.edges_read.iter().map(edge).collect(),
+            e_write: touched.edges_written.iter().map(edge).collect(),
+        };
+        fp.normalize();
+        fp
+    }
+}
+
+impl Independent for Footprint {
+    fn independent_of(&self, other: &Self) -> bool {
+        self.conflicts_with(other).is_none()
+    }
+}
+
+fn ww(resource: Resource, ours: String, theirs: String) -> ConflictKind {
+    ConflictKind::WriteWrite {
+        resource,
+        ours,
+        theirs,
+    }
+}
+
+fn rw(resource: Resource, ours: String, theirs: String) -> ConflictKind {
+    ConflictKind::ReadWrite {
+        resource,
+        ours,
+        theirs,
+    }
+}
+
+fn wr(resource: Resource, ours: String, theirs: String) -> ConflictKind {
+    ConflictKind::WriteRead {
+        resource,
+        ours,
+        theirs,
+    }
+}
+
+/// A footprint key, parsed.
+enum KeySet<'a> {
+    Exact(&'a str),
+    Prefix(&'a str),
+    Range(&'a str, &'a str),
+}
+
+impl<'a> KeySet<'a> {
+    fn parse(key: &'a str) -> Self {
+        if let Some(prefix) = key.strip_suffix('*') {
+            KeySet::Prefix(prefix)
+        } else if let Some((lo, hi)) = key.split_once("..") {
+            KeySet::Range(lo, hi)
+        } else {
+            KeySet::Exact(key)
+        }
+    }
+
+    fn overlaps(&self, other: &KeySet<'_>) -> bool {
+        use KeySet::*;
+        match (self, other) {
+            (Exact(a), Exact(b)) => a == b,
+            (Exact(s), Prefix(p)) | (Prefix(p), Exact(s)) => s.starts_with(p),
+            (Exact(s), Range(lo, hi)) | (Range(lo, hi), Exact(s)) => lo <= s && s < hi,
+            (Prefix(a), Prefix(b)) => a.starts_with(b) || b.starts_with(a),
+            // The smallest key with prefix `p` is `p` itself; keys with prefix
+            // `p` reach `lo` unless `lo` sorts after all of them.
+            (Prefix(p), Range(lo, hi)) | (Range(lo, hi), Prefix(p)) => {
+                p < hi && (lo <= p || lo.starts_with(p))
+            }
+            (Range(lo1, hi1), Range(lo2, hi2)) => lo1 < hi2 && lo2 < hi1,
+        }
+    }
+
+    /// True if every key in `other` is also in `self`.
+    fn covers(&self, other: &KeySet<'_>) -> bool {
+        use KeySet::*;
+        match (self, other) {
+            (Exact(a), Exact(b)) => a == b,
+            (Prefix(p), Exact(s)) | (Prefix(p), Prefix(s)) => s.starts_with(p),
+            (Range(lo, hi), Exact(s)) => lo <= s && s < hi,
+            (Range(lo1, hi1), Range(lo2, hi2)) => lo1 <= lo2 && hi2 <= hi1,
+            // Conservatively uncovered.
+            _ => false,
+        }
+    }
+}
+
+fn covered(keys: &[String], key: &str) -> bool {
+    keys.iter().any(|pattern| key_covers(pattern, key))
+}
+
+/// True if every key `key` denotes is also denoted by `pattern` (see
+/// [`Footprint::permits_read`] for how wildcards and ranges cover).
+pub fn key_covers(pattern: &str, key: &str) -> bool {
+    KeySet::parse(pattern).covers(&KeySet::parse(key))
+}
+
+fn first_overlap<'a>(ours: &'a [String], theirs: &'a [String]) -> Option<(&'a String, &'a String)> {
+    ours.iter().find_map(|a| {
+        let ka = KeySet::parse(a);
+        theirs
+            .iter()
+            .find(|b| ka.overlaps(&KeySet::parse(b)))
+            .map(|b| (a, b))
+    })
+}
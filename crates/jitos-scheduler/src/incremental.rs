@@ -0,0 +1,189 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Incremental Scheduling
+//!
+//! Between ticks most proposals are usually the same SLAPs again (deferred
+//! work carried over). In incremental mode the scheduler keeps, keyed by SLAP
+//! hash, each proposal's footprint and its conflicts with every other live
+//! proposal. A tick then only infers footprints for new SLAPs and only checks
+//! pairs that involve one; SLAPs that disappeared are dropped with their
+//! edges. Graph-dependent footprints (`DeleteNode`) are re-solved whenever
+//! the graph commit digest changes.
+//!
+//! If nothing changed — same proposals, same graph, same visit order — the
+//! previous batch assignment is reused outright.
+//!
+//! Incremental mode is an optimisation only: schedules are identical to the
+//! non-incremental scheduler's.
+
+use crate::{ConflictKind, ConflictReason, Footprint};
+use jitos_core::Hash;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Work counters for incremental mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IncrementalStats {
+    /// Passes that reused the previous batch assignment.
+    pub reused: u64,
+    /// Passes that re-ran batch assignment.
+    pub resolved: u64,
+    /// Footprints inferred for new (or graph-invalidated) SLAPs.
+    pub footprints_computed: u64,
+    /// Ordered SLAP pairs checked for conflicts.
+    pub pairs_checked: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    footprint: Footprint,
+    reads_graph: bool,
+    /// `self.conflicts_with(other)` for every conflicting live SLAP.
+    conflicts: BTreeMap<Hash, ConflictKind>,
+}
+
+/// Batch assignment of a pass. Indices are radix positions, which are the
+/// same in any later pass with the same visit order.
+#[derive(Debug, Clone)]
+pub(crate) struct Assignment {
+    /// SLAP hashes in visit order.
+    pub visit: Vec<Hash>,
+    pub chains: Vec<Vec<usize>>,
+    pub deferred: Vec<(usize, ConflictReason)>,
+}
+
+/// Conflict graph over the live proposals, keyed by SLAP hash.
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalState {
+    entries: HashMap<Hash, Entry>,
+    /// Graph digest graph-dependent entries were inferred against.
+    graph: Option<Hash>,
+    last: Option<Assignment>,
+    stats: IncrementalStats,
+}
+
+impl IncrementalState {
+    pub fn stats(&self) -> IncrementalStats {
+        self.stats
+    }
+
+    /// Number of live proposals tracked.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget everything (e.g. after the cost model or script access changed).
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.graph = None;
+        self.last = None;
+    }
+
+    /// Bring the conflict graph in line with `hashes` against the graph with
+    /// digest `graph_digest`. `footprint(i)` infers the footprint of
+    /// `hashes[i]` and whether it read the graph. Returns true if anything
+    /// changed.
+    pub(crate) fn update(
+        &mut self,
+        hashes: &[Hash],
+        graph_digest: impl Fn() -> Hash,
+        mut footprint: impl FnMut(usize) -> (Footprint, bool),
+    ) -> bool {
+        let live: HashSet<&Hash> = hashes.iter().collect();
+        let mut stale: Vec<Hash> = self
+            .entries
+            .keys()
+            .filter(|h| !live.contains(h))
+            .copied()
+            .collect();
+
+        if self.entries.values().any(|e| e.reads_graph) {
+            let digest = graph_digest();
+            if self.graph != Some(digest) {
+                stale.extend(
+                    self.entries
+                        .iter()
+                        .filter(|(h, e)| e.reads_graph && live.contains(h))
+                        .map(|(h, _)| *h),
+                );
+                self.graph = Some(digest);
+            }
+        }
+
+        let mut changed = !stale.is_empty();
+        for hash in &stale {
+            if let Some(entry) = self.entries.remove(hash) {
+                for other in entry.conflicts.keys() {
+                    if let Some(e) = self.entries.get_mut(other) {
+                        e.conflicts.remove(hash);
+                    }
+                }
+            }
+        }
+
+        for (i, hash) in hashes.iter().enumerate() {
+            if self.entries.contains_key(hash) {
+                continue;
+            }
+            changed = true;
+            let (fp, reads_graph) = footprint(i);
+            self.stats.footprints_computed += 1;
+            if reads_graph && self.graph.is_none() {
+                self.graph = Some(graph_digest());
+            }
+            let mut conflicts = BTreeMap::new();
+            for (other, entry) in self.entries.iter_mut() {
+                self.stats.pairs_checked += 2;
+                if let Some(kind) = fp.conflicts_with(&entry.footprint) {
+                    conflicts.insert(*other, kind);
+                }
+                if let Some(kind) = entry.footprint.conflicts_with(&fp) {
+                    entry.conflicts.insert(*hash, kind);
+                }
+            }
+            self.entries.insert(
+                *hash,
+                Entry {
+                    footprint: fp,
+                    reads_graph,
+                    conflicts,
+                },
+            );
+        }
+
+        if changed {
+            self.last = None;
+        }
+        changed
+    }
+
+    /// `ours.conflicts_with(theirs)` for two live SLAPs. A SLAP proposed
+    /// twice is checked against its own footprint.
+    pub(crate) fn conflict(&self, ours: &Hash, theirs: &Hash) -> Option<ConflictKind> {
+        let entry = self.entries.get(ours)?;
+        if ours == theirs {
+            return entry.footprint.conflicts_with(&entry.footprint);
+        }
+        entry.conflicts.get(theirs).cloned()
+    }
+
+    /// Previous assignment, if it was made for exactly this visit order.
+    pub(crate) fn reusable(&mut self, visit: &[Hash]) -> Option<Assignment> {
+        let last = self
+            .last
+            .as_ref()
+            .filter(|a| a.visit.as_slice() == visit)?
+            .clone();
+        self.stats.reused += 1;
+        Some(last)
+    }
+
+    pub(crate) fn record(&mut self, assignment: Assignment) {
+        self.stats.resolved += 1;
+        self.last = Some(assignment);
+    }
+}
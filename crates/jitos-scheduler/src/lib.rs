@@ -1,19 +1,114 @@
 // @ts-check
-use jitos_core::Slap;
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventError, EventId};
+use jitos_core::{Hash, NodeId, Proposal, Slap, SlapMeta};
 use jitos_graph::WarpGraph;
+use serde::Serialize;
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
-/// Footprint of a SLAP operation (Read/Write sets).
-#[derive(Debug, Default, Clone)]
-pub struct Footprint {
-    pub n_read: Vec<String>,
-    pub n_write: Vec<String>,
-    pub e_read: Vec<String>,
-    pub e_write: Vec<String>,
+pub mod cache;
+pub mod cost;
+pub mod dag;
+pub mod deferred;
+pub mod exec;
+pub mod footprint;
+pub mod incremental;
+pub mod policy;
+pub mod radix;
+pub mod report;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use cache::{CacheKey, CacheStats, FootprintCache};
+pub use cost::{CostModel, DefaultCostModel};
+pub use dag::{DependencyEdge, DependencyGraph, DependencyNode};
+pub use deferred::{DeferredEntry, DeferredQueue};
+pub use exec::{execute_batch, execute_batch_logged, execute_sequential, ExecError};
+use footprint::{edge_key, node_key, ALL_EDGES, ALL_NODES, SYS_TIME};
+pub use footprint::{ConflictKind, Footprint, Resource};
+use incremental::Assignment;
+pub use incremental::{IncrementalState, IncrementalStats};
+pub use policy::{BatchingStrategy, ScheduleDecision, SchedulerPolicy, TieBreak};
+use radix::permute_by_hash;
+pub use radix::{radix_order, RadixOrder};
+pub use report::{OrderingKey, Placement, ProposalReport, ScheduleReport};
+
+/// Why a SLAP was not admitted to any batch this tick.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum ConflictReason {
+    /// Its footprint conflicts with an admitted SLAP in the last batch.
+    Conflict {
+        /// Canonical hash of the admitted SLAP.
+        blocked_by: Hash,
+        conflict: ConflictKind,
+    },
+    /// The tick budget ran out before it could be admitted.
+    OverBudget {
+        /// Cost of this SLAP.
+        cost: u64,
+        /// Budget left when filling stopped.
+        remaining: u64,
+    },
+    /// Its agent's share of the tick was used up by the agent's other SLAPs.
+    AgentOverBudget {
+        agent: AgentId,
+        /// Cost of this SLAP.
+        cost: u64,
+        /// Agent budget left when it was considered.
+        remaining: u64,
+    },
+}
+
+impl ConflictReason {
+    /// The admitted SLAP this one conflicts with, if deferred by a conflict.
+    pub fn blocked_by(&self) -> Option<Hash> {
+        match self {
+            ConflictReason::Conflict { blocked_by, .. } => Some(*blocked_by),
+            ConflictReason::OverBudget { .. } | ConflictReason::AgentOverBudget { .. } => None,
+        }
+    }
+
+    /// The footprint conflict, if deferred by one.
+    pub fn conflict(&self) -> Option<&ConflictKind> {
+        match self {
+            ConflictReason::Conflict { conflict, .. } => Some(conflict),
+            ConflictReason::OverBudget { .. } | ConflictReason::AgentOverBudget { .. } => None,
+        }
+    }
+}
+
+/// Output of one scheduling pass.
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    /// Conflict-free independent sets, to be applied in order. Each batch is
+    /// sorted by canonical hash.
+    pub batches: Vec<Vec<Slap>>,
+    /// SLAPs left for a later tick, sorted by canonical hash.
+    pub deferred: Vec<(Slap, ConflictReason)>,
 }
 
 /// The Echo Radix Scheduler (Paper II).
+///
+/// A clone shares the cost model and copies everything else, so it can
+/// schedule (or explain) without touching the original's deferred queue or
+/// caches.
+#[derive(Clone)]
 pub struct EchoScheduler {
-    pub footprint_cache: std::collections::HashMap<String, Footprint>,
+    /// Inferred footprints, keyed by SLAP hash (and graph digest where it matters).
+    footprint_cache: FootprintCache,
+    /// Access declared by scripts, keyed by script id.
+    script_access: HashMap<Hash, Footprint>,
+    policy: SchedulerPolicy,
+    /// PolicyContext event publishing `policy`.
+    policy_context: EventEnvelope,
+    /// SLAPs carried over from earlier ticks by [`Self::tick`].
+    deferred: DeferredQueue,
+    /// Prices SLAPs against the policy's tick budget.
+    cost_model: Arc<dyn CostModel + Send + Sync>,
+    /// Conflict graph carried between passes, when incremental mode is on.
+    incremental: Option<IncrementalState>,
 }
 
 impl Default for EchoScheduler {
@@ -24,16 +119,695 @@ impl Default for EchoScheduler {
 
 impl EchoScheduler {
     pub fn new() -> Self {
+        Self::with_policy(SchedulerPolicy::default())
+    }
+
+    /// Scheduler following `policy`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the policy allows zero batches per tick.
+    pub fn with_policy(policy: SchedulerPolicy) -> Self {
+        assert!(policy.max_batches() > 0, "max_batches must be non-zero");
+        let policy_context = policy
+            .to_policy_context()
+            .expect("scheduler policy encoding");
         Self {
-            footprint_cache: std::collections::HashMap::new(),
+            footprint_cache: FootprintCache::default(),
+            script_access: HashMap::new(),
+            policy,
+            policy_context,
+            deferred: DeferredQueue::new(),
+            cost_model: Arc::new(DefaultCostModel::default()),
+            incremental: None,
+        }
+    }
+
+    /// Default policy, but emitting up to `max_batches` sequential batches per tick.
+    pub fn with_max_batches(max_batches: u32) -> Self {
+        Self::with_policy(SchedulerPolicy {
+            batching: BatchingStrategy::Antichains { max_batches },
+            ..SchedulerPolicy::default()
+        })
+    }
+
+    pub fn policy(&self) -> &SchedulerPolicy {
+        &self.policy
+    }
+
+    /// The PolicyContext event every scheduling Decision references.
+    pub fn policy_context(&self) -> &EventEnvelope {
+        &self.policy_context
+    }
+
+    /// Replace the cost model used against the policy's `tick_budget`.
+    pub fn set_cost_model(&mut self, model: impl CostModel + Send + Sync + 'static) {
+        self.cost_model = Arc::new(model);
+        if let Some(state) = self.incremental.as_mut() {
+            state.clear();
+        }
+    }
+
+    /// Turn incremental mode on or off (see [`incremental`]). Turning it off
+    /// drops the carried state.
+    pub fn set_incremental(&mut self, enabled: bool) {
+        if enabled != self.incremental.is_some() {
+            self.incremental = enabled.then(IncrementalState::default);
+        }
+    }
+
+    /// Incremental-mode state, if enabled.
+    pub fn incremental(&self) -> Option<&IncrementalState> {
+        self.incremental.as_ref()
+    }
+
+    /// Cost of `slap` under the current cost model.
+    pub fn cost(&self, slap: &Slap) -> u64 {
+        self.cost_model.cost(slap)
+    }
+
+    /// SLAPs waiting for a later tick.
+    pub fn deferred(&self) -> &DeferredQueue {
+        &self.deferred
+    }
+
+    /// Put back a queue taken from [`Self::deferred`], undoing what later
+    /// ticks deferred (for a caller that could not apply their schedules).
+    pub fn restore_deferred(&mut self, queue: DeferredQueue) {
+        self.deferred = queue;
+    }
+
+    /// Declare what a script reads and writes.
+    ///
+    /// `InvokeScript` of an undeclared script gets [`Footprint::global`].
+    pub fn declare_script_access(&mut self, script_id: Hash, mut access: Footprint) {
+        access.normalize();
+        self.script_access.insert(script_id, access);
+        // Cached InvokeScript footprints may have used the old declaration.
+        self.footprint_cache.clear();
+        if let Some(state) = self.incremental.as_mut() {
+            state.clear();
+        }
+    }
+
+    pub fn footprint_cache(&self) -> &FootprintCache {
+        &self.footprint_cache
+    }
+
+    /// Bound the footprint cache (see [`FootprintCache::set_capacity`]).
+    pub fn set_footprint_cache_capacity(&mut self, capacity: usize) {
+        self.footprint_cache.set_capacity(capacity);
+    }
+
+    /// Infer the read/write footprint of `slap` against `graph`.
+    ///
+    /// `DeleteNode` also writes every edge currently incident to the node, so
+    /// its footprint is cached per graph commit digest; every other footprint
+    /// is determined by the SLAP alone and cached by its canonical hash. Node
+    /// keys name nodes by NodeId hex. A transaction's footprint is the union
+    /// of its members', so it is scheduled (or deferred) as one unit.
+    pub fn footprint(
+        &mut self,
+        slap: &Slap,
+        graph: &WarpGraph,
+    ) -> Result<Footprint, CanonicalError> {
+        let slap_hash = canonical::hash_canonical(slap)?;
+        Ok(self.footprint_for(slap, slap_hash, &LazyDigest::new(graph)))
+    }
+
+    fn footprint_for(&mut self, slap: &Slap, slap_hash: Hash, graph: &LazyDigest<'_>) -> Footprint {
+        let key = CacheKey {
+            slap: slap_hash,
+            graph: reads_graph(slap).then(|| graph.digest()),
+        };
+        if let Some(fp) = self.footprint_cache.get(&key) {
+            return fp.clone();
+        }
+
+        let mut fp = match slap {
+            Slap::Transaction(transaction) => {
+                let mut fp = Footprint::default();
+                for member in &transaction.slaps {
+                    let member_hash = canonical::hash_canonical(member)
+                        .expect("SLAPs are always canonically encodable");
+                    fp.merge(&self.footprint_for(member, member_hash, graph));
+                }
+                fp
+            }
+            _ => self.infer(slap, &slap_hash),
+        };
+        if let Slap::DeleteNode { id } = slap {
+            let incident = incident_edge_keys(graph.graph, id);
+            if !incident.is_empty() {
+                fp.e_write.extend(incident);
+                fp.normalize();
+            }
+        }
+        self.footprint_cache.insert(key, fp.clone());
+        fp
+    }
+
+    fn infer(&self, slap: &Slap, slap_hash: &Hash) -> Footprint {
+        let mut fp = Footprint::default();
+        match slap {
+            Slap::CreateNode { .. } => {
+                fp.n_write.push(node_key(format_args!("new:{slap_hash}")));
+            }
+            Slap::DeleteNode { id } => {
+                fp.n_write.push(node_key(id));
+            }
+            Slap::Connect {
+                source,
+                target,
+                edge_type,
+            } => {
+                fp.n_read.push(node_key(source));
+                fp.n_read.push(node_key(target));
+                fp.e_write.push(edge_key(source, target, edge_type));
+            }
+            Slap::InvokeScript { script_id, .. } => {
+                fp = self
+                    .script_access
+                    .get(script_id)
+                    .cloned()
+                    .unwrap_or_else(Footprint::global);
+            }
+            Slap::SetTime { .. } => {
+                fp.n_write.push(SYS_TIME.to_string());
+            }
+            Slap::Collapse { sws_id } => {
+                // Collapsing publishes an overlay into the system graph; its
+                // contents are not visible here, so assume it touches everything.
+                fp.n_write.push(format!("sws:{sws_id}"));
+                fp.n_write.push(ALL_NODES.to_string());
+                fp.e_write.push(ALL_EDGES.to_string());
+            }
+            Slap::Transaction(_) => unreachable!("transactions are the union of their members"),
+        }
+        fp.normalize();
+        fp
+    }
+
+    /// Sorts and batches SLAPS into deterministic, independent execution sets.
+    ///
+    /// Proposals are visited by descending policy weight, ties broken by the
+    /// policy's [`TieBreak`] and finally Echo Radix order, and greedily packed
+    /// into antichains of non-conflicting footprints. The first `max_batches`
+    /// antichains are returned as batches (each sorted by hash); everything
+    /// else is deferred with the conflict that kept it out of the last batch.
+    /// The result depends only on the set of proposals, the graph and the
+    /// policy, never on arrival order.
+    ///
+    /// This does not touch the [`DeferredQueue`]; see [`Self::tick`].
+    pub fn schedule(&mut self, graph: &WarpGraph, proposals: Vec<Slap>) -> Schedule {
+        let RadixOrder { proposals, hashes } =
+            radix_order(proposals).expect("SLAPs are always canonically encodable");
+        let metas = vec![SlapMeta::default(); proposals.len()];
+        self.pack(graph, proposals, &hashes, &metas, |_| 0).0
+    }
+
+    /// [`Self::schedule`] for SLAPs with priority/deadline metadata.
+    pub fn schedule_proposals(&mut self, graph: &WarpGraph, proposals: Vec<Proposal>) -> Schedule {
+        let (proposals, metas, hashes) = order_proposals(proposals);
+        self.pack(graph, proposals, &hashes, &metas, |_| 0).0
+    }
+
+    /// Schedule one tick, retrying everything in the [`DeferredQueue`].
+    ///
+    /// Waiting SLAPs are scheduled together with `proposals`, older entries
+    /// first. Whatever is deferred this tick replaces the queue, one tick
+    /// older. Identical SLAPs (same canonical hash) are scheduled once.
+    pub fn tick(&mut self, graph: &WarpGraph, proposals: Vec<Slap>) -> Schedule {
+        self.tick_proposals(graph, proposals.into_iter().map(Proposal::from).collect())
+    }
+
+    /// [`Self::tick`] for SLAPs with priority/deadline metadata.
+    ///
+    /// A SLAP that is already waiting keeps the metadata it was deferred with.
+    pub fn tick_proposals(&mut self, graph: &WarpGraph, proposals: Vec<Proposal>) -> Schedule {
+        let waiting = self.deferred.drain();
+        let (proposals, metas, hashes, ages) = with_waiting(waiting, proposals);
+
+        let (schedule, deferred) = self.pack(graph, proposals, &hashes, &metas, |h| {
+            ages.get(h).copied().unwrap_or(0)
+        });
+
+        for ((slap, reason), i) in schedule.deferred.iter().zip(deferred) {
+            let hash = hashes[i];
+            let age = ages.get(&hash).copied().unwrap_or(0).saturating_add(1);
+            self.deferred.insert(
+                hash,
+                DeferredEntry {
+                    slap: slap.clone(),
+                    meta: metas[i].clone(),
+                    age,
+                    reason: reason.clone(),
+                },
+            );
         }
+        schedule
+    }
+
+    /// Pack radix-ordered proposals into batches. Also returns the radix
+    /// indices of the deferred SLAPs.
+    fn pack(
+        &mut self,
+        graph: &WarpGraph,
+        proposals: Vec<Slap>,
+        hashes: &[Hash],
+        metas: &[SlapMeta],
+        age: impl Fn(&Hash) -> u32,
+    ) -> (Schedule, Vec<usize>) {
+        let Packing {
+            chains, deferred, ..
+        } = self.assign(graph, &proposals, hashes, metas, age);
+
+        let deferred_indices = deferred.iter().map(|(i, _)| *i).collect();
+        let mut slots: Vec<Option<Slap>> = proposals.into_iter().map(Some).collect();
+        let mut take = |i: usize| slots[i].take().expect("each index appears once");
+        let schedule = Schedule {
+            batches: chains
+                .into_iter()
+                .map(|mut chain| {
+                    chain.sort_unstable();
+                    chain.into_iter().map(&mut take).collect()
+                })
+                .collect(),
+            deferred: deferred
+                .into_iter()
+                .map(|(i, reason)| (take(i), reason))
+                .collect(),
+        };
+        (schedule, deferred_indices)
     }
 
-    /// Sorts and batches SLAPS into a deterministic, independent execution set.
-    pub fn schedule(&self, _graph: &WarpGraph, proposals: Vec<Slap>) -> Vec<Slap> {
-        // 1. Sort by Hash (Radix Sort logic would go here)
+    /// Assign radix-ordered proposals to batches, visiting higher `age` first,
+    /// then by the policy's ordering rules.
+    fn assign(
+        &mut self,
+        graph: &WarpGraph,
+        proposals: &[Slap],
+        hashes: &[Hash],
+        metas: &[SlapMeta],
+        age: impl Fn(&Hash) -> u32,
+    ) -> Packing {
+        // 1. Order by age and policy; ties keep Echo Radix order
+        let mut visit: Vec<usize> = (0..proposals.len()).collect();
+        visit.sort_by_key(|&i| {
+            self.policy
+                .visit_key(age(&hashes[i]), &proposals[i], &metas[i])
+        });
+
         // 2. Check Footprint overlap
-        // 3. Return independent batch
-        proposals
+        let digest = LazyDigest::new(graph);
+        let Some(mut state) = self.incremental.take() else {
+            let footprints: Vec<Footprint> = proposals
+                .iter()
+                .zip(hashes)
+                .map(|(slap, hash)| self.footprint_for(slap, *hash, &digest))
+                .collect();
+            return self.pack_greedy(proposals, hashes, metas, visit, |i, m| {
+                footprints[i].conflicts_with(&footprints[m])
+            });
+        };
+
+        state.update(
+            hashes,
+            || digest.digest(),
+            |i| {
+                let slap = &proposals[i];
+                (
+                    self.footprint_for(slap, hashes[i], &digest),
+                    reads_graph(slap),
+                )
+            },
+        );
+        let visit_hashes: Vec<Hash> = visit.iter().map(|&i| hashes[i]).collect();
+        // Agent budgets depend on metadata, which the carried state does not key.
+        let reusable = match self.policy.agent_budget {
+            Some(_) => None,
+            None => state.reusable(&visit_hashes),
+        };
+        let packing = match reusable {
+            Some(previous) => Packing {
+                visit,
+                chains: previous.chains,
+                deferred: previous.deferred,
+            },
+            None => {
+                let packing = self.pack_greedy(proposals, hashes, metas, visit, |i, m| {
+                    state.conflict(&hashes[i], &hashes[m])
+                });
+                state.record(Assignment {
+                    visit: visit_hashes,
+                    chains: packing.chains.clone(),
+                    deferred: packing.deferred.clone(),
+                });
+                packing
+            }
+        };
+        self.incremental = Some(state);
+        packing
+    }
+
+    /// Greedy antichain packing in `visit` order, under the tick and agent
+    /// budgets. `conflict(i, m)` is `footprint(i).conflicts_with(footprint(m))`.
+    fn pack_greedy(
+        &self,
+        proposals: &[Slap],
+        hashes: &[Hash],
+        metas: &[SlapMeta],
+        visit: Vec<usize>,
+        conflict: impl Fn(usize, usize) -> Option<ConflictKind>,
+    ) -> Packing {
+        let mut order = visit.clone();
+        let mut chains: Vec<Vec<usize>> = Vec::new();
+        let mut remaining = self.policy.tick_budget;
+        // Budget left when filling stopped, and everything not yet admitted then.
+        let mut stopped: Option<u64> = None;
+        let mut over_budget: Vec<usize> = Vec::new();
+        // Budget left per agent, once the agent has been admitted anything.
+        let mut agent_left: HashMap<&AgentId, u64> = HashMap::new();
+        let mut agent_over: Vec<(usize, ConflictReason)> = Vec::new();
+        while !order.is_empty() && chains.len() < self.policy.max_batches() && stopped.is_none() {
+            let mut chain: Vec<usize> = Vec::new();
+            let first_chain = chains.is_empty();
+            order.retain(|&i| {
+                if stopped.is_some() {
+                    over_budget.push(i);
+                    return false;
+                }
+                let free = chain.iter().all(|&m| conflict(m, i).is_none());
+                if !free {
+                    return true;
+                }
+                let cost = self.cost_model.cost(&proposals[i]);
+                let agent = metas[i].agent.as_ref().zip(self.policy.agent_budget);
+                if let Some((agent, budget)) = agent {
+                    // An agent's first SLAP of a tick always runs, like the
+                    // first SLAP of the tick.
+                    if let Some(&left) = agent_left.get(agent) {
+                        if cost > left {
+                            agent_over.push((
+                                i,
+                                ConflictReason::AgentOverBudget {
+                                    agent: agent.clone(),
+                                    cost,
+                                    remaining: left,
+                                },
+                            ));
+                            return false;
+                        }
+                    }
+                    let left = agent_left.entry(agent).or_insert(budget);
+                    *left = left.saturating_sub(cost);
+                }
+                if let Some(left) = remaining.as_mut() {
+                    // The first SLAP of a tick always runs, so no SLAP can be
+                    // too expensive to ever be scheduled.
+                    if cost > *left && !(first_chain && chain.is_empty()) {
+                        stopped = Some(*left);
+                        over_budget.push(i);
+                        return false;
+                    }
+                    *left = left.saturating_sub(cost);
+                }
+                chain.push(i);
+                false
+            });
+            chains.push(chain);
+        }
+
+        // 3. Explain what did not fit
+        let last = chains.last().map(Vec::as_slice).unwrap_or_default();
+        let mut deferred: Vec<(usize, ConflictReason)> = order
+            .into_iter()
+            .map(|i| {
+                let reason = last
+                    .iter()
+                    .find_map(|&m| {
+                        conflict(i, m).map(|conflict| ConflictReason::Conflict {
+                            blocked_by: hashes[m],
+                            conflict,
+                        })
+                    })
+                    .expect("greedy packing defers only conflicting SLAPs");
+                (i, reason)
+            })
+            .chain(over_budget.into_iter().map(|i| {
+                let reason = ConflictReason::OverBudget {
+                    cost: self.cost_model.cost(&proposals[i]),
+                    remaining: stopped.unwrap_or(0),
+                };
+                (i, reason)
+            }))
+            .chain(agent_over)
+            .collect();
+        deferred.sort_unstable_by_key(|(i, _)| *i);
+
+        Packing {
+            visit,
+            chains,
+            deferred,
+        }
+    }
+
+    /// Dry run: explain how [`Self::schedule`] would treat `proposals`.
+    ///
+    /// Nothing is executed and the [`DeferredQueue`] is not touched (every
+    /// proposal has age 0, as in `schedule`). Only the footprint cache is
+    /// updated.
+    pub fn explain(&mut self, graph: &WarpGraph, proposals: Vec<Slap>) -> ScheduleReport {
+        self.explain_proposals(graph, proposals.into_iter().map(Proposal::from).collect())
+    }
+
+    /// [`Self::explain`] for SLAPs with priority/deadline metadata.
+    pub fn explain_proposals(
+        &mut self,
+        graph: &WarpGraph,
+        proposals: Vec<Proposal>,
+    ) -> ScheduleReport {
+        let (proposals, metas, hashes) = order_proposals(proposals);
+        self.report(graph, proposals, &metas, &hashes, |_| 0)
+    }
+
+    /// Dry run of [`Self::tick_proposals`]: explain how the next tick would
+    /// treat `proposals` together with every waiting SLAP, at its age.
+    ///
+    /// Nothing is executed and the [`DeferredQueue`] is not touched. Only
+    /// the footprint cache is updated.
+    pub fn explain_tick_proposals(
+        &mut self,
+        graph: &WarpGraph,
+        proposals: Vec<Proposal>,
+    ) -> ScheduleReport {
+        let (proposals, metas, hashes, ages) =
+            with_waiting(self.deferred.clone().drain(), proposals);
+        self.report(graph, proposals, &metas, &hashes, |h| {
+            ages.get(h).copied().unwrap_or(0)
+        })
+    }
+
+    /// Explain the assignment of radix-ordered proposals.
+    fn report(
+        &mut self,
+        graph: &WarpGraph,
+        proposals: Vec<Slap>,
+        metas: &[SlapMeta],
+        hashes: &[Hash],
+        age: impl Fn(&Hash) -> u32,
+    ) -> ScheduleReport {
+        let Packing {
+            visit,
+            chains,
+            deferred,
+        } = self.assign(graph, &proposals, hashes, metas, &age);
+        let digest = LazyDigest::new(graph);
+        let footprints: Vec<Footprint> = proposals
+            .iter()
+            .zip(hashes)
+            .map(|(slap, hash)| self.footprint_for(slap, *hash, &digest))
+            .collect();
+
+        let mut placements: Vec<Option<Placement>> = vec![None; proposals.len()];
+        for (batch, chain) in chains.iter().enumerate() {
+            for &i in chain {
+                placements[i] = Some(Placement::Batch(batch));
+            }
+        }
+        for (i, reason) in deferred {
+            placements[i] = Some(Placement::Deferred(reason));
+        }
+        let mut ranks = vec![0; proposals.len()];
+        for (rank, &i) in visit.iter().enumerate() {
+            ranks[i] = rank;
+        }
+
+        let entries = proposals
+            .into_iter()
+            .enumerate()
+            .map(|(i, slap)| ProposalReport {
+                key: OrderingKey {
+                    age: age(&hashes[i]),
+                    weight: self.policy.weight(&slap),
+                    priority: metas[i].priority,
+                    deadline: metas[i].deadline,
+                    hash: hashes[i],
+                },
+                rank: ranks[i],
+                cost: self.cost_model.cost(&slap),
+                conflicts: (0..footprints.len())
+                    .filter(|&j| j != i)
+                    .filter_map(|j| {
+                        footprints[i]
+                            .conflicts_with(&footprints[j])
+                            .map(|conflict| (hashes[j], conflict))
+                    })
+                    .collect(),
+                footprint: footprints[i].clone(),
+                placement: placements[i].take().expect("every proposal is placed"),
+                slap,
+            })
+            .collect();
+
+        ScheduleReport {
+            policy: self.policy.policy_hash(),
+            max_batches: self.policy.max_batches(),
+            proposals: entries,
+        }
+    }
+
+    /// Conflict DAG behind [`Self::schedule`] of `proposals` (a dry run, like
+    /// [`Self::explain`]).
+    pub fn dependencies(&mut self, graph: &WarpGraph, proposals: Vec<Slap>) -> DependencyGraph {
+        DependencyGraph::from_report(&self.explain(graph, proposals))
+    }
+
+    /// Schedule `proposals` and record the outcome as a Decision event.
+    ///
+    /// `evidence` are the events the proposals came from; the Decision's
+    /// policy parent is [`Self::policy_context`].
+    pub fn decide(
+        &mut self,
+        graph: &WarpGraph,
+        proposals: Vec<Slap>,
+        evidence: Vec<EventId>,
+    ) -> Result<(Schedule, EventEnvelope), EventError> {
+        let schedule = self.schedule(graph, proposals);
+        let hash = |slap: &Slap| canonical::hash_canonical(slap);
+        let payload = ScheduleDecision {
+            policy: self.policy.policy_hash(),
+            batches: schedule
+                .batches
+                .iter()
+                .map(|batch| batch.iter().map(hash).collect())
+                .collect::<Result<_, _>>()?,
+            deferred: schedule
+                .deferred
+                .iter()
+                .map(|(slap, _)| hash(slap))
+                .collect::<Result<_, _>>()?,
+        };
+        let decision = EventEnvelope::new_decision(
+            CanonicalBytes::from_value(&payload)?,
+            evidence,
+            self.policy_context.event_id(),
+            None,
+            None,
+        )?;
+        Ok((schedule, decision))
+    }
+}
+
+/// `waiting` SLAPs and `proposals` in radix order, each SLAP once (the
+/// waiting copy, with its metadata), and the age of each waiting SLAP.
+fn with_waiting(
+    waiting: BTreeMap<Hash, DeferredEntry>,
+    proposals: Vec<Proposal>,
+) -> (Vec<Slap>, Vec<SlapMeta>, Vec<Hash>, HashMap<Hash, u32>) {
+    let ages: HashMap<Hash, u32> = waiting.iter().map(|(h, e)| (*h, e.age)).collect();
+    let all: Vec<Proposal> = waiting
+        .into_values()
+        .map(|entry| Proposal::new(entry.slap, entry.meta))
+        .chain(proposals)
+        .collect();
+    let (mut proposals, mut metas, mut hashes) = order_proposals(all);
+
+    // Radix order is sorted and stable, so duplicates are adjacent and
+    // the waiting copy comes first.
+    let mut keep = vec![true];
+    keep.extend(hashes.windows(2).map(|w| w[0] != w[1]));
+    let mut flags = keep.iter();
+    proposals.retain(|_| *flags.next().expect("one flag per proposal"));
+    let mut flags = keep.iter();
+    metas.retain(|_| *flags.next().expect("one flag per proposal"));
+    hashes.dedup();
+    (proposals, metas, hashes, ages)
+}
+
+/// Radix-order proposals, split into SLAPs, metadata and canonical hashes.
+fn order_proposals(proposals: Vec<Proposal>) -> (Vec<Slap>, Vec<SlapMeta>, Vec<Hash>) {
+    let hashes = proposals
+        .iter()
+        .map(|p| canonical::hash_canonical(&p.slap))
+        .collect::<Result<Vec<_>, _>>()
+        .expect("SLAPs are always canonically encodable");
+    let (proposals, hashes) = permute_by_hash(proposals, hashes);
+    let (slaps, metas) = proposals.into_iter().map(|p| (p.slap, p.meta)).unzip();
+    (slaps, metas, hashes)
+}
+
+/// Batch assignment of one scheduling pass, by index into the radix order.
+struct Packing {
+    /// Indices in the order they were offered to the batches.
+    visit: Vec<usize>,
+    /// Admitted indices per batch, in admission order.
+    chains: Vec<Vec<usize>>,
+    /// Left-over indices, ascending, with the reason.
+    deferred: Vec<(usize, ConflictReason)>,
+}
+
+/// True if the footprint of `slap` depends on graph state.
+fn reads_graph(slap: &Slap) -> bool {
+    match slap {
+        Slap::DeleteNode { .. } => true,
+        Slap::Transaction(transaction) => transaction.slaps.iter().any(reads_graph),
+        _ => false,
+    }
+}
+
+/// A graph and its commit digest, computed on first use.
+struct LazyDigest<'a> {
+    graph: &'a WarpGraph,
+    digest: OnceCell<Hash>,
+}
+
+impl<'a> LazyDigest<'a> {
+    fn new(graph: &'a WarpGraph) -> Self {
+        Self {
+            graph,
+            digest: OnceCell::new(),
+        }
     }
+
+    fn digest(&self) -> Hash {
+        *self.digest.get_or_init(|| self.graph.compute_hash())
+    }
+}
+
+/// Edge keys for every edge incident to node `id`, if it is in `graph`.
+fn incident_edge_keys(graph: &WarpGraph, id: &NodeId) -> Vec<String> {
+    let Some(key) = graph.node_key(id) else {
+        return Vec::new();
+    };
+    graph
+        .edges
+        .values()
+        .filter(|e| e.source == key || e.target == key)
+        .filter_map(|e| {
+            let from = graph.nodes.get(e.source)?.id;
+            let to = graph.nodes.get(e.target)?.id;
+            Some(edge_key(from, to, &e.edge_type))
+        })
+        .collect()
 }
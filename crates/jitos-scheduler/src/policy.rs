@@ -0,0 +1,145 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scheduler Policy
+//!
+//! The scheduler's ordering rules are a policy like any other: a canonical
+//! payload, referenced by hash, published as a PolicyContext event. Every
+//! scheduling Decision names that PolicyContext as its policy parent, so a
+//! replay can tell which rules produced a schedule and a what-if fork can swap
+//! them by hash (`DeltaKind::SchedulerPolicy`).
+
+use jitos_core::canonical;
+use jitos_core::delta::PolicyHash;
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventError};
+use jitos_core::{Hash, Slap, SlapMeta};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// How proposals of equal age and weight are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TieBreak {
+    /// Ascending canonical hash (Echo Radix order); SLAP metadata is ignored.
+    CanonicalHash,
+    /// [`SlapMeta`] priority (highest first), then deadline (earliest first,
+    /// none last), then ascending canonical hash.
+    PriorityDeadline,
+}
+
+/// How ordered proposals are grouped into batches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatchingStrategy {
+    /// Greedily pack conflict-free antichains; emit at most `max_batches` per
+    /// tick and defer the rest.
+    Antichains { max_batches: u32 },
+}
+
+/// The scheduler's ordering rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchedulerPolicy {
+    pub tie_break: TieBreak,
+    pub batching: BatchingStrategy,
+    /// Priority per SLAP op name (`"CreateNode"`, `"SetTime"`, ...). Higher
+    /// weights are packed first; missing ops weigh 0.
+    pub priority_weights: BTreeMap<String, u32>,
+    /// Total [`crate::CostModel`] cost admitted per tick; `None` is unbounded.
+    pub tick_budget: Option<u64>,
+    /// Cost admitted per tick for each agent (see [`SlapMeta::agent`]);
+    /// `None` is unbounded. Omitted from the encoding when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_budget: Option<u64>,
+}
+
+impl Default for SchedulerPolicy {
+    /// Echo: hash order, one antichain per tick, no priorities.
+    fn default() -> Self {
+        Self {
+            tie_break: TieBreak::CanonicalHash,
+            batching: BatchingStrategy::Antichains { max_batches: 1 },
+            priority_weights: BTreeMap::new(),
+            tick_budget: None,
+            agent_budget: None,
+        }
+    }
+}
+
+impl SchedulerPolicy {
+    /// Latency-aware policy: like the default, but honours SLAP priority and
+    /// deadline metadata.
+    pub fn priority_deadline() -> Self {
+        Self {
+            tie_break: TieBreak::PriorityDeadline,
+            ..Self::default()
+        }
+    }
+
+    /// Canonical hash of the policy payload.
+    pub fn policy_hash(&self) -> PolicyHash {
+        // Enums, integers and a string-keyed map always encode.
+        canonical::hash_canonical(self).expect("scheduler policy encoding")
+    }
+
+    /// The PolicyContext event publishing this policy.
+    pub fn to_policy_context(&self) -> Result<EventEnvelope, EventError> {
+        EventEnvelope::new_policy_context(CanonicalBytes::from_value(self)?, vec![], None, None)
+    }
+
+    /// Priority weight of `slap` under this policy.
+    pub fn weight(&self, slap: &Slap) -> u32 {
+        self.priority_weights
+            .get(slap_op(slap))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Sort key for visiting a proposal; lower keys are offered batch slots
+    /// first. Ties fall back to radix (hash) order.
+    pub(crate) fn visit_key(
+        &self,
+        age: u32,
+        slap: &Slap,
+        meta: &SlapMeta,
+    ) -> (Reverse<u32>, Reverse<u32>, Reverse<u32>, u64) {
+        let (priority, deadline) = match self.tie_break {
+            TieBreak::CanonicalHash => (0, u64::MAX),
+            TieBreak::PriorityDeadline => (meta.priority, meta.deadline.unwrap_or(u64::MAX)),
+        };
+        (
+            Reverse(age),
+            Reverse(self.weight(slap)),
+            Reverse(priority),
+            deadline,
+        )
+    }
+
+    pub(crate) fn max_batches(&self) -> usize {
+        match self.batching {
+            BatchingStrategy::Antichains { max_batches } => max_batches as usize,
+        }
+    }
+}
+
+/// Payload of a scheduling Decision event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleDecision {
+    /// Hash of the [`SchedulerPolicy`] that produced the schedule.
+    pub policy: PolicyHash,
+    /// Canonical SLAP hashes of each batch, in application order.
+    pub batches: Vec<Vec<Hash>>,
+    /// Canonical SLAP hashes left for a later tick.
+    pub deferred: Vec<Hash>,
+}
+
+/// SLAP op name, as in its serde tag.
+fn slap_op(slap: &Slap) -> &'static str {
+    match slap {
+        Slap::CreateNode { .. } => "CreateNode",
+        Slap::DeleteNode { .. } => "DeleteNode",
+        Slap::Connect { .. } => "Connect",
+        Slap::InvokeScript { .. } => "InvokeScript",
+        Slap::SetTime { .. } => "SetTime",
+        Slap::Collapse { .. } => "Collapse",
+        Slap::Transaction(_) => "Transaction",
+    }
+}
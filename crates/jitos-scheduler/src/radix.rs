@@ -0,0 +1,75 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Echo Radix Ordering
+//!
+//! The scheduler's deterministic tie-break: every proposal is keyed by the
+//! BLAKE3 hash of its canonical encoding (SPEC-0001) and proposals are ordered
+//! by those 32-byte keys, ascending. Arrival order never matters.
+//!
+//! The sort is an LSD radix sort (one stable counting pass per key byte), so
+//! its cost is linear in the number of proposals and it never compares SLAPs.
+
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::{Hash, Slap};
+
+/// Proposals in radix order, with their canonical hashes.
+#[derive(Debug, Clone)]
+pub struct RadixOrder {
+    /// Proposals sorted by canonical hash, ascending.
+    pub proposals: Vec<Slap>,
+    /// `hashes[i]` is the canonical hash of `proposals[i]`.
+    pub hashes: Vec<Hash>,
+}
+
+/// Hash every proposal and sort by hash.
+///
+/// Identical proposals have identical hashes and keep their relative order.
+pub fn radix_order(proposals: Vec<Slap>) -> Result<RadixOrder, CanonicalError> {
+    let hashes = proposals
+        .iter()
+        .map(canonical::hash_canonical)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (proposals, hashes) = permute_by_hash(proposals, hashes);
+    Ok(RadixOrder { proposals, hashes })
+}
+
+/// Radix-order `items` by their precomputed `hashes`.
+pub(crate) fn permute_by_hash<T>(items: Vec<T>, hashes: Vec<Hash>) -> (Vec<T>, Vec<Hash>) {
+    let order = radix_sort_indices(&hashes);
+
+    let mut slots: Vec<Option<T>> = items.into_iter().map(Some).collect();
+    let mut sorted = Vec::with_capacity(order.len());
+    let mut sorted_hashes = Vec::with_capacity(order.len());
+    for i in order {
+        sorted.push(slots[i].take().expect("each index appears once"));
+        sorted_hashes.push(hashes[i]);
+    }
+    (sorted, sorted_hashes)
+}
+
+/// Stable LSD radix sort of 32-byte keys; returns the permutation that sorts
+/// `keys` ascending (`keys[result[0]]` is the smallest).
+pub fn radix_sort_indices(keys: &[Hash]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    let mut scratch = vec![0usize; keys.len()];
+
+    for byte in (0..32).rev() {
+        let mut counts = [0usize; 257];
+        for &i in &order {
+            counts[keys[i].0[byte] as usize + 1] += 1;
+        }
+        for b in 0..256 {
+            counts[b + 1] += counts[b];
+        }
+        for &i in &order {
+            let bucket = keys[i].0[byte] as usize;
+            scratch[counts[bucket]] = i;
+            counts[bucket] += 1;
+        }
+        std::mem::swap(&mut order, &mut scratch);
+    }
+
+    order
+}
@@ -0,0 +1,180 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Schedule Explanation
+//!
+//! [`crate::EchoScheduler::explain`] runs the scheduler's assignment logic
+//! without executing anything and reports, per proposal, the ordering key it
+//! was visited by, its inferred footprint, every conflict with another
+//! proposal, and the batch it landed in (or why it was deferred).
+
+use crate::{ConflictKind, ConflictReason, DependencyGraph, Footprint};
+use jitos_core::delta::PolicyHash;
+use jitos_core::{Hash, Slap};
+use serde::Serialize;
+use std::fmt;
+
+/// The key proposals are visited by: higher age, then higher weight, then
+/// (under [`crate::TieBreak::PriorityDeadline`]) higher priority and earlier
+/// deadline, then lower canonical hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OrderingKey {
+    /// Ticks spent in the deferred queue.
+    pub age: u32,
+    /// Policy priority weight.
+    pub weight: u32,
+    /// SLAP metadata priority.
+    pub priority: u32,
+    /// SLAP metadata deadline tick.
+    pub deadline: Option<u64>,
+    /// Canonical SLAP hash (Echo Radix tie-break).
+    pub hash: Hash,
+}
+
+/// Where a proposal ended up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Placement {
+    /// Admitted to the batch with this index.
+    Batch(usize),
+    /// Left for a later tick.
+    Deferred(ConflictReason),
+}
+
+/// Explanation for one proposal.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProposalReport {
+    pub slap: Slap,
+    pub key: OrderingKey,
+    /// Position in visit order (0 is offered a batch slot first).
+    pub rank: usize,
+    /// Cost under the scheduler's cost model.
+    pub cost: u64,
+    pub footprint: Footprint,
+    /// Every other proposal this one conflicts with, by hash, ascending.
+    pub conflicts: Vec<(Hash, ConflictKind)>,
+    pub placement: Placement,
+}
+
+/// Dry-run result of a scheduling pass.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleReport {
+    /// Hash of the scheduler policy in force.
+    pub policy: PolicyHash,
+    pub max_batches: usize,
+    /// One entry per proposal, ascending by canonical hash.
+    pub proposals: Vec<ProposalReport>,
+}
+
+impl ScheduleReport {
+    /// Report for the proposal with canonical hash `hash`.
+    pub fn get(&self, hash: &Hash) -> Option<&ProposalReport> {
+        self.proposals
+            .binary_search_by_key(hash, |p| p.key.hash)
+            .ok()
+            .map(|i| &self.proposals[i])
+    }
+
+    /// Proposal hashes per batch, each ascending.
+    pub fn batches(&self) -> Vec<Vec<Hash>> {
+        let mut batches: Vec<Vec<Hash>> = Vec::new();
+        for p in &self.proposals {
+            if let Placement::Batch(b) = p.placement {
+                if batches.len() <= b {
+                    batches.resize_with(b + 1, Vec::new);
+                }
+                batches[b].push(p.key.hash);
+            }
+        }
+        batches
+    }
+
+    /// Conflict DAG over the proposals, oriented by visit order.
+    pub fn dependencies(&self) -> DependencyGraph {
+        DependencyGraph::from_report(self)
+    }
+
+    /// Deferred proposal hashes, ascending.
+    pub fn deferred(&self) -> Vec<Hash> {
+        self.proposals
+            .iter()
+            .filter(|p| matches!(p.placement, Placement::Deferred(_)))
+            .map(|p| p.key.hash)
+            .collect()
+    }
+}
+
+impl fmt::Display for ScheduleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "schedule report: {} proposals, policy {}, max {} batch(es)",
+            self.proposals.len(),
+            short(&self.policy),
+            self.max_batches
+        )?;
+        let mut by_rank: Vec<&ProposalReport> = self.proposals.iter().collect();
+        by_rank.sort_by_key(|p| p.rank);
+        for p in by_rank {
+            let placement = match &p.placement {
+                Placement::Batch(b) => format!("batch {b}"),
+                Placement::Deferred(ConflictReason::Conflict { blocked_by, .. }) => {
+                    format!("deferred, blocked by {}", short(blocked_by))
+                }
+                Placement::Deferred(ConflictReason::OverBudget { cost, remaining }) => {
+                    format!("deferred, over budget (cost {cost}, {remaining} left)")
+                }
+                Placement::Deferred(ConflictReason::AgentOverBudget {
+                    agent,
+                    cost,
+                    remaining,
+                }) => format!(
+                    "deferred, {} over budget (cost {cost}, {remaining} left)",
+                    agent.as_str()
+                ),
+            };
+            writeln!(
+                f,
+                "#{:<3} {}  age {} weight {}  {}",
+                p.rank,
+                short(&p.key.hash),
+                p.key.age,
+                p.key.weight,
+                placement
+            )?;
+            for (other, conflict) in &p.conflicts {
+                writeln!(
+                    f,
+                    "       conflicts with {}: {}",
+                    short(other),
+                    describe(conflict)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn short(hash: &Hash) -> String {
+    hash.to_string()[..12].to_string()
+}
+
+fn describe(conflict: &ConflictKind) -> String {
+    let (kind, resource, ours, theirs) = match conflict {
+        ConflictKind::WriteWrite {
+            resource,
+            ours,
+            theirs,
+        } => ("write/write", resource, ours, theirs),
+        ConflictKind::ReadWrite {
+            resource,
+            ours,
+            theirs,
+        } => ("read/write", resource, ours, theirs),
+        ConflictKind::WriteRead {
+            resource,
+            ours,
+            theirs,
+        } => ("write/read", resource, ours, theirs),
+    };
+    format!("{kind} on {resource:?} ({ours} vs {theirs})")
+}
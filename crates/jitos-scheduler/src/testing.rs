@@ -0,0 +1,141 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test support: commutativity harness for the schedule → execute pipeline.
+//!
+//! Enabled with the `testing` feature. Intended for tests and benches only.
+//!
+//! The scheduler promises that its output depends on the *set* of proposals,
+//! never on arrival order. [`assert_commutes`] checks that end to end: it
+//! feeds `n` permutations of the same proposals through
+//! [`EchoScheduler::schedule`] and [`execute_batch`], and asserts that every
+//! run produces the same batches, deferrals and commit digest.
+//!
+//! ```ignore
+//! use jitos_scheduler::testing::assert_commutes;
+//!
+//! let digest = assert_commutes(&graph, &proposals, 100);
+//! ```
+//!
+//! Permutations come from a fixed-seed generator, so a failure reproduces
+//! exactly and names the permutation that diverged.
+
+use crate::{execute_batch, ConflictReason, EchoScheduler};
+use jitos_core::{canonical, Hash, Slap};
+use jitos_graph::WarpGraph;
+
+/// Observable result of one schedule → execute run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineRun {
+    /// Canonical hashes of each batch, in batch order.
+    pub batches: Vec<Vec<Hash>>,
+    /// Deferred SLAPs by canonical hash, with the reason.
+    pub deferred: Vec<(Hash, ConflictReason)>,
+    /// Commit digest after each batch was applied.
+    pub commits: Vec<Hash>,
+    /// Final graph digest.
+    pub digest: Hash,
+}
+
+/// Schedule `proposals` against `graph`, apply every batch to a copy of it
+/// and record what happened.
+///
+/// Panics if a batch fails to execute: the harness is for proposal sets that
+/// are valid against `graph`.
+pub fn run_pipeline(
+    scheduler: &mut EchoScheduler,
+    graph: &WarpGraph,
+    proposals: Vec<Slap>,
+) -> PipelineRun {
+    let schedule = scheduler.schedule(graph, proposals);
+    let mut graph = graph.clone();
+    let commits = schedule
+        .batches
+        .iter()
+        .map(|batch| execute_batch(&mut graph, batch).expect("batch executes"))
+        .collect();
+    PipelineRun {
+        batches: schedule
+            .batches
+            .iter()
+            .map(|batch| batch.iter().map(slap_hash).collect())
+            .collect(),
+        deferred: schedule
+            .deferred
+            .iter()
+            .map(|(slap, reason)| (slap_hash(slap), reason.clone()))
+            .collect(),
+        commits,
+        digest: graph.compute_hash(),
+    }
+}
+
+/// `n` deterministic permutations of `items`.
+///
+/// The first is `items` as given and the second (if `n > 1`) is reversed;
+/// the rest are Fisher–Yates shuffles driven by a fixed-seed SplitMix64.
+pub fn permutations<T: Clone>(items: &[T], n: usize) -> Vec<Vec<T>> {
+    let mut rng = SplitMix64(0x6a09_e667_f3bc_c908);
+    (0..n)
+        .map(|i| {
+            let mut perm = items.to_vec();
+            match i {
+                0 => {}
+                1 => perm.reverse(),
+                _ => {
+                    for j in (1..perm.len()).rev() {
+                        let k = (rng.next() % (j as u64 + 1)) as usize;
+                        perm.swap(j, k);
+                    }
+                }
+            }
+            perm
+        })
+        .collect()
+}
+
+/// Assert that `n` permutations of `proposals` all produce the same
+/// [`PipelineRun`], each with a fresh [`EchoScheduler::new`]. Returns the
+/// common final digest.
+pub fn assert_commutes(graph: &WarpGraph, proposals: &[Slap], n: usize) -> Hash {
+    assert_commutes_with(EchoScheduler::new, graph, proposals, n)
+}
+
+/// [`assert_commutes`] with schedulers built by `make` (e.g. a custom policy
+/// or cost model).
+pub fn assert_commutes_with(
+    make: impl Fn() -> EchoScheduler,
+    graph: &WarpGraph,
+    proposals: &[Slap],
+    n: usize,
+) -> Hash {
+    let mut expected: Option<PipelineRun> = None;
+    for (i, perm) in permutations(proposals, n).into_iter().enumerate() {
+        let order: Vec<Hash> = perm.iter().map(slap_hash).collect();
+        let run = run_pipeline(&mut make(), graph, perm);
+        match &expected {
+            None => expected = Some(run),
+            Some(first) => assert_eq!(
+                &run, first,
+                "permutation {i} diverged from permutation 0 (arrival order {order:?})"
+            ),
+        }
+    }
+    expected.expect("at least one permutation").digest
+}
+
+fn slap_hash(slap: &Slap) -> Hash {
+    canonical::hash_canonical(slap).expect("SLAPs are always canonically encodable")
+}
+
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
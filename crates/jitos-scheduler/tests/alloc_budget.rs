@@ -0,0 +1,43 @@
+//! Scheduler allocation budget tests.
+//!
+//! Batch selection runs every tick; allocations here scale with tick rate.
+
+use jitos_core::testing::{assert_alloc_budget, CountingAllocator};
+use jitos_core::{canonical, NodeId, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::EchoScheduler;
+
+/// Allocations allowed per scheduled proposal.
+const PER_PROPOSAL_BUDGET: u64 = 32;
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+fn node(name: &str) -> NodeId {
+    NodeId::from_hash(canonical::hash_canonical(&name).expect("hash"))
+}
+
+fn proposals() -> Vec<Slap> {
+    (0..8)
+        .map(|i| Slap::DeleteNode {
+            id: node(&format!("node-{i}")),
+        })
+        .collect()
+}
+
+#[test]
+fn schedule_within_budget() {
+    let mut scheduler = EchoScheduler::new();
+    let graph = WarpGraph::new();
+    let proposals = proposals();
+
+    // Radix ordering hashes every proposal canonically (SPEC-0001 value tree +
+    // encode buffer) and infers its footprint (key strings + cache entry); both
+    // are linear in the proposal count.
+    let budget = proposals.len() as u64 * PER_PROPOSAL_BUDGET;
+    let schedule = assert_alloc_budget("EchoScheduler::schedule", budget, || {
+        scheduler.schedule(&graph, proposals)
+    });
+    assert_eq!(schedule.batches.len(), 1);
+    assert_eq!(schedule.batches[0].len(), 8);
+}
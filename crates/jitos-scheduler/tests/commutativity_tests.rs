@@ -0,0 +1,93 @@
+use jitos_core::{Hash, Slap};
+use jitos_graph::{NodeId, WarpGraph, WarpNode};
+use jitos_scheduler::testing::{assert_commutes, assert_commutes_with, permutations, run_pipeline};
+use jitos_scheduler::EchoScheduler;
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn graph() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    for byte in 1..=6 {
+        g.insert_node(WarpNode {
+            id: node_id(byte),
+            node_type: "task".to_string(),
+            payload_bytes: vec![byte],
+            attachment: None,
+        });
+    }
+    g
+}
+
+fn connect(source: u8, target: u8) -> Slap {
+    Slap::Connect {
+        source: node_id(source),
+        target: node_id(target),
+        edge_type: "next".to_string(),
+    }
+}
+
+/// Independent and conflicting SLAPs mixed: node 2 is both connected and
+/// deleted, and two creates share the node-type index.
+fn proposals() -> Vec<Slap> {
+    vec![
+        Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: vec![7],
+        },
+        Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: vec![8],
+        },
+        Slap::DeleteNode { id: node_id(1) },
+        Slap::DeleteNode { id: node_id(2) },
+        connect(2, 3),
+        connect(4, 5),
+        connect(5, 6),
+        Slap::SetTime { tick: 1, dt: 0.5 },
+    ]
+}
+
+#[test]
+fn permutations_are_deterministic_reorderings() {
+    let items: Vec<u32> = (0..8).collect();
+    let perms = permutations(&items, 50);
+    assert_eq!(perms, permutations(&items, 50));
+    assert_eq!(perms[0], items);
+    assert_eq!(perms[1], items.iter().rev().copied().collect::<Vec<_>>());
+    for perm in &perms {
+        let mut sorted = perm.clone();
+        sorted.sort();
+        assert_eq!(sorted, items);
+    }
+    let distinct: std::collections::BTreeSet<_> = perms.iter().collect();
+    assert!(distinct.len() > 40, "shuffles actually reorder");
+}
+
+#[test]
+fn schedule_and_execute_commute_across_arrival_orders() {
+    let base = graph();
+    let digest = assert_commutes_with(
+        || EchoScheduler::with_max_batches(8),
+        &base,
+        &proposals(),
+        200,
+    );
+
+    let run = run_pipeline(&mut EchoScheduler::with_max_batches(8), &base, proposals());
+    assert_eq!(run.digest, digest);
+    assert!(run.batches.len() > 1, "conflicts split the proposals");
+    assert!(run.deferred.is_empty());
+    assert_eq!(run.commits.last(), Some(&digest));
+}
+
+#[test]
+fn deferrals_commute_when_batches_are_capped() {
+    let base = graph();
+    let digest = assert_commutes(&base, &proposals(), 200);
+
+    let run = run_pipeline(&mut EchoScheduler::new(), &base, proposals());
+    assert!(!run.deferred.is_empty());
+    assert_eq!(run.digest, digest);
+}
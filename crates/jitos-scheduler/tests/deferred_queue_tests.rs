@@ -0,0 +1,109 @@
+use jitos_core::{canonical, Hash, NodeId, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::{EchoScheduler, SchedulerPolicy};
+
+fn node(name: &str) -> NodeId {
+    NodeId::from_hash(canonical::hash_canonical(&name).expect("hash"))
+}
+
+fn hash(slap: &Slap) -> Hash {
+    canonical::hash_canonical(slap).expect("hash")
+}
+
+fn set_time(tick: u64) -> Slap {
+    Slap::SetTime { tick, dt: 0.5 }
+}
+
+fn delete(id: &str) -> Slap {
+    Slap::DeleteNode { id: node(id) }
+}
+
+#[test]
+fn deferred_slaps_are_retried_and_age() {
+    let graph = WarpGraph::new();
+    let mut s = EchoScheduler::new();
+    let proposals: Vec<Slap> = (1..=3).map(set_time).collect();
+
+    let first = s.tick(&graph, proposals.clone());
+    assert_eq!(first.batches[0].len(), 1);
+    assert_eq!(s.deferred().len(), 2);
+    assert!(s.deferred().iter().all(|(_, e)| e.age == 1));
+    let blocker = hash(&first.batches[0][0]);
+    assert!(s
+        .deferred()
+        .iter()
+        .all(|(_, e)| e.reason.blocked_by() == Some(blocker)));
+
+    let second = s.tick(&graph, vec![]);
+    assert_eq!(second.batches[0].len(), 1);
+    assert_eq!(s.deferred().len(), 1);
+    assert!(s.deferred().iter().all(|(_, e)| e.age == 2));
+
+    let third = s.tick(&graph, vec![]);
+    assert!(s.deferred().is_empty());
+
+    let mut ran: Vec<Hash> = [first, second, third]
+        .iter()
+        .map(|schedule| hash(&schedule.batches[0][0]))
+        .collect();
+    ran.sort();
+    let mut expected: Vec<Hash> = proposals.iter().map(hash).collect();
+    expected.sort();
+    assert_eq!(ran, expected, "every proposal ran exactly once");
+}
+
+#[test]
+fn high_priority_arrivals_cannot_starve_a_waiting_slap() {
+    let graph = WarpGraph::new();
+    let mut policy = SchedulerPolicy::default();
+    policy.priority_weights.insert("DeleteNode".to_string(), 10);
+    let mut s = EchoScheduler::with_policy(policy);
+
+    // Collapse writes every node, and every DeleteNode outranks it.
+    let victim = Slap::Collapse {
+        sws_id: "sws-a".into(),
+    };
+    let first = s.tick(&graph, vec![victim.clone(), delete("a")]);
+    assert_eq!(hash(&first.batches[0][0]), hash(&delete("a")));
+    assert_eq!(s.deferred().age(&hash(&victim)), 1);
+
+    // A fresh, higher-weight rival arrives, but the aged entry goes first.
+    let second = s.tick(&graph, vec![delete("b")]);
+    assert_eq!(hash(&second.batches[0][0]), hash(&victim));
+    assert_eq!(s.deferred().age(&hash(&delete("b"))), 1);
+}
+
+#[test]
+fn queue_state_is_canonical_and_hashable() {
+    let graph = WarpGraph::new();
+    let proposals: Vec<Slap> = (1..=4).map(set_time).collect();
+    let mut reversed = proposals.clone();
+    reversed.reverse();
+
+    let mut a = EchoScheduler::new();
+    let mut b = EchoScheduler::new();
+    a.tick(&graph, proposals.clone());
+    b.tick(&graph, reversed);
+    assert_eq!(a.deferred().state(), b.deferred().state());
+    assert_eq!(a.deferred().state_hash(), b.deferred().state_hash());
+
+    // Re-proposing a waiting SLAP does not duplicate it.
+    let before = a.deferred().state_hash();
+    let waiting = a.deferred().iter().next().unwrap().1.slap.clone();
+    a.tick(&graph, vec![waiting]);
+    assert_eq!(a.deferred().len(), 2);
+    assert_ne!(a.deferred().state_hash(), before, "ages moved on");
+
+    let state = a.deferred().state();
+    assert!(state.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(state.iter().all(|(_, age)| *age == 2));
+}
+
+#[test]
+fn schedule_leaves_the_queue_alone() {
+    let graph = WarpGraph::new();
+    let mut s = EchoScheduler::new();
+    let schedule = s.schedule(&graph, (1..=3).map(set_time).collect());
+    assert_eq!(schedule.deferred.len(), 2);
+    assert!(s.deferred().is_empty());
+}
@@ -0,0 +1,74 @@
+use jitos_core::{canonical, Hash, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::EchoScheduler;
+
+fn hash(slap: &Slap) -> Hash {
+    canonical::hash_canonical(slap).expect("hash")
+}
+
+fn set_time(tick: u64) -> Slap {
+    Slap::SetTime { tick, dt: 0.5 }
+}
+
+fn create() -> Slap {
+    Slap::CreateNode {
+        node_type: "task".to_string(),
+        payload_bytes: vec![],
+    }
+}
+
+#[test]
+fn edges_follow_visit_order_and_explain_the_batches() {
+    let graph = WarpGraph::new();
+    let mut s = EchoScheduler::with_max_batches(u32::MAX);
+    let proposals = vec![set_time(1), set_time(2), set_time(3), create()];
+    let dag = s.dependencies(&graph, proposals.clone());
+
+    assert_eq!(dag.nodes.len(), 4);
+    // The three SetTimes all write sys:time: a chain of 3 pairwise conflicts.
+    assert_eq!(dag.edges.len(), 3);
+    for edge in &dag.edges {
+        let from = dag.node(&edge.from).unwrap();
+        let to = dag.node(&edge.to).unwrap();
+        assert!(from.rank < to.rank);
+        assert!(from.batch < to.batch, "dependents land in later batches");
+    }
+    let independent = hash(&create());
+    assert_eq!(dag.predecessors(&independent).count(), 0);
+    assert_eq!(dag.successors(&independent).count(), 0);
+    assert_eq!(dag.node(&independent).unwrap().batch, Some(0));
+
+    let first = dag
+        .nodes
+        .iter()
+        .filter(|n| n.hash != independent)
+        .min_by_key(|n| n.rank)
+        .unwrap();
+    assert_eq!(dag.successors(&first.hash).count(), 2);
+}
+
+#[test]
+fn digest_is_canonical() {
+    let graph = WarpGraph::new();
+    let mut s = EchoScheduler::new();
+    let a = s.dependencies(&graph, vec![set_time(1), set_time(2), create()]);
+    let b = s.dependencies(&graph, vec![create(), set_time(2), set_time(1)]);
+    assert_eq!(a, b);
+    assert_eq!(a.digest(), b.digest());
+
+    let c = s.dependencies(&graph, vec![set_time(1), create()]);
+    assert_ne!(a.digest(), c.digest());
+}
+
+#[test]
+fn renders_as_graphviz() {
+    let graph = WarpGraph::new();
+    let mut s = EchoScheduler::new();
+    let report = s.explain(&graph, vec![set_time(1), set_time(2)]);
+    let dag = report.dependencies();
+    let dot = dag.to_dot();
+    assert!(dot.starts_with("digraph dependencies {"));
+    let edge = &dag.edges[0];
+    assert!(dot.contains(&format!("\"{}\" -> \"{}\";", edge.from, edge.to)));
+    assert!(dot.contains("deferred"));
+}
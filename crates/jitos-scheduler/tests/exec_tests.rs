@@ -0,0 +1,97 @@
+use jitos_core::{Hash, Slap};
+use jitos_graph::{NodeId, WarpGraph, WarpNode};
+use jitos_scheduler::{execute_batch, execute_sequential, EchoScheduler, ExecError};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn graph() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    for byte in 1..=6 {
+        g.insert_node(WarpNode {
+            id: NodeId::from_hash(Hash([byte; 32])),
+            node_type: "task".to_string(),
+            payload_bytes: vec![byte],
+            attachment: None,
+        });
+    }
+    g
+}
+
+fn proposals() -> Vec<Slap> {
+    vec![
+        Slap::CreateNode {
+            node_type: "task".to_string(),
+            payload_bytes: vec![7],
+        },
+        Slap::CreateNode {
+            node_type: "note".to_string(),
+            payload_bytes: vec![8],
+        },
+        Slap::DeleteNode { id: node_id(1) },
+        Slap::Connect {
+            source: node_id(2),
+            target: node_id(3),
+            edge_type: "next".to_string(),
+        },
+        Slap::Connect {
+            source: node_id(4),
+            target: node_id(5),
+            edge_type: "next".to_string(),
+        },
+        Slap::SetTime { tick: 1, dt: 0.5 },
+    ]
+}
+
+#[test]
+fn parallel_batch_matches_sequential_application() {
+    let base = graph();
+    let schedule = EchoScheduler::new().schedule(&base, proposals());
+    assert!(schedule.deferred.is_empty(), "proposals are independent");
+    let batch = &schedule.batches[0];
+
+    let mut parallel = base.clone();
+    let digest = execute_batch(&mut parallel, batch).unwrap();
+    let mut sequential = base.clone();
+    assert_eq!(execute_sequential(&mut sequential, batch).unwrap(), digest);
+    assert_eq!(parallel.compute_hash(), digest);
+
+    assert_eq!(parallel.nodes.len(), 6 - 1 + 2);
+    assert_eq!(parallel.edges.len(), 2);
+    assert_eq!(parallel.nodes_of_type("note").count(), 1);
+
+    // Batch order does not matter.
+    let mut reversed = batch.clone();
+    reversed.reverse();
+    let mut again = base.clone();
+    assert_eq!(execute_batch(&mut again, &reversed).unwrap(), digest);
+}
+
+#[test]
+fn failed_batches_leave_the_graph_untouched() {
+    let base = graph();
+    let before = base.compute_hash();
+
+    let mut g = base.clone();
+    let err = execute_batch(
+        &mut g,
+        &[
+            Slap::DeleteNode { id: node_id(2) },
+            Slap::DeleteNode { id: node_id(9) },
+        ],
+    )
+    .unwrap_err();
+    assert!(matches!(err, ExecError::UnknownNode { ref node, .. } if *node == node_id(9)));
+    assert_eq!(g.compute_hash(), before);
+
+    let err = execute_batch(
+        &mut g,
+        &[Slap::Collapse {
+            sws_id: "sws-a".into(),
+        }],
+    )
+    .unwrap_err();
+    assert!(matches!(err, ExecError::Unsupported { op: "Collapse", .. }));
+    assert_eq!(g.compute_hash(), before);
+}
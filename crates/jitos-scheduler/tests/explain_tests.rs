@@ -0,0 +1,90 @@
+use jitos_core::{canonical, Hash, NodeId, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::{ConflictKind, EchoScheduler, Placement, SchedulerPolicy};
+
+fn node(name: &str) -> NodeId {
+    NodeId::from_hash(canonical::hash_canonical(&name).expect("hash"))
+}
+
+fn hash(slap: &Slap) -> Hash {
+    canonical::hash_canonical(slap).expect("hash")
+}
+
+fn proposals() -> Vec<Slap> {
+    vec![
+        Slap::DeleteNode { id: node("a") },
+        Slap::Connect {
+            source: node("a"),
+            target: node("b"),
+            edge_type: "next".to_string(),
+        },
+        Slap::SetTime { tick: 1, dt: 0.5 },
+        Slap::SetTime { tick: 2, dt: 0.5 },
+    ]
+}
+
+#[test]
+fn report_matches_the_schedule() {
+    let graph = WarpGraph::new();
+    let mut s = EchoScheduler::new();
+    let report = s.explain(&graph, proposals());
+    let schedule = s.schedule(&graph, proposals());
+
+    let batches: Vec<Vec<Hash>> = schedule
+        .batches
+        .iter()
+        .map(|b| b.iter().map(hash).collect())
+        .collect();
+    assert_eq!(report.batches(), batches);
+    let deferred: Vec<Hash> = schedule.deferred.iter().map(|(s, _)| hash(s)).collect();
+    assert_eq!(report.deferred(), deferred);
+
+    for (slap, reason) in &schedule.deferred {
+        let entry = report.get(&hash(slap)).expect("reported");
+        assert_eq!(entry.placement, Placement::Deferred(reason.clone()));
+        assert!(entry
+            .conflicts
+            .iter()
+            .any(|(other, _)| Some(*other) == reason.blocked_by()));
+    }
+    assert_eq!(report.policy, s.policy().policy_hash());
+    assert!(s.deferred().is_empty(), "dry run leaves the queue alone");
+}
+
+#[test]
+fn report_lists_keys_footprints_and_all_conflicts() {
+    let graph = WarpGraph::new();
+    let mut policy = SchedulerPolicy::default();
+    policy.priority_weights.insert("Connect".to_string(), 3);
+    let mut s = EchoScheduler::with_policy(policy);
+    let report = s.explain(&graph, proposals());
+
+    let hashes: Vec<Hash> = report.proposals.iter().map(|p| p.key.hash).collect();
+    assert!(hashes.windows(2).all(|w| w[0] < w[1]));
+
+    let connect = proposals()[1].clone();
+    let entry = report.get(&hash(&connect)).unwrap();
+    assert_eq!(entry.key.weight, 3);
+    assert_eq!(entry.rank, 0, "highest weight is visited first");
+    assert_eq!(entry.placement, Placement::Batch(0));
+    assert_eq!(entry.footprint, s.footprint(&connect, &graph).unwrap());
+
+    // Connect reads the node DeleteNode writes, and vice versa.
+    let delete = proposals()[0].clone();
+    assert!(matches!(
+        entry.conflicts.as_slice(),
+        [(h, ConflictKind::ReadWrite { .. })] if *h == hash(&delete)
+    ));
+    let entry = report.get(&hash(&delete)).unwrap();
+    assert!(matches!(
+        entry.conflicts.as_slice(),
+        [(h, ConflictKind::WriteRead { .. })] if *h == hash(&connect)
+    ));
+    assert!(
+        matches!(entry.placement, Placement::Deferred(ref r) if r.blocked_by() == Some(hash(&connect)))
+    );
+
+    let text = report.to_string();
+    assert!(text.starts_with("schedule report: 4 proposals"));
+    assert_eq!(text.matches("deferred, blocked by").count(), 2);
+}
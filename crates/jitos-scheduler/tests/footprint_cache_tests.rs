@@ -0,0 +1,106 @@
+use jitos_core::{canonical, Hash, Slap};
+use jitos_graph::{NodeId, WarpEdge, WarpGraph, WarpNode};
+use jitos_scheduler::footprint::edge_key;
+use jitos_scheduler::{CacheKey, CacheStats, EchoScheduler};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn graph() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    for byte in [1, 2] {
+        g.insert_node(WarpNode {
+            id: NodeId::from_hash(Hash([byte; 32])),
+            node_type: "task".to_string(),
+            payload_bytes: vec![],
+            attachment: None,
+        });
+    }
+    g
+}
+
+fn set_time(tick: u64) -> Slap {
+    Slap::SetTime { tick, dt: 0.5 }
+}
+
+#[test]
+fn counts_hits_and_misses() {
+    let mut s = EchoScheduler::new();
+    let g = WarpGraph::new();
+    s.footprint(&set_time(1), &g).unwrap();
+    s.footprint(&set_time(1), &g).unwrap();
+    s.footprint(&set_time(2), &g).unwrap();
+    assert_eq!(
+        s.footprint_cache().stats(),
+        CacheStats {
+            hits: 1,
+            misses: 2,
+            evictions: 0
+        }
+    );
+
+    // Graph-independent footprints are shared across graphs.
+    s.footprint(&set_time(1), &graph()).unwrap();
+    assert_eq!(s.footprint_cache().stats().hits, 2);
+    assert_eq!(s.footprint_cache().len(), 2);
+}
+
+#[test]
+fn graph_changes_never_serve_stale_footprints() {
+    let mut s = EchoScheduler::new();
+    let mut g = graph();
+    let delete = Slap::DeleteNode { id: node_id(2) };
+
+    assert!(s.footprint(&delete, &g).unwrap().e_write.is_empty());
+
+    let a = g.node_key(&NodeId::from_hash(Hash([1; 32]))).unwrap();
+    let b = g.node_key(&NodeId::from_hash(Hash([2; 32]))).unwrap();
+    g.edges.insert(WarpEdge {
+        source: a,
+        target: b,
+        edge_type: "next".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    });
+    let fp = s.footprint(&delete, &g).unwrap();
+    assert_eq!(fp.e_write, vec![edge_key(node_id(1), node_id(2), "next")]);
+    assert_eq!(s.footprint_cache().stats().hits, 0);
+
+    let key = CacheKey {
+        slap: canonical::hash_canonical(&delete).unwrap(),
+        graph: Some(g.compute_hash()),
+    };
+    assert!(s.footprint_cache().contains(&key));
+}
+
+#[test]
+fn least_recently_used_entries_are_evicted() {
+    let mut s = EchoScheduler::new();
+    s.set_footprint_cache_capacity(2);
+    let g = WarpGraph::new();
+    let key = |tick| CacheKey {
+        slap: canonical::hash_canonical(&set_time(tick)).unwrap(),
+        graph: None,
+    };
+
+    s.footprint(&set_time(1), &g).unwrap();
+    s.footprint(&set_time(2), &g).unwrap();
+    s.footprint(&set_time(1), &g).unwrap(); // 2 is now least recent
+    s.footprint(&set_time(3), &g).unwrap();
+
+    let cache = s.footprint_cache();
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains(&key(1)));
+    assert!(!cache.contains(&key(2)));
+    assert!(cache.contains(&key(3)));
+    assert_eq!(cache.stats().evictions, 1);
+
+    s.set_footprint_cache_capacity(0);
+    assert!(s.footprint_cache().is_empty());
+    s.footprint(&set_time(1), &g).unwrap();
+    assert!(
+        s.footprint_cache().is_empty(),
+        "capacity 0 disables caching"
+    );
+}
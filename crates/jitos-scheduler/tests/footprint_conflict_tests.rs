@@ -0,0 +1,141 @@
+use jitos_graph::antichain::Independent;
+use jitos_scheduler::footprint::{edge_key, node_key, prefix_key, range_key, ALL_NODES};
+use jitos_scheduler::{ConflictKind, Footprint, Resource};
+
+fn reads(nodes: &[&str]) -> Footprint {
+    Footprint {
+        n_read: nodes.iter().map(node_key).collect(),
+        ..Footprint::default()
+    }
+}
+
+fn writes(nodes: &[&str]) -> Footprint {
+    Footprint {
+        n_write: nodes.iter().map(node_key).collect(),
+        ..Footprint::default()
+    }
+}
+
+#[test]
+fn disjoint_and_read_only_footprints_are_independent() {
+    assert_eq!(writes(&["a"]).conflicts_with(&writes(&["b"])), None);
+    assert_eq!(reads(&["a"]).conflicts_with(&reads(&["a"])), None);
+    assert!(reads(&["a"]).independent_of(&reads(&["a"])));
+}
+
+#[test]
+fn write_write_and_read_write_are_distinguished() {
+    assert_eq!(
+        writes(&["a"]).conflicts_with(&writes(&["a"])),
+        Some(ConflictKind::WriteWrite {
+            resource: Resource::Node,
+            ours: node_key("a"),
+            theirs: node_key("a"),
+        })
+    );
+    assert_eq!(
+        reads(&["a"]).conflicts_with(&writes(&["a"])),
+        Some(ConflictKind::ReadWrite {
+            resource: Resource::Node,
+            ours: node_key("a"),
+            theirs: node_key("a"),
+        })
+    );
+    assert_eq!(
+        writes(&["a"]).conflicts_with(&reads(&["a"])),
+        Some(ConflictKind::WriteRead {
+            resource: Resource::Node,
+            ours: node_key("a"),
+            theirs: node_key("a"),
+        })
+    );
+}
+
+#[test]
+fn nodes_and_edges_do_not_alias() {
+    let edge = Footprint {
+        e_write: vec![edge_key("a", "b", "next")],
+        ..Footprint::default()
+    };
+    assert_eq!(writes(&["a"]).conflicts_with(&edge), None);
+    assert!(matches!(
+        edge.conflicts_with(&edge),
+        Some(ConflictKind::WriteWrite {
+            resource: Resource::Edge,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn prefix_wildcards_cover_their_namespace() {
+    let all = Footprint {
+        n_write: vec![ALL_NODES.to_string()],
+        ..Footprint::default()
+    };
+    assert_eq!(
+        reads(&["x"]).conflicts_with(&all),
+        Some(ConflictKind::ReadWrite {
+            resource: Resource::Node,
+            ours: node_key("x"),
+            theirs: ALL_NODES.to_string(),
+        })
+    );
+
+    let tasks = Footprint {
+        n_write: vec![prefix_key(&node_key("task/"))],
+        ..Footprint::default()
+    };
+    assert!(tasks.conflicts_with(&writes(&["task/7"])).is_some());
+    assert!(tasks.conflicts_with(&writes(&["timer/7"])).is_none());
+    assert!(
+        tasks.conflicts_with(&all).is_some(),
+        "nested prefixes overlap"
+    );
+}
+
+#[test]
+fn intervals_overlap_half_open() {
+    let range = |lo: &str, hi: &str| Footprint {
+        n_write: vec![range_key(&node_key(lo), &node_key(hi))],
+        ..Footprint::default()
+    };
+    let a_to_m = range("a", "m");
+
+    assert!(a_to_m.conflicts_with(&writes(&["c"])).is_some());
+    assert!(
+        a_to_m.conflicts_with(&writes(&["m"])).is_none(),
+        "hi is exclusive"
+    );
+    assert!(a_to_m.conflicts_with(&range("l", "z")).is_some());
+    assert!(a_to_m.conflicts_with(&range("m", "z")).is_none());
+
+    let prefix = |p: &str| Footprint {
+        n_read: vec![prefix_key(&node_key(p))],
+        ..Footprint::default()
+    };
+    assert!(prefix("b").conflicts_with(&a_to_m).is_some());
+    assert!(prefix("q").conflicts_with(&a_to_m).is_none());
+    // Range starting inside the prefix's key space.
+    assert!(prefix("a").conflicts_with(&range("ab", "b")).is_some());
+}
+
+#[test]
+fn conflict_report_is_deterministic_and_symmetric_in_existence() {
+    let x = Footprint {
+        n_read: vec![node_key("b")],
+        n_write: vec![node_key("a")],
+        ..Footprint::default()
+    };
+    let y = Footprint {
+        n_read: vec![node_key("a")],
+        n_write: vec![node_key("b")],
+        ..Footprint::default()
+    };
+    // Both a read/write and a write/read overlap exist; read/write wins.
+    assert!(matches!(
+        x.conflicts_with(&y),
+        Some(ConflictKind::ReadWrite { .. })
+    ));
+    assert_eq!(x.independent_of(&y), y.independent_of(&x));
+}
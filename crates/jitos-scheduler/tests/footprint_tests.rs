@@ -0,0 +1,170 @@
+use jitos_core::events::CanonicalBytes;
+use jitos_core::{Hash, Slap};
+use jitos_graph::{GraphTxn, NodeId, WarpEdge, WarpGraph, WarpNode};
+use jitos_scheduler::footprint::{edge_key, node_key, ALL_EDGES, ALL_NODES, SYS_TIME};
+use jitos_scheduler::{EchoScheduler, Footprint};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn graph_with_edge() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    let mut add = |byte: u8| {
+        g.insert_node(WarpNode {
+            id: node_id(byte),
+            node_type: "task".to_string(),
+            payload_bytes: vec![],
+            attachment: None,
+        })
+    };
+    let a = add(1);
+    let b = add(2);
+    g.edges.insert(WarpEdge {
+        source: a,
+        target: b,
+        edge_type: "next".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    });
+    g
+}
+
+#[test]
+fn connect_reads_endpoints_and_writes_edge() {
+    let mut s = EchoScheduler::new();
+    let fp = s
+        .footprint(
+            &Slap::Connect {
+                source: node_id(2),
+                target: node_id(1),
+                edge_type: "link".to_string(),
+            },
+            &WarpGraph::new(),
+        )
+        .expect("footprint");
+    assert_eq!(fp.n_read, vec![node_key(node_id(1)), node_key(node_id(2))]);
+    assert_eq!(fp.e_write, vec![edge_key(node_id(2), node_id(1), "link")]);
+    assert!(fp.n_write.is_empty());
+}
+
+#[test]
+fn delete_node_writes_node_and_incident_edges() {
+    let mut s = EchoScheduler::new();
+    let graph = graph_with_edge();
+    let slap = Slap::DeleteNode { id: node_id(2) };
+
+    let fp = s.footprint(&slap, &graph).expect("footprint");
+    assert_eq!(fp.n_write, vec![node_key(node_id(2))]);
+    assert_eq!(fp.e_write, vec![edge_key(node_id(1), node_id(2), "next")]);
+
+    // Cached per graph digest: an empty graph drops the edges.
+    let fp = s.footprint(&slap, &WarpGraph::new()).expect("footprint");
+    assert!(fp.e_write.is_empty());
+    assert_eq!(s.footprint_cache().len(), 2);
+}
+
+#[test]
+fn create_set_time_and_collapse() {
+    let mut s = EchoScheduler::new();
+    let g = WarpGraph::new();
+    let create = |payload: u8| Slap::CreateNode {
+        node_type: "task".to_string(),
+        payload_bytes: vec![payload],
+    };
+
+    let a = s.footprint(&create(1), &g).expect("footprint");
+    let b = s.footprint(&create(2), &g).expect("footprint");
+    assert_eq!(a.n_write.len(), 1);
+    assert!(a.n_write[0].starts_with("node:new:"));
+    assert_ne!(
+        a.n_write, b.n_write,
+        "each creation writes its own new node"
+    );
+
+    let time = s
+        .footprint(&Slap::SetTime { tick: 3, dt: 0.5 }, &g)
+        .expect("footprint");
+    assert_eq!(time.n_write, vec![SYS_TIME.to_string()]);
+
+    let collapse = s
+        .footprint(
+            &Slap::Collapse {
+                sws_id: "sws-1".into(),
+            },
+            &g,
+        )
+        .expect("footprint");
+    assert!(collapse.n_write.contains(&ALL_NODES.to_string()));
+    assert_eq!(collapse.e_write, vec![ALL_EDGES.to_string()]);
+}
+
+#[test]
+fn scripts_use_declared_access_or_global() {
+    let mut s = EchoScheduler::new();
+    let g = WarpGraph::new();
+    let script = Hash([7; 32]);
+    let invoke = Slap::InvokeScript {
+        script_id: script,
+        args: vec![CanonicalBytes::from_value(&1u64).expect("arg")],
+    };
+
+    assert_eq!(
+        s.footprint(&invoke, &g).expect("footprint"),
+        Footprint::global()
+    );
+
+    s.declare_script_access(
+        script,
+        Footprint {
+            n_read: vec![node_key("z"), node_key("a"), node_key("a")],
+            ..Footprint::default()
+        },
+    );
+    let fp = s.footprint(&invoke, &g).expect("footprint");
+    assert_eq!(fp.n_read, vec![node_key("a"), node_key("z")]);
+    assert!(fp.n_write.is_empty());
+}
+
+#[test]
+fn transaction_footprint_matches_the_inferred_one() {
+    let mut s = EchoScheduler::new();
+    let mut graph = graph_with_edge();
+    let slap = Slap::DeleteNode { id: node_id(2) };
+    let inferred = s.footprint(&slap, &graph).expect("footprint");
+
+    let mut txn = GraphTxn::begin(&mut graph);
+    txn.remove_node(node_id(2)).expect("node exists");
+    let touched = Footprint::from(&txn.abort());
+    assert_eq!(touched, inferred);
+}
+
+#[test]
+fn declared_access_covers_but_does_not_widen() {
+    use jitos_scheduler::footprint::{prefix_key, range_key};
+    use jitos_scheduler::Resource;
+
+    let access = Footprint {
+        n_read: vec![node_key(node_id(1))],
+        n_write: vec![prefix_key("node:new:")],
+        e_write: vec![range_key("edge:a", "edge:c")],
+        ..Footprint::default()
+    };
+    let a = node_key(node_id(1));
+    assert!(access.permits_read(Resource::Node, &a));
+    assert!(!access.permits_write(Resource::Node, &a));
+    assert!(!access.permits_read(Resource::Node, &node_key(node_id(2))));
+    assert!(!access.permits_read(Resource::Node, ALL_NODES));
+
+    // Writes imply reads; prefixes cover narrower prefixes.
+    assert!(access.permits_read(Resource::Node, "node:new:x"));
+    assert!(access.permits_write(Resource::Node, "node:new:x*"));
+    assert!(!access.permits_write(Resource::Node, "node:*"));
+
+    assert!(access.permits_write(Resource::Edge, "edge:b"));
+    assert!(!access.permits_write(Resource::Edge, "edge:c"));
+    assert!(
+        !access.permits_write(Resource::Node, "edge:b"),
+        "classes are separate"
+    );
+}
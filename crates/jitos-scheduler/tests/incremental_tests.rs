@@ -0,0 +1,163 @@
+use jitos_core::{canonical, Hash, Slap};
+use jitos_graph::{NodeId, WarpEdge, WarpGraph, WarpNode};
+use jitos_scheduler::{EchoScheduler, Schedule, SchedulerPolicy};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn hash(slap: &Slap) -> Hash {
+    canonical::hash_canonical(slap).expect("hash")
+}
+
+fn graph() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    for byte in [1, 2] {
+        g.insert_node(WarpNode {
+            id: NodeId::from_hash(Hash([byte; 32])),
+            node_type: "task".to_string(),
+            payload_bytes: vec![],
+            attachment: None,
+        });
+    }
+    g
+}
+
+fn set_time(tick: u64) -> Slap {
+    Slap::SetTime { tick, dt: 0.5 }
+}
+
+fn delete(id: NodeId) -> Slap {
+    Slap::DeleteNode { id }
+}
+
+fn create(byte: u8) -> Slap {
+    Slap::CreateNode {
+        node_type: "task".to_string(),
+        payload_bytes: vec![byte],
+    }
+}
+
+fn shape(schedule: &Schedule) -> (Vec<Vec<Hash>>, Vec<Hash>) {
+    let batches = schedule
+        .batches
+        .iter()
+        .map(|batch| batch.iter().map(hash).collect())
+        .collect();
+    let deferred = schedule.deferred.iter().map(|(s, _)| hash(s)).collect();
+    (batches, deferred)
+}
+
+fn two_batches() -> EchoScheduler {
+    EchoScheduler::with_max_batches(2)
+}
+
+#[test]
+fn incremental_ticks_match_full_recomputation() {
+    let g = graph();
+    let arrivals: Vec<Vec<Slap>> = vec![
+        vec![set_time(1), set_time(2), set_time(3), delete(node_id(1))],
+        vec![create(1), set_time(4)],
+        vec![],
+        vec![delete(node_id(2)), set_time(5)],
+        vec![],
+        vec![],
+    ];
+
+    let mut full = two_batches();
+    let mut incremental = two_batches();
+    incremental.set_incremental(true);
+    for proposals in arrivals {
+        let expected = full.tick(&g, proposals.clone());
+        let actual = incremental.tick(&g, proposals);
+        assert_eq!(shape(&actual), shape(&expected));
+        assert_eq!(
+            incremental.deferred().state_hash(),
+            full.deferred().state_hash()
+        );
+    }
+}
+
+#[test]
+fn unchanged_proposals_reuse_the_previous_assignment() {
+    let g = graph();
+    let mut s = EchoScheduler::new();
+    s.set_incremental(true);
+    let proposals = vec![set_time(1), set_time(2), create(1)];
+
+    let first = s.schedule(&g, proposals.clone());
+    let second = s.schedule(&g, proposals.clone());
+    assert_eq!(shape(&first), shape(&second));
+
+    let stats = s.incremental().unwrap().stats();
+    assert_eq!(stats.resolved, 1);
+    assert_eq!(stats.reused, 1);
+    assert_eq!(stats.footprints_computed, 3);
+}
+
+#[test]
+fn only_new_slaps_are_inferred_and_checked() {
+    let g = graph();
+    let mut s = EchoScheduler::new();
+    s.set_incremental(true);
+    s.schedule(&g, vec![set_time(1), set_time(2), create(1)]);
+    let before = s.incremental().unwrap().stats();
+
+    // set_time(2) leaves, create(2) arrives.
+    let proposals = vec![set_time(1), create(1), create(2)];
+    let schedule = s.schedule(&g, proposals.clone());
+    let stats = s.incremental().unwrap().stats();
+    assert_eq!(stats.footprints_computed - before.footprints_computed, 1);
+    // One new SLAP against two carried over, both directions.
+    assert_eq!(stats.pairs_checked - before.pairs_checked, 4);
+    assert_eq!(s.incremental().unwrap().len(), 3);
+
+    let mut full = EchoScheduler::new();
+    assert_eq!(shape(&schedule), shape(&full.schedule(&g, proposals)));
+}
+
+#[test]
+fn graph_changes_re_solve_graph_dependent_slaps() {
+    let mut g = graph();
+    let mut s = EchoScheduler::new();
+    s.set_incremental(true);
+    let proposals = vec![set_time(1), delete(node_id(2))];
+    s.schedule(&g, proposals.clone());
+    s.schedule(&g, proposals.clone());
+    assert_eq!(s.incremental().unwrap().stats().footprints_computed, 2);
+
+    let a = g.node_key(&NodeId::from_hash(Hash([1; 32]))).unwrap();
+    let b = g.node_key(&NodeId::from_hash(Hash([2; 32]))).unwrap();
+    g.edges.insert(WarpEdge {
+        source: a,
+        target: b,
+        edge_type: "next".to_string(),
+        payload_bytes: None,
+        attachment: None,
+    });
+    let schedule = s.schedule(&g, proposals.clone());
+    let stats = s.incremental().unwrap().stats();
+    assert_eq!(
+        stats.footprints_computed, 3,
+        "only the DeleteNode re-inferred"
+    );
+    assert_eq!(stats.resolved, 2);
+
+    let mut full = EchoScheduler::new();
+    assert_eq!(shape(&schedule), shape(&full.schedule(&g, proposals)));
+}
+
+#[test]
+fn policy_state_changes_drop_incremental_state() {
+    let g = graph();
+    let mut s = EchoScheduler::with_policy(SchedulerPolicy::default());
+    s.set_incremental(true);
+    s.schedule(&g, vec![set_time(1), create(1)]);
+    assert_eq!(s.incremental().unwrap().len(), 2);
+
+    s.declare_script_access(Hash([9; 32]), Default::default());
+    assert!(s.incremental().unwrap().is_empty());
+
+    s.set_incremental(false);
+    assert!(s.incremental().is_none());
+}
@@ -0,0 +1,122 @@
+use jitos_core::{canonical, Hash, Proposal, Slap, SlapMeta};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::{EchoScheduler, SchedulerPolicy};
+
+fn hash(slap: &Slap) -> Hash {
+    canonical::hash_canonical(slap).expect("hash")
+}
+
+fn set_time(tick: u64) -> Slap {
+    Slap::SetTime { tick, dt: 0.5 }
+}
+
+fn proposal(tick: u64, priority: u32, deadline: Option<u64>) -> Proposal {
+    Proposal::new(
+        set_time(tick),
+        SlapMeta {
+            priority,
+            deadline,
+            agent: None,
+        },
+    )
+}
+
+/// SetTime proposals all conflict, so one tick admits exactly one: the
+/// admission order is the visit order.
+fn admission_order(policy: SchedulerPolicy, proposals: Vec<Proposal>) -> Vec<Hash> {
+    let graph = WarpGraph::new();
+    let mut s = EchoScheduler::with_policy(policy);
+    let mut order = Vec::new();
+    let mut pending = proposals;
+    loop {
+        let schedule = s.tick_proposals(&graph, std::mem::take(&mut pending));
+        match schedule.batches.first() {
+            Some(batch) => order.extend(batch.iter().map(hash)),
+            None => break,
+        }
+    }
+    order
+}
+
+#[test]
+fn priority_then_deadline_then_hash() {
+    let proposals = vec![
+        proposal(1, 0, None),
+        proposal(2, 0, Some(50)),
+        proposal(3, 5, Some(90)),
+        proposal(4, 0, Some(10)),
+        proposal(5, 5, Some(20)),
+    ];
+    // Ages grow equally for everything that waits, so the relative order of
+    // the original proposals is exactly the policy order.
+    let order = admission_order(SchedulerPolicy::priority_deadline(), proposals.clone());
+    let expected: Vec<Hash> = [5, 3, 4, 2, 1].map(set_time).iter().map(hash).collect();
+    assert_eq!(order, expected);
+
+    // Arrival order is irrelevant.
+    let mut reversed = proposals;
+    reversed.reverse();
+    assert_eq!(
+        admission_order(SchedulerPolicy::priority_deadline(), reversed),
+        expected
+    );
+}
+
+#[test]
+fn default_policy_ignores_metadata() {
+    let graph = WarpGraph::new();
+    let plain: Vec<Slap> = (1..=4).map(set_time).collect();
+    let with_meta: Vec<Proposal> = (1..=4)
+        .map(|t| proposal(t, t as u32, Some(100 - t)))
+        .collect();
+
+    let a = EchoScheduler::new().schedule(&graph, plain);
+    let b = EchoScheduler::new().schedule_proposals(&graph, with_meta);
+    assert_eq!(hash(&a.batches[0][0]), hash(&b.batches[0][0]));
+}
+
+#[test]
+fn ties_without_deadline_fall_back_to_hash() {
+    let graph = WarpGraph::new();
+    let proposals: Vec<Proposal> = (1..=4).map(|t| proposal(t, 1, None)).collect();
+    let schedule = EchoScheduler::with_policy(SchedulerPolicy::priority_deadline())
+        .schedule_proposals(&graph, proposals.clone());
+    let lowest = proposals.iter().map(|p| hash(&p.slap)).min().unwrap();
+    assert_eq!(hash(&schedule.batches[0][0]), lowest);
+}
+
+#[test]
+fn explain_reports_metadata_and_policy_hashes_differ() {
+    assert_ne!(
+        SchedulerPolicy::priority_deadline().policy_hash(),
+        SchedulerPolicy::default().policy_hash()
+    );
+
+    let mut s = EchoScheduler::with_policy(SchedulerPolicy::priority_deadline());
+    let report = s.explain_proposals(&WarpGraph::new(), vec![proposal(1, 7, Some(3))]);
+    let key = report.proposals[0].key;
+    assert_eq!((key.priority, key.deadline), (7, Some(3)));
+}
+
+#[test]
+fn waiting_slaps_keep_their_metadata() {
+    let graph = WarpGraph::new();
+    let mut s = EchoScheduler::with_policy(SchedulerPolicy::priority_deadline());
+    s.tick_proposals(&graph, vec![proposal(1, 9, None), proposal(2, 1, Some(4))]);
+    let waiting = s.deferred().get(&hash(&set_time(2))).expect("deferred");
+    assert_eq!(
+        waiting.meta,
+        SlapMeta {
+            priority: 1,
+            deadline: Some(4),
+            agent: None,
+        }
+    );
+
+    // Re-proposing it with other metadata does not replace the waiting copy.
+    s.tick_proposals(&graph, vec![proposal(3, 9, None), proposal(2, 0, None)]);
+    assert!(
+        s.deferred().get(&hash(&set_time(2))).is_none(),
+        "aged entry ran"
+    );
+}
@@ -0,0 +1,74 @@
+use jitos_core::{canonical, Hash, NodeId, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::radix::radix_sort_indices;
+use jitos_scheduler::{radix_order, EchoScheduler, Schedule};
+
+fn node(name: &str) -> NodeId {
+    NodeId::from_hash(canonical::hash_canonical(&name).expect("hash"))
+}
+
+fn proposals() -> Vec<Slap> {
+    let mut out: Vec<Slap> = (0..16)
+        .map(|i| Slap::DeleteNode {
+            id: node(&format!("node-{i}")),
+        })
+        .collect();
+    out.push(Slap::SetTime { tick: 1, dt: 0.25 });
+    out.push(Slap::Collapse {
+        sws_id: "sws-a".into(),
+    });
+    out
+}
+
+#[test]
+fn radix_order_matches_comparison_sort_by_canonical_hash() {
+    let order = radix_order(proposals()).expect("order");
+
+    let mut expected: Vec<Hash> = proposals()
+        .iter()
+        .map(|s| canonical::hash_canonical(s).expect("hash"))
+        .collect();
+    expected.sort();
+    assert_eq!(order.hashes, expected);
+
+    for (slap, hash) in order.proposals.iter().zip(&order.hashes) {
+        assert_eq!(canonical::hash_canonical(slap).expect("hash"), *hash);
+    }
+}
+
+#[test]
+fn arrival_order_does_not_matter() {
+    let forward = radix_order(proposals()).expect("order");
+    let mut reversed = proposals();
+    reversed.reverse();
+    let backward = radix_order(reversed).expect("order");
+    assert_eq!(forward.hashes, backward.hashes);
+
+    let mut scheduler = EchoScheduler::with_max_batches(u32::MAX);
+    let graph = WarpGraph::new();
+    let mut rotated = proposals();
+    rotated.rotate_left(5);
+    let a = scheduler.schedule(&graph, proposals());
+    let b = scheduler.schedule(&graph, rotated);
+    let hashes = |v: &[Slap]| -> Vec<Hash> {
+        v.iter()
+            .map(|s| canonical::hash_canonical(s).expect("hash"))
+            .collect()
+    };
+    let batch_hashes =
+        |s: &Schedule| -> Vec<Vec<Hash>> { s.batches.iter().map(|b| hashes(b)).collect() };
+    assert_eq!(batch_hashes(&a), batch_hashes(&b));
+    assert!(a.deferred.is_empty() && b.deferred.is_empty());
+}
+
+#[test]
+fn radix_sort_is_stable_and_orders_every_byte() {
+    let mut low = [0u8; 32];
+    low[31] = 1;
+    let mut high = [0u8; 32];
+    high[0] = 1;
+    let keys = [Hash(high), Hash(low), Hash([0; 32]), Hash(low)];
+
+    assert_eq!(radix_sort_indices(&keys), vec![2, 1, 3, 0]);
+    assert!(radix_sort_indices(&[]).is_empty());
+}
@@ -0,0 +1,151 @@
+use jitos_core::{canonical, Hash, NodeId, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::{ConflictKind, EchoScheduler, Footprint, Resource, Schedule};
+
+fn node(name: &str) -> NodeId {
+    NodeId::from_hash(canonical::hash_canonical(&name).expect("hash"))
+}
+
+fn hash(slap: &Slap) -> Hash {
+    canonical::hash_canonical(slap).expect("hash")
+}
+
+fn delete(id: &str) -> Slap {
+    Slap::DeleteNode { id: node(id) }
+}
+
+fn connect(source: &str, target: &str) -> Slap {
+    Slap::Connect {
+        source: node(source),
+        target: node(target),
+        edge_type: "next".to_string(),
+    }
+}
+
+fn proposals() -> Vec<Slap> {
+    vec![
+        delete("a"),
+        delete("b"),
+        connect("a", "c"),
+        connect("b", "c"),
+        Slap::SetTime { tick: 1, dt: 0.5 },
+        Slap::SetTime { tick: 2, dt: 0.5 },
+    ]
+}
+
+fn footprints(s: &mut EchoScheduler, graph: &WarpGraph, batch: &[Slap]) -> Vec<Footprint> {
+    batch
+        .iter()
+        .map(|slap| s.footprint(slap, graph).expect("footprint"))
+        .collect()
+}
+
+#[test]
+fn batches_are_conflict_free_and_sorted() {
+    let graph = WarpGraph::new();
+    let mut s = EchoScheduler::with_max_batches(u32::MAX);
+    let schedule = s.schedule(&graph, proposals());
+
+    assert!(schedule.deferred.is_empty());
+    assert_eq!(
+        schedule.batches.iter().map(Vec::len).sum::<usize>(),
+        proposals().len()
+    );
+    for batch in &schedule.batches {
+        let hashes: Vec<Hash> = batch.iter().map(hash).collect();
+        assert!(
+            hashes.windows(2).all(|w| w[0] < w[1]),
+            "batch in hash order"
+        );
+        let fps = footprints(&mut s, &graph, batch);
+        for (i, a) in fps.iter().enumerate() {
+            for b in &fps[i + 1..] {
+                assert_eq!(a.conflicts_with(b), None);
+            }
+        }
+    }
+}
+
+#[test]
+fn single_batch_defers_conflicts_with_reason() {
+    let graph = WarpGraph::new();
+    let mut s = EchoScheduler::new();
+    let schedule = s.schedule(&graph, proposals());
+
+    assert_eq!(schedule.batches.len(), 1);
+    let admitted: Vec<Hash> = schedule.batches[0].iter().map(hash).collect();
+    assert!(!schedule.deferred.is_empty());
+
+    let deferred: Vec<Hash> = schedule
+        .deferred
+        .iter()
+        .map(|(slap, _)| hash(slap))
+        .collect();
+    assert!(
+        deferred.windows(2).all(|w| w[0] < w[1]),
+        "deferred in hash order"
+    );
+
+    for (slap, reason) in &schedule.deferred {
+        let blocked_by = reason.blocked_by().expect("deferred by a conflict");
+        assert!(admitted.contains(&blocked_by));
+        let blocker = schedule.batches[0]
+            .iter()
+            .find(|b| hash(b) == blocked_by)
+            .expect("blocker admitted");
+        let ours = s.footprint(slap, &graph).expect("footprint");
+        let theirs = s.footprint(blocker, &graph).expect("footprint");
+        assert_eq!(ours.conflicts_with(&theirs).as_ref(), reason.conflict());
+    }
+
+    // Exactly one of the two SetTime proposals gets through.
+    let times = |slaps: &mut dyn Iterator<Item = &Slap>| {
+        slaps.filter(|s| matches!(s, Slap::SetTime { .. })).count()
+    };
+    assert_eq!(times(&mut schedule.batches[0].iter()), 1);
+    assert_eq!(times(&mut schedule.deferred.iter().map(|(s, _)| s)), 1);
+    let (_, reason) = schedule
+        .deferred
+        .iter()
+        .find(|(s, _)| matches!(s, Slap::SetTime { .. }))
+        .expect("deferred SetTime");
+    assert!(matches!(
+        reason.conflict(),
+        Some(ConflictKind::WriteWrite {
+            resource: Resource::Node,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn schedule_is_independent_of_arrival_order() {
+    let graph = WarpGraph::new();
+    let summary = |schedule: &Schedule| {
+        (
+            schedule
+                .batches
+                .iter()
+                .map(|b| b.iter().map(hash).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            schedule
+                .deferred
+                .iter()
+                .map(|(s, r)| (hash(s), r.clone()))
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    let forward = EchoScheduler::new().schedule(&graph, proposals());
+    let mut reversed = proposals();
+    reversed.reverse();
+    let backward = EchoScheduler::new().schedule(&graph, reversed);
+    assert_eq!(summary(&forward), summary(&backward));
+}
+
+#[test]
+fn empty_proposals_make_an_empty_schedule() {
+    let schedule = EchoScheduler::new().schedule(&WarpGraph::new(), Vec::new());
+    assert!(schedule.batches.is_empty());
+    assert!(schedule.deferred.is_empty());
+}
@@ -0,0 +1,129 @@
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventKind};
+use jitos_core::store::MemoryEventStore;
+use jitos_core::{canonical, Hash, NodeId, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::{BatchingStrategy, EchoScheduler, ScheduleDecision, SchedulerPolicy};
+
+fn node(name: &str) -> NodeId {
+    NodeId::from_hash(canonical::hash_canonical(&name).expect("hash"))
+}
+
+fn hash(slap: &Slap) -> Hash {
+    canonical::hash_canonical(slap).expect("hash")
+}
+
+fn times() -> Vec<Slap> {
+    (1..=4)
+        .map(|tick| Slap::SetTime { tick, dt: 0.5 })
+        .collect()
+}
+
+fn observation() -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&"proposals").unwrap(),
+        vec![],
+        None,
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn policy_hash_is_canonical_and_distinguishes_policies() {
+    let default = SchedulerPolicy::default();
+    assert_eq!(
+        default.policy_hash(),
+        SchedulerPolicy::default().policy_hash()
+    );
+    assert_eq!(
+        default.policy_hash(),
+        canonical::hash_canonical(&default).unwrap()
+    );
+
+    let mut weighted = SchedulerPolicy::default();
+    weighted.priority_weights.insert("SetTime".to_string(), 5);
+    assert_ne!(weighted.policy_hash(), default.policy_hash());
+
+    let batched = SchedulerPolicy {
+        batching: BatchingStrategy::Antichains { max_batches: 2 },
+        ..SchedulerPolicy::default()
+    };
+    assert_ne!(batched.policy_hash(), default.policy_hash());
+
+    // The PolicyContext payload decodes back to the policy.
+    let ctx = weighted.to_policy_context().unwrap();
+    assert_eq!(ctx.kind(), &EventKind::PolicyContext);
+    assert_eq!(
+        ctx.payload().to_value::<SchedulerPolicy>().unwrap(),
+        weighted
+    );
+}
+
+#[test]
+fn decisions_reference_the_policy_context() {
+    let graph = WarpGraph::new();
+    let mut scheduler = EchoScheduler::new();
+    let obs = observation();
+    let (schedule, decision) = scheduler
+        .decide(&graph, times(), vec![obs.event_id()])
+        .unwrap();
+
+    assert_eq!(decision.kind(), &EventKind::Decision);
+    let ctx = scheduler.policy_context().clone();
+    assert!(decision.parents().contains(&ctx.event_id()));
+    assert!(decision.parents().contains(&obs.event_id()));
+
+    let mut store = MemoryEventStore::new();
+    store.append(obs).unwrap();
+    store.append(ctx).unwrap();
+    store
+        .append(decision.clone())
+        .expect("well-formed decision");
+
+    let payload: ScheduleDecision = decision.payload().to_value().unwrap();
+    assert_eq!(payload.policy, scheduler.policy().policy_hash());
+    assert_eq!(payload.batches.len(), 1);
+    assert_eq!(payload.batches[0], vec![hash(&schedule.batches[0][0])]);
+    assert_eq!(payload.deferred.len(), 3);
+
+    assert!(scheduler.decide(&graph, times(), vec![]).is_err());
+}
+
+#[test]
+fn priority_weights_pick_what_is_packed_first() {
+    let graph = WarpGraph::new();
+    // Collapse writes every node, so it conflicts with both deletes.
+    let collapse = Slap::Collapse {
+        sws_id: "sws-a".into(),
+    };
+    let deletes: Vec<Slap> = ["a", "b"]
+        .into_iter()
+        .map(|id| Slap::DeleteNode { id: node(id) })
+        .collect();
+    let proposals: Vec<Slap> = deletes.iter().cloned().chain([collapse.clone()]).collect();
+    let batch = |policy: SchedulerPolicy| {
+        let schedule = EchoScheduler::with_policy(policy).schedule(&graph, proposals.clone());
+        let mut batch: Vec<Hash> = schedule.batches[0].iter().map(hash).collect();
+        batch.sort();
+        (batch, schedule.deferred)
+    };
+
+    let mut policy = SchedulerPolicy::default();
+    policy.priority_weights.insert("Collapse".to_string(), 1);
+    let (admitted, deferred) = batch(policy);
+    assert_eq!(admitted, vec![hash(&collapse)]);
+    assert_eq!(deferred.len(), 2);
+    assert!(deferred
+        .iter()
+        .all(|(_, reason)| reason.blocked_by() == Some(hash(&collapse))));
+
+    let mut policy = SchedulerPolicy::default();
+    policy.priority_weights.insert("DeleteNode".to_string(), 1);
+    let (admitted, deferred) = batch(policy);
+    let mut expected: Vec<Hash> = deletes.iter().map(hash).collect();
+    expected.sort();
+    assert_eq!(admitted, expected);
+    assert_eq!(deferred.len(), 1);
+    assert_eq!(hash(&deferred[0].0), hash(&collapse));
+}
@@ -0,0 +1,152 @@
+use jitos_core::events::CanonicalBytes;
+use jitos_core::{canonical, Hash, NodeId, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::{
+    ConflictReason, CostModel, DefaultCostModel, EchoScheduler, SchedulerPolicy,
+};
+
+fn node(name: &str) -> NodeId {
+    NodeId::from_hash(canonical::hash_canonical(&name).expect("hash"))
+}
+
+fn hash(slap: &Slap) -> Hash {
+    canonical::hash_canonical(slap).expect("hash")
+}
+
+fn delete(i: u32) -> Slap {
+    Slap::DeleteNode {
+        id: node(&format!("node-{i}")),
+    }
+}
+
+fn budgeted(budget: u64) -> EchoScheduler {
+    EchoScheduler::with_policy(SchedulerPolicy {
+        tick_budget: Some(budget),
+        ..SchedulerPolicy::default()
+    })
+}
+
+#[test]
+fn default_costs_depend_on_kind_and_payload_size() {
+    let model = DefaultCostModel::default();
+    let create = |len: usize| Slap::CreateNode {
+        node_type: "task".to_string(),
+        payload_bytes: vec![0; len],
+    };
+    assert_eq!(model.cost(&create(0)), 4);
+    assert_eq!(model.cost(&create(1)), 5);
+    assert_eq!(model.cost(&create(64)), 5);
+    assert_eq!(model.cost(&create(65)), 6);
+    assert_eq!(model.cost(&delete(1)), 2);
+    assert_eq!(model.cost(&Slap::SetTime { tick: 1, dt: 0.5 }), 1);
+
+    let invoke = Slap::InvokeScript {
+        script_id: Hash([1; 32]),
+        args: vec![CanonicalBytes::from_value(&vec![0u8; 100]).unwrap()],
+    };
+    assert!(model.cost(&invoke) > model.invoke_script);
+}
+
+#[test]
+fn filling_stops_when_the_budget_is_exhausted() {
+    let graph = WarpGraph::new();
+    // Independent deletes at cost 2 each: a budget of 7 fits three.
+    let proposals: Vec<Slap> = (0..6).map(delete).collect();
+    let schedule = budgeted(7).schedule(&graph, proposals.clone());
+
+    assert_eq!(schedule.batches.len(), 1);
+    assert_eq!(schedule.batches[0].len(), 3);
+    assert_eq!(schedule.deferred.len(), 3);
+    for (_, reason) in &schedule.deferred {
+        assert_eq!(
+            *reason,
+            ConflictReason::OverBudget {
+                cost: 2,
+                remaining: 1
+            }
+        );
+    }
+
+    // The admitted three are the first three in hash order.
+    let mut hashes: Vec<Hash> = proposals.iter().map(hash).collect();
+    hashes.sort();
+    let admitted: Vec<Hash> = schedule.batches[0].iter().map(hash).collect();
+    assert_eq!(admitted, hashes[..3]);
+
+    // Unbounded policy admits everything.
+    let all = EchoScheduler::new().schedule(&graph, proposals);
+    assert_eq!(all.batches[0].len(), 6);
+}
+
+#[test]
+fn an_expensive_slap_is_never_starved() {
+    let graph = WarpGraph::new();
+    let big = Slap::CreateNode {
+        node_type: "blob".to_string(),
+        payload_bytes: vec![0; 4096],
+    };
+    let mut s = budgeted(10);
+    assert!(s.cost(&big) > 10);
+
+    // Alone, it runs despite exceeding the budget.
+    let schedule = s.schedule(&graph, vec![big.clone()]);
+    assert_eq!(schedule.batches[0].len(), 1);
+
+    // Behind cheaper, higher-weight work it waits, then goes first once aged.
+    let mut policy = SchedulerPolicy {
+        tick_budget: Some(10),
+        ..SchedulerPolicy::default()
+    };
+    policy.priority_weights.insert("DeleteNode".to_string(), 1);
+    let mut s = EchoScheduler::with_policy(policy);
+    let first = s.tick(&graph, vec![big.clone(), delete(1), delete(2)]);
+    assert_eq!(first.batches[0].len(), 2);
+    assert!(matches!(
+        s.deferred().get(&hash(&big)).unwrap().reason,
+        ConflictReason::OverBudget { remaining: 6, .. }
+    ));
+    let second = s.tick(&graph, vec![delete(3)]);
+    assert_eq!(second.batches[0].len(), 1, "big exhausts the budget");
+    assert_eq!(hash(&second.batches[0][0]), hash(&big));
+    assert!(s.deferred().get(&hash(&delete(3))).is_some());
+}
+
+struct Flat;
+
+impl CostModel for Flat {
+    fn cost(&self, _: &Slap) -> u64 {
+        5
+    }
+}
+
+#[test]
+fn custom_cost_models_are_honoured() {
+    let graph = WarpGraph::new();
+    let mut s = budgeted(10);
+    s.set_cost_model(Flat);
+    let schedule = s.schedule(&graph, (0..4).map(delete).collect());
+    assert_eq!(schedule.batches[0].len(), 2);
+
+    let report = s.explain(&graph, (0..4).map(delete).collect());
+    assert!(report.proposals.iter().all(|p| p.cost == 5));
+    assert_eq!(report.deferred().len(), 2);
+    assert!(report.to_string().contains("over budget (cost 5, 0 left)"));
+}
+
+#[test]
+fn script_invocations_are_charged_their_declared_fuel() {
+    let script = Hash([5; 32]);
+    let invoke = Slap::InvokeScript {
+        script_id: script,
+        args: vec![CanonicalBytes::from_value(&1u8).expect("encode")],
+    };
+    let model = DefaultCostModel::default();
+    let base = model.cost(&invoke);
+    let metered = DefaultCostModel::default().with_script_fuel(script, 25_000);
+    assert_eq!(metered.cost(&invoke), base + 3);
+    let other = Slap::InvokeScript {
+        script_id: Hash([6; 32]),
+        args: vec![],
+    };
+    assert_eq!(metered.cost(&other), model.cost(&other));
+}
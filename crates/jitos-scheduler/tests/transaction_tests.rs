@@ -0,0 +1,131 @@
+use jitos_core::{Hash, Slap, Transaction};
+use jitos_graph::{NodeId, WarpGraph, WarpNode};
+use jitos_scheduler::{
+    execute_batch, CostModel, DefaultCostModel, EchoScheduler, ExecError, SchedulerPolicy,
+};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn graph() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    for byte in 1..=3 {
+        g.insert_node(WarpNode {
+            id: node_id(byte),
+            node_type: "task".to_string(),
+            payload_bytes: vec![byte],
+            attachment: None,
+        });
+    }
+    g
+}
+
+/// Create a note and attach it to `task`
+fn annotate(task: NodeId, text: u8) -> Slap {
+    Transaction::new(vec![
+        Slap::CreateNode {
+            node_type: "note".to_string(),
+            payload_bytes: vec![text],
+        },
+        Slap::Connect {
+            source: Transaction::created(0),
+            target: task,
+            edge_type: "about".to_string(),
+        },
+    ])
+    .into()
+}
+
+#[test]
+fn members_see_nodes_created_earlier_in_the_transaction() {
+    let mut g = graph();
+    let schedule = EchoScheduler::new().schedule(&g, vec![annotate(node_id(1), 9)]);
+    execute_batch(&mut g, &schedule.batches[0]).unwrap();
+
+    let (note_key, note) = g.nodes_of_type("note").next().unwrap();
+    assert_ne!(note.id, Transaction::created(0), "placeholder is resolved");
+    let edge = g.edges.values().next().unwrap();
+    assert_eq!(edge.source, note_key);
+    assert_eq!(edge.target, g.node_key(&node_id(1)).unwrap());
+}
+
+#[test]
+fn conflicting_transaction_is_deferred_whole() {
+    let g = graph();
+    let delete = Slap::DeleteNode { id: node_id(1) };
+    let bundle: Slap = Transaction::new(vec![
+        Slap::CreateNode {
+            node_type: "note".to_string(),
+            payload_bytes: vec![4],
+        },
+        Slap::Connect {
+            source: node_id(2),
+            target: node_id(1),
+            edge_type: "next".to_string(),
+        },
+    ])
+    .into();
+    let schedule = EchoScheduler::new().schedule(&g, vec![delete.clone(), bundle.clone()]);
+    assert_eq!(schedule.batches.len(), 1);
+    assert_eq!(schedule.batches[0].len(), 1);
+    assert_eq!(schedule.deferred.len(), 1);
+    let (deferred, reason) = &schedule.deferred[0];
+    assert!(reason.conflict().is_some());
+    // Whichever lost, it lost as a unit: no member was split off.
+    let placed = [&schedule.batches[0][0], deferred];
+    assert!(placed
+        .iter()
+        .any(|s| matches!(s, Slap::Transaction(t) if t.slaps.len() == 2)));
+
+    // The budget counts every member.
+    let cost = DefaultCostModel::default();
+    assert_eq!(
+        cost.cost(&bundle),
+        cost.cost(&Slap::CreateNode {
+            node_type: "note".to_string(),
+            payload_bytes: vec![4],
+        }) + cost.cost(&Slap::Connect {
+            source: node_id(2),
+            target: node_id(1),
+            edge_type: "next".to_string(),
+        })
+    );
+    let mut tight = EchoScheduler::with_policy(SchedulerPolicy {
+        tick_budget: Some(cost.cost(&bundle) - 1),
+        ..SchedulerPolicy::default()
+    });
+    let schedule = tight.schedule(&g, vec![Slap::SetTime { tick: 1, dt: 1.0 }, bundle]);
+    assert_eq!(schedule.batches[0].len(), 1);
+    assert!(matches!(schedule.deferred[0].0, Slap::Transaction(_)));
+}
+
+#[test]
+fn failing_member_rejects_the_whole_transaction() {
+    let base = graph();
+    let broken: Slap = Transaction::new(vec![
+        Slap::CreateNode {
+            node_type: "note".to_string(),
+            payload_bytes: vec![5],
+        },
+        Slap::DeleteNode { id: node_id(2) },
+        Slap::Connect {
+            source: Transaction::created(0),
+            target: node_id(2),
+            edge_type: "about".to_string(),
+        },
+    ])
+    .into();
+    let mut g = base.clone();
+    assert!(matches!(
+        execute_batch(&mut g, &[broken]),
+        Err(ExecError::UnknownNode { node, .. }) if node == node_id(2)
+    ));
+    assert_eq!(g.compute_hash(), base.compute_hash());
+
+    let nested: Slap = Transaction::new(vec![annotate(node_id(1), 1)]).into();
+    assert!(matches!(
+        execute_batch(&mut g, &[nested]),
+        Err(ExecError::Unsupported { .. })
+    ));
+}
@@ -0,0 +1,175 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Capability-Checked Host Calls
+//!
+//! What a script may see and request, shared by every guest runtime. Each
+//! host call is checked against the invocation's [`Capabilities`]; the first
+//! violation is remembered so the invocation fails even if the guest
+//! recovers.
+
+use std::collections::BTreeMap;
+
+use jitos_core::canonical;
+use jitos_core::events::CanonicalBytes;
+use jitos_core::{Hash, NodeId, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::footprint::{edge_key, node_key, ALL_NODES};
+
+use crate::capability::{Capabilities, Capability, Right, Violation};
+use crate::EmittedObservation;
+
+/// What a running script has done so far.
+#[derive(Debug, Default)]
+pub(crate) struct Effects {
+    pub slaps: Vec<Slap>,
+    pub observations: Vec<EmittedObservation>,
+    /// First request no capability covered.
+    pub violation: Option<Violation>,
+}
+
+/// A request no capability covers.
+#[derive(Debug)]
+pub(crate) struct Denied(pub String);
+
+/// A node as a script sees it.
+#[derive(Debug)]
+pub(crate) struct NodeView {
+    pub node_type: String,
+    pub payload: Vec<u8>,
+}
+
+/// The part of the graph a script's capabilities let it read.
+#[derive(Debug)]
+pub(crate) struct GraphView {
+    script: Hash,
+    caps: Capabilities,
+    nodes: BTreeMap<NodeId, NodeView>,
+    /// Edge keys incident to each writable node (deleting a node deletes them).
+    incident: BTreeMap<NodeId, Vec<String>>,
+}
+
+impl GraphView {
+    pub fn new(graph: &WarpGraph, script: Hash, caps: Capabilities) -> Self {
+        let mut nodes = BTreeMap::new();
+        for node in graph.nodes.values() {
+            if caps.permits(&Capability::new(Right::ReadNode, node_key(node.id))) {
+                let view = NodeView {
+                    node_type: node.node_type.clone(),
+                    payload: node.payload_bytes.clone(),
+                };
+                nodes.insert(node.id, view);
+            }
+        }
+        let mut incident: BTreeMap<NodeId, Vec<String>> = BTreeMap::new();
+        for edge in graph.edges.values() {
+            let (Some(source), Some(target)) =
+                (graph.nodes.get(edge.source), graph.nodes.get(edge.target))
+            else {
+                continue;
+            };
+            let key = edge_key(source.id, target.id, &edge.edge_type);
+            for id in [source.id, target.id] {
+                if caps.permits(&Capability::new(Right::WriteNode, node_key(id))) {
+                    incident.entry(id).or_default().push(key.clone());
+                }
+            }
+        }
+        Self {
+            script,
+            caps,
+            nodes,
+            incident,
+        }
+    }
+
+    /// The node `id`, or `None` if it does not exist.
+    pub fn read(&self, fx: &mut Effects, id: NodeId) -> Result<Option<&NodeView>, Denied> {
+        self.check(fx, Right::ReadNode, node_key(id))?;
+        Ok(self.nodes.get(&id))
+    }
+
+    /// Ids of every node of `node_type`, ascending. Requires reading all nodes.
+    pub fn nodes_of_type(&self, fx: &mut Effects, node_type: &str) -> Result<Vec<NodeId>, Denied> {
+        self.check(fx, Right::ReadNode, ALL_NODES)?;
+        Ok(self
+            .nodes
+            .iter()
+            .filter(|(_, node)| node.node_type == node_type)
+            .map(|(id, _)| *id)
+            .collect())
+    }
+
+    pub fn create_node(
+        &self,
+        fx: &mut Effects,
+        node_type: &str,
+        payload: Vec<u8>,
+    ) -> Result<(), Denied> {
+        let slap = Slap::CreateNode {
+            node_type: node_type.to_string(),
+            payload_bytes: payload,
+        };
+        let hash = canonical::hash_canonical(&slap).expect("CreateNode encodes canonically");
+        let key = node_key(format_args!("new:{hash}"));
+        self.check(fx, Right::WriteNode, key)?;
+        fx.slaps.push(slap);
+        Ok(())
+    }
+
+    pub fn delete_node(&self, fx: &mut Effects, id: NodeId) -> Result<(), Denied> {
+        self.check(fx, Right::WriteNode, node_key(id))?;
+        for edge in self.incident.get(&id).into_iter().flatten() {
+            self.check(fx, Right::WriteEdge, edge.as_str())?;
+        }
+        fx.slaps.push(Slap::DeleteNode { id });
+        Ok(())
+    }
+
+    pub fn connect(
+        &self,
+        fx: &mut Effects,
+        source: NodeId,
+        target: NodeId,
+        edge_type: &str,
+    ) -> Result<(), Denied> {
+        self.check(fx, Right::ReadNode, node_key(source))?;
+        self.check(fx, Right::ReadNode, node_key(target))?;
+        self.check(fx, Right::WriteEdge, edge_key(source, target, edge_type))?;
+        fx.slaps.push(Slap::Connect {
+            source,
+            target,
+            edge_type: edge_type.to_string(),
+        });
+        Ok(())
+    }
+
+    pub fn emit(
+        &self,
+        fx: &mut Effects,
+        observation_type: &str,
+        payload: CanonicalBytes,
+    ) -> Result<(), Denied> {
+        let request = Capability::emit(observation_type);
+        self.check(fx, request.right, request.key)?;
+        fx.observations.push(EmittedObservation {
+            observation_type: observation_type.to_string(),
+            payload,
+        });
+        Ok(())
+    }
+
+    /// Deny (and remember) a request no capability covers.
+    fn check(&self, fx: &mut Effects, right: Right, key: impl Into<String>) -> Result<(), Denied> {
+        let requested = Capability::new(right, key);
+        if self.caps.permits(&requested) {
+            return Ok(());
+        }
+        let message = format!("{requested} denied");
+        fx.violation.get_or_insert(Violation {
+            script_id: self.script,
+            requested,
+        });
+        Err(Denied(message))
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Script Capabilities
+//!
+//! A script's host API is gated by capabilities derived from the footprint
+//! its `InvokeScript` SLAP is scheduled with, so a script can never touch
+//! more than the scheduler accounted for:
+//! - node reads and writes come from `n_read` / `n_write` (a write implies
+//!   a read)
+//! - edge reads and writes come from `e_read` / `e_write`
+//! - `obs:<type>` keys in `n_write` grant emitting observations of that type
+//!
+//! A request no capability covers is a [`Violation`], which is recorded as
+//! an [`OBS_CAPABILITY_VIOLATION_V0`] observation.
+
+use std::fmt;
+
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventError, EventId};
+use jitos_core::Hash;
+use jitos_scheduler::footprint::{key_covers, obs_key};
+use jitos_scheduler::Footprint;
+use serde::{Deserialize, Serialize};
+
+/// Observation type of a recorded [`Violation`].
+pub const OBS_CAPABILITY_VIOLATION_V0: &str = "OBS_CAPABILITY_VIOLATION_V0";
+
+/// What a capability allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Right {
+    ReadNode,
+    WriteNode,
+    ReadEdge,
+    WriteEdge,
+    EmitObservation,
+}
+
+/// A right over a footprint key. Granted capabilities may use wildcard or
+/// range keys; requested ones name the exact key being accessed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Capability {
+    pub right: Right,
+    pub key: String,
+}
+
+impl Capability {
+    pub fn new(right: Right, key: impl Into<String>) -> Self {
+        Self {
+            right,
+            key: key.into(),
+        }
+    }
+
+    /// The capability to emit observations of `observation_type`.
+    pub fn emit(observation_type: &str) -> Self {
+        Self::new(Right::EmitObservation, obs_key(observation_type))
+    }
+
+    /// True if `self` grants everything `request` asks for.
+    pub fn covers(&self, request: &Capability) -> bool {
+        self.right == request.right && key_covers(&self.key, &request.key)
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}({})", self.right, self.key)
+    }
+}
+
+/// The capabilities one invocation holds, sorted and deduplicated.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities(Vec<Capability>);
+
+impl Capabilities {
+    /// Capabilities granted by `footprint`.
+    pub fn from_footprint(footprint: &Footprint) -> Self {
+        let mut caps = Vec::new();
+        for key in &footprint.n_read {
+            caps.push(Capability::new(Right::ReadNode, key.as_str()));
+        }
+        for key in &footprint.n_write {
+            if key.starts_with("obs:") {
+                caps.push(Capability::new(Right::EmitObservation, key.as_str()));
+            } else {
+                caps.push(Capability::new(Right::ReadNode, key.as_str()));
+                caps.push(Capability::new(Right::WriteNode, key.as_str()));
+            }
+        }
+        for key in &footprint.e_read {
+            caps.push(Capability::new(Right::ReadEdge, key.as_str()));
+        }
+        for key in &footprint.e_write {
+            caps.push(Capability::new(Right::ReadEdge, key.as_str()));
+            caps.push(Capability::new(Right::WriteEdge, key.as_str()));
+        }
+        caps.sort();
+        caps.dedup();
+        Self(caps)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Capability> {
+        self.0.iter()
+    }
+
+    /// True if some held capability covers `request`.
+    pub fn permits(&self, request: &Capability) -> bool {
+        self.0.iter().any(|cap| cap.covers(request))
+    }
+}
+
+/// A script asked for something it holds no capability for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Violation {
+    pub script_id: Hash,
+    pub requested: Capability,
+}
+
+impl Violation {
+    /// Record the violation as an observation with `parents`.
+    pub fn to_observation(&self, parents: Vec<EventId>) -> Result<EventEnvelope, EventError> {
+        EventEnvelope::new_observation(
+            CanonicalBytes::from_value(self)?,
+            parents,
+            Some(OBS_CAPABILITY_VIOLATION_V0.to_string()),
+            None,
+            None,
+        )
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "script {} lacks {}", self.script_id, self.requested)
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! JITOS Script
+//!
+//! Runs the scripts named by `Slap::InvokeScript`: Rhai source or WASM
+//! modules, stored in a [`jitos_core::blob::BlobStore`] under their hash. An
+//! invocation executes in a deterministic sandbox ([`ScriptSandbox`]) whose
+//! host API is limited to the [`Capabilities`] its footprint grants, and
+//! yields a [`ScriptResult`] that is recorded as an event.
+
+mod access;
+pub mod capability;
+mod rhai_vm;
+pub mod sandbox;
+pub mod wasm_vm;
+
+pub use capability::{Capabilities, Capability, Right, Violation, OBS_CAPABILITY_VIOLATION_V0};
+pub use sandbox::{
+    EmittedObservation, FloatPolicy, ScriptError, ScriptLimits, ScriptResult, ScriptSandbox, ARGS,
+    OBS_SCRIPT_RESULT_V0,
+};
@@ -0,0 +1,178 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rhai Guests
+//!
+//! Each invocation compiles the script into a fresh engine with no module
+//! resolver, no time source, no randomness and discarded `print`/`debug`
+//! output. Arguments are decoded into the `ARGS` array; graph access is the
+//! functions `has_node`, `node_type`, `node_payload`, `nodes_of_type`,
+//! `create_node`, `delete_node` and `connect`, with node ids as hex strings;
+//! `emit(type, value)` emits an observation.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use jitos_core::events::CanonicalBytes;
+use jitos_core::{Hash, NodeId};
+use rhai::{Array, Blob, Dynamic, Engine, EvalAltResult, Position, Scope};
+
+use crate::access::{Denied, Effects, GraphView};
+use crate::sandbox::Run;
+use crate::{ScriptError, ScriptLimits, ARGS};
+
+type RhaiResult<T> = Result<T, Box<EvalAltResult>>;
+
+pub(crate) fn run(
+    limits: &ScriptLimits,
+    view: GraphView,
+    script: Hash,
+    source: &str,
+    args: &[CanonicalBytes],
+) -> Run {
+    let view = Rc::new(view);
+    let effects = Rc::new(RefCell::new(Effects::default()));
+    let operations = Rc::new(RefCell::new(0u64));
+    let engine = engine(limits, &view, &effects, &operations);
+    let value = eval(&engine, limits, script, source, args);
+    let operations = *operations.borrow();
+    Run {
+        value,
+        effects: effects.take(),
+        operations,
+    }
+}
+
+fn eval(
+    engine: &Engine,
+    limits: &ScriptLimits,
+    script: Hash,
+    source: &str,
+    args: &[CanonicalBytes],
+) -> Result<CanonicalBytes, ScriptError> {
+    let mut arg_values = Array::with_capacity(args.len());
+    for (index, arg) in args.iter().enumerate() {
+        let value: Dynamic = arg.to_value().map_err(|source| ScriptError::Arg {
+            script,
+            index,
+            source,
+        })?;
+        arg_values.push(value);
+    }
+
+    let ast = engine.compile(source).map_err(|e| ScriptError::Compile {
+        script,
+        message: e.to_string(),
+    })?;
+    let mut scope = Scope::new();
+    scope.push_constant(ARGS, arg_values);
+    let value = engine
+        .eval_ast_with_scope::<Dynamic>(&mut scope, &ast)
+        .map_err(|e| match *e {
+            EvalAltResult::ErrorTooManyOperations(_) => ScriptError::OutOfBudget {
+                script,
+                limit: limits.max_operations,
+            },
+            e => ScriptError::Runtime {
+                script,
+                message: e.to_string(),
+            },
+        })?;
+    Ok(CanonicalBytes::from_value(&value)?)
+}
+
+fn engine(
+    limits: &ScriptLimits,
+    view: &Rc<GraphView>,
+    effects: &Rc<RefCell<Effects>>,
+    operations: &Rc<RefCell<u64>>,
+) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+    engine.set_max_operations(limits.max_operations);
+    engine.set_max_call_levels(limits.max_call_levels);
+    engine.set_max_expr_depths(limits.max_expr_depth, limits.max_expr_depth);
+    engine.set_max_string_size(limits.max_string_size);
+    engine.set_max_array_size(limits.max_array_size);
+    engine.set_max_map_size(limits.max_map_size);
+    let counter = Rc::clone(operations);
+    engine.on_progress(move |ops| {
+        *counter.borrow_mut() = ops;
+        None
+    });
+
+    let (v, fx) = (Rc::clone(view), Rc::clone(effects));
+    engine.register_fn("has_node", move |id: &str| -> RhaiResult<bool> {
+        let id = parse_id(id)?;
+        Ok(v.read(&mut fx.borrow_mut(), id).map_err(denied)?.is_some())
+    });
+    let (v, fx) = (Rc::clone(view), Rc::clone(effects));
+    engine.register_fn("node_type", move |id: &str| -> RhaiResult<Dynamic> {
+        let id = parse_id(id)?;
+        let node = v.read(&mut fx.borrow_mut(), id).map_err(denied)?;
+        Ok(node.map_or(Dynamic::UNIT, |n| n.node_type.clone().into()))
+    });
+    let (v, fx) = (Rc::clone(view), Rc::clone(effects));
+    engine.register_fn("node_payload", move |id: &str| -> RhaiResult<Dynamic> {
+        let id = parse_id(id)?;
+        let node = v.read(&mut fx.borrow_mut(), id).map_err(denied)?;
+        Ok(node.map_or(Dynamic::UNIT, |n| Dynamic::from_blob(n.payload.clone())))
+    });
+    let (v, fx) = (Rc::clone(view), Rc::clone(effects));
+    engine.register_fn(
+        "nodes_of_type",
+        move |node_type: &str| -> RhaiResult<Array> {
+            let ids = v
+                .nodes_of_type(&mut fx.borrow_mut(), node_type)
+                .map_err(denied)?;
+            Ok(ids.into_iter().map(|id| id.to_string().into()).collect())
+        },
+    );
+    let (v, fx) = (Rc::clone(view), Rc::clone(effects));
+    engine.register_fn(
+        "create_node",
+        move |node_type: &str, payload: Blob| -> RhaiResult<()> {
+            v.create_node(&mut fx.borrow_mut(), node_type, payload)
+                .map_err(denied)
+        },
+    );
+    let (v, fx) = (Rc::clone(view), Rc::clone(effects));
+    engine.register_fn("delete_node", move |id: &str| -> RhaiResult<()> {
+        let id = parse_id(id)?;
+        v.delete_node(&mut fx.borrow_mut(), id).map_err(denied)
+    });
+    let (v, fx) = (Rc::clone(view), Rc::clone(effects));
+    engine.register_fn(
+        "connect",
+        move |source: &str, target: &str, edge_type: &str| -> RhaiResult<()> {
+            let (source, target) = (parse_id(source)?, parse_id(target)?);
+            v.connect(&mut fx.borrow_mut(), source, target, edge_type)
+                .map_err(denied)
+        },
+    );
+    let (v, fx) = (Rc::clone(view), Rc::clone(effects));
+    engine.register_fn(
+        "emit",
+        move |observation_type: &str, value: Dynamic| -> RhaiResult<()> {
+            let payload =
+                CanonicalBytes::from_value(&value).map_err(|e| runtime_error(e.to_string()))?;
+            v.emit(&mut fx.borrow_mut(), observation_type, payload)
+                .map_err(denied)
+        },
+    );
+    engine
+}
+
+fn parse_id(id: &str) -> RhaiResult<NodeId> {
+    NodeId::from_hex(id).ok_or_else(|| runtime_error(format!("invalid node id {id:?}")))
+}
+
+fn denied(Denied(message): Denied) -> Box<EvalAltResult> {
+    runtime_error(message)
+}
+
+fn runtime_error(message: String) -> Box<EvalAltResult> {
+    EvalAltResult::ErrorRuntime(message.into(), Position::NONE).into()
+}
@@ -0,0 +1,257 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Script Sandbox
+//!
+//! A script is a blob: WASM modules (recognized by their magic number) run on
+//! wasmtime, anything else is Rhai source. Either way the result depends only
+//! on the script, the arguments and the graph it is shown, and execution is
+//! bounded by a deterministic budget (Rhai operations or WASM fuel).
+//!
+//! The host API is gated by [`Capabilities`] derived from the invocation's
+//! footprint: a request they do not cover fails the invocation with a
+//! [`Violation`], even if the script recovers from the error. Writes are not
+//! applied; they are collected as SLAPs in the [`ScriptResult`] for the
+//! scheduler to run like any other proposal, alongside the observations the
+//! script emitted.
+
+use jitos_core::blob::BlobStore;
+use jitos_core::canonical::CanonicalError;
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventError, EventId};
+use jitos_core::{Hash, Slap};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::{EchoScheduler, Footprint};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::access::{Effects, GraphView};
+use crate::capability::{Capabilities, Violation};
+use crate::rhai_vm;
+use crate::wasm_vm::WasmEngine;
+
+/// Observation type of a recorded [`ScriptResult`].
+pub const OBS_SCRIPT_RESULT_V0: &str = "OBS_SCRIPT_RESULT_V0";
+
+/// Leading bytes of every WASM module.
+const WASM_MAGIC: &[u8] = b"\0asm";
+
+/// Name of the argument array in a Rhai script's scope.
+pub const ARGS: &str = "ARGS";
+
+/// Script errors.
+#[derive(Debug, Error)]
+pub enum ScriptError {
+    #[error("SLAP is not an InvokeScript")]
+    NotAnInvocation,
+    #[error("script {0} is not in the blob store")]
+    NotFound(Hash),
+    #[error("script {0} is not UTF-8")]
+    NotUtf8(Hash),
+    #[error("script {script} does not compile: {message}")]
+    Compile { script: Hash, message: String },
+    #[error("script {script} cannot be instantiated: {message}")]
+    Instantiate { script: Hash, message: String },
+    #[error("script {script} failed: {message}")]
+    Runtime { script: Hash, message: String },
+    #[error("script {script} exceeded its budget of {limit} operations")]
+    OutOfBudget { script: Hash, limit: u64 },
+    #[error("{0}")]
+    Violation(Violation),
+    #[error("script {script} argument {index} cannot be decoded: {source}")]
+    Arg {
+        script: Hash,
+        index: usize,
+        source: CanonicalError,
+    },
+    #[error("canonical encoding error: {0}")]
+    Canonical(#[from] CanonicalError),
+    #[error("event error: {0}")]
+    Event(#[from] EventError),
+}
+
+/// How WASM guests may use floating point.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatPolicy {
+    /// Reject modules that use floats at all.
+    Forbid,
+    /// Allow floats, canonicalizing every NaN they produce.
+    #[default]
+    Canonicalize,
+}
+
+/// Deterministic resource limits for one invocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptLimits {
+    /// Rhai operations before the script is aborted.
+    pub max_operations: u64,
+    /// WASM fuel before the guest is aborted.
+    pub max_fuel: u64,
+    /// WASM linear memory, in bytes.
+    pub max_memory_bytes: usize,
+    pub float_policy: FloatPolicy,
+    pub max_call_levels: usize,
+    pub max_expr_depth: usize,
+    pub max_string_size: usize,
+    pub max_array_size: usize,
+    pub max_map_size: usize,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            max_operations: 100_000,
+            max_fuel: 10_000_000,
+            max_memory_bytes: 16 << 20,
+            float_policy: FloatPolicy::default(),
+            max_call_levels: 32,
+            max_expr_depth: 64,
+            max_string_size: 64 * 1024,
+            max_array_size: 4 * 1024,
+            max_map_size: 4 * 1024,
+        }
+    }
+}
+
+/// An observation a script emitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmittedObservation {
+    pub observation_type: String,
+    pub payload: CanonicalBytes,
+}
+
+/// Outcome of one invocation, recorded as an [`OBS_SCRIPT_RESULT_V0`] observation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptResult {
+    pub script_id: Hash,
+    pub args: Vec<CanonicalBytes>,
+    /// The script's final value, canonically encoded.
+    pub value: CanonicalBytes,
+    /// Graph writes the script requested, in call order.
+    pub slaps: Vec<Slap>,
+    /// Observations the script emitted, in call order.
+    pub observations: Vec<EmittedObservation>,
+    /// Rhai operations or WASM fuel the script used.
+    pub operations: u64,
+}
+
+impl ScriptResult {
+    /// Record the result as an observation with `parents`.
+    pub fn to_observation(&self, parents: Vec<EventId>) -> Result<EventEnvelope, ScriptError> {
+        Ok(EventEnvelope::new_observation(
+            CanonicalBytes::from_value(self)?,
+            parents,
+            Some(OBS_SCRIPT_RESULT_V0.to_string()),
+            None,
+            None,
+        )?)
+    }
+
+    /// The emitted observations as events, each with `parents`.
+    pub fn emitted_events(&self, parents: &[EventId]) -> Result<Vec<EventEnvelope>, ScriptError> {
+        self.observations
+            .iter()
+            .map(|obs| {
+                Ok(EventEnvelope::new_observation(
+                    obs.payload.clone(),
+                    parents.to_vec(),
+                    Some(obs.observation_type.clone()),
+                    None,
+                    None,
+                )?)
+            })
+            .collect()
+    }
+}
+
+/// Runs `InvokeScript` SLAPs.
+#[derive(Debug, Clone)]
+pub struct ScriptSandbox {
+    limits: ScriptLimits,
+    wasm: WasmEngine,
+}
+
+impl Default for ScriptSandbox {
+    fn default() -> Self {
+        Self::with_limits(ScriptLimits::default())
+    }
+}
+
+impl ScriptSandbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limits(limits: ScriptLimits) -> Self {
+        Self {
+            limits,
+            wasm: WasmEngine::new(limits.float_policy),
+        }
+    }
+
+    pub fn limits(&self) -> &ScriptLimits {
+        &self.limits
+    }
+
+    /// Run an `InvokeScript` SLAP under the footprint `scheduler` schedules it
+    /// with (its declared script access, or [`Footprint::global`]).
+    pub fn invoke_slap(
+        &self,
+        blobs: &impl BlobStore,
+        graph: &WarpGraph,
+        scheduler: &mut EchoScheduler,
+        slap: &Slap,
+    ) -> Result<ScriptResult, ScriptError> {
+        let Slap::InvokeScript { script_id, args } = slap else {
+            return Err(ScriptError::NotAnInvocation);
+        };
+        let access = scheduler.footprint(slap, graph)?;
+        self.invoke(blobs, graph, &access, script_id, args)
+    }
+
+    /// Run the script `script_id` from `blobs` with `args`, holding the
+    /// capabilities `access` grants.
+    pub fn invoke(
+        &self,
+        blobs: &impl BlobStore,
+        graph: &WarpGraph,
+        access: &Footprint,
+        script_id: &Hash,
+        args: &[CanonicalBytes],
+    ) -> Result<ScriptResult, ScriptError> {
+        let script = *script_id;
+        let bytes = blobs.get(&script).ok_or(ScriptError::NotFound(script))?;
+        let view = GraphView::new(graph, script, Capabilities::from_footprint(access));
+        let run = if bytes.starts_with(WASM_MAGIC) {
+            self.wasm.run(&self.limits, view, script, bytes, args)
+        } else {
+            let source = std::str::from_utf8(bytes).map_err(|_| ScriptError::NotUtf8(script))?;
+            rhai_vm::run(&self.limits, view, script, source, args)
+        };
+
+        // A violation fails the invocation even if the script recovered.
+        if let Some(violation) = run.effects.violation {
+            return Err(ScriptError::Violation(violation));
+        }
+        let value = run.value?;
+        let Effects {
+            slaps,
+            observations,
+            ..
+        } = run.effects;
+        Ok(ScriptResult {
+            script_id: script,
+            args: args.to_vec(),
+            value,
+            slaps,
+            observations,
+            operations: run.operations,
+        })
+    }
+}
+
+/// What a guest runtime reports back.
+pub(crate) struct Run {
+    pub value: Result<CanonicalBytes, ScriptError>,
+    pub effects: Effects,
+    pub operations: u64,
+}
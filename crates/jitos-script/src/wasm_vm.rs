@@ -0,0 +1,331 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! WASM Guests
+//!
+//! Modules run on wasmtime configured for replay: fuel metering instead of
+//! wall-clock limits, no threads (not compiled in), no relaxed SIMD, no WASI
+//! (so no clock, randomness or I/O), and floats either rejected at
+//! validation or with NaNs canonicalized (see [`FloatPolicy`]).
+//!
+//! ## Guest ABI
+//!
+//! A guest exports `memory`, `alloc(len: i32) -> i32` and
+//! `run(ptr: i32, len: i32) -> i64`. The host writes the arguments, encoded
+//! as a canonical CBOR array of byte strings (one per canonical argument),
+//! into a buffer from `alloc` and calls `run`, which returns
+//! `(ptr << 32) | len` of its result: canonical CBOR, which the host verifies.
+//!
+//! The only imports are in module `jitos` (node ids are 32 raw bytes):
+//! - `read_node(id, out, cap) -> i32` copies the node's payload to `out` if
+//!   it fits in `cap` bytes and returns its length, or -1 if there is no
+//!   such node
+//! - `create_node(type, type_len, payload, payload_len)`
+//! - `delete_node(id)`
+//! - `connect(source, target, type, type_len)`
+//! - `emit(type, type_len, payload, payload_len)` emits an observation; the
+//!   payload must be canonical CBOR
+//!
+//! A call no capability covers traps.
+
+use ciborium::value::Value;
+use jitos_core::canonical;
+use jitos_core::events::CanonicalBytes;
+use jitos_core::{Hash, NodeId};
+use wasmtime::{
+    Caller, Config, Engine, Extern, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, Trap,
+    WasmFeatures,
+};
+
+use crate::access::{Denied, Effects, GraphView};
+use crate::sandbox::Run;
+use crate::{FloatPolicy, ScriptError, ScriptLimits};
+
+/// Import module of the host functions.
+pub const HOST_MODULE: &str = "jitos";
+
+struct HostState {
+    view: GraphView,
+    effects: Effects,
+    limits: StoreLimits,
+}
+
+/// A wasmtime engine configured for deterministic execution.
+#[derive(Debug, Clone)]
+pub(crate) struct WasmEngine {
+    engine: Engine,
+}
+
+impl WasmEngine {
+    pub fn new(float_policy: FloatPolicy) -> Self {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        config.wasm_relaxed_simd(false);
+        match float_policy {
+            FloatPolicy::Forbid => {
+                config.wasm_simd(false);
+                config.wasm_features(WasmFeatures::FLOATS, false);
+            }
+            FloatPolicy::Canonicalize => {
+                config.cranelift_nan_canonicalization(true);
+            }
+        }
+        let engine = Engine::new(&config).expect("deterministic wasm configuration is supported");
+        Self { engine }
+    }
+
+    pub fn run(
+        &self,
+        limits: &ScriptLimits,
+        view: GraphView,
+        script: Hash,
+        bytes: &[u8],
+        args: &[CanonicalBytes],
+    ) -> Run {
+        let state = HostState {
+            view,
+            effects: Effects::default(),
+            limits: StoreLimitsBuilder::new()
+                .memory_size(limits.max_memory_bytes)
+                .instances(1)
+                .memories(1)
+                .tables(1)
+                .build(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store
+            .set_fuel(limits.max_fuel)
+            .expect("fuel metering is enabled");
+
+        let value = self.call(&mut store, limits, script, bytes, args);
+        let operations = limits.max_fuel - store.get_fuel().unwrap_or(0);
+        Run {
+            value,
+            effects: store.into_data().effects,
+            operations,
+        }
+    }
+
+    fn call(
+        &self,
+        store: &mut Store<HostState>,
+        limits: &ScriptLimits,
+        script: Hash,
+        bytes: &[u8],
+        args: &[CanonicalBytes],
+    ) -> Result<CanonicalBytes, ScriptError> {
+        let trapped = |e: wasmtime::Error| match e.downcast_ref::<Trap>() {
+            Some(Trap::OutOfFuel) => ScriptError::OutOfBudget {
+                script,
+                limit: limits.max_fuel,
+            },
+            _ => ScriptError::Runtime {
+                script,
+                message: format!("{e:#}"),
+            },
+        };
+        let unusable = |message: String| ScriptError::Instantiate { script, message };
+
+        let module = Module::new(&self.engine, bytes).map_err(|e| ScriptError::Compile {
+            script,
+            message: format!("{e:#}"),
+        })?;
+        let instance = linker(&self.engine)
+            .instantiate(&mut *store, &module)
+            .map_err(|e| match e.downcast_ref::<Trap>() {
+                Some(_) => trapped(e),
+                None => unusable(format!("{e:#}")),
+            })?;
+        let memory = instance
+            .get_memory(&mut *store, "memory")
+            .ok_or_else(|| unusable("no exported memory".to_string()))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut *store, "alloc")
+            .map_err(|e| unusable(format!("{e:#}")))?;
+        let run = instance
+            .get_typed_func::<(i32, i32), i64>(&mut *store, "run")
+            .map_err(|e| unusable(format!("{e:#}")))?;
+
+        let input = canonical::encode(&Value::Array(
+            args.iter()
+                .map(|arg| Value::Bytes(arg.as_bytes().to_vec()))
+                .collect(),
+        ))?;
+        let len = i32::try_from(input.len()).map_err(|_| ScriptError::Runtime {
+            script,
+            message: "arguments exceed guest address space".to_string(),
+        })?;
+        let ptr = alloc.call(&mut *store, len).map_err(trapped)?;
+        memory
+            .write(&mut *store, ptr as u32 as usize, &input)
+            .map_err(|e| ScriptError::Runtime {
+                script,
+                message: format!("alloc returned an unusable buffer: {e}"),
+            })?;
+
+        let packed = run.call(&mut *store, (ptr, len)).map_err(trapped)? as u64;
+        let out =
+            span(memory.data(&*store), (packed >> 32) as u32, packed as u32).ok_or_else(|| {
+                ScriptError::Runtime {
+                    script,
+                    message: "result out of bounds".to_string(),
+                }
+            })?;
+        canonical_value(out).ok_or_else(|| ScriptError::Runtime {
+            script,
+            message: "result is not canonical CBOR".to_string(),
+        })
+    }
+}
+
+/// `bytes` as canonical bytes, if they already are canonical.
+fn canonical_value(bytes: &[u8]) -> Option<CanonicalBytes> {
+    let value: Value = canonical::decode(bytes).ok()?;
+    let encoded = CanonicalBytes::from_value(&value).ok()?;
+    (encoded.as_bytes() == bytes).then_some(encoded)
+}
+
+fn span(data: &[u8], ptr: u32, len: u32) -> Option<&[u8]> {
+    let start = ptr as usize;
+    data.get(start..start.checked_add(len as usize)?)
+}
+
+fn linker(engine: &Engine) -> Linker<HostState> {
+    let mut linker = Linker::new(engine);
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "read_node",
+            |mut caller: Caller<'_, HostState>,
+             id: i32,
+             out: i32,
+             cap: i32|
+             -> wasmtime::Result<i32> {
+                let memory = memory(&mut caller)?;
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                let id = node_id(data, id)?;
+                let Some(node) = state.view.read(&mut state.effects, id).map_err(denied)? else {
+                    return Ok(-1);
+                };
+                let len = node.payload.len();
+                if len <= cap as u32 as usize {
+                    let dst = data
+                        .get_mut(out as u32 as usize..)
+                        .and_then(|d| d.get_mut(..len))
+                        .ok_or_else(out_of_bounds)?;
+                    dst.copy_from_slice(&node.payload);
+                }
+                i32::try_from(len).map_err(|_| wasmtime::Error::msg("payload too large"))
+            },
+        )
+        .expect("host function names are unique");
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "create_node",
+            |mut caller: Caller<'_, HostState>,
+             ty: i32,
+             ty_len: i32,
+             payload: i32,
+             payload_len: i32|
+             -> wasmtime::Result<()> {
+                let memory = memory(&mut caller)?;
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                let node_type = text(data, ty, ty_len)?;
+                let payload = bytes(data, payload, payload_len)?.to_vec();
+                state
+                    .view
+                    .create_node(&mut state.effects, node_type, payload)
+                    .map_err(denied)
+            },
+        )
+        .expect("host function names are unique");
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "delete_node",
+            |mut caller: Caller<'_, HostState>, id: i32| -> wasmtime::Result<()> {
+                let memory = memory(&mut caller)?;
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                let id = node_id(data, id)?;
+                state
+                    .view
+                    .delete_node(&mut state.effects, id)
+                    .map_err(denied)
+            },
+        )
+        .expect("host function names are unique");
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "connect",
+            |mut caller: Caller<'_, HostState>,
+             source: i32,
+             target: i32,
+             ty: i32,
+             ty_len: i32|
+             -> wasmtime::Result<()> {
+                let memory = memory(&mut caller)?;
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                let (source, target) = (node_id(data, source)?, node_id(data, target)?);
+                let edge_type = text(data, ty, ty_len)?;
+                state
+                    .view
+                    .connect(&mut state.effects, source, target, edge_type)
+                    .map_err(denied)
+            },
+        )
+        .expect("host function names are unique");
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "emit",
+            |mut caller: Caller<'_, HostState>,
+             ty: i32,
+             ty_len: i32,
+             payload: i32,
+             payload_len: i32|
+             -> wasmtime::Result<()> {
+                let memory = memory(&mut caller)?;
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                let observation_type = text(data, ty, ty_len)?;
+                let payload = canonical_value(bytes(data, payload, payload_len)?)
+                    .ok_or_else(|| wasmtime::Error::msg("payload is not canonical CBOR"))?;
+                state
+                    .view
+                    .emit(&mut state.effects, observation_type, payload)
+                    .map_err(denied)
+            },
+        )
+        .expect("host function names are unique");
+    linker
+}
+
+fn memory(caller: &mut Caller<'_, HostState>) -> wasmtime::Result<wasmtime::Memory> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmtime::Error::msg("guest exports no memory"))
+}
+
+fn bytes(data: &[u8], ptr: i32, len: i32) -> wasmtime::Result<&[u8]> {
+    span(data, ptr as u32, len as u32).ok_or_else(out_of_bounds)
+}
+
+fn text(data: &[u8], ptr: i32, len: i32) -> wasmtime::Result<&str> {
+    std::str::from_utf8(bytes(data, ptr, len)?).map_err(wasmtime::Error::msg)
+}
+
+fn node_id(data: &[u8], ptr: i32) -> wasmtime::Result<NodeId> {
+    let bytes: [u8; 32] = bytes(data, ptr, 32)?.try_into().expect("32 bytes");
+    Ok(NodeId::from_hash(Hash(bytes)))
+}
+
+fn out_of_bounds() -> wasmtime::Error {
+    wasmtime::Error::msg("guest pointer out of bounds")
+}
+
+fn denied(Denied(message): Denied) -> wasmtime::Error {
+    wasmtime::Error::msg(message)
+}
@@ -0,0 +1,99 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Script Capability Tests
+//!
+//! Capabilities come from the footprint, and a request outside them is a
+//! recordable violation.
+
+use jitos_core::blob::{BlobStore, MemoryBlobStore};
+use jitos_core::events::EventKind;
+use jitos_core::{Hash, NodeId};
+use jitos_graph::WarpGraph;
+use jitos_scheduler::footprint::{edge_key, node_key, obs_key, prefix_key};
+use jitos_scheduler::Footprint;
+use jitos_script::{
+    Capabilities, Capability, Right, ScriptError, ScriptResult, ScriptSandbox, Violation,
+    OBS_CAPABILITY_VIOLATION_V0,
+};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn run(source: &str, access: &Footprint) -> Result<ScriptResult, ScriptError> {
+    let mut blobs = MemoryBlobStore::new();
+    let script = blobs.put(source.as_bytes().to_vec());
+    ScriptSandbox::new().invoke(&blobs, &WarpGraph::new(), access, &script, &[])
+}
+
+#[test]
+fn t1_capabilities_follow_the_footprint() {
+    let footprint = Footprint {
+        n_read: vec![node_key(node_id(1))],
+        n_write: vec![node_key(node_id(2)), obs_key("OBS_METRIC_V0")],
+        e_write: vec![edge_key(node_id(1), node_id(2), "next")],
+        ..Footprint::default()
+    };
+    let caps = Capabilities::from_footprint(&footprint);
+    let read = |id| Capability::new(Right::ReadNode, node_key(node_id(id)));
+    let write = |id| Capability::new(Right::WriteNode, node_key(node_id(id)));
+
+    assert!(caps.permits(&read(1)));
+    assert!(!caps.permits(&write(1)));
+    assert!(caps.permits(&read(2)), "writes imply reads");
+    assert!(caps.permits(&write(2)));
+    assert!(caps.permits(&Capability::emit("OBS_METRIC_V0")));
+    assert!(!caps.permits(&Capability::emit("OBS_OTHER_V0")));
+    assert!(!caps.permits(&Capability::new(Right::WriteNode, obs_key("OBS_METRIC_V0"))));
+    assert!(caps.permits(&Capability::new(
+        Right::ReadEdge,
+        edge_key(node_id(1), node_id(2), "next")
+    )));
+    assert_eq!(caps.iter().count(), 6);
+
+    // Wildcards grant every narrower request.
+    let caps = Capabilities::from_footprint(&Footprint {
+        n_write: vec![prefix_key("obs:OBS_METRIC_")],
+        ..Footprint::default()
+    });
+    assert!(caps.permits(&Capability::emit("OBS_METRIC_CPU_V0")));
+}
+
+#[test]
+fn t2_scripts_emit_observations_they_are_granted() {
+    let access = Footprint {
+        n_write: vec![obs_key("OBS_METRIC_V0")],
+        ..Footprint::default()
+    };
+    let result = run(r#"emit("OBS_METRIC_V0", #{ load: 3 }); 0"#, &access).unwrap();
+    assert_eq!(result.observations.len(), 1);
+
+    let events = result.emitted_events(&[]).unwrap();
+    assert_eq!(events[0].observation_type(), Some("OBS_METRIC_V0"));
+    let payload: std::collections::BTreeMap<String, i64> = events[0].payload().to_value().unwrap();
+    assert_eq!(payload["load"], 3);
+}
+
+#[test]
+fn t3_violations_are_structured_events() {
+    let source = r#"
+        emit("OBS_METRIC_V0", 1);
+        emit("OBS_SECRET_V0", 2);
+    "#;
+    let access = Footprint {
+        n_write: vec![obs_key("OBS_METRIC_V0")],
+        ..Footprint::default()
+    };
+    let violation = match run(source, &access) {
+        Err(ScriptError::Violation(v)) => v,
+        other => panic!("expected a violation, got {other:?}"),
+    };
+    assert_eq!(violation.requested, Capability::emit("OBS_SECRET_V0"));
+
+    let event = violation.to_observation(vec![]).unwrap();
+    assert_eq!(event.kind(), &EventKind::Observation);
+    assert_eq!(event.observation_type(), Some(OBS_CAPABILITY_VIOLATION_V0));
+    let recorded: Violation = event.payload().to_value().unwrap();
+    assert_eq!(recorded, violation);
+}
@@ -0,0 +1,180 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Script Sandbox Tests
+//!
+//! Scripts run deterministically, within their op budget, and only against
+//! the graph keys their footprint declares.
+
+use jitos_core::blob::{BlobStore, MemoryBlobStore};
+use jitos_core::events::{CanonicalBytes, EventKind};
+use jitos_core::{Hash, NodeId, Slap};
+use jitos_graph::{WarpGraph, WarpNode};
+use jitos_scheduler::footprint::{node_key, prefix_key};
+use jitos_scheduler::{EchoScheduler, Footprint};
+use jitos_script::{
+    Capability, Right, ScriptError, ScriptLimits, ScriptResult, ScriptSandbox, OBS_SCRIPT_RESULT_V0,
+};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn graph() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    for byte in [1, 2] {
+        g.insert_node(WarpNode {
+            id: node_id(byte),
+            node_type: "counter".to_string(),
+            payload_bytes: vec![byte * 10],
+            attachment: None,
+        });
+    }
+    g
+}
+
+/// Reads node 1, creates nodes.
+fn access() -> Footprint {
+    Footprint {
+        n_read: vec![node_key(node_id(1))],
+        n_write: vec![prefix_key("node:new:")],
+        ..Footprint::default()
+    }
+}
+
+fn arg(value: i64) -> CanonicalBytes {
+    CanonicalBytes::from_value(&value).unwrap()
+}
+
+fn run(source: &str, args: &[CanonicalBytes]) -> Result<ScriptResult, ScriptError> {
+    let mut blobs = MemoryBlobStore::new();
+    let script = blobs.put(source.as_bytes().to_vec());
+    ScriptSandbox::new().invoke(&blobs, &graph(), &access(), &script, args)
+}
+
+#[test]
+fn t1_script_reads_declared_nodes_and_proposes_writes() {
+    let source = format!(
+        r#"
+        let payload = node_payload("{}");
+        create_node("sum", blob(1, payload[0] + ARGS[0]));
+        payload[0] + ARGS[0] + ARGS[1]
+        "#,
+        node_id(1)
+    );
+    let result = run(&source, &[arg(5), arg(7)]).unwrap();
+    assert_eq!(result.value.to_value::<i64>().unwrap(), 22);
+    assert!(matches!(
+        result.slaps.as_slice(),
+        [Slap::CreateNode { node_type, payload_bytes }]
+            if node_type == "sum" && payload_bytes == &vec![15]
+    ));
+    assert!(result.operations > 0);
+
+    // Same inputs, same result and op count.
+    let again = run(&source, &[arg(5), arg(7)]).unwrap();
+    assert_eq!(again.value, result.value);
+    assert_eq!(again.operations, result.operations);
+
+    let event = result.to_observation(vec![]).unwrap();
+    assert_eq!(event.kind(), &EventKind::Observation);
+    assert_eq!(event.observation_type(), Some(OBS_SCRIPT_RESULT_V0));
+    let recorded: ScriptResult = event.payload().to_value().unwrap();
+    assert_eq!(recorded.script_id, result.script_id);
+    assert_eq!(recorded.args, vec![arg(5), arg(7)]);
+}
+
+#[test]
+fn t2_undeclared_access_fails_even_if_caught() {
+    let source = format!(
+        r#"
+        try {{ node_payload("{}") }} catch {{ () }}
+        1
+        "#,
+        node_id(2)
+    );
+    match run(&source, &[]) {
+        Err(ScriptError::Violation(v)) => {
+            assert_eq!(
+                v.requested,
+                Capability::new(Right::ReadNode, node_key(node_id(2)))
+            );
+        }
+        other => panic!("expected a violation, got {other:?}"),
+    }
+
+    let source = format!(r#"delete_node("{}")"#, node_id(1));
+    assert!(matches!(
+        run(&source, &[]),
+        Err(ScriptError::Violation(v)) if v.requested.right == Right::WriteNode
+    ));
+    assert!(matches!(
+        run(r#"nodes_of_type("counter")"#, &[]),
+        Err(ScriptError::Violation(_))
+    ));
+}
+
+#[test]
+fn t3_budget_and_environment_are_deterministic() {
+    let mut blobs = MemoryBlobStore::new();
+    let spin = blobs.put(b"loop { }".to_vec());
+    let sandbox = ScriptSandbox::with_limits(ScriptLimits {
+        max_operations: 1_000,
+        ..ScriptLimits::default()
+    });
+    assert!(matches!(
+        sandbox.invoke(&blobs, &graph(), &access(), &spin, &[]),
+        Err(ScriptError::OutOfBudget { limit: 1_000, .. })
+    ));
+
+    // No clock and no module loading.
+    assert!(matches!(
+        run("timestamp()", &[]),
+        Err(ScriptError::Runtime { .. })
+    ));
+    assert!(matches!(
+        run(r#"import "std" as s; 1"#, &[]),
+        Err(ScriptError::Runtime { .. })
+    ));
+    assert!(matches!(
+        run("let x = ;", &[]),
+        Err(ScriptError::Compile { .. })
+    ));
+    assert!(matches!(
+        sandbox.invoke(&blobs, &graph(), &access(), &Hash([0u8; 32]), &[]),
+        Err(ScriptError::NotFound(_))
+    ));
+}
+
+#[test]
+fn t4_invoke_slap_uses_the_scheduled_footprint() {
+    let mut blobs = MemoryBlobStore::new();
+    let script = blobs.put(br#"nodes_of_type("counter").len()"#.to_vec());
+    let slap = Slap::InvokeScript {
+        script_id: script,
+        args: vec![],
+    };
+    let sandbox = ScriptSandbox::new();
+
+    // Undeclared scripts get the global footprint.
+    let mut scheduler = EchoScheduler::new();
+    let result = sandbox
+        .invoke_slap(&blobs, &graph(), &mut scheduler, &slap)
+        .unwrap();
+    assert_eq!(result.value.to_value::<i64>().unwrap(), 2);
+
+    scheduler.declare_script_access(script, access());
+    assert!(matches!(
+        sandbox.invoke_slap(&blobs, &graph(), &mut scheduler, &slap),
+        Err(ScriptError::Violation(_))
+    ));
+    assert!(matches!(
+        sandbox.invoke_slap(
+            &blobs,
+            &graph(),
+            &mut scheduler,
+            &Slap::DeleteNode { id: node_id(1) }
+        ),
+        Err(ScriptError::NotAnInvocation)
+    ));
+}
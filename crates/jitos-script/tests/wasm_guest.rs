@@ -0,0 +1,166 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! WASM Guest Tests
+//!
+//! WASM scripts run under fuel, without WASI, with floats forbidden or
+//! NaN-canonicalized, and through the same footprint checks as Rhai.
+
+use jitos_core::blob::{BlobStore, MemoryBlobStore};
+use jitos_core::{Hash, NodeId, Slap};
+use jitos_graph::{WarpGraph, WarpNode};
+use jitos_scheduler::footprint::{node_key, prefix_key};
+use jitos_scheduler::Footprint;
+use jitos_script::{
+    Capability, FloatPolicy, Right, ScriptError, ScriptLimits, ScriptResult, ScriptSandbox,
+};
+
+fn node_id(byte: u8) -> NodeId {
+    NodeId::from_hash(Hash([byte; 32]))
+}
+
+fn graph() -> WarpGraph {
+    let mut g = WarpGraph::new();
+    for byte in [1, 2] {
+        g.insert_node(WarpNode {
+            id: node_id(byte),
+            node_type: "counter".to_string(),
+            payload_bytes: vec![byte * 10],
+            attachment: None,
+        });
+    }
+    g
+}
+
+fn access() -> Footprint {
+    Footprint {
+        n_read: vec![node_key(node_id(1))],
+        n_write: vec![prefix_key("node:new:")],
+        ..Footprint::default()
+    }
+}
+
+/// Guest that reads the payload byte of node `byte`, creates a node with
+/// that byte plus one, and returns it as a CBOR integer.
+fn increment(byte: u8) -> String {
+    let id = format!("\\{byte:02x}").repeat(32);
+    format!(
+        r#"(module
+            (import "jitos" "read_node" (func $read (param i32 i32 i32) (result i32)))
+            (import "jitos" "create_node" (func $create (param i32 i32 i32 i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "{id}")
+            (data (i32.const 32) "sum")
+            (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+            (func (export "run") (param i32 i32) (result i64)
+                (drop (call $read (i32.const 0) (i32.const 64) (i32.const 16)))
+                (i32.store8 (i32.const 64)
+                    (i32.add (i32.load8_u (i32.const 64)) (i32.const 1)))
+                (call $create (i32.const 32) (i32.const 3) (i32.const 64) (i32.const 1))
+                ;; (ptr 64 << 32) | len 1
+                (i64.const 274877906945)))"#
+    )
+}
+
+fn run_with(sandbox: &ScriptSandbox, wat: &str) -> Result<ScriptResult, ScriptError> {
+    let mut blobs = MemoryBlobStore::new();
+    let script = blobs.put(wat::parse_str(wat).unwrap());
+    sandbox.invoke(&blobs, &graph(), &access(), &script, &[])
+}
+
+fn run(wat: &str) -> Result<ScriptResult, ScriptError> {
+    run_with(&ScriptSandbox::new(), wat)
+}
+
+#[test]
+fn t1_guest_reads_declared_nodes_and_proposes_writes() {
+    let result = run(&increment(1)).unwrap();
+    assert_eq!(result.value.to_value::<u8>().unwrap(), 11);
+    assert!(matches!(
+        result.slaps.as_slice(),
+        [Slap::CreateNode { node_type, payload_bytes }]
+            if node_type == "sum" && payload_bytes == &vec![11]
+    ));
+    assert!(result.operations > 0);
+    let again = run(&increment(1)).unwrap();
+    assert_eq!(
+        again.operations, result.operations,
+        "fuel use is replayable"
+    );
+
+    match run(&increment(2)) {
+        Err(ScriptError::Violation(v)) => {
+            assert_eq!(
+                v.requested,
+                Capability::new(Right::ReadNode, node_key(node_id(2)))
+            );
+        }
+        other => panic!("expected a violation, got {other:?}"),
+    }
+}
+
+#[test]
+fn t2_fuel_bounds_execution() {
+    let spin = r#"(module
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) (i32.const 0))
+        (func (export "run") (param i32 i32) (result i64) (loop (br 0)) (i64.const 0)))"#;
+    let sandbox = ScriptSandbox::with_limits(ScriptLimits {
+        max_fuel: 5_000,
+        ..ScriptLimits::default()
+    });
+    assert!(matches!(
+        run_with(&sandbox, spin),
+        Err(ScriptError::OutOfBudget { limit: 5_000, .. })
+    ));
+}
+
+#[test]
+fn t3_float_policy() {
+    // Returns CBOR true/false (0xf5/0xf4): is a NaN with a payload
+    // canonicalized by arithmetic?
+    let nan = r#"(module
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) (i32.const 0))
+        (func (export "run") (param i32 i32) (result i64)
+            (i32.store8 (i32.const 0)
+                (i32.add (i32.const 0xf4)
+                    (i64.eq
+                        (i64.reinterpret_f64
+                            (f64.add
+                                (f64.reinterpret_i64 (i64.const 0xfff0000000000001))
+                                (f64.const 0)))
+                        (i64.const 0x7ff8000000000000))))
+            (i64.const 1)))"#;
+    let result = run(nan).unwrap();
+    assert!(result.value.to_value::<bool>().unwrap());
+
+    let forbid = ScriptSandbox::with_limits(ScriptLimits {
+        float_policy: FloatPolicy::Forbid,
+        ..ScriptLimits::default()
+    });
+    assert!(matches!(
+        run_with(&forbid, nan),
+        Err(ScriptError::Compile { .. })
+    ));
+    assert!(run_with(&forbid, &increment(1)).is_ok());
+}
+
+#[test]
+fn t4_no_wasi_and_results_must_be_canonical() {
+    let wasi = r#"(module
+        (import "wasi_snapshot_preview1" "clock_time_get"
+            (func (param i32 i64 i32) (result i32)))
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) (i32.const 0))
+        (func (export "run") (param i32 i32) (result i64) (i64.const 0)))"#;
+    assert!(matches!(run(wasi), Err(ScriptError::Instantiate { .. })));
+
+    // 0x18 0x05 encodes 5 with a needlessly wide integer.
+    let wide = r#"(module
+        (memory (export "memory") 1)
+        (data (i32.const 0) "\18\05")
+        (func (export "alloc") (param i32) (result i32) (i32.const 16))
+        (func (export "run") (param i32 i32) (result i64) (i64.const 2)))"#;
+    assert!(matches!(run(wide), Err(ScriptError::Runtime { .. })));
+}
@@ -0,0 +1,73 @@
+//! Replay throughput of the clock and timer views: events folded per second
+//! from a fresh view.
+//!
+//! Run with `cargo xtask bench`, which collects the results as JSON.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use jitos_core::events::{CanonicalBytes, EventEnvelope};
+use jitos_core::Hash;
+use jitos_views::testing::replay;
+use jitos_views::{
+    ClockPolicyId, ClockSample, ClockSource, ClockView, TimerRequest, TimerView,
+    OBS_CLOCK_SAMPLE_V0, OBS_TIMER_REQUEST_V0,
+};
+
+fn observation<T: serde::Serialize>(value: &T, observation_type: &str) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(value).unwrap(),
+        vec![],
+        Some(observation_type.to_string()),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+/// `n` events alternating monotonic clock samples and timer requests, so
+/// each view folds half of them and skips the rest.
+fn worldline(n: u64) -> Vec<EventEnvelope> {
+    (0..n)
+        .map(|i| {
+            if i % 2 == 0 {
+                let sample = ClockSample {
+                    source: ClockSource::Monotonic,
+                    value_ns: 1_000 * i,
+                    uncertainty_ns: 5,
+                };
+                observation(&sample, OBS_CLOCK_SAMPLE_V0)
+            } else {
+                let mut request_id = [0u8; 32];
+                request_id[..8].copy_from_slice(&i.to_be_bytes());
+                let request = TimerRequest {
+                    request_id: Hash(request_id),
+                    duration_ns: 10_000 + i % 7 * 1_000,
+                    requested_at_ns: 1_000 * i,
+                };
+                observation(&request, OBS_TIMER_REQUEST_V0)
+            }
+        })
+        .collect()
+}
+
+fn view_replay(c: &mut Criterion) {
+    let mut group = c.benchmark_group("view_replay");
+    for n in [1_000, 10_000] {
+        let events = worldline(n);
+        group.throughput(Throughput::Elements(n));
+        group.bench_with_input(BenchmarkId::new("clock", n), &events, |b, events| {
+            b.iter(|| {
+                replay(
+                    || ClockView::new(ClockPolicyId::TrustMonotonicLatest),
+                    black_box(events),
+                )
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("timer", n), &events, |b, events| {
+            b.iter(|| replay(TimerView::new, black_box(events)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, view_replay);
+criterion_main!(benches);
@@ -0,0 +1,130 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Anchor View - Time Domain Conversion
+//!
+//! A clock anchor is a pair of simultaneous monotonic and Unix readings,
+//! recorded as an `OBS_CLOCK_ANCHOR_V0` observation. The latest anchor fixes
+//! the offset between the two domains, and [`AnchorView::convert`] uses it to
+//! move a [`Time`] belief from one domain to the other. Without an anchor
+//! there is no conversion: cross-domain comparisons fail loudly instead of
+//! silently mixing domains.
+
+use jitos_core::{events::EventEnvelope, Hash};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{ClockError, Time, TimeDomain};
+
+/// Observation type tag for clock anchor events
+pub const OBS_CLOCK_ANCHOR_V0: &str = "OBS_CLOCK_ANCHOR_V0";
+
+/// Anchor view - latest monotonic/Unix anchor over observation events
+#[derive(Debug, Clone, Default)]
+pub struct AnchorView {
+    latest: Option<ClockAnchorRecord>,
+}
+
+impl AnchorView {
+    /// Create anchor view with no anchor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one event in canonical worldline order
+    ///
+    /// # Errors
+    ///
+    /// Currently never returns an error. Events that are not anchor
+    /// observations (or fail to decode) are silently ignored.
+    pub fn apply_event(&mut self, event: &EventEnvelope) -> Result<(), ClockError> {
+        if !matches!(event.kind(), jitos_core::events::EventKind::Observation)
+            || event.observation_type() != Some(OBS_CLOCK_ANCHOR_V0)
+        {
+            return Ok(());
+        }
+        if let Ok(anchor) = event.payload().to_value::<ClockAnchor>() {
+            self.latest = Some(ClockAnchorRecord {
+                event_id: event.event_id(),
+                anchor,
+            });
+        }
+        Ok(())
+    }
+
+    /// The anchor conversions currently use
+    pub fn anchor(&self) -> Option<&ClockAnchorRecord> {
+        self.latest.as_ref()
+    }
+
+    /// Express `time` in domain `to`
+    ///
+    /// Converting within a domain returns `time` unchanged. Across domains the
+    /// anchor offset is applied, the anchor's uncertainty is added and the
+    /// anchor event is appended to the provenance.
+    ///
+    /// # Errors
+    ///
+    /// - [`ConversionError::UnknownDomain`] if either domain is `Unknown`
+    /// - [`ConversionError::LogicalDomain`] if exactly one domain is `Logical`
+    /// - [`ConversionError::NoAnchor`] if no anchor has been observed
+    /// - [`ConversionError::OutOfRange`] if the result does not fit in u64
+    pub fn convert(&self, time: &Time, to: TimeDomain) -> Result<Time, ConversionError> {
+        let from = time.domain();
+        if from == TimeDomain::Unknown || to == TimeDomain::Unknown {
+            return Err(ConversionError::UnknownDomain);
+        }
+        if from != to && (from == TimeDomain::Logical || to == TimeDomain::Logical) {
+            return Err(ConversionError::LogicalDomain);
+        }
+        if from == to {
+            return Ok(time.clone());
+        }
+        let record = self.latest.as_ref().ok_or(ConversionError::NoAnchor)?;
+        let offset = i128::from(record.anchor.unix_ns) - i128::from(record.anchor.monotonic_ns);
+        let ns = match to {
+            TimeDomain::Unix => i128::from(time.ns()) + offset,
+            _ => i128::from(time.ns()) - offset,
+        };
+        let ns = u64::try_from(ns).map_err(|_| ConversionError::OutOfRange { ns })?;
+
+        let mut provenance = time.provenance().to_vec();
+        provenance.push(record.event_id);
+        Ok(Time::new(
+            ns,
+            time.uncertainty_ns()
+                .saturating_add(record.anchor.uncertainty_ns),
+            to,
+            provenance,
+        ))
+    }
+}
+
+/// Simultaneous monotonic and Unix readings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockAnchor {
+    pub monotonic_ns: u64,
+    pub unix_ns: u64,
+    /// How far apart the two readings may really be
+    pub uncertainty_ns: u64,
+}
+
+/// Clock anchor with provenance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockAnchorRecord {
+    pub event_id: Hash,
+    pub anchor: ClockAnchor,
+}
+
+/// Domain conversion errors
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConversionError {
+    #[error("no clock anchor observed; cannot convert between time domains")]
+    NoAnchor,
+    #[error("cannot convert to or from the Unknown time domain")]
+    UnknownDomain,
+    #[error("logical time has no physical counterpart to convert to or from")]
+    LogicalDomain,
+    #[error("converted time {ns} ns is out of range")]
+    OutOfRange { ns: i128 },
+}
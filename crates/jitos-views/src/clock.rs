@@ -5,77 +5,146 @@
 //!
 //! SPEC-0003: Clock View provides deterministic time beliefs as a pure fold
 //! over observation events. Time never comes from syscalls.
+//!
+//! Simulations drive time explicitly with `SetTime` SLAPs, which the runtime
+//! records as [`OBS_LOGICAL_TIME_V0`] observations. Once logical time has
+//! been set, it is what the view believes, whatever the clock policy.
 
-use jitos_core::{events::EventEnvelope, Hash};
+use jitos_core::{canonical, events::EventEnvelope, Hash};
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use thiserror::Error;
 
 /// Observation type tag for clock sample events (Phase 0.5.4)
 pub const OBS_CLOCK_SAMPLE_V0: &str = "OBS_CLOCK_SAMPLE_V0";
 
+/// Observation type tag for logical time set by a `SetTime` SLAP
+pub const OBS_LOGICAL_TIME_V0: &str = "OBS_LOGICAL_TIME_V0";
+
 /// Clock view - deterministic materialized view over clock observation events
 #[derive(Debug, Clone)]
 pub struct ClockView {
-    samples: Vec<ClockSampleRecord>,
-    latest: LatestSamples,
+    /// Retained sample history per source, oldest first (see [`SampleRetention`])
+    history: [VecDeque<ClockSampleRecord>; 4],
+    retention: SampleRetention,
+    state: ClockState,
     current: Time,
     policy: ClockPolicyId,
+    staleness: Staleness,
+    /// Reject malformed samples instead of skipping them (see [`ClockView::strict`])
+    strict: bool,
+    /// Event ids of malformed samples skipped in lenient mode
+    skipped: Vec<Hash>,
 }
 
 impl ClockView {
     /// Create new clock view with given policy
     pub fn new(policy: ClockPolicyId) -> Self {
         Self {
-            samples: Vec::new(),
-            latest: LatestSamples::default(),
+            history: Default::default(),
+            retention: policy.retention(),
+            state: ClockState::default(),
             current: Time::unknown(),
             policy,
+            staleness: Staleness::default(),
+            strict: false,
+            skipped: Vec::new(),
+        }
+    }
+
+    /// Create clock view that rejects malformed samples
+    ///
+    /// An observation tagged `OBS_CLOCK_SAMPLE_V0` whose payload does not
+    /// decode fails with [`ClockError::Malformed`] instead of being skipped.
+    pub fn strict(policy: ClockPolicyId) -> Self {
+        Self {
+            strict: true,
+            ..Self::new(policy)
         }
     }
 
+    /// Keep sample history as `retention` asks
+    ///
+    /// Never keeps less than the policy needs ([`ClockPolicyId::retention`]).
+    /// Applies to samples applied from now on; call it before replay.
+    pub fn with_retention(mut self, retention: SampleRetention) -> Self {
+        self.retention = retention.max(self.policy.retention());
+        self
+    }
+
+    /// Expire stale samples as `staleness` says (see [`Staleness`])
+    pub fn with_staleness(mut self, staleness: Staleness) -> Self {
+        self.staleness = staleness;
+        self.current = self.compute_current_time();
+        self
+    }
+
+    /// Policy this view believes time by
+    pub fn policy(&self) -> ClockPolicyId {
+        self.policy
+    }
+
+    /// Staleness bounds in force
+    pub fn staleness(&self) -> &Staleness {
+        &self.staleness
+    }
+
+    /// Sample history this view keeps
+    pub fn retention(&self) -> SampleRetention {
+        self.retention
+    }
+
+    /// Retained samples from `source`, oldest first
+    pub fn history(&self, source: ClockSource) -> impl Iterator<Item = &ClockSampleRecord> {
+        self.history[source_index(source)].iter()
+    }
+
+    /// True if malformed samples are rejected (see [`Self::strict`])
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Malformed samples skipped so far, in apply order (always empty in
+    /// strict mode)
+    pub fn skipped(&self) -> &[Hash] {
+        &self.skipped
+    }
+
     /// Apply one event in canonical worldline order
     ///
     /// # Errors
     ///
-    /// Currently never returns an error. Events that are not clock observations
-    /// are silently ignored.
+    /// In strict mode, returns [`ClockError::Malformed`] for a clock sample
+    /// or logical time observation whose payload does not decode; lenient
+    /// views record its event id in [`Self::skipped`] instead. Events that
+    /// are not clock observations are silently ignored.
     pub fn apply_event(&mut self, event: &EventEnvelope) -> Result<(), ClockError> {
-        // Only process Observation events
-        if !matches!(event.kind(), jitos_core::events::EventKind::Observation) {
-            return Ok(()); // Ignore non-observation events
-        }
-
-        // SPEC-0003 (lines 127-130): Only decode observations tagged OBS_CLOCK_SAMPLE_V0
-        // Strict enforcement: untagged or mismatched observations are ignored
-        if event.observation_type() != Some(OBS_CLOCK_SAMPLE_V0) {
-            return Ok(()); // Ignore observations without correct type tag
-        }
-
-        // Decode payload as ClockSample (type tag already verified)
-        let sample: ClockSample = match event.payload().to_value() {
-            Ok(s) => s,
-            Err(_) => {
-                // Decoding failed even with correct tag - ignore silently
+        let record = match try_decode_sample(event) {
+            Ok(Some(record)) => record,
+            Ok(None) => return self.apply_logical(event),
+            Err(event_id) if self.strict => return Err(ClockError::Malformed(event_id)),
+            Err(event_id) => {
+                self.skipped.push(event_id);
                 return Ok(());
             }
         };
 
-        // Create sample record with provenance
-        let record = ClockSampleRecord {
-            event_id: event.event_id(),
-            sample,
-        };
-
         // Update latest cache (O(1) per source)
-        match record.sample.source {
-            ClockSource::Monotonic => self.latest.monotonic = Some(record.clone()),
-            ClockSource::Ntp => self.latest.ntp = Some(record.clone()),
-            ClockSource::Rtc => self.latest.rtc = Some(record.clone()),
-            ClockSource::PeerClaim => self.latest.peer = Some(record.clone()),
-        }
+        self.state.record(&record);
 
-        // Append to full sample history
-        self.samples.push(record);
+        // Append to sample history, evicting beyond the retention bound
+        let keep = match self.retention {
+            SampleRetention::None => 0,
+            SampleRetention::LastPerSource(k) => k,
+            SampleRetention::All => usize::MAX,
+        };
+        if keep > 0 {
+            let history = &mut self.history[source_index(record.sample.source)];
+            if history.len() == keep {
+                history.pop_front();
+            }
+            history.push_back(record);
+        }
 
         // Recompute current time based on policy
         self.current = self.compute_current_time();
@@ -83,8 +152,33 @@ impl ClockView {
         Ok(())
     }
 
+    /// Fold a logical time observation (anything else is ignored)
+    fn apply_logical(&mut self, event: &EventEnvelope) -> Result<(), ClockError> {
+        match try_decode_logical(event) {
+            Ok(Some(record)) => {
+                self.state.logical = Some(record);
+                self.current = self.compute_current_time();
+                Ok(())
+            }
+            Ok(None) => Ok(()),
+            Err(event_id) if self.strict => Err(ClockError::Malformed(event_id)),
+            Err(event_id) => {
+                self.skipped.push(event_id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Logical time set so far, if any
+    pub fn logical(&self) -> Option<&LogicalTimeRecord> {
+        self.state.logical.as_ref()
+    }
+
     /// Pure fold over a prefix of a canonical worldline
     ///
+    /// O(cut) per query; see [`ClockCheckpoints`] for repeated historical
+    /// queries over the same worldline.
+    ///
     /// # Errors
     ///
     /// Returns [`ClockError::CutOutOfBounds`] if `cut > events.len()`.
@@ -115,34 +209,486 @@ impl ClockView {
         &self.current
     }
 
+    /// Canonical digest of the view's semantic state
+    ///
+    /// Covers the policy, the latest sample of each source, the NTP anchor,
+    /// the logical time and the current belief. Sample history is not included: two views that
+    /// answer every query the same way hash equal.
+    pub fn state_hash(&self) -> Hash {
+        let sample = |slot: &Option<ClockSampleRecord>| {
+            slot.as_ref().map(|r| {
+                (
+                    r.event_id,
+                    r.sample.source,
+                    r.sample.value_ns,
+                    r.sample.uncertainty_ns,
+                )
+            })
+        };
+        let latest = &self.state.latest;
+        let anchor = self.state.anchor.as_ref().map(|a| (a.ntp, a.monotonic));
+        let logical = self
+            .state
+            .logical
+            .as_ref()
+            .map(|r| (r.event_id, r.time.tick, r.time.dt_ns));
+        let current = &self.current;
+        // Encoding tags, hashes, integers and unit enums cannot fail.
+        canonical::hash_canonical(&(
+            "clock-view-v0",
+            self.policy.name(),
+            [
+                sample(&latest.monotonic),
+                sample(&latest.ntp),
+                sample(&latest.rtc),
+                sample(&latest.peer),
+            ],
+            anchor,
+            logical,
+            (
+                current.ns,
+                current.uncertainty_ns,
+                current.domain,
+                &current.provenance,
+            ),
+        ))
+        .expect("clock state hash encoding")
+    }
+
     /// Compute current time based on active policy and latest samples
     fn compute_current_time(&self) -> Time {
-        match self.policy {
-            ClockPolicyId::TrustMonotonicLatest => {
-                if let Some(ref record) = self.latest.monotonic {
-                    Time {
-                        ns: record.sample.value_ns,
-                        uncertainty_ns: record.sample.uncertainty_ns,
-                        domain: TimeDomain::Monotonic,
-                        provenance: vec![record.event_id],
-                    }
-                } else {
-                    Time::unknown()
+        believe(self.policy, &self.staleness, &self.state)
+    }
+}
+
+/// How much sample history a [`ClockView`] keeps
+///
+/// Current policies read only the latest sample of each source, so views keep
+/// no history unless asked. Ordered by how much is kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SampleRetention {
+    /// No history
+    #[default]
+    None,
+    /// The last `k` samples of each source
+    LastPerSource(usize),
+    /// Every sample (unbounded)
+    All,
+}
+
+fn source_index(source: ClockSource) -> usize {
+    match source {
+        ClockSource::Monotonic => 0,
+        ClockSource::Ntp => 1,
+        ClockSource::Rtc => 2,
+        ClockSource::PeerClaim => 3,
+    }
+}
+
+/// Decode a clock sample observation, or `None` for any other event.
+///
+/// A tagged observation whose payload fails to decode is ignored silently.
+pub(crate) fn decode_sample(event: &EventEnvelope) -> Option<ClockSampleRecord> {
+    try_decode_sample(event).ok().flatten()
+}
+
+/// Decode a clock sample observation: `Ok(None)` for any other event,
+/// `Err(event_id)` for a tagged observation whose payload fails to decode.
+fn try_decode_sample(event: &EventEnvelope) -> Result<Option<ClockSampleRecord>, Hash> {
+    // Only process Observation events
+    if !matches!(event.kind(), jitos_core::events::EventKind::Observation) {
+        return Ok(None); // Ignore non-observation events
+    }
+
+    // SPEC-0003 (lines 127-130): Only decode observations tagged OBS_CLOCK_SAMPLE_V0
+    // Strict enforcement: untagged or mismatched observations are ignored
+    if event.observation_type() != Some(OBS_CLOCK_SAMPLE_V0) {
+        return Ok(None); // Ignore observations without correct type tag
+    }
+
+    // Decode payload as ClockSample (type tag already verified)
+    let sample: ClockSample = event.payload().to_value().map_err(|_| event.event_id())?;
+
+    // Create sample record with provenance
+    Ok(Some(ClockSampleRecord {
+        event_id: event.event_id(),
+        sample,
+    }))
+}
+
+/// Decode a logical time observation: `Ok(None)` for any other event,
+/// `Err(event_id)` for a tagged observation whose payload fails to decode.
+fn try_decode_logical(event: &EventEnvelope) -> Result<Option<LogicalTimeRecord>, Hash> {
+    if !matches!(event.kind(), jitos_core::events::EventKind::Observation)
+        || event.observation_type() != Some(OBS_LOGICAL_TIME_V0)
+    {
+        return Ok(None);
+    }
+    let time: LogicalTime = event.payload().to_value().map_err(|_| event.event_id())?;
+    Ok(Some(LogicalTimeRecord {
+        event_id: event.event_id(),
+        time,
+    }))
+}
+
+/// Per-source expiry of clock samples
+///
+/// A sample's age is how far the latest monotonic sample has advanced past
+/// the monotonic reading current when the sample arrived (samples that
+/// arrive before any monotonic reading are stamped by the first one). A
+/// source whose latest sample is older than its bound is treated as absent,
+/// and so is the NTP anchor once its NTP sample is. Monotonic samples define
+/// the cut and never expire.
+///
+/// If the policy has no estimate from the remaining samples, the
+/// [`StaleFallback`] decides what to believe. Ages are pure functions of the
+/// folded events, so expiry replays exactly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Staleness {
+    /// Bounds for NTP, RTC and peer claims, in that order
+    max_age_ns: [Option<u64>; 3],
+    fallback: StaleFallback,
+}
+
+/// What to believe when staleness leaves a policy without an estimate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StaleFallback {
+    /// Time is unknown
+    #[default]
+    Unknown,
+    /// The latest fresh sample of this source, taken as-is
+    Source(ClockSource),
+}
+
+impl Staleness {
+    /// No source expires
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Expire `source` samples older than `max_age_ns`
+    ///
+    /// Bounds on [`ClockSource::Monotonic`] are ignored.
+    pub fn expire(mut self, source: ClockSource, max_age_ns: u64) -> Self {
+        if let Some(slot) = Self::slot(source) {
+            self.max_age_ns[slot] = Some(max_age_ns);
+        }
+        self
+    }
+
+    /// Believe `fallback` when no fresh estimate remains
+    pub fn with_fallback(mut self, fallback: StaleFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Bound on `source`, if it expires
+    pub fn max_age_ns(&self, source: ClockSource) -> Option<u64> {
+        Self::slot(source).and_then(|slot| self.max_age_ns[slot])
+    }
+
+    /// Fallback when no fresh estimate remains
+    pub fn fallback(&self) -> StaleFallback {
+        self.fallback
+    }
+
+    /// True if nothing expires and there is no fallback
+    pub fn is_none(&self) -> bool {
+        *self == Self::none()
+    }
+
+    fn slot(source: ClockSource) -> Option<usize> {
+        match source {
+            ClockSource::Monotonic => None,
+            ClockSource::Ntp => Some(0),
+            ClockSource::Rtc => Some(1),
+            ClockSource::PeerClaim => Some(2),
+        }
+    }
+}
+
+/// Time belief under `policy` and `staleness` given the folded state.
+fn believe(policy: ClockPolicyId, staleness: &Staleness, state: &ClockState) -> Time {
+    if let Some(record) = &state.logical {
+        return Time {
+            ns: record.time.value_ns(),
+            uncertainty_ns: 0,
+            domain: TimeDomain::Logical,
+            provenance: vec![record.event_id],
+        };
+    }
+    if staleness.is_none() {
+        return current_time(policy, state);
+    }
+    let fresh = state.without_stale(staleness);
+    let time = current_time(policy, &fresh);
+    match staleness.fallback {
+        StaleFallback::Source(source) if time.domain == TimeDomain::Unknown => {
+            match fresh.latest.get(source) {
+                Some(record) => Time {
+                    ns: record.sample.value_ns,
+                    uncertainty_ns: record.sample.uncertainty_ns,
+                    domain: match source {
+                        ClockSource::Monotonic => TimeDomain::Monotonic,
+                        _ => TimeDomain::Unix,
+                    },
+                    provenance: vec![record.event_id],
+                },
+                None => time,
+            }
+        }
+        _ => time,
+    }
+}
+
+/// Time belief under `policy` given the folded state.
+fn current_time(policy: ClockPolicyId, state: &ClockState) -> Time {
+    let latest = &state.latest;
+    match policy {
+        ClockPolicyId::TrustMonotonicLatest => {
+            if let Some(ref record) = latest.monotonic {
+                Time {
+                    ns: record.sample.value_ns,
+                    uncertainty_ns: record.sample.uncertainty_ns,
+                    domain: TimeDomain::Monotonic,
+                    provenance: vec![record.event_id],
                 }
+            } else {
+                Time::unknown()
             }
-            ClockPolicyId::TrustNtpLatest => {
-                if let Some(ref record) = self.latest.ntp {
-                    Time {
-                        ns: record.sample.value_ns,
-                        uncertainty_ns: record.sample.uncertainty_ns,
-                        domain: TimeDomain::Unix,
-                        provenance: vec![record.event_id],
-                    }
-                } else {
-                    Time::unknown()
+        }
+        ClockPolicyId::TrustNtpLatest => {
+            if let Some(ref record) = latest.ntp {
+                Time {
+                    ns: record.sample.value_ns,
+                    uncertainty_ns: record.sample.uncertainty_ns,
+                    domain: TimeDomain::Unix,
+                    provenance: vec![record.event_id],
                 }
+            } else {
+                Time::unknown()
             }
         }
+        ClockPolicyId::WeightedFusion => fuse_unix_samples(latest),
+        ClockPolicyId::HybridMonotonicNtp => hybrid_time(state),
+    }
+}
+
+/// Monotonic time carried into the Unix domain by the NTP anchor.
+///
+/// `now = latest monotonic + (anchor NTP - anchor monotonic)`, saturated to
+/// the u64 range; uncertainty is the monotonic sample's plus the anchor's.
+/// Before any monotonic sample the latest NTP sample is used as-is; with no
+/// NTP sample there is no Unix-domain estimate at all.
+fn hybrid_time(state: &ClockState) -> Time {
+    match (&state.anchor, &state.latest.monotonic, &state.latest.ntp) {
+        (Some(anchor), Some(mono), _) => {
+            let ns = i128::from(mono.sample.value_ns) + anchor.offset_ns;
+            let mut provenance = vec![anchor.ntp, anchor.monotonic];
+            if mono.event_id != anchor.monotonic {
+                provenance.push(mono.event_id);
+            }
+            Time {
+                ns: ns.clamp(0, i128::from(u64::MAX)) as u64,
+                uncertainty_ns: mono
+                    .sample
+                    .uncertainty_ns
+                    .saturating_add(anchor.uncertainty_ns),
+                domain: TimeDomain::Unix,
+                provenance,
+            }
+        }
+        (_, _, Some(ntp)) => Time {
+            ns: ntp.sample.value_ns,
+            uncertainty_ns: ntp.sample.uncertainty_ns,
+            domain: TimeDomain::Unix,
+            provenance: vec![ntp.event_id],
+        },
+        _ => Time::unknown(),
+    }
+}
+
+/// Inverse-variance fusion of the latest Unix-domain samples (NTP, RTC, peer
+/// claim, in that order).
+///
+/// Folding pairwise is exact inverse-variance weighting up to integer
+/// rounding: two estimates `(x1, v1)` and `(x2, v2)` fuse to
+/// `x1 + (x2 - x1) * v1 / (v1 + v2)` with variance `v1 * v2 / (v1 + v2)`.
+/// Everything is integer nanoseconds (variances in ns², u128), so the result
+/// is bit-identical everywhere. Monotonic samples live in another domain and
+/// are not fused.
+fn fuse_unix_samples(latest: &LatestSamples) -> Time {
+    let mut fused: Option<(u64, u128)> = None;
+    let mut provenance = Vec::new();
+    for record in [&latest.ntp, &latest.rtc, &latest.peer]
+        .into_iter()
+        .flatten()
+    {
+        let sample = &record.sample;
+        let variance = u128::from(sample.uncertainty_ns) * u128::from(sample.uncertainty_ns);
+        fused = Some(match fused {
+            None => (sample.value_ns, variance),
+            Some(acc) => fuse_pair(acc, (sample.value_ns, variance)),
+        });
+        provenance.push(record.event_id);
+    }
+    match fused {
+        Some((ns, variance)) => Time {
+            ns,
+            uncertainty_ns: variance.isqrt() as u64,
+            domain: TimeDomain::Unix,
+            provenance,
+        },
+        None => Time::unknown(),
+    }
+}
+
+/// Fuse two estimates `(ns, variance_ns2)`; see [`fuse_unix_samples`].
+fn fuse_pair((x1, v1): (u64, u128), (x2, v2): (u64, u128)) -> (u64, u128) {
+    // Scale both variances down until their sum fits in 64 bits, so the
+    // products below fit in u128. Only the ratio matters for the estimate.
+    let (mut a, mut b, mut shift) = (v1, v2, 0u32);
+    while a
+        .checked_add(b)
+        .is_none_or(|sum| sum > u128::from(u64::MAX))
+    {
+        a >>= 1;
+        b >>= 1;
+        shift += 1;
+    }
+    if a + b == 0 {
+        // Both exact: split the difference.
+        return (x1 / 2 + x2 / 2 + (x1 % 2 + x2 % 2) / 2, 0);
+    }
+    let step = |delta: u64| (u128::from(delta) * a / (a + b)) as u64;
+    let ns = if x2 >= x1 {
+        x1 + step(x2 - x1)
+    } else {
+        x1 - step(x1 - x2)
+    };
+    // a*b/(a+b) <= min(a, b), so shifting back cannot overflow.
+    (ns, (a * b / (a + b)) << shift)
+}
+
+/// Checkpoint index for historical time queries.
+///
+/// [`ClockView::now_at_cut`] refolds the whole prefix, which is O(cut). The
+/// index records the clock state every `interval` events, so a query binary
+/// searches for the nearest checkpoint at or before the cut and folds at most
+/// `interval - 1` events from there. Results are identical to the naive fold.
+///
+/// The index covers the events pushed into it; queries must pass the same
+/// worldline (or an extension of it).
+#[derive(Debug, Clone)]
+pub struct ClockCheckpoints {
+    policy: ClockPolicyId,
+    staleness: Staleness,
+    interval: usize,
+    checkpoints: Vec<ClockCheckpoint>,
+    /// State after every pushed event.
+    tip: ClockState,
+    len: usize,
+}
+
+/// Clock state before `events[cut]`.
+#[derive(Debug, Clone)]
+struct ClockCheckpoint {
+    cut: usize,
+    state: ClockState,
+    current: Time,
+}
+
+impl ClockCheckpoints {
+    /// Empty index with a checkpoint every `interval` events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn new(policy: ClockPolicyId, interval: usize) -> Self {
+        assert!(interval > 0, "checkpoint interval must be non-zero");
+        Self {
+            policy,
+            staleness: Staleness::default(),
+            interval,
+            checkpoints: vec![ClockCheckpoint {
+                cut: 0,
+                state: ClockState::default(),
+                current: Time::unknown(),
+            }],
+            tip: ClockState::default(),
+            len: 0,
+        }
+    }
+
+    /// Expire stale samples as `staleness` says, like
+    /// [`ClockView::with_staleness`]. Call before pushing events.
+    pub fn with_staleness(mut self, staleness: Staleness) -> Self {
+        self.staleness = staleness;
+        self
+    }
+
+    /// Index a whole worldline.
+    pub fn build(events: &[EventEnvelope], policy: ClockPolicyId, interval: usize) -> Self {
+        let mut index = Self::new(policy, interval);
+        for event in events {
+            index.push(event);
+        }
+        index
+    }
+
+    /// Index the next event of the worldline.
+    pub fn push(&mut self, event: &EventEnvelope) {
+        self.tip.fold(event);
+        self.len += 1;
+        if self.len.is_multiple_of(self.interval) {
+            self.checkpoints.push(ClockCheckpoint {
+                cut: self.len,
+                state: self.tip.clone(),
+                current: believe(self.policy, &self.staleness, &self.tip),
+            });
+        }
+    }
+
+    /// Number of events indexed.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if no events have been indexed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Policy the index was built for.
+    pub fn policy(&self) -> ClockPolicyId {
+        self.policy
+    }
+
+    /// [`ClockView::now_at_cut`] for `events`, resumed from the nearest
+    /// checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClockError::CutOutOfBounds`] if `cut > events.len()`.
+    pub fn now_at_cut(&self, events: &[EventEnvelope], cut: usize) -> Result<Time, ClockError> {
+        if cut > events.len() {
+            return Err(ClockError::CutOutOfBounds {
+                cut,
+                len: events.len(),
+            });
+        }
+
+        // The checkpoint at cut 0 always exists, so the search never comes up empty.
+        let at = self.checkpoints.partition_point(|c| c.cut <= cut) - 1;
+        let checkpoint = &self.checkpoints[at];
+        if checkpoint.cut == cut {
+            return Ok(checkpoint.current.clone());
+        }
+        let mut state = checkpoint.state.clone();
+        for event in &events[checkpoint.cut..cut] {
+            state.fold(event);
+        }
+        Ok(believe(self.policy, &self.staleness, &state))
     }
 }
 
@@ -166,6 +712,21 @@ impl Time {
         }
     }
 
+    /// Belief assembled by another view in this crate.
+    pub(crate) fn new(
+        ns: u64,
+        uncertainty_ns: u64,
+        domain: TimeDomain,
+        provenance: Vec<Hash>,
+    ) -> Self {
+        Self {
+            ns,
+            uncertainty_ns,
+            domain,
+            provenance,
+        }
+    }
+
     /// Time value in nanoseconds
     pub fn ns(&self) -> u64 {
         self.ns
@@ -176,6 +737,18 @@ impl Time {
         self.uncertainty_ns
     }
 
+    /// Lower bound of the belief: `ns - uncertainty_ns`, clamped at 0.
+    pub fn earliest_ns(&self) -> u64 {
+        self.ns.saturating_sub(self.uncertainty_ns)
+    }
+
+    /// Upper bound of the belief: `ns + uncertainty_ns`, clamped at `u64::MAX`.
+    ///
+    /// [`Time::unknown`] spans every representable instant.
+    pub fn latest_ns(&self) -> u64 {
+        self.ns.saturating_add(self.uncertainty_ns)
+    }
+
     /// Time domain
     pub fn domain(&self) -> TimeDomain {
         self.domain
@@ -193,6 +766,7 @@ pub enum TimeDomain {
     Monotonic, // Monotonic time (relative, no wall-clock meaning)
     Unix,      // Unix epoch time (1970-01-01 00:00:00 UTC)
     Unknown,   // No time information available
+    Logical,   // Simulated time set by SetTime (no physical meaning)
 }
 
 /// Clock sample with provenance
@@ -219,6 +793,45 @@ pub enum ClockSource {
     PeerClaim, // Time claim from another agent
 }
 
+/// Logical time set by a `SetTime { tick, dt }` SLAP
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogicalTime {
+    /// Logical tick the time was set to
+    pub tick: u64,
+    /// Length of one logical tick in nanoseconds
+    pub dt_ns: u64,
+}
+
+impl LogicalTime {
+    /// Logical time for `SetTime { tick, dt }`, `dt` in seconds
+    ///
+    /// `dt` is rounded to whole nanoseconds once, here, so everything
+    /// downstream is integer arithmetic. `None` if `dt` is negative, not
+    /// finite or does not fit in u64 nanoseconds.
+    pub fn from_set_time(tick: u64, dt: f64) -> Option<Self> {
+        let dt_ns = (dt * 1e9).round();
+        if !(0.0..u64::MAX as f64).contains(&dt_ns) {
+            return None;
+        }
+        Some(Self {
+            tick,
+            dt_ns: dt_ns as u64,
+        })
+    }
+
+    /// `tick * dt_ns`, saturating at `u64::MAX`
+    pub fn value_ns(&self) -> u64 {
+        self.tick.saturating_mul(self.dt_ns)
+    }
+}
+
+/// Logical time with provenance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogicalTimeRecord {
+    pub event_id: Hash,
+    pub time: LogicalTime,
+}
+
 /// Latest samples by source (O(1) cache)
 #[derive(Debug, Clone, Default)]
 pub struct LatestSamples {
@@ -228,11 +841,190 @@ pub struct LatestSamples {
     pub peer: Option<ClockSampleRecord>,
 }
 
+/// Everything a policy may read: the latest samples plus the NTP anchor.
+#[derive(Debug, Clone, Default)]
+struct ClockState {
+    latest: LatestSamples,
+    anchor: Option<ClockAnchor>,
+    /// Monotonic reading current when each latest sample arrived, by source
+    /// index; `None` until a monotonic reading exists.
+    stamps: [Option<u64>; 4],
+    /// Latest logical time; overrides every policy once set.
+    logical: Option<LogicalTimeRecord>,
+}
+
+/// Offset from the monotonic domain to the Unix domain, fixed by pairing an
+/// NTP sample with the latest monotonic sample when either arrives.
+#[derive(Debug, Clone)]
+struct ClockAnchor {
+    /// NTP value minus monotonic value.
+    offset_ns: i128,
+    uncertainty_ns: u64,
+    ntp: Hash,
+    monotonic: Hash,
+    /// Monotonic stamp of the anchoring NTP sample.
+    stamp: Option<u64>,
+}
+
+impl LatestSamples {
+    fn get(&self, source: ClockSource) -> Option<&ClockSampleRecord> {
+        match source {
+            ClockSource::Monotonic => self.monotonic.as_ref(),
+            ClockSource::Ntp => self.ntp.as_ref(),
+            ClockSource::Rtc => self.rtc.as_ref(),
+            ClockSource::PeerClaim => self.peer.as_ref(),
+        }
+    }
+}
+
+impl ClockState {
+    /// Fold a clock sample or logical time observation, skipping anything
+    /// else (malformed ones included).
+    fn fold(&mut self, event: &EventEnvelope) {
+        if let Some(record) = decode_sample(event) {
+            self.record(&record);
+        } else if let Ok(Some(record)) = try_decode_logical(event) {
+            self.logical = Some(record);
+        }
+    }
+
+    /// Copy of the state with expired samples (and anchor) removed.
+    fn without_stale(&self, staleness: &Staleness) -> ClockState {
+        let mut fresh = self.clone();
+        let Some(now) = self.latest.monotonic.as_ref().map(|m| m.sample.value_ns) else {
+            return fresh;
+        };
+        let expired = |stamp: Option<u64>, source: ClockSource| {
+            matches!(
+                (stamp, staleness.max_age_ns(source)),
+                (Some(stamp), Some(max_age)) if now.saturating_sub(stamp) > max_age
+            )
+        };
+        for (source, slot) in [
+            (ClockSource::Ntp, &mut fresh.latest.ntp),
+            (ClockSource::Rtc, &mut fresh.latest.rtc),
+            (ClockSource::PeerClaim, &mut fresh.latest.peer),
+        ] {
+            if expired(self.stamps[source_index(source)], source) {
+                *slot = None;
+            }
+        }
+        if let Some(anchor) = &self.anchor {
+            if expired(anchor.stamp, ClockSource::Ntp) {
+                fresh.anchor = None;
+            }
+        }
+        fresh
+    }
+
+    /// Replace the latest sample for the record's source and re-anchor.
+    ///
+    /// Every NTP sample re-anchors against the latest monotonic sample. A
+    /// monotonic sample only anchors if no anchor exists yet (NTP arrived
+    /// first); afterwards monotonic time advances from the existing anchor.
+    fn record(&mut self, record: &ClockSampleRecord) {
+        let slot = match record.sample.source {
+            ClockSource::Monotonic => &mut self.latest.monotonic,
+            ClockSource::Ntp => &mut self.latest.ntp,
+            ClockSource::Rtc => &mut self.latest.rtc,
+            ClockSource::PeerClaim => &mut self.latest.peer,
+        };
+        *slot = Some(record.clone());
+
+        // Stamp the sample with the current monotonic reading; the first
+        // monotonic reading also stamps every sample that arrived before it.
+        let index = source_index(record.sample.source);
+        if record.sample.source == ClockSource::Monotonic {
+            let now = record.sample.value_ns;
+            for stamp in &mut self.stamps {
+                stamp.get_or_insert(now);
+            }
+            self.stamps[index] = Some(now);
+        } else {
+            self.stamps[index] = self.latest.monotonic.as_ref().map(|m| m.sample.value_ns);
+        }
+
+        let reanchor = match record.sample.source {
+            ClockSource::Ntp => true,
+            ClockSource::Monotonic => self.anchor.is_none(),
+            _ => false,
+        };
+        if let (true, Some(ntp), Some(mono)) = (reanchor, &self.latest.ntp, &self.latest.monotonic)
+        {
+            self.anchor = Some(ClockAnchor {
+                offset_ns: i128::from(ntp.sample.value_ns) - i128::from(mono.sample.value_ns),
+                uncertainty_ns: ntp
+                    .sample
+                    .uncertainty_ns
+                    .saturating_add(mono.sample.uncertainty_ns),
+                ntp: ntp.event_id,
+                monotonic: mono.event_id,
+                stamp: self.stamps[source_index(ClockSource::Ntp)],
+            });
+        }
+    }
+}
+
 /// Clock policy selector
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClockPolicyId {
     TrustMonotonicLatest, // Use latest monotonic sample only
     TrustNtpLatest,       // Use latest NTP sample only
+    WeightedFusion,       // Inverse-variance fusion of latest Unix-domain samples
+    HybridMonotonicNtp,   // Monotonic time offset into the Unix domain by NTP
+}
+
+impl ClockPolicyId {
+    /// Every clock policy, in declaration order.
+    pub const ALL: [ClockPolicyId; 4] = [
+        ClockPolicyId::TrustMonotonicLatest,
+        ClockPolicyId::TrustNtpLatest,
+        ClockPolicyId::WeightedFusion,
+        ClockPolicyId::HybridMonotonicNtp,
+    ];
+
+    /// Stable policy name (CLI flags, reports).
+    pub fn name(&self) -> &'static str {
+        match self {
+            ClockPolicyId::TrustMonotonicLatest => "trust_monotonic_latest",
+            ClockPolicyId::TrustNtpLatest => "trust_ntp_latest",
+            ClockPolicyId::WeightedFusion => "weighted_fusion",
+            ClockPolicyId::HybridMonotonicNtp => "hybrid_monotonic_ntp",
+        }
+    }
+
+    /// Sample history the policy reads beyond the latest sample per source.
+    ///
+    /// Every current policy needs only the latest samples. A policy that
+    /// reads history (e.g. a median filter) returns what it needs here, and
+    /// [`ClockView`] never keeps less.
+    pub fn retention(&self) -> SampleRetention {
+        match self {
+            ClockPolicyId::TrustMonotonicLatest
+            | ClockPolicyId::TrustNtpLatest
+            | ClockPolicyId::WeightedFusion
+            | ClockPolicyId::HybridMonotonicNtp => SampleRetention::None,
+        }
+    }
+
+    /// Inverse of [`ClockPolicyId::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    /// Content-addressed policy reference, as carried by
+    /// `DeltaKind::ClockPolicy { new_policy }`.
+    ///
+    /// H(canonical("clock-policy-v0", name))
+    pub fn policy_hash(&self) -> Hash {
+        // Encoding a pair of strings cannot fail.
+        canonical::hash_canonical(&("clock-policy-v0", self.name())).expect("policy hash encoding")
+    }
+
+    /// Resolve a policy hash back to a known clock policy.
+    pub fn from_policy_hash(hash: &Hash) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.policy_hash() == *hash)
+    }
 }
 
 /// Clock view errors
@@ -240,4 +1032,6 @@ pub enum ClockPolicyId {
 pub enum ClockError {
     #[error("cut {cut} exceeds event sequence length {len}")]
     CutOutOfBounds { cut: usize, len: usize },
+    #[error("malformed clock sample payload in event {0}")]
+    Malformed(Hash),
 }
@@ -0,0 +1,63 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Derived Events - Decisions Proposed by Views
+//!
+//! Views know which events justify acting (the timer request, the clock
+//! samples behind the current time), so they can hand the host fully-formed
+//! `Decision` envelopes instead of leaving it to assemble parents by hand.
+//! A derived decision cites its evidence and exactly one policy context; the
+//! same view state, policy and inputs always derive byte-identical events.
+
+use jitos_core::{
+    events::{CanonicalBytes, EventEnvelope, EventError, EventKind},
+    Hash,
+};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// Build a Decision with `payload`, citing `evidence` under `policy`
+///
+/// Evidence is deduplicated and the policy itself is dropped from it (it is
+/// cited as the policy parent instead).
+///
+/// # Errors
+///
+/// - [`DeriveError::NotAPolicy`] if `policy` is not a `PolicyContext` event
+/// - [`DeriveError::NoEvidence`] if no evidence remains
+/// - [`DeriveError::Event`] if the envelope cannot be built
+pub fn derive_decision<T: Serialize>(
+    payload: &T,
+    evidence: impl IntoIterator<Item = Hash>,
+    policy: &EventEnvelope,
+) -> Result<EventEnvelope, DeriveError> {
+    if !matches!(policy.kind(), EventKind::PolicyContext) {
+        return Err(DeriveError::NotAPolicy(policy.event_id()));
+    }
+    let policy_id = policy.event_id();
+    let evidence: BTreeSet<Hash> = evidence.into_iter().filter(|e| *e != policy_id).collect();
+    if evidence.is_empty() {
+        return Err(DeriveError::NoEvidence);
+    }
+    let payload = CanonicalBytes::from_value(payload).map_err(|e| DeriveError::Event(e.into()))?;
+    EventEnvelope::new_decision(
+        payload,
+        evidence.into_iter().collect(),
+        policy_id,
+        None,
+        None,
+    )
+    .map_err(DeriveError::Event)
+}
+
+/// Errors deriving events from views
+#[derive(Debug, Error)]
+pub enum DeriveError {
+    #[error("event {0} is not a policy context")]
+    NotAPolicy(Hash),
+    #[error("derived decision has no evidence")]
+    NoEvidence,
+    #[error("cannot build derived event: {0}")]
+    Event(EventError),
+}
@@ -0,0 +1,198 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drift View - Clock Skew as Materialized View
+//!
+//! Folds clock sample observations and estimates, per source, how fast that
+//! source runs relative to the monotonic clock. Each non-monotonic sample is
+//! paired with the latest monotonic sample; the drift rate is the two-point
+//! slope between the first and the latest pair of a source:
+//!
+//! ```text
+//! drift = ((src₂ - src₁) - (mono₂ - mono₁)) / (mono₂ - mono₁)
+//! ```
+//!
+//! Rates are fixed-point parts per billion and everything is integer
+//! arithmetic, so estimates are bit-identical on replay.
+
+use jitos_core::{events::EventEnvelope, Hash};
+
+use crate::clock::decode_sample;
+use crate::{ClockError, ClockSampleRecord, ClockSource, Time, TimeDomain};
+
+/// Fixed-point scale of drift rates: 1.0 = 1_000_000_000 ppb.
+const PPB: i128 = 1_000_000_000;
+
+/// Drift view - deterministic drift estimates over clock observation events
+#[derive(Debug, Clone, Default)]
+pub struct DriftView {
+    latest_monotonic: Option<ClockSampleRecord>,
+    ntp: Option<SourcePairs>,
+    rtc: Option<SourcePairs>,
+    peer: Option<SourcePairs>,
+}
+
+/// Drift of one source relative to monotonic time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DriftEstimate {
+    /// Source clock rate minus monotonic rate, in parts per billion. Positive
+    /// means the source runs fast.
+    pub drift_ppb: i64,
+    /// Bound on `drift_ppb` from the uncertainties of the four samples used.
+    pub uncertainty_ppb: u64,
+    /// Monotonic time spanned by the estimate.
+    pub span_ns: u64,
+    /// Number of samples from this source that were paired.
+    pub samples: u64,
+}
+
+/// A source sample and the monotonic sample current when it arrived.
+#[derive(Debug, Clone, Copy)]
+struct Pair {
+    source_ns: u64,
+    source_uncertainty_ns: u64,
+    source_event: Hash,
+    monotonic_ns: u64,
+    monotonic_uncertainty_ns: u64,
+    monotonic_event: Hash,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SourcePairs {
+    first: Pair,
+    latest: Pair,
+    samples: u64,
+}
+
+impl DriftView {
+    /// Create empty drift view
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one event in canonical worldline order
+    ///
+    /// # Errors
+    ///
+    /// Currently never returns an error. Events that are not clock observations
+    /// are silently ignored, as are source samples seen before any monotonic
+    /// sample (there is nothing to pair them with).
+    pub fn apply_event(&mut self, event: &EventEnvelope) -> Result<(), ClockError> {
+        let Some(record) = decode_sample(event) else {
+            return Ok(());
+        };
+        if record.sample.source == ClockSource::Monotonic {
+            self.latest_monotonic = Some(record);
+            return Ok(());
+        }
+        let Some(mono) = &self.latest_monotonic else {
+            return Ok(());
+        };
+        let pair = Pair {
+            source_ns: record.sample.value_ns,
+            source_uncertainty_ns: record.sample.uncertainty_ns,
+            source_event: record.event_id,
+            monotonic_ns: mono.sample.value_ns,
+            monotonic_uncertainty_ns: mono.sample.uncertainty_ns,
+            monotonic_event: mono.event_id,
+        };
+        let Some(slot) = self.slot_mut(record.sample.source) else {
+            return Ok(());
+        };
+        match slot {
+            Some(pairs) => {
+                pairs.latest = pair;
+                pairs.samples += 1;
+            }
+            None => {
+                *slot = Some(SourcePairs {
+                    first: pair,
+                    latest: pair,
+                    samples: 1,
+                })
+            }
+        }
+        Ok(())
+    }
+
+    /// Drift of `source` relative to monotonic time.
+    ///
+    /// `None` for [`ClockSource::Monotonic`] and until two samples of
+    /// `source` have been paired with different monotonic times.
+    pub fn estimate(&self, source: ClockSource) -> Option<DriftEstimate> {
+        let pairs = self.slot(source)?.as_ref()?;
+        let (first, latest) = (pairs.first, pairs.latest);
+        let span = i128::from(latest.monotonic_ns) - i128::from(first.monotonic_ns);
+        if span <= 0 {
+            return None;
+        }
+        let source_span = i128::from(latest.source_ns) - i128::from(first.source_ns);
+        let drift = (source_span - span) * PPB / span;
+        let noise = [
+            first.source_uncertainty_ns,
+            first.monotonic_uncertainty_ns,
+            latest.source_uncertainty_ns,
+            latest.monotonic_uncertainty_ns,
+        ]
+        .into_iter()
+        .map(i128::from)
+        .sum::<i128>();
+        Some(DriftEstimate {
+            drift_ppb: drift.clamp(i128::from(i64::MIN), i128::from(i64::MAX)) as i64,
+            uncertainty_ppb: (noise * PPB / span).min(i128::from(u64::MAX)) as u64,
+            span_ns: span as u64,
+            samples: pairs.samples,
+        })
+    }
+
+    /// Predicted `source` time at monotonic instant `monotonic_ns`.
+    ///
+    /// Extrapolates from the latest pair at the estimated rate. The
+    /// uncertainty is that of the latest pair plus the drift bound over the
+    /// elapsed monotonic time. Provenance lists the events behind the
+    /// estimate. `None` whenever [`Self::estimate`] is.
+    pub fn predict(&self, source: ClockSource, monotonic_ns: u64) -> Option<Time> {
+        let estimate = self.estimate(source)?;
+        let pairs = self.slot(source)?.as_ref()?;
+        let (first, latest) = (pairs.first, pairs.latest);
+
+        let elapsed = i128::from(monotonic_ns) - i128::from(latest.monotonic_ns);
+        let ns =
+            i128::from(latest.source_ns) + elapsed + elapsed * i128::from(estimate.drift_ppb) / PPB;
+        let spread = elapsed.unsigned_abs() * u128::from(estimate.uncertainty_ppb) / PPB as u128;
+        let uncertainty = u128::from(latest.source_uncertainty_ns)
+            + u128::from(latest.monotonic_uncertainty_ns)
+            + spread;
+
+        let provenance = vec![
+            first.source_event,
+            first.monotonic_event,
+            latest.source_event,
+            latest.monotonic_event,
+        ];
+        Some(Time::new(
+            ns.clamp(0, i128::from(u64::MAX)) as u64,
+            uncertainty.min(u128::from(u64::MAX)) as u64,
+            TimeDomain::Unix,
+            provenance,
+        ))
+    }
+
+    fn slot(&self, source: ClockSource) -> Option<&Option<SourcePairs>> {
+        match source {
+            ClockSource::Monotonic => None,
+            ClockSource::Ntp => Some(&self.ntp),
+            ClockSource::Rtc => Some(&self.rtc),
+            ClockSource::PeerClaim => Some(&self.peer),
+        }
+    }
+
+    fn slot_mut(&mut self, source: ClockSource) -> Option<&mut Option<SourcePairs>> {
+        match source {
+            ClockSource::Monotonic => None,
+            ClockSource::Ntp => Some(&mut self.ntp),
+            ClockSource::Rtc => Some(&mut self.rtc),
+            ClockSource::PeerClaim => Some(&mut self.peer),
+        }
+    }
+}
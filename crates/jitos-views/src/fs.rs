@@ -0,0 +1,238 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! File System View - Virtual File Tree as Materialized View
+//!
+//! Folds `OBS_FS_WRITE_V0` / `OBS_FS_DELETE_V0` observations into a
+//! path → (content hash, length, provenance) tree. Contents themselves stay in
+//! the event payloads; the view only remembers what each path currently
+//! holds and which event put it there.
+//!
+//! Paths are absolute, `/`-separated and already normalized: no empty, `.` or
+//! `..` components and no trailing slash. Directories are implicit - a
+//! directory exists while some file lives under it - so a path can never be
+//! both a file and a directory.
+
+use jitos_core::{canonical, events::EventEnvelope, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Observation type tag for file write events
+pub const OBS_FS_WRITE_V0: &str = "OBS_FS_WRITE_V0";
+
+/// Observation type tag for file delete events
+pub const OBS_FS_DELETE_V0: &str = "OBS_FS_DELETE_V0";
+
+/// File system view - deterministic materialized view over file events
+#[derive(Debug, Clone, Default)]
+pub struct FileSystemView {
+    files: BTreeMap<String, FileEntry>,
+}
+
+impl FileSystemView {
+    /// Create empty file system view
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one event in canonical worldline order
+    ///
+    /// A write replaces whatever the path held; deleting a path that holds no
+    /// file is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// - [`FsError::MalformedWrite`] / [`FsError::MalformedDelete`] if a tagged
+    ///   observation has an invalid payload
+    /// - [`FsError::InvalidPath`] if the path is not normalized and absolute
+    /// - [`FsError::PathConflict`] if a write would turn a directory into a
+    ///   file or write beneath an existing file
+    ///
+    /// A rejected event leaves the view unchanged. Other events are silently
+    /// ignored.
+    pub fn apply_event(&mut self, event: &EventEnvelope) -> Result<(), FsError> {
+        if !matches!(event.kind(), jitos_core::events::EventKind::Observation) {
+            return Ok(());
+        }
+        match event.observation_type() {
+            Some(OBS_FS_WRITE_V0) => {
+                let write: FsWrite = event
+                    .payload()
+                    .to_value()
+                    .map_err(|_| FsError::MalformedWrite(event.event_id()))?;
+                validate_path(&write.path, event.event_id())?;
+                if self.conflicts(&write.path) {
+                    return Err(FsError::PathConflict {
+                        path: write.path,
+                        event_id: event.event_id(),
+                    });
+                }
+                let entry = FileEntry {
+                    content_hash: content_hash(&write.content),
+                    len: write.content.len() as u64,
+                    event_id: event.event_id(),
+                };
+                self.files.insert(write.path, entry);
+            }
+            Some(OBS_FS_DELETE_V0) => {
+                let delete: FsDelete = event
+                    .payload()
+                    .to_value()
+                    .map_err(|_| FsError::MalformedDelete(event.event_id()))?;
+                validate_path(&delete.path, event.event_id())?;
+                self.files.remove(&delete.path);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// True if writing a file at `path` would clash with the current tree
+    fn conflicts(&self, path: &str) -> bool {
+        let ancestor_is_file = path
+            .match_indices('/')
+            .skip(1)
+            .any(|(i, _)| self.files.contains_key(&path[..i]));
+        let prefix = format!("{path}/");
+        let is_directory = self
+            .files
+            .range(prefix.clone()..)
+            .next()
+            .is_some_and(|(p, _)| p.starts_with(&prefix));
+        ancestor_is_file || is_directory
+    }
+
+    /// Current contents and provenance of the file at `path`
+    pub fn get(&self, path: &str) -> Option<&FileEntry> {
+        self.files.get(path)
+    }
+
+    /// Immediate children of directory `dir`, ascending by name
+    ///
+    /// `"/"` lists the root. Empty if `dir` is not a directory.
+    pub fn list(&self, dir: &str) -> Vec<DirEntry<'_>> {
+        let prefix = if dir == "/" {
+            "/".to_string()
+        } else {
+            format!("{dir}/")
+        };
+        let mut children = BTreeMap::new();
+        for (path, entry) in self
+            .files
+            .range(prefix.clone()..)
+            .take_while(|(p, _)| p.starts_with(&prefix))
+        {
+            let rest = &path[prefix.len()..];
+            match rest.split_once('/') {
+                Some((name, _)) => {
+                    children.insert(name, DirEntry::Directory { name });
+                }
+                None => {
+                    children.insert(rest, DirEntry::File { name: rest, entry });
+                }
+            }
+        }
+        children.into_values().collect()
+    }
+
+    /// All files, ascending by path
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FileEntry)> {
+        self.files.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Number of files
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// True if no file exists
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Digest of the tree contents
+    ///
+    /// Covers every path with its content hash and length, but not
+    /// provenance: two worldlines that end with the same files hash equal.
+    pub fn state_hash(&self) -> Hash {
+        let files: Vec<(&str, Hash, u64)> = self
+            .files
+            .iter()
+            .map(|(path, entry)| (path.as_str(), entry.content_hash, entry.len))
+            .collect();
+        // Encoding strings, hashes and integers cannot fail.
+        canonical::hash_canonical(&("fs-state-v0", files)).expect("fs state hash encoding")
+    }
+}
+
+fn content_hash(content: &[u8]) -> Hash {
+    canonical::hash_canonical(&("fs-content-v0", content)).expect("fs content hash encoding")
+}
+
+fn validate_path(path: &str, event_id: Hash) -> Result<(), FsError> {
+    let valid = path.strip_prefix('/').is_some_and(|rest| {
+        rest.split('/')
+            .all(|c| !c.is_empty() && c != "." && c != "..")
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(FsError::InvalidPath {
+            path: path.to_string(),
+            event_id,
+        })
+    }
+}
+
+/// A file's current contents, by hash, with provenance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    pub content_hash: Hash,
+    /// Content length in bytes
+    pub len: u64,
+    /// Event id of the write that produced this content
+    pub event_id: Hash,
+}
+
+/// One child of a listed directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirEntry<'a> {
+    File { name: &'a str, entry: &'a FileEntry },
+    Directory { name: &'a str },
+}
+
+impl<'a> DirEntry<'a> {
+    /// Child name (last path component)
+    pub fn name(&self) -> &'a str {
+        match self {
+            DirEntry::File { name, .. } | DirEntry::Directory { name } => name,
+        }
+    }
+}
+
+/// File write observation payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsWrite {
+    pub path: String,
+    pub content: Vec<u8>,
+}
+
+/// File delete observation payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsDelete {
+    pub path: String,
+}
+
+/// File system view errors
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FsError {
+    #[error("malformed file write payload in event {0}")]
+    MalformedWrite(Hash),
+    #[error("malformed file delete payload in event {0}")]
+    MalformedDelete(Hash),
+    #[error("invalid path {path:?} in event {event_id}")]
+    InvalidPath { path: String, event_id: Hash },
+    #[error("path {path:?} in event {event_id} conflicts with an existing file or directory")]
+    PathConflict { path: String, event_id: Hash },
+}
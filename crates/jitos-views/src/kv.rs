@@ -0,0 +1,141 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Key-Value View - Application State as Materialized View
+//!
+//! Folds `OBS_KV_PUT_V0` / `OBS_KV_DELETE_V0` observations into a key → value
+//! map. Writes are last-writer-wins by canonical worldline order: the view
+//! holds whatever the latest applied put (or delete) said, with the event id
+//! of that put as provenance.
+
+use jitos_core::{events::EventEnvelope, Hash};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Observation type tag for key-value put events
+pub const OBS_KV_PUT_V0: &str = "OBS_KV_PUT_V0";
+
+/// Observation type tag for key-value delete events
+pub const OBS_KV_DELETE_V0: &str = "OBS_KV_DELETE_V0";
+
+/// Key-value view - deterministic materialized view over put/delete events
+#[derive(Debug, Clone, Default)]
+pub struct KvView {
+    entries: BTreeMap<String, KvEntry>,
+}
+
+impl KvView {
+    /// Create empty key-value view
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one event in canonical worldline order
+    ///
+    /// # Errors
+    ///
+    /// Returns `KvError::MalformedPut` / `KvError::MalformedDelete` if a tagged
+    /// observation has an invalid payload. Other events are silently ignored.
+    pub fn apply_event(&mut self, event: &EventEnvelope) -> Result<(), KvError> {
+        if !matches!(event.kind(), jitos_core::events::EventKind::Observation) {
+            return Ok(());
+        }
+        match event.observation_type() {
+            Some(OBS_KV_PUT_V0) => {
+                let put: KvPut = event
+                    .payload()
+                    .to_value()
+                    .map_err(|_| KvError::MalformedPut(event.event_id()))?;
+                self.entries.insert(
+                    put.key,
+                    KvEntry {
+                        value: put.value,
+                        event_id: event.event_id(),
+                    },
+                );
+            }
+            Some(OBS_KV_DELETE_V0) => {
+                let delete: KvDelete = event
+                    .payload()
+                    .to_value()
+                    .map_err(|_| KvError::MalformedDelete(event.event_id()))?;
+                self.entries.remove(&delete.key);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Pure fold over a prefix of a canonical worldline
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KvError::CutOutOfBounds`] if `cut > events.len()`, or the
+    /// first malformed put/delete in the prefix.
+    pub fn at_cut(events: &[EventEnvelope], cut: usize) -> Result<Self, KvError> {
+        if cut > events.len() {
+            return Err(KvError::CutOutOfBounds {
+                cut,
+                len: events.len(),
+            });
+        }
+        let mut view = Self::new();
+        for event in &events[..cut] {
+            view.apply_event(event)?;
+        }
+        Ok(view)
+    }
+
+    /// Current value and provenance of `key`
+    pub fn get(&self, key: &str) -> Option<&KvEntry> {
+        self.entries.get(key)
+    }
+
+    /// All live entries, ascending by key
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &KvEntry)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Number of live keys
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True if no key is set
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// A live value with provenance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KvEntry {
+    pub value: Vec<u8>,
+    /// Event id of the put that wrote `value`
+    pub event_id: Hash,
+}
+
+/// Put observation payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KvPut {
+    pub key: String,
+    pub value: Vec<u8>,
+}
+
+/// Delete observation payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KvDelete {
+    pub key: String,
+}
+
+/// Key-value view errors
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum KvError {
+    #[error("malformed key-value put payload in event {0}")]
+    MalformedPut(Hash),
+    #[error("malformed key-value delete payload in event {0}")]
+    MalformedDelete(Hash),
+    #[error("cut {cut} exceeds event sequence length {len}")]
+    CutOutOfBounds { cut: usize, len: usize },
+}
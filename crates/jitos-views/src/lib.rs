@@ -9,14 +9,52 @@
 //! without side effects. Views never touch syscalls - they are pure functions
 //! of their input events.
 
+pub mod anchor;
 pub mod clock;
+pub mod derive;
+pub mod drift;
+pub mod fs;
+pub mod kv;
+pub mod log;
+pub mod network;
+pub mod policy;
+pub mod quota;
+pub mod random;
+pub mod router;
+pub mod schema;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod timer;
 
+pub use anchor::{
+    AnchorView, ClockAnchor, ClockAnchorRecord, ConversionError, OBS_CLOCK_ANCHOR_V0,
+};
 pub use clock::{
-    ClockError, ClockPolicyId, ClockSample, ClockSampleRecord, ClockSource, ClockView,
-    LatestSamples, Time, TimeDomain, OBS_CLOCK_SAMPLE_V0,
+    ClockCheckpoints, ClockError, ClockPolicyId, ClockSample, ClockSampleRecord, ClockSource,
+    ClockView, LatestSamples, LogicalTime, LogicalTimeRecord, SampleRetention, StaleFallback,
+    Staleness, Time, TimeDomain, OBS_CLOCK_SAMPLE_V0, OBS_LOGICAL_TIME_V0,
+};
+pub use derive::{derive_decision, DeriveError};
+pub use drift::{DriftEstimate, DriftView};
+pub use fs::{
+    DirEntry, FileEntry, FileSystemView, FsDelete, FsError, FsWrite, OBS_FS_DELETE_V0,
+    OBS_FS_WRITE_V0,
+};
+pub use kv::{KvDelete, KvEntry, KvError, KvPut, KvView, OBS_KV_DELETE_V0, OBS_KV_PUT_V0};
+pub use log::{LogEntry, LogView};
+pub use network::{
+    InboundMessage, MessageAck, MessageReceive, MessageSend, NetworkError, NetworkView,
+    OutboundMessage, OutboundStatus, OBS_NET_ACK_V0, OBS_NET_MESSAGE_V0,
+};
+pub use policy::{GovernedDecision, PolicyRecord, PolicyView};
+pub use quota::{
+    QuotaError, QuotaLimit, QuotaLimitRecord, QuotaPolicy, QuotaUsage, QuotaView,
+    OBS_QUOTA_USAGE_V0,
 };
+pub use random::RandomnessView;
+pub use router::{ObservationRouter, Route, RouterError, StateHash, View};
+pub use schema::{payload_schemas, EnumSchema, Field, FieldType, PayloadKind, PayloadSchema};
 pub use timer::{
-    TimerError, TimerFire, TimerFireRecord, TimerRequest, TimerRequestRecord, TimerView,
-    OBS_TIMER_REQUEST_V0,
+    FireSemantics, TimerError, TimerFire, TimerFireRecord, TimerRequest, TimerRequestRecord,
+    TimerView, OBS_TIMER_REQUEST_V0,
 };
@@ -0,0 +1,95 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Log View - Per-Topic Append-Only Logs
+//!
+//! Groups tagged observation events by their observation type (the topic)
+//! into append-only logs, in canonical worldline order. Readers consume a
+//! topic with a cursor (an offset into that topic's log) or ask for the log
+//! as of a worldline cut. This is the substrate for message queues built on
+//! the event DAG: a queue is a topic, a consumer is a cursor.
+
+use jitos_core::{
+    events::{CanonicalBytes, EventEnvelope},
+    Hash,
+};
+use std::collections::BTreeMap;
+
+/// Log view - deterministic per-topic logs over observation events
+#[derive(Debug, Clone, Default)]
+pub struct LogView {
+    topics: BTreeMap<String, Vec<LogEntry>>,
+    /// Events applied so far (the next worldline position)
+    events: usize,
+}
+
+/// One appended observation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// Index of the event in the worldline
+    pub position: usize,
+    pub event_id: Hash,
+    pub payload: CanonicalBytes,
+}
+
+impl LogView {
+    /// Create empty log view
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one event in canonical worldline order
+    ///
+    /// Every observation with a type tag is appended to that topic's log;
+    /// untagged observations and other event kinds are ignored. Every event
+    /// advances the worldline position, so positions match cuts.
+    pub fn apply_event(&mut self, event: &EventEnvelope) {
+        let position = self.events;
+        self.events += 1;
+
+        if !matches!(event.kind(), jitos_core::events::EventKind::Observation) {
+            return;
+        }
+        let Some(topic) = event.observation_type() else {
+            return;
+        };
+        let entry = LogEntry {
+            position,
+            event_id: event.event_id(),
+            payload: event.payload().clone(),
+        };
+        match self.topics.get_mut(topic) {
+            Some(log) => log.push(entry),
+            None => {
+                self.topics.insert(topic.to_string(), vec![entry]);
+            }
+        }
+    }
+
+    /// Topics with at least one entry, ascending
+    pub fn topics(&self) -> impl Iterator<Item = &str> {
+        self.topics.keys().map(String::as_str)
+    }
+
+    /// Whole log of `topic` (empty if the topic has never been seen)
+    pub fn log(&self, topic: &str) -> &[LogEntry] {
+        self.topics.get(topic).map_or(&[], Vec::as_slice)
+    }
+
+    /// Up to `max` entries of `topic` starting at offset `cursor`
+    ///
+    /// The next cursor is `cursor + returned.len()`; an empty result means the
+    /// consumer has caught up.
+    pub fn read(&self, topic: &str, cursor: usize, max: usize) -> &[LogEntry] {
+        let log = self.log(topic);
+        let start = cursor.min(log.len());
+        let end = start.saturating_add(max).min(log.len());
+        &log[start..end]
+    }
+
+    /// Log of `topic` as of worldline cut `cut` (events `[0, cut)`)
+    pub fn at_cut(&self, topic: &str, cut: usize) -> &[LogEntry] {
+        let log = self.log(topic);
+        &log[..log.partition_point(|e| e.position < cut)]
+    }
+}
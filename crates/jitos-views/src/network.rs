@@ -0,0 +1,232 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Network View - Inbox/Outbox as Materialized View
+//!
+//! Networking is modelled entirely as events, so it replays like everything
+//! else:
+//!
+//! - a `Decision` carrying a [`MessageSend`] records the intent to send,
+//! - a `Commit` carrying the same [`MessageSend`] records that the message
+//!   actually left the system,
+//! - an `OBS_NET_MESSAGE_V0` observation records an inbound [`MessageReceive`],
+//! - an `OBS_NET_ACK_V0` observation records a peer's [`MessageAck`].
+//!
+//! The view tracks outbound messages and inbound messages per peer `AgentId`
+//! and answers "sent but unacknowledged" queries.
+
+use jitos_core::{
+    events::{AgentId, EventEnvelope, EventKind},
+    Hash,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use thiserror::Error;
+
+/// Observation type tag for inbound message events
+pub const OBS_NET_MESSAGE_V0: &str = "OBS_NET_MESSAGE_V0";
+
+/// Observation type tag for message acknowledgement events
+pub const OBS_NET_ACK_V0: &str = "OBS_NET_ACK_V0";
+
+/// Network view - deterministic materialized view over message events
+#[derive(Debug, Clone, Default)]
+pub struct NetworkView {
+    /// Outbound messages in the order they were first decided or committed
+    outbound: Vec<OutboundMessage>,
+    /// message_id -> index into `outbound`
+    outbound_ids: HashMap<Hash, usize>,
+    /// Inbound messages per sending peer, in worldline order
+    inbound: BTreeMap<String, Vec<InboundMessage>>,
+}
+
+impl NetworkView {
+    /// Create empty network view
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one event in canonical worldline order
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::MalformedMessage` / `NetworkError::MalformedAck`
+    /// if a tagged observation has an invalid payload. Decisions and commits
+    /// that are not message sends, and acks for messages that were never
+    /// committed or that come from a peer other than the recipient, are
+    /// silently ignored.
+    pub fn apply_event(&mut self, event: &EventEnvelope) -> Result<(), NetworkError> {
+        match event.kind() {
+            EventKind::Decision | EventKind::Commit => {
+                if let Ok(send) = event.payload().to_value::<MessageSend>() {
+                    let committed = matches!(event.kind(), EventKind::Commit);
+                    self.record_send(event.event_id(), send, committed);
+                }
+            }
+            EventKind::Observation => match event.observation_type() {
+                Some(OBS_NET_MESSAGE_V0) => {
+                    let message: MessageReceive = event
+                        .payload()
+                        .to_value()
+                        .map_err(|_| NetworkError::MalformedMessage(event.event_id()))?;
+                    self.inbound
+                        .entry(message.from.as_str().to_string())
+                        .or_default()
+                        .push(InboundMessage {
+                            event_id: event.event_id(),
+                            message,
+                        });
+                }
+                Some(OBS_NET_ACK_V0) => {
+                    let ack: MessageAck = event
+                        .payload()
+                        .to_value()
+                        .map_err(|_| NetworkError::MalformedAck(event.event_id()))?;
+                    if let Some(&index) = self.outbound_ids.get(&ack.message_id) {
+                        let outbound = &mut self.outbound[index];
+                        if outbound.commit.is_some()
+                            && outbound.ack.is_none()
+                            && outbound.message.to == ack.from
+                        {
+                            outbound.ack = Some(event.event_id());
+                        }
+                    }
+                }
+                _ => {}
+            },
+            EventKind::PolicyContext => {}
+        }
+        Ok(())
+    }
+
+    fn record_send(&mut self, event_id: Hash, send: MessageSend, committed: bool) {
+        let index = *self.outbound_ids.entry(send.message_id).or_insert_with(|| {
+            self.outbound.push(OutboundMessage {
+                message: send,
+                decision: None,
+                commit: None,
+                ack: None,
+            });
+            self.outbound.len() - 1
+        });
+        let outbound = &mut self.outbound[index];
+        let slot = if committed {
+            &mut outbound.commit
+        } else {
+            &mut outbound.decision
+        };
+        // The first decision / commit for a message id wins.
+        slot.get_or_insert(event_id);
+    }
+
+    /// Every peer seen in either direction, ascending
+    pub fn peers(&self) -> BTreeSet<&str> {
+        self.outbound
+            .iter()
+            .map(|m| m.message.to.as_str())
+            .chain(self.inbound.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// Outbound messages addressed to `peer`, in the order first recorded
+    pub fn outbox(&self, peer: &AgentId) -> Vec<&OutboundMessage> {
+        self.outbound
+            .iter()
+            .filter(|m| m.message.to == *peer)
+            .collect()
+    }
+
+    /// Inbound messages from `peer`, in worldline order
+    pub fn inbox(&self, peer: &AgentId) -> &[InboundMessage] {
+        self.inbound.get(peer.as_str()).map_or(&[], Vec::as_slice)
+    }
+
+    /// Messages to `peer` that were committed but not yet acknowledged
+    pub fn unacknowledged(&self, peer: &AgentId) -> Vec<&OutboundMessage> {
+        self.outbound
+            .iter()
+            .filter(|m| m.message.to == *peer && m.status() == OutboundStatus::Sent)
+            .collect()
+    }
+
+    /// Outbound message by id
+    pub fn outbound(&self, message_id: &Hash) -> Option<&OutboundMessage> {
+        self.outbound_ids
+            .get(message_id)
+            .map(|&i| &self.outbound[i])
+    }
+}
+
+/// Send payload, carried by both the Decision and the Commit of a send
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageSend {
+    pub message_id: Hash,
+    pub to: AgentId,
+    pub payload_bytes: Vec<u8>,
+}
+
+/// Inbound message observation payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageReceive {
+    pub message_id: Hash,
+    pub from: AgentId,
+    pub payload_bytes: Vec<u8>,
+}
+
+/// Acknowledgement observation payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageAck {
+    pub message_id: Hash,
+    /// Peer acknowledging; must be the message's recipient
+    pub from: AgentId,
+}
+
+/// Outbound message with the events that moved it along
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboundMessage {
+    pub message: MessageSend,
+    /// Decision event that chose to send it
+    pub decision: Option<Hash>,
+    /// Commit event that sent it
+    pub commit: Option<Hash>,
+    /// Ack observation from the recipient
+    pub ack: Option<Hash>,
+}
+
+impl OutboundMessage {
+    /// Where the message is in its lifecycle
+    pub fn status(&self) -> OutboundStatus {
+        match (self.commit, self.ack) {
+            (None, _) => OutboundStatus::Decided,
+            (Some(_), None) => OutboundStatus::Sent,
+            (Some(_), Some(_)) => OutboundStatus::Acknowledged,
+        }
+    }
+}
+
+/// Outbound message lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OutboundStatus {
+    /// Decided but not committed
+    Decided,
+    /// Committed, no ack yet
+    Sent,
+    /// Committed and acknowledged by the recipient
+    Acknowledged,
+}
+
+/// Inbound message with provenance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboundMessage {
+    pub event_id: Hash,
+    pub message: MessageReceive,
+}
+
+/// Network view errors
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum NetworkError {
+    #[error("malformed inbound message payload in event {0}")]
+    MalformedMessage(Hash),
+    #[error("malformed message ack payload in event {0}")]
+    MalformedAck(Hash),
+}
@@ -0,0 +1,130 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Policy View - Audit Trail of Policy Contexts
+//!
+//! Policies are first-class events, but nothing indexes them. This view
+//! records every `PolicyContext` event in worldline order and links each
+//! `Decision` to the policy contexts among its parents, so tooling can ask
+//! which policy governed a decision, which decisions a policy governed, and
+//! which policies were in play between two cuts.
+
+use jitos_core::{
+    events::{CanonicalBytes, EventEnvelope, EventKind},
+    Hash,
+};
+use std::collections::{BTreeSet, HashMap};
+
+/// Policy view - deterministic index of policies and the decisions they govern
+#[derive(Debug, Clone, Default)]
+pub struct PolicyView {
+    policies: Vec<PolicyRecord>,
+    /// policy event id -> index into `policies`
+    policy_ids: HashMap<Hash, usize>,
+    decisions: Vec<GovernedDecision>,
+    /// Events applied so far (the next worldline position)
+    events: usize,
+}
+
+/// A policy context event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyRecord {
+    /// Index of the event in the worldline
+    pub position: usize,
+    pub event_id: Hash,
+    pub payload: CanonicalBytes,
+    /// Decisions that cite this policy, in worldline order
+    pub decisions: Vec<Hash>,
+}
+
+/// A decision and the policies among its parents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GovernedDecision {
+    /// Index of the event in the worldline
+    pub position: usize,
+    pub event_id: Hash,
+    /// Policy contexts among the decision's parents, in parent order. Empty if
+    /// the policy was never applied to this view (e.g. the view started
+    /// mid-worldline).
+    pub policies: Vec<Hash>,
+}
+
+impl PolicyView {
+    /// Create empty policy view
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one event in canonical worldline order
+    ///
+    /// Every event advances the worldline position; only policy contexts and
+    /// decisions are recorded.
+    pub fn apply_event(&mut self, event: &EventEnvelope) {
+        let position = self.events;
+        self.events += 1;
+
+        match event.kind() {
+            EventKind::PolicyContext => {
+                if self.policy_ids.contains_key(&event.event_id()) {
+                    return;
+                }
+                self.policy_ids
+                    .insert(event.event_id(), self.policies.len());
+                self.policies.push(PolicyRecord {
+                    position,
+                    event_id: event.event_id(),
+                    payload: event.payload().clone(),
+                    decisions: Vec::new(),
+                });
+            }
+            EventKind::Decision => {
+                let mut policies = Vec::new();
+                for parent in event.parents() {
+                    if let Some(&index) = self.policy_ids.get(parent) {
+                        self.policies[index].decisions.push(event.event_id());
+                        policies.push(*parent);
+                    }
+                }
+                self.decisions.push(GovernedDecision {
+                    position,
+                    event_id: event.event_id(),
+                    policies,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Every policy context, in worldline order
+    pub fn policies(&self) -> &[PolicyRecord] {
+        &self.policies
+    }
+
+    /// Policy context by event id
+    pub fn policy(&self, event_id: &Hash) -> Option<&PolicyRecord> {
+        self.policy_ids.get(event_id).map(|&i| &self.policies[i])
+    }
+
+    /// Every decision with its governing policies, in worldline order
+    pub fn decisions(&self) -> &[GovernedDecision] {
+        &self.decisions
+    }
+
+    /// Decisions whose governing policy was never seen
+    pub fn ungoverned(&self) -> impl Iterator<Item = &GovernedDecision> {
+        self.decisions.iter().filter(|d| d.policies.is_empty())
+    }
+
+    /// Policies that governed decisions in worldline range `[from, to)`
+    ///
+    /// In worldline order of the policies, each listed once.
+    pub fn governing_between(&self, from: usize, to: usize) -> Vec<&PolicyRecord> {
+        let start = self.decisions.partition_point(|d| d.position < from);
+        let end = self.decisions.partition_point(|d| d.position < to);
+        let indices: BTreeSet<usize> = self.decisions[start..end.max(start)]
+            .iter()
+            .flat_map(|d| d.policies.iter().map(|p| self.policy_ids[p]))
+            .collect();
+        indices.into_iter().map(|i| &self.policies[i]).collect()
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Quota View - Per-Agent Resource Accounting
+//!
+//! Folds `OBS_QUOTA_USAGE_V0` observations into per-agent, per-resource usage
+//! totals, and `PolicyContext` events carrying a [`QuotaPolicy`] into limits.
+//! The view only accounts; it never rejects usage. Enforcement (the scheduler,
+//! the script sandbox) asks [`QuotaView::remaining`] before admitting work,
+//! and because both usage and limits are events, every replica answers the
+//! same way at the same cut.
+
+use jitos_core::{
+    events::{AgentId, EventEnvelope, EventKind},
+    Hash,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use thiserror::Error;
+
+/// Observation type tag for resource usage events
+pub const OBS_QUOTA_USAGE_V0: &str = "OBS_QUOTA_USAGE_V0";
+
+/// Quota view - deterministic accounting over usage and policy events
+#[derive(Debug, Clone, Default)]
+pub struct QuotaView {
+    /// (agent, resource) -> total usage
+    usage: BTreeMap<(String, String), u64>,
+    /// (agent, resource) -> limit; agent `None` is the default for everyone
+    limits: BTreeMap<(Option<String>, String), QuotaLimitRecord>,
+}
+
+impl QuotaView {
+    /// Create quota view with no usage and no limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one event in canonical worldline order
+    ///
+    /// Usage saturates at `u64::MAX`. A quota policy sets each limit it lists
+    /// and leaves the others in place. Policy contexts that are not quota
+    /// policies are silently ignored, as are other events.
+    ///
+    /// # Errors
+    ///
+    /// Returns `QuotaError::MalformedUsage` if a usage observation has an
+    /// invalid payload.
+    pub fn apply_event(&mut self, event: &EventEnvelope) -> Result<(), QuotaError> {
+        match event.kind() {
+            EventKind::Observation if event.observation_type() == Some(OBS_QUOTA_USAGE_V0) => {
+                let usage: QuotaUsage = event
+                    .payload()
+                    .to_value()
+                    .map_err(|_| QuotaError::MalformedUsage(event.event_id()))?;
+                let total = self
+                    .usage
+                    .entry((usage.agent.as_str().to_string(), usage.resource))
+                    .or_insert(0);
+                *total = total.saturating_add(usage.amount);
+            }
+            EventKind::PolicyContext => {
+                if let Ok(policy) = event.payload().to_value::<QuotaPolicy>() {
+                    for limit in policy.limits {
+                        let key = (limit.agent.map(|a| a.as_str().to_string()), limit.resource);
+                        self.limits.insert(
+                            key,
+                            QuotaLimitRecord {
+                                limit: limit.limit,
+                                event_id: event.event_id(),
+                            },
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Total `resource` used by `agent`
+    pub fn usage(&self, agent: &AgentId, resource: &str) -> u64 {
+        self.usage
+            .get(&(agent.as_str().to_string(), resource.to_string()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Limit governing `agent`'s use of `resource`, with provenance
+    ///
+    /// An agent-specific limit wins over the default one; `None` means
+    /// unlimited.
+    pub fn limit(&self, agent: &AgentId, resource: &str) -> Option<&QuotaLimitRecord> {
+        let resource = resource.to_string();
+        self.limits
+            .get(&(Some(agent.as_str().to_string()), resource.clone()))
+            .or_else(|| self.limits.get(&(None, resource)))
+    }
+
+    /// Budget `agent` has left for `resource` (`None` if unlimited)
+    pub fn remaining(&self, agent: &AgentId, resource: &str) -> Option<u64> {
+        let limit = self.limit(agent, resource)?;
+        Some(limit.limit.saturating_sub(self.usage(agent, resource)))
+    }
+
+    /// True if `agent` has used more `resource` than its limit allows
+    pub fn is_over_budget(&self, agent: &AgentId, resource: &str) -> bool {
+        self.limit(agent, resource)
+            .is_some_and(|limit| self.usage(agent, resource) > limit.limit)
+    }
+
+    /// Every agent with recorded usage or an agent-specific limit, ascending
+    pub fn agents(&self) -> BTreeSet<&str> {
+        self.usage
+            .keys()
+            .map(|(agent, _)| agent.as_str())
+            .chain(self.limits.keys().filter_map(|(agent, _)| agent.as_deref()))
+            .collect()
+    }
+}
+
+/// Usage observation payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    pub agent: AgentId,
+    pub resource: String,
+    pub amount: u64,
+}
+
+/// Quota policy, carried as a `PolicyContext` payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaPolicy {
+    pub limits: Vec<QuotaLimit>,
+}
+
+/// One limit within a quota policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaLimit {
+    /// Agent the limit applies to; `None` sets the default for all agents
+    pub agent: Option<AgentId>,
+    pub resource: String,
+    pub limit: u64,
+}
+
+/// A limit in force, with provenance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaLimitRecord {
+    pub limit: u64,
+    /// Event id of the policy context that set the limit
+    pub event_id: Hash,
+}
+
+/// Quota view errors
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QuotaError {
+    #[error("malformed quota usage payload in event {0}")]
+    MalformedUsage(Hash),
+}
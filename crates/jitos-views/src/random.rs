@@ -0,0 +1,83 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Randomness View - Replayable Entropy
+//!
+//! Scripts need randomness that reproduces exactly on replay, so entropy is
+//! derived from the worldline instead of the host. The view chains every
+//! applied event id into a cut hash; each draw hashes the cut hash with the
+//! consumer's domain and a per-domain counter:
+//!
+//! ```text
+//! cut₀   = 0³²
+//! cutₙ₊₁ = H("random-cut-v0", cutₙ, event_id)
+//! draw   = H("random-draw-v0", cut, domain, counter)[..8]
+//! ```
+//!
+//! Counters reset whenever an event is applied, so a draw is a pure function
+//! of (worldline prefix, domain, draws in that domain since the last event).
+//! Domains keep independent consumers from perturbing each other's streams.
+
+use jitos_core::{canonical, events::EventEnvelope, Hash};
+use std::collections::BTreeMap;
+
+/// Randomness view - deterministic entropy derived from event ids
+#[derive(Debug, Clone)]
+pub struct RandomnessView {
+    cut_hash: Hash,
+    /// Draws per domain since the last applied event
+    counters: BTreeMap<String, u64>,
+}
+
+impl Default for RandomnessView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RandomnessView {
+    /// Create randomness view at the empty cut
+    pub fn new() -> Self {
+        Self {
+            cut_hash: Hash([0u8; 32]),
+            counters: BTreeMap::new(),
+        }
+    }
+
+    /// Apply one event in canonical worldline order
+    ///
+    /// Every event, of any kind, advances the cut hash and resets all
+    /// per-domain counters.
+    pub fn apply_event(&mut self, event: &EventEnvelope) {
+        // Encoding a tag and two hashes cannot fail.
+        self.cut_hash =
+            canonical::hash_canonical(&("random-cut-v0", self.cut_hash, event.event_id()))
+                .expect("random cut hash encoding");
+        self.counters.clear();
+    }
+
+    /// Next 64 random bits for consumer `domain`
+    pub fn next_u64(&mut self, domain: &str) -> u64 {
+        let counter = match self.counters.get_mut(domain) {
+            Some(counter) => counter,
+            None => self.counters.entry(domain.to_string()).or_insert(0),
+        };
+        let draw = *counter;
+        *counter += 1;
+        let hash = canonical::hash_canonical(&("random-draw-v0", self.cut_hash, domain, draw))
+            .expect("random draw encoding");
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&hash.0[..8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Hash chaining every event applied so far
+    pub fn cut_hash(&self) -> Hash {
+        self.cut_hash
+    }
+
+    /// Draws taken from `domain` since the last applied event
+    pub fn draws(&self, domain: &str) -> u64 {
+        self.counters.get(domain).copied().unwrap_or(0)
+    }
+}
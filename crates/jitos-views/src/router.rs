@@ -0,0 +1,240 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Observation Router - Single-Pass Fan-Out to Views
+//!
+//! Replaying a worldline through N views one at a time costs N passes. The
+//! router makes one pass: each event is offered to every registered view
+//! whose [`Route`] matches it, in registration order. Each view still sees
+//! its events in canonical worldline order, so every view ends up exactly
+//! where a dedicated pass would have left it.
+//!
+//! Views that count worldline positions ([`TimerView`], [`LogView`],
+//! [`PolicyView`]) or chain every event ([`RandomnessView`]) must be
+//! registered with [`Route::All`], or their positions will not match cuts.
+
+use std::convert::Infallible;
+use std::error::Error;
+
+use jitos_core::{
+    events::{EventEnvelope, EventKind},
+    Hash,
+};
+use thiserror::Error;
+
+use crate::{
+    AnchorView, ClockError, ClockView, DriftView, FileSystemView, FsError, KvError, KvView,
+    LogView, NetworkError, NetworkView, PolicyView, QuotaError, QuotaView, RandomnessView,
+    TimerError, TimerView,
+};
+
+/// A materialized view that folds events one at a time
+pub trait View {
+    type Error: Error + Send + Sync + 'static;
+
+    /// Apply one event in canonical worldline order
+    fn apply(&mut self, event: &EventEnvelope) -> Result<(), Self::Error>;
+}
+
+/// A view whose semantic state has a canonical digest
+///
+/// Two views with equal state hashes answer every query the same way.
+pub trait StateHash {
+    fn state_hash(&self) -> Hash;
+}
+
+impl StateHash for ClockView {
+    fn state_hash(&self) -> Hash {
+        ClockView::state_hash(self)
+    }
+}
+
+impl StateHash for TimerView {
+    fn state_hash(&self) -> Hash {
+        TimerView::state_hash(self)
+    }
+}
+
+impl StateHash for FileSystemView {
+    fn state_hash(&self) -> Hash {
+        FileSystemView::state_hash(self)
+    }
+}
+
+macro_rules! impl_view {
+    ($($view:ty => $error:ty),* $(,)?) => {
+        $(
+            impl View for $view {
+                type Error = $error;
+
+                fn apply(&mut self, event: &EventEnvelope) -> Result<(), Self::Error> {
+                    self.apply_event(event)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_infallible_view {
+    ($($view:ty),* $(,)?) => {
+        $(
+            impl View for $view {
+                type Error = Infallible;
+
+                fn apply(&mut self, event: &EventEnvelope) -> Result<(), Self::Error> {
+                    self.apply_event(event);
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_view! {
+    AnchorView => ClockError,
+    ClockView => ClockError,
+    DriftView => ClockError,
+    FileSystemView => FsError,
+    KvView => KvError,
+    NetworkView => NetworkError,
+    QuotaView => QuotaError,
+    TimerView => TimerError,
+}
+
+impl_infallible_view! {
+    LogView,
+    PolicyView,
+    RandomnessView,
+}
+
+/// Which events a registered view receives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// Every event
+    All,
+    /// Events of one kind
+    Kind(EventKind),
+    /// Observations carrying this type tag
+    ObservationType(String),
+    /// Events matching any of the routes
+    AnyOf(Vec<Route>),
+}
+
+impl Route {
+    /// Observations tagged `tag`
+    pub fn observation(tag: &str) -> Self {
+        Route::ObservationType(tag.to_string())
+    }
+
+    /// True if `event` should be delivered along this route
+    pub fn matches(&self, event: &EventEnvelope) -> bool {
+        match self {
+            Route::All => true,
+            Route::Kind(kind) => event.kind() == kind,
+            Route::ObservationType(tag) => {
+                matches!(event.kind(), EventKind::Observation)
+                    && event.observation_type() == Some(tag.as_str())
+            }
+            Route::AnyOf(routes) => routes.iter().any(|r| r.matches(event)),
+        }
+    }
+}
+
+/// Object-safe face of [`View`], boxing the error
+trait DynView {
+    fn apply_dyn(&mut self, event: &EventEnvelope) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+impl<V: View> DynView for V {
+    fn apply_dyn(&mut self, event: &EventEnvelope) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.apply(event).map_err(Into::into)
+    }
+}
+
+struct Registration<'a> {
+    name: &'static str,
+    route: Route,
+    view: &'a mut dyn DynView,
+}
+
+/// Single-pass dispatcher over borrowed views
+///
+/// The router borrows its views mutably; drop it after the replay to query
+/// them.
+#[derive(Default)]
+pub struct ObservationRouter<'a> {
+    registrations: Vec<Registration<'a>>,
+    /// Events dispatched so far
+    events: usize,
+}
+
+impl<'a> ObservationRouter<'a> {
+    /// Create router with no views
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `view` under `name` to receive events matching `route`
+    ///
+    /// Views receive each event in registration order.
+    pub fn register<V: View>(&mut self, name: &'static str, route: Route, view: &'a mut V) {
+        self.registrations.push(Registration { name, route, view });
+    }
+
+    /// Offer one event to every matching view
+    ///
+    /// # Errors
+    ///
+    /// Returns the first view error as [`RouterError::View`]. Views
+    /// registered before the failing one have already applied the event;
+    /// the ones after it have not.
+    pub fn dispatch(&mut self, event: &EventEnvelope) -> Result<(), RouterError> {
+        let position = self.events;
+        self.events += 1;
+        for registration in &mut self.registrations {
+            if registration.route.matches(event) {
+                registration
+                    .view
+                    .apply_dyn(event)
+                    .map_err(|source| RouterError::View {
+                        view: registration.name,
+                        position,
+                        event_id: event.event_id(),
+                        source,
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Dispatch a whole worldline in one pass
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first view error (see [`ObservationRouter::dispatch`]).
+    pub fn replay(&mut self, events: &[EventEnvelope]) -> Result<(), RouterError> {
+        events.iter().try_for_each(|event| self.dispatch(event))
+    }
+
+    /// Names of the registered views, in dispatch order
+    pub fn views(&self) -> Vec<&'static str> {
+        self.registrations.iter().map(|r| r.name).collect()
+    }
+
+    /// Events dispatched so far
+    pub fn events(&self) -> usize {
+        self.events
+    }
+}
+
+/// Router errors
+#[derive(Debug, Error)]
+pub enum RouterError {
+    #[error("view {view} rejected event {event_id} at position {position}: {source}")]
+    View {
+        view: &'static str,
+        position: usize,
+        event_id: Hash,
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
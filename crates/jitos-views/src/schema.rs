@@ -0,0 +1,241 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Payload Schemas - Wire Shapes of the Payloads Views Fold
+//!
+//! [`payload_schemas`] lists every observation and decision payload type the
+//! views in this crate decode, with the shape serde gives it under the
+//! canonical encoder (SPEC-0001): a CBOR map keyed by field name. Code
+//! generators read the registry to produce codecs for other languages that
+//! stay byte-compatible with Rust producers (see `jitos codegen`).
+//!
+//! Each entry carries a round-trip function over the real Rust type, so a
+//! schema that drifts from its struct is caught by decoding bytes built from
+//! the schema.
+
+use jitos_core::canonical::{self, CanonicalError};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    ClockAnchor, ClockSample, FsDelete, FsWrite, KvDelete, KvPut, LogicalTime, MessageAck,
+    MessageReceive, MessageSend, QuotaUsage, TimerFire, TimerRequest, OBS_CLOCK_ANCHOR_V0,
+    OBS_CLOCK_SAMPLE_V0, OBS_FS_DELETE_V0, OBS_FS_WRITE_V0, OBS_KV_DELETE_V0, OBS_KV_PUT_V0,
+    OBS_LOGICAL_TIME_V0, OBS_NET_ACK_V0, OBS_NET_MESSAGE_V0, OBS_QUOTA_USAGE_V0,
+    OBS_TIMER_REQUEST_V0,
+};
+
+/// Wire type of one payload field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    /// `u64`: an unsigned integer in its shortest form
+    U64,
+    /// `String`: UTF-8 text
+    Text,
+    /// `AgentId`: non-empty text
+    AgentId,
+    /// `Vec<u8>`: an array of unsigned integers below 256, not a byte string
+    Bytes,
+    /// `Hash`: an array of exactly 32 unsigned integers below 256
+    Hash,
+    /// Enum of unit variants: the variant name as text
+    Enum(&'static EnumSchema),
+}
+
+/// A unit-variant enum used by payload fields
+#[derive(Debug, PartialEq, Eq)]
+pub struct EnumSchema {
+    pub name: &'static str,
+    pub variants: &'static [&'static str],
+}
+
+/// One payload field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field {
+    pub name: &'static str,
+    pub ty: FieldType,
+}
+
+/// Event kind a payload travels in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadKind {
+    Observation,
+    Decision,
+}
+
+/// Wire shape of one payload type
+#[derive(Debug, Clone, Copy)]
+pub struct PayloadSchema {
+    /// Rust type name
+    pub name: &'static str,
+    pub kind: PayloadKind,
+    /// Observation type tag; decisions carry none
+    pub observation_type: Option<&'static str>,
+    /// Fields in declaration order
+    pub fields: &'static [Field],
+    /// Decode canonical bytes as the Rust type and encode it again
+    pub round_trip: fn(&[u8]) -> Result<Vec<u8>, CanonicalError>,
+}
+
+impl PayloadSchema {
+    /// Fields in canonical map-key order: by encoded key bytes, which for
+    /// text keys is shorter names first, then bytewise
+    pub fn canonical_fields(&self) -> Vec<Field> {
+        let mut fields = self.fields.to_vec();
+        fields.sort_by(|a, b| {
+            (a.name.len(), a.name.as_bytes()).cmp(&(b.name.len(), b.name.as_bytes()))
+        });
+        fields
+    }
+}
+
+/// `ClockSource`
+pub const CLOCK_SOURCE: EnumSchema = EnumSchema {
+    name: "ClockSource",
+    variants: &["Monotonic", "Rtc", "Ntp", "PeerClaim"],
+};
+
+const fn field(name: &'static str, ty: FieldType) -> Field {
+    Field { name, ty }
+}
+
+fn round_trip<T: Serialize + DeserializeOwned>(bytes: &[u8]) -> Result<Vec<u8>, CanonicalError> {
+    canonical::encode(&canonical::decode::<T>(bytes)?)
+}
+
+static PAYLOAD_SCHEMAS: &[PayloadSchema] = &[
+    PayloadSchema {
+        name: "ClockSample",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_CLOCK_SAMPLE_V0),
+        fields: &[
+            field("source", FieldType::Enum(&CLOCK_SOURCE)),
+            field("value_ns", FieldType::U64),
+            field("uncertainty_ns", FieldType::U64),
+        ],
+        round_trip: round_trip::<ClockSample>,
+    },
+    PayloadSchema {
+        name: "LogicalTime",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_LOGICAL_TIME_V0),
+        fields: &[
+            field("tick", FieldType::U64),
+            field("dt_ns", FieldType::U64),
+        ],
+        round_trip: round_trip::<LogicalTime>,
+    },
+    PayloadSchema {
+        name: "ClockAnchor",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_CLOCK_ANCHOR_V0),
+        fields: &[
+            field("monotonic_ns", FieldType::U64),
+            field("unix_ns", FieldType::U64),
+            field("uncertainty_ns", FieldType::U64),
+        ],
+        round_trip: round_trip::<ClockAnchor>,
+    },
+    PayloadSchema {
+        name: "TimerRequest",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_TIMER_REQUEST_V0),
+        fields: &[
+            field("request_id", FieldType::Hash),
+            field("duration_ns", FieldType::U64),
+            field("requested_at_ns", FieldType::U64),
+        ],
+        round_trip: round_trip::<TimerRequest>,
+    },
+    PayloadSchema {
+        name: "TimerFire",
+        kind: PayloadKind::Decision,
+        observation_type: None,
+        fields: &[
+            field("request_id", FieldType::Hash),
+            field("fired_at_ns", FieldType::U64),
+        ],
+        round_trip: round_trip::<TimerFire>,
+    },
+    PayloadSchema {
+        name: "KvPut",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_KV_PUT_V0),
+        fields: &[
+            field("key", FieldType::Text),
+            field("value", FieldType::Bytes),
+        ],
+        round_trip: round_trip::<KvPut>,
+    },
+    PayloadSchema {
+        name: "KvDelete",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_KV_DELETE_V0),
+        fields: &[field("key", FieldType::Text)],
+        round_trip: round_trip::<KvDelete>,
+    },
+    PayloadSchema {
+        name: "FsWrite",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_FS_WRITE_V0),
+        fields: &[
+            field("path", FieldType::Text),
+            field("content", FieldType::Bytes),
+        ],
+        round_trip: round_trip::<FsWrite>,
+    },
+    PayloadSchema {
+        name: "FsDelete",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_FS_DELETE_V0),
+        fields: &[field("path", FieldType::Text)],
+        round_trip: round_trip::<FsDelete>,
+    },
+    PayloadSchema {
+        name: "QuotaUsage",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_QUOTA_USAGE_V0),
+        fields: &[
+            field("agent", FieldType::AgentId),
+            field("resource", FieldType::Text),
+            field("amount", FieldType::U64),
+        ],
+        round_trip: round_trip::<QuotaUsage>,
+    },
+    PayloadSchema {
+        name: "MessageSend",
+        kind: PayloadKind::Decision,
+        observation_type: None,
+        fields: &[
+            field("message_id", FieldType::Hash),
+            field("to", FieldType::AgentId),
+            field("payload_bytes", FieldType::Bytes),
+        ],
+        round_trip: round_trip::<MessageSend>,
+    },
+    PayloadSchema {
+        name: "MessageReceive",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_NET_MESSAGE_V0),
+        fields: &[
+            field("message_id", FieldType::Hash),
+            field("from", FieldType::AgentId),
+            field("payload_bytes", FieldType::Bytes),
+        ],
+        round_trip: round_trip::<MessageReceive>,
+    },
+    PayloadSchema {
+        name: "MessageAck",
+        kind: PayloadKind::Observation,
+        observation_type: Some(OBS_NET_ACK_V0),
+        fields: &[
+            field("message_id", FieldType::Hash),
+            field("from", FieldType::AgentId),
+        ],
+        round_trip: round_trip::<MessageAck>,
+    },
+];
+
+/// Every registered observation and decision payload type
+pub fn payload_schemas() -> &'static [PayloadSchema] {
+    PAYLOAD_SCHEMAS
+}
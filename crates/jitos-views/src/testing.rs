@@ -0,0 +1,151 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test support: replay determinism harness for views.
+//!
+//! Enabled with the `testing` feature. Intended for tests and benches only.
+//!
+//! Every view promises to be a pure fold: the same events produce the same
+//! state. [`assert_replays`] checks that by replaying an event slice into `k`
+//! fresh views and comparing their [`StateHash`]es. Views that also promise
+//! not to care about the order of some events (e.g. timer requests applied
+//! between the same two fires) state those stretches as groups, and
+//! [`assert_order_insensitive`] replays `n` permutations within them.
+//!
+//! ```ignore
+//! use jitos_views::{testing::assert_replays, TimerView};
+//!
+//! let hash = assert_replays(TimerView::new, &events, 100);
+//! ```
+//!
+//! Permutations come from a fixed-seed generator, so a failure reproduces
+//! exactly and names the permutation that diverged.
+
+use std::ops::Range;
+
+use jitos_core::{events::EventEnvelope, Hash};
+
+use crate::{StateHash, View};
+
+/// Apply `events` in order to a view built by `make`.
+///
+/// Panics if the view rejects an event: the harness is for event slices that
+/// are valid for the view.
+pub fn replay<V: View>(make: impl Fn() -> V, events: &[EventEnvelope]) -> V {
+    let mut view = make();
+    for (i, event) in events.iter().enumerate() {
+        if let Err(err) = view.apply(event) {
+            panic!("event {i} ({}) rejected: {err}", event.event_id());
+        }
+    }
+    view
+}
+
+/// Assert that `k` replays of `events` into fresh views all reach the same
+/// state hash. Returns the common hash.
+pub fn assert_replays<V: View + StateHash>(
+    make: impl Fn() -> V,
+    events: &[EventEnvelope],
+    k: usize,
+) -> Hash {
+    let mut expected: Option<Hash> = None;
+    for i in 0..k {
+        let hash = replay(&make, events).state_hash();
+        match expected {
+            None => expected = Some(hash),
+            Some(first) => assert_eq!(hash, first, "replay {i} diverged from replay 0"),
+        }
+    }
+    expected.expect("at least one replay")
+}
+
+/// Assert that shuffling the events inside each of `groups` never changes
+/// the state hash, over `n` permutations. Returns the common hash.
+///
+/// Groups are index ranges into `events`; events outside every group keep
+/// their position. Permutation 0 is `events` as given.
+///
+/// Panics if a group is out of bounds or groups overlap.
+pub fn assert_order_insensitive<V: View + StateHash>(
+    make: impl Fn() -> V,
+    events: &[EventEnvelope],
+    groups: &[Range<usize>],
+    n: usize,
+) -> Hash {
+    let mut sorted: Vec<&Range<usize>> = groups.iter().collect();
+    sorted.sort_by_key(|g| g.start);
+    for pair in sorted.windows(2) {
+        assert!(
+            pair[0].end <= pair[1].start,
+            "groups {:?} and {:?} overlap",
+            pair[0],
+            pair[1]
+        );
+    }
+    assert!(
+        sorted.last().is_none_or(|g| g.end <= events.len()),
+        "group out of bounds for {} events",
+        events.len()
+    );
+
+    let mut per_group: Vec<_> = groups
+        .iter()
+        .map(|g| permutations(&events[g.clone()], n).into_iter())
+        .collect();
+    let mut expected: Option<Hash> = None;
+    for i in 0..n {
+        let mut order = events.to_vec();
+        for (group, perms) in groups.iter().zip(&mut per_group) {
+            let perm = perms.next().expect("n permutations per group");
+            order[group.clone()].clone_from_slice(&perm);
+        }
+        let hash = replay(&make, &order).state_hash();
+        match expected {
+            None => expected = Some(hash),
+            Some(first) => {
+                let ids: Vec<Hash> = order.iter().map(EventEnvelope::event_id).collect();
+                assert_eq!(
+                    hash, first,
+                    "permutation {i} diverged from permutation 0 (event order {ids:?})"
+                );
+            }
+        }
+    }
+    expected.expect("at least one permutation")
+}
+
+/// `n` deterministic permutations of `items`.
+///
+/// The first is `items` as given and the second (if `n > 1`) is reversed;
+/// the rest are Fisher–Yates shuffles driven by a fixed-seed SplitMix64.
+pub fn permutations<T: Clone>(items: &[T], n: usize) -> Vec<Vec<T>> {
+    let mut rng = SplitMix64(0x6a09_e667_f3bc_c908);
+    (0..n)
+        .map(|i| {
+            let mut perm = items.to_vec();
+            match i {
+                0 => {}
+                1 => perm.reverse(),
+                _ => {
+                    for j in (1..perm.len()).rev() {
+                        let k = (rng.next() % (j as u64 + 1)) as usize;
+                        perm.swap(j, k);
+                    }
+                }
+            }
+            perm
+        })
+        .collect()
+}
+
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
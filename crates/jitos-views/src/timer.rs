@@ -6,12 +6,12 @@
 //! SPEC-0004: Timers as materialized view over timer request/fire events.
 //! No hidden wall-clock timers.
 
-use jitos_core::{events::EventEnvelope, Hash};
+use jitos_core::{canonical, events::EventEnvelope, Hash};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use thiserror::Error;
 
-use crate::Time;
+use crate::{derive_decision, DeriveError, Time};
 
 /// Observation type tag for timer request events
 pub const OBS_TIMER_REQUEST_V0: &str = "OBS_TIMER_REQUEST_V0";
@@ -19,10 +19,26 @@ pub const OBS_TIMER_REQUEST_V0: &str = "OBS_TIMER_REQUEST_V0";
 /// Timer view - deterministic materialized view over timer events
 #[derive(Debug, Clone)]
 pub struct TimerView {
-    requests: Vec<TimerRequestRecord>,
-    fired: Vec<TimerFireRecord>,
-    /// HashSet of fired request IDs for O(1) lookup in pending_timers
+    /// Request history with worldline positions (see [`TimerView::compact`])
+    requests: Vec<(usize, TimerRequestRecord)>,
+    /// Fire history with worldline positions
+    fired: Vec<(usize, TimerFireRecord)>,
+    /// HashSet of fired request IDs for O(1) lookup on apply
     fired_ids: HashSet<Hash>,
+    /// Unfired requests keyed by (fire_time_ns, request_id, apply sequence),
+    /// so due timers are a range scan. The sequence keeps duplicate requests
+    /// apart, in apply order.
+    pending: BTreeMap<(u64, Hash, u64), TimerRequestRecord>,
+    /// The same keys ordered by request_id first, for removal on fire
+    pending_by_id: BTreeSet<(Hash, u64, u64)>,
+    /// Requests applied so far (the next apply sequence number)
+    applied: u64,
+    /// Events applied so far (the next worldline position)
+    events: usize,
+    /// Digest over every record dropped by compaction
+    compacted: Hash,
+    /// Reject fires that do not match a request (see [`TimerView::strict`])
+    strict: bool,
 }
 
 impl TimerView {
@@ -32,16 +48,45 @@ impl TimerView {
             requests: Vec::new(),
             fired: Vec::new(),
             fired_ids: HashSet::new(),
+            pending: BTreeMap::new(),
+            pending_by_id: BTreeSet::new(),
+            applied: 0,
+            events: 0,
+            compacted: Hash([0u8; 32]),
+            strict: false,
         }
     }
 
+    /// Create timer view that validates fires against their requests
+    ///
+    /// A fire for a request that was never applied, or with `fired_at_ns`
+    /// before the request's fire time, is rejected with a [`TimerError`] and
+    /// not indexed. Fires for already-fired requests are accepted as before.
+    pub fn strict() -> Self {
+        Self {
+            strict: true,
+            ..Self::new()
+        }
+    }
+
+    /// True if fires are validated (see [`Self::strict`])
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
     /// Apply one event in canonical worldline order
     ///
     /// # Errors
     ///
     /// Returns `TimerError::MalformedRequest` if a timer request observation
-    /// has invalid payload. Events that are not timer-related are silently ignored.
+    /// has invalid payload. In strict mode, returns `TimerError::UnknownRequest`
+    /// or `TimerError::EarlyFire` for a fire that fails validation; the fire is
+    /// not indexed. Events that are not timer-related are silently ignored.
     pub fn apply_event(&mut self, event: &EventEnvelope) -> Result<(), TimerError> {
+        // Worldline position of this event (counted for every event)
+        let position = self.events;
+        self.events += 1;
+
         // Process timer request observations
         if matches!(event.kind(), jitos_core::events::EventKind::Observation)
             && event.observation_type() == Some(OBS_TIMER_REQUEST_V0)
@@ -58,8 +103,19 @@ impl TimerView {
                 request,
             };
 
+            // Index the request unless it has already fired
+            let request_id = record.request.request_id;
+            if !self.fired_ids.contains(&request_id) {
+                let fire_time_ns = record.request.fire_time_ns();
+                let seq = self.applied;
+                self.pending
+                    .insert((fire_time_ns, request_id, seq), record.clone());
+                self.pending_by_id.insert((request_id, fire_time_ns, seq));
+            }
+            self.applied += 1;
+
             // Track the request
-            self.requests.push(record);
+            self.requests.push((position, record));
         }
 
         // Process timer fire decisions
@@ -71,6 +127,10 @@ impl TimerView {
                 // Extract request_id (Copy) before moving fire into record
                 let request_id = fire.request_id;
 
+                if self.strict {
+                    self.validate_fire(event.event_id(), &fire)?;
+                }
+
                 // Create fire record with provenance
                 let record = TimerFireRecord {
                     event_id: event.event_id(),
@@ -78,10 +138,21 @@ impl TimerView {
                 };
 
                 // Track the fire event
-                self.fired.push(record);
+                self.fired.push((position, record));
 
                 // Maintain fired_ids index for O(1) lookup
                 self.fired_ids.insert(request_id);
+
+                // Fired timers are no longer pending
+                let fired: Vec<_> = self
+                    .pending_by_id
+                    .range((request_id, 0, 0)..=(request_id, u64::MAX, u64::MAX))
+                    .copied()
+                    .collect();
+                for (id, fire_time_ns, seq) in fired {
+                    self.pending_by_id.remove(&(id, fire_time_ns, seq));
+                    self.pending.remove(&(fire_time_ns, id, seq));
+                }
             }
             // Silently ignore decisions that aren't timer fires
         }
@@ -89,39 +160,216 @@ impl TimerView {
         Ok(())
     }
 
+    /// Strict-mode checks for a fire before it is indexed
+    fn validate_fire(&self, event_id: Hash, fire: &TimerFire) -> Result<(), TimerError> {
+        let request_id = fire.request_id;
+        if self.fired_ids.contains(&request_id) {
+            return Ok(());
+        }
+        // Earliest fire time among pending requests with this id
+        let due_ns = self
+            .pending_by_id
+            .range((request_id, 0, 0)..=(request_id, u64::MAX, u64::MAX))
+            .map(|(_, fire_time_ns, _)| *fire_time_ns)
+            .next()
+            .ok_or(TimerError::UnknownRequest {
+                fire: event_id,
+                request_id,
+            })?;
+        if fire.fired_at_ns < due_ns {
+            return Err(TimerError::EarlyFire {
+                fire: event_id,
+                request_id,
+                fired_at_ns: fire.fired_at_ns,
+                due_ns,
+            });
+        }
+        Ok(())
+    }
+
     /// Get timers that should fire at current_time but haven't yet
     ///
+    /// Compares against the point estimate only; see [`Self::due_timers`]
+    /// for interval semantics.
+    ///
     /// Returns the full TimerRequestRecord (including event_id) so that
     /// callers can construct valid Decision events with proper evidence parents.
+    /// Results are sorted by (fire time, request_id), independent of the order
+    /// in which requests were applied.
     ///
-    /// Complexity: O(M) where M is the number of requests.
-    /// Uses O(1) HashSet lookup instead of O(F) scan over fired events.
+    /// Complexity: O(log P + D) where P is the number of unfired requests and
+    /// D the number returned; fired timers are never visited.
     pub fn pending_timers(&self, current_time: &Time) -> Vec<TimerRequestRecord> {
-        let mut pending = Vec::new();
+        self.due_timers(current_time, FireSemantics::Point)
+    }
 
-        for record in &self.requests {
-            // O(1) check if already fired using HashSet index
-            if self.fired_ids.contains(&record.request.request_id) {
-                continue;
-            }
+    /// Get unfired timers that are due at `current_time` under `semantics`
+    ///
+    /// Same contract and complexity as [`Self::pending_timers`].
+    pub fn due_timers(
+        &self,
+        current_time: &Time,
+        semantics: FireSemantics,
+    ) -> Vec<TimerRequestRecord> {
+        // The instant the fire time is compared against
+        let now_ns = match semantics {
+            FireSemantics::Point => current_time.ns(),
+            FireSemantics::Definitely => current_time.earliest_ns(),
+            FireSemantics::Possibly => current_time.latest_ns(),
+        };
 
-            // Calculate fire time: requested_at + duration
-            // Use saturating_add to prevent overflow (clamps to u64::MAX)
-            let fire_time_ns = record
-                .request
-                .requested_at_ns
-                .saturating_add(record.request.duration_ns);
+        // Every key with fire time <= now, in (fire_time, request_id) order
+        // (receipts depend on a deterministic output order)
+        self.pending
+            .range(..=(now_ns, Hash([0xff; 32]), u64::MAX))
+            .map(|(_, record)| record.clone())
+            .collect()
+    }
 
-            // Check if current time >= fire time
-            if current_time.ns() >= fire_time_ns {
-                pending.push(record.clone());
+    /// Fire decisions for every timer due at `current_time` under `semantics`
+    ///
+    /// One Decision per due request id, in [`Self::due_timers`] order. Each
+    /// cites the timer request and the events behind `current_time` as
+    /// evidence and `policy` as its policy parent. `fired_at_ns` is the instant
+    /// `semantics` compared against the fire time, so it is never before the
+    /// due time and strict views accept the fires.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DeriveError`] if `policy` is not a policy context or an
+    /// envelope cannot be built.
+    pub fn propose_fires(
+        &self,
+        current_time: &Time,
+        semantics: FireSemantics,
+        policy: &EventEnvelope,
+    ) -> Result<Vec<EventEnvelope>, DeriveError> {
+        let fired_at_ns = match semantics {
+            FireSemantics::Point => current_time.ns(),
+            FireSemantics::Definitely => current_time.earliest_ns(),
+            FireSemantics::Possibly => current_time.latest_ns(),
+        };
+        let mut proposed = HashSet::new();
+        self.due_timers(current_time, semantics)
+            .into_iter()
+            .filter(|record| proposed.insert(record.request.request_id))
+            .map(|record| {
+                let fire = TimerFire {
+                    request_id: record.request.request_id,
+                    fired_at_ns,
+                };
+                let evidence =
+                    std::iter::once(record.event_id).chain(current_time.provenance().to_vec());
+                derive_decision(&fire, evidence, policy)
+            })
+            .collect()
+    }
+
+    /// Request history, in apply order (compacted records excluded)
+    pub fn requests(&self) -> impl Iterator<Item = &TimerRequestRecord> {
+        self.requests.iter().map(|(_, record)| record)
+    }
+
+    /// Fire history, in apply order (compacted records excluded)
+    pub fn fired(&self) -> impl Iterator<Item = &TimerFireRecord> {
+        self.fired.iter().map(|(_, record)| record)
+    }
+
+    /// Canonical digest of the view's semantic state
+    ///
+    /// Covers the unfired timers in (fire time, request_id) order and the set
+    /// of fired request ids - everything pending queries depend on. History
+    /// is not included, so [`Self::compact`] leaves the hash unchanged.
+    pub fn state_hash(&self) -> Hash {
+        let pending: Vec<(u64, Hash, Hash)> = self
+            .pending
+            .iter()
+            .map(|((fire_time_ns, request_id, _), record)| {
+                (*fire_time_ns, *request_id, record.event_id)
+            })
+            .collect();
+        let fired: BTreeSet<&Hash> = self.fired_ids.iter().collect();
+        // Encoding a tag, hashes and integers cannot fail.
+        canonical::hash_canonical(&("timer-view-v0", pending, fired))
+            .expect("timer state hash encoding")
+    }
+
+    /// Digest over every record dropped by [`Self::compact`]
+    ///
+    /// All zeros until something is compacted.
+    pub fn compacted_digest(&self) -> Hash {
+        self.compacted
+    }
+
+    /// Drop history that pending computation no longer needs
+    ///
+    /// Removes fire records applied before worldline position `before_cut`
+    /// and the fired requests applied before it. Unfired requests are kept
+    /// however old they are, and so is the set of fired request ids, so
+    /// pending queries answer exactly as before. `before_cut` counts every
+    /// event passed to [`Self::apply_event`], like `ClockView::now_at_cut`.
+    ///
+    /// The dropped event ids (requests, then fires, each in apply order) are
+    /// folded into [`Self::compacted_digest`]:
+    /// `H(canonical("timer-compaction-v0", previous_digest, dropped_ids))`.
+    /// Returns the number of records dropped.
+    pub fn compact(&mut self, before_cut: usize) -> usize {
+        let fired_ids = &self.fired_ids;
+        let mut dropped = Vec::new();
+        self.requests.retain(|(position, record)| {
+            let drop = *position < before_cut && fired_ids.contains(&record.request.request_id);
+            if drop {
+                dropped.push(record.event_id);
+            }
+            !drop
+        });
+        self.fired.retain(|(position, record)| {
+            let drop = *position < before_cut;
+            if drop {
+                dropped.push(record.event_id);
             }
+            !drop
+        });
+
+        if !dropped.is_empty() {
+            // Encoding a hash and a list of hashes cannot fail.
+            self.compacted =
+                canonical::hash_canonical(&("timer-compaction-v0", self.compacted, &dropped))
+                    .expect("compaction digest encoding");
         }
+        dropped.len()
+    }
 
-        pending
+    /// Earliest fire time among timers that have not fired yet
+    ///
+    /// `None` if nothing is outstanding. Hosts use this to decide how long
+    /// they may sleep before something becomes due.
+    pub fn next_fire_time(&self) -> Option<u64> {
+        self.pending
+            .keys()
+            .next()
+            .map(|(fire_time_ns, _, _)| *fire_time_ns)
     }
 }
 
+/// How a [`Time`] interval is compared against a timer's fire time
+///
+/// A timer is due when the chosen instant has reached `requested_at + duration`.
+/// Every `Definitely` timer is also due under `Point`, and every `Point` timer
+/// is also due under `Possibly`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FireSemantics {
+    /// Point estimate (`Time::ns`) - the historical behaviour
+    Point,
+    /// Definitely due: even the earliest plausible time has reached the fire
+    /// time (conservative firing, never early)
+    Definitely,
+    /// Possibly due: the latest plausible time has reached the fire time
+    /// (eager firing, never late; everything is possibly due under
+    /// `Time::unknown()`)
+    Possibly,
+}
+
 impl Default for TimerView {
     fn default() -> Self {
         Self::new()
@@ -143,6 +391,15 @@ pub struct TimerRequest {
     pub requested_at_ns: u64, // Nanosecond timestamp when request was made
 }
 
+impl TimerRequest {
+    /// When the timer is due: requested_at + duration
+    ///
+    /// Saturates at `u64::MAX` instead of overflowing.
+    pub fn fire_time_ns(&self) -> u64 {
+        self.requested_at_ns.saturating_add(self.duration_ns)
+    }
+}
+
 /// Timer fire record with provenance
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TimerFireRecord {
@@ -162,6 +419,15 @@ pub struct TimerFire {
 pub enum TimerError {
     #[error("malformed timer request payload in event {0}")]
     MalformedRequest(Hash),
+    #[error("timer fire {fire} references unknown request {request_id}")]
+    UnknownRequest { fire: Hash, request_id: Hash },
+    #[error("timer fire {fire} for request {request_id} at {fired_at_ns} ns is before its due time {due_ns} ns")]
+    EarlyFire {
+        fire: Hash,
+        request_id: Hash,
+        fired_at_ns: u64,
+        due_ns: u64,
+    },
     // Note: MalformedFire not needed - Decision decode failures are silently
     // ignored until decision_type tagging is implemented
 }
@@ -0,0 +1,46 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! View Allocation Budget Tests
+//!
+//! `apply_event` runs once per event on every replay, so per-event allocation
+//! counts are a direct proxy for replay throughput. Budgets sit a little above
+//! today's measured counts to catch convenience clones creeping in.
+
+mod common;
+
+use common::{make_clock_event, make_timer_request};
+use jitos_core::testing::{assert_alloc_budget, count_allocations, CountingAllocator};
+use jitos_views::{ClockPolicyId, ClockSource, ClockView, TimerView};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+#[test]
+fn a1_clock_apply_event_within_budget() {
+    let mut view = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    let event = make_clock_event(ClockSource::Monotonic, 1_000_000_000, 100_000);
+
+    assert_alloc_budget("ClockView::apply_event", 12, || {
+        view.apply_event(&event).expect("apply event")
+    });
+}
+
+#[test]
+fn a2_clock_ignores_foreign_events_without_allocating() {
+    let mut view = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    let event = make_timer_request([1u8; 32], 5_000_000_000, 1_000_000_000);
+
+    let (_, stats) = count_allocations(|| view.apply_event(&event).expect("apply event"));
+    assert_eq!(stats.allocations, 0, "ignored events must not allocate");
+}
+
+#[test]
+fn a3_timer_apply_request_within_budget() {
+    let mut view = TimerView::new();
+    let event = make_timer_request([1u8; 32], 5_000_000_000, 1_000_000_000);
+
+    assert_alloc_budget("TimerView::apply_event", 12, || {
+        view.apply_event(&event).expect("apply event")
+    });
+}
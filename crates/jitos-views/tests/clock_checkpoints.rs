@@ -0,0 +1,72 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Clock Checkpoint Index Tests
+//!
+//! `ClockCheckpoints::now_at_cut` must agree with the naive
+//! `ClockView::now_at_cut` fold at every cut, for every policy and interval.
+
+mod common;
+
+use common::{make_clock_event, make_timer_request};
+use jitos_core::events::EventEnvelope;
+use jitos_views::{ClockCheckpoints, ClockError, ClockPolicyId, ClockSource, ClockView};
+
+fn worldline() -> Vec<EventEnvelope> {
+    let mut events = Vec::new();
+    for i in 0..40u64 {
+        events.push(match i % 4 {
+            0 => make_clock_event(ClockSource::Monotonic, 1_000_000_000 + i, 100_000),
+            1 => make_clock_event(ClockSource::Ntp, 1_735_387_200_000_000_000 + i, 50_000_000),
+            2 => make_timer_request([i as u8; 32], 5_000_000_000, i),
+            _ => make_clock_event(ClockSource::Rtc, i, 1),
+        });
+    }
+    events
+}
+
+#[test]
+fn c1_checkpointed_queries_match_the_naive_fold() {
+    let events = worldline();
+    for policy in ClockPolicyId::ALL {
+        for interval in [1, 3, 7, 16, 64] {
+            let index = ClockCheckpoints::build(&events, policy, interval);
+            assert_eq!(index.len(), events.len());
+            for cut in 0..=events.len() {
+                assert_eq!(
+                    index.now_at_cut(&events, cut),
+                    ClockView::now_at_cut(&events, cut, policy),
+                    "policy {} interval {interval} cut {cut}",
+                    policy.name()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn c2_queries_past_the_indexed_prefix_fold_the_remainder() {
+    let events = worldline();
+    let policy = ClockPolicyId::TrustMonotonicLatest;
+    let mut index = ClockCheckpoints::new(policy, 8);
+    for event in &events[..10] {
+        index.push(event);
+    }
+    assert_eq!(
+        index.now_at_cut(&events, 30),
+        ClockView::now_at_cut(&events, 30, policy)
+    );
+}
+
+#[test]
+fn c3_out_of_bounds_cut_is_rejected() {
+    let events = worldline();
+    let index = ClockCheckpoints::build(&events, ClockPolicyId::TrustNtpLatest, 4);
+    assert_eq!(
+        index.now_at_cut(&events, events.len() + 1),
+        Err(ClockError::CutOutOfBounds {
+            cut: events.len() + 1,
+            len: events.len()
+        })
+    );
+}
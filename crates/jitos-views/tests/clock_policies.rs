@@ -138,3 +138,170 @@ fn t6_event_integration_both_policies() {
     );
     assert_eq!(time_after_ntp.domain(), TimeDomain::Unix);
 }
+
+#[test]
+fn t7_policy_hashes_are_distinct_and_resolve() {
+    let mono = ClockPolicyId::TrustMonotonicLatest.policy_hash();
+    let ntp = ClockPolicyId::TrustNtpLatest.policy_hash();
+    assert_ne!(mono, ntp);
+
+    for policy in ClockPolicyId::ALL {
+        assert_eq!(
+            ClockPolicyId::from_policy_hash(&policy.policy_hash()),
+            Some(policy)
+        );
+        assert_eq!(ClockPolicyId::from_name(policy.name()), Some(policy));
+    }
+    assert_eq!(
+        ClockPolicyId::from_policy_hash(&jitos_core::Hash([0u8; 32])),
+        None
+    );
+}
+
+// ============================================================================
+// T8: Weighted Fusion
+// ============================================================================
+
+fn fused(events: &[jitos_core::events::EventEnvelope]) -> jitos_views::Time {
+    let mut view = ClockView::new(ClockPolicyId::WeightedFusion);
+    for event in events {
+        view.apply_event(event).expect("apply event");
+    }
+    view.now().clone()
+}
+
+#[test]
+fn t8_fusion_weights_by_inverse_variance() {
+    let base = 1_735_387_200_000_000_000;
+    let events = vec![
+        make_clock_event(ClockSource::Monotonic, 5_000_000_000, 1),
+        make_clock_event(ClockSource::Ntp, base, 10_000_000),
+        make_clock_event(ClockSource::Rtc, base + 1_000_000_000, 30_000_000),
+    ];
+    let time = fused(&events);
+
+    // Weights 1/σ² are 9:1 in favour of NTP.
+    assert_eq!(time.ns(), base + 100_000_000);
+    // sqrt(σ1²σ2² / (σ1² + σ2²)) = sqrt(9e13) ns
+    assert_eq!(time.uncertainty_ns(), 9_486_832);
+    assert_eq!(time.domain(), TimeDomain::Unix);
+    // Monotonic samples are another domain and do not contribute.
+    assert_eq!(
+        time.provenance(),
+        &[events[1].event_id(), events[2].event_id()]
+    );
+}
+
+#[test]
+fn t8_fusion_of_equal_samples_shrinks_uncertainty() {
+    let base = 1_735_387_200_000_000_000;
+    let events = vec![
+        make_clock_event(ClockSource::Ntp, base, 4_000),
+        make_clock_event(ClockSource::PeerClaim, base + 1_000, 4_000),
+    ];
+    let time = fused(&events);
+    assert_eq!(time.ns(), base + 500);
+    assert_eq!(time.uncertainty_ns(), 2_828); // 4000 / sqrt(2)
+
+    // A single sample passes through unchanged.
+    let single = fused(&events[..1]);
+    assert_eq!((single.ns(), single.uncertainty_ns()), (base, 4_000));
+}
+
+#[test]
+fn t8_fusion_handles_extreme_uncertainties() {
+    let events = vec![
+        make_clock_event(ClockSource::Ntp, u64::MAX, u64::MAX),
+        make_clock_event(ClockSource::Rtc, 0, u64::MAX),
+        make_clock_event(ClockSource::PeerClaim, 42, 0),
+    ];
+    let time = fused(&events);
+    assert_eq!(time.ns(), 42, "an exact sample dominates");
+    assert_eq!(time.uncertainty_ns(), 0);
+
+    let both_exact = fused(&[
+        make_clock_event(ClockSource::Ntp, 10, 0),
+        make_clock_event(ClockSource::Rtc, 21, 0),
+    ]);
+    assert_eq!(both_exact.ns(), 15);
+
+    let unknown = fused(&[make_clock_event(ClockSource::Monotonic, 1, 1)]);
+    assert_eq!(unknown.domain(), TimeDomain::Unknown);
+}
+
+// ============================================================================
+// T9: Hybrid Monotonic + NTP
+// ============================================================================
+
+fn hybrid(events: &[jitos_core::events::EventEnvelope]) -> jitos_views::Time {
+    let mut view = ClockView::new(ClockPolicyId::HybridMonotonicNtp);
+    for event in events {
+        view.apply_event(event).expect("apply event");
+    }
+    view.now().clone()
+}
+
+#[test]
+fn t9_hybrid_advances_with_monotonic_in_unix_domain() {
+    let base = 1_735_387_200_000_000_000;
+    let events = vec![
+        make_clock_event(ClockSource::Monotonic, 1_000_000_000, 100),
+        make_clock_event(ClockSource::Ntp, base, 5_000),
+        make_clock_event(ClockSource::Monotonic, 3_000_000_000, 200),
+    ];
+
+    let anchored = hybrid(&events[..2]);
+    assert_eq!(anchored.ns(), base);
+    assert_eq!(anchored.uncertainty_ns(), 100 + 5_000 + 100);
+
+    let time = hybrid(&events);
+    assert_eq!(time.ns(), base + 2_000_000_000);
+    assert_eq!(time.uncertainty_ns(), 200 + 5_000 + 100);
+    assert_eq!(time.domain(), TimeDomain::Unix);
+    assert_eq!(
+        time.provenance(),
+        &[
+            events[1].event_id(),
+            events[0].event_id(),
+            events[2].event_id()
+        ]
+    );
+}
+
+#[test]
+fn t9_hybrid_anchors_on_first_monotonic_after_ntp() {
+    let base = 1_735_387_200_000_000_000;
+    let events = vec![
+        make_clock_event(ClockSource::Ntp, base, 5_000),
+        make_clock_event(ClockSource::Monotonic, 7_000, 10),
+        make_clock_event(ClockSource::Monotonic, 9_000, 10),
+    ];
+
+    // NTP alone passes through.
+    let ntp_only = hybrid(&events[..1]);
+    assert_eq!((ntp_only.ns(), ntp_only.domain()), (base, TimeDomain::Unix));
+
+    // The first monotonic sample fixes the offset; later ones advance from it.
+    assert_eq!(hybrid(&events[..2]).ns(), base);
+    assert_eq!(hybrid(&events).ns(), base + 2_000);
+}
+
+#[test]
+fn t9_hybrid_reanchors_on_every_ntp_sample() {
+    let base = 1_735_387_200_000_000_000;
+    let mut events = vec![
+        make_clock_event(ClockSource::Monotonic, 1_000, 0),
+        make_clock_event(ClockSource::Ntp, base, 0),
+        make_clock_event(ClockSource::Monotonic, 2_000, 0),
+    ];
+    assert_eq!(hybrid(&events).ns(), base + 1_000);
+
+    // NTP says the wall clock drifted back by 500ns.
+    events.push(make_clock_event(ClockSource::Ntp, base + 500, 0));
+    assert_eq!(hybrid(&events).ns(), base + 500);
+    events.push(make_clock_event(ClockSource::Monotonic, 2_100, 0));
+    assert_eq!(hybrid(&events).ns(), base + 600);
+
+    let mono_only = hybrid(&events[..1]);
+    assert_eq!(mono_only.domain(), TimeDomain::Unknown);
+}
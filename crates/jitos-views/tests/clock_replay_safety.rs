@@ -10,7 +10,9 @@ mod common;
 
 use common::make_clock_event;
 use jitos_core::events::{CanonicalBytes, EventEnvelope};
-use jitos_views::{ClockError, ClockPolicyId, ClockSource, ClockView, TimeDomain};
+use jitos_views::{
+    ClockError, ClockPolicyId, ClockSource, ClockView, TimeDomain, OBS_CLOCK_SAMPLE_V0,
+};
 
 // ============================================================================
 // T4: No Host Clock Dependency (AC5)
@@ -133,3 +135,39 @@ fn test_malformed_observation_handling() {
     // Verify time is still unknown (no samples applied)
     assert_eq!(view.now().domain(), TimeDomain::Unknown);
 }
+
+#[test]
+fn test_malformed_tagged_sample_strict_vs_lenient() {
+    // Scenario: a tagged clock sample whose payload does not decode
+    let malformed_event = EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&"not a sample".to_string()).expect("encode string"),
+        vec![],
+        Some(OBS_CLOCK_SAMPLE_V0.to_string()),
+        None,
+        None,
+    )
+    .expect("create malformed event");
+    let sample = make_clock_event(ClockSource::Monotonic, 1_000, 10);
+
+    // Lenient (default): skipped, event id recorded
+    let mut lenient = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    assert!(!lenient.is_strict());
+    lenient
+        .apply_event(&malformed_event)
+        .expect("lenient skips");
+    lenient.apply_event(&sample).expect("apply sample");
+    assert_eq!(lenient.skipped(), &[malformed_event.event_id()]);
+    assert_eq!(lenient.now().ns(), 1_000);
+
+    // Strict: rejected, belief untouched
+    let mut strict = ClockView::strict(ClockPolicyId::TrustMonotonicLatest);
+    assert!(strict.is_strict());
+    assert_eq!(
+        strict.apply_event(&malformed_event),
+        Err(ClockError::Malformed(malformed_event.event_id()))
+    );
+    assert!(strict.skipped().is_empty());
+    assert_eq!(strict.now().domain(), TimeDomain::Unknown);
+    strict.apply_event(&sample).expect("apply sample");
+    assert_eq!(strict.state_hash(), lenient.state_hash());
+}
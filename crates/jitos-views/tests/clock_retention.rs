@@ -0,0 +1,97 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Clock Sample Retention Tests
+//!
+//! Sample history is bounded per source and never affects time beliefs.
+
+mod common;
+
+use common::make_clock_event;
+use jitos_core::events::EventEnvelope;
+use jitos_views::{ClockPolicyId, ClockSource, ClockView, SampleRetention};
+
+fn worldline() -> Vec<EventEnvelope> {
+    (0..10)
+        .flat_map(|i| {
+            [
+                make_clock_event(ClockSource::Monotonic, 1_000 + i * 10, 5),
+                make_clock_event(ClockSource::Ntp, 9_000 + i * 10, 50),
+            ]
+        })
+        .collect()
+}
+
+fn fold(mut view: ClockView, events: &[EventEnvelope]) -> ClockView {
+    for event in events {
+        view.apply_event(event).unwrap();
+    }
+    view
+}
+
+fn values(view: &ClockView, source: ClockSource) -> Vec<u64> {
+    view.history(source).map(|r| r.sample.value_ns).collect()
+}
+
+#[test]
+fn t1_default_keeps_no_history() {
+    let view = fold(
+        ClockView::new(ClockPolicyId::HybridMonotonicNtp),
+        &worldline(),
+    );
+    assert_eq!(view.retention(), SampleRetention::None);
+    assert_eq!(view.history(ClockSource::Monotonic).count(), 0);
+    assert_eq!(view.history(ClockSource::Ntp).count(), 0);
+}
+
+#[test]
+fn t2_last_per_source_keeps_the_newest_k() {
+    let view = fold(
+        ClockView::new(ClockPolicyId::TrustMonotonicLatest)
+            .with_retention(SampleRetention::LastPerSource(3)),
+        &worldline(),
+    );
+    assert_eq!(
+        values(&view, ClockSource::Monotonic),
+        vec![1_070, 1_080, 1_090]
+    );
+    assert_eq!(values(&view, ClockSource::Ntp), vec![9_070, 9_080, 9_090]);
+    assert_eq!(view.history(ClockSource::Rtc).count(), 0);
+}
+
+#[test]
+fn t3_all_keeps_everything_in_order() {
+    let view = fold(
+        ClockView::new(ClockPolicyId::TrustNtpLatest).with_retention(SampleRetention::All),
+        &worldline(),
+    );
+    let expected: Vec<u64> = (0..10).map(|i| 9_000 + i * 10).collect();
+    assert_eq!(values(&view, ClockSource::Ntp), expected);
+}
+
+#[test]
+fn t4_retention_never_changes_beliefs() {
+    let events = worldline();
+    for policy in ClockPolicyId::ALL {
+        let views = [
+            SampleRetention::None,
+            SampleRetention::LastPerSource(1),
+            SampleRetention::All,
+        ]
+        .map(|retention| fold(ClockView::new(policy).with_retention(retention), &events));
+        for view in &views[1..] {
+            assert_eq!(view.now(), views[0].now());
+            assert_eq!(view.state_hash(), views[0].state_hash());
+        }
+    }
+}
+
+#[test]
+fn t5_retention_is_ordered_by_how_much_is_kept() {
+    assert!(SampleRetention::None < SampleRetention::LastPerSource(0));
+    assert!(SampleRetention::LastPerSource(2) < SampleRetention::LastPerSource(5));
+    assert!(SampleRetention::LastPerSource(usize::MAX) < SampleRetention::All);
+    for policy in ClockPolicyId::ALL {
+        assert_eq!(policy.retention(), SampleRetention::None);
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Clock Staleness Tests
+//!
+//! Samples expire once the monotonic cut has advanced past their bound.
+
+mod common;
+
+use common::make_clock_event;
+use jitos_core::events::EventEnvelope;
+use jitos_views::{
+    ClockCheckpoints, ClockPolicyId, ClockSource, ClockView, StaleFallback, Staleness, Time,
+    TimeDomain,
+};
+
+fn mono(ns: u64) -> EventEnvelope {
+    make_clock_event(ClockSource::Monotonic, ns, 1)
+}
+
+fn now(policy: ClockPolicyId, staleness: Staleness, events: &[EventEnvelope]) -> Time {
+    let mut view = ClockView::new(policy).with_staleness(staleness);
+    for event in events {
+        view.apply_event(event).unwrap();
+    }
+    view.now().clone()
+}
+
+#[test]
+fn t1_stale_ntp_expires_to_unknown() {
+    let ntp = make_clock_event(ClockSource::Ntp, 5_000_000, 10);
+    let staleness = Staleness::none().expire(ClockSource::Ntp, 1_000);
+    let policy = ClockPolicyId::TrustNtpLatest;
+
+    let fresh = [mono(0), ntp.clone(), mono(1_000)];
+    assert_eq!(now(policy, staleness, &fresh).ns(), 5_000_000);
+
+    let stale = [mono(0), ntp.clone(), mono(1_001)];
+    assert_eq!(now(policy, staleness, &stale).domain(), TimeDomain::Unknown);
+    // Without a bound the week-old sample is trusted forever.
+    assert_eq!(now(policy, Staleness::none(), &stale).ns(), 5_000_000);
+
+    // A new NTP sample is fresh again.
+    let renewed = [
+        mono(0),
+        ntp,
+        mono(1_001),
+        make_clock_event(ClockSource::Ntp, 6_000_000, 10),
+    ];
+    assert_eq!(now(policy, staleness, &renewed).ns(), 6_000_000);
+}
+
+#[test]
+fn t2_fallback_to_another_source() {
+    let events = [
+        mono(0),
+        make_clock_event(ClockSource::Ntp, 5_000_000, 10),
+        mono(900),
+        make_clock_event(ClockSource::Rtc, 5_000_700, 1_000),
+        mono(2_000),
+    ];
+    let expire_ntp = Staleness::none().expire(ClockSource::Ntp, 1_000);
+
+    let rtc = now(
+        ClockPolicyId::TrustNtpLatest,
+        expire_ntp.with_fallback(StaleFallback::Source(ClockSource::Rtc)),
+        &events,
+    );
+    assert_eq!(rtc.ns(), 5_000_700);
+    assert_eq!(rtc.domain(), TimeDomain::Unix);
+    assert_eq!(rtc.provenance(), &[events[3].event_id()]);
+
+    let monotonic = now(
+        ClockPolicyId::TrustNtpLatest,
+        expire_ntp.with_fallback(StaleFallback::Source(ClockSource::Monotonic)),
+        &events,
+    );
+    assert_eq!(monotonic.ns(), 2_000);
+    assert_eq!(monotonic.domain(), TimeDomain::Monotonic);
+
+    // A fallback source that is itself stale gives Unknown.
+    let both = expire_ntp
+        .expire(ClockSource::Rtc, 1_000)
+        .with_fallback(StaleFallback::Source(ClockSource::Rtc));
+    let later = [&events[..], &[mono(3_000)]].concat();
+    assert_eq!(
+        now(ClockPolicyId::TrustNtpLatest, both, &later).domain(),
+        TimeDomain::Unknown
+    );
+}
+
+#[test]
+fn t3_fusion_drops_only_stale_sources() {
+    let events = [
+        mono(0),
+        make_clock_event(ClockSource::Ntp, 5_000_000, 10),
+        mono(5_000),
+        make_clock_event(ClockSource::Rtc, 5_004_000, 10),
+        mono(6_000),
+    ];
+    let staleness = Staleness::none().expire(ClockSource::Ntp, 1_000);
+    let fused = now(ClockPolicyId::WeightedFusion, staleness, &events);
+    assert_eq!(fused.ns(), 5_004_000);
+    assert_eq!(fused.provenance(), &[events[3].event_id()]);
+
+    let unbounded = now(ClockPolicyId::WeightedFusion, Staleness::none(), &events);
+    assert_eq!(unbounded.provenance().len(), 2);
+}
+
+#[test]
+fn t4_hybrid_anchor_expires_with_its_ntp_sample() {
+    let events = [
+        mono(1_000),
+        make_clock_event(ClockSource::Ntp, 9_000_000, 10),
+        mono(1_500),
+    ];
+    let staleness = Staleness::none().expire(ClockSource::Ntp, 1_000);
+    assert_eq!(
+        now(ClockPolicyId::HybridMonotonicNtp, staleness, &events).ns(),
+        9_000_500
+    );
+
+    let later = [&events[..], &[mono(2_001)]].concat();
+    assert_eq!(
+        now(ClockPolicyId::HybridMonotonicNtp, staleness, &later).domain(),
+        TimeDomain::Unknown
+    );
+}
+
+#[test]
+fn t5_samples_before_any_monotonic_reading_age_from_the_first() {
+    let events = [
+        make_clock_event(ClockSource::Ntp, 5_000_000, 10),
+        mono(10_000),
+        mono(10_800),
+    ];
+    let staleness = Staleness::none().expire(ClockSource::Ntp, 1_000);
+    assert_eq!(
+        now(ClockPolicyId::TrustNtpLatest, staleness, &events).ns(),
+        5_000_000
+    );
+    let later = [&events[..], &[mono(11_001)]].concat();
+    assert_eq!(
+        now(ClockPolicyId::TrustNtpLatest, staleness, &later).domain(),
+        TimeDomain::Unknown
+    );
+}
+
+#[test]
+fn t6_checkpoints_honour_staleness() {
+    let events: Vec<_> = (0..40u64)
+        .map(|i| match i % 4 {
+            0 => make_clock_event(ClockSource::Ntp, 1_000_000 + i * 100, 5),
+            3 if i % 8 == 3 => make_clock_event(ClockSource::Rtc, 1_000_050 + i * 100, 50),
+            _ => mono(i * 400),
+        })
+        .collect();
+    let staleness = Staleness::none()
+        .expire(ClockSource::Ntp, 700)
+        .expire(ClockSource::Rtc, 2_000)
+        .with_fallback(StaleFallback::Source(ClockSource::Rtc));
+
+    for policy in ClockPolicyId::ALL {
+        let mut index = ClockCheckpoints::new(policy, 3).with_staleness(staleness);
+        for event in &events {
+            index.push(event);
+        }
+        for cut in 0..=events.len() {
+            assert_eq!(
+                index.now_at_cut(&events, cut).unwrap(),
+                now(policy, staleness, &events[..cut]),
+                "{} at cut {cut}",
+                policy.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn t7_monotonic_never_expires() {
+    let staleness = Staleness::none().expire(ClockSource::Monotonic, 0);
+    assert_eq!(staleness, Staleness::none());
+    assert!(staleness.is_none());
+    assert_eq!(staleness.max_age_ns(ClockSource::Monotonic), None);
+    let events = [mono(0), mono(1_000_000)];
+    assert_eq!(
+        now(ClockPolicyId::TrustMonotonicLatest, staleness, &events).ns(),
+        1_000_000
+    );
+}
@@ -8,7 +8,8 @@ use jitos_core::{
     Hash,
 };
 use jitos_views::{
-    ClockSample, ClockSource, TimerRequest, OBS_CLOCK_SAMPLE_V0, OBS_TIMER_REQUEST_V0,
+    ClockAnchor, ClockSample, ClockSource, TimerFire, TimerRequest, OBS_CLOCK_ANCHOR_V0,
+    OBS_CLOCK_SAMPLE_V0, OBS_TIMER_REQUEST_V0,
 };
 
 /// Helper: Create a clock sample observation event
@@ -51,3 +52,53 @@ pub fn make_timer_request(
     )
     .expect("create timer request event")
 }
+
+/// Helper: Create a clock anchor observation event
+#[allow(dead_code)]
+pub fn make_anchor_event(monotonic_ns: u64, unix_ns: u64, uncertainty_ns: u64) -> EventEnvelope {
+    let anchor = ClockAnchor {
+        monotonic_ns,
+        unix_ns,
+        uncertainty_ns,
+    };
+
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&anchor).expect("encode anchor"),
+        vec![],
+        Some(OBS_CLOCK_ANCHOR_V0.to_string()),
+        None,
+        None,
+    )
+    .expect("create anchor event")
+}
+
+/// Helper: Create a timer fire decision event
+#[allow(dead_code)]
+pub fn make_timer_fire(
+    request_id: [u8; 32],
+    fired_at_ns: u64,
+    request_event_id: Hash,
+) -> EventEnvelope {
+    let fire = TimerFire {
+        request_id: Hash(request_id),
+        fired_at_ns,
+    };
+
+    // Create a dummy policy context to use as parent
+    let policy = EventEnvelope::new_policy_context(
+        CanonicalBytes::from_value(&"timer_policy".to_string()).expect("encode policy"),
+        vec![],
+        None,
+        None,
+    )
+    .expect("create policy event");
+
+    EventEnvelope::new_decision(
+        CanonicalBytes::from_value(&fire).expect("encode fire"),
+        vec![request_event_id], // Use timer request as evidence
+        policy.event_id(),
+        None,
+        None,
+    )
+    .expect("create timer fire event")
+}
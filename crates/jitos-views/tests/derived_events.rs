@@ -0,0 +1,121 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Derived Event Tests
+//!
+//! Views propose fully-formed decisions; applying them must be accepted.
+
+mod common;
+
+use common::{make_clock_event, make_timer_request};
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope, EventKind};
+use jitos_views::{
+    derive_decision, ClockPolicyId, ClockSource, ClockView, DeriveError, FireSemantics, TimerFire,
+    TimerView,
+};
+
+fn policy() -> EventEnvelope {
+    EventEnvelope::new_policy_context(
+        CanonicalBytes::from_value(&"fire-due-timers").unwrap(),
+        vec![],
+        Some(AgentId::new("scheduler").unwrap()),
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn t1_proposed_fires_cite_request_time_and_policy() {
+    let sample = make_clock_event(ClockSource::Monotonic, 5_000, 0);
+    let early = make_timer_request([1u8; 32], 1_000, 1_000);
+    let late = make_timer_request([2u8; 32], 9_000, 1_000);
+    let due = make_timer_request([3u8; 32], 500, 3_000);
+    let policy = policy();
+
+    let mut clock = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    clock.apply_event(&sample).unwrap();
+    let mut timers = TimerView::new();
+    for event in [&early, &late, &due] {
+        timers.apply_event(event).unwrap();
+    }
+
+    let fires = timers
+        .propose_fires(clock.now(), FireSemantics::Point, &policy)
+        .unwrap();
+
+    assert_eq!(fires.len(), 2);
+    for (fire, request) in fires.iter().zip([&early, &due]) {
+        assert_eq!(fire.kind(), &EventKind::Decision);
+        let mut expected = vec![request.event_id(), sample.event_id(), policy.event_id()];
+        expected.sort();
+        let mut parents = fire.parents().to_vec();
+        parents.sort();
+        assert_eq!(parents, expected);
+
+        let payload: TimerFire = fire.payload().to_value().unwrap();
+        let requested: jitos_views::TimerRequest = request.payload().to_value().unwrap();
+        assert_eq!(payload.request_id, requested.request_id);
+        assert_eq!(payload.fired_at_ns, 5_000);
+    }
+
+    // Same state, same inputs: byte-identical events.
+    let again = timers
+        .propose_fires(clock.now(), FireSemantics::Point, &policy)
+        .unwrap();
+    assert_eq!(fires, again);
+}
+
+#[test]
+fn t2_strict_view_accepts_proposed_fires() {
+    let sample = make_clock_event(ClockSource::Monotonic, 2_000, 500);
+    let request = make_timer_request([7u8; 32], 400, 1_000);
+    let policy = policy();
+
+    let mut clock = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    clock.apply_event(&sample).unwrap();
+
+    for semantics in [
+        FireSemantics::Point,
+        FireSemantics::Definitely,
+        FireSemantics::Possibly,
+    ] {
+        let mut timers = TimerView::strict();
+        timers.apply_event(&request).unwrap();
+        let fires = timers
+            .propose_fires(clock.now(), semantics, &policy)
+            .unwrap();
+        assert_eq!(fires.len(), 1, "{semantics:?}");
+        for fire in &fires {
+            timers.apply_event(fire).unwrap();
+        }
+        assert!(timers
+            .propose_fires(clock.now(), semantics, &policy)
+            .unwrap()
+            .is_empty());
+    }
+}
+
+#[test]
+fn t3_derive_decision_requires_policy_and_evidence() {
+    let request = make_timer_request([9u8; 32], 10, 0);
+    let fire = TimerFire {
+        request_id: jitos_core::Hash([9u8; 32]),
+        fired_at_ns: 10,
+    };
+
+    assert!(matches!(
+        derive_decision(&fire, [request.event_id()], &request),
+        Err(DeriveError::NotAPolicy(id)) if id == request.event_id()
+    ));
+
+    let policy = policy();
+    assert!(matches!(
+        derive_decision(&fire, [policy.event_id()], &policy),
+        Err(DeriveError::NoEvidence)
+    ));
+
+    // Duplicate evidence is cited once.
+    let decision =
+        derive_decision(&fire, [request.event_id(), request.event_id()], &policy).unwrap();
+    assert_eq!(decision.parents().len(), 2);
+}
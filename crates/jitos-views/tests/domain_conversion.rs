@@ -0,0 +1,103 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Time Domain Conversion Tests
+//!
+//! Conversions between Monotonic and Unix go through an explicit anchor
+//! observation, and fail when there is none.
+
+mod common;
+
+use common::{make_anchor_event, make_clock_event};
+use jitos_views::{
+    AnchorView, ClockPolicyId, ClockSource, ClockView, ConversionError, Time, TimeDomain,
+};
+
+const BASE: u64 = 1_735_387_200_000_000_000;
+
+fn monotonic_now(value_ns: u64, uncertainty_ns: u64) -> Time {
+    let mut clock = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    clock
+        .apply_event(&make_clock_event(
+            ClockSource::Monotonic,
+            value_ns,
+            uncertainty_ns,
+        ))
+        .expect("apply clock event");
+    clock.now().clone()
+}
+
+#[test]
+fn x1_conversion_requires_an_anchor() {
+    let view = AnchorView::new();
+    let mono = monotonic_now(5_000, 10);
+    assert_eq!(
+        view.convert(&mono, TimeDomain::Unix),
+        Err(ConversionError::NoAnchor)
+    );
+    // Same-domain conversion needs no anchor.
+    assert_eq!(view.convert(&mono, TimeDomain::Monotonic), Ok(mono.clone()));
+    assert_eq!(
+        view.convert(&Time::unknown(), TimeDomain::Unix),
+        Err(ConversionError::UnknownDomain)
+    );
+    assert_eq!(
+        view.convert(&mono, TimeDomain::Unknown),
+        Err(ConversionError::UnknownDomain)
+    );
+    assert_eq!(
+        view.convert(&mono, TimeDomain::Logical),
+        Err(ConversionError::LogicalDomain)
+    );
+}
+
+#[test]
+fn x2_monotonic_and_unix_round_trip_through_the_anchor() {
+    let anchor = make_anchor_event(1_000_000_000, BASE, 50);
+    let mut view = AnchorView::new();
+    view.apply_event(&make_clock_event(ClockSource::Ntp, 1, 1))
+        .expect("foreign events are ignored");
+    view.apply_event(&anchor).expect("apply anchor");
+    assert_eq!(view.anchor().unwrap().event_id, anchor.event_id());
+
+    let mono = monotonic_now(3_000_000_000, 10);
+    let unix = view.convert(&mono, TimeDomain::Unix).unwrap();
+    assert_eq!(unix.ns(), BASE + 2_000_000_000);
+    assert_eq!(unix.uncertainty_ns(), 60);
+    assert_eq!(unix.domain(), TimeDomain::Unix);
+    assert_eq!(
+        unix.provenance(),
+        &[mono.provenance()[0], anchor.event_id()]
+    );
+
+    let back = view.convert(&unix, TimeDomain::Monotonic).unwrap();
+    assert_eq!(back.ns(), mono.ns());
+    assert_eq!(
+        back.uncertainty_ns(),
+        110,
+        "each hop adds anchor uncertainty"
+    );
+    assert_eq!(back.domain(), TimeDomain::Monotonic);
+}
+
+#[test]
+fn x3_latest_anchor_wins_and_range_is_checked() {
+    let mut view = AnchorView::new();
+    view.apply_event(&make_anchor_event(0, BASE, 0)).unwrap();
+    view.apply_event(&make_anchor_event(0, BASE + 7, 0))
+        .unwrap();
+    let unix = view
+        .convert(&monotonic_now(1, 0), TimeDomain::Unix)
+        .unwrap();
+    assert_eq!(unix.ns(), BASE + 8);
+
+    // A monotonic instant before the anchor's epoch does not exist.
+    let mut clock = ClockView::new(ClockPolicyId::TrustNtpLatest);
+    clock
+        .apply_event(&make_clock_event(ClockSource::Ntp, 3, 0))
+        .unwrap();
+    assert!(matches!(
+        view.convert(clock.now(), TimeDomain::Monotonic),
+        Err(ConversionError::OutOfRange { .. })
+    ));
+}
@@ -0,0 +1,113 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drift View Tests
+//!
+//! Drift estimates are integer fixed-point folds over clock samples: they must
+//! recover a known skew exactly and be identical on replay.
+
+mod common;
+
+use common::{make_clock_event, make_timer_request};
+use jitos_core::events::EventEnvelope;
+use jitos_views::{ClockSource, DriftEstimate, DriftView, TimeDomain};
+
+const BASE: u64 = 1_735_387_200_000_000_000;
+
+/// NTP running 50 ppm fast against monotonic, one sample per second.
+fn skewed() -> Vec<EventEnvelope> {
+    let mut events = Vec::new();
+    for s in 0..4u64 {
+        let mono = 1_000_000_000 + s * 1_000_000_000;
+        events.push(make_clock_event(ClockSource::Monotonic, mono, 10));
+        events.push(make_clock_event(
+            ClockSource::Ntp,
+            BASE + s * 1_000_050_000,
+            1_000,
+        ));
+    }
+    events
+}
+
+fn fold(events: &[EventEnvelope]) -> DriftView {
+    let mut view = DriftView::new();
+    for event in events {
+        view.apply_event(event).expect("apply event");
+    }
+    view
+}
+
+#[test]
+fn d1_recovers_a_known_skew() {
+    let view = fold(&skewed());
+    assert_eq!(
+        view.estimate(ClockSource::Ntp),
+        Some(DriftEstimate {
+            drift_ppb: 50_000,
+            // (1000 + 10 + 1000 + 10) ns over 3 s
+            uncertainty_ppb: 673,
+            span_ns: 3_000_000_000,
+            samples: 4,
+        })
+    );
+    assert_eq!(view.estimate(ClockSource::Rtc), None);
+    assert_eq!(view.estimate(ClockSource::Monotonic), None);
+}
+
+#[test]
+fn d2_predicts_with_widening_bounds() {
+    let events = skewed();
+    let view = fold(&events);
+
+    // Ten seconds after the last pair: 10 s + 500 µs of NTP time.
+    let at = 4_000_000_000 + 10_000_000_000;
+    let time = view.predict(ClockSource::Ntp, at).expect("prediction");
+    assert_eq!(time.ns(), BASE + 3 * 1_000_050_000 + 10_000_500_000);
+    assert_eq!(time.uncertainty_ns(), 1_000 + 10 + 6_730);
+    assert_eq!(time.domain(), TimeDomain::Unix);
+    assert_eq!(
+        time.provenance(),
+        &[
+            events[1].event_id(),
+            events[0].event_id(),
+            events[7].event_id(),
+            events[6].event_id(),
+        ]
+    );
+
+    let now = view.predict(ClockSource::Ntp, 4_000_000_000).unwrap();
+    assert_eq!(now.ns(), BASE + 3 * 1_000_050_000);
+    assert_eq!(now.uncertainty_ns(), 1_010);
+}
+
+#[test]
+fn d3_needs_monotonic_span_and_ignores_foreign_events() {
+    // Source samples before any monotonic sample cannot be paired.
+    let mut events = vec![make_clock_event(ClockSource::Rtc, 5, 0)];
+    // Two RTC samples against the same monotonic sample span no time.
+    events.push(make_clock_event(ClockSource::Monotonic, 100, 0));
+    events.push(make_clock_event(ClockSource::Rtc, 10, 0));
+    events.push(make_timer_request([1u8; 32], 5, 0));
+    events.push(make_clock_event(ClockSource::Rtc, 20, 0));
+    let view = fold(&events);
+    assert_eq!(view.estimate(ClockSource::Rtc), None);
+    assert!(view.predict(ClockSource::Rtc, 200).is_none());
+
+    // A slow clock drifts negatively.
+    events.push(make_clock_event(ClockSource::Monotonic, 1_100, 0));
+    events.push(make_clock_event(ClockSource::Rtc, 510, 0));
+    let estimate = fold(&events).estimate(ClockSource::Rtc).unwrap();
+    assert_eq!(estimate.drift_ppb, -500_000_000);
+    assert_eq!(estimate.samples, 3);
+}
+
+#[test]
+fn d4_replay_is_deterministic() {
+    let events = skewed();
+    let a = fold(&events);
+    let b = fold(&events);
+    for source in [ClockSource::Ntp, ClockSource::Rtc, ClockSource::PeerClaim] {
+        assert_eq!(a.estimate(source), b.estimate(source));
+        assert_eq!(a.predict(source, 9_999), b.predict(source, 9_999));
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! File System View Tests
+//!
+//! Writes and deletes fold into a path tree with implicit directories.
+
+mod common;
+
+use common::make_clock_event;
+use jitos_core::events::{CanonicalBytes, EventEnvelope};
+use jitos_views::{
+    ClockSource, DirEntry, FileSystemView, FsDelete, FsError, FsWrite, OBS_FS_DELETE_V0,
+    OBS_FS_WRITE_V0,
+};
+
+fn observation<T: serde::Serialize>(tag: &str, payload: &T) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(payload).expect("encode payload"),
+        vec![],
+        Some(tag.to_string()),
+        None,
+        None,
+    )
+    .expect("create observation event")
+}
+
+fn write(path: &str, content: &[u8]) -> EventEnvelope {
+    observation(
+        OBS_FS_WRITE_V0,
+        &FsWrite {
+            path: path.to_string(),
+            content: content.to_vec(),
+        },
+    )
+}
+
+fn delete(path: &str) -> EventEnvelope {
+    observation(
+        OBS_FS_DELETE_V0,
+        &FsDelete {
+            path: path.to_string(),
+        },
+    )
+}
+
+fn fold(events: &[EventEnvelope]) -> FileSystemView {
+    let mut view = FileSystemView::new();
+    for event in events {
+        view.apply_event(event).expect("apply event");
+    }
+    view
+}
+
+#[test]
+fn t1_writes_and_deletes_fold_with_provenance() {
+    let first = write("/etc/motd", b"hello");
+    let second = write("/etc/motd", b"goodbye!");
+    let view = fold(&[
+        first.clone(),
+        write("/tmp/scratch", b"x"),
+        second.clone(),
+        delete("/tmp/scratch"),
+        delete("/never/existed"),
+    ]);
+
+    let motd = view.get("/etc/motd").unwrap();
+    assert_eq!(motd.len, 8);
+    assert_eq!(motd.event_id, second.event_id());
+    assert_ne!(
+        motd.content_hash,
+        fold(&[first]).get("/etc/motd").unwrap().content_hash
+    );
+    assert!(view.get("/tmp/scratch").is_none());
+    assert_eq!(view.len(), 1);
+}
+
+#[test]
+fn t2_list_shows_immediate_children_in_name_order() {
+    let view = fold(&[
+        write("/a/x", b"1"),
+        write("/a-b", b"2"),
+        write("/a/sub/y", b"3"),
+        write("/a/sub/z", b"4"),
+        write("/b", b"5"),
+    ]);
+
+    let root: Vec<_> = view.list("/").iter().map(DirEntry::name).collect();
+    assert_eq!(root, vec!["a", "a-b", "b"]);
+    assert!(matches!(
+        view.list("/")[0],
+        DirEntry::Directory { name: "a" }
+    ));
+
+    let a = view.list("/a");
+    assert_eq!(a.len(), 2);
+    assert!(matches!(a[0], DirEntry::Directory { name: "sub" }));
+    match a[1] {
+        DirEntry::File { name, entry } => {
+            assert_eq!(name, "x");
+            assert_eq!(entry.len, 1);
+        }
+        other => panic!("expected file, got {other:?}"),
+    }
+
+    assert!(view.list("/b").is_empty());
+    assert!(view.list("/missing").is_empty());
+}
+
+#[test]
+fn t3_state_hash_ignores_provenance_and_order() {
+    let a = fold(&[write("/one", b"1"), write("/two", b"2")]);
+    let b = fold(&[
+        write("/two", b"stale"),
+        write("/one", b"1"),
+        write("/two", b"2"),
+    ]);
+    let c = fold(&[write("/one", b"1"), write("/two", b"3")]);
+
+    assert_eq!(a.state_hash(), b.state_hash());
+    assert_ne!(a.state_hash(), c.state_hash());
+    assert_ne!(a.state_hash(), FileSystemView::new().state_hash());
+}
+
+#[test]
+fn t4_invalid_and_conflicting_paths_are_rejected() {
+    let mut view = fold(&[write("/dir/file", b"1")]);
+
+    for path in ["relative", "/", "/a//b", "/a/./b", "/a/../b", "/trailing/"] {
+        let event = write(path, b"x");
+        assert_eq!(
+            view.apply_event(&event),
+            Err(FsError::InvalidPath {
+                path: path.to_string(),
+                event_id: event.event_id(),
+            })
+        );
+    }
+
+    for path in ["/dir", "/dir/file/child"] {
+        let event = write(path, b"x");
+        assert_eq!(
+            view.apply_event(&event),
+            Err(FsError::PathConflict {
+                path: path.to_string(),
+                event_id: event.event_id(),
+            })
+        );
+    }
+    assert_eq!(view.len(), 1);
+
+    // Once the file is gone, its directory disappears and the path is free.
+    view.apply_event(&delete("/dir/file")).unwrap();
+    view.apply_event(&write("/dir", b"now a file")).unwrap();
+    assert!(view.get("/dir").is_some());
+}
+
+#[test]
+fn t5_malformed_and_unrelated_events() {
+    let bad = observation(OBS_FS_WRITE_V0, &7u64);
+    let mut view = FileSystemView::new();
+    assert_eq!(
+        view.apply_event(&bad),
+        Err(FsError::MalformedWrite(bad.event_id()))
+    );
+    view.apply_event(&make_clock_event(ClockSource::Monotonic, 1, 0))
+        .unwrap();
+    assert!(view.is_empty());
+}
@@ -0,0 +1,93 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Key-Value View Tests
+//!
+//! Puts and deletes fold into a last-writer-wins map in worldline order.
+
+mod common;
+
+use common::make_clock_event;
+use jitos_core::events::{CanonicalBytes, EventEnvelope};
+use jitos_views::{ClockSource, KvDelete, KvError, KvPut, KvView, OBS_KV_DELETE_V0, OBS_KV_PUT_V0};
+
+fn observation<T: serde::Serialize>(tag: &str, payload: &T) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(payload).expect("encode payload"),
+        vec![],
+        Some(tag.to_string()),
+        None,
+        None,
+    )
+    .expect("create observation event")
+}
+
+fn put(key: &str, value: &[u8]) -> EventEnvelope {
+    observation(
+        OBS_KV_PUT_V0,
+        &KvPut {
+            key: key.to_string(),
+            value: value.to_vec(),
+        },
+    )
+}
+
+fn delete(key: &str) -> EventEnvelope {
+    observation(
+        OBS_KV_DELETE_V0,
+        &KvDelete {
+            key: key.to_string(),
+        },
+    )
+}
+
+#[test]
+fn kv1_last_writer_wins_in_worldline_order() {
+    let events = vec![
+        put("b", b"1"),
+        put("a", b"1"),
+        make_clock_event(ClockSource::Monotonic, 1, 1),
+        put("b", b"2"),
+        delete("a"),
+        put("c", b"3"),
+        delete("missing"),
+    ];
+    let view = KvView::at_cut(&events, events.len()).unwrap();
+
+    assert_eq!(view.len(), 2);
+    assert!(view.get("a").is_none(), "deleted");
+    let b = view.get("b").unwrap();
+    assert_eq!(b.value, b"2");
+    assert_eq!(
+        b.event_id,
+        events[3].event_id(),
+        "provenance is the last put"
+    );
+    let keys: Vec<_> = view.iter().map(|(k, _)| k).collect();
+    assert_eq!(keys, vec!["b", "c"]);
+
+    // Historical state at a cut
+    let early = KvView::at_cut(&events, 2).unwrap();
+    assert_eq!(early.get("a").unwrap().value, b"1");
+    assert_eq!(early.get("b").unwrap().value, b"1");
+    assert_eq!(
+        KvView::at_cut(&events, 8).unwrap_err(),
+        KvError::CutOutOfBounds { cut: 8, len: 7 }
+    );
+}
+
+#[test]
+fn kv2_malformed_payloads_are_errors() {
+    let bad_put = observation(OBS_KV_PUT_V0, &42u64);
+    let bad_delete = observation(OBS_KV_DELETE_V0, &"not a struct");
+    let mut view = KvView::new();
+    assert_eq!(
+        view.apply_event(&bad_put),
+        Err(KvError::MalformedPut(bad_put.event_id()))
+    );
+    assert_eq!(
+        view.apply_event(&bad_delete),
+        Err(KvError::MalformedDelete(bad_delete.event_id()))
+    );
+    assert!(view.is_empty());
+}
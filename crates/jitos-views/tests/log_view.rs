@@ -0,0 +1,99 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Log View Tests
+//!
+//! Tagged observations group into per-topic logs in worldline order, readable
+//! by cursor or as of a cut.
+
+mod common;
+
+use common::{make_clock_event, make_timer_request};
+use jitos_core::events::{CanonicalBytes, EventEnvelope};
+use jitos_views::{LogView, OBS_CLOCK_SAMPLE_V0, OBS_TIMER_REQUEST_V0};
+
+fn message(topic: &str, body: &str) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&body).expect("encode body"),
+        vec![],
+        Some(topic.to_string()),
+        None,
+        None,
+    )
+    .expect("create observation event")
+}
+
+fn untagged() -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&"raw").expect("encode body"),
+        vec![],
+        None,
+        None,
+        None,
+    )
+    .expect("create observation event")
+}
+
+fn worldline() -> Vec<EventEnvelope> {
+    vec![
+        message("orders", "o1"),
+        make_clock_event(jitos_views::ClockSource::Monotonic, 1, 1),
+        message("audit", "a1"),
+        untagged(),
+        message("orders", "o2"),
+        make_timer_request([1u8; 32], 5, 0),
+        message("orders", "o3"),
+    ]
+}
+
+fn fold(events: &[EventEnvelope]) -> LogView {
+    let mut view = LogView::new();
+    for event in events {
+        view.apply_event(event);
+    }
+    view
+}
+
+#[test]
+fn l1_observations_group_by_topic_in_worldline_order() {
+    let events = worldline();
+    let view = fold(&events);
+
+    let topics: Vec<_> = view.topics().collect();
+    assert_eq!(
+        topics,
+        vec![OBS_CLOCK_SAMPLE_V0, OBS_TIMER_REQUEST_V0, "audit", "orders"]
+    );
+    let orders = view.log("orders");
+    let positions: Vec<_> = orders.iter().map(|e| e.position).collect();
+    assert_eq!(positions, vec![0, 4, 6]);
+    assert_eq!(orders[1].event_id, events[4].event_id());
+    assert_eq!(orders[1].payload, *events[4].payload());
+    assert!(view.log("missing").is_empty());
+}
+
+#[test]
+fn l2_cursor_reads_page_through_a_topic() {
+    let view = fold(&worldline());
+    let page = view.read("orders", 0, 2);
+    assert_eq!(page.len(), 2);
+    let rest = view.read("orders", page.len(), 2);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest[0].position, 6);
+    assert!(view.read("orders", 3, 2).is_empty(), "caught up");
+    assert!(view.read("orders", 99, usize::MAX).is_empty());
+}
+
+#[test]
+fn l3_cut_reads_match_folding_the_prefix() {
+    let events = worldline();
+    let view = fold(&events);
+    for cut in 0..=events.len() {
+        let prefix = fold(&events[..cut]);
+        assert_eq!(
+            view.at_cut("orders", cut),
+            prefix.log("orders"),
+            "cut {cut}"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Network View Tests
+//!
+//! Sends move through decided → sent → acknowledged; inbound messages are
+//! grouped per peer. Everything is a fold over the worldline.
+
+mod common;
+
+use common::make_clock_event;
+use jitos_core::{
+    events::{AgentId, CanonicalBytes, EventEnvelope, Signature},
+    Hash,
+};
+use jitos_views::{
+    ClockSource, MessageAck, MessageReceive, MessageSend, NetworkError, NetworkView,
+    OutboundStatus, OBS_NET_ACK_V0, OBS_NET_MESSAGE_V0,
+};
+
+fn agent(name: &str) -> AgentId {
+    AgentId::new(name).expect("valid agent id")
+}
+
+fn observation<T: serde::Serialize>(tag: &str, payload: &T) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(payload).expect("encode payload"),
+        vec![],
+        Some(tag.to_string()),
+        None,
+        None,
+    )
+    .expect("create observation event")
+}
+
+fn send(id: u8, to: &str) -> MessageSend {
+    MessageSend {
+        message_id: Hash([id; 32]),
+        to: agent(to),
+        payload_bytes: vec![id],
+    }
+}
+
+/// Decision + Commit for one outbound message
+fn decide_and_commit(message: &MessageSend) -> (EventEnvelope, EventEnvelope) {
+    let evidence = make_clock_event(ClockSource::Monotonic, 1, 0);
+    let policy = EventEnvelope::new_policy_context(
+        CanonicalBytes::from_value(&"net_policy".to_string()).expect("encode policy"),
+        vec![],
+        None,
+        None,
+    )
+    .expect("create policy event");
+    let payload = CanonicalBytes::from_value(message).expect("encode send");
+    let decision = EventEnvelope::new_decision(
+        payload.clone(),
+        vec![evidence.event_id()],
+        policy.event_id(),
+        None,
+        None,
+    )
+    .expect("create decision event");
+    let commit = EventEnvelope::new_commit(
+        payload,
+        decision.event_id(),
+        vec![],
+        None,
+        Signature::new(vec![1]).expect("signature"),
+    )
+    .expect("create commit event");
+    (decision, commit)
+}
+
+fn receive(id: u8, from: &str) -> EventEnvelope {
+    observation(
+        OBS_NET_MESSAGE_V0,
+        &MessageReceive {
+            message_id: Hash([id; 32]),
+            from: agent(from),
+            payload_bytes: vec![id],
+        },
+    )
+}
+
+fn ack(id: u8, from: &str) -> EventEnvelope {
+    observation(
+        OBS_NET_ACK_V0,
+        &MessageAck {
+            message_id: Hash([id; 32]),
+            from: agent(from),
+        },
+    )
+}
+
+#[test]
+fn t1_send_lifecycle_decided_sent_acknowledged() {
+    let message = send(1, "bob");
+    let (decision, commit) = decide_and_commit(&message);
+    let mut view = NetworkView::new();
+
+    view.apply_event(&decision).unwrap();
+    let outbound = view.outbound(&message.message_id).unwrap();
+    assert_eq!(outbound.status(), OutboundStatus::Decided);
+    assert_eq!(outbound.decision, Some(decision.event_id()));
+    assert_eq!(view.unacknowledged(&agent("bob")).len(), 0);
+
+    view.apply_event(&commit).unwrap();
+    let outbound = view.outbound(&message.message_id).unwrap();
+    assert_eq!(outbound.status(), OutboundStatus::Sent);
+    assert_eq!(outbound.commit, Some(commit.event_id()));
+    assert_eq!(view.unacknowledged(&agent("bob")).len(), 1);
+
+    let acked = ack(1, "bob");
+    view.apply_event(&acked).unwrap();
+    let outbound = view.outbound(&message.message_id).unwrap();
+    assert_eq!(outbound.status(), OutboundStatus::Acknowledged);
+    assert_eq!(outbound.ack, Some(acked.event_id()));
+    assert_eq!(view.unacknowledged(&agent("bob")).len(), 0);
+}
+
+#[test]
+fn t2_acks_only_count_from_the_recipient_after_commit() {
+    let message = send(2, "bob");
+    let (decision, commit) = decide_and_commit(&message);
+    let mut view = NetworkView::new();
+
+    view.apply_event(&decision).unwrap();
+    // Ack before the commit: the message has not left yet.
+    view.apply_event(&ack(2, "bob")).unwrap();
+    view.apply_event(&commit).unwrap();
+    // Ack from the wrong peer.
+    view.apply_event(&ack(2, "mallory")).unwrap();
+    // Ack for a message nobody sent.
+    view.apply_event(&ack(9, "bob")).unwrap();
+
+    let unacked = view.unacknowledged(&agent("bob"));
+    assert_eq!(unacked.len(), 1);
+    assert_eq!(unacked[0].message, message);
+}
+
+#[test]
+fn t3_inbox_and_outbox_are_per_peer() {
+    let mut view = NetworkView::new();
+    for message in [send(1, "bob"), send(2, "carol"), send(3, "bob")] {
+        let (decision, commit) = decide_and_commit(&message);
+        view.apply_event(&decision).unwrap();
+        view.apply_event(&commit).unwrap();
+    }
+    let r1 = receive(10, "carol");
+    let r2 = receive(11, "carol");
+    view.apply_event(&r1).unwrap();
+    view.apply_event(&receive(12, "dave")).unwrap();
+    view.apply_event(&r2).unwrap();
+    view.apply_event(&ack(3, "bob")).unwrap();
+
+    let bob: Vec<_> = view
+        .outbox(&agent("bob"))
+        .into_iter()
+        .map(|m| m.message.message_id)
+        .collect();
+    assert_eq!(bob, vec![Hash([1; 32]), Hash([3; 32])]);
+    let bob_unacked: Vec<_> = view
+        .unacknowledged(&agent("bob"))
+        .into_iter()
+        .map(|m| m.message.message_id)
+        .collect();
+    assert_eq!(bob_unacked, vec![Hash([1; 32])]);
+
+    let carol_inbox: Vec<_> = view
+        .inbox(&agent("carol"))
+        .iter()
+        .map(|m| m.event_id)
+        .collect();
+    assert_eq!(carol_inbox, vec![r1.event_id(), r2.event_id()]);
+    assert!(view.inbox(&agent("bob")).is_empty());
+
+    let peers: Vec<_> = view.peers().into_iter().collect();
+    assert_eq!(peers, vec!["bob", "carol", "dave"]);
+}
+
+#[test]
+fn t4_replay_is_deterministic() {
+    let mut events = Vec::new();
+    for message in [send(1, "bob"), send(2, "carol")] {
+        let (decision, commit) = decide_and_commit(&message);
+        events.push(decision);
+        events.push(commit);
+    }
+    events.push(receive(5, "bob"));
+    events.push(ack(2, "carol"));
+
+    let fold = |events: &[EventEnvelope]| {
+        let mut view = NetworkView::new();
+        for event in events {
+            view.apply_event(event).unwrap();
+        }
+        view
+    };
+    let (a, b) = (fold(&events), fold(&events));
+    for peer in ["bob", "carol"] {
+        let peer = agent(peer);
+        assert_eq!(a.outbox(&peer), b.outbox(&peer));
+        assert_eq!(a.inbox(&peer), b.inbox(&peer));
+    }
+}
+
+#[test]
+fn t5_malformed_observations_are_rejected() {
+    let bad_message = observation(OBS_NET_MESSAGE_V0, &"not a message".to_string());
+    let bad_ack = observation(OBS_NET_ACK_V0, &42u64);
+    let mut view = NetworkView::new();
+
+    assert_eq!(
+        view.apply_event(&bad_message),
+        Err(NetworkError::MalformedMessage(bad_message.event_id()))
+    );
+    assert_eq!(
+        view.apply_event(&bad_ack),
+        Err(NetworkError::MalformedAck(bad_ack.event_id()))
+    );
+    // Unrelated events are ignored.
+    view.apply_event(&make_clock_event(ClockSource::Ntp, 5, 0))
+        .unwrap();
+    assert!(view.peers().is_empty());
+}
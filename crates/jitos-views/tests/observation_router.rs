@@ -0,0 +1,141 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Observation Router Tests
+//!
+//! One routed pass must leave every view exactly where a dedicated pass
+//! would have.
+
+mod common;
+
+use common::{make_clock_event, make_timer_fire, make_timer_request};
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventKind};
+use jitos_views::{
+    ClockPolicyId, ClockSource, ClockView, KvPut, KvView, LogView, ObservationRouter, Route,
+    RouterError, TimerView, View, OBS_CLOCK_SAMPLE_V0, OBS_KV_PUT_V0,
+};
+
+fn put(key: &str, value: &[u8]) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&KvPut {
+            key: key.to_string(),
+            value: value.to_vec(),
+        })
+        .expect("encode put"),
+        vec![],
+        Some(OBS_KV_PUT_V0.to_string()),
+        None,
+        None,
+    )
+    .expect("create put event")
+}
+
+fn worldline() -> Vec<EventEnvelope> {
+    let request = make_timer_request([7u8; 32], 1_000, 1_000);
+    let request_id = request.event_id();
+    vec![
+        make_clock_event(ClockSource::Monotonic, 1_000, 10),
+        request,
+        put("a", b"1"),
+        make_clock_event(ClockSource::Ntp, 1_500, 100),
+        put("b", b"2"),
+        make_clock_event(ClockSource::Monotonic, 2_100, 10),
+        make_timer_fire([7u8; 32], 2_100, request_id),
+        put("a", b"3"),
+    ]
+}
+
+fn dedicated<V: View>(mut view: V, events: &[EventEnvelope]) -> V {
+    for event in events {
+        view.apply(event).expect("apply event");
+    }
+    view
+}
+
+#[test]
+fn t1_single_pass_matches_dedicated_passes() {
+    let events = worldline();
+    let mut clock = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    let mut timers = TimerView::new();
+    let mut kv = KvView::new();
+    let mut log = LogView::new();
+
+    let mut router = ObservationRouter::new();
+    router.register("clock", Route::observation(OBS_CLOCK_SAMPLE_V0), &mut clock);
+    router.register("timers", Route::All, &mut timers);
+    router.register("kv", Route::observation(OBS_KV_PUT_V0), &mut kv);
+    router.register("log", Route::All, &mut log);
+    assert_eq!(router.views(), vec!["clock", "timers", "kv", "log"]);
+    router.replay(&events).unwrap();
+    assert_eq!(router.events(), events.len());
+    drop(router);
+
+    let expected_clock = dedicated(ClockView::new(ClockPolicyId::TrustMonotonicLatest), &events);
+    assert_eq!(clock.now(), expected_clock.now());
+
+    let expected_timers = dedicated(TimerView::new(), &events);
+    assert!(timers.requests().eq(expected_timers.requests()));
+    assert!(timers.fired().eq(expected_timers.fired()));
+    assert_eq!(timers.fired().count(), 1);
+
+    let expected_kv = dedicated(KvView::new(), &events);
+    assert!(kv.iter().eq(expected_kv.iter()));
+    assert_eq!(kv.get("a").unwrap().value, b"3");
+
+    let expected_log = dedicated(LogView::new(), &events);
+    for topic in expected_log.topics() {
+        assert_eq!(log.log(topic), expected_log.log(topic));
+    }
+}
+
+#[test]
+fn t2_routes_select_events() {
+    let events = worldline();
+    let decision = &events[6];
+    let clock = &events[0];
+
+    assert!(Route::All.matches(decision));
+    assert!(Route::Kind(EventKind::Decision).matches(decision));
+    assert!(!Route::Kind(EventKind::Decision).matches(clock));
+    assert!(Route::observation(OBS_CLOCK_SAMPLE_V0).matches(clock));
+    assert!(!Route::observation(OBS_KV_PUT_V0).matches(clock));
+
+    let either = Route::AnyOf(vec![
+        Route::Kind(EventKind::Decision),
+        Route::observation(OBS_KV_PUT_V0),
+    ]);
+    let routed: Vec<usize> = (0..events.len())
+        .filter(|&i| either.matches(&events[i]))
+        .collect();
+    assert_eq!(routed, vec![2, 4, 6, 7]);
+}
+
+#[test]
+fn t3_view_errors_name_the_view_and_event() {
+    let orphan_fire = make_timer_fire(
+        [9u8; 32],
+        5,
+        make_clock_event(ClockSource::Ntp, 1, 0).event_id(),
+    );
+    let events = vec![put("a", b"1"), orphan_fire.clone()];
+    let mut kv = KvView::new();
+    let mut timers = TimerView::strict();
+
+    let mut router = ObservationRouter::new();
+    router.register("kv", Route::observation(OBS_KV_PUT_V0), &mut kv);
+    router.register("timers", Route::All, &mut timers);
+    let err = router.replay(&events).unwrap_err();
+    let RouterError::View {
+        view,
+        position,
+        event_id,
+        ..
+    } = &err;
+    assert_eq!(*view, "timers");
+    assert_eq!(*position, 1);
+    assert_eq!(*event_id, orphan_fire.event_id());
+    assert!(err.to_string().starts_with("view timers rejected event"));
+    drop(router);
+
+    assert_eq!(kv.len(), 1);
+}
@@ -0,0 +1,110 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Policy View Tests
+//!
+//! Policy contexts are indexed and linked to the decisions that cite them.
+
+mod common;
+
+use common::make_clock_event;
+use jitos_core::events::{CanonicalBytes, EventEnvelope};
+use jitos_views::{ClockSource, PolicyView};
+
+fn policy(name: &str) -> EventEnvelope {
+    EventEnvelope::new_policy_context(
+        CanonicalBytes::from_value(&name.to_string()).expect("encode policy"),
+        vec![],
+        None,
+        None,
+    )
+    .expect("create policy event")
+}
+
+fn decision(evidence: &EventEnvelope, policy: &EventEnvelope, tag: u64) -> EventEnvelope {
+    EventEnvelope::new_decision(
+        CanonicalBytes::from_value(&tag).expect("encode decision"),
+        vec![evidence.event_id()],
+        policy.event_id(),
+        None,
+        None,
+    )
+    .expect("create decision event")
+}
+
+fn fold(events: &[EventEnvelope]) -> PolicyView {
+    let mut view = PolicyView::new();
+    for event in events {
+        view.apply_event(event);
+    }
+    view
+}
+
+#[test]
+fn t1_decisions_link_to_their_policy() {
+    let evidence = make_clock_event(ClockSource::Monotonic, 1, 0);
+    let (p1, p2) = (policy("clock_v1"), policy("clock_v2"));
+    let (d1, d2, d3) = (
+        decision(&evidence, &p1, 1),
+        decision(&evidence, &p1, 2),
+        decision(&evidence, &p2, 3),
+    );
+    let view = fold(&[
+        evidence.clone(),
+        p1.clone(),
+        d1.clone(),
+        d2.clone(),
+        p2.clone(),
+        d3.clone(),
+    ]);
+
+    let ids: Vec<_> = view.policies().iter().map(|p| p.event_id).collect();
+    assert_eq!(ids, vec![p1.event_id(), p2.event_id()]);
+    assert_eq!(
+        view.policy(&p1.event_id()).unwrap().decisions,
+        vec![d1.event_id(), d2.event_id()]
+    );
+    assert_eq!(view.policy(&p2.event_id()).unwrap().position, 4);
+    assert_eq!(view.decisions()[2].policies, vec![p2.event_id()]);
+    assert_eq!(view.decisions()[2].position, 5);
+    assert_eq!(view.ungoverned().count(), 0);
+}
+
+#[test]
+fn t2_governing_between_cuts() {
+    let evidence = make_clock_event(ClockSource::Monotonic, 1, 0);
+    let (p1, p2) = (policy("a"), policy("b"));
+    // positions: 0 evidence, 1 p1, 2 p2, 3 d(p2), 4 d(p1), 5 d(p1)
+    let view = fold(&[
+        evidence.clone(),
+        p1.clone(),
+        p2.clone(),
+        decision(&evidence, &p2, 1),
+        decision(&evidence, &p1, 2),
+        decision(&evidence, &p1, 3),
+    ]);
+
+    let ids = |from, to| -> Vec<_> {
+        view.governing_between(from, to)
+            .iter()
+            .map(|p| p.event_id)
+            .collect()
+    };
+    assert_eq!(ids(0, 6), vec![p1.event_id(), p2.event_id()]);
+    assert_eq!(ids(0, 4), vec![p2.event_id()]);
+    assert_eq!(ids(4, 100), vec![p1.event_id()]);
+    assert!(ids(0, 3).is_empty());
+    assert!(ids(5, 2).is_empty());
+}
+
+#[test]
+fn t3_decisions_without_a_seen_policy_are_ungoverned() {
+    let evidence = make_clock_event(ClockSource::Monotonic, 1, 0);
+    let unseen = policy("applied elsewhere");
+    let orphan = decision(&evidence, &unseen, 1);
+    let view = fold(&[evidence, orphan.clone()]);
+
+    let ungoverned: Vec<_> = view.ungoverned().map(|d| d.event_id).collect();
+    assert_eq!(ungoverned, vec![orphan.event_id()]);
+    assert!(view.policies().is_empty());
+}
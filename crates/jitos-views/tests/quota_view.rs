@@ -0,0 +1,145 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Quota View Tests
+//!
+//! Usage observations accumulate per agent and resource; policy contexts set
+//! the limits they are measured against.
+
+mod common;
+
+use common::make_clock_event;
+use jitos_core::events::{AgentId, CanonicalBytes, EventEnvelope};
+use jitos_views::{
+    ClockSource, QuotaError, QuotaLimit, QuotaPolicy, QuotaUsage, QuotaView, OBS_QUOTA_USAGE_V0,
+};
+
+fn agent(name: &str) -> AgentId {
+    AgentId::new(name).expect("valid agent id")
+}
+
+fn usage(who: &str, resource: &str, amount: u64) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&QuotaUsage {
+            agent: agent(who),
+            resource: resource.to_string(),
+            amount,
+        })
+        .expect("encode usage"),
+        vec![],
+        Some(OBS_QUOTA_USAGE_V0.to_string()),
+        None,
+        None,
+    )
+    .expect("create usage event")
+}
+
+fn policy(limits: &[(Option<&str>, &str, u64)]) -> EventEnvelope {
+    let policy = QuotaPolicy {
+        limits: limits
+            .iter()
+            .map(|&(who, resource, limit)| QuotaLimit {
+                agent: who.map(agent),
+                resource: resource.to_string(),
+                limit,
+            })
+            .collect(),
+    };
+    EventEnvelope::new_policy_context(
+        CanonicalBytes::from_value(&policy).expect("encode policy"),
+        vec![],
+        None,
+        None,
+    )
+    .expect("create policy event")
+}
+
+fn fold(events: &[EventEnvelope]) -> QuotaView {
+    let mut view = QuotaView::new();
+    for event in events {
+        view.apply_event(event).expect("apply event");
+    }
+    view
+}
+
+#[test]
+fn t1_usage_accumulates_per_agent_and_resource() {
+    let view = fold(&[
+        usage("alice", "cpu", 10),
+        usage("bob", "cpu", 3),
+        usage("alice", "cpu", 5),
+        usage("alice", "mem", 7),
+        usage("alice", "cpu", u64::MAX),
+    ]);
+
+    assert_eq!(view.usage(&agent("alice"), "cpu"), u64::MAX);
+    assert_eq!(view.usage(&agent("alice"), "mem"), 7);
+    assert_eq!(view.usage(&agent("bob"), "cpu"), 3);
+    assert_eq!(view.usage(&agent("bob"), "mem"), 0);
+    let agents: Vec<_> = view.agents().into_iter().collect();
+    assert_eq!(agents, vec!["alice", "bob"]);
+}
+
+#[test]
+fn t2_remaining_budget_uses_specific_then_default_limit() {
+    let limits = policy(&[(None, "cpu", 100), (Some("alice"), "cpu", 20)]);
+    let view = fold(&[
+        limits.clone(),
+        usage("alice", "cpu", 15),
+        usage("bob", "cpu", 40),
+    ]);
+
+    assert_eq!(view.remaining(&agent("alice"), "cpu"), Some(5));
+    assert_eq!(view.remaining(&agent("bob"), "cpu"), Some(60));
+    assert_eq!(view.remaining(&agent("carol"), "cpu"), Some(100));
+    assert_eq!(view.remaining(&agent("alice"), "mem"), None);
+    assert_eq!(
+        view.limit(&agent("alice"), "cpu").unwrap().event_id,
+        limits.event_id()
+    );
+}
+
+#[test]
+fn t3_later_policies_override_listed_limits_only() {
+    let view = fold(&[
+        policy(&[(None, "cpu", 100), (None, "mem", 50)]),
+        usage("alice", "cpu", 30),
+        usage("alice", "mem", 60),
+        policy(&[(None, "cpu", 10)]),
+    ]);
+
+    assert_eq!(view.remaining(&agent("alice"), "cpu"), Some(0));
+    assert!(view.is_over_budget(&agent("alice"), "cpu"));
+    assert_eq!(view.remaining(&agent("alice"), "mem"), Some(0));
+    assert!(view.is_over_budget(&agent("alice"), "mem"));
+    assert!(!view.is_over_budget(&agent("alice"), "disk"));
+}
+
+#[test]
+fn t4_unrelated_and_malformed_events() {
+    let unrelated_policy = EventEnvelope::new_policy_context(
+        CanonicalBytes::from_value(&"clock_policy".to_string()).expect("encode policy"),
+        vec![],
+        None,
+        None,
+    )
+    .expect("create policy event");
+    let mut view = fold(&[
+        unrelated_policy,
+        make_clock_event(ClockSource::Monotonic, 1, 0),
+    ]);
+    assert!(view.agents().is_empty());
+
+    let bad = EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&1u64).expect("encode"),
+        vec![],
+        Some(OBS_QUOTA_USAGE_V0.to_string()),
+        None,
+        None,
+    )
+    .expect("create event");
+    assert_eq!(
+        view.apply_event(&bad),
+        Err(QuotaError::MalformedUsage(bad.event_id()))
+    );
+}
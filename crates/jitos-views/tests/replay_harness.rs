@@ -0,0 +1,120 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replay Harness Tests
+//!
+//! The `testing` harness replays views and compares state hashes.
+
+mod common;
+
+use common::{make_clock_event, make_timer_fire, make_timer_request};
+use jitos_core::events::{CanonicalBytes, EventEnvelope};
+use jitos_views::testing::{assert_order_insensitive, assert_replays, permutations, replay};
+use jitos_views::{
+    ClockPolicyId, ClockSource, ClockView, FileSystemView, FsWrite, TimerView, OBS_FS_WRITE_V0,
+};
+
+fn write(path: &str, content: &[u8]) -> EventEnvelope {
+    EventEnvelope::new_observation(
+        CanonicalBytes::from_value(&FsWrite {
+            path: path.to_string(),
+            content: content.to_vec(),
+        })
+        .expect("encode write"),
+        vec![],
+        Some(OBS_FS_WRITE_V0.to_string()),
+        None,
+        None,
+    )
+    .expect("create write event")
+}
+
+fn timer_worldline() -> Vec<EventEnvelope> {
+    let first = make_timer_request([1u8; 32], 100, 0);
+    let fire = make_timer_fire([1u8; 32], 100, first.event_id());
+    vec![
+        first,
+        make_timer_request([2u8; 32], 200, 0),
+        make_timer_request([3u8; 32], 300, 0),
+        fire,
+        make_timer_request([4u8; 32], 50, 100),
+        make_timer_request([5u8; 32], 50, 100),
+    ]
+}
+
+#[test]
+fn t1_replays_agree_for_every_hashed_view() {
+    let clock_events: Vec<_> = (0..10)
+        .map(|i| make_clock_event(ClockSource::Ntp, 1_000 + i * 10, 5))
+        .collect();
+    let clock = assert_replays(
+        || ClockView::new(ClockPolicyId::TrustNtpLatest),
+        &clock_events,
+        100,
+    );
+    assert_eq!(
+        clock,
+        replay(
+            || ClockView::new(ClockPolicyId::TrustNtpLatest),
+            &clock_events
+        )
+        .state_hash()
+    );
+
+    assert_replays(TimerView::new, &timer_worldline(), 100);
+    assert_replays(
+        FileSystemView::new,
+        &[write("/a", b"1"), write("/b/c", b"2")],
+        100,
+    );
+}
+
+#[test]
+fn t2_order_insensitive_groups() {
+    // Requests between fires commute; the fire must stay after its request.
+    let events = timer_worldline();
+    let hash = assert_order_insensitive(TimerView::new, &events, &[0..3, 4..6], 50);
+    assert_eq!(hash, replay(TimerView::new, &events).state_hash());
+
+    // Writes to distinct paths commute.
+    let writes = vec![write("/x", b"1"), write("/y", b"2"), write("/z/w", b"3")];
+    let all = 0..writes.len();
+    assert_order_insensitive(FileSystemView::new, &writes, &[all], 20);
+}
+
+#[test]
+#[should_panic(expected = "diverged from permutation 0")]
+fn t3_order_sensitive_groups_are_caught() {
+    // The latest NTP sample wins, so NTP samples do not commute.
+    let events = vec![
+        make_clock_event(ClockSource::Ntp, 1_000, 5),
+        make_clock_event(ClockSource::Ntp, 2_000, 5),
+    ];
+    let both = 0..2;
+    assert_order_insensitive(
+        || ClockView::new(ClockPolicyId::TrustNtpLatest),
+        &events,
+        &[both],
+        2,
+    );
+}
+
+#[test]
+#[should_panic(expected = "overlap")]
+fn t4_overlapping_groups_are_rejected() {
+    assert_order_insensitive(TimerView::new, &timer_worldline(), &[0..3, 2..4], 2);
+}
+
+#[test]
+fn t5_permutations_are_deterministic() {
+    let items: Vec<u32> = (0..8).collect();
+    let perms = permutations(&items, 10);
+    assert_eq!(perms, permutations(&items, 10));
+    assert_eq!(perms[0], items);
+    assert_eq!(perms[1], items.iter().rev().copied().collect::<Vec<_>>());
+    for perm in &perms {
+        let mut sorted = perm.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, items);
+    }
+}
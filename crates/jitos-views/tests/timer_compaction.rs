@@ -0,0 +1,103 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! Timer View Compaction Tests
+//!
+//! Compaction drops history but must never change what is pending.
+
+mod common;
+
+use common::{make_clock_event, make_timer_fire, make_timer_request};
+use jitos_core::{events::EventEnvelope, Hash};
+use jitos_views::{ClockPolicyId, ClockSource, ClockView, Time, TimerView};
+
+/// Ten timers; the even ones fire. Positions: requests 0..10, fires 10..15.
+fn worldline() -> Vec<EventEnvelope> {
+    let requests: Vec<_> = (0..10u8)
+        .map(|i| make_timer_request([i; 32], 1_000 * u64::from(i), 0))
+        .collect();
+    let fires: Vec<_> = (0..10u8)
+        .step_by(2)
+        .map(|i| {
+            make_timer_fire(
+                [i; 32],
+                1_000 * u64::from(i),
+                requests[i as usize].event_id(),
+            )
+        })
+        .collect();
+    requests.into_iter().chain(fires).collect()
+}
+
+fn fold(events: &[EventEnvelope]) -> TimerView {
+    let mut view = TimerView::new();
+    for event in events {
+        view.apply_event(event).expect("apply event");
+    }
+    view
+}
+
+fn at(ns: u64) -> Time {
+    let mut clock = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    clock
+        .apply_event(&make_clock_event(ClockSource::Monotonic, ns, 0))
+        .expect("apply clock event");
+    clock.now().clone()
+}
+
+#[test]
+fn k1_compaction_preserves_pending_timers() {
+    let events = worldline();
+    let full = fold(&events);
+    let mut compacted = full.clone();
+
+    assert_eq!(compacted.compacted_digest(), Hash([0u8; 32]));
+    assert_eq!(compacted.compact(events.len()), 10, "5 requests + 5 fires");
+    assert_eq!(compacted.requests().count(), 5, "unfired requests stay");
+    assert_eq!(compacted.fired().count(), 0);
+    assert_ne!(compacted.compacted_digest(), Hash([0u8; 32]));
+
+    for ns in [0, 4_500, 9_000, u64::MAX] {
+        assert_eq!(
+            compacted.pending_timers(&at(ns)),
+            full.pending_timers(&at(ns))
+        );
+    }
+    assert_eq!(compacted.next_fire_time(), full.next_fire_time());
+}
+
+#[test]
+fn k2_only_records_before_the_cut_are_dropped() {
+    let events = worldline();
+    let mut view = fold(&events);
+
+    // Cut after the first two fires: every fired request goes (all requests
+    // precede the cut), but only the first two fire records do.
+    assert_eq!(view.compact(12), 5 + 2);
+    let ids: Vec<_> = view.fired().map(|r| r.fire.request_id.0[0]).collect();
+    assert_eq!(ids, vec![4, 6, 8]);
+
+    let once = view.compacted_digest();
+    assert_eq!(view.compact(12), 0, "nothing new to drop");
+    assert_eq!(view.compacted_digest(), once);
+    assert_eq!(view.compact(events.len()), 3);
+    assert_ne!(view.compacted_digest(), once);
+}
+
+#[test]
+fn k3_compaction_is_deterministic_and_keeps_fired_ids() {
+    let events = worldline();
+    let mut a = fold(&events);
+    let mut b = fold(&events);
+    a.compact(events.len());
+    b.compact(events.len());
+    assert_eq!(a.compacted_digest(), b.compacted_digest());
+
+    // A late duplicate of a fired (and compacted) request stays fired.
+    let late = make_timer_request([0u8; 32], 0, 0);
+    a.apply_event(&late).expect("apply request");
+    assert!(a
+        .pending_timers(&at(u64::MAX))
+        .iter()
+        .all(|r| r.request.request_id != Hash([0u8; 32])));
+}
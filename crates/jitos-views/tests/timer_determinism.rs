@@ -9,8 +9,8 @@
 
 mod common;
 
-use common::{make_clock_event, make_timer_request};
-use jitos_views::{ClockPolicyId, ClockSource, ClockView, TimerView};
+use common::{make_clock_event, make_timer_fire, make_timer_request};
+use jitos_views::{ClockPolicyId, ClockSource, ClockView, FireSemantics, TimerView};
 
 // ============================================================================
 // T1: Basic Timer Request Processing
@@ -183,3 +183,182 @@ fn t3_multiple_timers() {
     assert!(ids.contains(&jitos_core::Hash([2u8; 32])), "timer 2 ready");
     assert!(ids.contains(&jitos_core::Hash([3u8; 32])), "timer 3 ready");
 }
+
+// ============================================================================
+// T4: Interval Semantics
+// ============================================================================
+
+#[test]
+fn t4_interval_semantics_choose_conservative_or_eager_firing() {
+    // Scenario: time is 6s ± 1ms and the timer fires at 6s + 500µs
+    let mut timer_view = TimerView::new();
+    let mut clock_view = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    timer_view
+        .apply_event(&make_timer_request([1u8; 32], 5_000_500_000, 1_000_000_000))
+        .expect("apply timer request");
+    clock_view
+        .apply_event(&make_clock_event(
+            ClockSource::Monotonic,
+            6_000_000_000,
+            1_000_000,
+        ))
+        .expect("apply clock event");
+
+    let now = clock_view.now();
+    assert_eq!(now.earliest_ns(), 5_999_000_000);
+    assert_eq!(now.latest_ns(), 6_001_000_000);
+
+    // Then: it is possibly due, but neither definitely nor point-due
+    let due = |semantics| timer_view.due_timers(now, semantics).len();
+    assert_eq!(due(FireSemantics::Possibly), 1, "eager firing");
+    assert_eq!(due(FireSemantics::Point), 0);
+    assert_eq!(due(FireSemantics::Definitely), 0, "conservative firing");
+    assert_eq!(timer_view.pending_timers(now).len(), 0, "point semantics");
+
+    // When: the earliest plausible time passes the fire time
+    clock_view
+        .apply_event(&make_clock_event(
+            ClockSource::Monotonic,
+            6_001_500_000,
+            1_000_000,
+        ))
+        .expect("apply clock event");
+    assert_eq!(
+        timer_view
+            .due_timers(clock_view.now(), FireSemantics::Definitely)
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn t4_unknown_time_is_possibly_anything() {
+    let mut timer_view = TimerView::new();
+    timer_view
+        .apply_event(&make_timer_request([1u8; 32], 5_000_000_000, 1_000_000_000))
+        .expect("apply timer request");
+    let unknown = jitos_views::Time::unknown();
+    assert_eq!((unknown.earliest_ns(), unknown.latest_ns()), (0, u64::MAX));
+    assert_eq!(
+        timer_view
+            .due_timers(&unknown, FireSemantics::Possibly)
+            .len(),
+        1
+    );
+    assert!(timer_view
+        .due_timers(&unknown, FireSemantics::Definitely)
+        .is_empty());
+}
+
+// ============================================================================
+// T5: Ordering and Next Deadline
+// ============================================================================
+
+#[test]
+fn t5_pending_timers_are_sorted_by_fire_time_then_id() {
+    // Given: requests applied out of fire-time order, two with equal fire times
+    let requests = [
+        make_timer_request([9u8; 32], 1_000, 0), // fires at 1000
+        make_timer_request([3u8; 32], 500, 500), // fires at 1000
+        make_timer_request([7u8; 32], 200, 0),   // fires at 200
+    ];
+    let mut forward = TimerView::new();
+    let mut backward = TimerView::new();
+    for event in &requests {
+        forward.apply_event(event).expect("apply request");
+    }
+    for event in requests.iter().rev() {
+        backward.apply_event(event).expect("apply request");
+    }
+
+    let mut clock_view = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    clock_view
+        .apply_event(&make_clock_event(ClockSource::Monotonic, 5_000, 0))
+        .expect("apply clock event");
+
+    // Then: (fire_time, request_id) order regardless of application order
+    let pending = forward.pending_timers(clock_view.now());
+    let ids: Vec<_> = pending.iter().map(|r| r.request.request_id.0[0]).collect();
+    assert_eq!(ids, vec![7, 3, 9]);
+    assert_eq!(pending, backward.pending_timers(clock_view.now()));
+}
+
+#[test]
+fn t5_next_fire_time_tracks_outstanding_timers() {
+    let mut timer_view = TimerView::new();
+    assert_eq!(timer_view.next_fire_time(), None, "nothing outstanding");
+
+    let early = make_timer_request([1u8; 32], 1_000, 0);
+    let late = make_timer_request([2u8; 32], 5_000, 0);
+    timer_view.apply_event(&late).expect("apply request");
+    timer_view.apply_event(&early).expect("apply request");
+    assert_eq!(timer_view.next_fire_time(), Some(1_000));
+
+    // When: the early timer fires, the next deadline moves on
+    timer_view
+        .apply_event(&make_timer_fire([1u8; 32], 1_000, early.event_id()))
+        .expect("apply fire");
+    assert_eq!(timer_view.next_fire_time(), Some(5_000));
+
+    timer_view
+        .apply_event(&make_timer_fire([2u8; 32], 5_000, late.event_id()))
+        .expect("apply fire");
+    assert_eq!(timer_view.next_fire_time(), None);
+}
+
+// ============================================================================
+// T6: Large Timer Counts
+// ============================================================================
+
+#[test]
+fn t6_indexed_pending_matches_a_naive_scan() {
+    // Given: many timers with colliding fire times, a duplicate request and
+    // a fire that arrives before its request
+    let mut timer_view = TimerView::new();
+    let mut requests = Vec::new();
+    for i in 0..5_000u32 {
+        let id = {
+            let mut id = [0u8; 32];
+            id[..4].copy_from_slice(&i.to_be_bytes());
+            id
+        };
+        let event = make_timer_request(id, u64::from(i % 97) * 1_000, u64::from(i % 13));
+        timer_view.apply_event(&event).expect("apply request");
+        if i % 3 == 0 {
+            timer_view
+                .apply_event(&make_timer_fire(id, 0, event.event_id()))
+                .expect("apply fire");
+        } else {
+            requests.push(event);
+        }
+    }
+    let duplicate = make_timer_request([0xee; 32], 10, 0);
+    timer_view.apply_event(&duplicate).expect("apply request");
+    timer_view.apply_event(&duplicate).expect("apply duplicate");
+    let early_fire = make_timer_fire([0xdd; 32], 0, duplicate.event_id());
+    timer_view.apply_event(&early_fire).expect("apply fire");
+    timer_view
+        .apply_event(&make_timer_request([0xdd; 32], 0, 0))
+        .expect("apply already-fired request");
+
+    let mut clock_view = ClockView::new(ClockPolicyId::TrustMonotonicLatest);
+    clock_view
+        .apply_event(&make_clock_event(ClockSource::Monotonic, 40_000, 0))
+        .expect("apply clock event");
+
+    // Then: the indexed query agrees with filtering and sorting everything
+    let pending = timer_view.pending_timers(clock_view.now());
+    let mut expected: Vec<_> = requests
+        .iter()
+        .chain([&duplicate, &duplicate])
+        .map(|e| e.payload().to_value::<jitos_views::TimerRequest>().unwrap())
+        .filter(|r| r.fire_time_ns() <= 40_000)
+        .collect();
+    expected.sort_by_key(|r| (r.fire_time_ns(), r.request_id));
+    let got: Vec<_> = pending.into_iter().map(|r| r.request).collect();
+    assert_eq!(got, expected);
+    assert_eq!(
+        timer_view.next_fire_time(),
+        expected.first().map(|r| r.fire_time_ns())
+    );
+}
@@ -8,39 +8,9 @@
 
 mod common;
 
-use common::{make_clock_event, make_timer_request};
-use jitos_core::events::{CanonicalBytes, EventEnvelope};
-use jitos_views::{ClockPolicyId, ClockSource, ClockView, TimerFire, TimerView};
-
-/// Helper: Create a timer fire decision event
-fn make_timer_fire(
-    request_id: [u8; 32],
-    fired_at_ns: u64,
-    request_event_id: jitos_core::Hash,
-) -> EventEnvelope {
-    let fire = TimerFire {
-        request_id: jitos_core::Hash(request_id),
-        fired_at_ns,
-    };
-
-    // Create a dummy policy context to use as parent
-    let policy = EventEnvelope::new_policy_context(
-        CanonicalBytes::from_value(&"timer_policy".to_string()).expect("encode policy"),
-        vec![],
-        None,
-        None,
-    )
-    .expect("create policy event");
-
-    EventEnvelope::new_decision(
-        CanonicalBytes::from_value(&fire).expect("encode fire"),
-        vec![request_event_id], // Use timer request as evidence
-        policy.event_id(),
-        None,
-        None,
-    )
-    .expect("create timer fire event")
-}
+use common::{make_clock_event, make_timer_fire, make_timer_request};
+use jitos_core::Hash;
+use jitos_views::{ClockPolicyId, ClockSource, ClockView, TimerError, TimerView};
 
 // ============================================================================
 // T1: Fired Timers Don't Appear in Pending
@@ -221,3 +191,63 @@ fn t4_event_order_independence() {
     assert!(ids2.contains(&jitos_core::Hash([1u8; 32])));
     assert!(ids2.contains(&jitos_core::Hash([2u8; 32])));
 }
+
+// ============================================================================
+// T6: Strict Fire Validation
+// ============================================================================
+
+#[test]
+fn t6_strict_mode_rejects_unknown_and_early_fires() {
+    let request = make_timer_request([1u8; 32], 5_000, 1_000); // due at 6000
+    let mut strict = TimerView::strict();
+    assert!(strict.is_strict());
+    strict.apply_event(&request).expect("apply request");
+
+    // Unknown request id
+    let unknown = make_timer_fire([9u8; 32], 6_000, request.event_id());
+    assert_eq!(
+        strict.apply_event(&unknown),
+        Err(TimerError::UnknownRequest {
+            fire: unknown.event_id(),
+            request_id: Hash([9u8; 32]),
+        })
+    );
+
+    // Fired before the due time
+    let early = make_timer_fire([1u8; 32], 5_999, request.event_id());
+    assert_eq!(
+        strict.apply_event(&early),
+        Err(TimerError::EarlyFire {
+            fire: early.event_id(),
+            request_id: Hash([1u8; 32]),
+            fired_at_ns: 5_999,
+            due_ns: 6_000,
+        })
+    );
+
+    // Rejected fires are not indexed: the timer is still pending
+    assert_eq!(strict.fired().count(), 0);
+    assert_eq!(strict.next_fire_time(), Some(6_000));
+
+    // A valid fire is accepted, and a repeat of it too
+    let valid = make_timer_fire([1u8; 32], 6_000, request.event_id());
+    strict.apply_event(&valid).expect("valid fire");
+    strict.apply_event(&valid).expect("repeat fire");
+    assert_eq!(strict.next_fire_time(), None);
+}
+
+#[test]
+fn t6_lenient_mode_indexes_every_fire() {
+    let request = make_timer_request([1u8; 32], 5_000, 1_000);
+    let mut lenient = TimerView::new();
+    assert!(!lenient.is_strict());
+    lenient.apply_event(&request).expect("apply request");
+    lenient
+        .apply_event(&make_timer_fire([9u8; 32], 0, request.event_id()))
+        .expect("unknown fire accepted");
+    lenient
+        .apply_event(&make_timer_fire([1u8; 32], 0, request.event_id()))
+        .expect("early fire accepted");
+    assert_eq!(lenient.fired().count(), 2);
+    assert_eq!(lenient.next_fire_time(), None);
+}
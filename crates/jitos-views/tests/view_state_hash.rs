@@ -0,0 +1,97 @@
+// Copyright 2025 James Ross
+// SPDX-License-Identifier: Apache-2.0
+
+//! View State Hash Tests
+//!
+//! State hashes digest what a view answers, not how it stores it.
+
+mod common;
+
+use common::{make_clock_event, make_timer_fire, make_timer_request};
+use jitos_core::events::EventEnvelope;
+use jitos_views::{ClockPolicyId, ClockSource, ClockView, TimerView};
+
+fn clock_hash(policy: ClockPolicyId, events: &[EventEnvelope]) -> jitos_core::Hash {
+    let mut view = ClockView::new(policy);
+    for event in events {
+        view.apply_event(event).unwrap();
+    }
+    view.state_hash()
+}
+
+fn timers(events: &[EventEnvelope]) -> TimerView {
+    let mut view = TimerView::new();
+    for event in events {
+        view.apply_event(event).unwrap();
+    }
+    view
+}
+
+#[test]
+fn t1_clock_state_hash_tracks_semantic_state() {
+    let events = vec![
+        make_clock_event(ClockSource::Monotonic, 1_000, 10),
+        make_clock_event(ClockSource::Ntp, 5_000, 100),
+        make_clock_event(ClockSource::Monotonic, 2_000, 10),
+    ];
+    let policy = ClockPolicyId::HybridMonotonicNtp;
+
+    assert_eq!(clock_hash(policy, &events), clock_hash(policy, &events));
+    assert_ne!(
+        clock_hash(policy, &events),
+        clock_hash(policy, &events[..2])
+    );
+    assert_ne!(
+        clock_hash(policy, &events),
+        clock_hash(ClockPolicyId::TrustNtpLatest, &events)
+    );
+
+    // A superseded sample leaves no trace once the latest samples match.
+    let superseded = [
+        vec![make_clock_event(ClockSource::Rtc, 9, 9)],
+        events.clone(),
+        vec![make_clock_event(ClockSource::Rtc, 7_000, 50)],
+    ]
+    .concat();
+    let direct = [
+        events.clone(),
+        vec![make_clock_event(ClockSource::Rtc, 7_000, 50)],
+    ]
+    .concat();
+    assert_eq!(
+        clock_hash(ClockPolicyId::TrustNtpLatest, &superseded),
+        clock_hash(ClockPolicyId::TrustNtpLatest, &direct)
+    );
+}
+
+#[test]
+fn t2_timer_state_hash_tracks_pending_and_fired() {
+    let request = make_timer_request([1u8; 32], 100, 1_000);
+    let other = make_timer_request([2u8; 32], 50, 1_000);
+    let fire = make_timer_fire([1u8; 32], 1_100, request.event_id());
+
+    let both = timers(&[request.clone(), other.clone()]);
+    let reordered = timers(&[other.clone(), request.clone()]);
+    assert_eq!(both.state_hash(), reordered.state_hash());
+    assert_ne!(both.state_hash(), TimerView::new().state_hash());
+
+    let fired = timers(&[request.clone(), other.clone(), fire]);
+    assert_ne!(fired.state_hash(), both.state_hash());
+    // The fired id is state: it differs from never having requested timer 1.
+    assert_ne!(
+        fired.state_hash(),
+        timers(std::slice::from_ref(&other)).state_hash()
+    );
+}
+
+#[test]
+fn t3_timer_compaction_preserves_state_hash() {
+    let request = make_timer_request([1u8; 32], 100, 1_000);
+    let fire = make_timer_fire([1u8; 32], 1_100, request.event_id());
+    let later = make_timer_request([2u8; 32], 100, 2_000);
+
+    let mut view = timers(&[request, fire, later]);
+    let before = view.state_hash();
+    assert_eq!(view.compact(3), 2);
+    assert_eq!(view.state_hash(), before);
+}
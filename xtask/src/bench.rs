@@ -0,0 +1,303 @@
+//! `cargo xtask bench`: run the criterion benchmarks and collect them as JSON.
+//!
+//! Runs the `benches/` of jitos-core (canonical encode/decode, event ids,
+//! `validate_store`), jitos-graph (`WarpGraph::compute_hash`) and jitos-views
+//! (view replay), then reads what criterion measured in this run from
+//! `target/criterion` and writes one report (default `target/bench.json`):
+//!
+//! ```text
+//! { "version": "loom-bench-v0",
+//!   "benchmarks": [ { "id": "canonical/encode/16", "mean_ns": ..., "median_ns": ...,
+//!                     "std_dev_ns": ..., "throughput": { "unit": "bytes",
+//!                     "per_iteration": 412, "per_second": ... } }, ... ] }
+//! ```
+//!
+//! With `--baseline <report>` every benchmark present in both reports is
+//! compared by mean time, and the command fails if any got slower by more
+//! than `--threshold` percent (default 10).
+//!
+//! Options: `[FILTER] [--quick] [--out PATH] [--baseline PATH] [--threshold PCT]`.
+//! `FILTER` and `--quick` are passed to criterion.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Format tag of bench reports.
+const BENCH_REPORT_V0: &str = "loom-bench-v0";
+
+/// Criterion bench targets, by crate. Named explicitly: `--benches` would
+/// also run the libtest harness of each library, which rejects criterion's
+/// options.
+const BENCH_TARGETS: &[(&str, &str)] = &[
+    ("jitos-core", "core"),
+    ("jitos-graph", "graph"),
+    ("jitos-views", "replay"),
+];
+
+const DEFAULT_THRESHOLD_PCT: f64 = 10.0;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BenchReport {
+    version: String,
+    benchmarks: Vec<BenchResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BenchResult {
+    /// Criterion's `group/function/parameter` id.
+    id: String,
+    mean_ns: f64,
+    median_ns: f64,
+    std_dev_ns: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    throughput: Option<BenchThroughput>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BenchThroughput {
+    /// `bytes` or `elements`.
+    unit: String,
+    per_iteration: u64,
+    per_second: f64,
+}
+
+/// The parts of criterion's `new/benchmark.json` we read.
+#[derive(Deserialize)]
+struct CriterionBenchmark {
+    full_id: String,
+    throughput: Option<CriterionThroughput>,
+}
+
+#[derive(Deserialize)]
+enum CriterionThroughput {
+    Bytes(u64),
+    BytesDecimal(u64),
+    Elements(u64),
+}
+
+/// The parts of criterion's `new/estimates.json` we read.
+#[derive(Deserialize)]
+struct CriterionEstimates {
+    mean: Estimate,
+    median: Estimate,
+    std_dev: Estimate,
+}
+
+#[derive(Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+struct Options {
+    filter: Option<String>,
+    quick: bool,
+    out: PathBuf,
+    baseline: Option<PathBuf>,
+    threshold_pct: f64,
+}
+
+fn parse(mut args: impl Iterator<Item = String>) -> Result<Options> {
+    let mut options = Options {
+        filter: None,
+        quick: false,
+        out: target_dir().join("bench.json"),
+        baseline: None,
+        threshold_pct: DEFAULT_THRESHOLD_PCT,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().with_context(|| format!("{flag} needs a value"));
+        match arg.as_str() {
+            "--quick" => options.quick = true,
+            "--out" => options.out = value("--out")?.into(),
+            "--baseline" => options.baseline = Some(value("--baseline")?.into()),
+            "--threshold" => {
+                let pct = value("--threshold")?;
+                options.threshold_pct = pct
+                    .parse()
+                    .with_context(|| format!("bad --threshold `{pct}`"))?;
+            }
+            flag if flag.starts_with("--") => bail!(
+                "unknown bench option: {flag} (try: [FILTER] --quick --out --baseline --threshold)"
+            ),
+            filter if options.filter.is_none() => options.filter = Some(filter.to_string()),
+            extra => bail!("unexpected bench argument: {extra}"),
+        }
+    }
+    Ok(options)
+}
+
+pub fn bench(args: impl Iterator<Item = String>) -> Result<()> {
+    let options = parse(args)?;
+    let started = SystemTime::now();
+
+    let mut cargo = vec!["bench".to_string()];
+    for (krate, target) in BENCH_TARGETS {
+        cargo.extend(["-p", krate, "--bench", target].map(String::from));
+    }
+    cargo.push("--".to_string());
+    cargo.extend(options.filter.clone());
+    if options.quick {
+        cargo.push("--quick".to_string());
+    }
+    super::run("cargo", &cargo)?;
+
+    let report = collect(&target_dir().join("criterion"), started)?;
+    if report.benchmarks.is_empty() {
+        bail!("no benchmarks ran (filter: {:?})", options.filter);
+    }
+    if let Some(parent) = options.out.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    std::fs::write(
+        &options.out,
+        format!("{:#}\n", serde_json::to_value(&report)?),
+    )
+    .with_context(|| format!("failed to write {}", options.out.display()))?;
+
+    println!();
+    for b in &report.benchmarks {
+        let throughput = b
+            .throughput
+            .as_ref()
+            .map(|t| format!("  {:.3e} {}/s", t.per_second, t.unit))
+            .unwrap_or_default();
+        println!("{:<40} {:>14.1} ns{throughput}", b.id, b.mean_ns);
+    }
+    println!(
+        "bench: {} results in {}",
+        report.benchmarks.len(),
+        options.out.display()
+    );
+
+    if let Some(path) = &options.baseline {
+        compare(&load(path)?, &report, options.threshold_pct)?;
+    }
+    Ok(())
+}
+
+fn target_dir() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target"))
+}
+
+fn load(path: &Path) -> Result<BenchReport> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let report: BenchReport = serde_json::from_str(&text)
+        .with_context(|| format!("malformed bench report {}", path.display()))?;
+    if report.version != BENCH_REPORT_V0 {
+        bail!(
+            "{}: unsupported bench report format {}",
+            path.display(),
+            report.version
+        );
+    }
+    Ok(report)
+}
+
+/// Results criterion wrote at or after `since`, sorted by id.
+fn collect(dir: &Path, since: SystemTime) -> Result<BenchReport> {
+    let mut found = Vec::new();
+    find_new_estimates(dir, &mut found)?;
+
+    let mut benchmarks = Vec::new();
+    for new in found {
+        let estimates_path = new.join("estimates.json");
+        let modified = std::fs::metadata(&estimates_path)?.modified()?;
+        if modified < since {
+            continue;
+        }
+        let benchmark: CriterionBenchmark = read_json(&new.join("benchmark.json"))?;
+        let estimates: CriterionEstimates = read_json(&estimates_path)?;
+        let mean_ns = estimates.mean.point_estimate;
+        let throughput = benchmark.throughput.map(|t| {
+            let (unit, per_iteration) = match t {
+                CriterionThroughput::Bytes(n) | CriterionThroughput::BytesDecimal(n) => {
+                    ("bytes", n)
+                }
+                CriterionThroughput::Elements(n) => ("elements", n),
+            };
+            BenchThroughput {
+                unit: unit.to_string(),
+                per_iteration,
+                per_second: per_iteration as f64 * 1e9 / mean_ns,
+            }
+        });
+        benchmarks.push(BenchResult {
+            id: benchmark.full_id,
+            mean_ns,
+            median_ns: estimates.median.point_estimate,
+            std_dev_ns: estimates.std_dev.point_estimate,
+            throughput,
+        });
+    }
+    benchmarks.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(BenchReport {
+        version: BENCH_REPORT_V0.to_string(),
+        benchmarks,
+    })
+}
+
+/// Every `new/` directory holding criterion's latest estimates.
+fn find_new_estimates(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().is_some_and(|name| name == "new")
+            && path.join("estimates.json").exists()
+        {
+            out.push(path);
+        } else {
+            find_new_estimates(&path, out)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("malformed {}", path.display()))
+}
+
+/// Print the change in mean time of every benchmark in both reports; fail
+/// if any slowed down by more than `threshold_pct`.
+fn compare(baseline: &BenchReport, current: &BenchReport, threshold_pct: f64) -> Result<()> {
+    let mut regressions = Vec::new();
+    println!();
+    for b in &current.benchmarks {
+        let Some(base) = baseline.benchmarks.iter().find(|base| base.id == b.id) else {
+            println!("{:<40} new", b.id);
+            continue;
+        };
+        let change = (b.mean_ns / base.mean_ns - 1.0) * 100.0;
+        let verdict = if change > threshold_pct {
+            regressions.push(format!("{} {change:+.1}%", b.id));
+            "REGRESSED"
+        } else {
+            ""
+        };
+        println!("{:<40} {change:>+7.1}%  {verdict}", b.id);
+    }
+    if !regressions.is_empty() {
+        bail!(
+            "bench: {} regressions over {threshold_pct}%:\n  {}",
+            regressions.len(),
+            regressions.join("\n  ")
+        );
+    }
+    println!("bench: no regressions over {threshold_pct}%");
+    Ok(())
+}
@@ -0,0 +1,412 @@
+//! `cargo xtask conformance`: recompute golden vectors with the real implementation.
+//!
+//! Vectors are JSON files next to the tests of the crate whose rules they pin:
+//!
+//! - SPEC-0001 canonical encodings: values that must encode to exact bytes (and
+//!   round-trip), values that must be refused, and byte strings the strict
+//!   decoder must reject with a given error at a given offset;
+//! - event ids: payload bytes and `H(kind || payload || sorted_parents)`;
+//! - SPEC-WARP-0001 graph commit digests: edge ids and the digest of small
+//!   graphs, listed in arbitrary insertion order.
+//!
+//! Any difference is reported as a structured mismatch (suite, vector, field,
+//! expected, actual and the first differing byte for byte strings), as text or
+//! with `--json` as a JSON array, and the command fails. Changing a canonical
+//! rule therefore means changing these vectors in the same commit.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use jitos_core::canonical::{self, CanonicalError};
+use jitos_core::events::{CanonicalBytes, EventEnvelope, EventKind};
+use jitos_core::{Hash, NodeId};
+use jitos_graph::{WarpEdge, WarpGraph, WarpNode};
+use serde::{Deserialize, Serialize};
+
+const CANONICAL_VECTORS: &str = "crates/jitos-core/tests/vectors/canonical.json";
+const EVENT_ID_VECTORS: &str = "crates/jitos-core/tests/vectors/event_ids.json";
+const GRAPH_VECTORS: &str = "crates/jitos-graph/tests/vectors/graph_commit.json";
+
+/// A CBOR data model value as written in vector files.
+///
+/// Integers and floats are strings so every value (u64::MAX, -2^64, NaN,
+/// -0.0) survives JSON. Map entries keep the order written, which need not
+/// be canonical.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Value {
+    Int(String),
+    Float(String),
+    Text(String),
+    Bytes(String),
+    Bool(bool),
+    Null,
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Tag(u64, Box<Value>),
+}
+
+impl Value {
+    fn to_cbor(&self) -> Result<ciborium::Value> {
+        use ciborium::Value as V;
+        Ok(match self {
+            Value::Int(n) => {
+                let n: i128 = n.parse().with_context(|| format!("bad int `{n}`"))?;
+                V::Integer(
+                    n.try_into()
+                        .map_err(|_| anyhow!("int {n} out of CBOR range"))?,
+                )
+            }
+            Value::Float(x) => V::Float(x.parse().with_context(|| format!("bad float `{x}`"))?),
+            Value::Text(text) => V::Text(text.clone()),
+            Value::Bytes(bytes) => V::Bytes(hex_bytes(bytes)?),
+            Value::Bool(b) => V::Bool(*b),
+            Value::Null => V::Null,
+            Value::Array(items) => {
+                V::Array(items.iter().map(Value::to_cbor).collect::<Result<_>>()?)
+            }
+            Value::Map(entries) => V::Map(
+                entries
+                    .iter()
+                    .map(|(k, v)| Ok((k.to_cbor()?, v.to_cbor()?)))
+                    .collect::<Result<_>>()?,
+            ),
+            Value::Tag(tag, inner) => V::Tag(*tag, Box::new(inner.to_cbor()?)),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CanonicalVectors {
+    encode: Vec<EncodeVector>,
+    reject: Vec<RejectVector>,
+}
+
+/// `value` encodes to `cbor`, or fails with `error`.
+#[derive(Debug, Deserialize)]
+struct EncodeVector {
+    name: String,
+    value: Value,
+    #[serde(default)]
+    cbor: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// The strict decoder rejects `cbor` with `error` at byte `offset`.
+#[derive(Debug, Deserialize)]
+struct RejectVector {
+    name: String,
+    cbor: String,
+    error: String,
+    offset: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventIdVector {
+    name: String,
+    kind: String,
+    payload: Value,
+    /// Sorted ascending, as envelopes store them.
+    parents: Vec<String>,
+    payload_cbor: String,
+    event_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphVector {
+    name: String,
+    nodes: Vec<NodeVector>,
+    edges: Vec<EdgeVector>,
+    digest: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeVector {
+    id: String,
+    #[serde(rename = "type")]
+    node_type: String,
+    payload: String,
+    #[serde(default)]
+    attachment: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EdgeVector {
+    from: String,
+    to: String,
+    #[serde(rename = "type")]
+    edge_type: String,
+    #[serde(default)]
+    payload: Option<String>,
+    #[serde(default)]
+    attachment: Option<String>,
+    id: String,
+}
+
+/// One field of one vector that the implementation disagrees with.
+#[derive(Debug, Serialize)]
+struct Mismatch {
+    suite: &'static str,
+    vector: String,
+    field: String,
+    expected: String,
+    actual: String,
+    /// First differing byte, when both sides are hex byte strings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_difference: Option<usize>,
+}
+
+/// Vectors checked and mismatches found.
+#[derive(Default)]
+struct Report {
+    vectors: usize,
+    mismatches: Vec<Mismatch>,
+}
+
+impl Report {
+    fn check(
+        &mut self,
+        suite: &'static str,
+        vector: &str,
+        field: &str,
+        expected: &str,
+        actual: &str,
+    ) {
+        if expected == actual {
+            return;
+        }
+        let first_difference = match (hex::decode(expected), hex::decode(actual)) {
+            (Ok(expected), Ok(actual)) => Some(
+                expected
+                    .iter()
+                    .zip(&actual)
+                    .position(|(e, a)| e != a)
+                    .unwrap_or(expected.len().min(actual.len())),
+            ),
+            _ => None,
+        };
+        self.mismatches.push(Mismatch {
+            suite,
+            vector: vector.to_string(),
+            field: field.to_string(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+            first_difference,
+        });
+    }
+}
+
+pub fn conformance(args: impl Iterator<Item = String>) -> Result<()> {
+    let mut json = false;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            other => bail!("unknown conformance option: {other} (try: --json)"),
+        }
+    }
+
+    let mut report = Report::default();
+    canonical_suite(&load(CANONICAL_VECTORS)?, &mut report)?;
+    event_id_suite(&load::<Vec<_>>(EVENT_ID_VECTORS)?, &mut report)?;
+    graph_suite(&load::<Vec<_>>(GRAPH_VECTORS)?, &mut report)?;
+
+    if json {
+        println!("{:#}", serde_json::to_value(&report.mismatches)?);
+    } else {
+        for m in &report.mismatches {
+            println!("MISMATCH {}/{}  {}", m.suite, m.vector, m.field);
+            println!("  expected {}", m.expected);
+            println!("  actual   {}", m.actual);
+            if let Some(offset) = m.first_difference {
+                println!("  first difference at byte {offset}");
+            }
+        }
+    }
+    if !report.mismatches.is_empty() {
+        bail!(
+            "conformance: {} mismatches in {} vectors",
+            report.mismatches.len(),
+            report.vectors
+        );
+    }
+    if !json {
+        println!("conformance: ok ({} vectors)", report.vectors);
+    }
+    Ok(())
+}
+
+fn load<T: for<'de> Deserialize<'de>>(path: &str) -> Result<T> {
+    let text = std::fs::read_to_string(Path::new(path))
+        .with_context(|| format!("failed to read {path}"))?;
+    serde_json::from_str(&text).with_context(|| format!("malformed vector file {path}"))
+}
+
+fn hex_bytes(text: &str) -> Result<Vec<u8>> {
+    hex::decode(text).with_context(|| format!("`{text}` is not hex"))
+}
+
+fn hash(text: &str) -> Result<Hash> {
+    let bytes: [u8; 32] = hex_bytes(text)?
+        .try_into()
+        .map_err(|_| anyhow!("`{text}` is not a 32-byte hash"))?;
+    Ok(Hash(bytes))
+}
+
+/// Variant name of a canonical error, without its detail.
+fn error_name(error: &CanonicalError) -> String {
+    let debug = format!("{error:?}");
+    debug.split('(').next().unwrap_or_default().to_string()
+}
+
+fn canonical_suite(vectors: &CanonicalVectors, report: &mut Report) -> Result<()> {
+    const SUITE: &str = "canonical";
+    for v in &vectors.encode {
+        report.vectors += 1;
+        let value = v
+            .value
+            .to_cbor()
+            .with_context(|| format!("vector {}", v.name))?;
+        let encoded = canonical::encode(&value);
+        match (&v.cbor, &v.error) {
+            (Some(cbor), None) => {
+                let actual = match &encoded {
+                    Ok(bytes) => hex::encode(bytes),
+                    Err(e) => format!("error {}", error_name(e)),
+                };
+                report.check(SUITE, &v.name, "cbor", cbor, &actual);
+                // Canonical bytes decode strictly and re-encode unchanged.
+                let bytes = hex_bytes(cbor).with_context(|| format!("vector {}", v.name))?;
+                let round_trip = canonical::decode::<ciborium::Value>(&bytes)
+                    .and_then(|value| canonical::encode(&value))
+                    .map_or_else(|e| format!("error {}", error_name(&e)), hex::encode);
+                report.check(SUITE, &v.name, "round_trip", cbor, &round_trip);
+            }
+            (None, Some(error)) => {
+                let actual = match &encoded {
+                    Ok(bytes) => hex::encode(bytes),
+                    Err(e) => error_name(e),
+                };
+                report.check(SUITE, &v.name, "error", error, &actual);
+            }
+            _ => bail!("vector {} needs exactly one of `cbor` and `error`", v.name),
+        }
+    }
+    for v in &vectors.reject {
+        report.vectors += 1;
+        let bytes = hex_bytes(&v.cbor).with_context(|| format!("vector {}", v.name))?;
+        let (error, offset) = match canonical::check(&bytes) {
+            Ok(()) => ("accepted".to_string(), "-".to_string()),
+            Err(violation) => (error_name(&violation.error), violation.offset.to_string()),
+        };
+        report.check(SUITE, &v.name, "error", &v.error, &error);
+        report.check(SUITE, &v.name, "offset", &v.offset.to_string(), &offset);
+    }
+    Ok(())
+}
+
+fn event_id_suite(vectors: &[EventIdVector], report: &mut Report) -> Result<()> {
+    const SUITE: &str = "event_id";
+    for v in vectors {
+        report.vectors += 1;
+        let context = || format!("vector {}", v.name);
+        let kind = match v.kind.as_str() {
+            "Observation" => EventKind::Observation,
+            "PolicyContext" => EventKind::PolicyContext,
+            "Decision" => EventKind::Decision,
+            "Commit" => EventKind::Commit,
+            other => bail!("vector {}: unknown event kind {other}", v.name),
+        };
+        let parents = v
+            .parents
+            .iter()
+            .map(|p| hash(p))
+            .collect::<Result<Vec<_>>>()
+            .with_context(context)?;
+        ensure!(
+            parents.windows(2).all(|w| w[0] < w[1]),
+            "vector {}: parents must be sorted and distinct",
+            v.name
+        );
+
+        let payload = CanonicalBytes::from_value(&v.payload.to_cbor().with_context(context)?)
+            .with_context(context)?;
+        report.check(
+            SUITE,
+            &v.name,
+            "payload_cbor",
+            &v.payload_cbor,
+            &hex::encode(payload.as_bytes()),
+        );
+        let id =
+            EventEnvelope::compute_event_id(&kind, &payload, &parents).with_context(context)?;
+        report.check(SUITE, &v.name, "event_id", &v.event_id, &id.to_string());
+    }
+    Ok(())
+}
+
+fn graph_suite(vectors: &[GraphVector], report: &mut Report) -> Result<()> {
+    const SUITE: &str = "graph_commit";
+    for v in vectors {
+        report.vectors += 1;
+        let context = || format!("vector {}", v.name);
+        let mut graph = WarpGraph::new();
+        let mut keys = HashMap::new();
+        for node in &v.nodes {
+            let id = NodeId::from_hash(hash(&node.id).with_context(context)?);
+            let key = graph.nodes.insert(WarpNode {
+                id,
+                node_type: node.node_type.clone(),
+                payload_bytes: hex_bytes(&node.payload).with_context(context)?,
+                attachment: node
+                    .attachment
+                    .as_deref()
+                    .map(hash)
+                    .transpose()
+                    .with_context(context)?,
+            });
+            keys.insert(id, key);
+        }
+        for (index, edge) in v.edges.iter().enumerate() {
+            let endpoint = |hex: &str| -> Result<(NodeId, _)> {
+                let id = NodeId::from_hash(hash(hex)?);
+                let key = *keys
+                    .get(&id)
+                    .ok_or_else(|| anyhow!("edge endpoint {hex} is not a node"))?;
+                Ok((id, key))
+            };
+            let (from, source) = endpoint(&edge.from).with_context(context)?;
+            let (to, target) = endpoint(&edge.to).with_context(context)?;
+            let warp_edge = WarpEdge {
+                source,
+                target,
+                edge_type: edge.edge_type.clone(),
+                payload_bytes: edge
+                    .payload
+                    .as_deref()
+                    .map(hex_bytes)
+                    .transpose()
+                    .with_context(context)?,
+                attachment: edge
+                    .attachment
+                    .as_deref()
+                    .map(hash)
+                    .transpose()
+                    .with_context(context)?,
+            };
+            let id = jitos_graph::edge_id(from, to, &warp_edge).with_context(context)?;
+            report.check(
+                SUITE,
+                &v.name,
+                &format!("edges[{index}].id"),
+                &edge.id,
+                &id.to_string(),
+            );
+            graph.edges.insert(warp_edge);
+        }
+        let digest = graph.compute_hash_checked().with_context(context)?;
+        report.check(SUITE, &v.name, "digest", &v.digest, &digest.to_string());
+    }
+    Ok(())
+}
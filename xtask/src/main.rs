@@ -1,12 +1,16 @@
+use std::path::Path;
 use std::process::Command;
 
 use anyhow::{anyhow, bail, Context, Result};
 
+mod bench;
+mod conformance;
+
 fn main() -> Result<()> {
     let mut args = std::env::args().skip(1);
     let cmd = args
         .next()
-        .ok_or_else(|| anyhow!("missing command (try: `cargo run -p xtask -- roadmap-dags`)"))?;
+        .ok_or_else(|| anyhow!("missing command (try: `cargo xtask roadmap-dags`)"))?;
 
     match cmd.as_str() {
         "roadmap-dags" => {
@@ -19,6 +23,15 @@ fn main() -> Result<()> {
             // version-controlled hooks in `.githooks/`.
             run("git", &["config".into(), "core.hooksPath".into(), ".githooks".into()])?;
         }
+        "forbidden-encoders" => {
+            forbidden_encoders()?;
+        }
+        "bench" => {
+            bench::bench(args)?;
+        }
+        "conformance" => {
+            conformance::conformance(args)?;
+        }
         other => {
             bail!("unknown xtask command: {other}");
         }
@@ -39,3 +52,170 @@ fn run(bin: &str, args: &[String]) -> Result<()> {
     Ok(())
 }
 
+/// Crates whose outputs feed hashes, receipts, or the worldline.
+const DETERMINISM_CRATES: &[&str] = &[
+    "crates/jitos-core",
+    "crates/jitos-graph",
+    "crates/jitos-scheduler",
+    "crates/jitos-views",
+];
+
+/// Fail if a determinism-critical crate depends on or uses serde_json outside tests.
+///
+/// Canonical CBOR (`jitos_core::canonical`) is the only permitted encoder there;
+/// clippy's `disallowed-methods` (see `clippy.toml`) covers the call sites, this
+/// covers the dependency itself.
+fn forbidden_encoders() -> Result<()> {
+    let mut offenders = Vec::new();
+
+    for krate in DETERMINISM_CRATES {
+        let manifest_path = Path::new(krate).join("Cargo.toml");
+        let manifest = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+        let mut section = String::new();
+        for line in manifest.lines() {
+            let line = line.trim();
+            if line.starts_with('[') {
+                section = line.to_string();
+            } else if section == "[dependencies]" && line.starts_with("serde_json") {
+                offenders.push(format!(
+                    "{}: serde_json in [dependencies]",
+                    manifest_path.display()
+                ));
+            }
+        }
+
+        let mut sources = Vec::new();
+        collect_rs(&Path::new(krate).join("src"), &mut sources)?;
+        sources.sort();
+        for path in sources {
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            for (n, line) in encoder_lines(&text) {
+                offenders.push(format!("{}:{}: {}", path.display(), n, line.trim()));
+            }
+        }
+    }
+
+    if !offenders.is_empty() {
+        bail!(
+            "non-canonical encoder usage in determinism-critical crates:\n  {}",
+            offenders.join("\n  ")
+        );
+    }
+    println!("forbidden-encoders: ok");
+    Ok(())
+}
+
+/// Lines (1-based) that mention serde_json outside `#[cfg(test)]` items.
+///
+/// Only the item the attribute annotates is skipped: a `mod tests { … }` block,
+/// a braced item such as a struct or fn, or a single item ending in `;`.
+/// Scanning resumes on the line after it closes.
+fn encoder_lines(text: &str) -> Vec<(usize, &str)> {
+    let mut found = Vec::new();
+    // Brace depth of the test-only item being skipped, and whether its body has opened.
+    let mut skipping: Option<(usize, bool)> = None;
+
+    for (n, line) in text.lines().enumerate() {
+        let mut code = line.split("//").next().unwrap_or_default();
+        if skipping.is_none() {
+            match code.trim_start().strip_prefix("#[cfg(test)]") {
+                Some(rest) => {
+                    skipping = Some((0, false));
+                    code = rest;
+                }
+                None => {
+                    if code.contains("serde_json") {
+                        found.push((n + 1, line));
+                    }
+                    continue;
+                }
+            }
+        }
+
+        let Some((mut depth, mut opened)) = skipping else {
+            continue;
+        };
+        let mut done = false;
+        for c in code.chars() {
+            match c {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth = depth.saturating_sub(1),
+                ';' if depth == 0 => done = true,
+                _ => {}
+            }
+            if done || (opened && depth == 0) {
+                break;
+            }
+        }
+        skipping = if done || (opened && depth == 0) {
+            None
+        } else {
+            Some((depth, opened))
+        };
+    }
+    found
+}
+
+fn collect_rs(dir: &Path, out: &mut Vec<std::path::PathBuf>) -> Result<()> {
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_rs(&path, out)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encoder_lines;
+
+    const FIXTURE: &str = r#"use serde::Serialize;
+
+#[cfg(test)]
+#[derive(Serialize)]
+struct CommitV0 {
+    nodes: Vec<u8>,
+}
+
+#[cfg(test)] use serde_json as _;
+
+fn digest() -> Vec<u8> {
+    serde_json::to_vec(&1).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    fn nested() {
+        if true {
+            let _ = serde_json::to_vec(&1);
+        }
+    }
+}
+
+fn after_tests() {
+    let _ = serde_json::json!({}); // serde_json in a comment is fine
+}
+"#;
+
+    #[test]
+    fn test_only_items_are_skipped_and_scanning_resumes_after_them() {
+        let found: Vec<usize> = encoder_lines(FIXTURE).into_iter().map(|(n, _)| n).collect();
+        assert_eq!(found, vec![12, 25]);
+    }
+
+    #[test]
+    fn a_test_only_struct_does_not_hide_the_rest_of_the_file() {
+        let src = "#[cfg(test)]\nstruct Probe;\nuse serde_json::Value;\n";
+        assert_eq!(encoder_lines(src), vec![(3, "use serde_json::Value;")]);
+    }
+}